@@ -247,9 +247,11 @@ impl<S: HasStateApi> State<S> {
                     )
                 }
                 BidIncrement::Percentage(increment) => {
+                    let minimum = Percentage::HUNDRED
+                        .checked_add(increment)
+                        .ok_or(CustomContractError::Overflow)?;
                     ensure!(
-                        Percentage::from_percent(100) + increment
-                            <= Percentage::of_amount(amount, bid.amount),
+                        minimum <= Percentage::of_amount(amount, bid.amount),
                         CustomContractError::BidTooLow
                     )
                 }
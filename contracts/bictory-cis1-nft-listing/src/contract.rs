@@ -1,5 +1,7 @@
 use commons_v1::{
-    AuthorityUpdateParams, AuthorityViewParams, CustomContractError, Percentage, Royalty, Token,
+    supports_standards, AuthorityUpdateParams, AuthorityViewParams, CustomContractError,
+    Percentage, Royalty, StandardIdentifier, SupportsQueryParams, SupportsQueryResponse, Token,
+    BICTORY_AUTHORITY_STANDARD_IDENTIFIER, CIS0_STANDARD_IDENTIFIER,
 };
 use concordium_cis1::{OnReceivingCis1Params, TokenIdVec};
 use concordium_std::*;
@@ -9,6 +11,12 @@ use crate::external::*;
 use crate::nft;
 use crate::state::{ListingData, State};
 
+/// Standards supported by this contract.
+const SUPPORTED_STANDARDS: [StandardIdentifier<'static>; 2] = [
+    CIS0_STANDARD_IDENTIFIER,
+    BICTORY_AUTHORITY_STANDARD_IDENTIFIER,
+];
+
 /// Initialize the listing contract with an empty list of listings.
 #[init(contract = "BictoryNftListing", parameter = "InitParams")]
 fn init<S: HasStateApi>(
@@ -70,7 +78,7 @@ fn list<S: HasStateApi>(
     if royalties
         .iter()
         .fold(platform_fee, |acc, x| acc + x.percentage)
-        > Percentage::from_percent(100)
+        > Percentage::HUNDRED
     {
         bail!(CustomContractError::InvalidRoyalty.into());
     }
@@ -238,6 +246,24 @@ fn view_authority<S: HasStateApi>(
     Ok(host.state().authority.handle_view(params))
 }
 
+/// Check which standards the contract supports (CIS-0).
+///
+/// It rejects if:
+/// - It fails to parse the parameter.
+#[receive(
+    contract = "BictoryNftListing",
+    name = "supports",
+    parameter = "SupportsQueryParams",
+    return_value = "SupportsQueryResponse"
+)]
+fn supports<S: HasStateApi>(
+    ctx: &impl HasReceiveContext,
+    _host: &impl HasHost<State<S>, StateApiType = S>,
+) -> ReceiveResult<SupportsQueryResponse> {
+    let params = SupportsQueryParams::deserial(&mut ctx.parameter_cursor())?;
+    Ok(supports_standards(&SUPPORTED_STANDARDS, params))
+}
+
 /// Function to update values required for internal contract functionality. This includes:
 /// - Royalty. fee percentage for token sale. Gets assigned to a token on mint.
 /// - Beneficiary. Account address that receives the fee.
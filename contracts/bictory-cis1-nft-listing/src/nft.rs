@@ -1,4 +1,4 @@
-use commons_v1::{CustomContractError, Royalty, Token};
+use commons_v1::{BoundedVec, CustomContractError, Royalty, Token, MAX_ROYALTIES};
 use concordium_cis1::{AdditionalData, Receiver, TokenIdVec, Transfer};
 use concordium_std::*;
 
@@ -43,7 +43,9 @@ pub fn get_royalties<T>(
         .map_err(handle_call_error)?
         .ok_or(CustomContractError::Incompatible)?;
 
-    <Vec<Royalty>>::deserial(&mut response).map_err(|_| CustomContractError::Incompatible.into())
+    <BoundedVec<Royalty, MAX_ROYALTIES>>::deserial(&mut response)
+        .map(BoundedVec::into_inner)
+        .map_err(|_| CustomContractError::Incompatible.into())
 }
 
 fn handle_call_error<R>(error: CallContractError<R>) -> Reject {
@@ -61,6 +63,7 @@ mod tests {
     use commons_v1::{GetRoyaltiesParams, GetRoyaltiesResponse, Percentage};
     use concordium_cis1::{TokenIdVec, TransferParams};
     use concordium_std::test_infrastructure::*;
+    use core::convert::TryFrom;
 
     use super::*;
 
@@ -116,10 +119,11 @@ mod tests {
                 Ok((
                     false,
                     GetRoyaltiesResponse {
-                        royalties: vec![Royalty {
+                        royalties: BoundedVec::try_from(vec![Royalty {
                             beneficiary: USER_1,
                             percentage: Percentage::from_percent(2),
-                        }],
+                        }])
+                        .unwrap(),
                     },
                 ))
             }),
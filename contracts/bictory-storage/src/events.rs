@@ -0,0 +1,127 @@
+use commons::{
+    Bytes, CustomContractError, StorageKeySelection, STORAGE_INSERTED_TAG, STORAGE_REMOVED_TAG,
+    STORAGE_UPDATED_TAG,
+};
+use concordium_std::*;
+use core::convert::TryFrom;
+
+/// Count and hash of the affected keys. Logging the keys themselves could exceed the 512 byte event limit, so
+/// indexers match the hash against the keys in the transaction parameter instead.
+#[derive(Debug, PartialEq, Eq)]
+pub struct KeysDigest {
+    pub count: u16,
+    /// SHA-256 of the key list serialized with a u16 length, same as in `StorageKeySelection`
+    pub hash: HashSha2256,
+}
+
+impl KeysDigest {
+    /// Results in `InvalidFields` if there are more keys than fit into the u16 count.
+    pub fn new<'a>(
+        keys: impl ExactSizeIterator<Item = &'a Bytes>,
+        crypto_primitives: &impl HasCryptoPrimitives,
+    ) -> Result<Self, CustomContractError> {
+        let count = u16::try_from(keys.len()).map_err(|_| CustomContractError::InvalidFields)?;
+        let mut data = to_bytes(&count);
+        for key in keys {
+            key.serial(&mut data).unwrap_abort();
+        }
+        Ok(KeysDigest {
+            count,
+            hash: crypto_primitives.hash_sha2_256(&data),
+        })
+    }
+}
+
+/// Tagged storage event to be serialized for the event log. Prefixes are logged as the SHA-256 of their serialized
+/// form, so every event stays well below the 512 byte limit.
+#[derive(Debug, PartialEq, Eq)]
+pub enum StorageEvent {
+    /// New prefix was created with the given keys
+    Inserted {
+        prefix: HashSha2256,
+        keys: KeysDigest,
+    },
+    /// Values of the given keys were written under an existing prefix
+    Updated {
+        prefix: HashSha2256,
+        keys: KeysDigest,
+    },
+    /// Keys were removed from the prefix. `None` means all keys were selected and the prefix itself was removed
+    Removed {
+        prefix: HashSha2256,
+        keys: Option<KeysDigest>,
+    },
+}
+
+impl StorageEvent {
+    pub fn inserted<'a>(
+        prefix: &Bytes,
+        keys: impl ExactSizeIterator<Item = &'a Bytes>,
+        crypto_primitives: &impl HasCryptoPrimitives,
+    ) -> Result<Self, CustomContractError> {
+        Ok(StorageEvent::Inserted {
+            prefix: prefix_hash(prefix, crypto_primitives),
+            keys: KeysDigest::new(keys, crypto_primitives)?,
+        })
+    }
+
+    pub fn updated<'a>(
+        prefix: &Bytes,
+        keys: impl ExactSizeIterator<Item = &'a Bytes>,
+        crypto_primitives: &impl HasCryptoPrimitives,
+    ) -> Result<Self, CustomContractError> {
+        Ok(StorageEvent::Updated {
+            prefix: prefix_hash(prefix, crypto_primitives),
+            keys: KeysDigest::new(keys, crypto_primitives)?,
+        })
+    }
+
+    pub fn removed(
+        prefix: &Bytes,
+        keys: &StorageKeySelection,
+        crypto_primitives: &impl HasCryptoPrimitives,
+    ) -> Result<Self, CustomContractError> {
+        Ok(StorageEvent::Removed {
+            prefix: prefix_hash(prefix, crypto_primitives),
+            keys: match keys {
+                StorageKeySelection::All => None,
+                StorageKeySelection::Some(key_list) => {
+                    Some(KeysDigest::new(key_list.iter(), crypto_primitives)?)
+                }
+            },
+        })
+    }
+}
+
+impl Serial for KeysDigest {
+    fn serial<W: Write>(&self, out: &mut W) -> Result<(), W::Err> {
+        out.write_u16(self.count)?;
+        self.hash.0.serial(out)
+    }
+}
+
+fn prefix_hash(prefix: &Bytes, crypto_primitives: &impl HasCryptoPrimitives) -> HashSha2256 {
+    crypto_primitives.hash_sha2_256(&to_bytes(prefix))
+}
+
+impl Serial for StorageEvent {
+    fn serial<W: Write>(&self, out: &mut W) -> Result<(), W::Err> {
+        match self {
+            StorageEvent::Inserted { prefix, keys } => {
+                out.write_u8(STORAGE_INSERTED_TAG)?;
+                prefix.0.serial(out)?;
+                keys.serial(out)
+            }
+            StorageEvent::Updated { prefix, keys } => {
+                out.write_u8(STORAGE_UPDATED_TAG)?;
+                prefix.0.serial(out)?;
+                keys.serial(out)
+            }
+            StorageEvent::Removed { prefix, keys } => {
+                out.write_u8(STORAGE_REMOVED_TAG)?;
+                prefix.0.serial(out)?;
+                keys.serial(out)
+            }
+        }
+    }
+}
@@ -1,14 +1,20 @@
 #![no_std]
 
 use commons::{
-    AuthorityUpdateParams, AuthorityViewParams, CustomContractError, MaybeStorageEntry,
-    StorageEntries, StorageGetEntryResult, StorageKeySelection, StorageKeys,
+    AuthorityUpdateParams, AuthorityViewParams, Bytes, Capability, CapabilityUpdateParams,
+    CustomContractError, ReplaceOperatorsParams, SetExpiryParams, StorageEntries,
+    StorageGetEntryResult, StorageKeySelection, StorageKeys, StoragePage, StoragePageParams,
+    StorageProvenanceResult,
 };
 use concordium_std::*;
 
+mod events;
 mod state;
 
-use state::State;
+use events::StorageEvent;
+use state::{
+    encode_value, PrefixExport, State, StorageStats, EXPORT_FORMAT_VERSION, VALUE_ENVELOPE_VERSION,
+};
 
 #[init(contract = "BictoryStorage")]
 fn init<S: HasStateApi>(
@@ -22,11 +28,15 @@ fn init<S: HasStateApi>(
     mutable,
     contract = "BictoryStorage",
     name = "insert",
-    parameter = "StorageEntries"
+    parameter = "StorageEntries",
+    enable_logger,
+    crypto_primitives
 )]
 fn insert<S: HasStateApi>(
     ctx: &impl HasReceiveContext,
     host: &mut impl HasHost<State<S>, StateApiType = S>,
+    logger: &mut impl HasLogger,
+    crypto_primitives: &impl HasCryptoPrimitives,
 ) -> ReceiveResult<()> {
     let (state, builder) = host.state_and_builder();
 
@@ -37,29 +47,35 @@ fn insert<S: HasStateApi>(
 
     let params = StorageEntries::deserial(&mut ctx.parameter_cursor())?;
 
-    match state.storage.entry(params.prefix) {
-        Entry::Vacant(hole) => {
-            let mut map = builder.new_map();
-            for entry in params.entries {
-                map.insert(entry.key, entry.value);
-            }
-            hole.insert(map);
+    ensure!(
+        state.storage.get(&params.prefix).is_none(),
+        CustomContractError::AlreadyExists.into()
+    );
 
-            Ok(())
-        }
-        Entry::Occupied(_) => Err(CustomContractError::AlreadyExists.into()),
-    }
+    let event = StorageEvent::inserted(
+        &params.prefix,
+        params.entries.iter().map(|e| &e.key),
+        crypto_primitives,
+    )?;
+    state.insert_prefix(builder, ctx.sender(), params);
+    logger.log(&event)?;
+
+    Ok(())
 }
 
 #[receive(
     mutable,
     contract = "BictoryStorage",
     name = "update",
-    parameter = "StorageEntries"
+    parameter = "StorageEntries",
+    enable_logger,
+    crypto_primitives
 )]
 fn update<S: HasStateApi>(
     ctx: &impl HasReceiveContext,
     host: &mut impl HasHost<State<S>, StateApiType = S>,
+    logger: &mut impl HasLogger,
+    crypto_primitives: &impl HasCryptoPrimitives,
 ) -> ReceiveResult<()> {
     let state = host.state_mut();
 
@@ -70,26 +86,83 @@ fn update<S: HasStateApi>(
 
     let params = StorageEntries::deserial(&mut ctx.parameter_cursor())?;
 
-    match state.storage.entry(params.prefix) {
-        Entry::Occupied(mut map) => {
-            for entry in params.entries {
-                map.insert(entry.key, entry.value);
-            }
-            Ok(())
-        }
-        Entry::Vacant(_) => Err(CustomContractError::NotFound.into()),
-    }
+    ensure!(
+        state.has_prefix_rights(&params.prefix, &ctx.sender()),
+        CustomContractError::Unauthorized.into()
+    );
+
+    let event = StorageEvent::updated(
+        &params.prefix,
+        params.entries.iter().map(|e| &e.key),
+        crypto_primitives,
+    )?;
+    ensure!(
+        state.update_prefix(ctx.sender(), params),
+        CustomContractError::NotFound.into()
+    );
+    logger.log(&event)?;
+
+    Ok(())
+}
+
+/// Insert entries under the prefix, creating the prefix if it's missing. Values of existing keys are overwritten.
+#[receive(
+    mutable,
+    contract = "BictoryStorage",
+    name = "upsert",
+    parameter = "StorageEntries",
+    enable_logger,
+    crypto_primitives
+)]
+fn upsert<S: HasStateApi>(
+    ctx: &impl HasReceiveContext,
+    host: &mut impl HasHost<State<S>, StateApiType = S>,
+    logger: &mut impl HasLogger,
+    crypto_primitives: &impl HasCryptoPrimitives,
+) -> ReceiveResult<()> {
+    let (state, builder) = host.state_and_builder();
+
+    ensure!(
+        state.has_writer_rights(&ctx.sender()),
+        CustomContractError::Unauthorized.into()
+    );
+
+    let params = StorageEntries::deserial(&mut ctx.parameter_cursor())?;
+
+    // Missing prefixes have no owner, so this only restricts merging into an existing prefix
+    ensure!(
+        state.has_prefix_rights(&params.prefix, &ctx.sender()),
+        CustomContractError::Unauthorized.into()
+    );
+
+    let keys = params.entries.iter().map(|e| &e.key);
+    let event = if state.storage.get(&params.prefix).is_some() {
+        let event = StorageEvent::updated(&params.prefix, keys, crypto_primitives)?;
+        state.update_prefix(ctx.sender(), params);
+        event
+    } else {
+        let event = StorageEvent::inserted(&params.prefix, keys, crypto_primitives)?;
+        state.insert_prefix(builder, ctx.sender(), params);
+        event
+    };
+    logger.log(&event)?;
+
+    Ok(())
 }
 
 #[receive(
     mutable,
     contract = "BictoryStorage",
     name = "remove",
-    parameter = "StorageKeys"
+    parameter = "StorageKeys",
+    enable_logger,
+    crypto_primitives
 )]
 fn remove<S: HasStateApi>(
     ctx: &impl HasReceiveContext,
     host: &mut impl HasHost<State<S>, StateApiType = S>,
+    logger: &mut impl HasLogger,
+    crypto_primitives: &impl HasCryptoPrimitives,
 ) -> ReceiveResult<()> {
     let state = host.state_mut();
 
@@ -99,21 +172,57 @@ fn remove<S: HasStateApi>(
     );
 
     let params = StorageKeys::deserial(&mut ctx.parameter_cursor())?;
-    match params.keys {
-        StorageKeySelection::All => state
-            .storage
-            .remove_and_get(&params.prefix)
-            .map(|v| v.delete())
-            .ok_or(CustomContractError::NotFound)?,
-        StorageKeySelection::Some(key_list) => match state.storage.get_mut(&params.prefix) {
-            Some(mut map) => {
-                for key in key_list {
-                    map.remove(&key);
-                }
-            }
-            None => Err(CustomContractError::NotFound)?,
-        },
-    }
+
+    ensure!(
+        state.has_prefix_rights(&params.prefix, &ctx.sender()),
+        CustomContractError::Unauthorized.into()
+    );
+
+    let found = match &params.keys {
+        StorageKeySelection::All => state.remove_prefix(&params.prefix),
+        StorageKeySelection::Some(key_list) => state.remove_keys(&params.prefix, key_list),
+    };
+    ensure!(found, CustomContractError::NotFound.into());
+
+    logger.log(&StorageEvent::removed(
+        &params.prefix,
+        &params.keys,
+        crypto_primitives,
+    )?)?;
+
+    Ok(())
+}
+
+/// Replace all operators of the prefix, e.g. when the token stored under the prefix changes hands, so that operators
+/// of the previous owner can't modify its data anymore. Operators can modify data under the prefix like its owner, but
+/// only the owner and global writers can replace them.
+#[receive(
+    mutable,
+    contract = "BictoryStorage",
+    name = "replaceOperators",
+    parameter = "ReplaceOperatorsParams"
+)]
+fn replace_operators<S: HasStateApi>(
+    ctx: &impl HasReceiveContext,
+    host: &mut impl HasHost<State<S>, StateApiType = S>,
+) -> ReceiveResult<()> {
+    let state = host.state_mut();
+
+    ensure!(
+        state.has_writer_rights(&ctx.sender()),
+        CustomContractError::Unauthorized.into()
+    );
+
+    let params = ReplaceOperatorsParams::deserial(&mut ctx.parameter_cursor())?;
+
+    ensure!(
+        state.has_prefix_owner_rights(&params.prefix, &ctx.sender()),
+        CustomContractError::Unauthorized.into()
+    );
+    ensure!(
+        state.replace_operators(params.prefix, params.operators),
+        CustomContractError::NotFound.into()
+    );
 
     Ok(())
 }
@@ -128,36 +237,253 @@ fn get<S: HasStateApi>(
     ctx: &impl HasReceiveContext,
     host: &impl HasHost<State<S>, StateApiType = S>,
 ) -> ReceiveResult<Option<StorageGetEntryResult>> {
-    let state = host.state();
     let params = StorageKeys::deserial(&mut ctx.parameter_cursor())?;
+    Ok(host.state().get_entries(params, ctx.metadata().slot_time()))
+}
 
-    let result = state.storage.get(&params.prefix).map(|map| {
-        let entries = match params.keys {
-            StorageKeySelection::All => map.iter().fold(vec![], |mut acc, (key, value)| {
-                acc.push(MaybeStorageEntry {
-                    key: key.clone(),
-                    value: Some(value.clone()),
-                });
-                acc
-            }),
-            StorageKeySelection::Some(key_list) => key_list
-                .into_iter()
-                .map(|key| {
-                    let value = map.get(&key);
-                    MaybeStorageEntry {
-                        key,
-                        value: value.map(|v| v.clone()),
-                    }
-                })
-                .collect(),
-        };
-        StorageGetEntryResult {
-            prefix: params.prefix,
-            entries,
+/// Same as `get`, but every value is returned together with the writer that last modified it, so consumers can reject
+/// data written by an unexpected contract. Values written before writers were recorded have no provenance.
+#[receive(
+    contract = "BictoryStorage",
+    name = "getWithProvenance",
+    parameter = "StorageKeys",
+    return_value = "Option<StorageProvenanceResult>"
+)]
+fn get_with_provenance<S: HasStateApi>(
+    ctx: &impl HasReceiveContext,
+    host: &impl HasHost<State<S>, StateApiType = S>,
+) -> ReceiveResult<Option<StorageProvenanceResult>> {
+    let params = StorageKeys::deserial(&mut ctx.parameter_cursor())?;
+    Ok(host
+        .state()
+        .get_attested_entries(params, ctx.metadata().slot_time()))
+}
+
+#[receive(
+    contract = "BictoryStorage",
+    name = "getBatch",
+    parameter = "Vec<StorageKeys>",
+    return_value = "Vec<Option<StorageGetEntryResult>>"
+)]
+fn get_batch<S: HasStateApi>(
+    ctx: &impl HasReceiveContext,
+    host: &impl HasHost<State<S>, StateApiType = S>,
+) -> ReceiveResult<Vec<Option<StorageGetEntryResult>>> {
+    let state = host.state();
+    let params = <Vec<StorageKeys>>::deserial(&mut ctx.parameter_cursor())?;
+
+    Ok(params
+        .into_iter()
+        .map(|keys| state.get_entries(keys, ctx.metadata().slot_time()))
+        .collect())
+}
+
+/// Walk entries of a prefix page by page. Entries are ordered by serialized keys in ascending byte order, i.e. the key
+/// length as `u16` in little endian followed by the key bytes, so the order is the same for every call and doesn't
+/// depend on the order of writes. Pass `next_key` of the returned page as `start_after_key` to get the next page,
+/// `next_key` is `None` on the last page. Rejects with `InvalidFields` if `limit` is zero.
+#[receive(
+    contract = "BictoryStorage",
+    name = "getPage",
+    parameter = "StoragePageParams",
+    return_value = "Option<StoragePage>"
+)]
+fn get_page<S: HasStateApi>(
+    ctx: &impl HasReceiveContext,
+    host: &impl HasHost<State<S>, StateApiType = S>,
+) -> ReceiveResult<Option<StoragePage>> {
+    let params = StoragePageParams::deserial(&mut ctx.parameter_cursor())?;
+    ensure!(params.limit > 0, CustomContractError::InvalidFields.into());
+    Ok(host.state().get_page(params, ctx.metadata().slot_time()))
+}
+
+/// Set or clear time after which data under the prefix is considered stale. Expired data is not removed, but `get`
+/// flags it as expired.
+#[receive(
+    mutable,
+    contract = "BictoryStorage",
+    name = "setExpiry",
+    parameter = "SetExpiryParams"
+)]
+fn set_expiry<S: HasStateApi>(
+    ctx: &impl HasReceiveContext,
+    host: &mut impl HasHost<State<S>, StateApiType = S>,
+) -> ReceiveResult<()> {
+    let state = host.state_mut();
+
+    ensure!(
+        state.has_writer_rights(&ctx.sender()),
+        CustomContractError::Unauthorized.into()
+    );
+
+    let params = SetExpiryParams::deserial(&mut ctx.parameter_cursor())?;
+
+    ensure!(
+        state.has_prefix_rights(&params.prefix, &ctx.sender()),
+        CustomContractError::Unauthorized.into()
+    );
+    ensure!(
+        state.storage.get(&params.prefix).is_some(),
+        CustomContractError::NotFound.into()
+    );
+
+    match params.expiry {
+        Some(expiry) => {
+            state.expiries.insert(params.prefix, expiry);
         }
-    });
+        None => {
+            state.expiries.remove(&params.prefix);
+        }
+    }
 
-    Ok(result)
+    Ok(())
+}
+
+#[receive(
+    contract = "BictoryStorage",
+    name = "isExpired",
+    parameter = "Bytes",
+    return_value = "bool"
+)]
+fn is_expired<S: HasStateApi>(
+    ctx: &impl HasReceiveContext,
+    host: &impl HasHost<State<S>, StateApiType = S>,
+) -> ReceiveResult<bool> {
+    let prefix = Bytes::deserial(&mut ctx.parameter_cursor())?;
+    let state = host.state();
+
+    ensure!(
+        state.storage.get(&prefix).is_some(),
+        CustomContractError::NotFound.into()
+    );
+
+    Ok(state.is_expired(&prefix, ctx.metadata().slot_time()))
+}
+
+/// Rewrite all values under given legacy prefixes into the current value envelope. Large storages are expected to be
+/// migrated in several calls, each with a batch of prefixes. Already migrated prefixes are skipped.
+#[receive(
+    mutable,
+    contract = "BictoryStorage",
+    name = "reencodePrefix",
+    parameter = "Vec<Bytes>"
+)]
+fn reencode_prefix<S: HasStateApi>(
+    ctx: &impl HasReceiveContext,
+    host: &mut impl HasHost<State<S>, StateApiType = S>,
+) -> ReceiveResult<()> {
+    let state = host.state_mut();
+
+    ensure!(
+        state
+            .authority
+            .has_capability(&ctx.sender(), Capability::CanConfigure),
+        CustomContractError::Unauthorized.into()
+    );
+
+    let prefixes = <Vec<Bytes>>::deserial(&mut ctx.parameter_cursor())?;
+
+    for prefix in prefixes {
+        if state.value_version(&prefix).is_some() {
+            continue;
+        }
+
+        let mut map = state
+            .storage
+            .get_mut(&prefix)
+            .ok_or(CustomContractError::NotFound)?;
+        let entries: Vec<(Bytes, Bytes)> = map
+            .iter()
+            .map(|(key, value)| (key.clone(), value.clone()))
+            .collect();
+        for (key, value) in entries {
+            map.insert(key, encode_value(Some(VALUE_ENVELOPE_VERSION), value));
+            // Envelope adds the version byte to every value
+            state.stats.bytes += 1;
+        }
+        drop(map);
+
+        state.value_versions.insert(prefix, VALUE_ENVELOPE_VERSION);
+    }
+
+    Ok(())
+}
+
+/// View the number of prefixes, keys and total stored bytes, to monitor state growth.
+#[receive(
+    contract = "BictoryStorage",
+    name = "stats",
+    return_value = "StorageStats"
+)]
+fn stats<S: HasStateApi>(
+    _ctx: &impl HasReceiveContext,
+    host: &impl HasHost<State<S>, StateApiType = S>,
+) -> ReceiveResult<StorageStats> {
+    Ok(host.state().stats)
+}
+
+/// Export all data under the prefix, so that it can be moved to another storage instance with `importPrefix`.
+#[receive(
+    contract = "BictoryStorage",
+    name = "exportPrefix",
+    parameter = "Bytes",
+    return_value = "PrefixExport"
+)]
+fn export_prefix<S: HasStateApi>(
+    ctx: &impl HasReceiveContext,
+    host: &impl HasHost<State<S>, StateApiType = S>,
+) -> ReceiveResult<PrefixExport> {
+    let prefix = Bytes::deserial(&mut ctx.parameter_cursor())?;
+    let export = host
+        .state()
+        .export_prefix(prefix)
+        .ok_or(CustomContractError::NotFound)?;
+    Ok(export)
+}
+
+/// Recreate a prefix produced by `exportPrefix`. The prefix keeps its exported owner and expiry, while the values are
+/// attested as written by the importer.
+#[receive(
+    mutable,
+    contract = "BictoryStorage",
+    name = "importPrefix",
+    parameter = "PrefixExport",
+    enable_logger,
+    crypto_primitives
+)]
+fn import_prefix<S: HasStateApi>(
+    ctx: &impl HasReceiveContext,
+    host: &mut impl HasHost<State<S>, StateApiType = S>,
+    logger: &mut impl HasLogger,
+    crypto_primitives: &impl HasCryptoPrimitives,
+) -> ReceiveResult<()> {
+    let (state, builder) = host.state_and_builder();
+
+    ensure!(
+        state.has_writer_rights(&ctx.sender()),
+        CustomContractError::Unauthorized.into()
+    );
+
+    let export = PrefixExport::deserial(&mut ctx.parameter_cursor())?;
+
+    ensure!(
+        export.version == EXPORT_FORMAT_VERSION,
+        CustomContractError::InvalidFields.into()
+    );
+    ensure!(
+        state.storage.get(&export.prefix).is_none(),
+        CustomContractError::AlreadyExists.into()
+    );
+
+    let event = StorageEvent::inserted(
+        &export.prefix,
+        export.entries.iter().map(|e| &e.key),
+        crypto_primitives,
+    )?;
+    state.import_prefix(builder, ctx.sender(), export);
+    logger.log(&event)?;
+
+    Ok(())
 }
 
 #[receive(
@@ -190,6 +516,37 @@ fn view_authority<S: HasStateApi>(
     Ok(host.state().authority.handle_view(params))
 }
 
+/// Grant or revoke a single maintainer capability. Only admins are allowed to update capabilities.
+#[receive(
+    mutable,
+    contract = "BictoryStorage",
+    name = "updateCapability",
+    parameter = "CapabilityUpdateParams"
+)]
+fn update_capability<S: HasStateApi>(
+    ctx: &impl HasReceiveContext,
+    host: &mut impl HasHost<State<S>, StateApiType = S>,
+) -> ReceiveResult<()> {
+    let state = host.state_mut();
+    let params = CapabilityUpdateParams::deserial(&mut ctx.parameter_cursor())?;
+    let sender = ctx.sender();
+    state.authority.handle_capability_update(sender, params)
+}
+
+#[receive(
+    contract = "BictoryStorage",
+    name = "viewCapabilities",
+    parameter = "Address",
+    return_value = "Vec<Capability>"
+)]
+fn view_capabilities<S: HasStateApi>(
+    ctx: &impl HasReceiveContext,
+    host: &impl HasHost<State<S>, StateApiType = S>,
+) -> ReceiveResult<Vec<Capability>> {
+    let address = Address::deserial(&mut ctx.parameter_cursor())?;
+    Ok(host.state().authority.handle_capability_view(address))
+}
+
 #[derive(Debug, SchemaType, Serialize)]
 enum UpdateKind {
     Remove,
@@ -216,10 +573,47 @@ fn update_writer<S: HasStateApi>(
     let params = UpdateWriterParams::deserial(&mut ctx.parameter_cursor())?;
 
     ensure!(
-        state.authority.has_maintainer_rights(&ctx.sender()),
+        state
+            .authority
+            .has_capability(&ctx.sender(), Capability::CanManageWriters),
+        CustomContractError::Unauthorized.into()
+    );
+
+    apply_writer_update(state, params);
+
+    Ok(())
+}
+
+/// Add or remove multiple writers in a single call. Updates are applied in order, so the same list can remove retired
+/// writers and add their replacements. Either all updates are applied or none.
+#[receive(
+    mutable,
+    contract = "BictoryStorage",
+    name = "updateWriters",
+    parameter = "Vec<UpdateWriterParams>"
+)]
+fn update_writers<S: HasStateApi>(
+    ctx: &impl HasReceiveContext,
+    host: &mut impl HasHost<State<S>, StateApiType = S>,
+) -> ReceiveResult<()> {
+    let state = host.state_mut();
+    let params = Vec::<UpdateWriterParams>::deserial(&mut ctx.parameter_cursor())?;
+
+    ensure!(
+        state
+            .authority
+            .has_capability(&ctx.sender(), Capability::CanManageWriters),
         CustomContractError::Unauthorized.into()
     );
 
+    for update in params {
+        apply_writer_update(state, update);
+    }
+
+    Ok(())
+}
+
+fn apply_writer_update<S: HasStateApi>(state: &mut State<S>, params: UpdateWriterParams) {
     match params.kind {
         UpdateKind::Remove => {
             state.writers.remove(&params.address);
@@ -228,6 +622,37 @@ fn update_writer<S: HasStateApi>(
             state.writers.insert(params.address);
         }
     }
+}
+
+/// Global writers can modify data under any prefix, including the ones inserted by other writers.
+#[receive(
+    mutable,
+    contract = "BictoryStorage",
+    name = "updateGlobalWriter",
+    parameter = "UpdateWriterParams"
+)]
+fn update_global_writer<S: HasStateApi>(
+    ctx: &impl HasReceiveContext,
+    host: &mut impl HasHost<State<S>, StateApiType = S>,
+) -> ReceiveResult<()> {
+    let state = host.state_mut();
+    let params = UpdateWriterParams::deserial(&mut ctx.parameter_cursor())?;
+
+    ensure!(
+        state
+            .authority
+            .has_capability(&ctx.sender(), Capability::CanManageWriters),
+        CustomContractError::Unauthorized.into()
+    );
+
+    match params.kind {
+        UpdateKind::Remove => {
+            state.global_writers.remove(&params.address);
+        }
+        UpdateKind::Add => {
+            state.global_writers.insert(params.address);
+        }
+    }
 
     Ok(())
 }
@@ -262,11 +687,88 @@ fn view_writers<S: HasStateApi>(
     Ok(result)
 }
 
+#[receive(
+    contract = "BictoryStorage",
+    name = "viewGlobalWriters",
+    parameter = "ViewWritersParams",
+    return_value = "Vec<Address>"
+)]
+fn view_global_writers<S: HasStateApi>(
+    ctx: &impl HasReceiveContext,
+    host: &impl HasHost<State<S>, StateApiType = S>,
+) -> ReceiveResult<Vec<Address>> {
+    let params = ViewWritersParams::deserial(&mut ctx.parameter_cursor())?;
+
+    let result = host
+        .state()
+        .global_writers
+        .iter()
+        .skip(params.skip as usize)
+        .take(params.show as usize)
+        .map(|a| *a)
+        .collect();
+
+    Ok(result)
+}
+
+#[receive(
+    contract = "BictoryStorage",
+    name = "viewPrefixOwner",
+    parameter = "Bytes",
+    return_value = "Option<Address>"
+)]
+fn view_prefix_owner<S: HasStateApi>(
+    ctx: &impl HasReceiveContext,
+    host: &impl HasHost<State<S>, StateApiType = S>,
+) -> ReceiveResult<Option<Address>> {
+    let prefix = Bytes::deserial(&mut ctx.parameter_cursor())?;
+    Ok(host.state().prefix_owners.get(&prefix).map(|owner| *owner))
+}
+
+#[derive(Debug, SchemaType, Serialize)]
+struct ListPrefixesParams {
+    /// Only return prefixes starting with these bytes
+    filter: Option<Bytes>,
+    skip: u32,
+    show: u32,
+}
+
+#[receive(
+    contract = "BictoryStorage",
+    name = "listPrefixes",
+    parameter = "ListPrefixesParams",
+    return_value = "Vec<Bytes>"
+)]
+fn list_prefixes<S: HasStateApi>(
+    ctx: &impl HasReceiveContext,
+    host: &impl HasHost<State<S>, StateApiType = S>,
+) -> ReceiveResult<Vec<Bytes>> {
+    let params = ListPrefixesParams::deserial(&mut ctx.parameter_cursor())?;
+    let filter = params.filter.unwrap_or(Bytes(Vec::new()));
+
+    let result = host
+        .state()
+        .storage
+        .iter()
+        .map(|(prefix, _)| prefix)
+        .filter(|prefix| prefix.starts_with(&filter))
+        .skip(params.skip as usize)
+        .take(params.show as usize)
+        .map(|prefix| prefix.clone())
+        .collect();
+
+    Ok(result)
+}
+
 #[concordium_cfg_test]
 mod tests {
     use super::*;
-    use commons::{AuthorityField, AuthorityUpdateKind, Bytes, StorageEntry};
+    use commons::{
+        AttestedStorageEntry, AuthorityField, AuthorityUpdateKind, MaybeStorageEntry, StorageEntry,
+        StorageProvenance,
+    };
     use concordium_std::test_infrastructure::*;
+    use events::KeysDigest;
 
     const AUTHORIZED_CALLER: ContractAddress = ContractAddress {
         index: 1,
@@ -317,6 +819,37 @@ mod tests {
         host
     }
 
+    /// Hashing is mocked with a simple mix of the bytes
+    fn test_crypto_primitives() -> TestCryptoPrimitives {
+        let mut crypto_primitives = TestCryptoPrimitives::new();
+        crypto_primitives.setup_hash_sha2_256_mock(|data| {
+            let mut hash = [0u8; 32];
+            for (i, byte) in data.iter().enumerate() {
+                hash[i % 32] = hash[i % 32].wrapping_mul(31).wrapping_add(*byte);
+            }
+            HashSha2256(hash)
+        });
+        crypto_primitives
+    }
+
+    /// Read a single value the same way `get` returns it, with the value envelope stripped.
+    fn stored_value(
+        host: &TestHost<State<TestStateApi>>,
+        prefix: Bytes,
+        key: Bytes,
+    ) -> Option<Bytes> {
+        host.state()
+            .get_entries(
+                StorageKeys {
+                    prefix,
+                    keys: StorageKeySelection::Some(vec![key]),
+                },
+                Timestamp::from_timestamp_millis(0),
+            )
+            .and_then(|mut result| result.entries.pop())
+            .and_then(|entry| entry.value)
+    }
+
     #[concordium_test]
     fn test_init_test_state() {
         let host = default_host();
@@ -353,6 +886,8 @@ mod tests {
     #[concordium_test]
     fn test_insert() {
         let mut host = default_host();
+        let mut logger = TestLogger::init();
+        let crypto_primitives = test_crypto_primitives();
 
         // Authorized new insert
         let params = to_bytes(&StorageEntries {
@@ -366,15 +901,33 @@ mod tests {
         ctx.set_parameter(&params)
             .set_sender(Address::Contract(AUTHORIZED_CALLER));
 
-        let result = insert(&ctx, &mut host);
+        let result = insert(&ctx, &mut host, &mut logger, &crypto_primitives);
         claim_eq!(result, Ok(()));
         claim_eq!(
-            host.state()
-                .storage
-                .get(&Bytes::from([0, 0]))
-                .and_then(|map| map.get(&Bytes::from([1, 2, 3])).map(|v| v.clone())),
+            stored_value(&host, Bytes::from([0, 0]), Bytes::from([1, 2, 3])),
             Some(Bytes::from([4, 5, 6]))
         );
+        // Key list is hashed with a u16 length
+        let mut key_list = to_bytes(&1u16);
+        Bytes::from([1, 2, 3]).serial(&mut key_list).unwrap_abort();
+        claim_eq!(
+            logger.logs,
+            vec![to_bytes(&StorageEvent::Inserted {
+                prefix: crypto_primitives.hash_sha2_256(&to_bytes(&Bytes::from([0, 0]))),
+                keys: KeysDigest {
+                    count: 1,
+                    hash: crypto_primitives.hash_sha2_256(&key_list),
+                },
+            })]
+        );
+
+        // Key count has to fit into the u16 count of the digest
+        let keys = vec![Bytes::from([1]); usize::from(u16::MAX) + 1];
+        claim_eq!(
+            KeysDigest::new(keys.iter(), &crypto_primitives),
+            Err(CustomContractError::InvalidFields)
+        );
+        claim!(KeysDigest::new(keys[1..].iter(), &crypto_primitives).is_ok());
 
         // Unauthorized new insert
         let params = to_bytes(&StorageEntries {
@@ -388,7 +941,7 @@ mod tests {
         ctx.set_parameter(&params)
             .set_sender(Address::Contract(UNAUTHORIZED_CALLER));
 
-        let result = insert(&ctx, &mut host);
+        let result = insert(&ctx, &mut host, &mut logger, &crypto_primitives);
         claim_eq!(result, Err(CustomContractError::Unauthorized.into()));
 
         // Authorized duplicate insert
@@ -403,13 +956,44 @@ mod tests {
         ctx.set_parameter(&params)
             .set_sender(Address::Contract(AUTHORIZED_CALLER));
 
-        let result = insert(&ctx, &mut host);
+        let result = insert(&ctx, &mut host, &mut logger, &crypto_primitives);
         claim_eq!(result, Err(CustomContractError::AlreadyExists.into()));
     }
 
+    #[concordium_test]
+    fn test_insert_large_batch_event() {
+        let mut host = default_host();
+        let mut logger = TestLogger::init();
+        let crypto_primitives = test_crypto_primitives();
+
+        let entries: Vec<StorageEntry> = (0..100u8)
+            .map(|i| StorageEntry {
+                key: Bytes::from([i; 16]),
+                value: Bytes::from([i]),
+            })
+            .collect();
+        let params = to_bytes(&StorageEntries {
+            prefix: Bytes(vec![7; 600]),
+            entries,
+        });
+        let mut ctx = TestReceiveContext::default();
+        ctx.set_parameter(&params)
+            .set_sender(Address::Contract(AUTHORIZED_CALLER));
+
+        let result = insert(&ctx, &mut host, &mut logger, &crypto_primitives);
+        claim_eq!(result, Ok(()));
+
+        // The whole batch is summarized in a single event that fits the 512 byte limit
+        claim_eq!(logger.logs.len(), 1);
+        claim!(logger.logs[0].len() <= 512);
+        claim_eq!(&logger.logs[0][33..35], &100u16.to_le_bytes()[..]);
+    }
+
     #[concordium_test]
     fn test_update() {
         let mut host = default_host();
+        let mut logger = TestLogger::init();
+        let crypto_primitives = test_crypto_primitives();
 
         // Authorized missing update
         let params = to_bytes(&StorageEntries {
@@ -423,7 +1007,7 @@ mod tests {
         ctx.set_parameter(&params)
             .set_sender(Address::Contract(AUTHORIZED_CALLER));
 
-        let result = update(&ctx, &mut host);
+        let result = update(&ctx, &mut host, &mut logger, &crypto_primitives);
         claim_eq!(result, Err(CustomContractError::NotFound.into()));
 
         // Insert
@@ -437,7 +1021,10 @@ mod tests {
         let mut ctx = TestReceiveContext::default();
         ctx.set_parameter(&params)
             .set_sender(Address::Contract(AUTHORIZED_CALLER));
-        claim_eq!(insert(&ctx, &mut host), Ok(()));
+        claim_eq!(
+            insert(&ctx, &mut host, &mut logger, &crypto_primitives),
+            Ok(())
+        );
 
         // Authorized existing update
         let params = to_bytes(&StorageEntries {
@@ -451,13 +1038,10 @@ mod tests {
         ctx.set_parameter(&params)
             .set_sender(Address::Contract(AUTHORIZED_CALLER));
 
-        let result = update(&ctx, &mut host);
+        let result = update(&ctx, &mut host, &mut logger, &crypto_primitives);
         claim_eq!(result, Ok(()));
         claim_eq!(
-            host.state()
-                .storage
-                .get(&Bytes::from([0, 0]))
-                .and_then(|map| map.get(&Bytes::from([1, 2, 3])).map(|v| v.clone())),
+            stored_value(&host, Bytes::from([0, 0]), Bytes::from([1, 2, 3])),
             Some(Bytes::from([9, 8, 7]))
         );
 
@@ -473,13 +1057,85 @@ mod tests {
         ctx.set_parameter(&params)
             .set_sender(Address::Contract(UNAUTHORIZED_CALLER));
 
-        let result = update(&ctx, &mut host);
+        let result = update(&ctx, &mut host, &mut logger, &crypto_primitives);
+        claim_eq!(result, Err(CustomContractError::Unauthorized.into()));
+    }
+
+    #[concordium_test]
+    fn test_upsert() {
+        let mut host = default_host();
+        let mut logger = TestLogger::init();
+        let crypto_primitives = test_crypto_primitives();
+
+        // Upsert creates missing prefix
+        let params = to_bytes(&StorageEntries {
+            prefix: Bytes::from([0, 0]),
+            entries: vec![StorageEntry {
+                key: Bytes::from([1, 2, 3]),
+                value: Bytes::from([4, 5, 6]),
+            }],
+        });
+        let mut ctx = TestReceiveContext::default();
+        ctx.set_parameter(&params)
+            .set_sender(Address::Contract(AUTHORIZED_CALLER));
+
+        let result = upsert(&ctx, &mut host, &mut logger, &crypto_primitives);
+        claim_eq!(result, Ok(()));
+        claim_eq!(
+            stored_value(&host, Bytes::from([0, 0]), Bytes::from([1, 2, 3])),
+            Some(Bytes::from([4, 5, 6]))
+        );
+        claim_eq!(
+            host.state()
+                .prefix_owners
+                .get(&Bytes::from([0, 0]))
+                .map(|owner| *owner),
+            Some(Address::Contract(AUTHORIZED_CALLER))
+        );
+
+        // Upsert merges entries into existing prefix
+        let params = to_bytes(&StorageEntries {
+            prefix: Bytes::from([0, 0]),
+            entries: vec![
+                StorageEntry {
+                    key: Bytes::from([1, 2, 3]),
+                    value: Bytes::from([9, 8, 7]),
+                },
+                StorageEntry {
+                    key: Bytes::from([4, 5, 6]),
+                    value: Bytes::from([6, 5, 4]),
+                },
+            ],
+        });
+        let mut ctx = TestReceiveContext::default();
+        ctx.set_parameter(&params)
+            .set_sender(Address::Contract(AUTHORIZED_CALLER));
+
+        let result = upsert(&ctx, &mut host, &mut logger, &crypto_primitives);
+        claim_eq!(result, Ok(()));
+        claim_eq!(
+            stored_value(&host, Bytes::from([0, 0]), Bytes::from([1, 2, 3])),
+            Some(Bytes::from([9, 8, 7]))
+        );
+        claim_eq!(
+            stored_value(&host, Bytes::from([0, 0]), Bytes::from([4, 5, 6])),
+            Some(Bytes::from([6, 5, 4]))
+        );
+
+        // Unauthorized upsert
+        let mut ctx = TestReceiveContext::default();
+        ctx.set_parameter(&params)
+            .set_sender(Address::Contract(UNAUTHORIZED_CALLER));
+
+        let result = upsert(&ctx, &mut host, &mut logger, &crypto_primitives);
         claim_eq!(result, Err(CustomContractError::Unauthorized.into()));
     }
 
     #[concordium_test]
     fn test_remove() {
         let mut host = default_host();
+        let mut logger = TestLogger::init();
+        let crypto_primitives = test_crypto_primitives();
 
         // Authorized missing prefix remove
         let params = to_bytes(&StorageKeys {
@@ -490,7 +1146,7 @@ mod tests {
         ctx.set_parameter(&params)
             .set_sender(Address::Contract(AUTHORIZED_CALLER));
 
-        let result = remove(&ctx, &mut host);
+        let result = remove(&ctx, &mut host, &mut logger, &crypto_primitives);
         claim_eq!(result, Err(CustomContractError::NotFound.into()));
 
         // Authorized missing prefix remove
@@ -502,7 +1158,7 @@ mod tests {
         ctx.set_parameter(&params)
             .set_sender(Address::Contract(AUTHORIZED_CALLER));
 
-        let result = remove(&ctx, &mut host);
+        let result = remove(&ctx, &mut host, &mut logger, &crypto_primitives);
         claim_eq!(result, Err(CustomContractError::NotFound.into()));
 
         // Unauthorized missing remove
@@ -514,7 +1170,7 @@ mod tests {
         ctx.set_parameter(&params)
             .set_sender(Address::Contract(UNAUTHORIZED_CALLER));
 
-        let result = remove(&ctx, &mut host);
+        let result = remove(&ctx, &mut host, &mut logger, &crypto_primitives);
         claim_eq!(result, Err(CustomContractError::Unauthorized.into()));
 
         // Insert
@@ -538,7 +1194,10 @@ mod tests {
         let mut ctx = TestReceiveContext::default();
         ctx.set_parameter(&params)
             .set_sender(Address::Contract(AUTHORIZED_CALLER));
-        claim_eq!(insert(&ctx, &mut host), Ok(()));
+        claim_eq!(
+            insert(&ctx, &mut host, &mut logger, &crypto_primitives),
+            Ok(())
+        );
 
         // Unauthorized existing remove
         let params = to_bytes(&StorageKeys {
@@ -549,7 +1208,7 @@ mod tests {
         ctx.set_parameter(&params)
             .set_sender(Address::Contract(UNAUTHORIZED_CALLER));
 
-        let result = remove(&ctx, &mut host);
+        let result = remove(&ctx, &mut host, &mut logger, &crypto_primitives);
         claim_eq!(result, Err(CustomContractError::Unauthorized.into()));
 
         // Authorized existing partial remove
@@ -561,27 +1220,18 @@ mod tests {
         ctx.set_parameter(&params)
             .set_sender(Address::Contract(AUTHORIZED_CALLER));
 
-        let result = remove(&ctx, &mut host);
+        let result = remove(&ctx, &mut host, &mut logger, &crypto_primitives);
         claim_eq!(result, Ok(()));
         claim_eq!(
-            host.state()
-                .storage
-                .get(&Bytes::from([0, 0]))
-                .and_then(|map| map.get(&Bytes::from([1, 2, 3])).map(|v| v.clone())),
+            stored_value(&host, Bytes::from([0, 0]), Bytes::from([1, 2, 3])),
             None
         );
         claim_eq!(
-            host.state()
-                .storage
-                .get(&Bytes::from([0, 0]))
-                .and_then(|map| map.get(&Bytes::from([4, 5, 6])).map(|v| v.clone())),
+            stored_value(&host, Bytes::from([0, 0]), Bytes::from([4, 5, 6])),
             Some(Bytes::from([8, 8, 8]))
         );
         claim_eq!(
-            host.state()
-                .storage
-                .get(&Bytes::from([0, 0]))
-                .and_then(|map| map.get(&Bytes::from([7, 8, 9])).map(|v| v.clone())),
+            stored_value(&host, Bytes::from([0, 0]), Bytes::from([7, 8, 9])),
             Some(Bytes::from([7, 7, 7]))
         );
 
@@ -594,27 +1244,27 @@ mod tests {
         ctx.set_parameter(&params)
             .set_sender(Address::Contract(AUTHORIZED_CALLER));
 
-        let result = remove(&ctx, &mut host);
+        let result = remove(&ctx, &mut host, &mut logger, &crypto_primitives);
         claim_eq!(result, Ok(()));
         claim!(host.state().storage.get(&Bytes::from([0, 0])).is_none());
+        claim_eq!(
+            logger.logs.last(),
+            Some(&to_bytes(&StorageEvent::Removed {
+                prefix: crypto_primitives.hash_sha2_256(&to_bytes(&Bytes::from([0, 0]))),
+                keys: None,
+            }))
+        );
     }
 
     #[concordium_test]
-    fn test_get() {
+    fn test_stats() {
         let mut host = default_host();
+        let mut logger = TestLogger::init();
+        let crypto_primitives = test_crypto_primitives();
+        let ctx = TestReceiveContext::default();
+        claim_eq!(stats(&ctx, &host), Ok(StorageStats::default()));
 
-        // Get missing prefix
-        let params = to_bytes(&StorageKeys {
-            prefix: Bytes::from([0, 0]),
-            keys: StorageKeySelection::Some(vec![Bytes::from([1, 2, 3])]),
-        });
-        let mut ctx = TestReceiveContext::default();
-        ctx.set_parameter(&params);
-
-        let result = get(&ctx, &mut host);
-        claim_eq!(result, Ok(None));
-
-        // Insert
+        // Every entry takes 3 key bytes and 29 value bytes, including the version byte and 25 bytes of provenance
         let params = to_bytes(&StorageEntries {
             prefix: Bytes::from([0, 0]),
             entries: vec![
@@ -626,72 +1276,781 @@ mod tests {
                     key: Bytes::from([4, 5, 6]),
                     value: Bytes::from([8, 8, 8]),
                 },
-                StorageEntry {
-                    key: Bytes::from([7, 8, 9]),
-                    value: Bytes::from([7, 7, 7]),
-                },
             ],
         });
         let mut ctx = TestReceiveContext::default();
         ctx.set_parameter(&params)
             .set_sender(Address::Contract(AUTHORIZED_CALLER));
-        claim_eq!(insert(&ctx, &mut host), Ok(()));
+        claim_eq!(
+            insert(&ctx, &mut host, &mut logger, &crypto_primitives),
+            Ok(())
+        );
+        claim_eq!(
+            stats(&ctx, &host),
+            Ok(StorageStats {
+                prefixes: 1,
+                keys: 2,
+                bytes: 64,
+            })
+        );
 
-        // Get missing key
-        let params = to_bytes(&StorageKeys {
+        // Overwrite one key with a shorter value and add a new one
+        let params = to_bytes(&StorageEntries {
             prefix: Bytes::from([0, 0]),
-            keys: StorageKeySelection::Some(vec![Bytes::from([10, 11, 12])]),
+            entries: vec![
+                StorageEntry {
+                    key: Bytes::from([1, 2, 3]),
+                    value: Bytes::from([9]),
+                },
+                StorageEntry {
+                    key: Bytes::from([7, 8, 9]),
+                    value: Bytes::from([7, 7, 7]),
+                },
+            ],
         });
         let mut ctx = TestReceiveContext::default();
-        ctx.set_parameter(&params);
-
-        let result = get(&ctx, &mut host);
+        ctx.set_parameter(&params)
+            .set_sender(Address::Contract(AUTHORIZED_CALLER));
         claim_eq!(
-            result,
-            Ok(Some(StorageGetEntryResult {
-                prefix: Bytes::from([0, 0]),
-                entries: vec![MaybeStorageEntry {
-                    key: Bytes::from([10, 11, 12]),
-                    value: None,
-                }]
-            }))
+            update(&ctx, &mut host, &mut logger, &crypto_primitives),
+            Ok(())
+        );
+        claim_eq!(
+            stats(&ctx, &host),
+            Ok(StorageStats {
+                prefixes: 1,
+                keys: 3,
+                bytes: 94,
+            })
         );
 
-        // Get some existing
+        // Partial remove
         let params = to_bytes(&StorageKeys {
             prefix: Bytes::from([0, 0]),
-            keys: StorageKeySelection::Some(vec![Bytes::from([1, 2, 3])]),
+            keys: StorageKeySelection::Some(vec![Bytes::from([4, 5, 6])]),
         });
         let mut ctx = TestReceiveContext::default();
-        ctx.set_parameter(&params);
-
-        let result = get(&ctx, &mut host);
+        ctx.set_parameter(&params)
+            .set_sender(Address::Contract(AUTHORIZED_CALLER));
         claim_eq!(
-            result,
-            Ok(Some(StorageGetEntryResult {
-                prefix: Bytes::from([0, 0]),
-                entries: vec![MaybeStorageEntry {
-                    key: Bytes::from([1, 2, 3]),
-                    value: Some(Bytes::from([9, 9, 9])),
-                }]
-            }))
+            remove(&ctx, &mut host, &mut logger, &crypto_primitives),
+            Ok(())
+        );
+        claim_eq!(
+            stats(&ctx, &host),
+            Ok(StorageStats {
+                prefixes: 1,
+                keys: 2,
+                bytes: 62,
+            })
         );
 
-        // Get all existing
+        // Prefix remove
         let params = to_bytes(&StorageKeys {
             prefix: Bytes::from([0, 0]),
             keys: StorageKeySelection::All,
         });
         let mut ctx = TestReceiveContext::default();
-        ctx.set_parameter(&params);
-
-        let result = get(&ctx, &mut host);
+        ctx.set_parameter(&params)
+            .set_sender(Address::Contract(AUTHORIZED_CALLER));
         claim_eq!(
-            result,
-            Ok(Some(StorageGetEntryResult {
-                prefix: Bytes::from([0, 0]),
-                entries: vec![
-                    MaybeStorageEntry {
+            remove(&ctx, &mut host, &mut logger, &crypto_primitives),
+            Ok(())
+        );
+        claim_eq!(stats(&ctx, &host), Ok(StorageStats::default()));
+    }
+
+    #[concordium_test]
+    fn test_update_writers() {
+        let mut host = default_host();
+
+        const NEW_WRITER: ContractAddress = ContractAddress {
+            index: 2,
+            subindex: 0,
+        };
+
+        // Rotate the authorized caller to a new contract
+        let bytes = to_bytes(&vec![
+            UpdateWriterParams {
+                kind: UpdateKind::Remove,
+                address: Address::Contract(AUTHORIZED_CALLER),
+            },
+            UpdateWriterParams {
+                kind: UpdateKind::Add,
+                address: Address::Contract(NEW_WRITER),
+            },
+        ]);
+        let mut ctx = TestReceiveContext::default();
+        ctx.set_sender(Address::Contract(AUTHORIZED_CALLER))
+            .set_parameter(&bytes);
+        let result = update_writers(&ctx, &mut host);
+        claim_eq!(result, Err(CustomContractError::Unauthorized.into()));
+
+        ctx.set_sender(Address::Account(MAINTAINER));
+        let result = update_writers(&ctx, &mut host);
+        claim_eq!(result, Ok(()));
+
+        let state = host.state();
+        claim!(!state.has_writer_rights(&Address::Contract(AUTHORIZED_CALLER)));
+        claim!(state.has_writer_rights(&Address::Contract(NEW_WRITER)));
+    }
+
+    #[concordium_test]
+    fn test_update_writers_with_capability() {
+        let mut host = default_host();
+
+        let bytes = to_bytes(&vec![UpdateWriterParams {
+            kind: UpdateKind::Add,
+            address: Address::Contract(UNAUTHORIZED_CALLER),
+        }]);
+        let mut ctx = TestReceiveContext::default();
+        ctx.set_sender(Address::Contract(UNAUTHORIZED_CALLER))
+            .set_parameter(&bytes);
+        let result = update_writers(&ctx, &mut host);
+        claim_eq!(result, Err(CustomContractError::Unauthorized.into()));
+
+        // Only admins can grant capabilities
+        let grant = to_bytes(&CapabilityUpdateParams {
+            kind: AuthorityUpdateKind::Add,
+            address: Address::Contract(UNAUTHORIZED_CALLER),
+            capability: Capability::CanManageWriters,
+        });
+        let mut admin_ctx = TestReceiveContext::default();
+        admin_ctx
+            .set_sender(Address::Account(MAINTAINER))
+            .set_parameter(&grant);
+        let result = update_capability(&admin_ctx, &mut host);
+        claim_eq!(result, Err(CustomContractError::Unauthorized.into()));
+        admin_ctx.set_sender(Address::Account(ADMIN));
+        claim_eq!(update_capability(&admin_ctx, &mut host), Ok(()));
+
+        let result = update_writers(&ctx, &mut host);
+        claim_eq!(result, Ok(()));
+        claim!(host
+            .state()
+            .has_writer_rights(&Address::Contract(UNAUTHORIZED_CALLER)));
+
+        // Capability doesn't allow other maintainer actions
+        let bytes = to_bytes(&Vec::<Bytes>::new());
+        ctx.set_parameter(&bytes);
+        let result = reencode_prefix(&ctx, &mut host);
+        claim_eq!(result, Err(CustomContractError::Unauthorized.into()));
+    }
+
+    #[concordium_test]
+    fn test_prefix_owner() {
+        let mut host = default_host();
+        let mut logger = TestLogger::init();
+        let crypto_primitives = test_crypto_primitives();
+
+        const OTHER_WRITER: ContractAddress = ContractAddress {
+            index: 2,
+            subindex: 0,
+        };
+        const GLOBAL_WRITER: ContractAddress = ContractAddress {
+            index: 3,
+            subindex: 0,
+        };
+
+        let mut ctx = TestReceiveContext::default();
+        let bytes = to_bytes(&UpdateWriterParams {
+            kind: UpdateKind::Add,
+            address: Address::Contract(OTHER_WRITER),
+        });
+        ctx.set_sender(Address::Account(MAINTAINER))
+            .set_parameter(&bytes);
+        claim_eq!(update_writer(&ctx, &mut host), Ok(()));
+
+        let mut ctx = TestReceiveContext::default();
+        let bytes = to_bytes(&UpdateWriterParams {
+            kind: UpdateKind::Add,
+            address: Address::Contract(GLOBAL_WRITER),
+        });
+        ctx.set_sender(Address::Account(MAINTAINER))
+            .set_parameter(&bytes);
+        claim_eq!(update_global_writer(&ctx, &mut host), Ok(()));
+
+        // Insert makes sender the prefix owner
+        let params = to_bytes(&StorageEntries {
+            prefix: Bytes::from([0, 0]),
+            entries: vec![StorageEntry {
+                key: Bytes::from([1, 2, 3]),
+                value: Bytes::from([4, 5, 6]),
+            }],
+        });
+        let mut ctx = TestReceiveContext::default();
+        ctx.set_parameter(&params)
+            .set_sender(Address::Contract(AUTHORIZED_CALLER));
+        claim_eq!(
+            insert(&ctx, &mut host, &mut logger, &crypto_primitives),
+            Ok(())
+        );
+
+        let params = to_bytes(&Bytes::from([0, 0]));
+        let mut ctx = TestReceiveContext::default();
+        ctx.set_parameter(&params);
+        claim_eq!(
+            view_prefix_owner(&ctx, &host),
+            Ok(Some(Address::Contract(AUTHORIZED_CALLER)))
+        );
+
+        // Other writer can't modify owned prefix
+        let params = to_bytes(&StorageEntries {
+            prefix: Bytes::from([0, 0]),
+            entries: vec![StorageEntry {
+                key: Bytes::from([1, 2, 3]),
+                value: Bytes::from([9, 8, 7]),
+            }],
+        });
+        let mut ctx = TestReceiveContext::default();
+        ctx.set_parameter(&params)
+            .set_sender(Address::Contract(OTHER_WRITER));
+        claim_eq!(
+            update(&ctx, &mut host, &mut logger, &crypto_primitives),
+            Err(CustomContractError::Unauthorized.into())
+        );
+
+        let remove_params = to_bytes(&StorageKeys {
+            prefix: Bytes::from([0, 0]),
+            keys: StorageKeySelection::All,
+        });
+        let mut ctx = TestReceiveContext::default();
+        ctx.set_parameter(&remove_params)
+            .set_sender(Address::Contract(OTHER_WRITER));
+        claim_eq!(
+            remove(&ctx, &mut host, &mut logger, &crypto_primitives),
+            Err(CustomContractError::Unauthorized.into())
+        );
+
+        // Global writer can modify any prefix
+        let mut ctx = TestReceiveContext::default();
+        ctx.set_parameter(&params)
+            .set_sender(Address::Contract(GLOBAL_WRITER));
+        claim_eq!(
+            update(&ctx, &mut host, &mut logger, &crypto_primitives),
+            Ok(())
+        );
+
+        // Removing the prefix also clears its owner
+        let mut ctx = TestReceiveContext::default();
+        ctx.set_parameter(&remove_params)
+            .set_sender(Address::Contract(AUTHORIZED_CALLER));
+        claim_eq!(
+            remove(&ctx, &mut host, &mut logger, &crypto_primitives),
+            Ok(())
+        );
+        claim!(host
+            .state()
+            .prefix_owners
+            .get(&Bytes::from([0, 0]))
+            .is_none());
+    }
+
+    #[concordium_test]
+    fn test_replace_operators() {
+        let mut host = default_host();
+        let mut logger = TestLogger::init();
+        let crypto_primitives = test_crypto_primitives();
+
+        const PREVIOUS_OPERATOR: ContractAddress = ContractAddress {
+            index: 2,
+            subindex: 0,
+        };
+        const NEW_OPERATOR: ContractAddress = ContractAddress {
+            index: 3,
+            subindex: 0,
+        };
+
+        for writer in [PREVIOUS_OPERATOR, NEW_OPERATOR] {
+            let mut ctx = TestReceiveContext::default();
+            let bytes = to_bytes(&UpdateWriterParams {
+                kind: UpdateKind::Add,
+                address: Address::Contract(writer),
+            });
+            ctx.set_sender(Address::Account(MAINTAINER))
+                .set_parameter(&bytes);
+            claim_eq!(update_writer(&ctx, &mut host), Ok(()));
+        }
+
+        let replace = |host: &mut TestHost<State<TestStateApi>>,
+                       sender: ContractAddress,
+                       prefix: Bytes,
+                       operators: Vec<Address>| {
+            let bytes = to_bytes(&ReplaceOperatorsParams { prefix, operators });
+            let mut ctx = TestReceiveContext::default();
+            ctx.set_sender(Address::Contract(sender))
+                .set_parameter(&bytes);
+            replace_operators(&ctx, host)
+        };
+        let update_as = |host: &mut TestHost<State<TestStateApi>>,
+                         logger: &mut TestLogger,
+                         sender: ContractAddress| {
+            let bytes = to_bytes(&StorageEntries {
+                prefix: Bytes::from([0, 0]),
+                entries: vec![StorageEntry {
+                    key: Bytes::from([1, 2, 3]),
+                    value: Bytes::from([9, 8, 7]),
+                }],
+            });
+            let mut ctx = TestReceiveContext::default();
+            ctx.set_sender(Address::Contract(sender))
+                .set_parameter(&bytes);
+            update(&ctx, host, logger, &crypto_primitives)
+        };
+
+        claim_eq!(
+            replace(
+                &mut host,
+                AUTHORIZED_CALLER,
+                Bytes::from([0, 0]),
+                vec![Address::Contract(PREVIOUS_OPERATOR)],
+            ),
+            Err(CustomContractError::NotFound.into())
+        );
+
+        let params = to_bytes(&StorageEntries {
+            prefix: Bytes::from([0, 0]),
+            entries: vec![StorageEntry {
+                key: Bytes::from([1, 2, 3]),
+                value: Bytes::from([4, 5, 6]),
+            }],
+        });
+        let mut ctx = TestReceiveContext::default();
+        ctx.set_parameter(&params)
+            .set_sender(Address::Contract(AUTHORIZED_CALLER));
+        claim_eq!(
+            insert(&ctx, &mut host, &mut logger, &crypto_primitives),
+            Ok(())
+        );
+
+        // Operator set by the previous token owner can modify the prefix, but not replace operators
+        claim_eq!(
+            replace(
+                &mut host,
+                AUTHORIZED_CALLER,
+                Bytes::from([0, 0]),
+                vec![Address::Contract(PREVIOUS_OPERATOR)],
+            ),
+            Ok(())
+        );
+        claim_eq!(update_as(&mut host, &mut logger, PREVIOUS_OPERATOR), Ok(()));
+        claim_eq!(
+            replace(
+                &mut host,
+                PREVIOUS_OPERATOR,
+                Bytes::from([0, 0]),
+                vec![Address::Contract(PREVIOUS_OPERATOR)],
+            ),
+            Err(CustomContractError::Unauthorized.into())
+        );
+
+        // Operators of the previous owner are gone after the transfer
+        claim_eq!(
+            replace(
+                &mut host,
+                AUTHORIZED_CALLER,
+                Bytes::from([0, 0]),
+                vec![Address::Contract(NEW_OPERATOR)],
+            ),
+            Ok(())
+        );
+        claim_eq!(
+            update_as(&mut host, &mut logger, PREVIOUS_OPERATOR),
+            Err(CustomContractError::Unauthorized.into())
+        );
+        claim_eq!(update_as(&mut host, &mut logger, NEW_OPERATOR), Ok(()));
+
+        // Removing the prefix also clears its operators
+        let remove_params = to_bytes(&StorageKeys {
+            prefix: Bytes::from([0, 0]),
+            keys: StorageKeySelection::All,
+        });
+        let mut ctx = TestReceiveContext::default();
+        ctx.set_parameter(&remove_params)
+            .set_sender(Address::Contract(AUTHORIZED_CALLER));
+        claim_eq!(
+            remove(&ctx, &mut host, &mut logger, &crypto_primitives),
+            Ok(())
+        );
+        claim!(host
+            .state()
+            .prefix_operators
+            .get(&Bytes::from([0, 0]))
+            .is_none());
+    }
+
+    #[concordium_test]
+    fn test_reencode_prefix() {
+        let mut host = default_host();
+
+        // Legacy prefix, stored before value envelopes were introduced
+        let (state, builder) = host.state_and_builder();
+        let mut map = builder.new_map();
+        map.insert(Bytes::from([1, 2, 3]), Bytes::from([4, 5, 6]));
+        state.storage.insert(Bytes::from([0, 0]), map);
+
+        claim_eq!(
+            stored_value(&host, Bytes::from([0, 0]), Bytes::from([1, 2, 3])),
+            Some(Bytes::from([4, 5, 6]))
+        );
+
+        let params = to_bytes(&vec![Bytes::from([0, 0])]);
+
+        // Only maintainers can migrate data
+        let mut ctx = TestReceiveContext::default();
+        ctx.set_parameter(&params)
+            .set_sender(Address::Contract(AUTHORIZED_CALLER));
+        claim_eq!(
+            reencode_prefix(&ctx, &mut host),
+            Err(CustomContractError::Unauthorized.into())
+        );
+
+        let mut ctx = TestReceiveContext::default();
+        ctx.set_parameter(&params)
+            .set_sender(Address::Account(MAINTAINER));
+        claim_eq!(reencode_prefix(&ctx, &mut host), Ok(()));
+
+        claim_eq!(
+            host.state()
+                .storage
+                .get(&Bytes::from([0, 0]))
+                .and_then(|map| map.get(&Bytes::from([1, 2, 3])).map(|v| v.clone())),
+            Some(Bytes::from([VALUE_ENVELOPE_VERSION, 4, 5, 6]))
+        );
+        claim_eq!(
+            stored_value(&host, Bytes::from([0, 0]), Bytes::from([1, 2, 3])),
+            Some(Bytes::from([4, 5, 6]))
+        );
+
+        // Already migrated prefixes are skipped
+        claim_eq!(reencode_prefix(&ctx, &mut host), Ok(()));
+        claim_eq!(
+            stored_value(&host, Bytes::from([0, 0]), Bytes::from([1, 2, 3])),
+            Some(Bytes::from([4, 5, 6]))
+        );
+    }
+
+    #[concordium_test]
+    fn test_export_import_prefix() {
+        let mut host = default_host();
+        let mut logger = TestLogger::init();
+        let crypto_primitives = test_crypto_primitives();
+
+        let params = to_bytes(&StorageEntries {
+            prefix: Bytes::from([0, 0]),
+            entries: vec![
+                StorageEntry {
+                    key: Bytes::from([4, 5, 6]),
+                    value: Bytes::from([8, 8, 8]),
+                },
+                StorageEntry {
+                    key: Bytes::from([1, 2, 3]),
+                    value: Bytes::from([9, 9, 9]),
+                },
+            ],
+        });
+        let mut ctx = TestReceiveContext::default();
+        ctx.set_parameter(&params)
+            .set_sender(Address::Contract(AUTHORIZED_CALLER));
+        claim_eq!(
+            insert(&ctx, &mut host, &mut logger, &crypto_primitives),
+            Ok(())
+        );
+
+        // Missing prefix
+        let params = to_bytes(&Bytes::from([1, 1]));
+        let mut ctx = TestReceiveContext::default();
+        ctx.set_parameter(&params);
+        let result = export_prefix(&ctx, &host);
+        claim_eq!(result, Err(CustomContractError::NotFound.into()));
+
+        // Entries are exported sorted by key, without value envelope
+        let params = to_bytes(&Bytes::from([0, 0]));
+        let mut ctx = TestReceiveContext::default();
+        ctx.set_parameter(&params);
+        let export = export_prefix(&ctx, &host).expect_report("Export failed");
+        claim_eq!(
+            export,
+            PrefixExport {
+                version: EXPORT_FORMAT_VERSION,
+                prefix: Bytes::from([0, 0]),
+                owner: Some(Address::Contract(AUTHORIZED_CALLER)),
+                expiry: None,
+                entries: vec![
+                    StorageEntry {
+                        key: Bytes::from([1, 2, 3]),
+                        value: Bytes::from([9, 9, 9]),
+                    },
+                    StorageEntry {
+                        key: Bytes::from([4, 5, 6]),
+                        value: Bytes::from([8, 8, 8]),
+                    },
+                ],
+            }
+        );
+
+        // Import into a new instance by another writer
+        const IMPORTER: ContractAddress = ContractAddress {
+            index: 2,
+            subindex: 0,
+        };
+        let mut new_host = default_host();
+        new_host
+            .state_mut()
+            .writers
+            .insert(Address::Contract(IMPORTER));
+        let params = to_bytes(&export);
+        let mut ctx = TestReceiveContext::default();
+        ctx.set_parameter(&params)
+            .set_sender(Address::Contract(UNAUTHORIZED_CALLER));
+        let result = import_prefix(&ctx, &mut new_host, &mut logger, &crypto_primitives);
+        claim_eq!(result, Err(CustomContractError::Unauthorized.into()));
+
+        ctx.set_sender(Address::Contract(IMPORTER));
+        let result = import_prefix(&ctx, &mut new_host, &mut logger, &crypto_primitives);
+        claim_eq!(result, Ok(()));
+        claim_eq!(
+            stored_value(&new_host, Bytes::from([0, 0]), Bytes::from([1, 2, 3])),
+            Some(Bytes::from([9, 9, 9]))
+        );
+
+        // Imported values are attested as written by the importer, not by the exported owner
+        let params = to_bytes(&StorageKeys {
+            prefix: Bytes::from([0, 0]),
+            keys: StorageKeySelection::Some(vec![Bytes::from([1, 2, 3])]),
+        });
+        let mut provenance_ctx = TestReceiveContext::default();
+        provenance_ctx
+            .set_parameter(&params)
+            .set_metadata_slot_time(Timestamp::from_timestamp_millis(0));
+        let result = get_with_provenance(&provenance_ctx, &new_host)
+            .expect_report("Getting provenance failed")
+            .expect_report("Prefix should exist");
+        claim_eq!(
+            result.entries[0]
+                .provenance
+                .map(|provenance| provenance.writer),
+            Some(Address::Contract(IMPORTER))
+        );
+
+        // Prefix already exists
+        let result = import_prefix(&ctx, &mut new_host, &mut logger, &crypto_primitives);
+        claim_eq!(result, Err(CustomContractError::AlreadyExists.into()));
+
+        // Re-exporting gives the same data
+        let params = to_bytes(&Bytes::from([0, 0]));
+        let mut ctx = TestReceiveContext::default();
+        ctx.set_parameter(&params);
+        claim_eq!(export_prefix(&ctx, &new_host), Ok(export));
+
+        // Unknown layout version
+        let mut new_host = default_host();
+        let params = to_bytes(&PrefixExport {
+            version: EXPORT_FORMAT_VERSION + 1,
+            prefix: Bytes::from([0, 0]),
+            owner: None,
+            expiry: None,
+            entries: vec![],
+        });
+        let mut ctx = TestReceiveContext::default();
+        ctx.set_parameter(&params)
+            .set_sender(Address::Contract(AUTHORIZED_CALLER));
+        let result = import_prefix(&ctx, &mut new_host, &mut logger, &crypto_primitives);
+        claim_eq!(result, Err(CustomContractError::InvalidFields.into()));
+    }
+
+    #[concordium_test]
+    fn test_set_expiry() {
+        let mut host = default_host();
+        let mut logger = TestLogger::init();
+        let crypto_primitives = test_crypto_primitives();
+
+        // Insert
+        let params = to_bytes(&StorageEntries {
+            prefix: Bytes::from([0, 0]),
+            entries: vec![StorageEntry {
+                key: Bytes::from([1, 2, 3]),
+                value: Bytes::from([4, 5, 6]),
+            }],
+        });
+        let mut ctx = TestReceiveContext::default();
+        ctx.set_parameter(&params)
+            .set_sender(Address::Contract(AUTHORIZED_CALLER));
+        claim_eq!(
+            insert(&ctx, &mut host, &mut logger, &crypto_primitives),
+            Ok(())
+        );
+
+        // Unauthorized expiry update
+        let params = to_bytes(&SetExpiryParams {
+            prefix: Bytes::from([0, 0]),
+            expiry: Some(Timestamp::from_timestamp_millis(100)),
+        });
+        let mut ctx = TestReceiveContext::default();
+        ctx.set_parameter(&params)
+            .set_sender(Address::Contract(UNAUTHORIZED_CALLER));
+        claim_eq!(
+            set_expiry(&ctx, &mut host),
+            Err(CustomContractError::Unauthorized.into())
+        );
+
+        let mut ctx = TestReceiveContext::default();
+        ctx.set_parameter(&params)
+            .set_sender(Address::Contract(AUTHORIZED_CALLER));
+        claim_eq!(set_expiry(&ctx, &mut host), Ok(()));
+
+        // Not expired before expiry time
+        let prefix = to_bytes(&Bytes::from([0, 0]));
+        let mut ctx = TestReceiveContext::default();
+        ctx.set_parameter(&prefix)
+            .set_metadata_slot_time(Timestamp::from_timestamp_millis(100));
+        claim_eq!(is_expired(&ctx, &host), Ok(false));
+
+        // Expired after expiry time, data is still returned
+        let mut ctx = TestReceiveContext::default();
+        ctx.set_parameter(&prefix)
+            .set_metadata_slot_time(Timestamp::from_timestamp_millis(101));
+        claim_eq!(is_expired(&ctx, &host), Ok(true));
+
+        let params = to_bytes(&StorageKeys {
+            prefix: Bytes::from([0, 0]),
+            keys: StorageKeySelection::All,
+        });
+        let mut ctx = TestReceiveContext::default();
+        ctx.set_parameter(&params)
+            .set_metadata_slot_time(Timestamp::from_timestamp_millis(101));
+        claim_eq!(
+            get(&ctx, &host),
+            Ok(Some(StorageGetEntryResult {
+                prefix: Bytes::from([0, 0]),
+                entries: vec![MaybeStorageEntry {
+                    key: Bytes::from([1, 2, 3]),
+                    value: Some(Bytes::from([4, 5, 6])),
+                }],
+                expired: true,
+            }))
+        );
+
+        // Clearing the expiry
+        let params = to_bytes(&SetExpiryParams {
+            prefix: Bytes::from([0, 0]),
+            expiry: None,
+        });
+        let mut ctx = TestReceiveContext::default();
+        ctx.set_parameter(&params)
+            .set_sender(Address::Contract(AUTHORIZED_CALLER));
+        claim_eq!(set_expiry(&ctx, &mut host), Ok(()));
+
+        let mut ctx = TestReceiveContext::default();
+        ctx.set_parameter(&prefix)
+            .set_metadata_slot_time(Timestamp::from_timestamp_millis(101));
+        claim_eq!(is_expired(&ctx, &host), Ok(false));
+    }
+
+    #[concordium_test]
+    fn test_get() {
+        let mut host = default_host();
+        let mut logger = TestLogger::init();
+        let crypto_primitives = test_crypto_primitives();
+
+        // Get missing prefix
+        let params = to_bytes(&StorageKeys {
+            prefix: Bytes::from([0, 0]),
+            keys: StorageKeySelection::Some(vec![Bytes::from([1, 2, 3])]),
+        });
+        let mut ctx = TestReceiveContext::default();
+        ctx.set_parameter(&params)
+            .set_metadata_slot_time(Timestamp::from_timestamp_millis(0));
+
+        let result = get(&ctx, &mut host);
+        claim_eq!(result, Ok(None));
+
+        // Insert
+        let params = to_bytes(&StorageEntries {
+            prefix: Bytes::from([0, 0]),
+            entries: vec![
+                StorageEntry {
+                    key: Bytes::from([1, 2, 3]),
+                    value: Bytes::from([9, 9, 9]),
+                },
+                StorageEntry {
+                    key: Bytes::from([4, 5, 6]),
+                    value: Bytes::from([8, 8, 8]),
+                },
+                StorageEntry {
+                    key: Bytes::from([7, 8, 9]),
+                    value: Bytes::from([7, 7, 7]),
+                },
+            ],
+        });
+        let mut ctx = TestReceiveContext::default();
+        ctx.set_parameter(&params)
+            .set_sender(Address::Contract(AUTHORIZED_CALLER));
+        claim_eq!(
+            insert(&ctx, &mut host, &mut logger, &crypto_primitives),
+            Ok(())
+        );
+
+        // Get missing key
+        let params = to_bytes(&StorageKeys {
+            prefix: Bytes::from([0, 0]),
+            keys: StorageKeySelection::Some(vec![Bytes::from([10, 11, 12])]),
+        });
+        let mut ctx = TestReceiveContext::default();
+        ctx.set_parameter(&params)
+            .set_metadata_slot_time(Timestamp::from_timestamp_millis(0));
+
+        let result = get(&ctx, &mut host);
+        claim_eq!(
+            result,
+            Ok(Some(StorageGetEntryResult {
+                prefix: Bytes::from([0, 0]),
+                entries: vec![MaybeStorageEntry {
+                    key: Bytes::from([10, 11, 12]),
+                    value: None,
+                }],
+                expired: false,
+            }))
+        );
+
+        // Get some existing
+        let params = to_bytes(&StorageKeys {
+            prefix: Bytes::from([0, 0]),
+            keys: StorageKeySelection::Some(vec![Bytes::from([1, 2, 3])]),
+        });
+        let mut ctx = TestReceiveContext::default();
+        ctx.set_parameter(&params)
+            .set_metadata_slot_time(Timestamp::from_timestamp_millis(0));
+
+        let result = get(&ctx, &mut host);
+        claim_eq!(
+            result,
+            Ok(Some(StorageGetEntryResult {
+                prefix: Bytes::from([0, 0]),
+                entries: vec![MaybeStorageEntry {
+                    key: Bytes::from([1, 2, 3]),
+                    value: Some(Bytes::from([9, 9, 9])),
+                }],
+                expired: false,
+            }))
+        );
+
+        // Get all existing
+        let params = to_bytes(&StorageKeys {
+            prefix: Bytes::from([0, 0]),
+            keys: StorageKeySelection::All,
+        });
+        let mut ctx = TestReceiveContext::default();
+        ctx.set_parameter(&params)
+            .set_metadata_slot_time(Timestamp::from_timestamp_millis(0));
+
+        let result = get(&ctx, &mut host);
+        claim_eq!(
+            result,
+            Ok(Some(StorageGetEntryResult {
+                prefix: Bytes::from([0, 0]),
+                entries: vec![
+                    MaybeStorageEntry {
                         key: Bytes::from([1, 2, 3]),
                         value: Some(Bytes::from([9, 9, 9])),
                     },
@@ -703,8 +2062,346 @@ mod tests {
                         key: Bytes::from([7, 8, 9]),
                         value: Some(Bytes::from([7, 7, 7])),
                     }
-                ]
+                ],
+                expired: false,
+            }))
+        );
+    }
+
+    #[concordium_test]
+    fn test_get_with_provenance() {
+        let mut host = default_host();
+        let mut logger = TestLogger::init();
+        let crypto_primitives = test_crypto_primitives();
+
+        const GLOBAL_WRITER: ContractAddress = ContractAddress {
+            index: 3,
+            subindex: 0,
+        };
+
+        let mut ctx = TestReceiveContext::default();
+        let bytes = to_bytes(&UpdateWriterParams {
+            kind: UpdateKind::Add,
+            address: Address::Contract(GLOBAL_WRITER),
+        });
+        ctx.set_sender(Address::Account(MAINTAINER))
+            .set_parameter(&bytes);
+        claim_eq!(update_global_writer(&ctx, &mut host), Ok(()));
+
+        let params = to_bytes(&StorageEntries {
+            prefix: Bytes::from([0, 0]),
+            entries: vec![
+                StorageEntry {
+                    key: Bytes::from([1, 2, 3]),
+                    value: Bytes::from([9, 9, 9]),
+                },
+                StorageEntry {
+                    key: Bytes::from([4, 5, 6]),
+                    value: Bytes::from([8, 8, 8]),
+                },
+            ],
+        });
+        let mut ctx = TestReceiveContext::default();
+        ctx.set_parameter(&params)
+            .set_sender(Address::Contract(AUTHORIZED_CALLER));
+        claim_eq!(
+            insert(&ctx, &mut host, &mut logger, &crypto_primitives),
+            Ok(())
+        );
+
+        // Another writer overwrites one of the values
+        let params = to_bytes(&StorageEntries {
+            prefix: Bytes::from([0, 0]),
+            entries: vec![StorageEntry {
+                key: Bytes::from([4, 5, 6]),
+                value: Bytes::from([7, 7, 7]),
+            }],
+        });
+        let mut ctx = TestReceiveContext::default();
+        ctx.set_parameter(&params)
+            .set_sender(Address::Contract(GLOBAL_WRITER));
+        claim_eq!(
+            update(&ctx, &mut host, &mut logger, &crypto_primitives),
+            Ok(())
+        );
+
+        let params = to_bytes(&StorageKeys {
+            prefix: Bytes::from([0, 0]),
+            keys: StorageKeySelection::Some(vec![
+                Bytes::from([1, 2, 3]),
+                Bytes::from([4, 5, 6]),
+                Bytes::from([10, 11, 12]),
+            ]),
+        });
+        let mut ctx = TestReceiveContext::default();
+        ctx.set_parameter(&params)
+            .set_metadata_slot_time(Timestamp::from_timestamp_millis(0));
+        claim_eq!(
+            get_with_provenance(&ctx, &host),
+            Ok(Some(StorageProvenanceResult {
+                prefix: Bytes::from([0, 0]),
+                entries: vec![
+                    AttestedStorageEntry {
+                        key: Bytes::from([1, 2, 3]),
+                        value: Some(Bytes::from([9, 9, 9])),
+                        provenance: Some(StorageProvenance {
+                            writer: Address::Contract(AUTHORIZED_CALLER),
+                            sequence: 1,
+                        }),
+                    },
+                    AttestedStorageEntry {
+                        key: Bytes::from([4, 5, 6]),
+                        value: Some(Bytes::from([7, 7, 7])),
+                        provenance: Some(StorageProvenance {
+                            writer: Address::Contract(GLOBAL_WRITER),
+                            sequence: 2,
+                        }),
+                    },
+                    AttestedStorageEntry {
+                        key: Bytes::from([10, 11, 12]),
+                        value: None,
+                        provenance: None,
+                    },
+                ],
+                expired: false,
+            }))
+        );
+
+        // Values of legacy prefixes have no recorded writer
+        let (state, builder) = host.state_and_builder();
+        let mut map = builder.new_map();
+        map.insert(Bytes::from([1, 2, 3]), Bytes::from([4, 5, 6]));
+        state.storage.insert(Bytes::from([1, 1]), map);
+
+        let params = to_bytes(&StorageKeys {
+            prefix: Bytes::from([1, 1]),
+            keys: StorageKeySelection::All,
+        });
+        let mut ctx = TestReceiveContext::default();
+        ctx.set_parameter(&params)
+            .set_metadata_slot_time(Timestamp::from_timestamp_millis(0));
+        let result = get_with_provenance(&ctx, &host)
+            .expect_report("Unexpected error during 'getWithProvenance' call");
+        claim_eq!(
+            result.map(|result| result.entries),
+            Some(vec![AttestedStorageEntry {
+                key: Bytes::from([1, 2, 3]),
+                value: Some(Bytes::from([4, 5, 6])),
+                provenance: None,
+            }])
+        );
+    }
+
+    #[concordium_test]
+    fn test_get_batch() {
+        let mut host = default_host();
+        let mut logger = TestLogger::init();
+        let crypto_primitives = test_crypto_primitives();
+
+        // Insert
+        let params = to_bytes(&StorageEntries {
+            prefix: Bytes::from([0, 0]),
+            entries: vec![StorageEntry {
+                key: Bytes::from([1, 2, 3]),
+                value: Bytes::from([9, 9, 9]),
+            }],
+        });
+        let mut ctx = TestReceiveContext::default();
+        ctx.set_parameter(&params)
+            .set_sender(Address::Contract(AUTHORIZED_CALLER));
+        claim_eq!(
+            insert(&ctx, &mut host, &mut logger, &crypto_primitives),
+            Ok(())
+        );
+
+        // Get existing and missing prefixes
+        let params = to_bytes(&vec![
+            StorageKeys::some(Bytes::from([0, 0]), vec![Bytes::from([1, 2, 3])]),
+            StorageKeys::all(Bytes::from([1, 1])),
+        ]);
+        let mut ctx = TestReceiveContext::default();
+        ctx.set_parameter(&params)
+            .set_metadata_slot_time(Timestamp::from_timestamp_millis(0));
+
+        let result = get_batch(&ctx, &host);
+        claim_eq!(
+            result,
+            Ok(vec![
+                Some(StorageGetEntryResult {
+                    prefix: Bytes::from([0, 0]),
+                    entries: vec![MaybeStorageEntry {
+                        key: Bytes::from([1, 2, 3]),
+                        value: Some(Bytes::from([9, 9, 9])),
+                    }],
+                    expired: false,
+                }),
+                None
+            ])
+        );
+    }
+
+    #[concordium_test]
+    fn test_get_page() {
+        let mut host = default_host();
+        let mut logger = TestLogger::init();
+        let crypto_primitives = test_crypto_primitives();
+
+        // Insert keys out of order
+        let params = to_bytes(&StorageEntries {
+            prefix: Bytes::from([0, 0]),
+            entries: vec![
+                StorageEntry {
+                    key: Bytes::from([7, 8, 9]),
+                    value: Bytes::from([7, 7, 7]),
+                },
+                StorageEntry {
+                    key: Bytes::from([1, 2, 3]),
+                    value: Bytes::from([9, 9, 9]),
+                },
+                StorageEntry {
+                    key: Bytes::from([4, 5, 6]),
+                    value: Bytes::from([8, 8, 8]),
+                },
+            ],
+        });
+        let mut ctx = TestReceiveContext::default();
+        ctx.set_parameter(&params)
+            .set_sender(Address::Contract(AUTHORIZED_CALLER));
+        claim_eq!(
+            insert(&ctx, &mut host, &mut logger, &crypto_primitives),
+            Ok(())
+        );
+
+        // First page is sorted by key
+        let params = to_bytes(&StoragePageParams {
+            prefix: Bytes::from([0, 0]),
+            start_after_key: None,
+            limit: 2,
+        });
+        let mut ctx = TestReceiveContext::default();
+        ctx.set_parameter(&params)
+            .set_metadata_slot_time(Timestamp::from_timestamp_millis(0));
+
+        let result = get_page(&ctx, &host);
+        claim_eq!(
+            result,
+            Ok(Some(StoragePage {
+                prefix: Bytes::from([0, 0]),
+                entries: vec![
+                    StorageEntry {
+                        key: Bytes::from([1, 2, 3]),
+                        value: Bytes::from([9, 9, 9]),
+                    },
+                    StorageEntry {
+                        key: Bytes::from([4, 5, 6]),
+                        value: Bytes::from([8, 8, 8]),
+                    },
+                ],
+                next_key: Some(Bytes::from([4, 5, 6])),
+                expired: false,
+            }))
+        );
+
+        // Last page has no cursor
+        let params = to_bytes(&StoragePageParams {
+            prefix: Bytes::from([0, 0]),
+            start_after_key: Some(Bytes::from([4, 5, 6])),
+            limit: 2,
+        });
+        let mut ctx = TestReceiveContext::default();
+        ctx.set_parameter(&params)
+            .set_metadata_slot_time(Timestamp::from_timestamp_millis(0));
+
+        let result = get_page(&ctx, &host);
+        claim_eq!(
+            result,
+            Ok(Some(StoragePage {
+                prefix: Bytes::from([0, 0]),
+                entries: vec![StorageEntry {
+                    key: Bytes::from([7, 8, 9]),
+                    value: Bytes::from([7, 7, 7]),
+                }],
+                next_key: None,
+                expired: false,
             }))
         );
+
+        // Missing prefix
+        let params = to_bytes(&StoragePageParams {
+            prefix: Bytes::from([1, 1]),
+            start_after_key: None,
+            limit: 2,
+        });
+        let mut ctx = TestReceiveContext::default();
+        ctx.set_parameter(&params)
+            .set_metadata_slot_time(Timestamp::from_timestamp_millis(0));
+        claim_eq!(get_page(&ctx, &host), Ok(None));
+
+        // Empty pages are rejected
+        let params = to_bytes(&StoragePageParams {
+            prefix: Bytes::from([0, 0]),
+            start_after_key: None,
+            limit: 0,
+        });
+        let mut ctx = TestReceiveContext::default();
+        ctx.set_parameter(&params)
+            .set_metadata_slot_time(Timestamp::from_timestamp_millis(0));
+        claim_eq!(
+            get_page(&ctx, &host),
+            Err(CustomContractError::InvalidFields.into())
+        );
+    }
+
+    #[concordium_test]
+    fn test_list_prefixes() {
+        let mut host = default_host();
+        let mut logger = TestLogger::init();
+        let crypto_primitives = test_crypto_primitives();
+
+        // Insert
+        for prefix in [[0, 0], [0, 1], [1, 0]] {
+            let params = to_bytes(&StorageEntries {
+                prefix: Bytes::from(prefix),
+                entries: vec![],
+            });
+            let mut ctx = TestReceiveContext::default();
+            ctx.set_parameter(&params)
+                .set_sender(Address::Contract(AUTHORIZED_CALLER));
+            claim_eq!(
+                insert(&ctx, &mut host, &mut logger, &crypto_primitives),
+                Ok(())
+            );
+        }
+
+        // List all prefixes
+        let params = to_bytes(&ListPrefixesParams {
+            filter: None,
+            skip: 0,
+            show: 10,
+        });
+        let mut ctx = TestReceiveContext::default();
+        ctx.set_parameter(&params);
+
+        let result = list_prefixes(&ctx, &host);
+        claim_eq!(
+            result,
+            Ok(vec![
+                Bytes::from([0, 0]),
+                Bytes::from([0, 1]),
+                Bytes::from([1, 0])
+            ])
+        );
+
+        // List filtered prefixes with pagination
+        let params = to_bytes(&ListPrefixesParams {
+            filter: Some(Bytes::from([0])),
+            skip: 1,
+            show: 10,
+        });
+        let mut ctx = TestReceiveContext::default();
+        ctx.set_parameter(&params);
+
+        let result = list_prefixes(&ctx, &host);
+        claim_eq!(result, Ok(vec![Bytes::from([0, 1])]));
     }
 }
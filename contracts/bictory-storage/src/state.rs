@@ -1,14 +1,130 @@
-use commons::{Authority, Bytes};
+use commons::{
+    AttestedStorageEntry, Authority, Bytes, MaybeStorageEntry, StorageEntries, StorageEntry,
+    StorageGetEntryResult, StorageKeySelection, StorageKeys, StoragePage, StoragePageParams,
+    StorageProvenance, StorageProvenanceResult,
+};
 use concordium_std::*;
 
+/// Plain value envelope version. Enveloped values are stored as `(version: u8) (value: u8 * N)`, so the value
+/// format can evolve without rewriting all existing data at once.
+pub const VALUE_ENVELOPE_VERSION: u8 = 1;
+
+/// Current value envelope version, which records the writer of the value. Values are stored as
+/// `(version: u8) (provenance: StorageProvenance) (value: u8 * N)`. Envelope version is read from every value, so a
+/// prefix can hold values of both envelopes.
+pub const ATTESTED_ENVELOPE_VERSION: u8 = 2;
+
+/// Wrap value into the envelope of given version. Values of legacy prefixes are stored as is.
+pub fn encode_value(version: Option<u8>, value: Bytes) -> Bytes {
+    match version {
+        Some(version) => {
+            let mut buffer = Vec::with_capacity(value.len() + 1);
+            buffer.push(version);
+            buffer.extend_from_slice(&value);
+            Bytes(buffer)
+        }
+        None => value,
+    }
+}
+
+/// Wrap value into the attested envelope. Values of legacy prefixes are stored as is.
+pub fn encode_attested_value(
+    version: Option<u8>,
+    provenance: &StorageProvenance,
+    value: Bytes,
+) -> Bytes {
+    match version {
+        Some(_) => {
+            let mut buffer = to_bytes(&(ATTESTED_ENVELOPE_VERSION, *provenance));
+            buffer.extend_from_slice(&value);
+            Bytes(buffer)
+        }
+        None => value,
+    }
+}
+
+/// Split stored value into its provenance and the value without envelope. Provenance is `None` for legacy prefixes
+/// and plain envelopes.
+pub fn open_value(version: Option<u8>, value: &Bytes) -> (Option<StorageProvenance>, Bytes) {
+    if version.is_none() {
+        return (None, value.clone());
+    }
+
+    let mut cursor = Cursor::new(value.get(1..).unwrap_or_default());
+    let provenance = match value.first() {
+        Some(&ATTESTED_ENVELOPE_VERSION) => StorageProvenance::deserial(&mut cursor).ok(),
+        _ => None,
+    };
+    let rest = cursor.data.get(cursor.offset..).unwrap_or_default();
+    (provenance, Bytes(rest.to_vec()))
+}
+
+/// Strip the envelope from stored value.
+pub fn decode_value(version: Option<u8>, value: &Bytes) -> Bytes {
+    open_value(version, value).1
+}
+
+/// Current layout version of exported prefixes.
+pub const EXPORT_FORMAT_VERSION: u8 = 1;
+
+/// All data of a single prefix, used to move prefixes between storage instances. Entries are sorted by key and hold
+/// values without envelope, so the same data always produces the same export.
+#[derive(Debug, Serialize, SchemaType, PartialEq, Eq)]
+pub struct PrefixExport {
+    /// Layout version, must be equal to `EXPORT_FORMAT_VERSION` on import
+    pub version: u8,
+    pub prefix: Bytes,
+    /// Writer that inserted the prefix
+    pub owner: Option<Address>,
+    pub expiry: Option<Timestamp>,
+    pub entries: Vec<StorageEntry>,
+}
+
+/// Amount of stored data, tracked as entries are written and removed.
+#[derive(Debug, Default, Clone, Copy, Serialize, SchemaType, PartialEq, Eq)]
+pub struct StorageStats {
+    /// Number of prefixes
+    pub prefixes: u64,
+    /// Number of keys over all prefixes
+    pub keys: u64,
+    /// Total size of keys and stored values, including value envelopes
+    pub bytes: u64,
+}
+
+impl StorageStats {
+    fn add_entry(&mut self, key: &Bytes, value: &Bytes) {
+        self.keys += 1;
+        self.bytes += (key.len() + value.len()) as u64;
+    }
+
+    fn remove_entry(&mut self, key: &Bytes, value: &Bytes) {
+        self.keys = self.keys.saturating_sub(1);
+        self.bytes = self.bytes.saturating_sub((key.len() + value.len()) as u64);
+    }
+}
+
 #[derive(Debug, Serial, DeserialWithState)]
 #[concordium(state_parameter = "S")]
 pub struct State<S: HasStateApi> {
     /// Addresses that are allowed to modify storage data
     pub writers: StateSet<Address, S>,
+    /// Writers that are allowed to modify data under any prefix, regardless of its owner
+    pub global_writers: StateSet<Address, S>,
     pub authority: Authority<S>,
     /// Key-value data storage
     pub storage: StateMap<Bytes, StateMap<Bytes, Bytes, S>, S>,
+    /// Writer that inserted the prefix
+    pub prefix_owners: StateMap<Bytes, Address, S>,
+    /// Writers allowed to modify data under the prefix besides its owner
+    pub prefix_operators: StateMap<Bytes, Vec<Address>, S>,
+    /// Value envelope version of each prefix. Prefixes missing here hold legacy values without envelope.
+    pub value_versions: StateMap<Bytes, u8, S>,
+    /// Time after which prefix data is considered stale
+    pub expiries: StateMap<Bytes, Timestamp, S>,
+    /// Running totals of stored prefixes, keys and bytes
+    pub stats: StorageStats,
+    /// Sequence number of the last write, recorded with written values
+    pub sequence: u64,
 }
 
 impl<S: HasStateApi> State<S> {
@@ -16,12 +132,302 @@ impl<S: HasStateApi> State<S> {
         let authority = Authority::new(state_builder, Address::Account(admin));
         Self {
             writers: state_builder.new_set(),
+            global_writers: state_builder.new_set(),
             authority,
             storage: state_builder.new_map(),
+            prefix_owners: state_builder.new_map(),
+            prefix_operators: state_builder.new_map(),
+            value_versions: state_builder.new_map(),
+            expiries: state_builder.new_map(),
+            stats: StorageStats::default(),
+            sequence: 0,
         }
     }
 
     pub fn has_writer_rights(&self, addr: &Address) -> bool {
-        self.writers.contains(addr)
+        self.writers.contains(addr) || self.global_writers.contains(addr)
+    }
+
+    /// Check if writer is allowed to modify data under existing prefix, either as its owner or as one of its
+    /// operators.
+    pub fn has_prefix_rights(&self, prefix: &Bytes, addr: &Address) -> bool {
+        self.has_prefix_owner_rights(prefix, addr)
+            || self
+                .prefix_operators
+                .get(prefix)
+                .map_or(false, |operators| operators.contains(addr))
+    }
+
+    /// Check if writer is allowed to manage the prefix as its owner. Prefixes without a recorded owner can be managed
+    /// by any writer.
+    pub fn has_prefix_owner_rights(&self, prefix: &Bytes, addr: &Address) -> bool {
+        if self.global_writers.contains(addr) {
+            return true;
+        }
+
+        match self.prefix_owners.get(prefix) {
+            Some(owner) => *owner == *addr,
+            None => self.writers.contains(addr),
+        }
+    }
+
+    pub fn value_version(&self, prefix: &Bytes) -> Option<u8> {
+        self.value_versions.get(prefix).map(|v| *v)
+    }
+
+    /// Start a new write by `writer`, returning provenance to record with the written values.
+    fn next_write(&mut self, writer: Address) -> StorageProvenance {
+        self.sequence += 1;
+        StorageProvenance {
+            writer,
+            sequence: self.sequence,
+        }
+    }
+
+    /// Create a new prefix owned by `owner`, who is also recorded as the writer of the values. Values are wrapped
+    /// into the current envelope.
+    pub fn insert_prefix(
+        &mut self,
+        state_builder: &mut StateBuilder<S>,
+        owner: Address,
+        params: StorageEntries,
+    ) {
+        let version = Some(ATTESTED_ENVELOPE_VERSION);
+        let provenance = self.next_write(owner);
+        let mut map = state_builder.new_map();
+        for entry in params.entries {
+            let value = encode_attested_value(version, &provenance, entry.value);
+            self.stats.add_entry(&entry.key, &value);
+            if let Some(old) = map.insert(entry.key.clone(), value) {
+                self.stats.remove_entry(&entry.key, &old);
+            }
+        }
+        self.storage.insert(params.prefix.clone(), map);
+        self.stats.prefixes += 1;
+        self.prefix_owners.insert(params.prefix.clone(), owner);
+        self.value_versions
+            .insert(params.prefix, ATTESTED_ENVELOPE_VERSION);
+    }
+
+    /// Merge entries written by `writer` into an existing prefix, overwriting values of existing keys. Returns
+    /// `false` if the prefix is missing.
+    pub fn update_prefix(&mut self, writer: Address, params: StorageEntries) -> bool {
+        let version = self.value_version(&params.prefix);
+        let provenance = self.next_write(writer);
+
+        match self.storage.get_mut(&params.prefix) {
+            Some(mut map) => {
+                for entry in params.entries {
+                    let value = encode_attested_value(version, &provenance, entry.value);
+                    self.stats.add_entry(&entry.key, &value);
+                    if let Some(old) = map.insert(entry.key.clone(), value) {
+                        self.stats.remove_entry(&entry.key, &old);
+                    }
+                }
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Remove the prefix with all of its entries and metadata. Returns `false` if the prefix is missing.
+    pub fn remove_prefix(&mut self, prefix: &Bytes) -> bool {
+        match self.storage.remove_and_get(prefix) {
+            Some(map) => {
+                for (key, value) in map.iter() {
+                    self.stats.remove_entry(&key, &value);
+                }
+                map.delete();
+                self.stats.prefixes = self.stats.prefixes.saturating_sub(1);
+                self.prefix_owners.remove(prefix);
+                self.prefix_operators.remove(prefix);
+                self.value_versions.remove(prefix);
+                self.expiries.remove(prefix);
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Replace all operators of the prefix. Returns `false` if the prefix is missing.
+    pub fn replace_operators(&mut self, prefix: Bytes, operators: Vec<Address>) -> bool {
+        if self.storage.get(&prefix).is_none() {
+            return false;
+        }
+        if operators.is_empty() {
+            self.prefix_operators.remove(&prefix);
+        } else {
+            self.prefix_operators.insert(prefix, operators);
+        }
+        true
+    }
+
+    /// Remove keys from the prefix. Returns `false` if the prefix is missing.
+    pub fn remove_keys(&mut self, prefix: &Bytes, keys: &[Bytes]) -> bool {
+        match self.storage.get_mut(prefix) {
+            Some(mut map) => {
+                for key in keys {
+                    if let Some(old) = map.remove_and_get(key) {
+                        self.stats.remove_entry(key, &old);
+                    }
+                }
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Check if the prefix has passed its expiry time. Prefixes without expiry never expire.
+    pub fn is_expired(&self, prefix: &Bytes, now: Timestamp) -> bool {
+        self.expiries
+            .get(prefix)
+            .map(|expiry| *expiry < now)
+            .unwrap_or(false)
+    }
+
+    /// Get selected entries under the prefix. Returns `None` if the prefix is missing.
+    pub fn get_entries(
+        &self,
+        params: StorageKeys,
+        now: Timestamp,
+    ) -> Option<StorageGetEntryResult> {
+        self.get_attested_entries(params, now)
+            .map(|result| StorageGetEntryResult {
+                prefix: result.prefix,
+                entries: result
+                    .entries
+                    .into_iter()
+                    .map(|entry| MaybeStorageEntry {
+                        key: entry.key,
+                        value: entry.value,
+                    })
+                    .collect(),
+                expired: result.expired,
+            })
+    }
+
+    /// Get selected entries under the prefix together with their writers. Returns `None` if the prefix is missing.
+    pub fn get_attested_entries(
+        &self,
+        params: StorageKeys,
+        now: Timestamp,
+    ) -> Option<StorageProvenanceResult> {
+        let version = self.value_version(&params.prefix);
+        let expired = self.is_expired(&params.prefix, now);
+        let attest = |key: Bytes, value: Option<&Bytes>| {
+            let (provenance, value) = match value.map(|value| open_value(version, value)) {
+                Some((provenance, value)) => (provenance, Some(value)),
+                None => (None, None),
+            };
+            AttestedStorageEntry {
+                key,
+                value,
+                provenance,
+            }
+        };
+
+        self.storage.get(&params.prefix).map(|map| {
+            let entries = match params.keys {
+                StorageKeySelection::All => map
+                    .iter()
+                    .map(|(key, value)| attest(key.clone(), Some(&*value)))
+                    .collect(),
+                StorageKeySelection::Some(key_list) => key_list
+                    .into_iter()
+                    .map(|key| {
+                        let value = map.get(&key);
+                        attest(key, value.as_deref())
+                    })
+                    .collect(),
+            };
+            StorageProvenanceResult {
+                prefix: params.prefix,
+                entries,
+                expired,
+            }
+        })
+    }
+
+    /// Get up to `limit` entries under the prefix, starting after the cursor key. Entries are listed in the iteration
+    /// order of the state, which is the byte order of serialized keys, and iteration stops after the page. Returns
+    /// `None` if the prefix is missing.
+    pub fn get_page(&self, params: StoragePageParams, now: Timestamp) -> Option<StoragePage> {
+        let version = self.value_version(&params.prefix);
+        let expired = self.is_expired(&params.prefix, now);
+        let map = self.storage.get(&params.prefix)?;
+
+        // Cursor key may have been removed since the previous page, so keys are compared instead of looked up
+        let start_after_key = params.start_after_key.as_ref().map(to_bytes);
+        let mut iter = map.iter().skip_while(|(key, _)| match &start_after_key {
+            Some(start_after_key) => to_bytes(&**key) <= *start_after_key,
+            None => false,
+        });
+
+        let mut entries = Vec::with_capacity(params.limit as usize);
+        for (key, value) in iter.by_ref().take(params.limit as usize) {
+            entries.push(StorageEntry {
+                key: key.clone(),
+                value: decode_value(version, &value),
+            });
+        }
+        let next_key = if iter.next().is_some() {
+            entries.last().map(|entry| entry.key.clone())
+        } else {
+            None
+        };
+
+        Some(StoragePage {
+            prefix: params.prefix,
+            entries,
+            next_key,
+            expired,
+        })
+    }
+
+    /// Export all data under the prefix. Returns `None` if the prefix is missing.
+    pub fn export_prefix(&self, prefix: Bytes) -> Option<PrefixExport> {
+        let version = self.value_version(&prefix);
+        let map = self.storage.get(&prefix)?;
+        let mut entries: Vec<StorageEntry> = map
+            .iter()
+            .map(|(key, value)| StorageEntry {
+                key: key.clone(),
+                value: decode_value(version, &value),
+            })
+            .collect();
+        entries.sort_by(|a, b| a.key.cmp(&b.key));
+
+        Some(PrefixExport {
+            version: EXPORT_FORMAT_VERSION,
+            owner: self.prefix_owners.get(&prefix).map(|owner| *owner),
+            expiry: self.expiries.get(&prefix).map(|expiry| *expiry),
+            prefix,
+            entries,
+        })
+    }
+
+    /// Recreate exported prefix. Values are wrapped into the current envelope and attested as written by `importer`.
+    /// Prefixes exported without owner are owned by `importer`.
+    pub fn import_prefix(
+        &mut self,
+        state_builder: &mut StateBuilder<S>,
+        importer: Address,
+        export: PrefixExport,
+    ) {
+        let prefix = export.prefix.clone();
+        self.insert_prefix(
+            state_builder,
+            importer,
+            StorageEntries {
+                prefix: export.prefix,
+                entries: export.entries,
+            },
+        );
+        if let Some(owner) = export.owner {
+            self.prefix_owners.insert(prefix.clone(), owner);
+        }
+        if let Some(expiry) = export.expiry {
+            self.expiries.insert(prefix, expiry);
+        }
     }
 }
@@ -1,17 +1,29 @@
 use core::convert::TryFrom;
 
 use commons_v1::{
-    AuthorityUpdateParams, AuthorityViewParams, ContractReadError, CustomContractError, DomainKind,
-    DomainPrice, HostCnsNftExt, HostCnsOracleExt, HostStorageExt, StorageEntriesRef,
-    StorageKeysRef, SubscriptionExpiryStatus,
+    supports_standards, AuthorityUpdateParams, AuthorityViewParams, Bytes, Capability,
+    CapabilityUpdateParams, ContractReadError, CustomContractError, DomainKind, DomainPrice,
+    GetDomainPriceParams, HostCnsNftExt, HostCnsOracleExt, HostStorageExt, Percentage,
+    StandardIdentifier, StorageEntriesRef, StorageKeysRef, SubscriptionExpiryStatus,
+    SupportsQueryParams, SupportsQueryResponse, TokenExpiry, BICTORY_AUTHORITY_STANDARD_IDENTIFIER,
+    CIS0_STANDARD_IDENTIFIER,
 };
-use concordium_cis1::TokenIdVec;
+use concordium_cis1::{Receiver, TokenIdVec};
 use concordium_std::*;
 use sha3::{Digest, Keccak256};
+use unicode_normalization::UnicodeNormalization;
+use unicode_script::ScriptExtension;
 
+use crate::events::{record_hash, CnsEvent};
 use crate::external::*;
 use crate::state::State;
-use crate::YEAR_MILLIS;
+use crate::{MAX_BENEFICIARIES, MAX_DATA_KEY_LENGTH, YEAR_MILLIS};
+
+/// Standards supported by this contract.
+const SUPPORTED_STANDARDS: [StandardIdentifier<'static>; 2] = [
+    CIS0_STANDARD_IDENTIFIER,
+    BICTORY_AUTHORITY_STANDARD_IDENTIFIER,
+];
 
 #[init(contract = "BictoryCns", parameter = "InitParams")]
 fn init<S: HasStateApi>(
@@ -36,121 +48,375 @@ fn init<S: HasStateApi>(
     payable,
     contract = "BictoryCns",
     name = "register",
-    parameter = "RegisterParams"
+    parameter = "RegisterParams",
+    enable_logger
 )]
 fn register<S: HasStateApi>(
     ctx: &impl HasReceiveContext,
     host: &mut impl HasHost<State<S>, StateApiType = S>,
     amount: Amount,
+    logger: &mut impl HasLogger,
 ) -> ReceiveResult<()> {
+    ensure_direct_registration(ctx, host)?;
     let params =
         RegisterParams::deserial(&mut ctx.parameter_cursor()).map_err(CustomContractError::from)?;
-    let domain = TokenizedDomain::try_from(params.domain.as_ref())?;
+    let registration = prepare_registration(ctx, host, params)?;
+    let total_price = registration.price;
+    ensure!(
+        total_price <= amount,
+        CustomContractError::InsufficientFunds.into()
+    );
 
-    // Only domains can be registered with this function
+    // Transfer the fee to the beneficiaries
+    transfer_fee(host, total_price)?;
+
+    // Refund the remaining CCD if necessary
+    if amount - total_price != Amount::zero() {
+        host.invoke_transfer(&ctx.invoker(), amount - total_price)?;
+    }
+
+    complete_registration(ctx, host, logger, registration, ctx.sender())
+}
+
+/// Register several domains at once, e.g. a name together with its common typos. Prices of all domains are queried
+/// from the price oracle with a single call. Attached amount has to cover the total price of all domains, with the
+/// remainder refunded in a single transfer. Either all domains are registered or the whole call is rejected.
+#[receive(
+    mutable,
+    payable,
+    contract = "BictoryCns",
+    name = "registerMany",
+    parameter = "Vec<RegisterParams>",
+    enable_logger
+)]
+fn register_many<S: HasStateApi>(
+    ctx: &impl HasReceiveContext,
+    host: &mut impl HasHost<State<S>, StateApiType = S>,
+    amount: Amount,
+    logger: &mut impl HasLogger,
+) -> ReceiveResult<()> {
+    ensure_direct_registration(ctx, host)?;
+    let params = <Vec<RegisterParams>>::deserial(&mut ctx.parameter_cursor())
+        .map_err(CustomContractError::from)?;
     ensure!(
-        !domain.is_subdomain(),
-        CustomContractError::InvalidDomainFormat.into()
+        !params.is_empty(),
+        CustomContractError::InvalidFields.into()
     );
 
-    // Ensure registration duration does not exceed `subscription_year_limit` years in the future
+    let mut checked: Vec<(Registration, u16)> = Vec::with_capacity(params.len());
+    for params in params {
+        let (registration, length) = check_registration(ctx, host, params)?;
+        // Same domain can't be registered twice in one call
+        ensure!(
+            checked
+                .iter()
+                .all(|(r, _)| r.namehash != registration.namehash),
+            CustomContractError::AlreadyExists.into()
+        );
+        checked.push((registration, length));
+    }
+
+    let price_requests = checked
+        .iter()
+        .map(|(_, length)| GetDomainPriceParams {
+            domain_kind: DomainKind::Domain,
+            length: *length,
+            renewal_in_grace: false,
+        })
+        .collect();
+    let prices = host
+        .cns_get_yearly_domain_prices(&host.state().price_oracle, price_requests)
+        .map_err(handle_get_error)?;
+
+    let mut registrations: Vec<Registration> = Vec::with_capacity(checked.len());
+    let mut total_price = Amount::zero();
+    for ((registration, _), pricing) in checked.into_iter().zip(prices) {
+        let registration = price_registration(ctx, host, registration, pricing)?;
+        total_price = total_price
+            .micro_ccd
+            .checked_add(registration.price.micro_ccd)
+            .map(Amount::from_micro_ccd)
+            .ok_or(CustomContractError::Overflow)?;
+        registrations.push(registration);
+    }
+
     ensure!(
-        params.duration_years <= host.state().subscription_year_limit,
-        CustomContractError::InvalidDuration.into()
+        total_price <= amount,
+        CustomContractError::InsufficientFunds.into()
     );
 
-    let namehash = domain.namehash();
+    // Transfer the fee to the beneficiaries
+    transfer_fee(host, total_price)?;
 
-    let registry = host.state().registry;
-    let nft = host.state().nft;
+    // Refund the remaining CCD if necessary
+    if amount - total_price != Amount::zero() {
+        host.invoke_transfer(&ctx.invoker(), amount - total_price)?;
+    }
 
-    let token_id = TokenIdVec(namehash.into());
-    let ownership = host
-        .cns_nft_get_token_expiry(&nft, token_id.clone())
-        .map_err(handle_get_error)?;
+    for registration in registrations {
+        complete_registration(ctx, host, logger, registration, ctx.sender())?;
+    }
 
-    // Check if token exists
-    if let Some(ownership_data) = ownership {
-        // Check if token has already expired
+    Ok(())
+}
+
+/// Commit to a registration without revealing the domain, so that the registration can't be frontrun. Commitment is
+/// `keccak256(RegisterParams || sender || salt)` and can be revealed with `reveal` once it's old enough.
+#[receive(
+    mutable,
+    contract = "BictoryCns",
+    name = "commit",
+    parameter = "[u8; 32]"
+)]
+fn commit<S: HasStateApi>(
+    ctx: &impl HasReceiveContext,
+    host: &mut impl HasHost<State<S>, StateApiType = S>,
+) -> ReceiveResult<()> {
+    let commitment =
+        <[u8; 32]>::deserial(&mut ctx.parameter_cursor()).map_err(CustomContractError::from)?;
+    let slot_time = ctx.metadata().slot_time();
+    let state = host.state_mut();
+
+    ensure!(
+        state.commit_reveal_enabled(),
+        CustomContractError::Unsupported.into()
+    );
+
+    // Commitment can't be refreshed before it expires, otherwise it would never mature
+    let committed_at = state.commitments.get(&commitment).map(|time| *time);
+    if let Some(committed_at) = committed_at {
         ensure!(
-            ownership_data.is_expired(),
+            state.commitment_expired(committed_at, slot_time),
             CustomContractError::AlreadyExists.into()
         );
-
-        host.cns_nft_burn(&nft, &token_id)
-            .map_err(handle_call_error)?;
     }
 
-    let pricing = host
-        .cns_get_yearly_domain_price(
-            &host.state().price_oracle,
-            DomainKind::Domain,
-            domain.char_count(),
-        )
-        .map_err(handle_get_error)?;
+    state.commitments.insert(commitment, slot_time);
 
-    let yearly_price = match pricing {
-        DomainPrice::Limited => {
-            // Only maintainers and admins are allowed to create domains with limited pricing and registration policy
-            ensure!(
-                host.state().authority.has_maintainer_rights(&ctx.sender()),
-                CustomContractError::Unauthorized.into()
-            );
-            Amount::zero()
-        }
-        DomainPrice::Amount(yearly_price) => yearly_price,
-    };
-    let total_price = yearly_price * params.duration_years as u64;
+    Ok(())
+}
+
+/// Register the domain committed to with `commit`. Attached amount has to cover the subscription price, the remainder
+/// is refunded.
+#[receive(
+    mutable,
+    payable,
+    contract = "BictoryCns",
+    name = "reveal",
+    parameter = "RevealParams",
+    enable_logger
+)]
+fn reveal<S: HasStateApi>(
+    ctx: &impl HasReceiveContext,
+    host: &mut impl HasHost<State<S>, StateApiType = S>,
+    amount: Amount,
+    logger: &mut impl HasLogger,
+) -> ReceiveResult<()> {
+    let params =
+        RevealParams::deserial(&mut ctx.parameter_cursor()).map_err(CustomContractError::from)?;
+    let commitment = registration_commitment(&params.registration, &ctx.sender(), &params.salt);
+    let slot_time = ctx.metadata().slot_time();
+    let state = host.state_mut();
+
+    let committed_at = state
+        .commitments
+        .get(&commitment)
+        .map(|time| *time)
+        .ok_or(CustomContractError::NotFound)?;
+    ensure!(
+        state.commitment_matured(committed_at, slot_time),
+        CustomContractError::OperationNotPermitted.into()
+    );
+    ensure!(
+        !state.commitment_expired(committed_at, slot_time),
+        CustomContractError::NotFound.into()
+    );
+    state.commitments.remove(&commitment);
+
+    let registration = prepare_registration(ctx, host, params.registration)?;
+    let total_price = registration.price;
+    ensure!(
+        total_price <= amount,
+        CustomContractError::InsufficientFunds.into()
+    );
+
+    // Transfer the fee to the beneficiaries
+    transfer_fee(host, total_price)?;
 
-    // Transfer the fee to the beneficiary
-    if total_price != Amount::zero() {
-        host.invoke_transfer(&host.state().beneficiary, total_price)?;
+    // Refund the remaining CCD if necessary
+    if amount - total_price != Amount::zero() {
+        host.invoke_transfer(&ctx.invoker(), amount - total_price)?;
     }
 
+    complete_registration(ctx, host, logger, registration, ctx.sender())
+}
+
+/// Register a domain into a voucher instead of a specific owner, e.g. as a gift. Domain token is held by this contract
+/// and the domain resolves to it until someone claims the voucher with `claimVoucher`. Domain is checked and paid for
+/// the same way as in `register`, the remainder of the attached amount is refunded.
+#[receive(
+    mutable,
+    payable,
+    contract = "BictoryCns",
+    name = "registerVoucher",
+    parameter = "RegisterVoucherParams",
+    enable_logger
+)]
+fn register_voucher<S: HasStateApi>(
+    ctx: &impl HasReceiveContext,
+    host: &mut impl HasHost<State<S>, StateApiType = S>,
+    amount: Amount,
+    logger: &mut impl HasLogger,
+) -> ReceiveResult<()> {
+    ensure_direct_registration(ctx, host)?;
+    let params = RegisterVoucherParams::deserial(&mut ctx.parameter_cursor())
+        .map_err(CustomContractError::from)?;
+    ensure!(
+        host.state().vouchers.get(&params.code_hash).is_none(),
+        CustomContractError::AlreadyExists.into()
+    );
+
+    let holder = Address::Contract(ctx.self_address());
+    let registration = prepare_registration(
+        ctx,
+        host,
+        RegisterParams {
+            domain: params.domain,
+            address: holder,
+            duration_years: params.duration_years,
+        },
+    )?;
+    let total_price = registration.price;
+    ensure!(
+        total_price <= amount,
+        CustomContractError::InsufficientFunds.into()
+    );
+
+    // Transfer the fee to the beneficiaries
+    transfer_fee(host, total_price)?;
+
     // Refund the remaining CCD if necessary
     if amount - total_price != Amount::zero() {
         host.invoke_transfer(&ctx.invoker(), amount - total_price)?;
     }
 
-    host.cns_nft_mint(
-        &nft,
-        token_id,
-        params.domain,
-        ctx.sender(),
-        Duration::from_millis(YEAR_MILLIS * params.duration_years as u64),
+    let domain = registration.params.domain.clone();
+    complete_registration(ctx, host, logger, registration, holder)?;
+    host.state_mut().vouchers.insert(params.code_hash, domain);
+
+    Ok(())
+}
+
+/// Claim a domain registered with `registerVoucher` by presenting the code the voucher was made with. Domain token is
+/// transferred to the sender and the domain resolves to the sender afterwards.
+#[receive(
+    mutable,
+    contract = "BictoryCns",
+    name = "claimVoucher",
+    parameter = "[u8; 32]",
+    enable_logger
+)]
+fn claim_voucher<S: HasStateApi>(
+    ctx: &impl HasReceiveContext,
+    host: &mut impl HasHost<State<S>, StateApiType = S>,
+    logger: &mut impl HasLogger,
+) -> ReceiveResult<()> {
+    let code =
+        <[u8; 32]>::deserial(&mut ctx.parameter_cursor()).map_err(CustomContractError::from)?;
+    // Token can only be transferred to contracts together with an entrypoint, so vouchers are claimed by accounts
+    let owner = match ctx.sender() {
+        Address::Account(account) => account,
+        Address::Contract(_) => return Err(CustomContractError::Unauthorized.into()),
+    };
+
+    let code_hash = voucher_code_hash(&code);
+    let domain = host
+        .state()
+        .vouchers
+        .get(&code_hash)
+        .map(|domain| domain.clone())
+        .ok_or(CustomContractError::NotFound)?;
+    let namehash = TokenizedDomain::try_from(domain.as_ref())?.namehash();
+    ensure_not_frozen(host, &namehash)?;
+
+    let holder = Address::Contract(ctx.self_address());
+    let token_id = TokenIdVec(namehash.into());
+    let registry = host.state().registry;
+    let nft = host.state().nft;
+
+    // Voucher is void once the domain expires or is registered again by someone else
+    let subscription_status = host
+        .cns_nft_get_token_expiry(&nft, token_id.clone())
+        .map_err(handle_get_error)?
+        .ok_or(CustomContractError::NotFound)?;
+    match subscription_status.expiry {
+        SubscriptionExpiryStatus::Owned(_) if subscription_status.owner == holder => (),
+        _ => return Err(CustomContractError::NotFound.into()),
+    }
+
+    host.cns_nft_transfer(&nft, token_id, holder, Receiver::Account(owner))
+        .map_err(handle_call_error)?;
+    host.storage_update(
+        &registry,
+        namehash.as_slice().into(),
+        &(),
+        &Address::Account(owner),
     )
     .map_err(handle_call_error)?;
+    host.state_mut().vouchers.remove(&code_hash);
 
-    // Insert operation fails with AlreadyExists if entry is present. Token data is burnt without removing the
-    // registry data. Try clearing old registry data before inserting new. If NFT was successfully minted, this means
-    // that it is okay to remove previous registry data, if present.
-    //
-    // We ignore errors on remove call because, `CustomContractError::NotFound` is expected to be returned often and
-    // should be ignored. `insert` call will produce any other error that we may ignore from `remove` call.
-    let _ = host.storage_remove_raw(&registry, &StorageKeysRef::all(namehash.as_slice().into()));
-    host.storage_insert(&registry, namehash.as_slice().into(), &(), &params.address)
-        .map_err(handle_call_error)?;
+    logger.log(&CnsEvent::VoucherClaimed {
+        domain,
+        owner: Address::Account(owner),
+    })?;
 
     Ok(())
 }
 
+/// Get the domain of an unclaimed voucher by its code hash.
+#[receive(
+    contract = "BictoryCns",
+    name = "getVoucher",
+    parameter = "[u8; 32]",
+    return_value = "Option<String>"
+)]
+fn get_voucher<S: HasStateApi>(
+    ctx: &impl HasReceiveContext,
+    host: &impl HasHost<State<S>, StateApiType = S>,
+) -> ReceiveResult<Option<String>> {
+    let code_hash =
+        <[u8; 32]>::deserial(&mut ctx.parameter_cursor()).map_err(CustomContractError::from)?;
+    Ok(host
+        .state()
+        .vouchers
+        .get(&code_hash)
+        .map(|domain| domain.clone()))
+}
+
 #[receive(
     mutable,
     payable,
     contract = "BictoryCns",
     name = "extend",
-    parameter = "ExtendParams"
+    parameter = "ExtendParams",
+    enable_logger
 )]
 fn extend<S: HasStateApi>(
     ctx: &impl HasReceiveContext,
     host: &mut impl HasHost<State<S>, StateApiType = S>,
     amount: Amount,
+    logger: &mut impl HasLogger,
 ) -> ReceiveResult<()> {
     let params =
         ExtendParams::deserial(&mut ctx.parameter_cursor()).map_err(CustomContractError::from)?;
     let domain = TokenizedDomain::try_from(params.domain.as_ref())?;
+    // Domain token is minted with the normalized name
+    let params = ExtendParams {
+        domain: domain.name(),
+        ..params
+    };
 
-    // Only domains can be registered with this function
+    // Only domains can be extended with this function
     ensure!(
         !domain.is_subdomain(),
         CustomContractError::InvalidDomainFormat.into()
@@ -186,19 +452,24 @@ fn extend<S: HasStateApi>(
         SubscriptionExpiryStatus::Expired => return Err(CustomContractError::NotFound.into()),
     };
 
+    // Late renewals are priced with the grace renewal tier of the oracle
+    let renewal_in_grace = matches!(ownership.expiry, SubscriptionExpiryStatus::Grace(_));
     let pricing = host
         .cns_get_yearly_domain_price(
             &host.state().price_oracle,
             DomainKind::Domain,
             domain.char_count(),
+            renewal_in_grace,
         )
         .map_err(handle_get_error)?;
 
     let yearly_price = match pricing {
         DomainPrice::Limited => {
-            // Only maintainers and admins are allowed to extend domains with limited pricing and registration policy
+            // Only holders of `CanMintReserved` can extend domains with limited pricing and registration policy
             ensure!(
-                host.state().authority.has_maintainer_rights(&ctx.sender()),
+                host.state()
+                    .authority
+                    .has_capability(&ctx.sender(), Capability::CanMintReserved),
                 CustomContractError::Unauthorized.into()
             );
             Amount::zero()
@@ -206,11 +477,13 @@ fn extend<S: HasStateApi>(
         DomainPrice::Amount(yearly_price) => yearly_price,
     };
     let total_price = yearly_price * params.duration_years as u64;
+    ensure!(
+        total_price <= amount,
+        CustomContractError::InsufficientFunds.into()
+    );
 
-    // Transfer the fee to the beneficiary
-    if total_price != Amount::zero() {
-        host.invoke_transfer(&host.state().beneficiary, total_price)?;
-    }
+    // Transfer the fee to the beneficiaries
+    transfer_fee(host, total_price)?;
 
     // Lend the NFT
     host.cns_nft_lend(&nft, token_id, extension_duration)
@@ -221,6 +494,11 @@ fn extend<S: HasStateApi>(
         host.invoke_transfer(&ctx.invoker(), amount - total_price)?;
     }
 
+    logger.log(&CnsEvent::Extended {
+        domain: params.domain,
+        duration_years: params.duration_years,
+    })?;
+
     Ok(())
 }
 
@@ -228,30 +506,22 @@ fn extend<S: HasStateApi>(
     mutable,
     contract = "BictoryCns",
     name = "setAddress",
-    parameter = "SetAddressParams"
+    parameter = "SetAddressParams",
+    enable_logger
 )]
 fn set_address<S: HasStateApi>(
     ctx: &impl HasReceiveContext,
     host: &mut impl HasHost<State<S>, StateApiType = S>,
+    logger: &mut impl HasLogger,
 ) -> ReceiveResult<()> {
     let params = SetAddressParams::deserial(&mut ctx.parameter_cursor())
         .map_err(CustomContractError::from)?;
     let domain = TokenizedDomain::try_from(params.domain.as_ref())?;
     let (domain_namehash, subdomain_namehash) = domain.domain_subdomain_namehashes();
-    let token_id = TokenIdVec(domain_namehash.into());
+    ensure_not_frozen(host, &domain_namehash)?;
+    ensure_record_owner(host, &domain, ctx.sender())?;
     let registry = host.state().registry;
 
-    let subscription_status = host
-        .cns_nft_get_token_expiry(&host.state().nft, token_id)
-        .map_err(handle_get_error)?
-        .ok_or(CustomContractError::NotFound)?;
-
-    match subscription_status.expiry {
-        SubscriptionExpiryStatus::Owned(_) if subscription_status.owner == ctx.sender() => (),
-        SubscriptionExpiryStatus::Expired => return Err(CustomContractError::NotFound.into()),
-        _ => return Err(CustomContractError::Unauthorized.into()),
-    }
-
     host.storage_update(
         &registry,
         subdomain_namehash.as_slice().into(),
@@ -260,23 +530,77 @@ fn set_address<S: HasStateApi>(
     )
     .map_err(handle_call_error)?;
 
-    // TODO: log AddressChanged event
+    logger.log(&CnsEvent::AddressChanged {
+        domain: params.domain,
+        address: params.address,
+    })?;
 
     Ok(())
 }
 
+/// Set the address the domain or subdomain resolves to on another chain, identified by its SLIP-44 coin type. Each coin
+/// type is stored under its own registry key, next to the Concordium address set with `setAddress`.
 #[receive(
+    mutable,
     contract = "BictoryCns",
-    name = "resolve",
-    parameter = "ResolveParams",
-    return_value = "Address"
+    name = "setChainAddress",
+    parameter = "SetChainAddressParams",
+    enable_logger
 )]
-fn resolve<S: HasStateApi>(
+fn set_chain_address<S: HasStateApi>(
+    ctx: &impl HasReceiveContext,
+    host: &mut impl HasHost<State<S>, StateApiType = S>,
+    logger: &mut impl HasLogger,
+) -> ReceiveResult<()> {
+    let params = SetChainAddressParams::deserial(&mut ctx.parameter_cursor())
+        .map_err(CustomContractError::from)?;
+    let domain = TokenizedDomain::try_from(params.domain.as_ref())?;
+    let (domain_namehash, subdomain_namehash) = domain.domain_subdomain_namehashes();
+    ensure_not_frozen(host, &domain_namehash)?;
+    ensure_record_owner(host, &domain, ctx.sender())?;
+    let registry = host.state().registry;
+
+    let key = ChainAddressKey(params.coin_type);
+    if params.address.0.is_empty() {
+        host.storage_remove(&registry, subdomain_namehash.as_slice().into(), &key)
+            .map_err(handle_call_error)?;
+    } else {
+        host.storage_update(
+            &registry,
+            subdomain_namehash.as_slice().into(),
+            &key,
+            &params.address,
+        )
+        .map_err(handle_call_error)?;
+    }
+
+    let address_hash = if params.address.0.is_empty() {
+        None
+    } else {
+        Some(record_hash(&params.address))
+    };
+    logger.log(&CnsEvent::ChainAddressChanged {
+        domain: params.domain,
+        coin_type: params.coin_type,
+        address_hash,
+    })?;
+
+    Ok(())
+}
+
+/// Get the address the domain or subdomain resolves to on the chain with the given SLIP-44 coin type.
+#[receive(
+    contract = "BictoryCns",
+    name = "resolveChain",
+    parameter = "ResolveChainParams",
+    return_value = "Bytes"
+)]
+fn resolve_chain<S: HasStateApi>(
     ctx: &impl HasReceiveContext,
     host: &impl HasHost<State<S>, StateApiType = S>,
-) -> ReceiveResult<Address> {
-    let params =
-        ResolveParams::deserial(&mut ctx.parameter_cursor()).map_err(CustomContractError::from)?;
+) -> ReceiveResult<Bytes> {
+    let params = ResolveChainParams::deserial(&mut ctx.parameter_cursor())
+        .map_err(CustomContractError::from)?;
     let domain = TokenizedDomain::try_from(params.domain.as_ref())?;
     let (domain_namehash, subdomain_namehash) = domain.domain_subdomain_namehashes();
     let state = host.state();
@@ -291,47 +615,344 @@ fn resolve<S: HasStateApi>(
         CustomContractError::NotFound.into()
     );
 
-    let address: Option<Address> = host
-        .storage_get(&state.registry, subdomain_namehash.as_slice().into(), &())
+    let address: Option<Bytes> = host
+        .storage_get(
+            &state.registry,
+            subdomain_namehash.as_slice().into(),
+            &ChainAddressKey(params.coin_type),
+        )
         .map_err(handle_get_error)?;
 
     address.ok_or(CustomContractError::NotFound.into())
 }
 
+/// Set the content the domain or subdomain points to, so browsers can resolve decentralized websites from its name.
 #[receive(
     mutable,
     contract = "BictoryCns",
-    name = "setData",
-    parameter = "SetDataParams"
+    name = "setContentHash",
+    parameter = "SetContentHashParams",
+    enable_logger
 )]
-fn set_data<S: HasStateApi>(
+fn set_content_hash<S: HasStateApi>(
     ctx: &impl HasReceiveContext,
     host: &mut impl HasHost<State<S>, StateApiType = S>,
+    logger: &mut impl HasLogger,
 ) -> ReceiveResult<()> {
-    let params =
-        SetDataParams::deserial(&mut ctx.parameter_cursor()).map_err(CustomContractError::from)?;
-    ensure!(
-        !params.key.is_empty(),
-        CustomContractError::ParseParams.into()
-    );
-
+    let params = SetContentHashParams::deserial(&mut ctx.parameter_cursor())
+        .map_err(CustomContractError::from)?;
     let domain = TokenizedDomain::try_from(params.domain.as_ref())?;
     let (domain_namehash, subdomain_namehash) = domain.domain_subdomain_namehashes();
-    let token_id = TokenIdVec(domain_namehash.into());
+    ensure_not_frozen(host, &domain_namehash)?;
+    ensure_record_owner(host, &domain, ctx.sender())?;
     let registry = host.state().registry;
 
-    let subscription_status = host
-        .cns_nft_get_token_expiry(&host.state().nft, token_id)
-        .map_err(handle_get_error)?
-        .ok_or(CustomContractError::NotFound)?;
-
-    match subscription_status.expiry {
-        SubscriptionExpiryStatus::Owned(_) if subscription_status.owner == ctx.sender() => (),
-        SubscriptionExpiryStatus::Expired => return Err(CustomContractError::NotFound.into()),
+    match &params.content_hash {
+        Some(content_hash) => host.storage_update(
+            &registry,
+            subdomain_namehash.as_slice().into(),
+            &ContentHashKey,
+            content_hash,
+        ),
+        None => host.storage_remove(
+            &registry,
+            subdomain_namehash.as_slice().into(),
+            &ContentHashKey,
+        ),
+    }
+    .map_err(handle_call_error)?;
+
+    logger.log(&CnsEvent::ContentHashChanged {
+        domain: params.domain,
+        record_hash: params.content_hash.as_ref().map(record_hash),
+    })?;
+
+    Ok(())
+}
+
+/// Get the content the domain or subdomain points to.
+#[receive(
+    contract = "BictoryCns",
+    name = "getContentHash",
+    parameter = "ResolveParams",
+    return_value = "ContentHash"
+)]
+fn get_content_hash<S: HasStateApi>(
+    ctx: &impl HasReceiveContext,
+    host: &impl HasHost<State<S>, StateApiType = S>,
+) -> ReceiveResult<ContentHash> {
+    let params =
+        ResolveParams::deserial(&mut ctx.parameter_cursor()).map_err(CustomContractError::from)?;
+    let domain = TokenizedDomain::try_from(params.domain.as_ref())?;
+    let (domain_namehash, subdomain_namehash) = domain.domain_subdomain_namehashes();
+    let state = host.state();
+
+    let ownership_data = host
+        .cns_nft_get_token_expiry(&state.nft, TokenIdVec(domain_namehash.into()))
+        .map_err(handle_get_error)?
+        .ok_or(CustomContractError::NotFound)?;
+
+    ensure!(
+        ownership_data.is_owned(),
+        CustomContractError::NotFound.into()
+    );
+
+    let content_hash: Option<ContentHash> = host
+        .storage_get(
+            &state.registry,
+            subdomain_namehash.as_slice().into(),
+            &ContentHashKey,
+        )
+        .map_err(handle_get_error)?;
+
+    content_hash.ok_or(CustomContractError::NotFound.into())
+}
+
+/// Transfer the domain token to a new owner and reassign its registry records in the same transaction, so the domain
+/// doesn't keep resolving to the values of the previous owner. The sender has to own the domain and make this
+/// contract an operator on the CNS NFT contract.
+///
+/// Subdomains go along with the domain. Subdomain tokens held by the previous owner are transferred to the new owner,
+/// or with `clear_data` the subdomains are deleted and their tokens burnt. Tokenized subdomains held by someone else
+/// are kept by their holders.
+#[receive(
+    mutable,
+    contract = "BictoryCns",
+    name = "transferDomain",
+    parameter = "TransferDomainParams",
+    enable_logger
+)]
+fn transfer_domain<S: HasStateApi>(
+    ctx: &impl HasReceiveContext,
+    host: &mut impl HasHost<State<S>, StateApiType = S>,
+    logger: &mut impl HasLogger,
+) -> ReceiveResult<()> {
+    let params = TransferDomainParams::deserial(&mut ctx.parameter_cursor())
+        .map_err(CustomContractError::from)?;
+    let domain = TokenizedDomain::try_from(params.domain.as_ref())?;
+
+    // Subdomains don't have own tokens and are transferred together with the domain
+    ensure!(
+        !domain.is_subdomain(),
+        CustomContractError::InvalidDomainFormat.into()
+    );
+
+    let namehash = domain.namehash();
+    ensure_not_frozen(host, &namehash)?;
+    let token_id = TokenIdVec(namehash.into());
+    let registry = host.state().registry;
+    let nft = host.state().nft;
+
+    let subscription_status = host
+        .cns_nft_get_token_expiry(&nft, token_id.clone())
+        .map_err(handle_get_error)?
+        .ok_or(CustomContractError::NotFound)?;
+
+    match subscription_status.expiry {
+        SubscriptionExpiryStatus::Owned(_) if subscription_status.owner == ctx.sender() => (),
+        SubscriptionExpiryStatus::Expired => return Err(CustomContractError::NotFound.into()),
         _ => return Err(CustomContractError::Unauthorized.into()),
     }
 
-    match params.value {
+    let to = params.to.address();
+    host.cns_nft_transfer(&nft, token_id, ctx.sender(), params.to.clone())
+        .map_err(handle_call_error)?;
+
+    transfer_subdomains(host, &domain, ctx.sender(), &params.to, params.clear_data)?;
+
+    if params.clear_data {
+        host.storage_remove_raw(&registry, &StorageKeysRef::all(namehash.as_slice().into()))
+            .map_err(handle_call_error)?;
+        host.storage_insert(&registry, namehash.as_slice().into(), &(), &params.address)
+            .map_err(handle_call_error)?;
+    } else {
+        host.storage_update(&registry, namehash.as_slice().into(), &(), &params.address)
+            .map_err(handle_call_error)?;
+    }
+
+    logger.log(&CnsEvent::DomainTransferred {
+        domain: params.domain,
+        from: ctx.sender(),
+        to,
+    })?;
+
+    Ok(())
+}
+
+/// Move subdomains of the domain from the previous owner, see `transferDomain`. Subdomains are found through the
+/// subdomain index, so subdomains created before the index was introduced are left as is.
+fn transfer_subdomains<S: HasStateApi>(
+    host: &mut impl HasHost<State<S>, StateApiType = S>,
+    domain: &TokenizedDomain,
+    from: Address,
+    to: &Receiver,
+    clear_data: bool,
+) -> ReceiveResult<()> {
+    let registry = host.state().registry;
+    let nft = host.state().nft;
+    let namehash = domain.namehash();
+    let index_prefix = subdomain_index_prefix(&namehash);
+
+    let labels: Vec<String> = match host
+        .storage_get_raw(
+            &registry,
+            &StorageKeysRef::all(index_prefix.as_slice().into()),
+        )
+        .map_err(handle_get_error)?
+    {
+        Some(index) => index
+            .entries
+            .iter()
+            .map(|entry| from_bytes::<String>(&entry.key.0))
+            .collect::<ParseResult<_>>()
+            .map_err(|_| CustomContractError::InvokeContractError)?,
+        None => Vec::new(),
+    };
+
+    for label in labels {
+        let subdomain =
+            TokenizedDomain::try_from(format!("{}.{}.ccd", label, domain.domain).as_str())?;
+        let subdomain_namehash = subdomain.namehash();
+
+        let mut minted = false;
+        let mut held = false;
+        if host
+            .state()
+            .tokenized_subdomains
+            .contains(&subdomain_namehash)
+        {
+            if let Some(status) = host
+                .cns_nft_get_token_expiry(&nft, TokenIdVec(subdomain_namehash.into()))
+                .map_err(handle_get_error)?
+            {
+                // Subdomain was sold or transferred on its own
+                if !status.is_expired() && status.owner != from {
+                    continue;
+                }
+                minted = true;
+                held = !status.is_expired();
+            }
+        }
+
+        if clear_data {
+            host.storage_remove_raw(
+                &registry,
+                &StorageKeysRef::all(subdomain_namehash.as_slice().into()),
+            )
+            .map_err(handle_call_error)?;
+            host.storage_remove(&registry, index_prefix.as_slice().into(), &label)
+                .map_err(handle_call_error)?;
+            if minted {
+                host.cns_nft_burn(&nft, &TokenIdVec(subdomain_namehash.into()))
+                    .map_err(handle_call_error)?;
+            }
+            host.state_mut()
+                .tokenized_subdomains
+                .remove(&subdomain_namehash);
+        } else if held {
+            host.cns_nft_transfer(
+                &nft,
+                TokenIdVec(subdomain_namehash.into()),
+                from,
+                to.clone(),
+            )
+            .map_err(handle_call_error)?;
+        }
+    }
+
+    Ok(())
+}
+
+#[receive(
+    contract = "BictoryCns",
+    name = "resolve",
+    parameter = "ResolveParams",
+    return_value = "Address"
+)]
+fn resolve<S: HasStateApi>(
+    ctx: &impl HasReceiveContext,
+    host: &impl HasHost<State<S>, StateApiType = S>,
+) -> ReceiveResult<Address> {
+    let params =
+        ResolveParams::deserial(&mut ctx.parameter_cursor()).map_err(CustomContractError::from)?;
+    let domain = TokenizedDomain::try_from(params.domain.as_ref())?;
+    let (domain_namehash, subdomain_namehash) = domain.domain_subdomain_namehashes();
+    let state = host.state();
+
+    let ownership_data = host
+        .cns_nft_get_token_expiry(&state.nft, TokenIdVec(domain_namehash.into()))
+        .map_err(handle_get_error)?
+        .ok_or(CustomContractError::NotFound)?;
+
+    ensure!(
+        ownership_data.is_owned(),
+        CustomContractError::NotFound.into()
+    );
+
+    let address: Option<Address> = host
+        .storage_get(&state.registry, subdomain_namehash.as_slice().into(), &())
+        .map_err(handle_get_error)?;
+
+    address.ok_or(CustomContractError::NotFound.into())
+}
+
+/// Get expiry and grace period end for each requested domain with a single CNS NFT call. Subdomains report the dates
+/// of their parent domain. Result is returned in the same order as requested domains, with `None` for domains that
+/// were never registered or have been burnt.
+#[receive(
+    contract = "BictoryCns",
+    name = "getExpiries",
+    parameter = "GetExpiriesParams",
+    return_value = "Vec<Option<TokenExpiry>>"
+)]
+fn get_expiries<S: HasStateApi>(
+    ctx: &impl HasReceiveContext,
+    host: &impl HasHost<State<S>, StateApiType = S>,
+) -> ReceiveResult<Vec<Option<TokenExpiry>>> {
+    let params = GetExpiriesParams::deserial(&mut ctx.parameter_cursor())
+        .map_err(CustomContractError::from)?;
+
+    let mut token_ids = Vec::with_capacity(params.domains.len());
+    for domain in params.domains.iter() {
+        let domain = TokenizedDomain::try_from(domain.as_ref())?;
+        let (domain_namehash, _) = domain.domain_subdomain_namehashes();
+        token_ids.push(TokenIdVec(domain_namehash.into()));
+    }
+
+    let expiries = host
+        .cns_nft_get_token_expiries(&host.state().nft, token_ids)
+        .map_err(handle_get_error)?;
+
+    Ok(expiries)
+}
+
+#[receive(
+    mutable,
+    contract = "BictoryCns",
+    name = "setData",
+    parameter = "SetDataParams",
+    enable_logger
+)]
+fn set_data<S: HasStateApi>(
+    ctx: &impl HasReceiveContext,
+    host: &mut impl HasHost<State<S>, StateApiType = S>,
+    logger: &mut impl HasLogger,
+) -> ReceiveResult<()> {
+    let params =
+        SetDataParams::deserial(&mut ctx.parameter_cursor()).map_err(CustomContractError::from)?;
+    // Keys are logged as is, so they are bounded to keep `DataChanged` within the event size limit
+    ensure!(
+        !params.key.is_empty() && params.key.len() <= MAX_DATA_KEY_LENGTH,
+        CustomContractError::ParseParams.into()
+    );
+
+    let domain = TokenizedDomain::try_from(params.domain.as_ref())?;
+    let (domain_namehash, subdomain_namehash) = domain.domain_subdomain_namehashes();
+    ensure_not_frozen(host, &domain_namehash)?;
+    ensure_record_owner(host, &domain, ctx.sender())?;
+    let registry = host.state().registry;
+
+    match &params.value {
         DataValue::Empty => host
             .storage_remove(&registry, subdomain_namehash.as_slice().into(), &params.key)
             .map_err(handle_call_error)?,
@@ -340,12 +961,20 @@ fn set_data<S: HasStateApi>(
                 &registry,
                 subdomain_namehash.as_slice().into(),
                 &params.key,
-                &v,
+                v,
             )
             .map_err(handle_call_error)?,
     }
 
-    // TODO: log DataChanged event
+    let value_hash = match &params.value {
+        DataValue::Empty => None,
+        v => Some(record_hash(v)),
+    };
+    logger.log(&CnsEvent::DataChanged {
+        domain: params.domain,
+        key: params.key,
+        value_hash,
+    })?;
 
     Ok(())
 }
@@ -392,19 +1021,23 @@ fn get_data<S: HasStateApi>(
     data.ok_or(CustomContractError::NotFound.into())
 }
 
+/// Create a subdomain of a domain owned by the sender, paying its yearly price. With `mint_as_token` the subdomain is
+/// also tokenized in the same call, see `tokenizeSubdomain`.
 #[receive(
     mutable,
     payable,
     contract = "BictoryCns",
     name = "createSubdomain",
-    parameter = "SubdomainParams"
+    parameter = "CreateSubdomainParams",
+    enable_logger
 )]
 fn create_subdomain<S: HasStateApi>(
     ctx: &impl HasReceiveContext,
     host: &mut impl HasHost<State<S>, StateApiType = S>,
     amount: Amount,
+    logger: &mut impl HasLogger,
 ) -> ReceiveResult<()> {
-    let params = SubdomainParams::deserial(&mut ctx.parameter_cursor())
+    let params = CreateSubdomainParams::deserial(&mut ctx.parameter_cursor())
         .map_err(CustomContractError::from)?;
     let registry = host.state().registry;
     let domain = TokenizedDomain::try_from(params.subdomain.as_ref())?;
@@ -416,36 +1049,45 @@ fn create_subdomain<S: HasStateApi>(
         .map_err(handle_get_error)?
         .ok_or(CustomContractError::NotFound)?;
 
-    match subscription_status.expiry {
-        SubscriptionExpiryStatus::Owned(_) if subscription_status.owner == ctx.sender() => (),
+    let expiry = match subscription_status.expiry {
+        SubscriptionExpiryStatus::Owned(expiry) if subscription_status.owner == ctx.sender() => {
+            expiry
+        }
         SubscriptionExpiryStatus::Expired => return Err(CustomContractError::NotFound.into()),
         _ => return Err(CustomContractError::Unauthorized.into()),
-    }
+    };
+    ensure_not_frozen(host, &domain_namehash)?;
 
     let pricing = host
         .cns_get_yearly_domain_price(
             &host.state().price_oracle,
             DomainKind::Subdomain,
-            params.subdomain.len() as u16,
+            u16::try_from(params.subdomain.chars().count())
+                .map_err(|_| CustomContractError::InvalidDomainFormat)?,
+            false,
         )
         .map_err(handle_get_error)?;
 
     let total_price = match pricing {
         DomainPrice::Limited => {
-            // Only maintainers and admins are allowed to create domains with limited pricing and registration policy
+            // Only holders of `CanMintReserved` can create domains with limited pricing and registration policy
             ensure!(
-                host.state().authority.has_maintainer_rights(&ctx.sender()),
+                host.state()
+                    .authority
+                    .has_capability(&ctx.sender(), Capability::CanMintReserved),
                 CustomContractError::Unauthorized.into()
             );
             Amount::zero()
         }
         DomainPrice::Amount(yearly_price) => yearly_price,
     };
+    ensure!(
+        total_price <= amount,
+        CustomContractError::InsufficientFunds.into()
+    );
 
-    // Transfer the fee to the beneficiary
-    if total_price != Amount::zero() {
-        host.invoke_transfer(&host.state().beneficiary, total_price)?;
-    }
+    // Transfer the fee to the beneficiaries
+    transfer_fee(host, total_price)?;
 
     // Refund the remaining CCD if necessary
     if amount - total_price != Amount::zero() {
@@ -462,6 +1104,28 @@ fn create_subdomain<S: HasStateApi>(
     )
     .map_err(handle_call_error)?;
 
+    let index_prefix = subdomain_index_prefix(&domain_namehash);
+    host.storage_upsert(
+        &registry,
+        index_prefix.as_slice().into(),
+        &domain.subdomain_label(),
+        &(),
+    )
+    .map_err(handle_call_error)?;
+
+    logger.log(&CnsEvent::SubdomainCreated {
+        subdomain: params.subdomain.clone(),
+    })?;
+
+    if params.mint_as_token {
+        let duration = expiry.duration_between(ctx.metadata().slot_time());
+        mint_subdomain_token(host, &domain, ctx.sender(), duration)?;
+        logger.log(&CnsEvent::SubdomainTokenized {
+            subdomain: params.subdomain,
+            owner: ctx.sender(),
+        })?;
+    }
+
     Ok(())
 }
 
@@ -469,11 +1133,13 @@ fn create_subdomain<S: HasStateApi>(
     mutable,
     contract = "BictoryCns",
     name = "deleteSubdomain",
-    parameter = "SubdomainParams"
+    parameter = "SubdomainParams",
+    enable_logger
 )]
 fn delete_subdomain<S: HasStateApi>(
     ctx: &impl HasReceiveContext,
     host: &mut impl HasHost<State<S>, StateApiType = S>,
+    logger: &mut impl HasLogger,
 ) -> ReceiveResult<()> {
     let params = SubdomainParams::deserial(&mut ctx.parameter_cursor())
         .map_err(CustomContractError::from)?;
@@ -484,13 +1150,15 @@ fn delete_subdomain<S: HasStateApi>(
         CustomContractError::InvalidDomainFormat.into()
     );
     let (domain_namehash, subdomain_namehash) = domain.domain_subdomain_namehashes();
+    ensure_not_frozen(host, &domain_namehash)?;
 
     if let Some(ownership_data) = host
         .cns_nft_get_token_expiry(&host.state().nft, TokenIdVec(domain_namehash.into()))
         .map_err(handle_get_error)?
     {
+        // Tokenized subdomains can only be deleted by the token holder
         ensure!(
-            ownership_data.is_owned_by(ctx.sender()) || ownership_data.is_expired(),
+            ownership_data.is_expired() || ensure_record_owner(host, &domain, ctx.sender()).is_ok(),
             CustomContractError::Unauthorized.into()
         );
     }
@@ -502,69 +1170,616 @@ fn delete_subdomain<S: HasStateApi>(
     )
     .map_err(handle_call_error)?;
 
+    // Subdomains created before the index was introduced have no index entry
+    let index_prefix = subdomain_index_prefix(&domain_namehash);
+    match host.storage_remove(
+        &registry,
+        index_prefix.as_slice().into(),
+        &domain.subdomain_label(),
+    ) {
+        Ok(()) | Err(CallContractError::LogicReject { reason: -30, .. }) => (),
+        Err(error) => return Err(handle_call_error(error)),
+    }
+
+    // Subdomain token is burnt together with the subdomain, unless it was already burnt after expiry
+    if host
+        .state()
+        .tokenized_subdomains
+        .contains(&subdomain_namehash)
+    {
+        let nft = host.state().nft;
+        let token_id = TokenIdVec(subdomain_namehash.into());
+        let minted = host
+            .cns_nft_get_token_expiries(&nft, vec![token_id.clone()])
+            .map_err(handle_get_error)?
+            .pop()
+            .flatten()
+            .is_some();
+        if minted {
+            host.cns_nft_burn(&nft, &token_id)
+                .map_err(handle_call_error)?;
+        }
+    }
+    host.state_mut()
+        .tokenized_subdomains
+        .remove(&subdomain_namehash);
+
+    logger.log(&CnsEvent::SubdomainDeleted {
+        subdomain: params.subdomain,
+    })?;
+
     Ok(())
 }
 
+/// Tokenize the subdomain as its own CNS NFT minted to the domain owner, so that it can be sold or transferred
+/// independently of the domain. Token ID is the subdomain namehash and the token expires together with the domain.
+/// Registry records of a tokenized subdomain are managed by the token holder. Calling it for an already tokenized
+/// subdomain extends the subdomain token up to the current expiry of the domain, e.g. after the domain was extended.
+/// An expired subdomain token, e.g. left from a previous registration of the domain, is burnt and minted again to the
+/// domain owner.
 #[receive(
     mutable,
     contract = "BictoryCns",
-    name = "updateAuthority",
-    parameter = "AuthorityUpdateParams"
+    name = "tokenizeSubdomain",
+    parameter = "SubdomainParams",
+    enable_logger
 )]
-fn update_authority<S: HasStateApi>(
+fn tokenize_subdomain<S: HasStateApi>(
     ctx: &impl HasReceiveContext,
     host: &mut impl HasHost<State<S>, StateApiType = S>,
+    logger: &mut impl HasLogger,
 ) -> ReceiveResult<()> {
-    let state = host.state_mut();
-    let params = AuthorityUpdateParams::deserial(&mut ctx.parameter_cursor())
+    let params = SubdomainParams::deserial(&mut ctx.parameter_cursor())
         .map_err(CustomContractError::from)?;
-    let sender = ctx.sender();
-    state.authority.handle_update(sender, params)
+    let domain = TokenizedDomain::try_from(params.subdomain.as_ref())?;
+    ensure!(
+        domain.is_subdomain(),
+        CustomContractError::InvalidDomainFormat.into()
+    );
+    let (domain_namehash, subdomain_namehash) = domain.domain_subdomain_namehashes();
+    ensure_not_frozen(host, &domain_namehash)?;
+    let token_id = TokenIdVec(subdomain_namehash.into());
+    let registry = host.state().registry;
+    let nft = host.state().nft;
+    let slot_time = ctx.metadata().slot_time();
+
+    let subscription_status = host
+        .cns_nft_get_token_expiry(&nft, TokenIdVec(domain_namehash.into()))
+        .map_err(handle_get_error)?
+        .ok_or(CustomContractError::NotFound)?;
+
+    let expiry = match subscription_status.expiry {
+        SubscriptionExpiryStatus::Owned(expiry) => expiry,
+        SubscriptionExpiryStatus::Grace(_) => return Err(CustomContractError::Unauthorized.into()),
+        SubscriptionExpiryStatus::Expired => return Err(CustomContractError::NotFound.into()),
+    };
+    // Only the domain owner can tokenize subdomains or extend their tokens
+    ensure!(
+        subscription_status.owner == ctx.sender(),
+        CustomContractError::Unauthorized.into()
+    );
+
+    if host
+        .state()
+        .tokenized_subdomains
+        .contains(&subdomain_namehash)
+    {
+        match host
+            .cns_nft_get_token_expiry(&nft, token_id.clone())
+            .map_err(handle_get_error)?
+        {
+            Some(token_status) if !token_status.is_expired() => {
+                let token_expiry = host
+                    .cns_nft_get_token_expiries(&nft, vec![token_id.clone()])
+                    .map_err(handle_get_error)?
+                    .pop()
+                    .flatten()
+                    .ok_or(CustomContractError::NotFound)?;
+                let start = token_expiry.expiry.max(slot_time);
+                if start < expiry {
+                    host.cns_nft_lend(&nft, token_id, expiry.duration_between(start))
+                        .map_err(handle_call_error)?;
+                }
+                return Ok(());
+            }
+            // Subdomain token expired together with a previous registration of the domain, so it's replaced
+            Some(_) => {
+                host.cns_nft_burn(&nft, &token_id)
+                    .map_err(handle_call_error)?;
+                host.state_mut()
+                    .tokenized_subdomains
+                    .remove(&subdomain_namehash);
+            }
+            // Subdomain token was burnt, so the subdomain can be tokenized again
+            None => {
+                host.state_mut()
+                    .tokenized_subdomains
+                    .remove(&subdomain_namehash);
+            }
+        }
+    }
+
+    // Only existing subdomains can be tokenized
+    host.storage_get_raw(
+        &registry,
+        &StorageKeysRef::some(subdomain_namehash.as_slice().into(), &[]),
+    )
+    .map_err(handle_get_error)?
+    .ok_or(CustomContractError::NotFound)?;
+
+    mint_subdomain_token(
+        host,
+        &domain,
+        ctx.sender(),
+        expiry.duration_between(slot_time),
+    )?;
+
+    logger.log(&CnsEvent::SubdomainTokenized {
+        subdomain: params.subdomain,
+        owner: ctx.sender(),
+    })?;
+
+    Ok(())
+}
+
+/// Mint the subdomain token to `owner`, valid for `duration` so that it expires together with the domain.
+fn mint_subdomain_token<S: HasStateApi>(
+    host: &mut impl HasHost<State<S>, StateApiType = S>,
+    domain: &TokenizedDomain,
+    owner: Address,
+    duration: Duration,
+) -> ReceiveResult<()> {
+    let (_, subdomain_namehash) = domain.domain_subdomain_namehashes();
+    let nft = host.state().nft;
+    host.cns_nft_mint(
+        &nft,
+        TokenIdVec(subdomain_namehash.into()),
+        domain.name(),
+        owner,
+        duration,
+    )
+    .map_err(handle_call_error)?;
+    host.state_mut()
+        .tokenized_subdomains
+        .insert(subdomain_namehash);
+    Ok(())
 }
 
+/// List labels of subdomains created under the domain, e.g. `sub` for `sub.domain.ccd`. Subdomains are listed in the
+/// order of their serialized labels. Deleted subdomains are not listed, but subdomains left from a previous
+/// registration of an expired domain are, so that they can be found and deleted.
 #[receive(
     contract = "BictoryCns",
-    name = "viewAuthority",
-    parameter = "AuthorityViewParams",
-    return_value = "Vec<Address>"
+    name = "listSubdomains",
+    parameter = "ListSubdomainsParams",
+    return_value = "Vec<String>"
 )]
-fn view_authority<S: HasStateApi>(
+fn list_subdomains<S: HasStateApi>(
     ctx: &impl HasReceiveContext,
     host: &impl HasHost<State<S>, StateApiType = S>,
-) -> ReceiveResult<Vec<Address>> {
-    let params = AuthorityViewParams::deserial(&mut ctx.parameter_cursor())
+) -> ReceiveResult<Vec<String>> {
+    let params = ListSubdomainsParams::deserial(&mut ctx.parameter_cursor())
         .map_err(CustomContractError::from)?;
-    Ok(host.state().authority.handle_view(params))
-}
+    let index_prefix = subdomain_index_prefix(&params.namehash);
 
-#[receive(
-    mutable,
-    contract = "BictoryCns",
-    name = "updateInternalValue",
-    parameter = "InternalValue"
+    let index = host
+        .storage_get_raw(
+            &host.state().registry,
+            &StorageKeysRef::all(index_prefix.as_slice().into()),
+        )
+        .map_err(handle_get_error)?;
+
+    let labels = match index {
+        Some(index) => index
+            .entries
+            .iter()
+            .skip(params.skip as usize)
+            .take(params.show as usize)
+            .map(|entry| from_bytes::<String>(&entry.key.0))
+            .collect::<ParseResult<_>>()
+            .map_err(|_| CustomContractError::InvokeContractError)?,
+        None => Vec::new(),
+    };
+
+    Ok(labels)
+}
+
+#[receive(
+    mutable,
+    contract = "BictoryCns",
+    name = "updateAuthority",
+    parameter = "AuthorityUpdateParams"
 )]
-fn update_internal_value<S: HasStateApi>(
+fn update_authority<S: HasStateApi>(
     ctx: &impl HasReceiveContext,
     host: &mut impl HasHost<State<S>, StateApiType = S>,
 ) -> ReceiveResult<()> {
-    if !host.state().authority.has_maintainer_rights(&ctx.sender()) {
-        return Err(CustomContractError::Unauthorized.into());
-    }
+    let state = host.state_mut();
+    let params = AuthorityUpdateParams::deserial(&mut ctx.parameter_cursor())
+        .map_err(CustomContractError::from)?;
+    let sender = ctx.sender();
+    state.authority.handle_update(sender, params)
+}
+
+#[receive(
+    contract = "BictoryCns",
+    name = "viewAuthority",
+    parameter = "AuthorityViewParams",
+    return_value = "Vec<Address>"
+)]
+fn view_authority<S: HasStateApi>(
+    ctx: &impl HasReceiveContext,
+    host: &impl HasHost<State<S>, StateApiType = S>,
+) -> ReceiveResult<Vec<Address>> {
+    let params = AuthorityViewParams::deserial(&mut ctx.parameter_cursor())
+        .map_err(CustomContractError::from)?;
+    Ok(host.state().authority.handle_view(params))
+}
+
+/// Check which standards the contract supports (CIS-0).
+///
+/// It rejects if:
+/// - It fails to parse the parameter.
+#[receive(
+    contract = "BictoryCns",
+    name = "supports",
+    parameter = "SupportsQueryParams",
+    return_value = "SupportsQueryResponse"
+)]
+fn supports<S: HasStateApi>(
+    ctx: &impl HasReceiveContext,
+    _host: &impl HasHost<State<S>, StateApiType = S>,
+) -> ReceiveResult<SupportsQueryResponse> {
+    let params = SupportsQueryParams::deserial(&mut ctx.parameter_cursor())
+        .map_err(CustomContractError::from)?;
+    Ok(supports_standards(&SUPPORTED_STANDARDS, params))
+}
+
+/// Grant or revoke a single maintainer capability. Only admins are allowed to update capabilities.
+#[receive(
+    mutable,
+    contract = "BictoryCns",
+    name = "updateCapability",
+    parameter = "CapabilityUpdateParams"
+)]
+fn update_capability<S: HasStateApi>(
+    ctx: &impl HasReceiveContext,
+    host: &mut impl HasHost<State<S>, StateApiType = S>,
+) -> ReceiveResult<()> {
+    let state = host.state_mut();
+    let params = CapabilityUpdateParams::deserial(&mut ctx.parameter_cursor())
+        .map_err(CustomContractError::from)?;
+    let sender = ctx.sender();
+    state.authority.handle_capability_update(sender, params)
+}
 
+#[receive(
+    contract = "BictoryCns",
+    name = "viewCapabilities",
+    parameter = "Address",
+    return_value = "Vec<Capability>"
+)]
+fn view_capabilities<S: HasStateApi>(
+    ctx: &impl HasReceiveContext,
+    host: &impl HasHost<State<S>, StateApiType = S>,
+) -> ReceiveResult<Vec<Capability>> {
+    let address =
+        Address::deserial(&mut ctx.parameter_cursor()).map_err(CustomContractError::from)?;
+    Ok(host.state().authority.handle_capability_view(address))
+}
+
+#[receive(
+    mutable,
+    contract = "BictoryCns",
+    name = "updateInternalValue",
+    parameter = "InternalValue"
+)]
+fn update_internal_value<S: HasStateApi>(
+    ctx: &impl HasReceiveContext,
+    host: &mut impl HasHost<State<S>, StateApiType = S>,
+) -> ReceiveResult<()> {
     let params =
         InternalValue::deserial(&mut ctx.parameter_cursor()).map_err(CustomContractError::from)?;
 
+    let capability = match params {
+        InternalValue::Beneficiaries(_) => Capability::CanSetFees,
+        _ => Capability::CanConfigure,
+    };
+    ensure!(
+        host.state()
+            .authority
+            .has_capability(&ctx.sender(), capability),
+        CustomContractError::Unauthorized.into()
+    );
+
     let mut state = host.state_mut();
     match params {
         InternalValue::CnsNft(nft) => state.nft = nft,
         InternalValue::Oracle(oracle) => state.price_oracle = oracle,
-        InternalValue::Beneficiary(beneficiary) => state.beneficiary = beneficiary,
+        InternalValue::Beneficiaries(beneficiaries) => {
+            ensure!(
+                !beneficiaries.is_empty() && beneficiaries.len() <= MAX_BENEFICIARIES,
+                CustomContractError::InvalidFields.into()
+            );
+            // Shares must add up to the whole fee
+            let total = beneficiaries
+                .iter()
+                .try_fold(Percentage::ZERO, |acc, (_, share)| acc.checked_add(*share))
+                .ok_or(CustomContractError::InvalidFields)?;
+            ensure_eq!(
+                total,
+                Percentage::HUNDRED,
+                CustomContractError::InvalidFields.into()
+            );
+            state.beneficiaries = beneficiaries;
+        }
         InternalValue::SubscriptionYearLimit(limit) => state.subscription_year_limit = limit,
+        InternalValue::CommitmentAge(min, max) => {
+            ensure!(min <= max, CustomContractError::InvalidFields.into());
+            state.min_commitment_age = min;
+            state.max_commitment_age = max;
+        }
+        InternalValue::ExpiredDomainAuction(auction) => {
+            if let PremiumDecay::Exponential { half_life } = auction.decay {
+                ensure!(
+                    half_life.millis() > 0,
+                    CustomContractError::InvalidFields.into()
+                );
+            }
+            state.expired_auction = auction;
+        }
+    }
+
+    Ok(())
+}
+
+/// Transfer fees kept after failed beneficiary transfers to the current beneficiaries.
+#[receive(mutable, contract = "BictoryCns", name = "sweepFees")]
+fn sweep_fees<S: HasStateApi>(
+    ctx: &impl HasReceiveContext,
+    host: &mut impl HasHost<State<S>, StateApiType = S>,
+) -> ReceiveResult<()> {
+    ensure!(
+        host.state()
+            .authority
+            .has_capability(&ctx.sender(), Capability::CanSetFees),
+        CustomContractError::Unauthorized.into()
+    );
+
+    let fees = host.state().escrowed_fees;
+    ensure!(fees != Amount::zero(), CustomContractError::NotFound.into());
+
+    host.state_mut().escrowed_fees = Amount::zero();
+    for (account, share) in host.state().fee_shares(fees) {
+        if share != Amount::zero() {
+            host.invoke_transfer(&account, share)?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Freeze registry records of the domain and its subdomains, e.g. to comply with a court order. Owner can't change the
+/// address or data of a frozen domain, or transfer it with `transferDomain`. Token ownership and subscription are not
+/// affected.
+#[receive(
+    mutable,
+    contract = "BictoryCns",
+    name = "freezeDomain",
+    parameter = "FreezeDomainParams",
+    enable_logger
+)]
+fn freeze_domain<S: HasStateApi>(
+    ctx: &impl HasReceiveContext,
+    host: &mut impl HasHost<State<S>, StateApiType = S>,
+    logger: &mut impl HasLogger,
+) -> ReceiveResult<()> {
+    ensure!(
+        host.state()
+            .authority
+            .has_capability(&ctx.sender(), Capability::CanFreeze),
+        CustomContractError::Unauthorized.into()
+    );
+
+    let params = FreezeDomainParams::deserial(&mut ctx.parameter_cursor())
+        .map_err(CustomContractError::from)?;
+    let domain = TokenizedDomain::try_from(params.domain.as_ref())?;
+    ensure!(
+        !domain.is_subdomain(),
+        CustomContractError::InvalidDomainFormat.into()
+    );
+    let namehash = domain.namehash();
+
+    let freeze = DomainFreeze {
+        reason_hash: params.reason_hash,
+        frozen_by: ctx.sender(),
+        frozen_at: ctx.metadata().slot_time(),
+    };
+    ensure!(
+        host.state_mut()
+            .frozen_domains
+            .insert(namehash, freeze)
+            .is_none(),
+        CustomContractError::AlreadyExists.into()
+    );
+
+    logger.log(&CnsEvent::DomainFrozen {
+        domain: params.domain,
+        reason_hash: params.reason_hash,
+        by: ctx.sender(),
+    })?;
+
+    Ok(())
+}
+
+/// Lift the freeze of the domain. Reason hash refers to the decision to lift it, e.g. an appeal ruling.
+#[receive(
+    mutable,
+    contract = "BictoryCns",
+    name = "unfreezeDomain",
+    parameter = "FreezeDomainParams",
+    enable_logger
+)]
+fn unfreeze_domain<S: HasStateApi>(
+    ctx: &impl HasReceiveContext,
+    host: &mut impl HasHost<State<S>, StateApiType = S>,
+    logger: &mut impl HasLogger,
+) -> ReceiveResult<()> {
+    ensure!(
+        host.state()
+            .authority
+            .has_capability(&ctx.sender(), Capability::CanFreeze),
+        CustomContractError::Unauthorized.into()
+    );
+
+    let params = FreezeDomainParams::deserial(&mut ctx.parameter_cursor())
+        .map_err(CustomContractError::from)?;
+    let domain = TokenizedDomain::try_from(params.domain.as_ref())?;
+    ensure!(
+        !domain.is_subdomain(),
+        CustomContractError::InvalidDomainFormat.into()
+    );
+
+    host.state_mut()
+        .frozen_domains
+        .remove_and_get(&domain.namehash())
+        .ok_or(CustomContractError::NotFound)?;
+
+    logger.log(&CnsEvent::DomainUnfrozen {
+        domain: params.domain,
+        reason_hash: params.reason_hash,
+        by: ctx.sender(),
+    })?;
+
+    Ok(())
+}
+
+/// Reserve domains, so that they can only be registered by maintainers, e.g. trademarked or offensive names. Reserving
+/// doesn't affect domains that are already registered until their subscription expires.
+#[receive(
+    mutable,
+    contract = "BictoryCns",
+    name = "reserveDomains",
+    parameter = "ReserveDomainsParams"
+)]
+fn reserve_domains<S: HasStateApi>(
+    ctx: &impl HasReceiveContext,
+    host: &mut impl HasHost<State<S>, StateApiType = S>,
+) -> ReceiveResult<()> {
+    ensure!(
+        host.state()
+            .authority
+            .has_capability(&ctx.sender(), Capability::CanReserve),
+        CustomContractError::Unauthorized.into()
+    );
+
+    let params = ReserveDomainsParams::deserial(&mut ctx.parameter_cursor())
+        .map_err(CustomContractError::from)?;
+    for domain in params.domains.iter() {
+        let namehash = reserved_domain_namehash(domain)?;
+        ensure!(
+            host.state_mut().reserved_domains.insert(namehash),
+            CustomContractError::AlreadyExists.into()
+        );
+    }
+
+    Ok(())
+}
+
+/// Release reserved domains, so that anyone can register them again.
+#[receive(
+    mutable,
+    contract = "BictoryCns",
+    name = "releaseDomains",
+    parameter = "ReserveDomainsParams"
+)]
+fn release_domains<S: HasStateApi>(
+    ctx: &impl HasReceiveContext,
+    host: &mut impl HasHost<State<S>, StateApiType = S>,
+) -> ReceiveResult<()> {
+    ensure!(
+        host.state()
+            .authority
+            .has_capability(&ctx.sender(), Capability::CanReserve),
+        CustomContractError::Unauthorized.into()
+    );
+
+    let params = ReserveDomainsParams::deserial(&mut ctx.parameter_cursor())
+        .map_err(CustomContractError::from)?;
+    for domain in params.domains.iter() {
+        let namehash = reserved_domain_namehash(domain)?;
+        ensure!(
+            host.state_mut().reserved_domains.remove(&namehash),
+            CustomContractError::NotFound.into()
+        );
     }
 
     Ok(())
 }
 
+/// Get the freeze of the domain, if it's frozen. Subdomains report the freeze of their parent domain.
+#[receive(
+    contract = "BictoryCns",
+    name = "getFreeze",
+    parameter = "GetFreezeParams",
+    return_value = "Option<DomainFreeze>"
+)]
+fn get_freeze<S: HasStateApi>(
+    ctx: &impl HasReceiveContext,
+    host: &impl HasHost<State<S>, StateApiType = S>,
+) -> ReceiveResult<Option<DomainFreeze>> {
+    let params = GetFreezeParams::deserial(&mut ctx.parameter_cursor())
+        .map_err(CustomContractError::from)?;
+    let domain = TokenizedDomain::try_from(params.domain.as_ref())?;
+    let (domain_namehash, _) = domain.domain_subdomain_namehashes();
+
+    Ok(host
+        .state()
+        .frozen_domains
+        .get(&domain_namehash)
+        .map(|freeze| freeze.clone()))
+}
+
+/// Get the current premium of the expired domain auction, which is paid on top of the base price to register the
+/// domain. Premium is zero for domains that were never registered or finished the auction.
+#[receive(
+    contract = "BictoryCns",
+    name = "getPremium",
+    parameter = "GetPremiumParams",
+    return_value = "Amount"
+)]
+fn get_premium<S: HasStateApi>(
+    ctx: &impl HasReceiveContext,
+    host: &impl HasHost<State<S>, StateApiType = S>,
+) -> ReceiveResult<Amount> {
+    let params = GetPremiumParams::deserial(&mut ctx.parameter_cursor())
+        .map_err(CustomContractError::from)?;
+    let domain = TokenizedDomain::try_from(params.domain.as_ref())?;
+    ensure!(
+        !domain.is_subdomain(),
+        CustomContractError::InvalidDomainFormat.into()
+    );
+
+    let token_id = TokenIdVec(domain.namehash().into());
+    let expiry = host
+        .cns_nft_get_token_expiries(&host.state().nft, vec![token_id])
+        .map_err(handle_get_error)?
+        .pop()
+        .flatten();
+
+    let slot_time = ctx.metadata().slot_time();
+    match expiry {
+        Some(expiry) => {
+            ensure!(
+                expiry.grace_end <= slot_time,
+                CustomContractError::AlreadyExists.into()
+            );
+            Ok(host.state().auction_premium(expiry.grace_end, slot_time))
+        }
+        None => Ok(Amount::zero()),
+    }
+}
+
 #[receive(
     contract = "BictoryCns",
     name = "viewInternalValue",
@@ -582,66 +1797,98 @@ fn view_internal_value<S: HasStateApi>(
     let address = match params {
         InternalViewParams::CnsNft => InternalValue::CnsNft(state.nft),
         InternalViewParams::Oracle => InternalValue::Oracle(state.price_oracle),
-        InternalViewParams::Beneficiary => InternalValue::Beneficiary(state.beneficiary),
+        InternalViewParams::Beneficiaries => {
+            InternalValue::Beneficiaries(state.beneficiaries.clone())
+        }
         InternalViewParams::SubscriptionYearLimit => {
             InternalValue::SubscriptionYearLimit(state.subscription_year_limit)
         }
+        InternalViewParams::CommitmentAge => {
+            InternalValue::CommitmentAge(state.min_commitment_age, state.max_commitment_age)
+        }
+        InternalViewParams::ExpiredDomainAuction => {
+            InternalValue::ExpiredDomainAuction(state.expired_auction)
+        }
     };
 
     Ok(address)
 }
 
-struct TokenizedDomain<'a> {
-    domain: &'a str,
-    labels: Vec<&'a str>,
+/// Domain name split into normalized labels, so that names that look the same always have the same namehash.
+struct TokenizedDomain {
+    domain: String,
+    labels: Vec<String>,
 }
 
-impl<'a> TryFrom<&'a str> for TokenizedDomain<'a> {
+impl TryFrom<&str> for TokenizedDomain {
     type Error = CustomContractError;
 
-    fn try_from(domain: &'a str) -> Result<Self, Self::Error> {
-        // Domain must be under 256 bytes
-        ensure!(domain.len() < 256, CustomContractError::InvalidDomainFormat);
-
+    fn try_from(domain: &str) -> Result<Self, Self::Error> {
         let mut labels = domain.split('.').rev();
 
         // Root domain must be ccd. Check it and skip
+        let root = labels.next().map(normalize_label).transpose()?;
         ensure_eq!(
-            labels.next(),
+            root.as_deref(),
             Some("ccd"),
             CustomContractError::InvalidDomainFormat
         );
 
         let domain = labels
             .next()
-            .ok_or(CustomContractError::InvalidDomainFormat)?;
+            .ok_or(CustomContractError::InvalidDomainFormat)
+            .and_then(normalize_label)?;
 
-        let labels: Vec<&str> = labels.collect();
+        let labels = labels.map(normalize_label).collect::<Result<Vec<_>, _>>()?;
+        let domain = TokenizedDomain { domain, labels };
 
-        // Must be at least one label, each label must not be empty, but under 64 bytes
+        // Normalized domain must be under 256 bytes, since lowercasing can change the length of the name
         ensure!(
-            labels
-                .iter()
-                .all(|label| !label.is_empty() && label.len() < 64),
+            domain.name().len() < 256,
             CustomContractError::InvalidDomainFormat
         );
 
-        Ok(TokenizedDomain { domain, labels })
+        Ok(domain)
     }
 }
 
+/// Lowercase the label and convert it to Unicode NFC. Label must not be empty, must be under 64 bytes after
+/// normalization and must not contain control or whitespace characters. All characters must belong to a single script,
+/// apart from characters shared by all scripts like digits and `-`, so lookalikes mixing e.g. Latin and Cyrillic letters
+/// are rejected.
+fn normalize_label(label: &str) -> Result<String, CustomContractError> {
+    ensure!(
+        !label.chars().any(|c| c.is_control() || c.is_whitespace()),
+        CustomContractError::InvalidCharacter
+    );
+
+    let label: String = label.chars().flat_map(char::to_lowercase).nfc().collect();
+    ensure!(
+        !label.is_empty() && label.len() < 64,
+        CustomContractError::InvalidDomainFormat
+    );
+    ensure!(
+        !ScriptExtension::for_str(&label).is_empty(),
+        CustomContractError::MixedScript
+    );
+
+    Ok(label)
+}
+
+const SUBDOMAIN_INDEX_SUFFIX: &[u8] = b"/subdomains";
+
 const CCD_HASH: [u8; 32] = [
     0x53, 0xbb, 0xd6, 0xc8, 0xc1, 0xbd, 0xc5, 0xc6, 0x28, 0x27, 0x1c, 0x55, 0xa6, 0xac, 0x73, 0xa1,
     0xe9, 0x7a, 0xfb, 0xb1, 0x4d, 0x4a, 0xeb, 0x3a, 0xdd, 0xb8, 0xb7, 0xb8, 0x0e, 0x4f, 0x45, 0x5a,
 ];
 
-impl<'a> TokenizedDomain<'a> {
+impl TokenizedDomain {
     fn domain_subdomain_namehashes(&self) -> ([u8; 32], [u8; 32]) {
-        let domain_namehash = namehash_label(CCD_HASH, self.domain);
+        let domain_namehash = namehash_label(CCD_HASH, &self.domain);
         let subdomain_namehash = self
             .labels
             .iter()
-            .copied()
+            .map(String::as_str)
             .fold(domain_namehash, namehash_label);
         (domain_namehash, subdomain_namehash)
     }
@@ -649,8 +1896,19 @@ impl<'a> TokenizedDomain<'a> {
     fn namehash(&self) -> [u8; 32] {
         self.labels
             .iter()
-            .copied()
-            .fold(namehash_label(CCD_HASH, self.domain), namehash_label)
+            .map(String::as_str)
+            .fold(namehash_label(CCD_HASH, &self.domain), namehash_label)
+    }
+
+    /// Normalized name of the domain or subdomain, including the `.ccd` suffix.
+    fn name(&self) -> String {
+        let mut name = self.subdomain_label();
+        if !name.is_empty() {
+            name.push('.');
+        }
+        name.push_str(&self.domain);
+        name.push_str(".ccd");
+        name
     }
 
     fn char_count(&self) -> u16 {
@@ -665,6 +1923,26 @@ impl<'a> TokenizedDomain<'a> {
     fn is_subdomain(&self) -> bool {
         !self.labels.is_empty()
     }
+
+    /// Subdomain part of the name without the parent domain, e.g. `a.b` for `a.b.domain.ccd`.
+    fn subdomain_label(&self) -> String {
+        let mut label = String::new();
+        for (i, part) in self.labels.iter().rev().enumerate() {
+            if i > 0 {
+                label.push('.');
+            }
+            label.push_str(part);
+        }
+        label
+    }
+}
+
+/// Registry prefix of the subdomain index of a domain. Index keys are subdomain labels, values are empty. Suffix keeps
+/// the prefix distinct from 32 byte namehash prefixes.
+fn subdomain_index_prefix(domain_namehash: &[u8; 32]) -> Vec<u8> {
+    let mut prefix = domain_namehash.to_vec();
+    prefix.extend_from_slice(SUBDOMAIN_INDEX_SUFFIX);
+    prefix
 }
 
 fn namehash_label(namehash: [u8; 32], label: &str) -> [u8; 32] {
@@ -676,19 +1954,329 @@ fn namehash_label(namehash: [u8; 32], label: &str) -> [u8; 32] {
     hasher.finalize_reset().into()
 }
 
-fn handle_call_error<R>(error: CallContractError<R>) -> Reject {
-    match error {
-        CallContractError::LogicReject { reason, .. } => match reason {
-            // CustomContractError::ParseParams | concordium ParseError
-            -1 | -2147483646 => CustomContractError::Incompatible.into(),
-            // CustomContractError::NotFound
-            -30 => CustomContractError::NotFound.into(),
-            // CustomContractError::AlreadyExists
-            -35 => CustomContractError::AlreadyExists.into(),
-            // CustomContractError::Unauthorized, happens if CNS contract was not authorized
-            -36 => CustomContractError::OperationNotPermitted.into(),
-            // Remaining errors
-            _ => CustomContractError::InvokeContractError.into(),
+/// Commitment to registration parameters. Sender is included so that only the committer can reveal it.
+fn registration_commitment(params: &RegisterParams, sender: &Address, salt: &[u8; 32]) -> [u8; 32] {
+    let mut hasher = Keccak256::default();
+    hasher.update(to_bytes(params));
+    hasher.update(to_bytes(sender));
+    hasher.update(salt);
+    hasher.finalize().into()
+}
+
+/// Hash of the secret code that claims a voucher.
+fn voucher_code_hash(code: &[u8; 32]) -> [u8; 32] {
+    let mut hasher = Keccak256::default();
+    hasher.update(code);
+    hasher.finalize().into()
+}
+
+/// Namehash of a domain to reserve. Only domains can be reserved, subdomains are managed by domain owners.
+fn reserved_domain_namehash(domain: &str) -> ReceiveResult<[u8; 32]> {
+    let domain = TokenizedDomain::try_from(domain)?;
+    ensure!(
+        !domain.is_subdomain(),
+        CustomContractError::InvalidDomainFormat.into()
+    );
+    Ok(domain.namehash())
+}
+
+/// Registry records of frozen domains can't be changed by their owners.
+fn ensure_not_frozen<S: HasStateApi>(
+    host: &impl HasHost<State<S>, StateApiType = S>,
+    domain_namehash: &[u8; 32],
+) -> ReceiveResult<()> {
+    ensure!(
+        host.state().frozen_domains.get(domain_namehash).is_none(),
+        CustomContractError::Frozen.into()
+    );
+    Ok(())
+}
+
+/// Check that `sender` controls registry records of the domain or subdomain. Records of tokenized subdomains are
+/// controlled by the holder of the subdomain token, others by the owner of the domain. In both cases the domain must
+/// not be expired or in grace period. Expired subdomain tokens, e.g. left from a previous registration of the domain,
+/// don't control any records.
+fn ensure_record_owner<S: HasStateApi>(
+    host: &impl HasHost<State<S>, StateApiType = S>,
+    domain: &TokenizedDomain,
+    sender: Address,
+) -> ReceiveResult<()> {
+    let (domain_namehash, subdomain_namehash) = domain.domain_subdomain_namehashes();
+    let nft = host.state().nft;
+
+    let subscription_status = host
+        .cns_nft_get_token_expiry(&nft, TokenIdVec(domain_namehash.into()))
+        .map_err(handle_get_error)?
+        .ok_or(CustomContractError::NotFound)?;
+
+    let mut owner = subscription_status.owner;
+    if domain.is_subdomain()
+        && host
+            .state()
+            .tokenized_subdomains
+            .contains(&subdomain_namehash)
+    {
+        // Records fall back to the domain owner once the subdomain token is burnt or expired
+        if let Some(subdomain_status) = host
+            .cns_nft_get_token_expiry(&nft, TokenIdVec(subdomain_namehash.into()))
+            .map_err(handle_get_error)?
+        {
+            if !subdomain_status.is_expired() {
+                owner = subdomain_status.owner;
+            }
+        }
+    }
+
+    match subscription_status.expiry {
+        SubscriptionExpiryStatus::Owned(_) if owner == sender => Ok(()),
+        SubscriptionExpiryStatus::Expired => Err(CustomContractError::NotFound.into()),
+        _ => Err(CustomContractError::Unauthorized.into()),
+    }
+}
+
+/// Plain registration would let anyone frontrun revealed commitments, so it's only left to maintainers and holders of
+/// `CanMintReserved` while commit-reveal registration is enabled.
+fn ensure_direct_registration<S: HasStateApi>(
+    ctx: &impl HasReceiveContext,
+    host: &impl HasHost<State<S>, StateApiType = S>,
+) -> ReceiveResult<()> {
+    let state = host.state();
+    ensure!(
+        !state.commit_reveal_enabled()
+            || state
+                .authority
+                .has_capability(&ctx.sender(), Capability::CanMintReserved),
+        CustomContractError::OperationNotPermitted.into()
+    );
+    Ok(())
+}
+
+/// Domain that passed registration checks, together with its subscription price.
+struct Registration {
+    params: RegisterParams,
+    namehash: [u8; 32],
+    /// Token of an expired subscription that has to be burnt before minting
+    expired: bool,
+    /// Premium of the expired domain auction, included in the price
+    premium: Amount,
+    price: Amount,
+}
+
+/// Check that the domain can be registered by the sender and get the total subscription price.
+fn prepare_registration<S: HasStateApi>(
+    ctx: &impl HasReceiveContext,
+    host: &impl HasHost<State<S>, StateApiType = S>,
+    params: RegisterParams,
+) -> ReceiveResult<Registration> {
+    let (registration, length) = check_registration(ctx, host, params)?;
+    let pricing = host
+        .cns_get_yearly_domain_price(
+            &host.state().price_oracle,
+            DomainKind::Domain,
+            length,
+            false,
+        )
+        .map_err(handle_get_error)?;
+
+    price_registration(ctx, host, registration, pricing)
+}
+
+/// Check that the domain is valid and available to the sender. Returns the registration without price together with
+/// the domain length used for pricing.
+fn check_registration<S: HasStateApi>(
+    ctx: &impl HasReceiveContext,
+    host: &impl HasHost<State<S>, StateApiType = S>,
+    params: RegisterParams,
+) -> ReceiveResult<(Registration, u16)> {
+    let domain = TokenizedDomain::try_from(params.domain.as_ref())?;
+    // Domain token is minted with the normalized name
+    let params = RegisterParams {
+        domain: domain.name(),
+        ..params
+    };
+
+    // Only domains can be registered with this function
+    ensure!(
+        !domain.is_subdomain(),
+        CustomContractError::InvalidDomainFormat.into()
+    );
+
+    // Ensure registration duration does not exceed `subscription_year_limit` years in the future
+    ensure!(
+        params.duration_years <= host.state().subscription_year_limit,
+        CustomContractError::InvalidDuration.into()
+    );
+
+    let namehash = domain.namehash();
+
+    // Reserved domains can only be registered by maintainers and holders of `CanMintReserved`
+    ensure!(
+        !host.state().reserved_domains.contains(&namehash)
+            || host
+                .state()
+                .authority
+                .has_capability(&ctx.sender(), Capability::CanMintReserved),
+        CustomContractError::Reserved.into()
+    );
+
+    let token_id = TokenIdVec(namehash.into());
+    let ownership = host
+        .cns_nft_get_token_expiry(&host.state().nft, token_id.clone())
+        .map_err(handle_get_error)?;
+
+    // Check if token exists and has already expired
+    let expired = match ownership {
+        Some(ownership_data) => {
+            ensure!(
+                ownership_data.is_expired(),
+                CustomContractError::AlreadyExists.into()
+            );
+            true
+        }
+        None => false,
+    };
+
+    // Expired domains are auctioned with a decaying premium before they are available at base price
+    let premium = if expired && host.state().expired_auction.is_enabled() {
+        let grace_end = host
+            .cns_nft_get_token_expiries(&host.state().nft, vec![token_id])
+            .map_err(handle_get_error)?
+            .pop()
+            .flatten()
+            .ok_or(CustomContractError::NotFound)?
+            .grace_end;
+        host.state()
+            .auction_premium(grace_end, ctx.metadata().slot_time())
+    } else {
+        Amount::zero()
+    };
+
+    let length = domain.char_count();
+    let registration = Registration {
+        params,
+        namehash,
+        expired,
+        premium,
+        price: Amount::zero(),
+    };
+
+    Ok((registration, length))
+}
+
+/// Set the total price of the registration from the yearly price of the domain and the auction premium.
+fn price_registration<S: HasStateApi>(
+    ctx: &impl HasReceiveContext,
+    host: &impl HasHost<State<S>, StateApiType = S>,
+    mut registration: Registration,
+    pricing: DomainPrice,
+) -> ReceiveResult<Registration> {
+    let (yearly_price, premium) = match pricing {
+        DomainPrice::Limited => {
+            // Only holders of `CanMintReserved` can create domains with limited pricing and registration policy
+            ensure!(
+                host.state()
+                    .authority
+                    .has_capability(&ctx.sender(), Capability::CanMintReserved),
+                CustomContractError::Unauthorized.into()
+            );
+            // Domains with limited pricing are allocated by maintainers, so they are never auctioned
+            (Amount::zero(), Amount::zero())
+        }
+        DomainPrice::Amount(yearly_price) => (yearly_price, registration.premium),
+    };
+
+    registration.price = (yearly_price * registration.params.duration_years as u64)
+        .micro_ccd
+        .checked_add(premium.micro_ccd)
+        .map(Amount::from_micro_ccd)
+        .ok_or(CustomContractError::Overflow)?;
+    Ok(registration)
+}
+
+/// Mint the domain token to the sender and write the registry entry. Fee has to be handled by the caller.
+fn complete_registration<S: HasStateApi>(
+    ctx: &impl HasReceiveContext,
+    host: &mut impl HasHost<State<S>, StateApiType = S>,
+    logger: &mut impl HasLogger,
+    registration: Registration,
+    owner: Address,
+) -> ReceiveResult<()> {
+    let Registration {
+        params,
+        namehash,
+        expired,
+        ..
+    } = registration;
+    let registry = host.state().registry;
+    let nft = host.state().nft;
+    let token_id = TokenIdVec(namehash.into());
+
+    if expired {
+        host.cns_nft_burn(&nft, &token_id)
+            .map_err(handle_call_error)?;
+    }
+
+    host.cns_nft_mint(
+        &nft,
+        token_id,
+        params.domain.clone(),
+        owner,
+        Duration::from_millis(YEAR_MILLIS * params.duration_years as u64),
+    )
+    .map_err(handle_call_error)?;
+
+    // Insert operation fails with AlreadyExists if entry is present. Token data is burnt without removing the
+    // registry data. Try clearing old registry data before inserting new. If NFT was successfully minted, this means
+    // that it is okay to remove previous registry data, if present.
+    //
+    // We ignore errors on remove call because, `CustomContractError::NotFound` is expected to be returned often and
+    // should be ignored. `insert` call will produce any other error that we may ignore from `remove` call.
+    let _ = host.storage_remove_raw(&registry, &StorageKeysRef::all(namehash.as_slice().into()));
+    host.storage_insert(&registry, namehash.as_slice().into(), &(), &params.address)
+        .map_err(handle_call_error)?;
+
+    logger.log(&CnsEvent::Registered {
+        domain: params.domain,
+        owner,
+        duration_years: params.duration_years,
+    })?;
+
+    Ok(())
+}
+
+/// Split the fee between beneficiaries. If a beneficiary account doesn't exist, that share is kept in the contract
+/// until it's swept with `sweepFees`, so that beneficiary account issues never block payments. Other transfer errors
+/// reject the call.
+fn transfer_fee<S: HasStateApi>(
+    host: &mut impl HasHost<State<S>, StateApiType = S>,
+    amount: Amount,
+) -> ReceiveResult<()> {
+    for (account, share) in host.state().fee_shares(amount) {
+        if share == Amount::zero() {
+            continue;
+        }
+        match host.invoke_transfer(&account, share) {
+            Ok(()) => {}
+            Err(TransferError::MissingAccount) => host.state_mut().escrowed_fees += share,
+            Err(error) => return Err(error.into()),
+        }
+    }
+    Ok(())
+}
+
+fn handle_call_error<R>(error: CallContractError<R>) -> Reject {
+    match error {
+        CallContractError::LogicReject { reason, .. } => match reason {
+            // CustomContractError::ParseParams | concordium ParseError
+            -1 | -2147483646 => CustomContractError::Incompatible.into(),
+            // CustomContractError::NotFound
+            -30 => CustomContractError::NotFound.into(),
+            // CustomContractError::AlreadyExists
+            -35 => CustomContractError::AlreadyExists.into(),
+            // CustomContractError::Unauthorized, happens if CNS contract was not authorized
+            -36 => CustomContractError::OperationNotPermitted.into(),
+            // Remaining errors
+            _ => CustomContractError::InvokeContractError.into(),
         },
         e => e.into(),
     }
@@ -707,14 +2295,16 @@ mod tests {
     use super::*;
     use commons_v1::test::*;
     use commons_v1::{
-        Bytes, CnsMintParams, GetDomainPriceParams, GetDomainPriceResult, LendParams,
-        MaybeStorageEntry, StorageEntries, StorageGetEntryResult, StorageKeySelection, StorageKeys,
+        CnsMintParams, GetDomainPriceResult, LendParams, MaybeStorageEntry, StorageEntries,
+        StorageEntry, StorageGetEntryResult, StorageKeySelection, StorageKeys, TokenListParams,
         TokenParams, TokenSubscriptionStatus,
     };
+    use concordium_cis1::TransferParams;
     use concordium_std::test_infrastructure::*;
 
     const TEST_YEARLY_DOMAIN_PRICE: Amount = Amount::from_ccd(10);
     const TEST_YEARLY_SUBDOMAIN_PRICE: Amount = Amount::from_ccd(5);
+    const TEST_YEARLY_GRACE_RENEWAL_PRICE: Amount = Amount::from_ccd(15);
 
     const REGISTRY: ContractAddress = ContractAddress {
         index: 1,
@@ -775,6 +2365,9 @@ mod tests {
     fn test_price_oracle(params: &GetDomainPriceParams) -> Option<GetDomainPriceResult> {
         let result = match params.domain_kind {
             DomainKind::Domain if params.length <= 3 => DomainPrice::Limited,
+            DomainKind::Domain if params.renewal_in_grace => {
+                DomainPrice::Amount(TEST_YEARLY_GRACE_RENEWAL_PRICE)
+            }
             DomainKind::Domain => DomainPrice::Amount(TEST_YEARLY_DOMAIN_PRICE),
             DomainKind::Subdomain => DomainPrice::Amount(TEST_YEARLY_SUBDOMAIN_PRICE),
         };
@@ -843,6 +2436,49 @@ mod tests {
         }
     }
 
+    #[concordium_test]
+    fn test_domain_normalization() {
+        let parse =
+            |name: &str| TokenizedDomain::try_from(name).expect_report("Unable to parse domain");
+
+        // Case and Unicode composition don't change the name
+        claim_eq!(parse("Test.CCD").name(), "test.ccd");
+        claim_eq!(parse("Test.CCD").namehash(), parse("test.ccd").namehash());
+        claim_eq!(parse("Sub.Test.ccd").name(), "sub.test.ccd");
+        claim_eq!(
+            parse("cafe\u{301}.ccd").namehash(),
+            parse("caf\u{e9}.ccd").namehash()
+        );
+        claim_eq!(parse("ТЕСТ-1.ccd").name(), "тест-1.ccd");
+
+        // Lookalikes mixing scripts are rejected
+        claim_eq!(
+            TokenizedDomain::try_from("p\u{430}ypal.ccd").err(),
+            Some(CustomContractError::MixedScript)
+        );
+        claim_eq!(
+            TokenizedDomain::try_from("te st.ccd").err(),
+            Some(CustomContractError::InvalidCharacter)
+        );
+        claim_eq!(
+            TokenizedDomain::try_from("test\u{0}.ccd").err(),
+            Some(CustomContractError::InvalidCharacter)
+        );
+        claim_eq!(
+            TokenizedDomain::try_from("a..ccd").err(),
+            Some(CustomContractError::InvalidDomainFormat)
+        );
+
+        // Length limit applies to the normalized name. `İ` takes 2 bytes, but lowercases to 3 bytes
+        let label = "\u{130}".repeat(21);
+        let name = format!("{0}.{0}.{0}.{0}.ccd", label);
+        claim!(name.len() < 256);
+        claim_eq!(
+            TokenizedDomain::try_from(name.as_str()).err(),
+            Some(CustomContractError::InvalidDomainFormat)
+        );
+    }
+
     #[concordium_test]
     fn test_init_test_state() {
         let host = default_host();
@@ -871,6 +2507,7 @@ mod tests {
     #[concordium_test]
     fn test_register_new() {
         let mut host = default_host();
+        let mut logger = TestLogger::init();
 
         let mut ctx = TestReceiveContext::empty();
         let params = RegisterParams {
@@ -913,92 +2550,404 @@ mod tests {
             OwnedEntrypointName::new_unchecked(String::from("insert")),
             parse_and_ok_mock::<StorageEntries, _>(()),
         );
+        // Attached amount must cover the price of all years
+        let invoke_amount = TEST_YEARLY_DOMAIN_PRICE;
+        host.set_self_balance(invoke_amount);
+        let result = register(&ctx, &mut host, invoke_amount, &mut logger);
+        claim_eq!(result, Err(CustomContractError::InsufficientFunds.into()));
+
         let invoke_amount = TEST_YEARLY_DOMAIN_PRICE * 2 + Amount::from_ccd(2);
         host.set_self_balance(invoke_amount);
-        let result = register(&ctx, &mut host, invoke_amount);
+        let result = register(&ctx, &mut host, invoke_amount, &mut logger);
 
         claim_eq!(result, Ok(()));
         // Transfer subscription cost
         claim!(host.transfer_occurred(&ADMIN, TEST_YEARLY_DOMAIN_PRICE * 2));
         // Return extra
         claim!(host.transfer_occurred(&USER_1, Amount::from_ccd(2)));
+        claim_eq!(
+            logger.logs,
+            vec![to_bytes(&CnsEvent::Registered {
+                domain: String::from("test.ccd"),
+                owner: Address::Account(USER_1),
+                duration_years: 2,
+            })]
+        );
     }
 
     #[concordium_test]
-    fn test_register_expired() {
+    fn test_reserve_domains() {
         let mut host = default_host();
+        let mut logger = TestLogger::init();
 
         let mut ctx = TestReceiveContext::empty();
-        let params = RegisterParams {
-            domain: String::from("test.ccd"),
-            address: Address::Account(USER_1),
-            duration_years: 2,
+        let params = ReserveDomainsParams {
+            domains: vec![String::from("test.ccd")],
         };
         let bytes = to_bytes(&params);
         ctx.set_sender(Address::Account(USER_1))
-            .set_invoker(USER_1)
-            .set_parameter(&bytes)
-            .set_metadata_slot_time(test_slot_time());
-        // Get pricing info
+            .set_parameter(&bytes);
+
+        // Only maintainers can reserve domains
+        let result = reserve_domains(&ctx, &mut host);
+        claim_eq!(result, Err(CustomContractError::Unauthorized.into()));
+
+        ctx.set_sender(Address::Account(MAINTAINER));
+        let result = reserve_domains(&ctx, &mut host);
+        claim_eq!(result, Ok(()));
+        let result = reserve_domains(&ctx, &mut host);
+        claim_eq!(result, Err(CustomContractError::AlreadyExists.into()));
+
+        let params = ReserveDomainsParams {
+            domains: vec![String::from("sub.test.ccd")],
+        };
+        let bytes = to_bytes(&params);
+        ctx.set_parameter(&bytes);
+        let result = reserve_domains(&ctx, &mut host);
+        claim_eq!(result, Err(CustomContractError::InvalidDomainFormat.into()));
+
         host.setup_mock_entrypoint(
             PRICE_ORACLE,
             OwnedEntrypointName::new_unchecked(String::from("getYearlyDomainPrice")),
             parse_and_map_mock(test_price_oracle),
         );
-        // Get ownership info
         host.setup_mock_entrypoint(
             CNS_NFT,
             OwnedEntrypointName::new_unchecked(String::from("getTokenExpiry")),
-            parse_and_ok_mock::<TokenParams, _>(Some(TokenSubscriptionStatus {
-                owner: Address::Account(USER_2),
-                expiry: SubscriptionExpiryStatus::Expired,
-            })),
+            parse_and_ok_mock::<TokenParams, _>(None::<TokenSubscriptionStatus>),
         );
-        // Burn expired token
         host.setup_mock_entrypoint(
             CNS_NFT,
-            OwnedEntrypointName::new_unchecked(String::from("burn")),
-            parse_and_ok_mock::<TokenIdVec, _>(()),
+            OwnedEntrypointName::new_unchecked(String::from("mint")),
+            parse_and_ok_mock::<CnsMintParams, _>(()),
         );
-        // Clear registry data
         host.setup_mock_entrypoint(
             REGISTRY,
             OwnedEntrypointName::new_unchecked(String::from("remove")),
-            parse_and_ok_mock::<StorageKeys, _>(()),
-        );
-        // Mint token on success
-        host.setup_mock_entrypoint(
-            CNS_NFT,
-            OwnedEntrypointName::new_unchecked(String::from("mint")),
-            parse_and_ok_mock::<CnsMintParams, _>(()),
+            parse_and_ok_mock::<StorageEntries, _>(()),
         );
-        // Update registry data
         host.setup_mock_entrypoint(
             REGISTRY,
             OwnedEntrypointName::new_unchecked(String::from("insert")),
             parse_and_ok_mock::<StorageEntries, _>(()),
         );
-        let invoke_amount = TEST_YEARLY_DOMAIN_PRICE * 2 + Amount::from_ccd(2);
-        host.set_self_balance(invoke_amount);
-        let result = register(&ctx, &mut host, invoke_amount);
 
+        // Public can't register reserved domains
+        let params = RegisterParams {
+            domain: String::from("test.ccd"),
+            address: Address::Account(USER_1),
+            duration_years: 1,
+        };
+        let register_bytes = to_bytes(&params);
+        let mut register_ctx = TestReceiveContext::empty();
+        register_ctx
+            .set_sender(Address::Account(USER_1))
+            .set_invoker(USER_1)
+            .set_parameter(&register_bytes)
+            .set_metadata_slot_time(test_slot_time());
+        host.set_self_balance(TEST_YEARLY_DOMAIN_PRICE);
+        let result = register(
+            &register_ctx,
+            &mut host,
+            TEST_YEARLY_DOMAIN_PRICE,
+            &mut logger,
+        );
+        claim_eq!(result, Err(CustomContractError::Reserved.into()));
+
+        // Maintainers still can
+        register_ctx
+            .set_sender(Address::Account(MAINTAINER))
+            .set_invoker(MAINTAINER);
+        let result = register(
+            &register_ctx,
+            &mut host,
+            TEST_YEARLY_DOMAIN_PRICE,
+            &mut logger,
+        );
+        claim_eq!(result, Ok(()));
+
+        // Released domains can be registered by anyone
+        let params = ReserveDomainsParams {
+            domains: vec![String::from("test.ccd")],
+        };
+        let bytes = to_bytes(&params);
+        ctx.set_parameter(&bytes);
+        let result = release_domains(&ctx, &mut host);
+        claim_eq!(result, Ok(()));
+        let result = release_domains(&ctx, &mut host);
+        claim_eq!(result, Err(CustomContractError::NotFound.into()));
+
+        register_ctx
+            .set_sender(Address::Account(USER_1))
+            .set_invoker(USER_1);
+        let result = register(
+            &register_ctx,
+            &mut host,
+            TEST_YEARLY_DOMAIN_PRICE,
+            &mut logger,
+        );
         claim_eq!(result, Ok(()));
-        // Transfer subscription cost
-        claim!(host.transfer_occurred(&ADMIN, TEST_YEARLY_DOMAIN_PRICE * 2));
-        // Return extra
-        claim!(host.transfer_occurred(&USER_1, Amount::from_ccd(2)));
     }
 
     #[concordium_test]
-    fn test_register_grace() {
+    fn test_register_many() {
         let mut host = default_host();
-
-        let mut ctx = TestReceiveContext::empty();
-        let params = RegisterParams {
-            domain: String::from("test.ccd"),
-            address: Address::Account(USER_1),
-            duration_years: 2,
-        };
+        let mut logger = TestLogger::init();
+
+        let params = vec![
+            RegisterParams {
+                domain: String::from("test.ccd"),
+                address: Address::Account(USER_1),
+                duration_years: 2,
+            },
+            RegisterParams {
+                domain: String::from("tset.ccd"),
+                address: Address::Account(USER_1),
+                duration_years: 1,
+            },
+        ];
+        let bytes = to_bytes(&params);
+        let mut ctx = TestReceiveContext::empty();
+        ctx.set_sender(Address::Account(USER_1))
+            .set_invoker(USER_1)
+            .set_parameter(&bytes)
+            .set_metadata_slot_time(test_slot_time());
+        host.setup_mock_entrypoint(
+            PRICE_ORACLE,
+            OwnedEntrypointName::new_unchecked(String::from("getYearlyDomainPrices")),
+            parse_and_map_mock(|params: &Vec<GetDomainPriceParams>| {
+                params
+                    .iter()
+                    .map(test_price_oracle)
+                    .collect::<Option<Vec<_>>>()
+            }),
+        );
+        host.setup_mock_entrypoint(
+            CNS_NFT,
+            OwnedEntrypointName::new_unchecked(String::from("getTokenExpiry")),
+            parse_and_ok_mock::<TokenParams, _>(None::<TokenSubscriptionStatus>),
+        );
+        host.setup_mock_entrypoint(
+            CNS_NFT,
+            OwnedEntrypointName::new_unchecked(String::from("mint")),
+            parse_and_ok_mock::<CnsMintParams, _>(()),
+        );
+        host.setup_mock_entrypoint(
+            REGISTRY,
+            OwnedEntrypointName::new_unchecked(String::from("remove")),
+            parse_and_ok_mock::<StorageEntries, _>(()),
+        );
+        host.setup_mock_entrypoint(
+            REGISTRY,
+            OwnedEntrypointName::new_unchecked(String::from("insert")),
+            parse_and_ok_mock::<StorageEntries, _>(()),
+        );
+
+        // Attached amount must cover all domains
+        let invoke_amount = TEST_YEARLY_DOMAIN_PRICE * 2;
+        host.set_self_balance(invoke_amount);
+        let result = register_many(&ctx, &mut host, invoke_amount, &mut logger);
+        claim_eq!(result, Err(CustomContractError::InsufficientFunds.into()));
+
+        // Fee is paid and the remainder refunded once
+        let invoke_amount = TEST_YEARLY_DOMAIN_PRICE * 3 + Amount::from_ccd(2);
+        host.set_self_balance(invoke_amount);
+        let result = register_many(&ctx, &mut host, invoke_amount, &mut logger);
+        claim_eq!(result, Ok(()));
+        claim!(host.transfer_occurred(&ADMIN, TEST_YEARLY_DOMAIN_PRICE * 3));
+        claim!(host.transfer_occurred(&USER_1, Amount::from_ccd(2)));
+
+        // Duplicate domains reject the whole bundle
+        let params = vec![
+            RegisterParams {
+                domain: String::from("dupe.ccd"),
+                address: Address::Account(USER_1),
+                duration_years: 1,
+            },
+            RegisterParams {
+                domain: String::from("dupe.ccd"),
+                address: Address::Account(USER_2),
+                duration_years: 1,
+            },
+        ];
+        let bytes = to_bytes(&params);
+        ctx.set_parameter(&bytes);
+        let invoke_amount = TEST_YEARLY_DOMAIN_PRICE * 2;
+        host.set_self_balance(invoke_amount);
+        let result = register_many(&ctx, &mut host, invoke_amount, &mut logger);
+        claim_eq!(result, Err(CustomContractError::AlreadyExists.into()));
+    }
+
+    #[concordium_test]
+    fn test_commit_reveal() {
+        let mut host = default_host();
+        let mut logger = TestLogger::init();
+        host.state_mut().min_commitment_age = Duration::from_millis(60_000);
+        host.state_mut().max_commitment_age = Duration::from_millis(86_400_000);
+        host.setup_mock_entrypoint(
+            PRICE_ORACLE,
+            OwnedEntrypointName::new_unchecked(String::from("getYearlyDomainPrice")),
+            parse_and_map_mock(test_price_oracle),
+        );
+        host.setup_mock_entrypoint(
+            CNS_NFT,
+            OwnedEntrypointName::new_unchecked(String::from("getTokenExpiry")),
+            parse_and_ok_mock::<TokenParams, _>(None::<TokenSubscriptionStatus>),
+        );
+        host.setup_mock_entrypoint(
+            CNS_NFT,
+            OwnedEntrypointName::new_unchecked(String::from("mint")),
+            parse_and_ok_mock::<CnsMintParams, _>(()),
+        );
+        host.setup_mock_entrypoint(
+            REGISTRY,
+            OwnedEntrypointName::new_unchecked(String::from("remove")),
+            parse_and_ok_mock::<StorageEntries, _>(()),
+        );
+        host.setup_mock_entrypoint(
+            REGISTRY,
+            OwnedEntrypointName::new_unchecked(String::from("insert")),
+            parse_and_ok_mock::<StorageEntries, _>(()),
+        );
+
+        let registration = RegisterParams {
+            domain: String::from("test.ccd"),
+            address: Address::Account(USER_1),
+            duration_years: 1,
+        };
+        let salt = [7; 32];
+        let commitment = registration_commitment(&registration, &Address::Account(USER_1), &salt);
+
+        // Direct registration is not allowed while commit-reveal is enabled
+        let bytes = to_bytes(&registration);
+        let mut ctx = TestReceiveContext::empty();
+        ctx.set_sender(Address::Account(USER_1))
+            .set_invoker(USER_1)
+            .set_parameter(&bytes)
+            .set_metadata_slot_time(test_slot_time());
+        let result = register(&ctx, &mut host, TEST_YEARLY_DOMAIN_PRICE, &mut logger);
+        claim_eq!(
+            result,
+            Err(CustomContractError::OperationNotPermitted.into())
+        );
+
+        let bytes = to_bytes(&commitment);
+        ctx.set_parameter(&bytes);
+        let result = commit(&ctx, &mut host);
+        claim_eq!(result, Ok(()));
+
+        // Pending commitment can't be refreshed
+        let result = commit(&ctx, &mut host);
+        claim_eq!(result, Err(CustomContractError::AlreadyExists.into()));
+
+        // Commitment can't be revealed before it matures
+        let params = RevealParams { registration, salt };
+        let bytes = to_bytes(&params);
+        ctx.set_parameter(&bytes);
+        let result = reveal(&ctx, &mut host, TEST_YEARLY_DOMAIN_PRICE, &mut logger);
+        claim_eq!(
+            result,
+            Err(CustomContractError::OperationNotPermitted.into())
+        );
+
+        // Only the committer can reveal
+        ctx.set_sender(Address::Account(USER_2))
+            .set_invoker(USER_2)
+            .set_metadata_slot_time(
+                test_slot_time()
+                    .checked_add(Duration::from_millis(60_000))
+                    .unwrap(),
+            );
+        let result = reveal(&ctx, &mut host, TEST_YEARLY_DOMAIN_PRICE, &mut logger);
+        claim_eq!(result, Err(CustomContractError::NotFound.into()));
+
+        let invoke_amount = TEST_YEARLY_DOMAIN_PRICE + Amount::from_ccd(1);
+        host.set_self_balance(invoke_amount);
+        ctx.set_sender(Address::Account(USER_1)).set_invoker(USER_1);
+        let result = reveal(&ctx, &mut host, invoke_amount, &mut logger);
+        claim_eq!(result, Ok(()));
+        claim!(host.transfer_occurred(&ADMIN, TEST_YEARLY_DOMAIN_PRICE));
+        claim!(host.transfer_occurred(&USER_1, Amount::from_ccd(1)));
+        claim!(host.state().commitments.get(&commitment).is_none());
+    }
+
+    #[concordium_test]
+    fn test_register_expired() {
+        let mut host = default_host();
+        let mut logger = TestLogger::init();
+
+        let mut ctx = TestReceiveContext::empty();
+        let params = RegisterParams {
+            domain: String::from("test.ccd"),
+            address: Address::Account(USER_1),
+            duration_years: 2,
+        };
+        let bytes = to_bytes(&params);
+        ctx.set_sender(Address::Account(USER_1))
+            .set_invoker(USER_1)
+            .set_parameter(&bytes)
+            .set_metadata_slot_time(test_slot_time());
+        // Get pricing info
+        host.setup_mock_entrypoint(
+            PRICE_ORACLE,
+            OwnedEntrypointName::new_unchecked(String::from("getYearlyDomainPrice")),
+            parse_and_map_mock(test_price_oracle),
+        );
+        // Get ownership info
+        host.setup_mock_entrypoint(
+            CNS_NFT,
+            OwnedEntrypointName::new_unchecked(String::from("getTokenExpiry")),
+            parse_and_ok_mock::<TokenParams, _>(Some(TokenSubscriptionStatus {
+                owner: Address::Account(USER_2),
+                expiry: SubscriptionExpiryStatus::Expired,
+            })),
+        );
+        // Burn expired token
+        host.setup_mock_entrypoint(
+            CNS_NFT,
+            OwnedEntrypointName::new_unchecked(String::from("burn")),
+            parse_and_ok_mock::<TokenIdVec, _>(()),
+        );
+        // Clear registry data
+        host.setup_mock_entrypoint(
+            REGISTRY,
+            OwnedEntrypointName::new_unchecked(String::from("remove")),
+            parse_and_ok_mock::<StorageKeys, _>(()),
+        );
+        // Mint token on success
+        host.setup_mock_entrypoint(
+            CNS_NFT,
+            OwnedEntrypointName::new_unchecked(String::from("mint")),
+            parse_and_ok_mock::<CnsMintParams, _>(()),
+        );
+        // Update registry data
+        host.setup_mock_entrypoint(
+            REGISTRY,
+            OwnedEntrypointName::new_unchecked(String::from("insert")),
+            parse_and_ok_mock::<StorageEntries, _>(()),
+        );
+        let invoke_amount = TEST_YEARLY_DOMAIN_PRICE * 2 + Amount::from_ccd(2);
+        host.set_self_balance(invoke_amount);
+        let result = register(&ctx, &mut host, invoke_amount, &mut logger);
+
+        claim_eq!(result, Ok(()));
+        // Transfer subscription cost
+        claim!(host.transfer_occurred(&ADMIN, TEST_YEARLY_DOMAIN_PRICE * 2));
+        // Return extra
+        claim!(host.transfer_occurred(&USER_1, Amount::from_ccd(2)));
+    }
+
+    #[concordium_test]
+    fn test_register_grace() {
+        let mut host = default_host();
+        let mut logger = TestLogger::init();
+
+        let mut ctx = TestReceiveContext::empty();
+        let params = RegisterParams {
+            domain: String::from("test.ccd"),
+            address: Address::Account(USER_1),
+            duration_years: 2,
+        };
         let bytes = to_bytes(&params);
         ctx.set_sender(Address::Account(USER_1))
             .set_invoker(USER_1)
@@ -1017,27 +2966,664 @@ mod tests {
                 ),
             })),
         );
-        let invoke_amount = TEST_YEARLY_DOMAIN_PRICE * 2 + Amount::from_ccd(2);
-        host.set_self_balance(invoke_amount);
-        let result = register(&ctx, &mut host, invoke_amount);
+        let invoke_amount = TEST_YEARLY_DOMAIN_PRICE * 2 + Amount::from_ccd(2);
+        host.set_self_balance(invoke_amount);
+        let result = register(&ctx, &mut host, invoke_amount, &mut logger);
+
+        claim_eq!(result, Err(CustomContractError::AlreadyExists.into()));
+    }
+
+    #[concordium_test]
+    fn test_auction_premium() {
+        let auction = |decay| ExpiredDomainAuction {
+            start_price: Amount::from_micro_ccd(1000),
+            duration: Duration::from_days(4),
+            decay,
+        };
+
+        let linear = auction(PremiumDecay::Linear);
+        claim_eq!(
+            linear.premium(Duration::from_days(0)),
+            Amount::from_micro_ccd(1000)
+        );
+        claim_eq!(
+            linear.premium(Duration::from_days(1)),
+            Amount::from_micro_ccd(750)
+        );
+        claim_eq!(linear.premium(Duration::from_days(4)), Amount::zero());
+
+        // Value at the end of the auction, 1000 / 2^4 = 62, is subtracted
+        let exponential = auction(PremiumDecay::Exponential {
+            half_life: Duration::from_days(1),
+        });
+        claim_eq!(
+            exponential.premium(Duration::from_days(0)),
+            Amount::from_micro_ccd(938)
+        );
+        claim_eq!(
+            exponential.premium(Duration::from_days(1)),
+            Amount::from_micro_ccd(438)
+        );
+        claim_eq!(
+            exponential.premium(Duration::from_hours(36)),
+            Amount::from_micro_ccd(313)
+        );
+        claim_eq!(exponential.premium(Duration::from_days(4)), Amount::zero());
+        claim_eq!(
+            exponential.premium(Duration::from_days(400)),
+            Amount::zero()
+        );
+    }
+
+    #[concordium_test]
+    fn test_register_auctioned() {
+        let mut host = default_host();
+        let mut logger = TestLogger::init();
+        let premium = Amount::from_ccd(50);
+
+        // Premium decays from 100 CCD to zero over 10 days
+        let mut ctx = TestReceiveContext::empty();
+        let auction = ExpiredDomainAuction {
+            start_price: Amount::from_ccd(100),
+            duration: Duration::from_days(10),
+            decay: PremiumDecay::Linear,
+        };
+        let bytes = to_bytes(&InternalValue::ExpiredDomainAuction(auction));
+        ctx.set_sender(Address::Account(USER_1))
+            .set_parameter(&bytes);
+        let result = update_internal_value(&ctx, &mut host);
+        claim_eq!(result, Err(CustomContractError::Unauthorized.into()));
+
+        ctx.set_sender(Address::Account(MAINTAINER));
+        let result = update_internal_value(&ctx, &mut host);
+        claim_eq!(result, Ok(()));
+
+        // Grace period ended 5 days ago
+        let grace_end = Timestamp::from_timestamp_millis(
+            test_slot_time().timestamp_millis() - Duration::from_days(5).millis(),
+        );
+        host.setup_mock_entrypoint(
+            CNS_NFT,
+            OwnedEntrypointName::new_unchecked(String::from("getTokenExpiries")),
+            parse_and_ok_mock::<TokenListParams, _>(vec![Some(TokenExpiry {
+                expiry: Timestamp::from_timestamp_millis(0),
+                grace_end,
+            })]),
+        );
+
+        let mut ctx = TestReceiveContext::empty();
+        let bytes = to_bytes(&GetPremiumParams {
+            domain: String::from("test.ccd"),
+        });
+        ctx.set_parameter(&bytes)
+            .set_metadata_slot_time(test_slot_time());
+        claim_eq!(get_premium(&ctx, &host), Ok(premium));
+
+        let mut ctx = TestReceiveContext::empty();
+        let params = RegisterParams {
+            domain: String::from("test.ccd"),
+            address: Address::Account(USER_1),
+            duration_years: 2,
+        };
+        let bytes = to_bytes(&params);
+        ctx.set_sender(Address::Account(USER_1))
+            .set_invoker(USER_1)
+            .set_parameter(&bytes)
+            .set_metadata_slot_time(test_slot_time());
+        host.setup_mock_entrypoint(
+            PRICE_ORACLE,
+            OwnedEntrypointName::new_unchecked(String::from("getYearlyDomainPrice")),
+            parse_and_map_mock(test_price_oracle),
+        );
+        host.setup_mock_entrypoint(
+            CNS_NFT,
+            OwnedEntrypointName::new_unchecked(String::from("getTokenExpiry")),
+            parse_and_ok_mock::<TokenParams, _>(Some(TokenSubscriptionStatus {
+                owner: Address::Account(USER_2),
+                expiry: SubscriptionExpiryStatus::Expired,
+            })),
+        );
+        host.setup_mock_entrypoint(
+            CNS_NFT,
+            OwnedEntrypointName::new_unchecked(String::from("burn")),
+            parse_and_ok_mock::<TokenIdVec, _>(()),
+        );
+        host.setup_mock_entrypoint(
+            REGISTRY,
+            OwnedEntrypointName::new_unchecked(String::from("remove")),
+            parse_and_ok_mock::<StorageKeys, _>(()),
+        );
+        host.setup_mock_entrypoint(
+            CNS_NFT,
+            OwnedEntrypointName::new_unchecked(String::from("mint")),
+            parse_and_check_mock::<CnsMintParams, _>(
+                |params| params.owner == Address::Account(USER_1),
+                (),
+            ),
+        );
+        host.setup_mock_entrypoint(
+            REGISTRY,
+            OwnedEntrypointName::new_unchecked(String::from("insert")),
+            parse_and_ok_mock::<StorageEntries, _>(()),
+        );
+
+        let invoke_amount = TEST_YEARLY_DOMAIN_PRICE * 2 + premium + Amount::from_ccd(2);
+        host.set_self_balance(invoke_amount);
+        let result = register(&ctx, &mut host, invoke_amount, &mut logger);
+        claim_eq!(result, Ok(()));
+        // Winning price goes to the beneficiary
+        claim!(host.transfer_occurred(&ADMIN, TEST_YEARLY_DOMAIN_PRICE * 2 + premium));
+        claim!(host.transfer_occurred(&USER_1, Amount::from_ccd(2)));
+    }
+
+    #[concordium_test]
+    fn test_extend() {
+        let mut host = default_host();
+        let mut logger = TestLogger::init();
+
+        let mut ctx = TestReceiveContext::empty();
+        let params = ExtendParams {
+            domain: String::from("test.ccd"),
+            duration_years: 2,
+        };
+        let bytes = to_bytes(&params);
+        ctx.set_sender(Address::Account(USER_2))
+            .set_invoker(USER_2)
+            .set_parameter(&bytes)
+            .set_metadata_slot_time(test_slot_time());
+        host.setup_mock_entrypoint(
+            CNS_NFT,
+            OwnedEntrypointName::new_unchecked(String::from("getTokenExpiry")),
+            parse_and_ok_mock::<TokenParams, _>(Some(TokenSubscriptionStatus {
+                owner: Address::Account(USER_1),
+                expiry: SubscriptionExpiryStatus::Owned(
+                    test_slot_time()
+                        .checked_add(Duration::from_millis(YEAR_MILLIS))
+                        .unwrap(),
+                ),
+            })),
+        );
+        host.setup_mock_entrypoint(
+            PRICE_ORACLE,
+            OwnedEntrypointName::new_unchecked(String::from("getYearlyDomainPrice")),
+            parse_and_map_mock(test_price_oracle),
+        );
+        host.setup_mock_entrypoint(
+            CNS_NFT,
+            OwnedEntrypointName::new_unchecked(String::from("lend")),
+            parse_and_ok_mock::<LendParams, _>(()),
+        );
+
+        let invoke_amount = TEST_YEARLY_DOMAIN_PRICE * 2 + Amount::from_ccd(2);
+        host.set_self_balance(invoke_amount);
+
+        let result = extend(&ctx, &mut host, invoke_amount, &mut logger);
+
+        claim_eq!(result, Ok(()));
+        // Transfer subscription cost
+        claim!(host.transfer_occurred(&ADMIN, TEST_YEARLY_DOMAIN_PRICE * 2));
+        // Return extra
+        claim!(host.transfer_occurred(&USER_2, Amount::from_ccd(2)));
+    }
+
+    #[concordium_test]
+    fn test_extend_normalized() {
+        let mut host = default_host();
+        let mut logger = TestLogger::init();
+
+        let mut ctx = TestReceiveContext::empty();
+        let params = ExtendParams {
+            domain: String::from("TEST.Ccd"),
+            duration_years: 1,
+        };
+        let bytes = to_bytes(&params);
+        ctx.set_sender(Address::Account(USER_2))
+            .set_invoker(USER_2)
+            .set_parameter(&bytes)
+            .set_metadata_slot_time(test_slot_time());
+        // Token of the normalized name is extended
+        let token_id = TokenIdVec(
+            TokenizedDomain::try_from("test.ccd")
+                .unwrap()
+                .namehash()
+                .into(),
+        );
+        host.setup_mock_entrypoint(
+            CNS_NFT,
+            OwnedEntrypointName::new_unchecked(String::from("getTokenExpiry")),
+            parse_and_check_mock(
+                move |params: &TokenParams| params.token_id == token_id,
+                Some(TokenSubscriptionStatus {
+                    owner: Address::Account(USER_1),
+                    expiry: SubscriptionExpiryStatus::Owned(
+                        test_slot_time()
+                            .checked_add(Duration::from_millis(YEAR_MILLIS))
+                            .unwrap(),
+                    ),
+                }),
+            ),
+        );
+        host.setup_mock_entrypoint(
+            PRICE_ORACLE,
+            OwnedEntrypointName::new_unchecked(String::from("getYearlyDomainPrice")),
+            parse_and_map_mock(test_price_oracle),
+        );
+        host.setup_mock_entrypoint(
+            CNS_NFT,
+            OwnedEntrypointName::new_unchecked(String::from("lend")),
+            parse_and_ok_mock::<LendParams, _>(()),
+        );
+
+        host.set_self_balance(TEST_YEARLY_DOMAIN_PRICE);
+        let result = extend(&ctx, &mut host, TEST_YEARLY_DOMAIN_PRICE, &mut logger);
+
+        claim_eq!(result, Ok(()));
+        claim_eq!(
+            logger.logs,
+            vec![to_bytes(&CnsEvent::Extended {
+                domain: String::from("test.ccd"),
+                duration_years: 1,
+            })]
+        );
+    }
+
+    #[concordium_test]
+    fn test_extend_in_grace() {
+        let mut host = default_host();
+        let mut logger = TestLogger::init();
+
+        let mut ctx = TestReceiveContext::empty();
+        let params = ExtendParams {
+            domain: String::from("test.ccd"),
+            duration_years: 1,
+        };
+        let bytes = to_bytes(&params);
+        ctx.set_sender(Address::Account(USER_1))
+            .set_invoker(USER_1)
+            .set_parameter(&bytes)
+            .set_metadata_slot_time(test_slot_time());
+        host.setup_mock_entrypoint(
+            CNS_NFT,
+            OwnedEntrypointName::new_unchecked(String::from("getTokenExpiry")),
+            parse_and_ok_mock::<TokenParams, _>(Some(TokenSubscriptionStatus {
+                owner: Address::Account(USER_1),
+                expiry: SubscriptionExpiryStatus::Grace(
+                    test_slot_time()
+                        .checked_add(Duration::from_days(30))
+                        .unwrap(),
+                ),
+            })),
+        );
+        host.setup_mock_entrypoint(
+            PRICE_ORACLE,
+            OwnedEntrypointName::new_unchecked(String::from("getYearlyDomainPrice")),
+            parse_and_map_mock(test_price_oracle),
+        );
+        host.setup_mock_entrypoint(
+            CNS_NFT,
+            OwnedEntrypointName::new_unchecked(String::from("lend")),
+            parse_and_ok_mock::<LendParams, _>(()),
+        );
+
+        // Regular yearly price doesn't cover a renewal in grace period
+        host.set_self_balance(TEST_YEARLY_DOMAIN_PRICE);
+        let result = extend(&ctx, &mut host, TEST_YEARLY_DOMAIN_PRICE, &mut logger);
+        claim_eq!(result, Err(CustomContractError::InsufficientFunds.into()));
+
+        host.set_self_balance(TEST_YEARLY_GRACE_RENEWAL_PRICE);
+        let result = extend(
+            &ctx,
+            &mut host,
+            TEST_YEARLY_GRACE_RENEWAL_PRICE,
+            &mut logger,
+        );
+        claim_eq!(result, Ok(()));
+        claim!(host.transfer_occurred(&ADMIN, TEST_YEARLY_GRACE_RENEWAL_PRICE));
+    }
+
+    #[concordium_test]
+    fn test_set_address() {
+        let mut host = default_host();
+        let mut logger = TestLogger::init();
+
+        let mut ctx = TestReceiveContext::empty();
+        let params = SetAddressParams {
+            domain: String::from("test.ccd"),
+            address: Address::Account(USER_1),
+        };
+        let bytes = to_bytes(&params);
+        ctx.set_sender(Address::Account(USER_1))
+            .set_parameter(&bytes);
+        host.setup_mock_entrypoint(
+            CNS_NFT,
+            OwnedEntrypointName::new_unchecked(String::from("getTokenExpiry")),
+            parse_and_ok_mock::<TokenParams, _>(Some(TokenSubscriptionStatus {
+                owner: Address::Account(USER_1),
+                expiry: SubscriptionExpiryStatus::Owned(
+                    test_slot_time()
+                        .checked_add(Duration::from_millis(YEAR_MILLIS))
+                        .unwrap(),
+                ),
+            })),
+        );
+        host.setup_mock_entrypoint(
+            REGISTRY,
+            OwnedEntrypointName::new_unchecked(String::from("update")),
+            parse_and_ok_mock::<StorageEntries, _>(()),
+        );
+        let result = set_address(&ctx, &mut host, &mut logger);
+        claim_eq!(result, Ok(()));
+        claim_eq!(
+            logger.logs,
+            vec![to_bytes(&CnsEvent::AddressChanged {
+                domain: String::from("test.ccd"),
+                address: Address::Account(USER_1),
+            })]
+        );
+    }
+
+    #[concordium_test]
+    fn test_chain_address() {
+        let mut host = default_host();
+        let mut logger = TestLogger::init();
+        let eth_address = Bytes(vec![0xAB; 20]);
+
+        let mut ctx = TestReceiveContext::empty();
+        let params = SetChainAddressParams {
+            domain: String::from("test.ccd"),
+            coin_type: 60,
+            address: eth_address.clone(),
+        };
+        let bytes = to_bytes(&params);
+        ctx.set_sender(Address::Account(USER_1))
+            .set_parameter(&bytes);
+        host.setup_mock_entrypoint(
+            CNS_NFT,
+            OwnedEntrypointName::new_unchecked(String::from("getTokenExpiry")),
+            parse_and_ok_mock::<TokenParams, _>(Some(TokenSubscriptionStatus {
+                owner: Address::Account(USER_1),
+                expiry: SubscriptionExpiryStatus::Owned(
+                    test_slot_time()
+                        .checked_add(Duration::from_millis(YEAR_MILLIS))
+                        .unwrap(),
+                ),
+            })),
+        );
+        // Chain address is stored under its own key
+        let expected_entry = StorageEntry::new(&ChainAddressKey(60), &eth_address);
+        host.setup_mock_entrypoint(
+            REGISTRY,
+            OwnedEntrypointName::new_unchecked(String::from("update")),
+            parse_and_check_mock::<StorageEntries, _>(
+                move |entries| entries.entries == vec![expected_entry.clone()],
+                (),
+            ),
+        );
+        let result = set_chain_address(&ctx, &mut host, &mut logger);
+        claim_eq!(result, Ok(()));
+        claim_eq!(
+            logger.logs,
+            vec![to_bytes(&CnsEvent::ChainAddressChanged {
+                domain: String::from("test.ccd"),
+                coin_type: 60,
+                address_hash: Some(record_hash(&eth_address)),
+            })]
+        );
+
+        // Only the owner can set chain addresses
+        ctx.set_sender(Address::Account(USER_2));
+        let result = set_chain_address(&ctx, &mut host, &mut logger);
+        claim_eq!(result, Err(CustomContractError::Unauthorized.into()));
+
+        let params = ResolveChainParams {
+            domain: String::from("test.ccd"),
+            coin_type: 60,
+        };
+        let bytes = to_bytes(&params);
+        ctx.set_parameter(&bytes);
+        host.setup_mock_entrypoint(
+            REGISTRY,
+            OwnedEntrypointName::new_unchecked(String::from("get")),
+            parse_and_ok_mock::<StorageKeys, _>(Some(StorageGetEntryResult {
+                prefix: Bytes(
+                    TokenizedDomain::try_from(params.domain.as_ref())
+                        .expect_report("Unable to parse domain")
+                        .namehash()
+                        .into(),
+                ),
+                entries: vec![MaybeStorageEntry {
+                    key: Bytes(to_bytes(&ChainAddressKey(60))),
+                    value: Some(Bytes(to_bytes(&eth_address))),
+                }],
+                expired: false,
+            })),
+        );
+        let result = resolve_chain(&ctx, &host);
+        claim_eq!(result, Ok(eth_address));
+    }
+
+    #[concordium_test]
+    fn test_content_hash() {
+        let mut host = default_host();
+        let mut logger = TestLogger::init();
+        let content_hash = ContentHash::Https {
+            url: String::from("https://bictory.io"),
+            sha256: [5; 32],
+        };
+
+        let mut ctx = TestReceiveContext::empty();
+        let params = SetContentHashParams {
+            domain: String::from("test.ccd"),
+            content_hash: Some(content_hash.clone()),
+        };
+        let bytes = to_bytes(&params);
+        ctx.set_sender(Address::Account(USER_1))
+            .set_parameter(&bytes);
+        host.setup_mock_entrypoint(
+            CNS_NFT,
+            OwnedEntrypointName::new_unchecked(String::from("getTokenExpiry")),
+            parse_and_ok_mock::<TokenParams, _>(Some(TokenSubscriptionStatus {
+                owner: Address::Account(USER_1),
+                expiry: SubscriptionExpiryStatus::Owned(
+                    test_slot_time()
+                        .checked_add(Duration::from_millis(YEAR_MILLIS))
+                        .unwrap(),
+                ),
+            })),
+        );
+        let expected_entry = StorageEntry::new(&ContentHashKey, &content_hash);
+        host.setup_mock_entrypoint(
+            REGISTRY,
+            OwnedEntrypointName::new_unchecked(String::from("update")),
+            parse_and_check_mock::<StorageEntries, _>(
+                move |entries| entries.entries == vec![expected_entry.clone()],
+                (),
+            ),
+        );
+        let result = set_content_hash(&ctx, &mut host, &mut logger);
+        claim_eq!(result, Ok(()));
+        claim_eq!(
+            logger.logs,
+            vec![to_bytes(&CnsEvent::ContentHashChanged {
+                domain: String::from("test.ccd"),
+                record_hash: Some(record_hash(&content_hash)),
+            })]
+        );
+
+        // Only the owner can set content hash
+        ctx.set_sender(Address::Account(USER_2));
+        let result = set_content_hash(&ctx, &mut host, &mut logger);
+        claim_eq!(result, Err(CustomContractError::Unauthorized.into()));
+
+        let params = ResolveParams {
+            domain: String::from("test.ccd"),
+        };
+        let bytes = to_bytes(&params);
+        ctx.set_parameter(&bytes);
+        host.setup_mock_entrypoint(
+            REGISTRY,
+            OwnedEntrypointName::new_unchecked(String::from("get")),
+            parse_and_ok_mock::<StorageKeys, _>(Some(StorageGetEntryResult {
+                prefix: Bytes(
+                    TokenizedDomain::try_from(params.domain.as_ref())
+                        .expect_report("Unable to parse domain")
+                        .namehash()
+                        .into(),
+                ),
+                entries: vec![MaybeStorageEntry {
+                    key: Bytes(to_bytes(&ContentHashKey)),
+                    value: Some(Bytes(to_bytes(&content_hash))),
+                }],
+                expired: false,
+            })),
+        );
+        let result = get_content_hash(&ctx, &host);
+        claim_eq!(result, Ok(content_hash));
+    }
+
+    #[concordium_test]
+    fn test_freeze_domain() {
+        let mut host = default_host();
+        let mut logger = TestLogger::init();
+        let reason_hash = [3; 32];
+
+        let mut ctx = TestReceiveContext::empty();
+        let params = FreezeDomainParams {
+            domain: String::from("test.ccd"),
+            reason_hash,
+        };
+        let bytes = to_bytes(&params);
+        ctx.set_sender(Address::Account(USER_1))
+            .set_parameter(&bytes)
+            .set_metadata_slot_time(test_slot_time());
+
+        // Only maintainers can freeze domains
+        let result = freeze_domain(&ctx, &mut host, &mut logger);
+        claim_eq!(result, Err(CustomContractError::Unauthorized.into()));
+
+        ctx.set_sender(Address::Account(MAINTAINER));
+        let result = freeze_domain(&ctx, &mut host, &mut logger);
+        claim_eq!(result, Ok(()));
+        claim_eq!(
+            logger.logs,
+            vec![to_bytes(&CnsEvent::DomainFrozen {
+                domain: String::from("test.ccd"),
+                reason_hash,
+                by: Address::Account(MAINTAINER),
+            })]
+        );
+
+        // Freeze applies to subdomains as well
+        let params = GetFreezeParams {
+            domain: String::from("sub.test.ccd"),
+        };
+        let bytes = to_bytes(&params);
+        ctx.set_parameter(&bytes);
+        let result = get_freeze(&ctx, &host);
+        claim_eq!(
+            result,
+            Ok(Some(DomainFreeze {
+                reason_hash,
+                frozen_by: Address::Account(MAINTAINER),
+                frozen_at: test_slot_time(),
+            }))
+        );
+
+        // Owner can't change the records of a frozen domain
+        host.setup_mock_entrypoint(
+            CNS_NFT,
+            OwnedEntrypointName::new_unchecked(String::from("getTokenExpiry")),
+            parse_and_ok_mock::<TokenParams, _>(Some(TokenSubscriptionStatus {
+                owner: Address::Account(USER_1),
+                expiry: SubscriptionExpiryStatus::Owned(
+                    test_slot_time()
+                        .checked_add(Duration::from_millis(YEAR_MILLIS))
+                        .unwrap(),
+                ),
+            })),
+        );
+        host.setup_mock_entrypoint(
+            REGISTRY,
+            OwnedEntrypointName::new_unchecked(String::from("update")),
+            parse_and_ok_mock::<StorageEntries, _>(()),
+        );
+        let params = SetAddressParams {
+            domain: String::from("test.ccd"),
+            address: Address::Account(USER_2),
+        };
+        let bytes = to_bytes(&params);
+        ctx.set_sender(Address::Account(USER_1))
+            .set_parameter(&bytes);
+        let result = set_address(&ctx, &mut host, &mut logger);
+        claim_eq!(result, Err(CustomContractError::Frozen.into()));
 
-        claim_eq!(result, Err(CustomContractError::AlreadyExists.into()));
+        let params = SetDataParams {
+            domain: String::from("sub.test.ccd"),
+            key: String::from("url"),
+            value: DataValue::Url(String::from("https://example.com")),
+        };
+        let bytes = to_bytes(&params);
+        ctx.set_parameter(&bytes);
+        let result = set_data(&ctx, &mut host, &mut logger);
+        claim_eq!(result, Err(CustomContractError::Frozen.into()));
+
+        // Subdomains of a frozen domain can't be created or deleted
+        let params = CreateSubdomainParams {
+            subdomain: String::from("sub.test.ccd"),
+            mint_as_token: false,
+        };
+        let bytes = to_bytes(&params);
+        ctx.set_parameter(&bytes);
+        let result = create_subdomain(&ctx, &mut host, Amount::zero(), &mut logger);
+        claim_eq!(result, Err(CustomContractError::Frozen.into()));
+
+        let params = SubdomainParams {
+            subdomain: String::from("sub.test.ccd"),
+        };
+        let bytes = to_bytes(&params);
+        ctx.set_parameter(&bytes);
+        let result = delete_subdomain(&ctx, &mut host, &mut logger);
+        claim_eq!(result, Err(CustomContractError::Frozen.into()));
+
+        // Records can be changed again after the freeze is lifted
+        let params = FreezeDomainParams {
+            domain: String::from("test.ccd"),
+            reason_hash: [4; 32],
+        };
+        let bytes = to_bytes(&params);
+        ctx.set_sender(Address::Account(MAINTAINER))
+            .set_parameter(&bytes);
+        let result = unfreeze_domain(&ctx, &mut host, &mut logger);
+        claim_eq!(result, Ok(()));
+        let result = unfreeze_domain(&ctx, &mut host, &mut logger);
+        claim_eq!(result, Err(CustomContractError::NotFound.into()));
+
+        let params = SetAddressParams {
+            domain: String::from("test.ccd"),
+            address: Address::Account(USER_2),
+        };
+        let bytes = to_bytes(&params);
+        ctx.set_sender(Address::Account(USER_1))
+            .set_parameter(&bytes);
+        let result = set_address(&ctx, &mut host, &mut logger);
+        claim_eq!(result, Ok(()));
     }
 
     #[concordium_test]
-    fn test_extend() {
+    fn test_transfer_domain() {
         let mut host = default_host();
+        let mut logger = TestLogger::init();
 
-        let mut ctx = TestReceiveContext::empty();
-        let params = ExtendParams {
+        let params = TransferDomainParams {
             domain: String::from("test.ccd"),
-            duration_years: 2,
+            to: Receiver::Account(USER_2),
+            address: Address::Account(USER_2),
+            clear_data: true,
         };
         let bytes = to_bytes(&params);
-        ctx.set_sender(Address::Account(USER_2))
-            .set_invoker(USER_2)
-            .set_parameter(&bytes)
-            .set_metadata_slot_time(test_slot_time());
+        let mut ctx = TestReceiveContext::empty();
+        ctx.set_sender(Address::Account(USER_1))
+            .set_parameter(&bytes);
         host.setup_mock_entrypoint(
             CNS_NFT,
             OwnedEntrypointName::new_unchecked(String::from("getTokenExpiry")),
@@ -1051,45 +3637,267 @@ mod tests {
             })),
         );
         host.setup_mock_entrypoint(
-            PRICE_ORACLE,
-            OwnedEntrypointName::new_unchecked(String::from("getYearlyDomainPrice")),
-            parse_and_map_mock(test_price_oracle),
+            CNS_NFT,
+            OwnedEntrypointName::new_unchecked(String::from("transfer")),
+            parse_and_check_mock::<TransferParams<TokenIdVec>, _>(
+                |params| {
+                    params.0.len() == 1
+                        && params.0[0].from == Address::Account(USER_1)
+                        && params.0[0].to.address() == Address::Account(USER_2)
+                },
+                (),
+            ),
         );
         host.setup_mock_entrypoint(
-            CNS_NFT,
-            OwnedEntrypointName::new_unchecked(String::from("lend")),
-            parse_and_ok_mock::<LendParams, _>(()),
+            REGISTRY,
+            OwnedEntrypointName::new_unchecked(String::from("remove")),
+            parse_and_ok_mock::<StorageKeys, _>(()),
+        );
+        host.setup_mock_entrypoint(
+            REGISTRY,
+            OwnedEntrypointName::new_unchecked(String::from("insert")),
+            parse_and_ok_mock::<StorageEntries, _>(()),
+        );
+        host.setup_mock_entrypoint(
+            REGISTRY,
+            OwnedEntrypointName::new_unchecked(String::from("get")),
+            parse_and_ok_mock::<StorageKeys, _>(None::<StorageGetEntryResult>),
         );
 
-        let invoke_amount = TEST_YEARLY_DOMAIN_PRICE * 2 + Amount::from_ccd(2);
-        host.set_self_balance(invoke_amount);
-
-        let result = extend(&ctx, &mut host, invoke_amount);
+        // Only the owner can transfer the domain
+        ctx.set_sender(Address::Account(USER_2));
+        let result = transfer_domain(&ctx, &mut host, &mut logger);
+        claim_eq!(result, Err(CustomContractError::Unauthorized.into()));
 
+        ctx.set_sender(Address::Account(USER_1));
+        let result = transfer_domain(&ctx, &mut host, &mut logger);
         claim_eq!(result, Ok(()));
-        // Transfer subscription cost
-        claim!(host.transfer_occurred(&ADMIN, TEST_YEARLY_DOMAIN_PRICE * 2));
-        // Return extra
-        claim!(host.transfer_occurred(&USER_2, Amount::from_ccd(2)));
+        claim_eq!(
+            logger.logs,
+            vec![to_bytes(&CnsEvent::DomainTransferred {
+                domain: String::from("test.ccd"),
+                from: Address::Account(USER_1),
+                to: Address::Account(USER_2),
+            })]
+        );
     }
 
     #[concordium_test]
-    fn test_set_address() {
+    fn test_transfer_domain_with_subdomains() {
         let mut host = default_host();
+        let mut logger = TestLogger::init();
+        let holder = AccountAddress([18; 32]);
 
-        let mut ctx = TestReceiveContext::empty();
-        let params = SetAddressParams {
+        let namehash = |name: &str| {
+            TokenizedDomain::try_from(name)
+                .expect_report("Invalid domain")
+                .namehash()
+        };
+        let domain_namehash = namehash("test.ccd");
+        let plain_namehash = namehash("a.test.ccd");
+        let owned_namehash = namehash("b.test.ccd");
+        let sold_namehash = namehash("c.test.ccd");
+        host.state_mut().tokenized_subdomains.insert(owned_namehash);
+        host.state_mut().tokenized_subdomains.insert(sold_namehash);
+
+        let params = TransferDomainParams {
             domain: String::from("test.ccd"),
-            address: Address::Account(USER_1),
+            to: Receiver::Account(USER_2),
+            address: Address::Account(USER_2),
+            clear_data: false,
         };
         let bytes = to_bytes(&params);
+        let mut ctx = TestReceiveContext::empty();
         ctx.set_sender(Address::Account(USER_1))
             .set_parameter(&bytes);
+
+        host.setup_mock_entrypoint(
+            REGISTRY,
+            OwnedEntrypointName::new_unchecked(String::from("get")),
+            parse_and_map_mock::<StorageKeys, _, _>(move |params| {
+                let entries = ["a", "b", "c"]
+                    .iter()
+                    .map(|label| MaybeStorageEntry {
+                        key: Bytes(to_bytes(&String::from(*label))),
+                        value: Some(Bytes(Vec::new())),
+                    })
+                    .collect();
+                Some(
+                    (params.prefix == Bytes(subdomain_index_prefix(&domain_namehash))).then(|| {
+                        StorageGetEntryResult {
+                            prefix: params.prefix.clone(),
+                            entries,
+                            expired: false,
+                        }
+                    }),
+                )
+            }),
+        );
+        // Subdomain `c` was transferred to another holder
+        host.setup_mock_entrypoint(
+            CNS_NFT,
+            OwnedEntrypointName::new_unchecked(String::from("getTokenExpiry")),
+            parse_and_map_mock::<TokenParams, _, _>(move |params| {
+                let owner = if params.token_id == TokenIdVec(sold_namehash.into()) {
+                    holder
+                } else {
+                    USER_1
+                };
+                Some(TokenSubscriptionStatus {
+                    owner: Address::Account(owner),
+                    expiry: SubscriptionExpiryStatus::Owned(
+                        test_slot_time()
+                            .checked_add(Duration::from_millis(YEAR_MILLIS))
+                            .unwrap(),
+                    ),
+                })
+            }),
+        );
+        host.setup_mock_entrypoint(
+            CNS_NFT,
+            OwnedEntrypointName::new_unchecked(String::from("transfer")),
+            parse_and_check_mock::<TransferParams<TokenIdVec>, _>(
+                move |params| {
+                    params.0.len() == 1
+                        && (params.0[0].token_id == TokenIdVec(domain_namehash.into())
+                            || params.0[0].token_id == TokenIdVec(owned_namehash.into()))
+                        && params.0[0].from == Address::Account(USER_1)
+                        && params.0[0].to.address() == Address::Account(USER_2)
+                },
+                (),
+            ),
+        );
+        host.setup_mock_entrypoint(
+            REGISTRY,
+            OwnedEntrypointName::new_unchecked(String::from("update")),
+            parse_and_ok_mock::<StorageEntries, _>(()),
+        );
+
+        // Subdomain token of the previous owner goes along with the domain
+        let result = transfer_domain(&ctx, &mut host, &mut logger);
+        claim_eq!(result, Ok(()));
+
+        // Subdomains of the previous owner are deleted together with the domain records
+        let params = TransferDomainParams {
+            clear_data: true,
+            ..params
+        };
+        let bytes = to_bytes(&params);
+        ctx.set_parameter(&bytes);
+        host.setup_mock_entrypoint(
+            REGISTRY,
+            OwnedEntrypointName::new_unchecked(String::from("remove")),
+            parse_and_check_mock::<StorageKeys, _>(
+                move |params| {
+                    let records_cleared = [domain_namehash, plain_namehash, owned_namehash]
+                        .iter()
+                        .any(|namehash| params.prefix == Bytes(namehash.to_vec()))
+                        && params.keys == StorageKeySelection::All;
+                    let index_updated = params.prefix
+                        == Bytes(subdomain_index_prefix(&domain_namehash))
+                        && ["a", "b"].iter().any(|label| {
+                            params.keys
+                                == StorageKeySelection::Some(vec![Bytes(to_bytes(&String::from(
+                                    *label,
+                                )))])
+                        });
+                    records_cleared || index_updated
+                },
+                (),
+            ),
+        );
+        host.setup_mock_entrypoint(
+            REGISTRY,
+            OwnedEntrypointName::new_unchecked(String::from("insert")),
+            parse_and_ok_mock::<StorageEntries, _>(()),
+        );
+        host.setup_mock_entrypoint(
+            CNS_NFT,
+            OwnedEntrypointName::new_unchecked(String::from("burn")),
+            parse_and_check_mock::<TokenIdVec, _>(
+                move |token_id| *token_id == TokenIdVec(owned_namehash.into()),
+                (),
+            ),
+        );
+
+        let result = transfer_domain(&ctx, &mut host, &mut logger);
+        claim_eq!(result, Ok(()));
+        claim!(!host.state().tokenized_subdomains.contains(&owned_namehash));
+        claim!(host.state().tokenized_subdomains.contains(&sold_namehash));
+    }
+
+    #[concordium_test]
+    fn test_voucher() {
+        let mut host = default_host();
+        let mut logger = TestLogger::init();
+        let cns = ContractAddress {
+            index: 4,
+            subindex: 0,
+        };
+        let code = [9; 32];
+        let code_hash = voucher_code_hash(&code);
+
+        let params = RegisterVoucherParams {
+            domain: String::from("Gift.ccd"),
+            duration_years: 1,
+            code_hash,
+        };
+        let bytes = to_bytes(&params);
+        let mut ctx = TestReceiveContext::empty();
+        ctx.set_sender(Address::Account(USER_1))
+            .set_invoker(USER_1)
+            .set_self_address(cns)
+            .set_parameter(&bytes)
+            .set_metadata_slot_time(test_slot_time());
+        host.setup_mock_entrypoint(
+            PRICE_ORACLE,
+            OwnedEntrypointName::new_unchecked(String::from("getYearlyDomainPrice")),
+            parse_and_map_mock(test_price_oracle),
+        );
+        host.setup_mock_entrypoint(
+            CNS_NFT,
+            OwnedEntrypointName::new_unchecked(String::from("getTokenExpiry")),
+            parse_and_ok_mock::<TokenParams, _>(None::<TokenSubscriptionStatus>),
+        );
+        // Token is held by the CNS contract until claimed
+        host.setup_mock_entrypoint(
+            CNS_NFT,
+            OwnedEntrypointName::new_unchecked(String::from("mint")),
+            parse_and_check_mock::<CnsMintParams, _>(
+                |params| params.owner == Address::Contract(cns),
+                (),
+            ),
+        );
+        host.setup_mock_entrypoint(
+            REGISTRY,
+            OwnedEntrypointName::new_unchecked(String::from("remove")),
+            parse_and_ok_mock::<StorageEntries, _>(()),
+        );
+        host.setup_mock_entrypoint(
+            REGISTRY,
+            OwnedEntrypointName::new_unchecked(String::from("insert")),
+            parse_and_ok_mock::<StorageEntries, _>(()),
+        );
+        host.set_self_balance(TEST_YEARLY_DOMAIN_PRICE);
+        let result = register_voucher(&ctx, &mut host, TEST_YEARLY_DOMAIN_PRICE, &mut logger);
+        claim_eq!(result, Ok(()));
+        claim!(host.transfer_occurred(&ADMIN, TEST_YEARLY_DOMAIN_PRICE));
+
+        // Same code can't be used for another voucher
+        let result = register_voucher(&ctx, &mut host, TEST_YEARLY_DOMAIN_PRICE, &mut logger);
+        claim_eq!(result, Err(CustomContractError::AlreadyExists.into()));
+
+        // Voucher domain is normalized
+        let bytes = to_bytes(&code_hash);
+        ctx.set_parameter(&bytes);
+        let result = get_voucher(&ctx, &host);
+        claim_eq!(result, Ok(Some(String::from("gift.ccd"))));
+
         host.setup_mock_entrypoint(
             CNS_NFT,
             OwnedEntrypointName::new_unchecked(String::from("getTokenExpiry")),
             parse_and_ok_mock::<TokenParams, _>(Some(TokenSubscriptionStatus {
-                owner: Address::Account(USER_1),
+                owner: Address::Contract(cns),
                 expiry: SubscriptionExpiryStatus::Owned(
                     test_slot_time()
                         .checked_add(Duration::from_millis(YEAR_MILLIS))
@@ -1097,13 +3905,49 @@ mod tests {
                 ),
             })),
         );
+        host.setup_mock_entrypoint(
+            CNS_NFT,
+            OwnedEntrypointName::new_unchecked(String::from("transfer")),
+            parse_and_check_mock::<TransferParams<TokenIdVec>, _>(
+                |params| {
+                    params.0.len() == 1
+                        && params.0[0].from == Address::Contract(cns)
+                        && params.0[0].to.address() == Address::Account(USER_2)
+                },
+                (),
+            ),
+        );
         host.setup_mock_entrypoint(
             REGISTRY,
             OwnedEntrypointName::new_unchecked(String::from("update")),
             parse_and_ok_mock::<StorageEntries, _>(()),
         );
-        let result = set_address(&ctx, &mut host);
+
+        // Wrong code doesn't match any voucher
+        let bytes = to_bytes(&[8u8; 32]);
+        let mut ctx = TestReceiveContext::empty();
+        ctx.set_sender(Address::Account(USER_2))
+            .set_self_address(cns)
+            .set_parameter(&bytes);
+        let result = claim_voucher(&ctx, &mut host, &mut logger);
+        claim_eq!(result, Err(CustomContractError::NotFound.into()));
+
+        let mut logger = TestLogger::init();
+        let bytes = to_bytes(&code);
+        ctx.set_parameter(&bytes);
+        let result = claim_voucher(&ctx, &mut host, &mut logger);
         claim_eq!(result, Ok(()));
+        claim_eq!(
+            logger.logs,
+            vec![to_bytes(&CnsEvent::VoucherClaimed {
+                domain: String::from("gift.ccd"),
+                owner: Address::Account(USER_2),
+            })]
+        );
+
+        // Voucher can only be claimed once
+        let result = claim_voucher(&ctx, &mut host, &mut logger);
+        claim_eq!(result, Err(CustomContractError::NotFound.into()));
     }
 
     #[concordium_test]
@@ -1145,6 +3989,7 @@ mod tests {
                     key: Bytes::from([]),
                     value: Some(Bytes(to_bytes(&Address::Account(USER_1)))),
                 }],
+                expired: false,
             })),
         );
         let result = resolve(&ctx, &mut host);
@@ -1196,9 +4041,42 @@ mod tests {
         claim_eq!(result, Err(CustomContractError::NotFound.into()));
     }
 
+    #[concordium_test]
+    fn test_get_expiries() {
+        let mut host = default_host();
+
+        let mut ctx = TestReceiveContext::empty();
+        let params = GetExpiriesParams {
+            domains: vec![String::from("test.ccd"), String::from("missing.ccd")],
+        };
+        let bytes = to_bytes(&params);
+        ctx.set_parameter(&bytes);
+
+        let expiry = TokenExpiry {
+            expiry: test_slot_time()
+                .checked_add(Duration::from_days(50))
+                .unwrap(),
+            grace_end: test_slot_time()
+                .checked_add(Duration::from_days(80))
+                .unwrap(),
+        };
+        host.setup_mock_entrypoint(
+            CNS_NFT,
+            OwnedEntrypointName::new_unchecked(String::from("getTokenExpiries")),
+            parse_and_check_mock::<TokenListParams, _>(
+                |params| params.token_ids.len() == 2,
+                vec![Some(expiry), None],
+            ),
+        );
+
+        let result = get_expiries(&ctx, &host);
+        claim_eq!(result, Ok(vec![Some(expiry), None]));
+    }
+
     #[concordium_test]
     fn test_set_data() {
         let mut host = default_host();
+        let mut logger = TestLogger::init();
 
         let mut ctx = TestReceiveContext::empty();
         let params = SetDataParams {
@@ -1226,13 +4104,34 @@ mod tests {
             OwnedEntrypointName::new_unchecked(String::from("update")),
             parse_and_ok_mock::<StorageEntries, _>(()),
         );
-        let result = set_data(&ctx, &mut host);
+        let result = set_data(&ctx, &mut host, &mut logger);
         claim_eq!(result, Ok(()));
+        // Value is logged as a hash, so large values can't exceed the event size limit
+        claim_eq!(
+            logger.logs,
+            vec![to_bytes(&CnsEvent::DataChanged {
+                domain: String::from("test.ccd"),
+                key: String::from("Twitter"),
+                value_hash: Some(record_hash(&params.value)),
+            })]
+        );
+
+        // Keys longer than `MAX_DATA_KEY_LENGTH` are rejected
+        let params = SetDataParams {
+            domain: String::from("test.ccd"),
+            key: "k".repeat(MAX_DATA_KEY_LENGTH + 1),
+            value: DataValue::Url(String::from("https://twitter.com/cns-test")),
+        };
+        let bytes = to_bytes(&params);
+        ctx.set_parameter(&bytes);
+        let result = set_data(&ctx, &mut host, &mut logger);
+        claim_eq!(result, Err(CustomContractError::ParseParams.into()));
     }
 
     #[concordium_test]
     fn test_unset_data() {
         let mut host = default_host();
+        let mut logger = TestLogger::init();
 
         let mut ctx = TestReceiveContext::empty();
         let params = SetDataParams {
@@ -1260,8 +4159,17 @@ mod tests {
             OwnedEntrypointName::new_unchecked(String::from("remove")),
             parse_and_ok_mock::<StorageKeys, _>(()),
         );
-        let result = set_data(&ctx, &mut host);
+        let result = set_data(&ctx, &mut host, &mut logger);
         claim_eq!(result, Ok(()));
+        // Removed records are logged without a value hash
+        claim_eq!(
+            logger.logs,
+            vec![to_bytes(&CnsEvent::DataChanged {
+                domain: String::from("test.ccd"),
+                key: String::from("Twitter"),
+                value_hash: None,
+            })]
+        );
     }
 
     #[concordium_test]
@@ -1306,6 +4214,7 @@ mod tests {
                         "https://twitter.com/cns-test",
                     ))))),
                 }],
+                expired: false,
             })),
         );
         let result = get_data(&ctx, &mut host);
@@ -1358,6 +4267,7 @@ mod tests {
                     key: Bytes(to_bytes(&params.key)),
                     value: None,
                 }],
+                expired: false,
             })),
         );
         let result = get_data(&ctx, &mut host);
@@ -1391,20 +4301,110 @@ mod tests {
         // Get registry data
         host.setup_mock_entrypoint(
             REGISTRY,
-            OwnedEntrypointName::new_unchecked(String::from("get")),
-            parse_and_ok_mock::<StorageKeys, _>(None::<StorageGetEntryResult>),
+            OwnedEntrypointName::new_unchecked(String::from("get")),
+            parse_and_ok_mock::<StorageKeys, _>(None::<StorageGetEntryResult>),
+        );
+        let result = get_data(&ctx, &mut host);
+        claim_eq!(result, Err(CustomContractError::NotFound.into()));
+    }
+
+    #[concordium_test]
+    fn test_create_subdomain() {
+        let mut host = default_host();
+        let mut logger = TestLogger::init();
+
+        let mut ctx = TestReceiveContext::empty();
+        let params = SubdomainParams {
+            subdomain: String::from("tést.test.ccd"),
+        };
+        let bytes = to_bytes(&params);
+        ctx.set_parameter(&bytes)
+            .set_metadata_slot_time(test_slot_time())
+            .set_sender(Address::Account(USER_1))
+            .set_invoker(USER_1);
+
+        // Get ownership info
+        host.setup_mock_entrypoint(
+            CNS_NFT,
+            OwnedEntrypointName::new_unchecked(String::from("getTokenExpiry")),
+            parse_and_ok_mock::<TokenParams, _>(Some(TokenSubscriptionStatus {
+                owner: Address::Account(USER_1),
+                expiry: SubscriptionExpiryStatus::Owned(
+                    test_slot_time()
+                        .checked_add(Duration::from_days(50))
+                        .unwrap(),
+                ),
+            })),
+        );
+        // Get pricing info, subdomains are priced by their length in characters
+        host.setup_mock_entrypoint(
+            PRICE_ORACLE,
+            OwnedEntrypointName::new_unchecked(String::from("getYearlyDomainPrice")),
+            parse_and_map_mock(|params: &GetDomainPriceParams| {
+                if params.length == 13 {
+                    test_price_oracle(params)
+                } else {
+                    None
+                }
+            }),
+        );
+
+        // Get registry data
+        host.setup_mock_entrypoint(
+            REGISTRY,
+            OwnedEntrypointName::new_unchecked(String::from("insert")),
+            parse_and_ok_mock::<StorageEntries, _>(()),
         );
-        let result = get_data(&ctx, &mut host);
-        claim_eq!(result, Err(CustomContractError::NotFound.into()));
+        // Add subdomain to the index of the domain
+        host.setup_mock_entrypoint(
+            REGISTRY,
+            OwnedEntrypointName::new_unchecked(String::from("upsert")),
+            parse_and_check_mock::<StorageEntries, _>(
+                |params| {
+                    params.prefix
+                        == Bytes(subdomain_index_prefix(
+                            &TokenizedDomain::try_from("test.ccd")
+                                .expect_report("Invalid domain")
+                                .namehash(),
+                        ))
+                        && params.entries.len() == 1
+                        && params.entries[0].key == Bytes(to_bytes(&String::from("tést")))
+                },
+                (),
+            ),
+        );
+
+        // Attached amount must cover the price
+        let invoke_amount = TEST_YEARLY_SUBDOMAIN_PRICE - Amount::from_micro_ccd(1);
+        host.set_self_balance(invoke_amount);
+        let result = create_subdomain(&ctx, &mut host, invoke_amount, &mut logger);
+        claim_eq!(result, Err(CustomContractError::InsufficientFunds.into()));
+
+        let invoke_amount = TEST_YEARLY_SUBDOMAIN_PRICE + Amount::from_ccd(1);
+        host.set_self_balance(invoke_amount);
+
+        let result = create_subdomain(&ctx, &mut host, invoke_amount, &mut logger);
+
+        claim_eq!(result, Ok(()));
+        // Transfer subscription cost
+        claim!(host.transfer_occurred(&ADMIN, TEST_YEARLY_SUBDOMAIN_PRICE));
+        // Return extra
+        claim!(host.transfer_occurred(&USER_1, Amount::from_ccd(1)));
     }
 
     #[concordium_test]
-    fn test_create_subdomain() {
+    fn test_create_subdomain_as_token() {
         let mut host = default_host();
+        let mut logger = TestLogger::init();
 
+        let subdomain = "test.test.ccd";
+        let subdomain_namehash = TokenizedDomain::try_from(subdomain)
+            .expect_report("Invalid domain")
+            .namehash();
         let mut ctx = TestReceiveContext::empty();
-        let params = SubdomainParams {
-            subdomain: String::from("test.test.ccd"),
+        let params = CreateSubdomainParams {
+            subdomain: subdomain.into(),
+            mint_as_token: true,
         };
         let bytes = to_bytes(&params);
         ctx.set_parameter(&bytes)
@@ -1425,35 +4425,91 @@ mod tests {
                 ),
             })),
         );
-        // Get pricing info
         host.setup_mock_entrypoint(
             PRICE_ORACLE,
             OwnedEntrypointName::new_unchecked(String::from("getYearlyDomainPrice")),
             parse_and_map_mock(test_price_oracle),
         );
-
-        // Get registry data
         host.setup_mock_entrypoint(
             REGISTRY,
             OwnedEntrypointName::new_unchecked(String::from("insert")),
             parse_and_ok_mock::<StorageEntries, _>(()),
         );
+        host.setup_mock_entrypoint(
+            REGISTRY,
+            OwnedEntrypointName::new_unchecked(String::from("upsert")),
+            parse_and_ok_mock::<StorageEntries, _>(()),
+        );
+        // Mint subdomain token expiring together with the domain
+        host.setup_mock_entrypoint(
+            CNS_NFT,
+            OwnedEntrypointName::new_unchecked(String::from("mint")),
+            parse_and_check_mock::<CnsMintParams, _>(
+                move |params| {
+                    params.token_id == TokenIdVec(subdomain_namehash.into())
+                        && params.owner == Address::Account(USER_1)
+                        && params.duration == Duration::from_days(50)
+                },
+                (),
+            ),
+        );
 
-        let invoke_amount = TEST_YEARLY_SUBDOMAIN_PRICE + Amount::from_ccd(1);
-        host.set_self_balance(invoke_amount);
-
-        let result = create_subdomain(&ctx, &mut host, invoke_amount);
+        host.set_self_balance(TEST_YEARLY_SUBDOMAIN_PRICE);
+        let result = create_subdomain(&ctx, &mut host, TEST_YEARLY_SUBDOMAIN_PRICE, &mut logger);
+        claim_eq!(result, Ok(()));
+        claim!(host
+            .state()
+            .tokenized_subdomains
+            .contains(&subdomain_namehash));
+        claim!(logger
+            .logs
+            .contains(&to_bytes(&CnsEvent::SubdomainTokenized {
+                subdomain: subdomain.into(),
+                owner: Address::Account(USER_1),
+            })));
 
+        // Deleting the subdomain burns its token
+        host.setup_mock_entrypoint(
+            REGISTRY,
+            OwnedEntrypointName::new_unchecked(String::from("remove")),
+            parse_and_ok_mock::<StorageKeys, _>(()),
+        );
+        host.setup_mock_entrypoint(
+            CNS_NFT,
+            OwnedEntrypointName::new_unchecked(String::from("getTokenExpiries")),
+            parse_and_ok_mock::<TokenListParams, _>(vec![Some(TokenExpiry {
+                expiry: test_slot_time()
+                    .checked_add(Duration::from_days(50))
+                    .unwrap(),
+                grace_end: test_slot_time()
+                    .checked_add(Duration::from_days(80))
+                    .unwrap(),
+            })]),
+        );
+        host.setup_mock_entrypoint(
+            CNS_NFT,
+            OwnedEntrypointName::new_unchecked(String::from("burn")),
+            parse_and_check_mock::<TokenIdVec, _>(
+                move |token_id| *token_id == TokenIdVec(subdomain_namehash.into()),
+                (),
+            ),
+        );
+        let bytes = to_bytes(&SubdomainParams {
+            subdomain: subdomain.into(),
+        });
+        ctx.set_parameter(&bytes);
+        let result = delete_subdomain(&ctx, &mut host, &mut logger);
         claim_eq!(result, Ok(()));
-        // Transfer subscription cost
-        claim!(host.transfer_occurred(&ADMIN, TEST_YEARLY_SUBDOMAIN_PRICE));
-        // Return extra
-        claim!(host.transfer_occurred(&USER_1, Amount::from_ccd(1)));
+        claim!(!host
+            .state()
+            .tokenized_subdomains
+            .contains(&subdomain_namehash));
     }
 
     #[concordium_test]
     fn test_delete_subdomain() {
         let mut host = default_host();
+        let mut logger = TestLogger::init();
 
         let mut ctx = TestReceiveContext::empty();
         let subdomain = "test.test.ccd";
@@ -1485,7 +4541,7 @@ mod tests {
             parse_and_check_mock::<StorageKeys, _>(
                 move |params| {
                     // Ensure subdomain prefix
-                    params.prefix
+                    let subdomain_cleared = params.prefix
                         == Bytes(
                             TokenizedDomain::try_from(subdomain)
                                 .expect_report("Invalid domain")
@@ -1493,14 +4549,462 @@ mod tests {
                                 .into(),
                         )
                         // Ensure all keys are cleared
-                        && params.keys == StorageKeySelection::All
+                        && params.keys == StorageKeySelection::All;
+                    // Ensure subdomain is removed from the index of the domain
+                    let index_updated = params.prefix
+                        == Bytes(subdomain_index_prefix(
+                            &TokenizedDomain::try_from("test.ccd")
+                                .expect_report("Invalid domain")
+                                .namehash(),
+                        ))
+                        && params.keys
+                            == StorageKeySelection::Some(vec![Bytes(to_bytes(&String::from(
+                                "test",
+                            )))]);
+                    subdomain_cleared || index_updated
+                },
+                (),
+            ),
+        );
+
+        let result = delete_subdomain(&ctx, &mut host, &mut logger);
+
+        claim_eq!(result, Ok(()));
+    }
+
+    #[concordium_test]
+    fn test_tokenize_subdomain() {
+        let mut host = default_host();
+        let mut logger = TestLogger::init();
+
+        let mut ctx = TestReceiveContext::empty();
+        let subdomain = "test.test.ccd";
+        let subdomain_namehash = TokenizedDomain::try_from(subdomain)
+            .expect_report("Invalid domain")
+            .namehash();
+        let params = SubdomainParams {
+            subdomain: subdomain.into(),
+        };
+        let bytes = to_bytes(&params);
+        ctx.set_parameter(&bytes)
+            .set_metadata_slot_time(test_slot_time())
+            .set_sender(Address::Account(USER_1));
+
+        // Get ownership info
+        host.setup_mock_entrypoint(
+            CNS_NFT,
+            OwnedEntrypointName::new_unchecked(String::from("getTokenExpiry")),
+            parse_and_ok_mock::<TokenParams, _>(Some(TokenSubscriptionStatus {
+                owner: Address::Account(USER_1),
+                expiry: SubscriptionExpiryStatus::Owned(
+                    test_slot_time()
+                        .checked_add(Duration::from_days(50))
+                        .unwrap(),
+                ),
+            })),
+        );
+        // Subdomain exists
+        host.setup_mock_entrypoint(
+            REGISTRY,
+            OwnedEntrypointName::new_unchecked(String::from("get")),
+            parse_and_ok_mock::<StorageKeys, _>(Some(StorageGetEntryResult {
+                prefix: Bytes(subdomain_namehash.into()),
+                entries: Vec::new(),
+                expired: false,
+            })),
+        );
+        // Mint subdomain token to the domain owner
+        host.setup_mock_entrypoint(
+            CNS_NFT,
+            OwnedEntrypointName::new_unchecked(String::from("mint")),
+            parse_and_check_mock::<CnsMintParams, _>(
+                move |params| {
+                    params.token_id == TokenIdVec(subdomain_namehash.into())
+                        && params.owner == Address::Account(USER_1)
+                        && params.duration == Duration::from_days(50)
+                },
+                (),
+            ),
+        );
+
+        // Only the domain owner can tokenize subdomains
+        ctx.set_sender(Address::Account(ADMIN));
+        let result = tokenize_subdomain(&ctx, &mut host, &mut logger);
+        claim_eq!(result, Err(CustomContractError::Unauthorized.into()));
+
+        ctx.set_sender(Address::Account(USER_1));
+        let result = tokenize_subdomain(&ctx, &mut host, &mut logger);
+        claim_eq!(result, Ok(()));
+        claim!(host
+            .state()
+            .tokenized_subdomains
+            .contains(&subdomain_namehash));
+        claim!(logger
+            .logs
+            .contains(&to_bytes(&CnsEvent::SubdomainTokenized {
+                subdomain: subdomain.into(),
+                owner: Address::Account(USER_1),
+            })));
+    }
+
+    /// Test a subdomain token left from a previous registration of the domain controls no records and is replaced when
+    /// the new domain owner tokenizes the subdomain.
+    #[concordium_test]
+    fn test_stale_subdomain_token() {
+        let mut host = default_host();
+        let mut logger = TestLogger::init();
+
+        let subdomain = "test.test.ccd";
+        let domain = TokenizedDomain::try_from(subdomain).expect_report("Invalid domain");
+        let (domain_namehash, subdomain_namehash) = domain.domain_subdomain_namehashes();
+        host.state_mut()
+            .tokenized_subdomains
+            .insert(subdomain_namehash);
+
+        // Domain was re-registered by another account after the subdomain token of the previous owner expired
+        host.setup_mock_entrypoint(
+            CNS_NFT,
+            OwnedEntrypointName::new_unchecked(String::from("getTokenExpiry")),
+            parse_and_map_mock::<TokenParams, _, _>(move |params| {
+                Some(if params.token_id == TokenIdVec(domain_namehash.into()) {
+                    TokenSubscriptionStatus {
+                        owner: Address::Account(USER_2),
+                        expiry: SubscriptionExpiryStatus::Owned(
+                            test_slot_time()
+                                .checked_add(Duration::from_days(50))
+                                .unwrap(),
+                        ),
+                    }
+                } else {
+                    TokenSubscriptionStatus {
+                        owner: Address::Account(USER_1),
+                        expiry: SubscriptionExpiryStatus::Expired,
+                    }
+                })
+            }),
+        );
+        claim_eq!(
+            ensure_record_owner(&host, &domain, Address::Account(USER_1)),
+            Err(CustomContractError::Unauthorized.into())
+        );
+        claim_eq!(
+            ensure_record_owner(&host, &domain, Address::Account(USER_2)),
+            Ok(())
+        );
+
+        // Previous owner can't extend the stale token, and nothing is changed before the owner check
+        let bytes = to_bytes(&SubdomainParams {
+            subdomain: subdomain.into(),
+        });
+        let mut ctx = TestReceiveContext::empty();
+        ctx.set_parameter(&bytes)
+            .set_metadata_slot_time(test_slot_time())
+            .set_sender(Address::Account(USER_1));
+        let result = tokenize_subdomain(&ctx, &mut host, &mut logger);
+        claim_eq!(result, Err(CustomContractError::Unauthorized.into()));
+
+        // New owner replaces the stale token
+        host.setup_mock_entrypoint(
+            CNS_NFT,
+            OwnedEntrypointName::new_unchecked(String::from("burn")),
+            parse_and_check_mock::<TokenIdVec, _>(
+                move |token_id| *token_id == TokenIdVec(subdomain_namehash.into()),
+                (),
+            ),
+        );
+        host.setup_mock_entrypoint(
+            REGISTRY,
+            OwnedEntrypointName::new_unchecked(String::from("get")),
+            parse_and_ok_mock::<StorageKeys, _>(Some(StorageGetEntryResult {
+                prefix: Bytes(subdomain_namehash.into()),
+                entries: Vec::new(),
+                expired: false,
+            })),
+        );
+        host.setup_mock_entrypoint(
+            CNS_NFT,
+            OwnedEntrypointName::new_unchecked(String::from("mint")),
+            parse_and_check_mock::<CnsMintParams, _>(
+                move |params| {
+                    params.token_id == TokenIdVec(subdomain_namehash.into())
+                        && params.owner == Address::Account(USER_2)
                 },
                 (),
             ),
         );
+        ctx.set_sender(Address::Account(USER_2));
+        let result = tokenize_subdomain(&ctx, &mut host, &mut logger);
+        claim_eq!(result, Ok(()));
+        claim_eq!(
+            logger.logs,
+            vec![to_bytes(&CnsEvent::SubdomainTokenized {
+                subdomain: subdomain.into(),
+                owner: Address::Account(USER_2),
+            })]
+        );
+    }
+
+    #[concordium_test]
+    fn test_list_subdomains() {
+        let mut host = default_host();
+        let namehash = TokenizedDomain::try_from("test.ccd")
+            .expect_report("Invalid domain")
+            .namehash();
+
+        let mut ctx = TestReceiveContext::empty();
+        let params = ListSubdomainsParams {
+            namehash,
+            skip: 1,
+            show: 2,
+        };
+        let bytes = to_bytes(&params);
+        ctx.set_parameter(&bytes);
+
+        host.setup_mock_entrypoint(
+            REGISTRY,
+            OwnedEntrypointName::new_unchecked(String::from("get")),
+            parse_and_map_mock::<StorageKeys, _, _>(move |params| {
+                let entries = ["a", "b", "c.b", "d"]
+                    .iter()
+                    .map(|label| MaybeStorageEntry {
+                        key: Bytes(to_bytes(&String::from(*label))),
+                        value: Some(Bytes(Vec::new())),
+                    })
+                    .collect();
+                Some(
+                    (params.prefix == Bytes(subdomain_index_prefix(&namehash))
+                        && params.keys == StorageKeySelection::All)
+                        .then(|| StorageGetEntryResult {
+                            prefix: params.prefix.clone(),
+                            entries,
+                            expired: false,
+                        }),
+                )
+            }),
+        );
+
+        let result = list_subdomains(&ctx, &host);
+        claim_eq!(result, Ok(vec![String::from("b"), String::from("c.b")]));
+
+        // Domain without subdomains
+        let params = ListSubdomainsParams {
+            namehash: [0; 32],
+            skip: 0,
+            show: 10,
+        };
+        let bytes = to_bytes(&params);
+        ctx.set_parameter(&bytes);
+        let result = list_subdomains(&ctx, &host);
+        claim_eq!(result, Ok(Vec::new()));
+    }
+
+    #[concordium_test]
+    fn test_sweep_fees() {
+        let mut host = default_host();
+        let fees = Amount::from_ccd(10);
+        host.state_mut().escrowed_fees = fees;
+        host.set_self_balance(fees);
+
+        let mut ctx = TestReceiveContext::empty();
+        ctx.set_sender(Address::Account(USER_1));
+        let result = sweep_fees(&ctx, &mut host);
+        claim_eq!(result, Err(CustomContractError::Unauthorized.into()));
+
+        ctx.set_sender(Address::Account(MAINTAINER));
+        let result = sweep_fees(&ctx, &mut host);
+        claim_eq!(result, Ok(()));
+        // Beneficiary is initialized to the admin account
+        claim!(host.transfer_occurred(&ADMIN, fees));
+        claim_eq!(host.state().escrowed_fees, Amount::zero());
+
+        // Nothing left to sweep
+        let result = sweep_fees(&ctx, &mut host);
+        claim_eq!(result, Err(CustomContractError::NotFound.into()));
+    }
+
+    #[concordium_test]
+    fn test_transfer_fee_escrow() {
+        let fee = Amount::from_ccd(10);
+
+        // Share of a missing beneficiary account is escrowed
+        let mut host = default_host();
+        host.set_self_balance(fee);
+        host.make_account_missing(ADMIN);
+        claim_eq!(transfer_fee(&mut host, fee), Ok(()));
+        claim_eq!(host.state().escrowed_fees, fee);
+
+        // Other transfer errors, e.g. the contract being short of funds, are not hidden in the escrow
+        let mut host = default_host();
+        host.set_self_balance(Amount::zero());
+        claim_eq!(
+            transfer_fee(&mut host, fee),
+            Err(TransferError::AmountTooLarge.into())
+        );
+        claim_eq!(host.state().escrowed_fees, Amount::zero());
+    }
+
+    #[concordium_test]
+    fn test_beneficiary_split() {
+        let mut host = default_host();
+
+        // Shares must add up to 100%
+        let mut ctx = TestReceiveContext::empty();
+        let bytes = to_bytes(&InternalValue::Beneficiaries(vec![
+            (USER_1, Percentage::from_percent(70)),
+            (USER_2, Percentage::from_percent(20)),
+        ]));
+        ctx.set_sender(Address::Account(MAINTAINER))
+            .set_parameter(&bytes);
+        let result = update_internal_value(&ctx, &mut host);
+        claim_eq!(result, Err(CustomContractError::InvalidFields.into()));
+
+        let mut ctx = TestReceiveContext::empty();
+        let bytes = to_bytes(&InternalValue::Beneficiaries(vec![
+            (USER_1, Percentage::from_percent(70)),
+            (USER_2, Percentage::from_percent(30)),
+        ]));
+        ctx.set_sender(Address::Account(MAINTAINER))
+            .set_parameter(&bytes);
+        let result = update_internal_value(&ctx, &mut host);
+        claim_eq!(result, Ok(()));
+
+        // Fees are split between beneficiaries
+        let fees = Amount::from_ccd(10);
+        host.state_mut().escrowed_fees = fees;
+        host.set_self_balance(fees);
+
+        let mut ctx = TestReceiveContext::empty();
+        ctx.set_sender(Address::Account(MAINTAINER));
+        let result = sweep_fees(&ctx, &mut host);
+        claim_eq!(result, Ok(()));
+        claim!(host.transfer_occurred(&USER_1, Amount::from_ccd(7)));
+        claim!(host.transfer_occurred(&USER_2, Amount::from_ccd(3)));
+    }
+
+    #[concordium_test]
+    fn test_event_round_trip() {
+        let events = vec![
+            CnsEvent::DomainTransferred {
+                domain: String::from("test.ccd"),
+                from: Address::Account(USER_1),
+                to: Address::Account(USER_2),
+            },
+            CnsEvent::DomainFrozen {
+                domain: String::from("test.ccd"),
+                reason_hash: [3; 32],
+                by: Address::Account(MAINTAINER),
+            },
+            CnsEvent::DomainUnfrozen {
+                domain: String::from("test.ccd"),
+                reason_hash: [4; 32],
+                by: Address::Account(MAINTAINER),
+            },
+            CnsEvent::AddressChanged {
+                domain: String::from("test.ccd"),
+                address: Address::Contract(REGISTRY),
+            },
+            CnsEvent::ChainAddressChanged {
+                domain: String::from("test.ccd"),
+                coin_type: 60,
+                address_hash: Some(record_hash(&Bytes(vec![0xAB; 20]))),
+            },
+            CnsEvent::ContentHashChanged {
+                domain: String::from("test.ccd"),
+                record_hash: None,
+            },
+            CnsEvent::DataChanged {
+                domain: String::from("test.ccd"),
+                key: String::from("url"),
+                value_hash: Some(record_hash(&DataValue::Url(String::from(
+                    "https://bictory.io",
+                )))),
+            },
+            CnsEvent::SubdomainCreated {
+                subdomain: String::from("sub.test.ccd"),
+            },
+            CnsEvent::SubdomainDeleted {
+                subdomain: String::from("sub.test.ccd"),
+            },
+            CnsEvent::Registered {
+                domain: String::from("test.ccd"),
+                owner: Address::Account(USER_1),
+                duration_years: 2,
+            },
+            CnsEvent::Extended {
+                domain: String::from("test.ccd"),
+                duration_years: 1,
+            },
+            CnsEvent::VoucherClaimed {
+                domain: String::from("test.ccd"),
+                owner: Address::Account(USER_2),
+            },
+        ];
+        for event in events {
+            claim_eq!(from_bytes::<CnsEvent>(&to_bytes(&event)), Ok(event));
+        }
+
+        // Tags outside of the CNS range are rejected
+        claim!(from_bytes::<CnsEvent>(&[u8::MAX]).is_err());
+    }
+
+    #[concordium_test]
+    fn test_golden_register_params() {
+        // Registration parameters are built by SDKs, so their encoding must not change
+        let params = RegisterParams {
+            domain: String::from("bictory.ccd"),
+            address: Address::Account(USER_1),
+            duration_years: 2,
+        };
+        let golden = [&[11, 0, 0, 0][..], b"bictory.ccd", &[0], &USER_1.0, &[2]].concat();
+        claim_eq!(to_bytes(&params), golden);
+        claim_eq!(
+            to_bytes(&from_bytes::<RegisterParams>(&golden).expect_report("Failed to decode")),
+            golden
+        );
+    }
 
-        let result = delete_subdomain(&ctx, &mut host);
+    #[concordium_test]
+    fn test_freeze_domain_with_capability() {
+        let mut host = default_host();
+        let mut logger = TestLogger::init();
+
+        let params = FreezeDomainParams {
+            domain: String::from("test.ccd"),
+            reason_hash: [3; 32],
+        };
+        let bytes = to_bytes(&params);
+        let mut ctx = TestReceiveContext::empty();
+        ctx.set_sender(Address::Account(USER_1))
+            .set_parameter(&bytes)
+            .set_metadata_slot_time(test_slot_time());
+        let result = freeze_domain(&ctx, &mut host, &mut logger);
+        claim_eq!(result, Err(CustomContractError::Unauthorized.into()));
+
+        let grant = to_bytes(&CapabilityUpdateParams {
+            kind: commons_v1::AuthorityUpdateKind::Add,
+            address: Address::Account(USER_1),
+            capability: Capability::CanFreeze,
+        });
+        let mut admin_ctx = TestReceiveContext::empty();
+        admin_ctx
+            .set_sender(Address::Account(ADMIN))
+            .set_parameter(&grant);
+        claim_eq!(update_capability(&admin_ctx, &mut host), Ok(()));
+
+        let address = to_bytes(&Address::Account(USER_1));
+        admin_ctx.set_parameter(&address);
+        claim_eq!(
+            view_capabilities(&admin_ctx, &host),
+            Ok(vec![Capability::CanFreeze])
+        );
 
+        let result = freeze_domain(&ctx, &mut host, &mut logger);
         claim_eq!(result, Ok(()));
+
+        // Capability doesn't allow other maintainer actions
+        let bytes = to_bytes(&InternalValue::SubscriptionYearLimit(5));
+        ctx.set_parameter(&bytes);
+        let result = update_internal_value(&ctx, &mut host);
+        claim_eq!(result, Err(CustomContractError::Unauthorized.into()));
     }
 }
@@ -0,0 +1,256 @@
+//! CNS event log. Every event starts with a single tag byte from the CNS tag range in `commons_v1` constants, followed
+//! by the event fields in declaration order. Layout of the events is versioned with `CNS_EVENT_SCHEMA_VERSION` and is
+//! documented in `docs/overview.md`, so logs can be decoded without the contract source.
+use commons_v1::{
+    ADDRESS_CHANGED_TAG, CHAIN_ADDRESS_CHANGED_TAG, CONTENT_HASH_CHANGED_TAG, DATA_CHANGED_TAG,
+    DOMAIN_EXTENDED_TAG, DOMAIN_FROZEN_TAG, DOMAIN_REGISTERED_TAG, DOMAIN_TRANSFERRED_TAG,
+    DOMAIN_UNFROZEN_TAG, SUBDOMAIN_CREATED_TAG, SUBDOMAIN_DELETED_TAG, SUBDOMAIN_TOKENIZED_TAG,
+    VOUCHER_CLAIMED_TAG,
+};
+use concordium_std::*;
+use sha3::{Digest, Keccak256};
+
+/// Tagged CNS event to be serialized for the event log. Can be deserialized back to decode the logs.
+#[derive(Debug, PartialEq, Eq)]
+pub enum CnsEvent {
+    /// Domain token was transferred together with its registry records
+    DomainTransferred {
+        domain: String,
+        from: Address,
+        to: Address,
+    },
+    /// Maintainer froze registry records of the domain
+    DomainFrozen {
+        domain: String,
+        reason_hash: [u8; 32],
+        by: Address,
+    },
+    /// Maintainer lifted the freeze of the domain
+    DomainUnfrozen {
+        domain: String,
+        reason_hash: [u8; 32],
+        by: Address,
+    },
+    /// Domain or subdomain resolves to a new address
+    AddressChanged {
+        domain: String,
+        address: Address,
+    },
+    /// Domain or subdomain resolves to a new address on another chain. `None` means the record was removed
+    ChainAddressChanged {
+        domain: String,
+        coin_type: u32,
+        address_hash: Option<[u8; 32]>,
+    },
+    /// Content hash record of the domain or subdomain was set. `None` means the record was removed
+    ContentHashChanged {
+        domain: String,
+        record_hash: Option<[u8; 32]>,
+    },
+    /// Data record of the domain or subdomain was set. `None` means the record was removed
+    DataChanged {
+        domain: String,
+        key: String,
+        value_hash: Option<[u8; 32]>,
+    },
+    SubdomainCreated {
+        subdomain: String,
+    },
+    SubdomainDeleted {
+        subdomain: String,
+    },
+    /// New domain token was minted to the owner
+    Registered {
+        domain: String,
+        owner: Address,
+        duration_years: u8,
+    },
+    /// Subscription of the domain was extended
+    Extended {
+        domain: String,
+        duration_years: u8,
+    },
+    /// Domain registered into a voucher was claimed by the new owner
+    VoucherClaimed {
+        domain: String,
+        owner: Address,
+    },
+    /// Subdomain token was minted to the owner of the domain
+    SubdomainTokenized {
+        subdomain: String,
+        owner: Address,
+    },
+}
+
+impl Serial for CnsEvent {
+    fn serial<W: Write>(&self, out: &mut W) -> Result<(), W::Err> {
+        match self {
+            CnsEvent::DomainTransferred { domain, from, to } => {
+                out.write_u8(DOMAIN_TRANSFERRED_TAG)?;
+                domain.serial(out)?;
+                from.serial(out)?;
+                to.serial(out)
+            }
+            CnsEvent::DomainFrozen {
+                domain,
+                reason_hash,
+                by,
+            } => {
+                out.write_u8(DOMAIN_FROZEN_TAG)?;
+                domain.serial(out)?;
+                reason_hash.serial(out)?;
+                by.serial(out)
+            }
+            CnsEvent::DomainUnfrozen {
+                domain,
+                reason_hash,
+                by,
+            } => {
+                out.write_u8(DOMAIN_UNFROZEN_TAG)?;
+                domain.serial(out)?;
+                reason_hash.serial(out)?;
+                by.serial(out)
+            }
+            CnsEvent::AddressChanged { domain, address } => {
+                out.write_u8(ADDRESS_CHANGED_TAG)?;
+                domain.serial(out)?;
+                address.serial(out)
+            }
+            CnsEvent::ChainAddressChanged {
+                domain,
+                coin_type,
+                address_hash,
+            } => {
+                out.write_u8(CHAIN_ADDRESS_CHANGED_TAG)?;
+                domain.serial(out)?;
+                coin_type.serial(out)?;
+                address_hash.serial(out)
+            }
+            CnsEvent::ContentHashChanged {
+                domain,
+                record_hash,
+            } => {
+                out.write_u8(CONTENT_HASH_CHANGED_TAG)?;
+                domain.serial(out)?;
+                record_hash.serial(out)
+            }
+            CnsEvent::DataChanged {
+                domain,
+                key,
+                value_hash,
+            } => {
+                out.write_u8(DATA_CHANGED_TAG)?;
+                domain.serial(out)?;
+                key.serial(out)?;
+                value_hash.serial(out)
+            }
+            CnsEvent::SubdomainCreated { subdomain } => {
+                out.write_u8(SUBDOMAIN_CREATED_TAG)?;
+                subdomain.serial(out)
+            }
+            CnsEvent::SubdomainDeleted { subdomain } => {
+                out.write_u8(SUBDOMAIN_DELETED_TAG)?;
+                subdomain.serial(out)
+            }
+            CnsEvent::Registered {
+                domain,
+                owner,
+                duration_years,
+            } => {
+                out.write_u8(DOMAIN_REGISTERED_TAG)?;
+                domain.serial(out)?;
+                owner.serial(out)?;
+                duration_years.serial(out)
+            }
+            CnsEvent::Extended {
+                domain,
+                duration_years,
+            } => {
+                out.write_u8(DOMAIN_EXTENDED_TAG)?;
+                domain.serial(out)?;
+                duration_years.serial(out)
+            }
+            CnsEvent::VoucherClaimed { domain, owner } => {
+                out.write_u8(VOUCHER_CLAIMED_TAG)?;
+                domain.serial(out)?;
+                owner.serial(out)
+            }
+            CnsEvent::SubdomainTokenized { subdomain, owner } => {
+                out.write_u8(SUBDOMAIN_TOKENIZED_TAG)?;
+                subdomain.serial(out)?;
+                owner.serial(out)
+            }
+        }
+    }
+}
+
+impl Deserial for CnsEvent {
+    fn deserial<R: Read>(source: &mut R) -> ParseResult<Self> {
+        let tag = source.read_u8()?;
+        match tag {
+            DOMAIN_TRANSFERRED_TAG => Ok(CnsEvent::DomainTransferred {
+                domain: source.get()?,
+                from: source.get()?,
+                to: source.get()?,
+            }),
+            DOMAIN_FROZEN_TAG => Ok(CnsEvent::DomainFrozen {
+                domain: source.get()?,
+                reason_hash: source.get()?,
+                by: source.get()?,
+            }),
+            DOMAIN_UNFROZEN_TAG => Ok(CnsEvent::DomainUnfrozen {
+                domain: source.get()?,
+                reason_hash: source.get()?,
+                by: source.get()?,
+            }),
+            ADDRESS_CHANGED_TAG => Ok(CnsEvent::AddressChanged {
+                domain: source.get()?,
+                address: source.get()?,
+            }),
+            CHAIN_ADDRESS_CHANGED_TAG => Ok(CnsEvent::ChainAddressChanged {
+                domain: source.get()?,
+                coin_type: source.get()?,
+                address_hash: source.get()?,
+            }),
+            CONTENT_HASH_CHANGED_TAG => Ok(CnsEvent::ContentHashChanged {
+                domain: source.get()?,
+                record_hash: source.get()?,
+            }),
+            DATA_CHANGED_TAG => Ok(CnsEvent::DataChanged {
+                domain: source.get()?,
+                key: source.get()?,
+                value_hash: source.get()?,
+            }),
+            SUBDOMAIN_CREATED_TAG => Ok(CnsEvent::SubdomainCreated {
+                subdomain: source.get()?,
+            }),
+            SUBDOMAIN_DELETED_TAG => Ok(CnsEvent::SubdomainDeleted {
+                subdomain: source.get()?,
+            }),
+            DOMAIN_REGISTERED_TAG => Ok(CnsEvent::Registered {
+                domain: source.get()?,
+                owner: source.get()?,
+                duration_years: source.get()?,
+            }),
+            DOMAIN_EXTENDED_TAG => Ok(CnsEvent::Extended {
+                domain: source.get()?,
+                duration_years: source.get()?,
+            }),
+            VOUCHER_CLAIMED_TAG => Ok(CnsEvent::VoucherClaimed {
+                domain: source.get()?,
+                owner: source.get()?,
+            }),
+            SUBDOMAIN_TOKENIZED_TAG => Ok(CnsEvent::SubdomainTokenized {
+                subdomain: source.get()?,
+                owner: source.get()?,
+            }),
+            _ => Err(ParseError::default()),
+        }
+    }
+}
+
+/// Keccak-256 of the serialized record value. Record values are logged as hashes, so that events stay within the
+/// 512 byte event limit regardless of the value size.
+pub fn record_hash<T: Serial>(value: &T) -> [u8; 32] {
+    Keccak256::digest(to_bytes(value)).into()
+}
@@ -1,5 +1,5 @@
-use commons_v1::Bytes;
-use concordium_cis1::TokenIdVec;
+use commons_v1::{Bytes, Percentage};
+use concordium_cis1::{Receiver, TokenIdVec};
 use concordium_std::*;
 
 #[derive(Serialize, SchemaType)]
@@ -17,6 +17,21 @@ pub struct RegisterParams {
     pub duration_years: u8,
 }
 
+#[derive(Debug, Serialize, SchemaType)]
+pub struct RevealParams {
+    pub registration: RegisterParams,
+    /// Secret used in the commitment
+    pub salt: [u8; 32],
+}
+
+#[derive(Debug, Serialize, SchemaType)]
+pub struct RegisterVoucherParams {
+    pub domain: String,
+    pub duration_years: u8,
+    /// `keccak256` hash of the secret code that claims the domain
+    pub code_hash: [u8; 32],
+}
+
 #[derive(Debug, Serialize, SchemaType)]
 pub struct ExtendParams {
     pub domain: String,
@@ -29,11 +44,90 @@ pub struct SetAddressParams {
     pub address: Address,
 }
 
+#[derive(Debug, Serialize, SchemaType)]
+pub struct SetChainAddressParams {
+    pub domain: String,
+    /// SLIP-44 coin type of the chain, e.g. 0 for Bitcoin or 60 for Ethereum
+    pub coin_type: u32,
+    /// Address in the native binary format of the chain. Empty address removes the record
+    pub address: Bytes,
+}
+
+#[derive(Debug, Serialize, SchemaType)]
+pub struct ResolveChainParams {
+    pub domain: String,
+    pub coin_type: u32,
+}
+
+/// Registry key of the address record on another chain.
+pub struct ChainAddressKey(pub u32);
+
+impl ChainAddressKey {
+    /// Data record keys are serialized strings starting with their length, so a five byte data key always starts
+    /// with 1 and can't collide with chain address keys.
+    const TAG: u8 = 0xFF;
+}
+
+impl Serial for ChainAddressKey {
+    fn serial<W: Write>(&self, out: &mut W) -> Result<(), W::Err> {
+        out.write_u8(Self::TAG)?;
+        out.write_u32(self.0)
+    }
+}
+
+/// Location of the website or content the domain points to, together with the data needed to verify it.
+#[derive(Debug, Clone, Serialize, SchemaType, PartialEq, Eq)]
+pub enum ContentHash {
+    /// IPFS content identifier in binary format
+    Ipfs(Bytes),
+    /// Arweave transaction ID
+    Arweave([u8; 32]),
+    /// Content served over HTTPS, verified with its SHA-256 hash
+    Https { url: String, sha256: [u8; 32] },
+}
+
+#[derive(Debug, Serialize, SchemaType)]
+pub struct SetContentHashParams {
+    pub domain: String,
+    /// `None` removes the record
+    pub content_hash: Option<ContentHash>,
+}
+
+/// Registry key of the content hash record.
+pub struct ContentHashKey;
+
+impl ContentHashKey {
+    /// Data record keys are serialized strings and chain address keys are five bytes long, so a single byte key can't
+    /// collide with either of them.
+    const TAG: u8 = 0xFE;
+}
+
+impl Serial for ContentHashKey {
+    fn serial<W: Write>(&self, out: &mut W) -> Result<(), W::Err> {
+        out.write_u8(Self::TAG)
+    }
+}
+
+#[derive(Debug, Serialize, SchemaType)]
+pub struct TransferDomainParams {
+    pub domain: String,
+    pub to: Receiver,
+    /// Address the domain resolves to after the transfer
+    pub address: Address,
+    /// Remove data records of the previous owner instead of keeping them
+    pub clear_data: bool,
+}
+
 #[derive(Debug, Serialize, SchemaType)]
 pub struct ResolveParams {
     pub domain: String,
 }
 
+#[derive(Debug, Serialize, SchemaType)]
+pub struct GetExpiriesParams {
+    pub domains: Vec<String>,
+}
+
 #[derive(Debug, Serialize, SchemaType, PartialEq, Eq)]
 pub enum DataValue {
     Empty,
@@ -62,18 +156,150 @@ pub struct SubdomainParams {
     pub subdomain: String,
 }
 
-#[derive(Debug, Clone, Copy, Serialize, PartialEq, Eq, SchemaType)]
+/// Parameter type for the `createSubdomain` function, `SubdomainParams` optionally followed by the `mint_as_token`
+/// flag. Subdomains are not tokenized if the flag is omitted, so callers encoding `SubdomainParams` keep working.
+#[derive(Debug, Serial, SchemaType)]
+pub struct CreateSubdomainParams {
+    pub subdomain: String,
+    /// Mint the subdomain as its own CNS NFT, same as `tokenizeSubdomain`
+    pub mint_as_token: bool,
+}
+
+impl Deserial for CreateSubdomainParams {
+    fn deserial<R: Read>(source: &mut R) -> ParseResult<Self> {
+        let subdomain = String::deserial(source)?;
+        let mint_as_token = match source.read_u8() {
+            Ok(1) => true,
+            Ok(0) | Err(_) => false,
+            Ok(_) => return Err(ParseError::default()),
+        };
+        Ok(Self {
+            subdomain,
+            mint_as_token,
+        })
+    }
+}
+
+#[derive(Debug, Serialize, SchemaType)]
+pub struct ListSubdomainsParams {
+    /// Namehash of the parent domain, equal to its CNS NFT token ID
+    pub namehash: [u8; 32],
+    pub skip: u32,
+    pub show: u32,
+}
+
+#[derive(Debug, Serialize, SchemaType)]
+pub struct FreezeDomainParams {
+    pub domain: String,
+    /// Hash of the document justifying the decision, e.g. a court order or an appeal ruling
+    pub reason_hash: [u8; 32],
+}
+
+#[derive(Debug, Serialize, SchemaType)]
+pub struct ReserveDomainsParams {
+    pub domains: Vec<String>,
+}
+
+#[derive(Debug, Serialize, SchemaType)]
+pub struct GetFreezeParams {
+    pub domain: String,
+}
+
+#[derive(Debug, Clone, Serialize, SchemaType, PartialEq, Eq)]
+pub struct DomainFreeze {
+    pub reason_hash: [u8; 32],
+    /// Maintainer that froze the domain
+    pub frozen_by: Address,
+    pub frozen_at: Timestamp,
+}
+
+#[derive(Debug, Serialize, SchemaType)]
+pub struct GetPremiumParams {
+    pub domain: String,
+}
+
+/// How the premium of an expired domain auction decreases over time.
+#[derive(Debug, Clone, Copy, Serialize, SchemaType, PartialEq, Eq)]
+pub enum PremiumDecay {
+    /// Premium decreases by the same amount every millisecond
+    Linear,
+    /// Premium halves every `half_life`, decreasing linearly within each half-life
+    Exponential { half_life: Duration },
+}
+
+/// Dutch auction of expired domains. Once the grace period of a domain ends, registering it costs a premium on top of
+/// the base price, starting at `start_price` and decaying to zero over `duration`. Disabled while the duration is zero.
+#[derive(Debug, Clone, Copy, Serialize, SchemaType, PartialEq, Eq)]
+pub struct ExpiredDomainAuction {
+    pub start_price: Amount,
+    pub duration: Duration,
+    pub decay: PremiumDecay,
+}
+
+impl ExpiredDomainAuction {
+    pub fn disabled() -> Self {
+        Self {
+            start_price: Amount::zero(),
+            duration: Duration::from_millis(0),
+            decay: PremiumDecay::Linear,
+        }
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.duration.millis() > 0 && self.start_price != Amount::zero()
+    }
+
+    /// Premium `elapsed` after the grace period of the domain ended.
+    pub fn premium(&self, elapsed: Duration) -> Amount {
+        let (elapsed, duration) = (elapsed.millis(), self.duration.millis());
+        if elapsed >= duration {
+            return Amount::zero();
+        }
+
+        let start = self.start_price.micro_ccd;
+        let premium = match self.decay {
+            PremiumDecay::Linear => {
+                (start as u128 * (duration - elapsed) as u128 / duration as u128) as u64
+            }
+            // Value left at the end of the auction is subtracted, so that the premium reaches zero without a jump
+            PremiumDecay::Exponential { half_life } => {
+                halve(start, elapsed, half_life.millis())
+                    - halve(start, duration, half_life.millis())
+            }
+        };
+        Amount::from_micro_ccd(premium)
+    }
+}
+
+/// Halve `amount` once for every full `half_life` in `elapsed`, interpolating linearly within the current half-life.
+fn halve(amount: u64, elapsed: u64, half_life: u64) -> u64 {
+    let halvings = elapsed / half_life;
+    if halvings >= 64 {
+        return 0;
+    }
+    let current = amount >> halvings;
+    let decrease =
+        (current - (current >> 1)) as u128 * (elapsed % half_life) as u128 / half_life as u128;
+    current - decrease as u64
+}
+
+#[derive(Debug, Clone, Serialize, PartialEq, Eq, SchemaType)]
 pub enum InternalValue {
     CnsNft(ContractAddress),
     Oracle(ContractAddress),
-    Beneficiary(AccountAddress),
+    Beneficiaries(Vec<(AccountAddress, Percentage)>),
     SubscriptionYearLimit(u8),
+    /// Minimum and maximum commitment age
+    CommitmentAge(Duration, Duration),
+    ExpiredDomainAuction(ExpiredDomainAuction),
 }
 
 #[derive(Debug, Clone, Copy, Serialize, PartialEq, Eq, SchemaType)]
 pub enum InternalViewParams {
     CnsNft,
     Oracle,
-    Beneficiary,
+    Beneficiaries,
     SubscriptionYearLimit,
+    CommitmentAge,
+    ExpiredDomainAuction,
 }
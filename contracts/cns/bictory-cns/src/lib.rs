@@ -1,7 +1,12 @@
 #![no_std]
 
 const YEAR_MILLIS: u64 = 1000 * 60 * 60 * (24 * 365 + 6);
+/// Maximum number of accounts fees can be split between.
+const MAX_BENEFICIARIES: usize = 8;
+/// Maximum length of a data record key in bytes.
+const MAX_DATA_KEY_LENGTH: usize = 128;
 
 pub mod contract;
+pub mod events;
 pub mod external;
 pub mod state;
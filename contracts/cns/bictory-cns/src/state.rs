@@ -1,6 +1,8 @@
-use commons_v1::Authority;
+use commons_v1::{Authority, Percentage};
 use concordium_std::*;
 
+use crate::external::{DomainFreeze, ExpiredDomainAuction};
+
 #[derive(Debug, Serial, DeserialWithState)]
 #[concordium(state_parameter = "S")]
 pub struct State<S: HasStateApi> {
@@ -12,10 +14,28 @@ pub struct State<S: HasStateApi> {
     pub nft: ContractAddress,
     /// BictoryCnsPriceOracle contract address. Keeps the updated CNS pricing info.
     pub price_oracle: ContractAddress,
-    /// account address. Will receive payments from new subscriptions.
-    pub beneficiary: AccountAddress,
+    /// Accounts receiving payments from new subscriptions, together with their share of each payment.
+    pub beneficiaries: Vec<(AccountAddress, Percentage)>,
     /// Maximum subscription year count from slot time.
     pub subscription_year_limit: u8,
+    /// Fees kept by the contract after failed transfers to the beneficiary.
+    pub escrowed_fees: Amount,
+    /// Registration commitments together with the time they were made.
+    pub commitments: StateMap<[u8; 32], Timestamp, S>,
+    /// Minimum time between commitment and reveal, so that a commitment can't be made after seeing a reveal.
+    pub min_commitment_age: Duration,
+    /// Maximum time between commitment and reveal. Commit-reveal registration is disabled while it's zero.
+    pub max_commitment_age: Duration,
+    /// Domains with registry records frozen by maintainers, by domain namehash.
+    pub frozen_domains: StateMap<[u8; 32], DomainFreeze, S>,
+    /// Domains that can only be registered by maintainers, e.g. trademarks or offensive names, by domain namehash.
+    pub reserved_domains: StateSet<[u8; 32], S>,
+    /// Domains registered into vouchers and held by this contract until claimed, by voucher code hash.
+    pub vouchers: StateMap<[u8; 32], String, S>,
+    /// Dutch auction expired domains go through before they are available at base price.
+    pub expired_auction: ExpiredDomainAuction,
+    /// Subdomains tokenized as their own CNS NFTs, by subdomain namehash. Token ID is the subdomain namehash.
+    pub tokenized_subdomains: StateSet<[u8; 32], S>,
 }
 
 impl<S: HasStateApi> State<S> {
@@ -32,8 +52,60 @@ impl<S: HasStateApi> State<S> {
             registry,
             nft,
             price_oracle,
-            beneficiary: origin,
+            beneficiaries: vec![(origin, Percentage::HUNDRED)],
             subscription_year_limit,
+            escrowed_fees: Amount::zero(),
+            commitments: state_builder.new_map(),
+            min_commitment_age: Duration::from_millis(0),
+            max_commitment_age: Duration::from_millis(0),
+            frozen_domains: state_builder.new_map(),
+            reserved_domains: state_builder.new_set(),
+            vouchers: state_builder.new_map(),
+            expired_auction: ExpiredDomainAuction::disabled(),
+            tokenized_subdomains: state_builder.new_set(),
+        }
+    }
+
+    pub fn commit_reveal_enabled(&self) -> bool {
+        self.max_commitment_age.millis() > 0
+    }
+
+    /// Commitment is old enough to be revealed.
+    pub fn commitment_matured(&self, committed_at: Timestamp, now: Timestamp) -> bool {
+        committed_at
+            .checked_add(self.min_commitment_age)
+            .map_or(false, |matured_at| now >= matured_at)
+    }
+
+    /// Commitment is too old to be revealed and can be replaced.
+    pub fn commitment_expired(&self, committed_at: Timestamp, now: Timestamp) -> bool {
+        committed_at
+            .checked_add(self.max_commitment_age)
+            .map_or(false, |expires_at| now > expires_at)
+    }
+
+    /// Current premium of a domain with the grace period ended at `grace_end`.
+    pub fn auction_premium(&self, grace_end: Timestamp, now: Timestamp) -> Amount {
+        let elapsed = now
+            .duration_since(grace_end)
+            .unwrap_or_else(|| Duration::from_millis(0));
+        self.expired_auction.premium(elapsed)
+    }
+
+    /// Split the fee between beneficiaries. The last beneficiary receives the rounding remainder, so that the whole
+    /// fee is always distributed.
+    pub fn fee_shares(&self, fee: Amount) -> Vec<(AccountAddress, Amount)> {
+        let mut remaining = fee;
+        let mut shares = Vec::with_capacity(self.beneficiaries.len());
+        for (i, (account, share)) in self.beneficiaries.iter().enumerate() {
+            let amount = if i + 1 == self.beneficiaries.len() {
+                remaining
+            } else {
+                *share * fee
+            };
+            remaining -= amount;
+            shares.push((*account, amount));
         }
+        shares
     }
 }
@@ -7,13 +7,12 @@ use crate::external::*;
 use crate::state::State;
 use crate::storage;
 
-/// The baseurl for the token metadata, gets appended with the token ID as hex
-/// encoding before emitted in the TokenMetadata event.
-const TOKEN_METADATA_BASE_URL: &str = "https://some.example/token/";
-
 /// list of standards supported by this contract.
-const SUPPORTED_STANDARDS: [StandardIdentifier<'static>; 2] =
-    [CIS0_STANDARD_IDENTIFIER, CIS2_STANDARD_IDENTIFIER];
+const SUPPORTED_STANDARDS: [StandardIdentifier<'static>; 3] = [
+    CIS0_STANDARD_IDENTIFIER,
+    CIS2_STANDARD_IDENTIFIER,
+    BICTORY_AUTHORITY_STANDARD_IDENTIFIER,
+];
 
 /// Initialize contract instance with no token types initially.
 #[init(contract = "BictoryCnsNft", parameter = "InitParams")]
@@ -60,6 +59,12 @@ fn mint<S: HasStateApi>(
         .checked_add(params.duration)
         .ok_or(CustomContractError::InvalidDuration)?;
 
+    // URL is logged in the TokenMetadata event, which has to fit the event size limit
+    let metadata_url = cns_token_metadata_url(&state.metadata_base_url, &params.domain);
+    ensure!(
+        metadata_url.url.len() <= MAX_METADATA_URL_LENGTH,
+        CustomContractError::InvalidFields.into()
+    );
     let token_storage = state.token_storage;
     storage::insert_token(
         host,
@@ -81,12 +86,11 @@ fn mint<S: HasStateApi>(
         owner: params.owner,
     }))?;
 
-    let url = build_token_metadata_url(&params.token_id);
     // Event for Metadata URL update.
     logger.log(&Cis2Event::TokenMetadata::<_, TokenAmountU64>(
         TokenMetadataEvent {
             token_id: params.token_id,
-            metadata_url: MetadataUrl { url, hash: None },
+            metadata_url,
         },
     ))?;
 
@@ -118,6 +122,31 @@ fn get_token_expiry<S: HasStateApi>(
     Ok(status)
 }
 
+/// Function to get expiry and grace period end of several domains in one call. Result is returned in the same order
+/// as requested token IDs, with `None` in place of tokens that don't exist.
+#[receive(
+    contract = "BictoryCnsNft",
+    name = "getTokenExpiries",
+    parameter = "TokenListParams",
+    return_value = "Vec<Option<TokenExpiry>>"
+)]
+fn get_token_expiries<S: HasStateApi>(
+    ctx: &impl HasReceiveContext,
+    host: &impl HasHost<State<S>, StateApiType = S>,
+) -> ContractResult<Vec<Option<TokenExpiry>>> {
+    let params = TokenListParams::deserial(&mut ctx.parameter_cursor())?;
+    let state = host.state();
+
+    let mut expiries = Vec::with_capacity(params.token_ids.len());
+    for token_id in params.token_ids.iter() {
+        let expiry = storage::get_token_subscription_data(host, &state.token_storage, token_id)?
+            .map(storage::SubscriptionData::into_expiry);
+        expiries.push(expiry);
+    }
+
+    Ok(expiries)
+}
+
 /// View token data owned by particular address by token_id.
 #[receive(
     contract = "BictoryCnsNft",
@@ -221,9 +250,10 @@ fn transfer<S: HasStateApi>(
     for transfer in transfers {
         let state = host.state();
 
-        // Authenticate the sender for this transfer
+        // Authenticate the sender for this transfer, either as an operator of the owner or of the token
         ensure!(
-            state.is_operator(&sender, &transfer.from),
+            state.is_operator(&transfer.from, &sender)
+                || state.is_token_operator(&transfer.token_id, &sender),
             ContractError::Unauthorized
         );
 
@@ -267,6 +297,7 @@ fn transfer<S: HasStateApi>(
             &transfer.token_id,
             transfer.to.address(),
         )?;
+        host.state_mut().clear_token_operators(&transfer.token_id);
 
         // Log transfer event
         logger.log(&Cis2Event::Transfer(TransferEvent {
@@ -319,7 +350,7 @@ fn update_operator<S: HasStateApi>(
 ) -> ContractResult<()> {
     let UpdateOperatorParams(params) = UpdateOperatorParams::deserial(&mut ctx.parameter_cursor())?;
     // Get the sender who called this contract function.
-    let sender = Address::Account(ctx.invoker());
+    let sender = ctx.sender();
     let (state, state_builder) = host.state_and_builder();
 
     for param in params {
@@ -341,13 +372,62 @@ fn update_operator<S: HasStateApi>(
     Ok(())
 }
 
+/// Enable or disable addresses as operators of a single token owned by the sender. Token operators are allowed to
+/// transfer only that token and are removed once the token changes owner.
+/// Logs an `UpdateTokenOperator` event.
+///
+/// It rejects if:
+/// - It fails to parse the parameter.
+/// - Any of the tokens does not exist.
+/// - The sender is not the owner of the token.
+/// - Fails to log event.
+#[receive(
+    contract = "BictoryCnsNft",
+    name = "updateTokenOperator",
+    parameter = "UpdateTokenOperatorParams",
+    enable_logger,
+    mutable
+)]
+fn update_token_operator<S: HasStateApi>(
+    ctx: &impl HasReceiveContext,
+    host: &mut impl HasHost<State<S>, StateApiType = S>,
+    logger: &mut impl HasLogger,
+) -> ContractResult<()> {
+    let UpdateTokenOperatorParams(params) =
+        UpdateTokenOperatorParams::deserial(&mut ctx.parameter_cursor())?;
+    let sender = ctx.sender();
+
+    for param in params {
+        let token_storage = host.state().token_storage;
+        let subscription_data =
+            storage::get_token_subscription_data(host, &token_storage, &param.token_id)?
+                .ok_or(ContractError::InvalidTokenId)?;
+        ensure_eq!(subscription_data.owner, sender, ContractError::Unauthorized);
+
+        let (state, state_builder) = host.state_and_builder();
+        match param.update {
+            OperatorUpdate::Add => {
+                state.add_token_operator(&param.token_id, &param.operator, state_builder)
+            }
+            OperatorUpdate::Remove => state.remove_token_operator(&param.token_id, &param.operator),
+        }
+
+        logger.log(&CustomEvent::UpdateTokenOperator {
+            token: param.token_id,
+            owner: sender,
+            operator: param.operator,
+            update: param.update,
+        })?;
+    }
+    Ok(())
+}
+
 /// Takes a list of queries. Each query is an owner address and some address to
-/// check as an operator of the owner address. It takes a contract address plus
-/// contract function to invoke with the result.
+/// check as an operator of the owner address. Results are returned in the same
+/// order as the queries.
 ///
 /// It rejects if:
 /// - It fails to parse the parameter.
-/// - Message sent back with the result rejects.
 #[receive(
     contract = "BictoryCnsNft",
     name = "operatorOf",
@@ -372,13 +452,38 @@ fn operator_of<S: HasStateApi>(
     Ok(OperatorOfQueryResponse::from(response))
 }
 
-/// Get the balance of given token IDs and addresses. It takes a contract
-/// address plus contract function to invoke with the result.
+/// Takes a list of queries. Each query is a token ID and some address to check as an operator of that token. Only
+/// operators of the individual token are considered, see `operatorOf` for operators of the owner. Results are
+/// returned in the same order as the queries.
+///
+/// It rejects if:
+/// - It fails to parse the parameter.
+#[receive(
+    contract = "BictoryCnsNft",
+    name = "tokenOperatorOf",
+    parameter = "TokenOperatorOfQueryParams",
+    return_value = "Vec<bool>"
+)]
+fn token_operator_of<S: HasStateApi>(
+    ctx: &impl HasReceiveContext,
+    host: &impl HasHost<State<S>, StateApiType = S>,
+) -> ContractResult<Vec<bool>> {
+    let TokenOperatorOfQueryParams(queries) =
+        TokenOperatorOfQueryParams::deserial(&mut ctx.parameter_cursor())?;
+    let state = host.state();
+
+    Ok(queries
+        .iter()
+        .map(|query| state.is_token_operator(&query.token_id, &query.address))
+        .collect())
+}
+
+/// Get the balance of given token IDs and addresses. Balance is `1` for the
+/// owner of a token that did not expire and `0` otherwise.
 ///
 /// It rejects if:
 /// - It fails to parse the parameter.
 /// - Any of the queried `token_id` does not exist.
-/// - Message sent back with the result rejects.
 #[receive(
     contract = "BictoryCnsNft",
     name = "balanceOf",
@@ -412,11 +517,11 @@ fn balance_of<S: HasStateApi>(
     Ok(BalanceOfQueryResponse::from(response))
 }
 
-/// Get the token metadata URLs and checksums given a list of token IDs.
+/// Get the token metadata URLs given a list of token IDs.
 ///
 /// It rejects if:
 /// - It fails to parse the parameter.
-/// - Any of the queried `token_id` does not exist.
+/// - Any of the queried `token_id` does not exist or is expired.
 #[receive(
     contract = "BictoryCnsNft",
     name = "tokenMetadata",
@@ -432,34 +537,33 @@ fn contract_token_metadata<S: HasStateApi>(
     let params: TokenMetadataQueryParams<ContractTokenId> = ctx.parameter_cursor().get()?;
     // Build the response.
     let mut response = Vec::with_capacity(params.queries.len());
-    let token_storage = host.state().token_storage;
+    let state = host.state();
     let slot_time = ctx.metadata().slot_time();
 
     for token_id in params.queries {
         // Ensure the token exists and is not expired.
         ensure!(
-            !storage::get_token_subscription_data(host, &token_storage, &token_id)?
+            !storage::get_token_subscription_data(host, &state.token_storage, &token_id)?
                 .ok_or(ContractError::InvalidTokenId)?
                 .into_status(slot_time)
                 .is_expired(),
             ContractError::InvalidTokenId
         );
+        let info = storage::get_token_info(host, &state.token_storage, &token_id)?
+            .ok_or(ContractError::InvalidTokenId)?;
 
-        let metadata_url = MetadataUrl {
-            url: build_token_metadata_url(&token_id),
-            hash: None,
-        };
+        let metadata_url = cns_token_metadata_url(&state.metadata_base_url, &info.domain);
         response.push(metadata_url);
     }
     let result = TokenMetadataQueryResponse::from(response);
     Ok(result)
 }
 
-/// Function to burn token.
+/// Function to burn token. Authorized CNS contracts can burn tokens at any time, e.g. tokens of deleted subdomains.
 ///
 /// It rejects if:
 /// - Fails to log `BurnEvent`.
-/// - Current Time is less than expiry + Grace Period
+/// - Current Time is less than expiry + Grace Period and the sender is not an authorized CNS contract
 #[receive(
     mutable,
     contract = "BictoryCnsNft",
@@ -481,11 +585,12 @@ fn burn<S: HasStateApi>(
             .ok_or_else(|| ContractError::InvalidTokenId)?;
 
     ensure!(
-        subscription_data
-            .expiry
-            .checked_add(subscription_data.grace)
-            .unwrap()
-            < slot_time,
+        state.is_authorized_cns_contract(&ctx.sender())
+            || subscription_data
+                .expiry
+                .checked_add(subscription_data.grace)
+                .unwrap()
+                < slot_time,
         ContractError::Unauthorized
     );
 
@@ -494,6 +599,23 @@ fn burn<S: HasStateApi>(
         &token_storage,
         &StorageKeysRef::all(Bytes(token_id.0.clone()).as_ref()),
     )?;
+    host.state_mut().clear_token_operators(&token_id);
+
+    // Records of the domain must not carry over to the next registration. Registry prefix is missing if the domain was
+    // never registered by CNS or was already cleared.
+    if let Some(registry) = host.state().registry {
+        match host.storage_remove_raw(
+            &registry,
+            &StorageKeysRef::all(token_id.0.as_slice().into()),
+        ) {
+            Ok(())
+            | Err(CallContractError::LogicReject {
+                reason: storage::NOT_FOUND_REASON,
+                ..
+            }) => (),
+            Err(error) => return Err(error.into()),
+        }
+    }
 
     // Log Burn event
     logger.log(&Cis2Event::Burn(BurnEvent {
@@ -521,12 +643,41 @@ fn get_royalties<S: HasStateApi>(
     let royalty = storage::get_token_royalty(host, &state.token_storage, &params.token_id)?
         .ok_or(ContractError::InvalidTokenId)?;
 
-    Ok(GetRoyaltiesResponse {
-        royalties: vec![Royalty {
-            beneficiary: state.beneficiary,
-            percentage: royalty,
-        }],
-    })
+    let royalties = split_royalty(royalty, state.beneficiary, &state.royalty_split)?;
+
+    Ok(GetRoyaltiesResponse { royalties })
+}
+
+/// Split token royalty between recipients according to their shares. Beneficiary receives the rest of the royalty,
+/// including rounding remainders.
+fn split_royalty(
+    royalty: Percentage,
+    beneficiary: AccountAddress,
+    split: &[Royalty],
+) -> ContractResult<BoundedVec<Royalty, MAX_ROYALTIES>> {
+    let mut royalties = BoundedVec::new();
+    let mut shared = Percentage::ZERO;
+    for recipient in split {
+        let percentage = royalty * recipient.percentage;
+        shared = shared
+            .checked_add(percentage)
+            .ok_or(CustomContractError::Overflow)?;
+        royalties.push(Royalty {
+            beneficiary: recipient.beneficiary,
+            percentage,
+        })?;
+    }
+
+    if split.is_empty() || shared < royalty {
+        royalties.push(Royalty {
+            beneficiary,
+            percentage: royalty
+                .checked_sub(shared)
+                .ok_or(CustomContractError::Overflow)?,
+        })?;
+    }
+
+    Ok(royalties)
 }
 
 /// Function to manage addresses that are allowed to maintain and modify the state of the contract.
@@ -569,10 +720,12 @@ fn view_authority<S: HasStateApi>(
 /// - CnsContract. Address of authorised CNS contract, that is allowed to make changes to NFT.
 /// - Royalty. fee percentage for token sale. Gets assigned to a token on mint.
 /// - Beneficiary. Account address that receives the fee.
+/// - RoyaltySplit. Recipients that share the fee with the beneficiary.
 ///
 ///  It rejects if:
 ///  - Fails to parse `UpdateInternalAddressParams` parameters.
 ///  - If sender is neither one of the admins nor one of the maintainers.
+///  - Royalty split has too many recipients or shares sum up to more than 100%.
 #[receive(
     mutable,
     contract = "BictoryCnsNft",
@@ -601,6 +754,21 @@ fn update_internal_value<S: HasStateApi>(
         },
         UpdateInternalValueParams::Royalty(percentage) => state.royalty_on_mint = percentage,
         UpdateInternalValueParams::Beneficiary(account) => state.beneficiary = account,
+        UpdateInternalValueParams::MetadataBaseUrl(url) => state.metadata_base_url = url,
+        UpdateInternalValueParams::RoyaltySplit(split) => {
+            // Leave room for the beneficiary in `getRoyalties` result
+            ensure!(
+                split.len() < MAX_ROYALTIES,
+                CustomContractError::CapacityExceeded.into()
+            );
+            let total = royalty_total(&split).ok_or(CustomContractError::InvalidRoyalty)?;
+            ensure!(
+                total <= Percentage::HUNDRED,
+                CustomContractError::InvalidRoyalty.into()
+            );
+            state.royalty_split = split;
+        }
+        UpdateInternalValueParams::Registry(registry) => state.registry = registry,
     }
 
     Ok(())
@@ -633,13 +801,23 @@ fn view_internal_value<S: HasStateApi>(
         ViewInternalValueParams::Beneficiary => {
             ViewInternalValueResult::Beneficiary(state.beneficiary)
         }
+        ViewInternalValueParams::MetadataBaseUrl => {
+            ViewInternalValueResult::MetadataBaseUrl(state.metadata_base_url.clone())
+        }
+        ViewInternalValueParams::RoyaltySplit => {
+            ViewInternalValueResult::RoyaltySplit(state.royalty_split.clone())
+        }
+        ViewInternalValueParams::Registry => ViewInternalValueResult::Registry(state.registry),
     };
 
     Ok(value)
 }
 
-/// CIS-0
-
+/// Check which standards the contract supports (CIS-0). Standards other than
+/// CIS-0 and CIS-2 are answered with implementors set by a maintainer.
+///
+/// It rejects if:
+/// - It fails to parse the parameter.
 #[receive(
     contract = "BictoryCnsNft",
     name = "supports",
@@ -667,6 +845,11 @@ fn supports<S: HasStateApi>(
     Ok(result)
 }
 
+/// Set contracts implementing a standard on behalf of this contract.
+///
+/// It rejects if:
+/// - Sender is not a maintainer.
+/// - It fails to parse the parameter.
 #[receive(
     contract = "BictoryCnsNft",
     name = "setImplementors",
@@ -690,14 +873,6 @@ fn set_implementor<S: HasStateApi>(
     Ok(())
 }
 
-/// Build a string from TOKEN_METADATA_BASE_URL appended with the token ID
-/// encoded as hex.
-fn build_token_metadata_url(token_id: &ContractTokenId) -> String {
-    let mut token_metadata_url = String::from(TOKEN_METADATA_BASE_URL);
-    token_metadata_url.push_str(&token_id.to_string());
-    token_metadata_url
-}
-
 #[concordium_cfg_test]
 mod tests {
     use super::*;
@@ -725,6 +900,11 @@ mod tests {
         subindex: 0,
     };
 
+    const REGISTRY: ContractAddress = ContractAddress {
+        index: 2,
+        subindex: 0,
+    };
+
     fn slot_time() -> Timestamp {
         Timestamp::from_timestamp_millis(0)
             .checked_add(Duration::from_days(1000))
@@ -756,6 +936,7 @@ mod tests {
                     value: Some(Bytes(to_bytes(grace))),
                 },
             ],
+            expired: false,
         }
     }
 
@@ -768,6 +949,7 @@ mod tests {
             royalty_on_mint: Percentage::from_percent(3),
             grace_on_mint: Duration::from_days(60),
             beneficiary: ADMIN,
+            metadata_base_url: String::from("https://cns.bictory.io/metadata/"),
         };
         let bytes = to_bytes(&params);
         // admin is initialized to `ctx.origin()`
@@ -856,6 +1038,30 @@ mod tests {
         let result = mint(&ctx, &mut host, &mut logger);
 
         claim_eq!(result, Ok(()));
+        claim_eq!(
+            logger.logs.last(),
+            Some(&to_bytes(&Cis2Event::TokenMetadata::<_, TokenAmountU64>(
+                TokenMetadataEvent {
+                    token_id: token_0(),
+                    metadata_url: cns_token_metadata_url(
+                        "https://cns.bictory.io/metadata/",
+                        "test.ccd"
+                    ),
+                }
+            )))
+        );
+
+        // Metadata URL of the domain doesn't fit the TokenMetadata event
+        let params = CnsMintParams {
+            token_id: token_0(),
+            domain: "a".repeat(MAX_METADATA_URL_LENGTH),
+            owner: Address::Account(USER_1),
+            duration: Duration::from_hours(24 * 365 + 6),
+        };
+        let bytes = to_bytes(&params);
+        ctx.set_parameter(&bytes);
+        let result = mint(&ctx, &mut host, &mut logger);
+        claim_eq!(result, Err(CustomContractError::InvalidFields.into()));
     }
 
     #[concordium_test]
@@ -893,6 +1099,37 @@ mod tests {
         );
     }
 
+    #[concordium_test]
+    fn test_get_token_expiries() {
+        let mut host = default_host();
+
+        let mut ctx = TestReceiveContext::empty();
+        let bytes = to_bytes(&TokenListParams {
+            token_ids: vec![token_0(), token_0()],
+        });
+        ctx.set_parameter(&bytes)
+            .set_metadata_slot_time(slot_time());
+
+        host.setup_mock_entrypoint(
+            STORAGE,
+            OwnedEntrypointName::new_unchecked("get".into()),
+            parse_and_ok_mock::<StorageKeys, _>(Some(storage_ownership_data(
+                &Address::Account(USER_1),
+                &slot_time().checked_add(Duration::from_days(100)).unwrap(),
+                &Duration::from_days(60),
+            ))),
+        );
+
+        let result = get_token_expiries(&ctx, &host)
+            .expect_report("Unexpected error during 'getTokenExpiries' call");
+
+        let expiry = TokenExpiry {
+            expiry: slot_time().checked_add(Duration::from_days(100)).unwrap(),
+            grace_end: slot_time().checked_add(Duration::from_days(160)).unwrap(),
+        };
+        claim_eq!(result, vec![Some(expiry), Some(expiry)]);
+    }
+
     #[concordium_test]
     fn test_get_token_info() {
         let mut host = default_host();
@@ -919,6 +1156,7 @@ mod tests {
                         value: Some(Bytes(to_bytes(&Percentage::from_percent(3)))),
                     },
                 ],
+                expired: false,
             })),
         );
 
@@ -1060,4 +1298,435 @@ mod tests {
 
         claim_eq!(result, Ok(()));
     }
+
+    #[concordium_test]
+    fn test_balance_of() {
+        let mut host = default_host();
+
+        let mut ctx = TestReceiveContext::empty();
+        let params = ContractBalanceOfQueryParams {
+            queries: vec![
+                BalanceOfQuery {
+                    token_id: token_0(),
+                    address: Address::Account(USER_1),
+                },
+                BalanceOfQuery {
+                    token_id: token_0(),
+                    address: Address::Account(USER_2),
+                },
+            ],
+        };
+        let bytes = to_bytes(&params);
+        ctx.set_parameter(&bytes)
+            .set_metadata_slot_time(slot_time());
+
+        let expiry = slot_time().checked_add(Duration::from_days(100)).unwrap();
+        host.setup_mock_entrypoint(
+            STORAGE,
+            OwnedEntrypointName::new_unchecked("get".into()),
+            parse_and_ok_mock::<StorageKeys, _>(Some(storage_ownership_data(
+                &Address::Account(USER_1),
+                &expiry,
+                &Duration::from_days(60),
+            ))),
+        );
+
+        let result =
+            balance_of(&ctx, &host).expect_report("Unexpected error during 'balanceOf' call");
+        claim_eq!(
+            result,
+            BalanceOfQueryResponse::from(vec![TokenAmountU64(1), TokenAmountU64(0)])
+        );
+
+        // Owner has no balance after the token expires
+        ctx.set_metadata_slot_time(expiry);
+        let result =
+            balance_of(&ctx, &host).expect_report("Unexpected error during 'balanceOf' call");
+        claim_eq!(
+            result,
+            BalanceOfQueryResponse::from(vec![TokenAmountU64(0), TokenAmountU64(0)])
+        );
+    }
+
+    #[concordium_test]
+    fn test_update_operator() {
+        let mut host = default_host();
+
+        let mut ctx = TestReceiveContext::empty();
+        let params = UpdateOperatorParams(vec![UpdateOperator {
+            update: OperatorUpdate::Add,
+            operator: Address::Contract(CONTRACT_1),
+        }]);
+        let bytes = to_bytes(&params);
+        // Sender is a contract acting on behalf of itself, not the invoker
+        ctx.set_sender(Address::Contract(CNS))
+            .set_invoker(USER_1)
+            .set_parameter(&bytes);
+
+        let mut logger = TestLogger::init();
+        let result = update_operator(&ctx, &mut host, &mut logger);
+        claim_eq!(result, Ok(()));
+        claim_eq!(
+            logger.logs,
+            vec![to_bytes(
+                &Cis2Event::<ContractTokenId, TokenAmountU64>::UpdateOperator(
+                    UpdateOperatorEvent {
+                        owner: Address::Contract(CNS),
+                        operator: Address::Contract(CONTRACT_1),
+                        update: OperatorUpdate::Add,
+                    }
+                )
+            )]
+        );
+
+        let mut ctx = TestReceiveContext::empty();
+        let params = OperatorOfQueryParams {
+            queries: vec![
+                OperatorOfQuery {
+                    owner: Address::Contract(CNS),
+                    address: Address::Contract(CONTRACT_1),
+                },
+                OperatorOfQuery {
+                    owner: Address::Account(USER_1),
+                    address: Address::Contract(CONTRACT_1),
+                },
+            ],
+        };
+        let bytes = to_bytes(&params);
+        ctx.set_parameter(&bytes);
+
+        let result =
+            operator_of(&ctx, &host).expect_report("Unexpected error during 'operatorOf' call");
+        claim_eq!(result, OperatorOfQueryResponse::from(vec![true, false]));
+    }
+
+    #[concordium_test]
+    fn test_transfer_by_operator() {
+        let mut host = default_host();
+        host.setup_mock_entrypoint(
+            STORAGE,
+            OwnedEntrypointName::new_unchecked("get".into()),
+            parse_and_ok_mock::<StorageKeys, _>(Some(storage_ownership_data(
+                &Address::Account(USER_1),
+                &slot_time().checked_add(Duration::from_days(100)).unwrap(),
+                &Duration::from_days(60),
+            ))),
+        );
+        host.setup_mock_entrypoint(
+            STORAGE,
+            OwnedEntrypointName::new_unchecked("update".into()),
+            parse_and_ok_mock::<StorageEntries, _>(()),
+        );
+
+        // Owner `USER_1` makes `CONTRACT_1` its operator, `USER_2` makes the owner its operator
+        let add_operator =
+            |host: &mut TestHost<State<TestStateApi>>, owner: Address, operator: Address| {
+                let params = UpdateOperatorParams(vec![UpdateOperator {
+                    update: OperatorUpdate::Add,
+                    operator,
+                }]);
+                let bytes = to_bytes(&params);
+                let mut ctx = TestReceiveContext::empty();
+                ctx.set_sender(owner).set_parameter(&bytes);
+                let mut logger = TestLogger::init();
+                update_operator(&ctx, host, &mut logger)
+            };
+        let result = add_operator(
+            &mut host,
+            Address::Account(USER_1),
+            Address::Contract(CONTRACT_1),
+        );
+        claim_eq!(result, Ok(()));
+        let result = add_operator(
+            &mut host,
+            Address::Account(USER_2),
+            Address::Account(USER_1),
+        );
+        claim_eq!(result, Ok(()));
+
+        let params = TransferParams(vec![Transfer {
+            token_id: token_0(),
+            amount: TokenAmountU64::from(1),
+            from: Address::Account(USER_1),
+            to: Receiver::Account(USER_2),
+            data: AdditionalData::from(vec![]),
+        }]);
+        let bytes = to_bytes(&params);
+
+        // Being operated by the owner doesn't allow to transfer its tokens
+        let mut ctx = TestReceiveContext::empty();
+        ctx.set_sender(Address::Account(USER_2))
+            .set_parameter(&bytes)
+            .set_metadata_slot_time(slot_time());
+        let mut logger = TestLogger::init();
+        let result = transfer(&ctx, &mut host, &mut logger);
+        claim_eq!(result, Err(ContractError::Unauthorized));
+
+        // Operator of the owner can transfer its tokens
+        let mut ctx = TestReceiveContext::empty();
+        ctx.set_sender(Address::Contract(CONTRACT_1))
+            .set_parameter(&bytes)
+            .set_metadata_slot_time(slot_time());
+        let mut logger = TestLogger::init();
+        let result = transfer(&ctx, &mut host, &mut logger);
+        claim_eq!(result, Ok(()));
+    }
+
+    #[concordium_test]
+    fn test_token_operator() {
+        let mut host = default_host();
+        host.setup_mock_entrypoint(
+            STORAGE,
+            OwnedEntrypointName::new_unchecked("get".into()),
+            parse_and_ok_mock::<StorageKeys, _>(Some(storage_ownership_data(
+                &Address::Account(USER_1),
+                &slot_time().checked_add(Duration::from_days(100)).unwrap(),
+                &Duration::from_days(60),
+            ))),
+        );
+        host.setup_mock_entrypoint(
+            STORAGE,
+            OwnedEntrypointName::new_unchecked("update".into()),
+            parse_and_ok_mock::<StorageEntries, _>(()),
+        );
+
+        let params = UpdateTokenOperatorParams(vec![UpdateTokenOperator {
+            token_id: token_0(),
+            update: OperatorUpdate::Add,
+            operator: Address::Contract(CONTRACT_1),
+        }]);
+        let bytes = to_bytes(&params);
+
+        // Only the owner of the token can update its operators
+        let mut ctx = TestReceiveContext::empty();
+        ctx.set_sender(Address::Account(USER_2))
+            .set_parameter(&bytes);
+        let mut logger = TestLogger::init();
+        let result = update_token_operator(&ctx, &mut host, &mut logger);
+        claim_eq!(result, Err(ContractError::Unauthorized));
+
+        let mut ctx = TestReceiveContext::empty();
+        ctx.set_sender(Address::Account(USER_1))
+            .set_parameter(&bytes);
+        let mut logger = TestLogger::init();
+        let result = update_token_operator(&ctx, &mut host, &mut logger);
+        claim_eq!(result, Ok(()));
+        claim!(logger
+            .logs
+            .contains(&to_bytes(&CustomEvent::UpdateTokenOperator {
+                token: token_0(),
+                owner: Address::Account(USER_1),
+                operator: Address::Contract(CONTRACT_1),
+                update: OperatorUpdate::Add,
+            })));
+
+        // Token operator is not an operator of the owner
+        claim!(!host
+            .state()
+            .is_operator(&Address::Account(USER_1), &Address::Contract(CONTRACT_1)));
+
+        let params = TokenOperatorOfQueryParams(vec![
+            TokenOperatorOfQuery {
+                token_id: token_0(),
+                address: Address::Contract(CONTRACT_1),
+            },
+            TokenOperatorOfQuery {
+                token_id: token_0(),
+                address: Address::Account(USER_2),
+            },
+        ]);
+        let bytes = to_bytes(&params);
+        let mut ctx = TestReceiveContext::empty();
+        ctx.set_parameter(&bytes);
+        let result = token_operator_of(&ctx, &host);
+        claim_eq!(result, Ok(vec![true, false]));
+
+        // Token operator can transfer the token, which removes its approval
+        let params = TransferParams(vec![Transfer {
+            token_id: token_0(),
+            amount: TokenAmountU64::from(1),
+            from: Address::Account(USER_1),
+            to: Receiver::Account(USER_2),
+            data: AdditionalData::from(vec![]),
+        }]);
+        let bytes = to_bytes(&params);
+        let mut ctx = TestReceiveContext::empty();
+        ctx.set_sender(Address::Contract(CONTRACT_1))
+            .set_parameter(&bytes)
+            .set_metadata_slot_time(slot_time());
+        let mut logger = TestLogger::init();
+        let result = transfer(&ctx, &mut host, &mut logger);
+        claim_eq!(result, Ok(()));
+        claim!(!host
+            .state()
+            .is_token_operator(&token_0(), &Address::Contract(CONTRACT_1)));
+    }
+
+    #[concordium_test]
+    fn test_token_metadata() {
+        let mut host = default_host();
+
+        let mut ctx = TestReceiveContext::empty();
+        let params = TokenMetadataQueryParams {
+            queries: vec![token_0()],
+        };
+        let bytes = to_bytes(&params);
+        ctx.set_parameter(&bytes)
+            .set_metadata_slot_time(slot_time());
+
+        // Single entry with both ownership data and domain name
+        let expiry = slot_time().checked_add(Duration::from_days(100)).unwrap();
+        let mut data =
+            storage_ownership_data(&Address::Account(USER_1), &expiry, &Duration::from_days(60));
+        data.entries.push(MaybeStorageEntry {
+            key: Bytes("domain".as_bytes().into()),
+            value: Some(Bytes(to_bytes(&"caf\u{e9}.ccd"))),
+        });
+        host.setup_mock_entrypoint(
+            STORAGE,
+            OwnedEntrypointName::new_unchecked("get".into()),
+            parse_and_ok_mock::<StorageKeys, _>(Some(data)),
+        );
+
+        let result = token_metadata(&ctx, &host)
+            .expect_report("Unexpected error during 'tokenMetadata' call");
+        let expected = TokenMetadataQueryResponse::from(vec![MetadataUrl {
+            url: String::from("https://cns.bictory.io/metadata/caf%C3%A9.ccd"),
+            hash: None,
+        }]);
+        claim_eq!(to_bytes(&result), to_bytes(&expected));
+
+        // Expired token has no metadata
+        ctx.set_metadata_slot_time(expiry);
+        let result = token_metadata(&ctx, &host);
+        claim_eq!(result.err(), Some(ContractError::InvalidTokenId));
+    }
+
+    #[concordium_test]
+    fn test_burn() {
+        let mut host = default_host();
+        host.state_mut().registry = Some(REGISTRY);
+        let (state, state_builder) = host.state_and_builder();
+        state.add_token_operator(&token_0(), &Address::Contract(CONTRACT_1), state_builder);
+        host.setup_mock_entrypoint(
+            STORAGE,
+            OwnedEntrypointName::new_unchecked("get".into()),
+            parse_and_ok_mock::<StorageKeys, _>(Some(storage_ownership_data(
+                &Address::Account(USER_1),
+                &slot_time().checked_add(Duration::from_days(10)).unwrap(),
+                &Duration::from_days(60),
+            ))),
+        );
+        host.setup_mock_entrypoint(
+            STORAGE,
+            OwnedEntrypointName::new_unchecked("remove".into()),
+            parse_and_ok_mock::<StorageKeys, _>(()),
+        );
+        host.setup_mock_entrypoint(
+            REGISTRY,
+            OwnedEntrypointName::new_unchecked("remove".into()),
+            parse_and_check_mock::<StorageKeys, _>(
+                |params| {
+                    params.prefix == Bytes(token_0().0) && params.keys == StorageKeySelection::All
+                },
+                (),
+            ),
+        );
+
+        let bytes = to_bytes(&token_0());
+        let mut ctx = TestReceiveContext::empty();
+        ctx.set_sender(Address::Account(USER_2))
+            .set_parameter(&bytes)
+            .set_metadata_slot_time(slot_time());
+
+        // Token can't be burnt before grace period ends
+        let mut logger = TestLogger::init();
+        let result = burn(&ctx, &mut host, &mut logger);
+        claim_eq!(result, Err(ContractError::Unauthorized));
+
+        ctx.set_metadata_slot_time(slot_time().checked_add(Duration::from_days(71)).unwrap());
+        let result = burn(&ctx, &mut host, &mut logger);
+        claim_eq!(result, Ok(()));
+        claim!(!host
+            .state()
+            .is_token_operator(&token_0(), &Address::Contract(CONTRACT_1)));
+
+        // CNS contract can burn the token before grace period ends
+        ctx.set_sender(Address::Contract(CNS))
+            .set_metadata_slot_time(slot_time());
+        let result = burn(&ctx, &mut host, &mut logger);
+        claim_eq!(result, Ok(()));
+    }
+
+    #[concordium_test]
+    fn test_royalty_split() {
+        let mut host = default_host();
+        let split = vec![
+            Royalty {
+                beneficiary: USER_1,
+                percentage: Percentage::from_percent(50),
+            },
+            Royalty {
+                beneficiary: USER_2,
+                percentage: Percentage::from_percent(30),
+            },
+        ];
+
+        // Shares can't exceed 100% of the royalty
+        let params = UpdateInternalValueParams::RoyaltySplit(vec![
+            split[0].clone(),
+            Royalty {
+                beneficiary: USER_2,
+                percentage: Percentage::from_percent(60),
+            },
+        ]);
+        let bytes = to_bytes(&params);
+        let mut ctx = TestReceiveContext::empty();
+        ctx.set_sender(Address::Account(MAINTAINER))
+            .set_parameter(&bytes);
+        claim_eq!(
+            update_internal_value(&ctx, &mut host),
+            Err(CustomContractError::InvalidRoyalty.into())
+        );
+
+        let params = UpdateInternalValueParams::RoyaltySplit(split.clone());
+        let bytes = to_bytes(&params);
+        let mut ctx = TestReceiveContext::empty();
+        ctx.set_sender(Address::Account(MAINTAINER))
+            .set_parameter(&bytes);
+        claim_eq!(update_internal_value(&ctx, &mut host), Ok(()));
+        claim_eq!(host.state().royalty_split, split);
+
+        // Beneficiary receives the part of the royalty that is not shared
+        let royalties = split_royalty(Percentage::from_percent(10), ADMIN, &split)
+            .expect_report("Failed to split royalty");
+        claim_eq!(
+            royalties.to_vec(),
+            vec![
+                Royalty {
+                    beneficiary: USER_1,
+                    percentage: Percentage::from_percent(5),
+                },
+                Royalty {
+                    beneficiary: USER_2,
+                    percentage: Percentage::from_percent(3),
+                },
+                Royalty {
+                    beneficiary: ADMIN,
+                    percentage: Percentage::from_percent(2),
+                },
+            ]
+        );
+
+        // Without a split the whole royalty goes to the beneficiary
+        let royalties = split_royalty(Percentage::from_percent(10), ADMIN, &[])
+            .expect_report("Failed to split royalty");
+        claim_eq!(
+            royalties.to_vec(),
+            vec![Royalty {
+                beneficiary: ADMIN,
+                percentage: Percentage::from_percent(10),
+            }]
+        );
+    }
 }
@@ -1,4 +1,5 @@
-use commons::{ContractTokenId, LEND_TAG};
+use commons::{ContractTokenId, LEND_TAG, UPDATE_TOKEN_OPERATOR_TAG};
+use concordium_cis2::OperatorUpdate;
 use concordium_std::*;
 
 /// Tagged Custom event to be serialized for the event log.
@@ -9,6 +10,13 @@ pub enum CustomEvent {
         token: ContractTokenId,
         expiry: Timestamp,
     },
+    /// Adding or removing an operator of a single token
+    UpdateTokenOperator {
+        token: ContractTokenId,
+        owner: Address,
+        operator: Address,
+        update: OperatorUpdate,
+    },
 }
 
 impl Serial for CustomEvent {
@@ -19,6 +27,18 @@ impl Serial for CustomEvent {
                 token.serial(out)?;
                 expiry.serial(out)
             }
+            CustomEvent::UpdateTokenOperator {
+                token,
+                owner,
+                operator,
+                update,
+            } => {
+                out.write_u8(UPDATE_TOKEN_OPERATOR_TAG)?;
+                token.serial(out)?;
+                owner.serial(out)?;
+                operator.serial(out)?;
+                update.serial(out)
+            }
         }
     }
 }
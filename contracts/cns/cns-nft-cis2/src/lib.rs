@@ -1,4 +1,4 @@
-//! A CNS NFT smart contract implementing the Concordium Token Standard CIS-1.
+//! A CNS NFT smart contract implementing the Concordium Token Standard CIS-2.
 //!
 //! # Description
 //! An instance of this smart contract can operate on a number of different tokens
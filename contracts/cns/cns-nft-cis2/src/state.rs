@@ -1,4 +1,4 @@
-use commons::{Authority, Percentage};
+use commons::{Authority, ContractTokenId, Percentage, Royalty};
 use concordium_cis2::{StandardIdentifierOwned, SupportResult};
 use concordium_std::*;
 use core::ops::DerefMut;
@@ -15,14 +15,22 @@ pub struct State<S: HasStateApi> {
     pub cns_contracts: StateSet<ContractAddress, S>,
     /// Operators for each address for this CNS NFT contract.
     pub operators: StateMap<Address, StateSet<Address, S>, S>,
+    /// Operators of individual tokens, cleared whenever the token changes owner.
+    pub token_operators: StateMap<ContractTokenId, StateSet<Address, S>, S>,
     /// Platform royalty that gets permanenty assigned to a token on mint.
     pub royalty_on_mint: Percentage,
     /// Grace period that gets permanenty assigned to a token on mint.
     pub grace_on_mint: Duration,
     /// Address that receives platform royalty.
     pub beneficiary: AccountAddress,
+    /// Recipients of the token royalty with their shares of it. Beneficiary receives the part that is not shared.
+    pub royalty_split: Vec<Royalty>,
     /// Storage contract address with all token data.
     pub token_storage: ContractAddress,
+    /// CNS registry contract, cleared of domain records when the token is burnt.
+    pub registry: Option<ContractAddress>,
+    /// Base URL of token metadata, followed by the domain name in metadata URLs.
+    pub metadata_base_url: String,
     /// Implementors.
     pub implementors: StateMap<StandardIdentifierOwned, Vec<ContractAddress>, S>,
 }
@@ -38,10 +46,14 @@ impl<S: HasStateApi> State<S> {
             authority: Authority::new(state_builder, Address::Account(origin)),
             cns_contracts: state_builder.new_set(),
             operators: state_builder.new_map(),
+            token_operators: state_builder.new_map(),
             royalty_on_mint: params.royalty_on_mint,
             grace_on_mint: params.grace_on_mint,
             beneficiary: params.beneficiary,
+            royalty_split: Vec::new(),
             token_storage: params.storage_contract,
+            registry: None,
+            metadata_base_url: params.metadata_base_url,
             implementors: state_builder.new_map(),
         }
     }
@@ -79,6 +91,45 @@ impl<S: HasStateApi> State<S> {
             || owner == address
     }
 
+    /// Add a new operator for the given token.
+    ///
+    /// Succeeds even if the `operator` is already an operator for the token.
+    pub fn add_token_operator(
+        &mut self,
+        token_id: &ContractTokenId,
+        operator: &Address,
+        state_builder: &mut StateBuilder<S>,
+    ) {
+        self.token_operators
+            .entry(token_id.clone())
+            .or_insert_with(|| state_builder.new_set())
+            .deref_mut()
+            .insert(*operator);
+    }
+
+    /// Update the state removing an operator for a given token.
+    /// Succeeds even if the `operator` is _not_ an operator for the token.
+    pub fn remove_token_operator(&mut self, token_id: &ContractTokenId, operator: &Address) {
+        self.token_operators
+            .get_mut(token_id)
+            .map(|mut operators| operators.remove(operator));
+    }
+
+    /// Check if `address` is an operator of the token.
+    pub fn is_token_operator(&self, token_id: &ContractTokenId, address: &Address) -> bool {
+        self.token_operators
+            .get(token_id)
+            .map(|operators| operators.contains(address))
+            .unwrap_or(false)
+    }
+
+    /// Remove all operators of the token.
+    pub fn clear_token_operators(&mut self, token_id: &ContractTokenId) {
+        if let Some(operators) = self.token_operators.remove_and_get(token_id) {
+            operators.delete();
+        }
+    }
+
     /// Check if `address` is an authorized CNS contract.
     pub fn is_authorized_cns_contract(&self, address: &Address) -> bool {
         match address {
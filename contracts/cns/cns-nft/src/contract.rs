@@ -7,6 +7,13 @@ use crate::external::*;
 use crate::state::State;
 use crate::storage;
 
+/// Standards supported by this contract.
+const SUPPORTED_STANDARDS: [StandardIdentifier<'static>; 3] = [
+    CIS0_STANDARD_IDENTIFIER,
+    CIS1_STANDARD_IDENTIFIER,
+    BICTORY_AUTHORITY_STANDARD_IDENTIFIER,
+];
+
 /// Initialize contract instance with no token types initially.
 #[init(contract = "BictoryCnsNft", parameter = "InitParams")]
 fn init<S: HasStateApi>(
@@ -52,6 +59,12 @@ fn mint<S: HasStateApi>(
         .checked_add(params.duration)
         .ok_or(CustomContractError::InvalidDuration)?;
 
+    // URL is logged in the TokenMetadata event, which has to fit the event size limit
+    let metadata_url = cns_token_metadata_url(&state.metadata_base_url, &params.domain);
+    ensure!(
+        metadata_url.url.len() <= MAX_METADATA_URL_LENGTH,
+        CustomContractError::InvalidFields.into()
+    );
     let token_storage = state.token_storage;
     storage::insert_token(
         host,
@@ -68,11 +81,17 @@ fn mint<S: HasStateApi>(
 
     // Event for minted NFT.
     logger.log(&Cis1Event::Mint(MintEvent {
-        token_id: params.token_id,
+        token_id: params.token_id.clone(),
         amount: 1,
         owner: params.owner,
     }))?;
 
+    // Event for Metadata URL update.
+    logger.log(&Cis1Event::TokenMetadata(TokenMetadataEvent {
+        token_id: params.token_id,
+        metadata_url,
+    }))?;
+
     Ok(())
 }
 
@@ -101,6 +120,31 @@ fn get_token_expiry<S: HasStateApi>(
     Ok(status)
 }
 
+/// Function to get expiry and grace period end of several domains in one call. Result is returned in the same order
+/// as requested token IDs, with `None` in place of tokens that don't exist.
+#[receive(
+    contract = "BictoryCnsNft",
+    name = "getTokenExpiries",
+    parameter = "TokenListParams",
+    return_value = "Vec<Option<TokenExpiry>>"
+)]
+fn get_token_expiries<S: HasStateApi>(
+    ctx: &impl HasReceiveContext,
+    host: &impl HasHost<State<S>, StateApiType = S>,
+) -> ContractResult<Vec<Option<TokenExpiry>>> {
+    let params = TokenListParams::deserial(&mut ctx.parameter_cursor())?;
+    let state = host.state();
+
+    let mut expiries = Vec::with_capacity(params.token_ids.len());
+    for token_id in params.token_ids.iter() {
+        let expiry = storage::get_token_subscription_data(host, &state.token_storage, token_id)?
+            .map(storage::SubscriptionData::into_expiry);
+        expiries.push(expiry);
+    }
+
+    Ok(expiries)
+}
+
 /// View token data owned by particular address by token_id.
 #[receive(
     contract = "BictoryCnsNft",
@@ -204,9 +248,10 @@ fn transfer<S: HasStateApi>(
     for transfer in transfers {
         let state = host.state();
 
-        // Authenticate the sender for this transfer
+        // Authenticate the sender for this transfer, either as an operator of the owner or of the token
         ensure!(
-            state.is_operator(&sender, &transfer.from),
+            state.is_operator(&transfer.from, &sender)
+                || state.is_token_operator(&transfer.token_id, &sender),
             ContractError::Unauthorized
         );
 
@@ -250,6 +295,7 @@ fn transfer<S: HasStateApi>(
             &transfer.token_id,
             transfer.to.address(),
         )?;
+        host.state_mut().clear_token_operators(&transfer.token_id);
 
         // Log transfer event
         logger.log(&Cis1Event::Transfer(TransferEvent {
@@ -325,6 +371,56 @@ fn update_operator<S: HasStateApi>(
     Ok(())
 }
 
+/// Enable or disable addresses as operators of a single token owned by the sender. Token operators are allowed to
+/// transfer only that token and are removed once the token changes owner.
+/// Logs an `UpdateTokenOperator` event.
+///
+/// It rejects if:
+/// - It fails to parse the parameter.
+/// - Any of the tokens does not exist.
+/// - The sender is not the owner of the token.
+/// - Fails to log event.
+#[receive(
+    contract = "BictoryCnsNft",
+    name = "updateTokenOperator",
+    parameter = "UpdateTokenOperatorParams",
+    enable_logger,
+    mutable
+)]
+fn update_token_operator<S: HasStateApi>(
+    ctx: &impl HasReceiveContext,
+    host: &mut impl HasHost<State<S>, StateApiType = S>,
+    logger: &mut impl HasLogger,
+) -> ContractResult<()> {
+    let UpdateTokenOperatorParams(params) =
+        UpdateTokenOperatorParams::deserial(&mut ctx.parameter_cursor())?;
+    let sender = ctx.sender();
+
+    for param in params {
+        let token_storage = host.state().token_storage;
+        let subscription_data =
+            storage::get_token_subscription_data(host, &token_storage, &param.token_id)?
+                .ok_or(ContractError::InvalidTokenId)?;
+        ensure_eq!(subscription_data.owner, sender, ContractError::Unauthorized);
+
+        let (state, state_builder) = host.state_and_builder();
+        match param.update {
+            OperatorUpdate::Add => {
+                state.add_token_operator(&param.token_id, &param.operator, state_builder)
+            }
+            OperatorUpdate::Remove => state.remove_token_operator(&param.token_id, &param.operator),
+        }
+
+        logger.log(&CustomEvent::UpdateTokenOperator {
+            token: param.token_id,
+            owner: sender,
+            operator: param.operator,
+            update: param.update,
+        })?;
+    }
+    Ok(())
+}
+
 /// Takes a list of queries. Each query is an owner address and some address to
 /// check as an operator of the owner address. It takes a contract address plus
 /// contract function to invoke with the result.
@@ -364,6 +460,32 @@ fn operator_of<S: HasStateApi>(
     Ok(())
 }
 
+/// Takes a list of queries. Each query is a token ID and some address to check as an operator of that token. Only
+/// operators of the individual token are considered, see `operatorOf` for operators of the owner. Results are
+/// returned in the same order as the queries.
+///
+/// It rejects if:
+/// - It fails to parse the parameter.
+#[receive(
+    contract = "BictoryCnsNft",
+    name = "tokenOperatorOf",
+    parameter = "TokenOperatorOfQueryParams",
+    return_value = "Vec<bool>"
+)]
+fn token_operator_of<S: HasStateApi>(
+    ctx: &impl HasReceiveContext,
+    host: &impl HasHost<State<S>, StateApiType = S>,
+) -> ContractResult<Vec<bool>> {
+    let TokenOperatorOfQueryParams(queries) =
+        TokenOperatorOfQueryParams::deserial(&mut ctx.parameter_cursor())?;
+    let state = host.state();
+
+    Ok(queries
+        .iter()
+        .map(|query| state.is_token_operator(&query.token_id, &query.address))
+        .collect())
+}
+
 /// Get the balance of given token IDs and addresses. It takes a contract
 /// address plus contract function to invoke with the result.
 ///
@@ -412,11 +534,62 @@ fn balance_of<S: HasStateApi>(
     Ok(())
 }
 
-/// Function to burn token.
+/// Get the token metadata URLs given a list of token IDs. It takes a contract
+/// address plus contract function to invoke with the result.
+///
+/// It rejects if:
+/// - It fails to parse the parameter.
+/// - Any of the queried `token_id` does not exist or is expired.
+/// - Message sent back with the result rejects.
+#[receive(
+    mutable,
+    contract = "BictoryCnsNft",
+    name = "tokenMetadata",
+    parameter = "TokenMetadataQueryParams<ContractTokenId>"
+)]
+fn token_metadata<S: HasStateApi>(
+    ctx: &impl HasReceiveContext,
+    host: &mut impl HasHost<State<S>, StateApiType = S>,
+) -> ContractResult<()> {
+    let params =
+        TokenMetadataQueryParams::<ContractTokenId>::deserial(&mut ctx.parameter_cursor())?;
+    let slot_time = ctx.metadata().slot_time();
+    // Build the response.
+    let mut response = Vec::with_capacity(params.queries.len());
+    let state = host.state();
+
+    for token_id in params.queries {
+        // Ensure the token exists and is not expired.
+        ensure!(
+            !storage::get_token_subscription_data(host, &state.token_storage, &token_id)?
+                .ok_or(ContractError::InvalidTokenId)?
+                .into_status(slot_time)
+                .is_expired(),
+            ContractError::InvalidTokenId
+        );
+        let info = storage::get_token_info(host, &state.token_storage, &token_id)?
+            .ok_or(ContractError::InvalidTokenId)?;
+
+        let metadata_url = cns_token_metadata_url(&state.metadata_base_url, &info.domain);
+        response.push((token_id, metadata_url));
+    }
+
+    // Send back the response.
+    host.invoke_contract(
+        &params.result_contract,
+        &TokenMetadataQueryResponse::from(response),
+        params.result_function.as_receive_name().entrypoint_name(),
+        Amount::zero(),
+    )?;
+
+    Ok(())
+}
+
+/// Function to burn token. Authorized CNS contracts can burn tokens at any time, e.g. tokens of deleted subdomains.
 ///
 /// It rejects if:
 /// - Fails to log `BurnEvent`.
-/// - Current Time is less than expiry + Grace Period
+/// - Current Time is less than expiry + Grace Period and the sender is not an authorized CNS contract
 #[receive(
     mutable,
     contract = "BictoryCnsNft",
@@ -438,11 +611,12 @@ fn burn<S: HasStateApi>(
             .ok_or_else(|| ContractError::InvalidTokenId)?;
 
     ensure!(
-        subscription_data
-            .expiry
-            .checked_add(subscription_data.grace)
-            .unwrap()
-            < slot_time,
+        state.is_authorized_cns_contract(&ctx.sender())
+            || subscription_data
+                .expiry
+                .checked_add(subscription_data.grace)
+                .unwrap()
+                < slot_time,
         ContractError::Unauthorized
     );
 
@@ -451,6 +625,23 @@ fn burn<S: HasStateApi>(
         &token_storage,
         &StorageKeysRef::all(Bytes(token_id.0.clone()).as_ref()),
     )?;
+    host.state_mut().clear_token_operators(&token_id);
+
+    // Records of the domain must not carry over to the next registration. Registry prefix is missing if the domain was
+    // never registered by CNS or was already cleared.
+    if let Some(registry) = host.state().registry {
+        match host.storage_remove_raw(
+            &registry,
+            &StorageKeysRef::all(token_id.0.as_slice().into()),
+        ) {
+            Ok(())
+            | Err(CallContractError::LogicReject {
+                reason: storage::NOT_FOUND_REASON,
+                ..
+            }) => (),
+            Err(error) => return Err(error.into()),
+        }
+    }
 
     // Log Burn event
     logger.log(&Cis1Event::Burn(BurnEvent {
@@ -478,12 +669,41 @@ fn get_royalties<S: HasStateApi>(
     let royalty = storage::get_token_royalty(host, &state.token_storage, &params.token_id)?
         .ok_or(ContractError::InvalidTokenId)?;
 
-    Ok(GetRoyaltiesResponse {
-        royalties: vec![Royalty {
-            beneficiary: state.beneficiary,
-            percentage: royalty,
-        }],
-    })
+    let royalties = split_royalty(royalty, state.beneficiary, &state.royalty_split)?;
+
+    Ok(GetRoyaltiesResponse { royalties })
+}
+
+/// Split token royalty between recipients according to their shares. Beneficiary receives the rest of the royalty,
+/// including rounding remainders.
+fn split_royalty(
+    royalty: Percentage,
+    beneficiary: AccountAddress,
+    split: &[Royalty],
+) -> ContractResult<BoundedVec<Royalty, MAX_ROYALTIES>> {
+    let mut royalties = BoundedVec::new();
+    let mut shared = Percentage::ZERO;
+    for recipient in split {
+        let percentage = royalty * recipient.percentage;
+        shared = shared
+            .checked_add(percentage)
+            .ok_or(CustomContractError::Overflow)?;
+        royalties.push(Royalty {
+            beneficiary: recipient.beneficiary,
+            percentage,
+        })?;
+    }
+
+    if split.is_empty() || shared < royalty {
+        royalties.push(Royalty {
+            beneficiary,
+            percentage: royalty
+                .checked_sub(shared)
+                .ok_or(CustomContractError::Overflow)?,
+        })?;
+    }
+
+    Ok(royalties)
 }
 
 /// Function to manage addresses that are allowed to maintain and modify the state of the contract.
@@ -522,14 +742,70 @@ fn view_authority<S: HasStateApi>(
     Ok(host.state().authority.handle_view(params))
 }
 
+/// Check which standards the contract supports (CIS-0).
+///
+/// It rejects if:
+/// - It fails to parse the parameter.
+#[receive(
+    contract = "BictoryCnsNft",
+    name = "supports",
+    parameter = "SupportsQueryParams",
+    return_value = "SupportsQueryResponse"
+)]
+fn supports<S: HasStateApi>(
+    ctx: &impl HasReceiveContext,
+    _host: &impl HasHost<State<S>, StateApiType = S>,
+) -> ReceiveResult<SupportsQueryResponse> {
+    let params = SupportsQueryParams::deserial(&mut ctx.parameter_cursor())?;
+    Ok(supports_standards(&SUPPORTED_STANDARDS, params))
+}
+
+/// Function to grant or revoke a single maintainer capability.
+///
+///  It rejects if:
+///  - Fails to parse `CapabilityUpdateParams` parameters.
+///  - If sender is not one of the admins.
+#[receive(
+    mutable,
+    contract = "BictoryCnsNft",
+    name = "updateCapability",
+    parameter = "CapabilityUpdateParams"
+)]
+fn update_capability<S: HasStateApi>(
+    ctx: &impl HasReceiveContext,
+    host: &mut impl HasHost<State<S>, StateApiType = S>,
+) -> ReceiveResult<()> {
+    let state = host.state_mut();
+    let params = CapabilityUpdateParams::deserial(&mut ctx.parameter_cursor())?;
+    let sender = ctx.sender();
+    state.authority.handle_capability_update(sender, params)
+}
+
+/// Function to view capabilities granted to the address on top of the maintainer list.
+#[receive(
+    contract = "BictoryCnsNft",
+    name = "viewCapabilities",
+    parameter = "Address",
+    return_value = "Vec<Capability>"
+)]
+fn view_capabilities<S: HasStateApi>(
+    ctx: &impl HasReceiveContext,
+    host: &impl HasHost<State<S>, StateApiType = S>,
+) -> ReceiveResult<Vec<Capability>> {
+    let address = Address::deserial(&mut ctx.parameter_cursor())?;
+    Ok(host.state().authority.handle_capability_view(address))
+}
+
 /// Function to update internal values. This includes:
 /// - CnsContract. Address of authorised CNS contract, that is allowed to make changes to NFT.
 /// - Royalty. fee percentage for token sale. Gets assigned to a token on mint.
 /// - Beneficiary. Account address that receives the fee.
+/// - RoyaltySplit. Recipients that share the fee with the beneficiary.
 ///
 ///  It rejects if:
 ///  - Fails to parse `UpdateInternalAddressParams` parameters.
-///  - If sender is neither one of the admins nor one of the maintainers.
+///  - If sender lacks `CanSetFees` capability for royalty and beneficiary updates, or `CanConfigure` for the rest.
+///  - Royalty split has too many recipients or shares sum up to more than 100%.
 #[receive(
     mutable,
     contract = "BictoryCnsNft",
@@ -540,12 +816,22 @@ fn update_internal_value<S: HasStateApi>(
     ctx: &impl HasReceiveContext,
     host: &mut impl HasHost<State<S>, StateApiType = S>,
 ) -> ContractResult<()> {
-    if !host.state().authority.has_maintainer_rights(&ctx.sender()) {
-        return Err(ContractError::Unauthorized);
-    }
+    let params = UpdateInternalValueParams::deserial(&mut ctx.parameter_cursor())?;
+
+    let capability = match params {
+        UpdateInternalValueParams::Royalty(_)
+        | UpdateInternalValueParams::Beneficiary(_)
+        | UpdateInternalValueParams::RoyaltySplit(_) => Capability::CanSetFees,
+        _ => Capability::CanConfigure,
+    };
+    ensure!(
+        host.state()
+            .authority
+            .has_capability(&ctx.sender(), capability),
+        ContractError::Unauthorized
+    );
 
     let mut state = host.state_mut();
-    let params = UpdateInternalValueParams::deserial(&mut ctx.parameter_cursor())?;
 
     match params {
         UpdateInternalValueParams::CnsContract(update) => match update.operation {
@@ -558,6 +844,21 @@ fn update_internal_value<S: HasStateApi>(
         },
         UpdateInternalValueParams::Royalty(percentage) => state.royalty_on_mint = percentage,
         UpdateInternalValueParams::Beneficiary(account) => state.beneficiary = account,
+        UpdateInternalValueParams::MetadataBaseUrl(url) => state.metadata_base_url = url,
+        UpdateInternalValueParams::RoyaltySplit(split) => {
+            // Leave room for the beneficiary in `getRoyalties` result
+            ensure!(
+                split.len() < MAX_ROYALTIES,
+                CustomContractError::CapacityExceeded.into()
+            );
+            let total = royalty_total(&split).ok_or(CustomContractError::InvalidRoyalty)?;
+            ensure!(
+                total <= Percentage::HUNDRED,
+                CustomContractError::InvalidRoyalty.into()
+            );
+            state.royalty_split = split;
+        }
+        UpdateInternalValueParams::Registry(registry) => state.registry = registry,
     }
 
     Ok(())
@@ -590,6 +891,13 @@ fn view_internal_value<S: HasStateApi>(
         ViewInternalValueParams::Beneficiary => {
             ViewInternalValueResult::Beneficiary(state.beneficiary)
         }
+        ViewInternalValueParams::MetadataBaseUrl => {
+            ViewInternalValueResult::MetadataBaseUrl(state.metadata_base_url.clone())
+        }
+        ViewInternalValueParams::RoyaltySplit => {
+            ViewInternalValueResult::RoyaltySplit(state.royalty_split.clone())
+        }
+        ViewInternalValueParams::Registry => ViewInternalValueResult::Registry(state.registry),
     };
 
     Ok(value)
@@ -622,6 +930,11 @@ mod tests {
         subindex: 0,
     };
 
+    const REGISTRY: ContractAddress = ContractAddress {
+        index: 2,
+        subindex: 0,
+    };
+
     fn slot_time() -> Timestamp {
         Timestamp::from_timestamp_millis(0)
             .checked_add(Duration::from_days(1000))
@@ -653,6 +966,7 @@ mod tests {
                     value: Some(Bytes(to_bytes(grace))),
                 },
             ],
+            expired: false,
         }
     }
 
@@ -665,6 +979,7 @@ mod tests {
             royalty_on_mint: Percentage::from_percent(3),
             grace_on_mint: Duration::from_days(60),
             beneficiary: ADMIN,
+            metadata_base_url: String::from("https://cns.bictory.io/metadata/"),
         };
         let bytes = to_bytes(&params);
         // admin is initialized to `ctx.origin()`
@@ -753,6 +1068,28 @@ mod tests {
         let result = mint(&ctx, &mut host, &mut logger);
 
         claim_eq!(result, Ok(()));
+        claim_eq!(
+            logger.logs.last(),
+            Some(&to_bytes(&Cis1Event::TokenMetadata(TokenMetadataEvent {
+                token_id: token_0(),
+                metadata_url: cns_token_metadata_url(
+                    "https://cns.bictory.io/metadata/",
+                    "test.ccd"
+                ),
+            })))
+        );
+
+        // Metadata URL of the domain doesn't fit the TokenMetadata event
+        let params = CnsMintParams {
+            token_id: token_0(),
+            domain: "a".repeat(MAX_METADATA_URL_LENGTH),
+            owner: Address::Account(USER_1),
+            duration: Duration::from_hours(24 * 365 + 6),
+        };
+        let bytes = to_bytes(&params);
+        ctx.set_parameter(&bytes);
+        let result = mint(&ctx, &mut host, &mut logger);
+        claim_eq!(result, Err(CustomContractError::InvalidFields.into()));
     }
 
     #[concordium_test]
@@ -790,6 +1127,37 @@ mod tests {
         );
     }
 
+    #[concordium_test]
+    fn test_get_token_expiries() {
+        let mut host = default_host();
+
+        let mut ctx = TestReceiveContext::empty();
+        let bytes = to_bytes(&TokenListParams {
+            token_ids: vec![token_0(), token_0()],
+        });
+        ctx.set_parameter(&bytes)
+            .set_metadata_slot_time(slot_time());
+
+        host.setup_mock_entrypoint(
+            STORAGE,
+            OwnedEntrypointName::new_unchecked("get".into()),
+            parse_and_ok_mock::<StorageKeys, _>(Some(storage_ownership_data(
+                &Address::Account(USER_1),
+                &slot_time().checked_add(Duration::from_days(100)).unwrap(),
+                &Duration::from_days(60),
+            ))),
+        );
+
+        let result = get_token_expiries(&ctx, &host)
+            .expect_report("Unexpected error during 'getTokenExpiries' call");
+
+        let expiry = TokenExpiry {
+            expiry: slot_time().checked_add(Duration::from_days(100)).unwrap(),
+            grace_end: slot_time().checked_add(Duration::from_days(160)).unwrap(),
+        };
+        claim_eq!(result, vec![Some(expiry), Some(expiry)]);
+    }
+
     #[concordium_test]
     fn test_get_token_info() {
         let mut host = default_host();
@@ -816,6 +1184,7 @@ mod tests {
                         value: Some(Bytes(to_bytes(&Percentage::from_percent(3)))),
                     },
                 ],
+                expired: false,
             })),
         );
 
@@ -957,4 +1326,415 @@ mod tests {
 
         claim_eq!(result, Ok(()));
     }
+
+    #[concordium_test]
+    fn test_transfer_by_operator() {
+        let mut host = default_host();
+        host.setup_mock_entrypoint(
+            STORAGE,
+            OwnedEntrypointName::new_unchecked("get".into()),
+            parse_and_ok_mock::<StorageKeys, _>(Some(storage_ownership_data(
+                &Address::Account(USER_1),
+                &slot_time().checked_add(Duration::from_days(100)).unwrap(),
+                &Duration::from_days(60),
+            ))),
+        );
+        host.setup_mock_entrypoint(
+            STORAGE,
+            OwnedEntrypointName::new_unchecked("update".into()),
+            parse_and_ok_mock::<StorageEntries, _>(()),
+        );
+
+        // Owner `USER_1` makes `CONTRACT_1` its operator, `USER_2` makes the owner its operator
+        let add_operator =
+            |host: &mut TestHost<State<TestStateApi>>, owner: Address, operator: Address| {
+                let params = UpdateOperatorParams(vec![UpdateOperator {
+                    update: OperatorUpdate::Add,
+                    operator,
+                }]);
+                let bytes = to_bytes(&params);
+                let mut ctx = TestReceiveContext::empty();
+                ctx.set_sender(owner).set_parameter(&bytes);
+                let mut logger = TestLogger::init();
+                update_operator(&ctx, host, &mut logger)
+            };
+        let result = add_operator(
+            &mut host,
+            Address::Account(USER_1),
+            Address::Contract(CONTRACT_1),
+        );
+        claim_eq!(result, Ok(()));
+        let result = add_operator(
+            &mut host,
+            Address::Account(USER_2),
+            Address::Account(USER_1),
+        );
+        claim_eq!(result, Ok(()));
+
+        let params = TransferParams(vec![Transfer {
+            token_id: token_0(),
+            amount: 1,
+            from: Address::Account(USER_1),
+            to: Receiver::Account(USER_2),
+            data: AdditionalData::from(vec![]),
+        }]);
+        let bytes = to_bytes(&params);
+
+        // Being operated by the owner doesn't allow to transfer its tokens
+        let mut ctx = TestReceiveContext::empty();
+        ctx.set_sender(Address::Account(USER_2))
+            .set_parameter(&bytes)
+            .set_metadata_slot_time(slot_time());
+        let mut logger = TestLogger::init();
+        let result = transfer(&ctx, &mut host, &mut logger);
+        claim_eq!(result, Err(ContractError::Unauthorized));
+
+        // Operator of the owner can transfer its tokens
+        let mut ctx = TestReceiveContext::empty();
+        ctx.set_sender(Address::Contract(CONTRACT_1))
+            .set_parameter(&bytes)
+            .set_metadata_slot_time(slot_time());
+        let mut logger = TestLogger::init();
+        let result = transfer(&ctx, &mut host, &mut logger);
+        claim_eq!(result, Ok(()));
+    }
+
+    #[concordium_test]
+    fn test_token_operator() {
+        let mut host = default_host();
+        host.setup_mock_entrypoint(
+            STORAGE,
+            OwnedEntrypointName::new_unchecked("get".into()),
+            parse_and_ok_mock::<StorageKeys, _>(Some(storage_ownership_data(
+                &Address::Account(USER_1),
+                &slot_time().checked_add(Duration::from_days(100)).unwrap(),
+                &Duration::from_days(60),
+            ))),
+        );
+        host.setup_mock_entrypoint(
+            STORAGE,
+            OwnedEntrypointName::new_unchecked("update".into()),
+            parse_and_ok_mock::<StorageEntries, _>(()),
+        );
+
+        let params = UpdateTokenOperatorParams(vec![UpdateTokenOperator {
+            token_id: token_0(),
+            update: OperatorUpdate::Add,
+            operator: Address::Contract(CONTRACT_1),
+        }]);
+        let bytes = to_bytes(&params);
+
+        // Only the owner of the token can update its operators
+        let mut ctx = TestReceiveContext::empty();
+        ctx.set_sender(Address::Account(USER_2))
+            .set_parameter(&bytes);
+        let mut logger = TestLogger::init();
+        let result = update_token_operator(&ctx, &mut host, &mut logger);
+        claim_eq!(result, Err(ContractError::Unauthorized));
+
+        let mut ctx = TestReceiveContext::empty();
+        ctx.set_sender(Address::Account(USER_1))
+            .set_parameter(&bytes);
+        let mut logger = TestLogger::init();
+        let result = update_token_operator(&ctx, &mut host, &mut logger);
+        claim_eq!(result, Ok(()));
+        claim!(logger
+            .logs
+            .contains(&to_bytes(&CustomEvent::UpdateTokenOperator {
+                token: token_0(),
+                owner: Address::Account(USER_1),
+                operator: Address::Contract(CONTRACT_1),
+                update: OperatorUpdate::Add,
+            })));
+
+        // Token operator is not an operator of the owner
+        claim!(!host
+            .state()
+            .is_operator(&Address::Account(USER_1), &Address::Contract(CONTRACT_1)));
+
+        let params = TokenOperatorOfQueryParams(vec![
+            TokenOperatorOfQuery {
+                token_id: token_0(),
+                address: Address::Contract(CONTRACT_1),
+            },
+            TokenOperatorOfQuery {
+                token_id: token_0(),
+                address: Address::Account(USER_2),
+            },
+        ]);
+        let bytes = to_bytes(&params);
+        let mut ctx = TestReceiveContext::empty();
+        ctx.set_parameter(&bytes);
+        let result = token_operator_of(&ctx, &host);
+        claim_eq!(result, Ok(vec![true, false]));
+
+        // Token operator can transfer the token, which removes its approval
+        let params = TransferParams(vec![Transfer {
+            token_id: token_0(),
+            amount: 1,
+            from: Address::Account(USER_1),
+            to: Receiver::Account(USER_2),
+            data: AdditionalData::from(vec![]),
+        }]);
+        let bytes = to_bytes(&params);
+        let mut ctx = TestReceiveContext::empty();
+        ctx.set_sender(Address::Contract(CONTRACT_1))
+            .set_parameter(&bytes)
+            .set_metadata_slot_time(slot_time());
+        let mut logger = TestLogger::init();
+        let result = transfer(&ctx, &mut host, &mut logger);
+        claim_eq!(result, Ok(()));
+        claim!(!host
+            .state()
+            .is_token_operator(&token_0(), &Address::Contract(CONTRACT_1)));
+    }
+
+    #[concordium_test]
+    fn test_token_metadata() {
+        let mut host = default_host();
+
+        let mut ctx = TestReceiveContext::empty();
+        let params = TokenMetadataQueryParams {
+            queries: vec![token_0()],
+            result_contract: CONTRACT_1,
+            result_function: OwnedReceiveName::new_unchecked("Listing.metadata".into()),
+        };
+        let bytes = to_bytes(&params);
+        ctx.set_parameter(&bytes)
+            .set_metadata_slot_time(slot_time());
+
+        // Single entry with both ownership data and domain name
+        let expiry = slot_time().checked_add(Duration::from_days(100)).unwrap();
+        let mut data =
+            storage_ownership_data(&Address::Account(USER_1), &expiry, &Duration::from_days(60));
+        data.entries.push(MaybeStorageEntry {
+            key: Bytes("domain".as_bytes().into()),
+            value: Some(Bytes(to_bytes(&"caf\u{e9}.ccd"))),
+        });
+        host.setup_mock_entrypoint(
+            STORAGE,
+            OwnedEntrypointName::new_unchecked("get".into()),
+            parse_and_ok_mock::<StorageKeys, _>(Some(data)),
+        );
+
+        let expected = to_bytes(&TokenMetadataQueryResponse::from(vec![(
+            token_0(),
+            MetadataUrl {
+                url: String::from("https://cns.bictory.io/metadata/caf%C3%A9.ccd"),
+                hash: None,
+            },
+        )]));
+        host.setup_mock_entrypoint(
+            CONTRACT_1,
+            OwnedEntrypointName::new_unchecked("metadata".into()),
+            MockFn::new(move |parameter, _, _, _| {
+                if parameter.as_ref() == expected.as_slice() {
+                    Ok((false, Some(())))
+                } else {
+                    Err(CallContractError::Trap)
+                }
+            }),
+        );
+
+        let result = token_metadata(&ctx, &mut host);
+        claim_eq!(result, Ok(()));
+
+        // Expired token has no metadata
+        ctx.set_metadata_slot_time(expiry);
+        let result = token_metadata(&ctx, &mut host);
+        claim_eq!(result, Err(ContractError::InvalidTokenId));
+    }
+
+    #[concordium_test]
+    fn test_burn() {
+        let mut host = default_host();
+        host.state_mut().registry = Some(REGISTRY);
+        let (state, state_builder) = host.state_and_builder();
+        state.add_token_operator(&token_0(), &Address::Contract(CONTRACT_1), state_builder);
+        host.setup_mock_entrypoint(
+            STORAGE,
+            OwnedEntrypointName::new_unchecked("get".into()),
+            parse_and_ok_mock::<StorageKeys, _>(Some(storage_ownership_data(
+                &Address::Account(USER_1),
+                &slot_time().checked_add(Duration::from_days(10)).unwrap(),
+                &Duration::from_days(60),
+            ))),
+        );
+        host.setup_mock_entrypoint(
+            STORAGE,
+            OwnedEntrypointName::new_unchecked("remove".into()),
+            parse_and_ok_mock::<StorageKeys, _>(()),
+        );
+        host.setup_mock_entrypoint(
+            REGISTRY,
+            OwnedEntrypointName::new_unchecked("remove".into()),
+            parse_and_check_mock::<StorageKeys, _>(
+                |params| {
+                    params.prefix == Bytes(token_0().0) && params.keys == StorageKeySelection::All
+                },
+                (),
+            ),
+        );
+
+        let bytes = to_bytes(&token_0());
+        let mut ctx = TestReceiveContext::empty();
+        ctx.set_sender(Address::Account(USER_2))
+            .set_parameter(&bytes)
+            .set_metadata_slot_time(slot_time());
+
+        // Token can't be burnt before grace period ends
+        let mut logger = TestLogger::init();
+        let result = burn(&ctx, &mut host, &mut logger);
+        claim_eq!(result, Err(ContractError::Unauthorized));
+
+        ctx.set_metadata_slot_time(slot_time().checked_add(Duration::from_days(71)).unwrap());
+        let result = burn(&ctx, &mut host, &mut logger);
+        claim_eq!(result, Ok(()));
+        claim!(!host
+            .state()
+            .is_token_operator(&token_0(), &Address::Contract(CONTRACT_1)));
+
+        // CNS contract can burn the token before grace period ends
+        ctx.set_sender(Address::Contract(CNS))
+            .set_metadata_slot_time(slot_time());
+        let result = burn(&ctx, &mut host, &mut logger);
+        claim_eq!(result, Ok(()));
+    }
+
+    #[concordium_test]
+    fn test_royalty_split() {
+        let mut host = default_host();
+        let split = vec![
+            Royalty {
+                beneficiary: USER_1,
+                percentage: Percentage::from_percent(50),
+            },
+            Royalty {
+                beneficiary: USER_2,
+                percentage: Percentage::from_percent(30),
+            },
+        ];
+
+        // Shares can't exceed 100% of the royalty
+        let params = UpdateInternalValueParams::RoyaltySplit(vec![
+            split[0].clone(),
+            Royalty {
+                beneficiary: USER_2,
+                percentage: Percentage::from_percent(60),
+            },
+        ]);
+        let bytes = to_bytes(&params);
+        let mut ctx = TestReceiveContext::empty();
+        ctx.set_sender(Address::Account(MAINTAINER))
+            .set_parameter(&bytes);
+        claim_eq!(
+            update_internal_value(&ctx, &mut host),
+            Err(CustomContractError::InvalidRoyalty.into())
+        );
+
+        let params = UpdateInternalValueParams::RoyaltySplit(split.clone());
+        let bytes = to_bytes(&params);
+        let mut ctx = TestReceiveContext::empty();
+        ctx.set_sender(Address::Account(MAINTAINER))
+            .set_parameter(&bytes);
+        claim_eq!(update_internal_value(&ctx, &mut host), Ok(()));
+        claim_eq!(host.state().royalty_split, split);
+
+        // Beneficiary receives the part of the royalty that is not shared
+        let royalties = split_royalty(Percentage::from_percent(10), ADMIN, &split)
+            .expect_report("Failed to split royalty");
+        claim_eq!(
+            royalties.to_vec(),
+            vec![
+                Royalty {
+                    beneficiary: USER_1,
+                    percentage: Percentage::from_percent(5),
+                },
+                Royalty {
+                    beneficiary: USER_2,
+                    percentage: Percentage::from_percent(3),
+                },
+                Royalty {
+                    beneficiary: ADMIN,
+                    percentage: Percentage::from_percent(2),
+                },
+            ]
+        );
+
+        // Without a split the whole royalty goes to the beneficiary
+        let royalties = split_royalty(Percentage::from_percent(10), ADMIN, &[])
+            .expect_report("Failed to split royalty");
+        claim_eq!(
+            royalties.to_vec(),
+            vec![Royalty {
+                beneficiary: ADMIN,
+                percentage: Percentage::from_percent(10),
+            }]
+        );
+    }
+
+    #[concordium_test]
+    fn test_update_internal_value_with_capability() {
+        let mut host = default_host();
+
+        let grant = to_bytes(&CapabilityUpdateParams {
+            kind: AuthorityUpdateKind::Add,
+            address: Address::Account(USER_1),
+            capability: Capability::CanSetFees,
+        });
+        let mut ctx = TestReceiveContext::empty();
+        ctx.set_sender(Address::Account(MAINTAINER))
+            .set_parameter(&grant);
+        // Only admins can grant capabilities
+        claim_eq!(
+            update_capability(&ctx, &mut host),
+            Err(CustomContractError::Unauthorized.into())
+        );
+        ctx.set_sender(Address::Account(ADMIN));
+        claim_eq!(update_capability(&ctx, &mut host), Ok(()));
+
+        let params = UpdateInternalValueParams::Royalty(Percentage::from_percent(5));
+        let bytes = to_bytes(&params);
+        let mut ctx = TestReceiveContext::empty();
+        ctx.set_sender(Address::Account(USER_1))
+            .set_parameter(&bytes);
+        claim_eq!(update_internal_value(&ctx, &mut host), Ok(()));
+        claim_eq!(host.state().royalty_on_mint, Percentage::from_percent(5));
+
+        // Capability doesn't allow configuration changes
+        let params =
+            UpdateInternalValueParams::MetadataBaseUrl(String::from("https://example.com/"));
+        let bytes = to_bytes(&params);
+        ctx.set_parameter(&bytes);
+        claim_eq!(
+            update_internal_value(&ctx, &mut host),
+            Err(ContractError::Unauthorized)
+        );
+    }
+
+    #[concordium_test]
+    fn test_supports() {
+        let host = default_host();
+
+        let params = SupportsQueryParams {
+            queries: vec![
+                StandardIdentifierOwned::new_unchecked("CIS-1".into()),
+                StandardIdentifierOwned::new_unchecked("CIS-2".into()),
+                StandardIdentifierOwned::new_unchecked("BICTORY-AUTHORITY".into()),
+            ],
+        };
+        let bytes = to_bytes(&params);
+        let mut ctx = TestReceiveContext::empty();
+        ctx.set_parameter(&bytes);
+
+        let response = supports(&ctx, &host).expect_report("Query should succeed");
+        claim_eq!(
+            to_bytes(&response),
+            to_bytes(&SupportsQueryResponse::from(vec![
+                SupportResult::Support,
+                SupportResult::NoSupport,
+                SupportResult::Support,
+            ]))
+        );
+    }
 }
@@ -1,4 +1,5 @@
-use commons_v1::Percentage;
+use commons_v1::{ContractTokenId, Percentage, Royalty};
+use concordium_cis1::OperatorUpdate;
 use concordium_std::*;
 
 #[derive(Serialize, SchemaType)]
@@ -11,6 +12,8 @@ pub struct InitParams {
     pub grace_on_mint: Duration,
     /// Address that receives platform royalty.
     pub beneficiary: AccountAddress,
+    /// Base URL of token metadata. Metadata URL of a token is the base URL followed by the domain name.
+    pub metadata_base_url: String,
 }
 
 #[derive(Debug, Clone, Copy, Serialize, PartialEq, Eq, SchemaType)]
@@ -25,11 +28,16 @@ pub struct AddressUpdate {
     pub address: ContractAddress,
 }
 
-#[derive(Debug, Clone, Copy, Serialize, PartialEq, Eq, SchemaType)]
+#[derive(Debug, Clone, Serialize, PartialEq, Eq, SchemaType)]
 pub enum UpdateInternalValueParams {
     CnsContract(AddressUpdate),
     Royalty(Percentage),
     Beneficiary(AccountAddress),
+    MetadataBaseUrl(String),
+    /// Recipients of the token royalty, each with a share of the royalty. Shares must sum up to at most 100%
+    RoyaltySplit(Vec<Royalty>),
+    /// Registry contract to clear on burn. Requires the contract to have writer rights for the registry prefixes
+    Registry(Option<ContractAddress>),
 }
 
 #[derive(Debug, Clone, Copy, Serialize, PartialEq, Eq, SchemaType)]
@@ -37,6 +45,9 @@ pub enum ViewInternalValueParams {
     CnsContract(InternalAddressView),
     Royalty,
     Beneficiary,
+    MetadataBaseUrl,
+    RoyaltySplit,
+    Registry,
 }
 
 #[derive(Debug, Clone, Copy, Serialize, PartialEq, Eq, SchemaType)]
@@ -50,4 +61,28 @@ pub enum ViewInternalValueResult {
     CnsContract(Vec<ContractAddress>),
     Royalty(Percentage),
     Beneficiary(AccountAddress),
+    MetadataBaseUrl(String),
+    RoyaltySplit(Vec<Royalty>),
+    Registry(Option<ContractAddress>),
+}
+
+/// Add or remove an operator of a single token.
+#[derive(Debug, Serialize, SchemaType)]
+pub struct UpdateTokenOperator {
+    pub token_id: ContractTokenId,
+    pub update: OperatorUpdate,
+    pub operator: Address,
 }
+
+#[derive(Debug, Serialize, SchemaType)]
+pub struct UpdateTokenOperatorParams(pub Vec<UpdateTokenOperator>);
+
+/// Query whether `address` is an operator of the token.
+#[derive(Debug, Serialize, SchemaType)]
+pub struct TokenOperatorOfQuery {
+    pub token_id: ContractTokenId,
+    pub address: Address,
+}
+
+#[derive(Debug, Serialize, SchemaType)]
+pub struct TokenOperatorOfQueryParams(pub Vec<TokenOperatorOfQuery>);
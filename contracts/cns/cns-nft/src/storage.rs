@@ -9,6 +9,9 @@ const GRACE_KEY: &str = "grace";
 const DOMAIN_KEY: &str = "domain";
 const ROYALTY_KEY: &str = "royalty";
 
+/// Reject reason of the storage contract when the requested entries don't exist (`CustomContractError::NotFound`).
+pub const NOT_FOUND_REASON: i32 = -30;
+
 pub struct SubscriptionData {
     pub owner: Address,
     pub expiry: Timestamp,
@@ -32,6 +35,13 @@ impl SubscriptionData {
             expiry,
         }
     }
+
+    pub fn into_expiry(self) -> TokenExpiry {
+        TokenExpiry {
+            expiry: self.expiry,
+            grace_end: self.expiry.checked_add(self.grace).unwrap(),
+        }
+    }
 }
 
 pub struct TokenData {
@@ -240,8 +250,7 @@ fn handle_call_error<T>(error: CallContractError<T>) -> ContractError {
         CallContractError::LogicReject { reason, .. } => match reason {
             // CustomContractError::ParseParams | Concordium ParseError
             -1 | -2147483646 => CustomContractError::Incompatible.into(),
-            // CustomContractError::NotFound
-            -30 => ContractError::InvalidTokenId,
+            NOT_FOUND_REASON => ContractError::InvalidTokenId,
             // CustomContractError::AlreadyExists
             -35 => ContractError::Unauthorized,
             // Remaining errors
@@ -1,9 +1,9 @@
 use crate::events::CnsPriceOracleEvent;
 use crate::external::*;
-use crate::state::{DomainPricing, State};
+use crate::state::{ExchangeRate, PricingSchedule, ScheduledPricing, State};
 use commons::{
-    AuthorityUpdateParams, AuthorityViewParams, CustomContractError, DomainKind,
-    GetDomainPriceParams, GetDomainPriceResult,
+    AuthorityUpdateParams, AuthorityViewParams, CustomContractError, DomainPrice,
+    GetDomainPriceParams, GetDomainPriceResult, Percentage,
 };
 use concordium_std::*;
 
@@ -21,7 +21,8 @@ fn init<S: HasStateApi>(
     Ok(state)
 }
 
-/// Function to set yearly domain price.
+/// Function to set yearly domain price in CCD. Clears the currency set with `setCurrencyPricing`, so the prices are
+/// no longer converted with the exchange rate.
 ///
 /// It rejects if:
 /// - Fails to parse parameter;
@@ -52,14 +53,238 @@ fn set_yearly_domain_price<S: HasStateApi>(
 
     state.domain_prices = params.domain_pricing;
     state.subdomain_prices = params.subdomain_pricing;
+    state.grace_renewal_prices = params.grace_renewal_pricing;
+    state.currency = None;
 
     Ok(())
 }
 
-/// Function to get yearly domain price.
+/// Function to set yearly domain price denominated in a currency. Prices are in CCD if the currency is not set.
 ///
 /// It rejects if:
-/// - Fails to parse parameter.
+/// - Fails to parse parameter;
+/// - Fails to log `SetCurrencyPricing` event;
+/// - Sender does not have maintainer rights.
+#[receive(
+    mutable,
+    contract = "BictoryCnsPriceOracle",
+    name = "setCurrencyPricing",
+    parameter = "CurrencyPricingParams",
+    enable_logger
+)]
+fn set_currency_pricing<S: HasStateApi>(
+    ctx: &impl HasReceiveContext,
+    host: &mut impl HasHost<State<S>, StateApiType = S>,
+    logger: &mut impl HasLogger,
+) -> ReceiveResult<()> {
+    let params = CurrencyPricingParams::deserial(&mut ctx.parameter_cursor())?;
+    let state = host.state_mut();
+
+    ensure!(
+        state.authority.has_maintainer_rights(&ctx.sender()),
+        CustomContractError::Unauthorized.into()
+    );
+
+    logger.log(&CnsPriceOracleEvent::SetCurrencyPricing(&params))?;
+
+    state.domain_prices = params.pricing.domain_pricing;
+    state.subdomain_prices = params.pricing.subdomain_pricing;
+    state.grace_renewal_prices = params.pricing.grace_renewal_pricing;
+    state.currency = params.currency;
+    state.max_rate_age = params.max_rate_age;
+
+    Ok(())
+}
+
+/// Function to update the exchange rate of a currency to CCD. Prices denominated in the currency are converted to CCD
+/// with the latest rate.
+///
+/// It rejects if:
+/// - Fails to parse parameter;
+/// - Sender does not have maintainer rights;
+/// - Rate is zero;
+/// - Fails to log `UpdateRate` event.
+#[receive(
+    mutable,
+    contract = "BictoryCnsPriceOracle",
+    name = "updateRate",
+    parameter = "UpdateRateParams",
+    enable_logger
+)]
+fn update_rate<S: HasStateApi>(
+    ctx: &impl HasReceiveContext,
+    host: &mut impl HasHost<State<S>, StateApiType = S>,
+    logger: &mut impl HasLogger,
+) -> ReceiveResult<()> {
+    let params = UpdateRateParams::deserial(&mut ctx.parameter_cursor())?;
+    let state = host.state_mut();
+
+    ensure!(
+        state.authority.has_maintainer_rights(&ctx.sender()),
+        CustomContractError::Unauthorized.into()
+    );
+    ensure!(
+        params.rate > Amount::zero(),
+        CustomContractError::InvalidFields.into()
+    );
+
+    let rate = ExchangeRate {
+        currency: params.currency,
+        rate: params.rate,
+        updated_at: ctx.metadata().slot_time(),
+    };
+
+    logger.log(&CnsPriceOracleEvent::UpdateRate(&rate))?;
+
+    state.rate = Some(rate);
+
+    Ok(())
+}
+
+/// Function to get the latest exchange rate.
+///
+/// It rejects if:
+/// - Exchange rate was never set.
+#[receive(
+    contract = "BictoryCnsPriceOracle",
+    name = "getRate",
+    return_value = "ExchangeRate"
+)]
+fn get_rate<S: HasStateApi>(
+    _ctx: &impl HasReceiveContext,
+    host: &impl HasHost<State<S>, StateApiType = S>,
+) -> ReceiveResult<ExchangeRate> {
+    host.state()
+        .rate
+        .ok_or_else(|| CustomContractError::NotFound.into())
+}
+
+/// Function to send the latest exchange rate to a contract function. Allows contracts that can't read the rate with
+/// `getRate`, e.g. V0 contracts, to keep a copy of it.
+///
+/// It rejects if:
+/// - Fails to parse parameter;
+/// - Exchange rate was never set;
+/// - Receiving contract function rejects.
+#[receive(
+    mutable,
+    contract = "BictoryCnsPriceOracle",
+    name = "pushRate",
+    parameter = "PushRateParams"
+)]
+fn push_rate<S: HasStateApi>(
+    ctx: &impl HasReceiveContext,
+    host: &mut impl HasHost<State<S>, StateApiType = S>,
+) -> ReceiveResult<()> {
+    let params = PushRateParams::deserial(&mut ctx.parameter_cursor())?;
+    let rate = host.state().rate.ok_or(CustomContractError::NotFound)?;
+
+    host.invoke_contract(
+        &params.contract,
+        &rate,
+        params.entrypoint.as_entrypoint_name(),
+        Amount::zero(),
+    )
+    .map_err(CustomContractError::from)?;
+
+    Ok(())
+}
+
+/// Function to replace the pricing schedule. Every entry applies a discount and length overrides between its start
+/// and end time.
+///
+/// It rejects if:
+/// - Fails to parse parameter;
+/// - Sender does not have maintainer rights;
+/// - Any entry ends before it starts;
+/// - Any entry has a discount over 100%;
+/// - Fails to log `SetPricingSchedule` event.
+#[receive(
+    mutable,
+    contract = "BictoryCnsPriceOracle",
+    name = "setPricingSchedule",
+    parameter = "PricingSchedule",
+    enable_logger
+)]
+fn set_pricing_schedule<S: HasStateApi>(
+    ctx: &impl HasReceiveContext,
+    host: &mut impl HasHost<State<S>, StateApiType = S>,
+    logger: &mut impl HasLogger,
+) -> ReceiveResult<()> {
+    let schedule = PricingSchedule::deserial(&mut ctx.parameter_cursor())?;
+    let state = host.state_mut();
+
+    ensure!(
+        state.authority.has_maintainer_rights(&ctx.sender()),
+        CustomContractError::Unauthorized.into()
+    );
+
+    for entry in schedule.iter() {
+        ensure!(
+            entry.start < entry.end,
+            CustomContractError::InvalidDuration.into()
+        );
+        ensure!(
+            entry.discount <= Percentage::HUNDRED,
+            CustomContractError::InvalidFields.into()
+        );
+    }
+
+    logger.log(&CnsPriceOracleEvent::SetPricingSchedule(&schedule))?;
+
+    state.schedule = schedule;
+
+    Ok(())
+}
+
+/// Function to view the pricing schedule, including entries that are not active yet or have already ended.
+#[receive(
+    contract = "BictoryCnsPriceOracle",
+    name = "viewPricingSchedule",
+    return_value = "PricingSchedule"
+)]
+fn view_pricing_schedule<S: HasStateApi>(
+    _ctx: &impl HasReceiveContext,
+    host: &impl HasHost<State<S>, StateApiType = S>,
+) -> ReceiveResult<PricingSchedule> {
+    Ok(host.state().schedule.clone())
+}
+
+/// Scheduled pricing active at the current slot time. Slot time is only read if the schedule is not empty.
+fn active_schedule<'a, S: HasStateApi>(
+    ctx: &impl HasReceiveContext,
+    state: &'a State<S>,
+) -> Option<&'a ScheduledPricing> {
+    if state.schedule.is_empty() {
+        None
+    } else {
+        state.active_schedule(ctx.metadata().slot_time())
+    }
+}
+
+/// Price converted to CCD with the exchange rate valid at the current slot time. Slot time is only read if prices are
+/// denominated in a currency.
+fn to_ccd<S: HasStateApi>(
+    ctx: &impl HasReceiveContext,
+    state: &State<S>,
+    price: DomainPrice,
+) -> Result<DomainPrice, CustomContractError> {
+    if state.currency.is_none() {
+        Ok(price)
+    } else {
+        state.to_ccd(price, ctx.metadata().slot_time())
+    }
+}
+
+/// Function to get yearly domain price. Renewals of domains in their grace period are priced with the grace renewal
+/// pricing, if it is set. Pricing schedule active at the current slot time applies to all other prices. Prices
+/// denominated in another currency are converted to CCD with the latest exchange rate.
+///
+/// It rejects if:
+/// - Fails to parse parameter;
+/// - Prices are denominated in a currency without exchange rate;
+/// - Exchange rate is older than the maximum rate age;
+/// - Converted price overflows.
 #[receive(
     mutable,
     contract = "BictoryCnsPriceOracle",
@@ -74,25 +299,41 @@ fn get_yearly_domain_price<S: HasStateApi>(
     let params = GetDomainPriceParams::deserial(&mut ctx.parameter_cursor())?;
     let state = host.state();
 
-    let pricing = match params.domain_kind {
-        DomainKind::Domain => &state.domain_prices,
-        DomainKind::Subdomain => &state.subdomain_prices,
-    };
+    let price = state.yearly_price(&params, active_schedule(ctx, state));
 
-    let result = match pricing {
-        DomainPricing::Fixed(amount) => *amount,
-        DomainPricing::Scaling(pricing) => {
-            if params.length <= pricing.short_max_length {
-                pricing.short
-            } else if params.length > pricing.short_max_length + pricing.mid.len() as u16 {
-                pricing.long
-            } else {
-                pricing.mid[(params.length - pricing.short_max_length - 1) as usize]
-            }
-        }
-    };
+    Ok(GetDomainPriceResult {
+        result: to_ccd(ctx, state, price)?,
+    })
+}
 
-    Ok(GetDomainPriceResult { result })
+/// Function to get yearly prices of several domains in one call. Results are returned in the same order as requested.
+///
+/// It rejects if:
+/// - Fails to parse parameter;
+/// - Any of the prices can't be converted to CCD.
+#[receive(
+    contract = "BictoryCnsPriceOracle",
+    name = "getYearlyDomainPrices",
+    parameter = "Vec<GetDomainPriceParams>",
+    return_value = "Vec<GetDomainPriceResult>"
+)]
+fn get_yearly_domain_prices<S: HasStateApi>(
+    ctx: &impl HasReceiveContext,
+    host: &impl HasHost<State<S>, StateApiType = S>,
+) -> ReceiveResult<Vec<GetDomainPriceResult>> {
+    let params = <Vec<GetDomainPriceParams>>::deserial(&mut ctx.parameter_cursor())?;
+    let state = host.state();
+    let schedule = active_schedule(ctx, state);
+
+    params
+        .iter()
+        .map(|params| -> ReceiveResult<GetDomainPriceResult> {
+            let price = state.yearly_price(params, schedule);
+            Ok(GetDomainPriceResult {
+                result: to_ccd(ctx, state, price)?,
+            })
+        })
+        .collect()
 }
 
 #[receive(
@@ -127,11 +368,12 @@ fn view_authority<S: HasStateApi>(
 
 #[concordium_cfg_test]
 mod tests {
-    use commons::DomainPrice;
+    use commons::DomainKind;
     use concordium_std::*;
     use test_infrastructure::*;
 
-    use crate::state::ScalingPricing;
+    use crate::state::{Currency, DomainPricing, LengthOverride, ScalingPricing};
+    use core::convert::TryFrom;
 
     use super::*;
 
@@ -169,12 +411,14 @@ mod tests {
         let mut host = new_host_with(PricingParams {
             domain_pricing: DomainPricing::Fixed(DomainPrice::Amount(Amount::from_ccd(10))),
             subdomain_pricing: DomainPricing::Fixed(DomainPrice::Amount(Amount::from_ccd(5))),
+            grace_renewal_pricing: None,
         });
 
         let mut ctx = TestReceiveContext::default();
         let params = GetDomainPriceParams {
             domain_kind: DomainKind::Domain,
             length: 5,
+            renewal_in_grace: false,
         };
         let bytes = to_bytes(&params);
         ctx.set_parameter(&bytes);
@@ -193,6 +437,7 @@ mod tests {
         let params = GetDomainPriceParams {
             domain_kind: DomainKind::Subdomain,
             length: 1,
+            renewal_in_grace: false,
         };
         let bytes = to_bytes(&params);
         ctx.set_parameter(&bytes);
@@ -223,12 +468,14 @@ mod tests {
                 long: DomainPrice::Amount(Amount::from_ccd(5)),
             }),
             subdomain_pricing: DomainPricing::Fixed(DomainPrice::Amount(Amount::from_ccd(2))),
+            grace_renewal_pricing: None,
         });
 
         let mut ctx = TestReceiveContext::default();
         let params = GetDomainPriceParams {
             domain_kind: DomainKind::Domain,
             length: 3,
+            renewal_in_grace: false,
         };
         let bytes = to_bytes(&params);
         ctx.set_parameter(&bytes);
@@ -247,6 +494,7 @@ mod tests {
         let params = GetDomainPriceParams {
             domain_kind: DomainKind::Domain,
             length: 4,
+            renewal_in_grace: false,
         };
         let bytes = to_bytes(&params);
         ctx.set_parameter(&bytes);
@@ -265,6 +513,7 @@ mod tests {
         let params = GetDomainPriceParams {
             domain_kind: DomainKind::Domain,
             length: 7,
+            renewal_in_grace: false,
         };
         let bytes = to_bytes(&params);
         ctx.set_parameter(&bytes);
@@ -283,6 +532,7 @@ mod tests {
         let params = GetDomainPriceParams {
             domain_kind: DomainKind::Domain,
             length: 8,
+            renewal_in_grace: false,
         };
         let bytes = to_bytes(&params);
         ctx.set_parameter(&bytes);
@@ -301,6 +551,7 @@ mod tests {
         let params = GetDomainPriceParams {
             domain_kind: DomainKind::Subdomain,
             length: 1,
+            renewal_in_grace: false,
         };
         let bytes = to_bytes(&params);
         ctx.set_parameter(&bytes);
@@ -315,4 +566,402 @@ mod tests {
             }
         );
     }
+
+    #[concordium_test]
+    fn test_batch_pricing() {
+        let host = new_host_with(PricingParams {
+            domain_pricing: DomainPricing::Fixed(DomainPrice::Amount(Amount::from_ccd(10))),
+            subdomain_pricing: DomainPricing::Fixed(DomainPrice::Amount(Amount::from_ccd(5))),
+            grace_renewal_pricing: None,
+        });
+
+        let mut ctx = TestReceiveContext::default();
+        let params = vec![
+            GetDomainPriceParams {
+                domain_kind: DomainKind::Subdomain,
+                length: 1,
+                renewal_in_grace: false,
+            },
+            GetDomainPriceParams {
+                domain_kind: DomainKind::Domain,
+                length: 5,
+                renewal_in_grace: false,
+            },
+        ];
+        let bytes = to_bytes(&params);
+        ctx.set_parameter(&bytes);
+
+        let result = get_yearly_domain_prices(&ctx, &host)
+            .expect_report("Failed to call getYearlyDomainPrices");
+
+        claim_eq!(
+            result,
+            vec![
+                GetDomainPriceResult {
+                    result: DomainPrice::Amount(Amount::from_ccd(5))
+                },
+                GetDomainPriceResult {
+                    result: DomainPrice::Amount(Amount::from_ccd(10))
+                },
+            ]
+        );
+    }
+
+    #[concordium_test]
+    fn test_grace_renewal_pricing() {
+        let mut host = new_host_with(PricingParams {
+            domain_pricing: DomainPricing::Fixed(DomainPrice::Amount(Amount::from_ccd(10))),
+            subdomain_pricing: DomainPricing::Fixed(DomainPrice::Amount(Amount::from_ccd(5))),
+            grace_renewal_pricing: Some(DomainPricing::Fixed(DomainPrice::Amount(
+                Amount::from_ccd(15),
+            ))),
+        });
+
+        let mut ctx = TestReceiveContext::default();
+        let params = GetDomainPriceParams {
+            domain_kind: DomainKind::Domain,
+            length: 5,
+            renewal_in_grace: true,
+        };
+        let bytes = to_bytes(&params);
+        ctx.set_parameter(&bytes);
+
+        let result = get_yearly_domain_price(&ctx, &mut host)
+            .expect_report("Failed to call getYearlyDomainPrice");
+
+        claim_eq!(
+            result,
+            GetDomainPriceResult {
+                result: DomainPrice::Amount(Amount::from_ccd(15))
+            }
+        );
+
+        // Regular domain prices apply without grace renewal pricing
+        let params = PricingParams {
+            domain_pricing: DomainPricing::Fixed(DomainPrice::Amount(Amount::from_ccd(10))),
+            subdomain_pricing: DomainPricing::Fixed(DomainPrice::Amount(Amount::from_ccd(5))),
+            grace_renewal_pricing: None,
+        };
+        let bytes = to_bytes(&params);
+        let mut ctx = TestReceiveContext::default();
+        ctx.set_sender(Address::Account(MAINTAINER))
+            .set_parameter(&bytes);
+        let mut logger = TestLogger::init();
+        let result = set_yearly_domain_price(&ctx, &mut host, &mut logger);
+        claim_eq!(result, Ok(()));
+
+        let mut ctx = TestReceiveContext::default();
+        let params = GetDomainPriceParams {
+            domain_kind: DomainKind::Domain,
+            length: 5,
+            renewal_in_grace: true,
+        };
+        let bytes = to_bytes(&params);
+        ctx.set_parameter(&bytes);
+
+        let result = get_yearly_domain_price(&ctx, &mut host)
+            .expect_report("Failed to call getYearlyDomainPrice");
+
+        claim_eq!(
+            result,
+            GetDomainPriceResult {
+                result: DomainPrice::Amount(Amount::from_ccd(10))
+            }
+        );
+    }
+
+    #[concordium_test]
+    fn test_pricing_schedule() {
+        let mut host = new_host_with(PricingParams {
+            domain_pricing: DomainPricing::Fixed(DomainPrice::Amount(Amount::from_ccd(10))),
+            subdomain_pricing: DomainPricing::Fixed(DomainPrice::Amount(Amount::from_ccd(5))),
+            grace_renewal_pricing: None,
+        });
+
+        let get_price = |host: &mut TestHost<State<TestStateApi>>,
+                         now: u64,
+                         domain_kind: DomainKind,
+                         length: u16,
+                         renewal_in_grace: bool| {
+            let mut ctx = TestReceiveContext::default();
+            let params = GetDomainPriceParams {
+                domain_kind,
+                length,
+                renewal_in_grace,
+            };
+            let bytes = to_bytes(&params);
+            ctx.set_metadata_slot_time(Timestamp::from_timestamp_millis(now))
+                .set_parameter(&bytes);
+            get_yearly_domain_price(&ctx, host)
+                .expect_report("Failed to call getYearlyDomainPrice")
+                .result
+        };
+
+        let set_schedule = |host: &mut TestHost<State<TestStateApi>>,
+                            sender: AccountAddress,
+                            schedule: Vec<ScheduledPricing>| {
+            let schedule = PricingSchedule::try_from(schedule).expect_report("Schedule too long");
+            let bytes = to_bytes(&schedule);
+            let mut ctx = TestReceiveContext::default();
+            ctx.set_sender(Address::Account(sender))
+                .set_parameter(&bytes);
+            let mut logger = TestLogger::init();
+            set_pricing_schedule(&ctx, host, &mut logger)
+        };
+
+        let promo = ScheduledPricing {
+            start: Timestamp::from_timestamp_millis(100),
+            end: Timestamp::from_timestamp_millis(200),
+            discount: Percentage::from_percent(50),
+            length_overrides: vec![LengthOverride {
+                length: 3,
+                price: DomainPrice::Amount(Amount::from_ccd(100)),
+            }],
+        };
+
+        // Only maintainers can set the schedule
+        let result = set_schedule(&mut host, AccountAddress([3; 32]), vec![promo.clone()]);
+        claim_eq!(result, Err(CustomContractError::Unauthorized.into()));
+
+        let mut invalid = promo.clone();
+        invalid.end = invalid.start;
+        let result = set_schedule(&mut host, MAINTAINER, vec![invalid]);
+        claim_eq!(result, Err(CustomContractError::InvalidDuration.into()));
+
+        let mut invalid = promo.clone();
+        invalid.discount = Percentage::from_percent(101);
+        let result = set_schedule(&mut host, MAINTAINER, vec![invalid]);
+        claim_eq!(result, Err(CustomContractError::InvalidFields.into()));
+
+        let result = set_schedule(&mut host, MAINTAINER, vec![promo.clone()]);
+        claim_eq!(result, Ok(()));
+
+        let ctx = TestReceiveContext::default();
+        let result = view_pricing_schedule(&ctx, &host);
+        claim_eq!(
+            result.map(|schedule| schedule.into_inner()),
+            Ok(vec![promo])
+        );
+
+        let ccd = |amount: u64| DomainPrice::Amount(Amount::from_ccd(amount));
+
+        // Regular prices apply outside of the scheduled period
+        claim_eq!(
+            get_price(&mut host, 99, DomainKind::Domain, 5, false),
+            ccd(10)
+        );
+        claim_eq!(
+            get_price(&mut host, 200, DomainKind::Domain, 3, false),
+            ccd(10)
+        );
+
+        // Discount applies to domains, subdomains and length overrides
+        claim_eq!(
+            get_price(&mut host, 100, DomainKind::Domain, 5, false),
+            ccd(5)
+        );
+        claim_eq!(
+            get_price(&mut host, 150, DomainKind::Subdomain, 3, false),
+            DomainPrice::Amount(Amount::from_micro_ccd(2_500_000))
+        );
+        claim_eq!(
+            get_price(&mut host, 199, DomainKind::Domain, 3, false),
+            ccd(50)
+        );
+
+        // Renewals in grace period are not affected
+        claim_eq!(
+            get_price(&mut host, 150, DomainKind::Domain, 3, true),
+            ccd(10)
+        );
+    }
+
+    #[concordium_test]
+    fn test_exchange_rate() {
+        let mut host = new_host_with(PricingParams {
+            domain_pricing: DomainPricing::Fixed(DomainPrice::Amount(Amount::from_micro_ccd(
+                20_000_000,
+            ))),
+            subdomain_pricing: DomainPricing::Fixed(DomainPrice::Limited),
+            grace_renewal_pricing: None,
+        });
+
+        // Same pricing, denominated in EUR
+        let params = CurrencyPricingParams {
+            pricing: PricingParams {
+                domain_pricing: DomainPricing::Fixed(DomainPrice::Amount(Amount::from_micro_ccd(
+                    20_000_000,
+                ))),
+                subdomain_pricing: DomainPricing::Fixed(DomainPrice::Limited),
+                grace_renewal_pricing: None,
+            },
+            currency: Some(Currency::Eur),
+            max_rate_age: Duration::from_millis(1000),
+        };
+        let bytes = to_bytes(&params);
+        let mut ctx = TestReceiveContext::default();
+        ctx.set_sender(Address::Account(AccountAddress([3; 32])))
+            .set_parameter(&bytes);
+        let mut logger = TestLogger::init();
+        let result = set_currency_pricing(&ctx, &mut host, &mut logger);
+        claim_eq!(result, Err(CustomContractError::Unauthorized.into()));
+
+        ctx.set_sender(Address::Account(MAINTAINER));
+        let result = set_currency_pricing(&ctx, &mut host, &mut logger);
+        claim_eq!(result, Ok(()));
+        claim_eq!(
+            logger.logs,
+            vec![to_bytes(&CnsPriceOracleEvent::SetCurrencyPricing(&params))]
+        );
+        claim_eq!(logger.logs[0][0], commons::SET_CURRENCY_PRICING_TAG);
+
+        let get_price =
+            |host: &mut TestHost<State<TestStateApi>>, now: u64, domain_kind: DomainKind| {
+                let mut ctx = TestReceiveContext::default();
+                let params = GetDomainPriceParams {
+                    domain_kind,
+                    length: 5,
+                    renewal_in_grace: false,
+                };
+                let bytes = to_bytes(&params);
+                ctx.set_parameter(&bytes)
+                    .set_metadata_slot_time(Timestamp::from_timestamp_millis(now));
+                get_yearly_domain_price(&ctx, host).map(|result| result.result)
+            };
+
+        let set_rate = |host: &mut TestHost<State<TestStateApi>>,
+                        sender: AccountAddress,
+                        params: UpdateRateParams| {
+            let bytes = to_bytes(&params);
+            let mut ctx = TestReceiveContext::default();
+            ctx.set_sender(Address::Account(sender))
+                .set_parameter(&bytes)
+                .set_metadata_slot_time(Timestamp::from_timestamp_millis(10));
+            let mut logger = TestLogger::init();
+            update_rate(&ctx, host, &mut logger)
+        };
+
+        // Prices in EUR can't be quoted without exchange rate
+        claim_eq!(
+            get_price(&mut host, 10, DomainKind::Domain),
+            Err(CustomContractError::NotFound.into())
+        );
+        claim_eq!(
+            get_rate(&TestReceiveContext::default(), &host),
+            Err(CustomContractError::NotFound.into())
+        );
+
+        let result = set_rate(
+            &mut host,
+            AccountAddress([3; 32]),
+            UpdateRateParams {
+                currency: Currency::Eur,
+                rate: Amount::from_ccd(25),
+            },
+        );
+        claim_eq!(result, Err(CustomContractError::Unauthorized.into()));
+
+        let result = set_rate(
+            &mut host,
+            MAINTAINER,
+            UpdateRateParams {
+                currency: Currency::Eur,
+                rate: Amount::zero(),
+            },
+        );
+        claim_eq!(result, Err(CustomContractError::InvalidFields.into()));
+
+        // Rate of another currency doesn't apply
+        let result = set_rate(
+            &mut host,
+            MAINTAINER,
+            UpdateRateParams {
+                currency: Currency::Usd,
+                rate: Amount::from_ccd(20),
+            },
+        );
+        claim_eq!(result, Ok(()));
+        claim_eq!(
+            get_price(&mut host, 10, DomainKind::Domain),
+            Err(CustomContractError::NotFound.into())
+        );
+
+        let result = set_rate(
+            &mut host,
+            MAINTAINER,
+            UpdateRateParams {
+                currency: Currency::Eur,
+                rate: Amount::from_ccd(25),
+            },
+        );
+        claim_eq!(result, Ok(()));
+        claim_eq!(
+            get_rate(&TestReceiveContext::default(), &host),
+            Ok(ExchangeRate {
+                currency: Currency::Eur,
+                rate: Amount::from_ccd(25),
+                updated_at: Timestamp::from_timestamp_millis(10),
+            })
+        );
+
+        // 20 EUR at 25 CCD per EUR
+        claim_eq!(
+            get_price(&mut host, 10, DomainKind::Domain),
+            Ok(DomainPrice::Amount(Amount::from_ccd(500)))
+        );
+        claim_eq!(
+            get_price(&mut host, 10, DomainKind::Subdomain),
+            Ok(DomainPrice::Limited)
+        );
+
+        // Rate can be used until it gets older than the maximum rate age
+        claim_eq!(
+            get_price(&mut host, 1010, DomainKind::Domain),
+            Ok(DomainPrice::Amount(Amount::from_ccd(500)))
+        );
+        claim_eq!(
+            get_price(&mut host, 1011, DomainKind::Domain),
+            Err(CustomContractError::StaleExchangeRate.into())
+        );
+
+        // Prices in CCD don't depend on the rate
+        let bytes = to_bytes(&params.pricing);
+        let mut ctx = TestReceiveContext::default();
+        ctx.set_sender(Address::Account(MAINTAINER))
+            .set_parameter(&bytes);
+        let mut logger = TestLogger::init();
+        claim_eq!(
+            set_yearly_domain_price(&ctx, &mut host, &mut logger),
+            Ok(())
+        );
+        claim_eq!(
+            get_price(&mut host, 1011, DomainKind::Domain),
+            Ok(DomainPrice::Amount(Amount::from_micro_ccd(20_000_000)))
+        );
+
+        // Latest rate can be sent to other contracts
+        let receiver = ContractAddress {
+            index: 7,
+            subindex: 0,
+        };
+        host.setup_mock_entrypoint(
+            receiver,
+            OwnedEntrypointName::new_unchecked("receiveRate".into()),
+            MockFn::new_v1(|param, _, _, _| {
+                let rate = ExchangeRate::deserial(&mut Cursor::new(param.as_ref()))
+                    .map_err(|_| CallContractError::Trap)?;
+                if rate.rate != Amount::from_ccd(25) {
+                    return Err(CallContractError::Trap);
+                }
+                Ok((false, ()))
+            }),
+        );
+        let bytes = to_bytes(&PushRateParams {
+            contract: receiver,
+            entrypoint: OwnedEntrypointName::new_unchecked("receiveRate".into()),
+        });
+        let mut ctx = TestReceiveContext::default();
+        ctx.set_parameter(&bytes);
+        claim_eq!(push_rate(&ctx, &mut host), Ok(()));
+    }
 }
@@ -1,13 +1,23 @@
-use commons::SET_YEARLY_DOMAIN_PRICE_TAG;
+use commons::{
+    SET_CURRENCY_PRICING_TAG, SET_PRICING_SCHEDULE_TAG, SET_YEARLY_DOMAIN_PRICE_TAG,
+    UPDATE_RATE_TAG,
+};
 use concordium_std::*;
 
-use crate::external::PricingParams;
+use crate::external::{CurrencyPricingParams, PricingParams};
+use crate::state::{ExchangeRate, PricingSchedule};
 
 /// Tagged Custom event to be serialized for the event log.
 #[derive(Debug)]
 pub enum CnsPriceOracleEvent<'e> {
     /// Update the yearly domain pricing
     SetYearlyDomainPrice(&'e PricingParams),
+    /// Replace the pricing schedule
+    SetPricingSchedule(&'e PricingSchedule),
+    /// Update the exchange rate
+    UpdateRate(&'e ExchangeRate),
+    /// Update the yearly domain pricing denominated in a currency
+    SetCurrencyPricing(&'e CurrencyPricingParams),
 }
 
 impl<'e> Serial for CnsPriceOracleEvent<'e> {
@@ -17,6 +27,18 @@ impl<'e> Serial for CnsPriceOracleEvent<'e> {
                 out.write_u8(SET_YEARLY_DOMAIN_PRICE_TAG)?;
                 pricing.serial(out)
             }
+            CnsPriceOracleEvent::SetPricingSchedule(schedule) => {
+                out.write_u8(SET_PRICING_SCHEDULE_TAG)?;
+                schedule.serial(out)
+            }
+            CnsPriceOracleEvent::UpdateRate(rate) => {
+                out.write_u8(UPDATE_RATE_TAG)?;
+                rate.serial(out)
+            }
+            CnsPriceOracleEvent::SetCurrencyPricing(pricing) => {
+                out.write_u8(SET_CURRENCY_PRICING_TAG)?;
+                pricing.serial(out)
+            }
         }
     }
 }
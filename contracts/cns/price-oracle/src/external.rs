@@ -1,9 +1,36 @@
 use concordium_std::*;
 
-use crate::state::DomainPricing;
+use crate::state::{Currency, DomainPricing};
 
 #[derive(Debug, Serialize, SchemaType)]
 pub struct PricingParams {
     pub domain_pricing: DomainPricing,
     pub subdomain_pricing: DomainPricing,
+    pub grace_renewal_pricing: Option<DomainPricing>,
+}
+
+/// Pricing denominated in a currency other than CCD. Kept apart from `PricingParams`, so the parameter of
+/// `setYearlyDomainPrice` stays the same.
+#[derive(Debug, Serialize, SchemaType)]
+pub struct CurrencyPricingParams {
+    pub pricing: PricingParams,
+    /// Currency the prices are denominated in. Prices are in CCD if not set.
+    pub currency: Option<Currency>,
+    /// Maximum age of the exchange rate accepted for converting prices to CCD.
+    pub max_rate_age: Duration,
+}
+
+/// Contract function to send the exchange rate to.
+#[derive(Debug, Serialize, SchemaType)]
+pub struct PushRateParams {
+    pub contract: ContractAddress,
+    /// Entrypoint receiving `ExchangeRate` as its parameter.
+    pub entrypoint: OwnedEntrypointName,
+}
+
+#[derive(Debug, Serialize, SchemaType)]
+pub struct UpdateRateParams {
+    pub currency: Currency,
+    /// Price of one unit of the currency in CCD.
+    pub rate: Amount,
 }
@@ -1,6 +1,10 @@
 use crate::external::PricingParams;
-use commons::{Authority, DomainPrice};
+use commons::{
+    Authority, BoundedVec, CustomContractError, DomainKind, DomainPrice, GetDomainPriceParams,
+    Percentage, MAX_PRICING_SCHEDULE,
+};
 use concordium_std::*;
+use core::convert::TryFrom;
 
 /// Description of scaling domain name pricing policy.
 #[derive(Debug, Serialize, SchemaType)]
@@ -21,6 +25,98 @@ pub enum DomainPricing {
     Scaling(ScalingPricing),
 }
 
+impl DomainPricing {
+    /// Yearly price of a domain name with the given length.
+    pub fn price(&self, length: u16) -> DomainPrice {
+        match self {
+            DomainPricing::Fixed(amount) => *amount,
+            DomainPricing::Scaling(pricing) => {
+                if length <= pricing.short_max_length {
+                    pricing.short
+                } else if length > pricing.short_max_length + pricing.mid.len() as u16 {
+                    pricing.long
+                } else {
+                    pricing.mid[(length - pricing.short_max_length - 1) as usize]
+                }
+            }
+        }
+    }
+}
+
+/// Domain price for a specific domain name length.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, SchemaType)]
+pub struct LengthOverride {
+    pub length: u16,
+    pub price: DomainPrice,
+}
+
+/// Pricing that applies in addition to the regular pricing for a limited period of time, e.g. a launch promotion.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, SchemaType)]
+pub struct ScheduledPricing {
+    /// Time the pricing becomes active.
+    pub start: Timestamp,
+    /// Time the pricing stops being active, exclusive.
+    pub end: Timestamp,
+    /// Discount applied to domain and subdomain prices.
+    pub discount: Percentage,
+    /// Domain prices replacing the regular ones for the given lengths. The discount applies to them as well.
+    pub length_overrides: Vec<LengthOverride>,
+}
+
+impl ScheduledPricing {
+    pub fn is_active(&self, now: Timestamp) -> bool {
+        self.start <= now && now < self.end
+    }
+
+    /// Price after applying the schedule to the regular domain price.
+    pub fn apply(&self, params: &GetDomainPriceParams, price: DomainPrice) -> DomainPrice {
+        let price = match params.domain_kind {
+            DomainKind::Domain => self
+                .length_overrides
+                .iter()
+                .find(|length_override| length_override.length == params.length)
+                .map_or(price, |length_override| length_override.price),
+            DomainKind::Subdomain => price,
+        };
+
+        match price {
+            DomainPrice::Limited => DomainPrice::Limited,
+            DomainPrice::Amount(amount) => DomainPrice::Amount(Amount::from_micro_ccd(
+                amount
+                    .micro_ccd
+                    .saturating_sub((self.discount * amount).micro_ccd),
+            )),
+        }
+    }
+}
+
+pub type PricingSchedule = BoundedVec<ScheduledPricing, MAX_PRICING_SCHEDULE>;
+
+/// Currency domain prices can be denominated in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, SchemaType)]
+pub enum Currency {
+    Eur,
+    Usd,
+}
+
+/// Exchange rate of a currency to CCD.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, SchemaType)]
+pub struct ExchangeRate {
+    pub currency: Currency,
+    /// Price of one unit of the currency in CCD.
+    pub rate: Amount,
+    /// Time the rate was last updated.
+    pub updated_at: Timestamp,
+}
+
+impl ExchangeRate {
+    /// Convert a price in millionths of the currency unit to CCD. Results in `None` on overflow.
+    pub fn to_ccd(&self, price: Amount) -> Option<Amount> {
+        let micro_ccd = price.micro_ccd as u128 * self.rate.micro_ccd as u128 / 1_000_000;
+        u64::try_from(micro_ccd).ok().map(Amount::from_micro_ccd)
+    }
+}
+
 /// The contract state.
 #[derive(Serial, DeserialWithState)]
 #[concordium(state_parameter = "S")]
@@ -31,6 +127,16 @@ pub struct State<S: HasStateApi> {
     pub domain_prices: DomainPricing,
     /// Prices for subdomains.
     pub subdomain_prices: DomainPricing,
+    /// Prices for renewing domains in their grace period. Domain prices apply if not set.
+    pub grace_renewal_prices: Option<DomainPricing>,
+    /// Time-bounded pricing applied on top of regular prices.
+    pub schedule: PricingSchedule,
+    /// Currency the prices are denominated in. Prices are in CCD if not set.
+    pub currency: Option<Currency>,
+    /// Latest exchange rate to CCD.
+    pub rate: Option<ExchangeRate>,
+    /// Maximum age of the exchange rate accepted for converting prices to CCD.
+    pub max_rate_age: Duration,
 }
 
 impl<S: HasStateApi> State<S> {
@@ -44,6 +150,66 @@ impl<S: HasStateApi> State<S> {
             authority: Authority::new(state_builder, Address::Account(origin)),
             domain_prices: params.domain_pricing,
             subdomain_prices: params.subdomain_pricing,
+            grace_renewal_prices: params.grace_renewal_pricing,
+            schedule: PricingSchedule::new(),
+            currency: None,
+            rate: None,
+            max_rate_age: Duration::from_millis(0),
+        }
+    }
+
+    /// Convert the price to CCD if prices are denominated in another currency. Fails if there is no exchange rate for
+    /// the currency, or the rate is older than `max_rate_age` at the given time.
+    pub fn to_ccd(
+        &self,
+        price: DomainPrice,
+        now: Timestamp,
+    ) -> Result<DomainPrice, CustomContractError> {
+        let (currency, amount) = match (self.currency, price) {
+            (Some(currency), DomainPrice::Amount(amount)) => (currency, amount),
+            _ => return Ok(price),
+        };
+        let rate = self
+            .rate
+            .filter(|rate| rate.currency == currency)
+            .ok_or(CustomContractError::NotFound)?;
+        // Rates from the future can't be stale
+        let age = now
+            .timestamp_millis()
+            .saturating_sub(rate.updated_at.timestamp_millis());
+        ensure!(
+            age <= self.max_rate_age.millis(),
+            CustomContractError::StaleExchangeRate
+        );
+        rate.to_ccd(amount)
+            .map(DomainPrice::Amount)
+            .ok_or(CustomContractError::Overflow)
+    }
+
+    /// Scheduled pricing active at the given time. The first matching entry wins if several of them overlap.
+    pub fn active_schedule(&self, now: Timestamp) -> Option<&ScheduledPricing> {
+        self.schedule.iter().find(|entry| entry.is_active(now))
+    }
+
+    /// Yearly price of the requested domain. Renewals of domains in their grace period are priced with the grace
+    /// renewal pricing, if it is set. Scheduled pricing applies to everything except renewals in grace period.
+    pub fn yearly_price(
+        &self,
+        params: &GetDomainPriceParams,
+        schedule: Option<&ScheduledPricing>,
+    ) -> DomainPrice {
+        let pricing = match (&params.domain_kind, &self.grace_renewal_prices) {
+            (DomainKind::Domain, Some(grace_renewal_prices)) if params.renewal_in_grace => {
+                grace_renewal_prices
+            }
+            (DomainKind::Domain, _) => &self.domain_prices,
+            (DomainKind::Subdomain, _) => &self.subdomain_prices,
+        };
+
+        let price = pricing.price(params.length);
+        match schedule {
+            Some(schedule) if !params.renewal_in_grace => schedule.apply(params, price),
+            _ => price,
         }
     }
 }
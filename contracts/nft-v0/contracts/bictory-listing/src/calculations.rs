@@ -1,22 +1,18 @@
 use super::*;
 
-// convert the royalty percentage and amount to pay into a payout
-fn royalty_to_payout(price: Amount, royalty: u64) -> Amount {
-    let ttl_per = Amount::from_ccd(100);
-    Amount::from_micro_ccd(price.micro_ccd * royalty / ttl_per.micro_ccd)
-}
-
+/// Split the price into royalty payouts. Owner receives the whole price until royalties are subtracted with
+/// `Shares::adjust_owner_share`.
 pub fn calc_shares(
     price: Amount,
-    creator_royalty: u64,
-    minter_royalty: u64,
-    bictory_royalty: u64,
+    creator_royalty: Percentage,
+    minter_royalty: Percentage,
+    bictory_royalty: Percentage,
 ) -> Shares {
     Shares {
-        creator: royalty_to_payout(price, creator_royalty),
-        minter: royalty_to_payout(price, minter_royalty),
+        creator: creator_royalty.saturating_mul_amount(price),
+        minter: minter_royalty.saturating_mul_amount(price),
         owner: price,
-        bictory: royalty_to_payout(price, bictory_royalty),
+        bictory: bictory_royalty.saturating_mul_amount(price),
     }
 }
 
@@ -35,9 +31,9 @@ mod tests {
 
         let mut actual_shares = calc_shares(
             Amount::from_ccd(100),
-            Amount::from_ccd(5).micro_ccd,
-            Amount::from_ccd(2).micro_ccd,
-            Amount::from_ccd(2).micro_ccd,
+            Percentage::from_percent(5),
+            Percentage::from_percent(2),
+            Percentage::from_basis_points(200),
         );
         actual_shares.adjust_owner_share();
 
@@ -222,9 +222,9 @@ fn contract_buy<A: HasActions>(
     // Calculating shares
     let mut shares = calc_shares(
         nft_details.price,
-        nft_details.creator_royalty as u64,
-        nft_details.minter_royalty as u64,
-        params.bictory_royalty as u64,
+        Percentage::from_micro_percent(nft_details.creator_royalty as u64),
+        Percentage::from_micro_percent(nft_details.minter_royalty as u64),
+        Percentage::from_micro_percent(params.bictory_royalty as u64),
     );
     shares.adjust_owner_share();
 
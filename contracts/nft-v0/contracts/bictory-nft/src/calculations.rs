@@ -1,16 +1,13 @@
 use super::*;
 
-// convert the royalty percentage and amount to pay into a payout
-fn royalty_to_payout(price: Amount, royalty: u64) -> Amount {
-    let ttl_per = Amount::from_ccd(100);
-    Amount::from_micro_ccd(price.micro_ccd * royalty / ttl_per.micro_ccd)
-}
-
-pub fn calc_shares(price: Amount, bictory_royalty: u64) -> Shares {
-    let royalty_to_creator = 100_000_000 - bictory_royalty;
+/// Split the mint price between the creator and Bictory. Creator receives nothing if Bictory's royalty exceeds 100%.
+pub fn calc_shares(price: Amount, bictory_royalty: Percentage) -> Shares {
+    let royalty_to_creator = Percentage::HUNDRED
+        .checked_sub(bictory_royalty)
+        .unwrap_or(Percentage::ZERO);
     Shares {
-        creator: royalty_to_payout(price, royalty_to_creator),
-        bictory: royalty_to_payout(price, bictory_royalty),
+        creator: royalty_to_creator.saturating_mul_amount(price),
+        bictory: bictory_royalty.saturating_mul_amount(price),
     }
 }
 
@@ -25,7 +22,7 @@ mod tests {
             bictory: Amount::from_ccd(5),
         };
 
-        let actual_shares = calc_shares(Amount::from_ccd(100), Amount::from_ccd(5).micro_ccd);
+        let actual_shares = calc_shares(Amount::from_ccd(100), Percentage::from_percent(5));
 
         claim_eq!(expected_shares, actual_shares);
     }
@@ -48,7 +48,10 @@ pub fn contract_mint<A: HasActions>(
         // Mint the token in the state.
         state.mint(owner, mint_data.clone(), price)?;
 
-        let shares = calc_shares(price, mint_data.bictory_royalty as u64);
+        let shares = calc_shares(
+            price,
+            Percentage::from_micro_percent(mint_data.bictory_royalty as u64),
+        );
 
         if let Address::Account(creator) = mint_data.creator {
             actions = actions.and_then(A::simple_transfer(&creator, shares.creator));
@@ -277,22 +280,23 @@ fn contract_balance_of<A: HasActions>(
 }
 
 /// NFT Burn Functionality.
-/// Can only be called by token owner.
+/// Can only be called by token owner or its operator.
 /// Logs a `Burn` and a `TokenMetadata` event for each token.
 /// The url for the token metadata is the token ID encoded in hex, appended on
 /// the `TOKEN_METADATA_BASE_URL`.
 ///
 /// It rejects if:
-/// - The sender is not the token owner.
+/// - The sender is neither the token owner nor its operator.
 /// - Fails to parse parameter.
 /// - Tokens fails to be upated, which could be if:
 ///     - The minted token ID does not exist.
+///     - The quantity is more than 1.
 ///     - Fails to log Burn event
 ///     - Fails to log TokenMetadata event
 #[receive(
     contract = "BictoryNFT",
     name = "burn",
-    parameter = "ContractTokenId",
+    parameter = "BurnParams",
     enable_logger
 )]
 pub fn contract_burn<A: HasActions>(
@@ -301,21 +305,61 @@ pub fn contract_burn<A: HasActions>(
     state: &mut State,
 ) -> ContractResult<A> {
     // Parse the parameter.
-    let token_id: ContractTokenId = ctx.parameter_cursor().get()?;
+    let params: BurnParams = ctx.parameter_cursor().get()?;
 
-    let owner = Address::Account(ctx.invoker());
+    burn_tokens(ctx, logger, state, vec![params])
+}
 
-    // Burning NFT
-    let event = state.burn(&owner, token_id.clone())?;
+/// Burn a list of tokens, in the order of the list, same as `burn`.
+///
+/// It rejects if:
+/// - Fails to parse parameter.
+/// - Any of the burns fails, same as in `burn`.
+#[receive(
+    contract = "BictoryNFT",
+    name = "burnMany",
+    parameter = "Vec<BurnParams>",
+    enable_logger
+)]
+pub fn contract_burn_many<A: HasActions>(
+    ctx: &impl HasReceiveContext,
+    logger: &mut impl HasLogger,
+    state: &mut State,
+) -> ContractResult<A> {
+    // Parse the parameter.
+    let burns: Vec<BurnParams> = ctx.parameter_cursor().get()?;
 
-    // Remove empty state
-    state.clear_empty_state(&owner);
+    burn_tokens(ctx, logger, state, burns)
+}
 
-    // Event for burning NFT.
-    logger.log(&Cis1Event::Burn(event))?;
+/// Burn the tokens on behalf of the sender and log the `Burn` and `TokenMetadata` events.
+fn burn_tokens<A: HasActions>(
+    ctx: &impl HasReceiveContext,
+    logger: &mut impl HasLogger,
+    state: &mut State,
+    burns: Vec<BurnParams>,
+) -> ContractResult<A> {
+    let sender = ctx.sender();
 
-    // Metadata URL for the NFT.
-    logger.log(&token_metadata_event(token_id))?;
+    for params in burns {
+        // Authenticate the sender for this burn
+        ensure!(
+            params.owner == sender || state.is_operator(&sender, &params.owner),
+            ContractError::Unauthorized
+        );
+
+        // Burning NFT
+        let event = state.burn(&params)?;
+
+        // Remove empty state
+        state.clear_empty_state(&params.owner);
+
+        // Event for burning NFT.
+        logger.log(&Cis1Event::Burn(event))?;
+
+        // Metadata URL for the NFT.
+        logger.log(&token_metadata_event(params.token_id))?;
+    }
 
     Ok(A::accept())
 }
@@ -702,8 +746,25 @@ mod tests {
         let _: ContractResult<ActionsTree> =
             contract_mint(&ctx, Amount::zero(), &mut logger, &mut state);
 
-        let parameter_bytes = to_bytes(&token_0());
+        // Only a single copy can be burned
+        let mut burn_params = BurnParams {
+            owner: ADDRESS_0,
+            token_id: token_0(),
+            quantity: 2,
+        };
+        let too_many_bytes = to_bytes(&vec![burn_params.clone()]);
+        ctx.set_parameter(&too_many_bytes);
+        let result: ContractResult<ActionsTree> = contract_burn_many(&ctx, &mut logger, &mut state);
+        claim_eq!(result, Err(ContractError::InsufficientFunds));
+
+        // Operator of the owner can burn
+        ctx.set_sender(ADDRESS_1);
+        burn_params.quantity = 1;
+        let parameter_bytes = to_bytes(&burn_params);
         ctx.set_parameter(&parameter_bytes);
+        let result: ContractResult<ActionsTree> = contract_burn(&ctx, &mut logger, &mut state);
+        claim_eq!(result, Err(ContractError::Unauthorized));
+        state.add_operator(&ADDRESS_0, &ADDRESS_1);
 
         // Call the contract function.
         let result: ContractResult<ActionsTree> = contract_burn(&ctx, &mut logger, &mut state);
@@ -109,30 +109,34 @@ impl State {
 
     /// Burning of NFT.
     /// Results in an error if the
+    /// - quantity is more than the single copy of the NFT
     /// - token ID does not exist in the state
     /// - owner's address not found
-    pub fn burn(
-        &mut self,
-        owner: &Address,
-        token_id: ContractTokenId,
-    ) -> ContractResult<BurnEvent<ContractTokenId>> {
+    pub fn burn(&mut self, params: &BurnParams) -> ContractResult<BurnEvent<ContractTokenId>> {
+        // Since this contract only contains NFTs, no one will have an amount greater
+        // than 1.
+        ensure!(params.quantity <= 1, ContractError::InsufficientFunds);
+
         // Extracting owner account state associated with given owner address
         let addr_state = self
             .state
-            .get_mut(owner)
+            .get_mut(&params.owner)
             .ok_or(ContractError::Custom(CustomContractError::AddressNotFound))?;
 
-        // Extracting token_details
-        let _ = addr_state
-            .token_data
-            .owned_tokens
-            .remove(&token_id)
-            .ok_or(ContractError::InvalidTokenId)?;
+        // A zero burn does not modify the state.
+        if params.quantity == 1 {
+            // Extracting token_details
+            let _ = addr_state
+                .token_data
+                .owned_tokens
+                .remove(&params.token_id)
+                .ok_or(ContractError::InvalidTokenId)?;
+        }
 
         Ok(BurnEvent {
-            token_id,
-            amount: 1,
-            owner: *owner,
+            token_id: params.token_id.clone(),
+            amount: params.quantity,
+            owner: params.owner,
         })
     }
 
@@ -71,6 +71,17 @@ pub struct MintData<T: IsTokenId> {
     pub bictory_royalty: u32,
 }
 
+/// The parameter for the contract functions `burn` and `burnMany`.
+#[derive(Serialize, SchemaType, Clone)]
+pub struct BurnParams {
+    /// Token Owner
+    pub owner: Address,
+    /// TokenId to burn
+    pub token_id: ContractTokenId,
+    /// Copies of NFT
+    pub quantity: TokenAmount,
+}
+
 #[derive(SchemaType, Serialize, Eq, PartialEq, PartialOrd, Ord, Debug)]
 pub struct Shares {
     pub creator: Amount,
@@ -13,6 +13,9 @@ fn contract_init(_ctx: &impl HasInitContext) -> InitResult<State> {
 /// It rejects if:
 /// - The sender is not the token owner.
 /// - Fails to parse parameter.
+/// - Price of any token put on sale is below the price floor of its NFT contract.
+/// - Price floor is denominated in a currency without exchange rate, or the
+///   exchange rate is older than the configured maximum age.
 /// - Tokens fails to be upated, which could be if:
 ///     - Fails to log SetForSale event
 #[receive(
@@ -38,6 +41,15 @@ pub fn contract_set_for_sale<A: HasActions>(
     );
 
     for sales in params.sales {
+        // Rejecting listings at dust prices
+        if sales.for_sale {
+            state.check_price_floor(
+                &sales.token.contract,
+                sales.price,
+                ctx.metadata().slot_time(),
+            )?;
+        }
+
         // Updating state
         state.update_state(params.owner, &sales)?;
 
@@ -89,6 +101,108 @@ pub fn contract_set_for_sale<A: HasActions>(
     Ok(actions)
 }
 
+/// Set or remove the minimum price of listings of an NFT contract.
+/// Can only be called by the contract owner.
+///
+/// It rejects if:
+/// - The sender is not the contract owner.
+/// - Fails to parse parameter.
+#[receive(
+    contract = "BictorySetForSale",
+    name = "setPriceFloor",
+    parameter = "SetPriceFloorParams"
+)]
+pub fn contract_set_price_floor<A: HasActions>(
+    ctx: &impl HasReceiveContext,
+    state: &mut State,
+) -> ContractResult<A> {
+    // Ensuring sender is the contract owner
+    ensure!(
+        ctx.sender().matches_account(&ctx.owner()),
+        ContractError::Unauthorized
+    );
+
+    // Parse the parameter.
+    let params: SetPriceFloorParams = ctx.parameter_cursor().get()?;
+
+    match params.floor {
+        Some(floor) => {
+            state.price_floors.insert(params.contract, floor);
+        }
+        None => {
+            state.price_floors.remove(&params.contract);
+        }
+    }
+
+    Ok(A::accept())
+}
+
+/// Set the price oracle which sends exchange rates for price floors denominated
+/// in a currency, or remove it, together with the maximum age of exchange rates
+/// accepted for price floor checks. The previous exchange rate is dropped.
+/// Can only be called by the contract owner.
+///
+/// It rejects if:
+/// - The sender is not the contract owner.
+/// - Fails to parse parameter.
+#[receive(
+    contract = "BictorySetForSale",
+    name = "setPriceOracle",
+    parameter = "SetPriceOracleParams"
+)]
+pub fn contract_set_price_oracle<A: HasActions>(
+    ctx: &impl HasReceiveContext,
+    state: &mut State,
+) -> ContractResult<A> {
+    // Ensuring sender is the contract owner
+    ensure!(
+        ctx.sender().matches_account(&ctx.owner()),
+        ContractError::Unauthorized
+    );
+
+    // Parse the parameter.
+    let params: SetPriceOracleParams = ctx.parameter_cursor().get()?;
+    state.price_oracle = params.oracle;
+    state.max_rate_age = params.max_rate_age;
+    state.rate = None;
+
+    Ok(A::accept())
+}
+
+/// Store the exchange rate sent by the price oracle with its `pushRate` function.
+///
+/// It rejects if:
+/// - The sender is not the price oracle.
+/// - Fails to parse parameter.
+/// - The rate is older than the stored one, e.g. a stale rate pushed again.
+#[receive(
+    contract = "BictorySetForSale",
+    name = "receiveRate",
+    parameter = "ExchangeRate"
+)]
+pub fn contract_receive_rate<A: HasActions>(
+    ctx: &impl HasReceiveContext,
+    state: &mut State,
+) -> ContractResult<A> {
+    // Ensuring sender is the price oracle
+    ensure!(
+        state.price_oracle.map(Address::Contract) == Some(ctx.sender()),
+        ContractError::Unauthorized
+    );
+
+    // Parse the parameter.
+    let rate: ExchangeRate = ctx.parameter_cursor().get()?;
+    ensure!(
+        state
+            .rate
+            .map_or(true, |current| rate.updated_at >= current.updated_at),
+        CustomContractError::StaleExchangeRate.into()
+    );
+    state.rate = Some(rate);
+
+    Ok(A::accept())
+}
+
 #[concordium_cfg_test]
 mod tests {
     use super::*;
@@ -113,4 +227,134 @@ mod tests {
             "No tokens should be initialized."
         );
     }
+
+    /// Test listings below the price floor are rejected.
+    #[concordium_test]
+    fn test_price_floor() {
+        const OWNER: AccountAddress = AccountAddress([0u8; 32]);
+        const SELLER: AccountAddress = AccountAddress([1u8; 32]);
+        const NFT: ContractAddress = ContractAddress {
+            index: 1,
+            subindex: 0,
+        };
+        const ORACLE: ContractAddress = ContractAddress {
+            index: 2,
+            subindex: 0,
+        };
+
+        let mut state = State::empty();
+        let mut ctx = ReceiveContextTest::empty();
+        ctx.set_owner(OWNER);
+        ctx.set_metadata_slot_time(Timestamp::from_timestamp_millis(10));
+
+        // Only the contract owner can set price floors
+        let floor_bytes = to_bytes(&SetPriceFloorParams {
+            contract: NFT,
+            floor: Some(PriceFloor::Currency(Currency::Eur, Amount::from_ccd(2))),
+        });
+        ctx.set_sender(Address::Account(SELLER));
+        ctx.set_parameter(&floor_bytes);
+        let result: ContractResult<ActionsTree> = contract_set_price_floor(&ctx, &mut state);
+        claim_eq!(result, Err(ContractError::Unauthorized));
+
+        ctx.set_sender(Address::Account(OWNER));
+        let result: ContractResult<ActionsTree> = contract_set_price_floor(&ctx, &mut state);
+        claim!(result.is_ok(), "Results in rejection");
+
+        let sales = |price: Amount| Sales {
+            owner: SELLER,
+            sales: vec![ForSale {
+                token: Token {
+                    contract: NFT,
+                    id: TokenIdVec(vec![2]),
+                },
+                for_sale: true,
+                creator: SELLER,
+                creator_royalty: 0,
+                minter: SELLER,
+                minter_royalty: 0,
+                price,
+                cid: Vec::new(),
+                hash: Vec::new(),
+            }],
+        };
+        let mut logger = LogRecorder::init();
+
+        // Floor in EUR can't be checked without exchange rate
+        let sale_bytes = to_bytes(&sales(Amount::from_ccd(100)));
+        ctx.set_sender(Address::Account(SELLER));
+        ctx.set_parameter(&sale_bytes);
+        let result: ContractResult<ActionsTree> =
+            contract_set_for_sale(&ctx, &mut logger, &mut state);
+        claim_eq!(result, Err(CustomContractError::MissingExchangeRate.into()));
+
+        // Only the price oracle can send exchange rates
+        let oracle_bytes = to_bytes(&SetPriceOracleParams {
+            oracle: Some(ORACLE),
+            max_rate_age: Duration::from_millis(100),
+        });
+        ctx.set_sender(Address::Account(OWNER));
+        ctx.set_parameter(&oracle_bytes);
+        let result: ContractResult<ActionsTree> = contract_set_price_oracle(&ctx, &mut state);
+        claim!(result.is_ok(), "Results in rejection");
+
+        let rate_bytes = to_bytes(&ExchangeRate {
+            currency: Currency::Eur,
+            rate: Amount::from_ccd(25),
+            updated_at: Timestamp::from_timestamp_millis(10),
+        });
+        ctx.set_parameter(&rate_bytes);
+        let result: ContractResult<ActionsTree> = contract_receive_rate(&ctx, &mut state);
+        claim_eq!(result, Err(ContractError::Unauthorized));
+
+        ctx.set_sender(Address::Contract(ORACLE));
+        let result: ContractResult<ActionsTree> = contract_receive_rate(&ctx, &mut state);
+        claim!(result.is_ok(), "Results in rejection");
+
+        // 2 EUR at 25 CCD per EUR
+        ctx.set_sender(Address::Account(SELLER));
+        ctx.set_parameter(&sale_bytes);
+        let result: ContractResult<ActionsTree> =
+            contract_set_for_sale(&ctx, &mut logger, &mut state);
+        claim_eq!(result, Err(CustomContractError::PriceBelowFloor.into()));
+
+        let sale_bytes = to_bytes(&sales(Amount::from_ccd(50)));
+        ctx.set_parameter(&sale_bytes);
+        let result: ContractResult<ActionsTree> =
+            contract_set_for_sale(&ctx, &mut logger, &mut state);
+        claim!(result.is_ok(), "Results in rejection");
+
+        // Rate older than the maximum age is not used
+        ctx.set_metadata_slot_time(Timestamp::from_timestamp_millis(111));
+        let result: ContractResult<ActionsTree> =
+            contract_set_for_sale(&ctx, &mut logger, &mut state);
+        claim_eq!(result, Err(CustomContractError::StaleExchangeRate.into()));
+
+        // Oracle can't replace the rate with an older one
+        let old_rate_bytes = to_bytes(&ExchangeRate {
+            currency: Currency::Eur,
+            rate: Amount::from_ccd(1),
+            updated_at: Timestamp::from_timestamp_millis(5),
+        });
+        ctx.set_sender(Address::Contract(ORACLE));
+        ctx.set_parameter(&old_rate_bytes);
+        let result: ContractResult<ActionsTree> = contract_receive_rate(&ctx, &mut state);
+        claim_eq!(result, Err(CustomContractError::StaleExchangeRate.into()));
+
+        // Fresh rate makes the floor checkable again
+        let new_rate_bytes = to_bytes(&ExchangeRate {
+            currency: Currency::Eur,
+            rate: Amount::from_ccd(25),
+            updated_at: Timestamp::from_timestamp_millis(100),
+        });
+        ctx.set_parameter(&new_rate_bytes);
+        let result: ContractResult<ActionsTree> = contract_receive_rate(&ctx, &mut state);
+        claim!(result.is_ok(), "Results in rejection");
+
+        ctx.set_sender(Address::Account(SELLER));
+        ctx.set_parameter(&sale_bytes);
+        let result: ContractResult<ActionsTree> =
+            contract_set_for_sale(&ctx, &mut logger, &mut state);
+        claim!(result.is_ok(), "Results in rejection");
+    }
 }
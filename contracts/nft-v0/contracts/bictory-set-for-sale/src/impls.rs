@@ -6,6 +6,10 @@ impl State {
     pub fn empty() -> Self {
         State {
             token_details: Vec::default(),
+            price_floors: Map::default(),
+            price_oracle: None,
+            rate: None,
+            max_rate_age: Duration::from_millis(0),
         }
     }
 
@@ -24,4 +28,50 @@ impl State {
 
         Ok(())
     }
+
+    /// Check that the price is not below the price floor of the NFT contract.
+    /// Results in an error if the price is lower or the floor is denominated in a
+    /// currency without exchange rate, or with a rate older than `max_rate_age`.
+    pub fn check_price_floor(
+        &self,
+        contract: &ContractAddress,
+        price: Amount,
+        now: Timestamp,
+    ) -> ContractResult<()> {
+        let floor = match self.price_floors.get(contract) {
+            Some(PriceFloor::Ccd(amount)) => *amount,
+            Some(PriceFloor::Currency(currency, amount)) => {
+                let rate = self
+                    .rate
+                    .filter(|rate| rate.currency == *currency)
+                    .ok_or(CustomContractError::MissingExchangeRate)?;
+                // Rates from the future can't be stale
+                let age = now
+                    .timestamp_millis()
+                    .saturating_sub(rate.updated_at.timestamp_millis());
+                ensure!(
+                    age <= self.max_rate_age.millis(),
+                    CustomContractError::StaleExchangeRate.into()
+                );
+                // Floor exceeding any CCD amount can't be met
+                rate.to_ccd(*amount)
+                    .ok_or(CustomContractError::PriceBelowFloor)?
+            }
+            None => return Ok(()),
+        };
+
+        ensure!(price >= floor, CustomContractError::PriceBelowFloor.into());
+        Ok(())
+    }
+}
+
+impl ExchangeRate {
+    /// Convert a price in millionths of the currency unit to CCD. Results in `None` on overflow.
+    pub fn to_ccd(&self, price: Amount) -> Option<Amount> {
+        let micro_ccd = price.micro_ccd as u128 * self.rate.micro_ccd as u128 / 1_000_000;
+        if micro_ccd > u64::MAX as u128 {
+            return None;
+        }
+        Some(Amount::from_micro_ccd(micro_ccd as u64))
+    }
 }
@@ -4,7 +4,10 @@
 use crate::{events::*, structs::*, types::*};
 use commons::*;
 use concordium_cis1::*;
-use concordium_std::{collections::HashSet as Set, *};
+use concordium_std::{
+    collections::{HashMap as Map, HashSet as Set},
+    *,
+};
 
 mod contract;
 mod events;
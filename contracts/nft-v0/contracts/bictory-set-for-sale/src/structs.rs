@@ -12,6 +12,56 @@ pub struct Token {
 #[derive(Serialize, SchemaType)]
 pub struct State {
     pub token_details: Vec<TokenInfo>,
+    /// Minimum listing price per NFT contract
+    pub price_floors: Map<ContractAddress, PriceFloor>,
+    /// Price oracle allowed to send exchange rates
+    pub price_oracle: Option<ContractAddress>,
+    /// Latest exchange rate sent by the price oracle
+    pub rate: Option<ExchangeRate>,
+    /// Maximum age of the exchange rate accepted for price floor checks
+    pub max_rate_age: Duration,
+}
+
+/// Currency price floors can be denominated in, same as in the price oracle.
+#[derive(Debug, Serialize, SchemaType, Clone, Copy, PartialEq, Eq)]
+pub enum Currency {
+    Eur,
+    Usd,
+}
+
+/// Exchange rate of a currency to CCD, as sent by the price oracle.
+#[derive(Debug, Serialize, SchemaType, Clone, Copy, PartialEq, Eq)]
+pub struct ExchangeRate {
+    pub currency: Currency,
+    /// Price of one unit of the currency in CCD.
+    pub rate: Amount,
+    /// Time the rate was last updated.
+    pub updated_at: Timestamp,
+}
+
+/// Minimum price of listings of a collection.
+#[derive(Debug, Serialize, SchemaType, Clone, Copy, PartialEq, Eq)]
+pub enum PriceFloor {
+    /// Price in CCD
+    Ccd(Amount),
+    /// Price in millionths of the currency unit, converted to CCD with the latest exchange rate
+    Currency(Currency, Amount),
+}
+
+#[derive(Debug, Serialize, SchemaType)]
+pub struct SetPriceOracleParams {
+    /// Price oracle sending exchange rates, `None` removes it
+    pub oracle: Option<ContractAddress>,
+    /// Maximum age of the exchange rate accepted for price floor checks
+    pub max_rate_age: Duration,
+}
+
+#[derive(Debug, Serialize, SchemaType)]
+pub struct SetPriceFloorParams {
+    /// NFT contract of the collection
+    pub contract: ContractAddress,
+    /// New price floor, `None` removes it
+    pub floor: Option<PriceFloor>,
 }
 
 #[derive(Debug, SchemaType, Serialize, Clone)]
@@ -51,6 +51,12 @@ pub enum CustomContractError {
     // Raised if auction contract is already authorised to perforn transactions
     // on NFT contract(Error code: -21)
     AlreadyAuthorized,
+    /// Price is below the price floor of the collection (Error code: -22).
+    PriceBelowFloor,
+    /// No exchange rate for the currency of the price floor (Error code: -23).
+    MissingExchangeRate,
+    /// Exchange rate is older than the configured maximum age (Error code: -24).
+    StaleExchangeRate,
 }
 
 /// Mapping the logging errors to CustomContractError.
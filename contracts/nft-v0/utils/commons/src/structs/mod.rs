@@ -1,7 +1,10 @@
 use super::*;
 
 mod account;
+// Shared with the V1 commons, so that there is a single definition of the percentage type
+#[path = "../../../../../../utils/commons/src/structs/percentage.rs"]
+mod percentage;
 mod price;
 mod token;
 
-pub use self::{account::*, price::*, token::*};
+pub use self::{account::*, percentage::*, price::*, token::*};
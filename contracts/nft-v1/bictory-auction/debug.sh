@@ -8,7 +8,16 @@ cargo concordium run init \
 --module debug/mod.wasm \
 --contract "BictoryAuction" \
 --context debug/context.json \
---parameter-json parameters/init.json \
+--out-bin debug/state.bin
+
+# createAuction
+cargo concordium run update \
+--module debug/mod.wasm \
+--contract "BictoryAuction" \
+--func "createAuction" \
+--state-bin debug/state.bin \
+--context debug/context.json \
+--parameter-json parameters/createAuction.json \
 --out-bin debug/state.bin
 
 # bid
@@ -18,5 +27,6 @@ cargo concordium run update \
 --func "bid" \
 --state-bin debug/state.bin \
 --context debug/context.json \
+--parameter-json parameters/auctionId.json \
 --out-bin debug/state.bin \
 --amount 1000000
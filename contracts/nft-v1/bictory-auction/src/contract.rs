@@ -1,38 +1,142 @@
 use super::*;
 
-/// Init function that creates a new auction
-#[init(contract = "BictoryAuction", parameter = "InitParameter")]
+/// Standards supported by this contract.
+const SUPPORTED_STANDARDS: [StandardIdentifier<'static>; 2] = [
+    CIS0_STANDARD_IDENTIFIER,
+    BICTORY_AUTHORITY_STANDARD_IDENTIFIER,
+];
+
+/// Longest metadata URL cached for lifecycle events, so that events carrying the lot details stay within the log size
+/// limit.
+const MAX_METADATA_URL_LENGTH: usize = 200;
+
+/// Init function that creates an auction contract without auctions. Auctions are added with `createAuction`. The
+/// account initializing the contract becomes its admin.
+#[init(contract = "BictoryAuction")]
 fn init<S: HasStateApi>(
     ctx: &impl HasInitContext,
     state_builder: &mut StateBuilder<S>,
 ) -> InitResult<State<S>> {
-    let parameter: InitParameter = ctx.parameter_cursor().get()?;
-    Ok(State::empty(
-        parameter.item,
-        parameter.expiry,
-        state_builder,
-    ))
+    Ok(State::empty(state_builder, ctx.init_origin()))
 }
 
-/// Authorize the auction contract address as the operator of NFT contract's token owner
-#[receive(contract = "BictoryAuction", name = "authorize", mutable)]
-fn authorize<S: HasStateApi>(
+/// Create an auction of an item owned by the sender, who becomes the seller. The auction contract is added as the
+/// operator of the NFT contract and the metadata URL of the item is cached for lifecycle events. Returns the
+/// identifier of the new auction.
+///
+/// Rejects if the sender doesn't own the item, or if the item is already sold in another auction that is not over yet.
+#[receive(
+    contract = "BictoryAuction",
+    name = "createAuction",
+    parameter = "CreateAuctionParams",
+    return_value = "AuctionId",
+    mutable,
+    enable_logger
+)]
+fn create_auction<S: HasStateApi>(
     ctx: &impl HasReceiveContext,
     host: &mut impl HasHost<State<S>, StateApiType = S>,
-) -> ContractResult<()> {
-    let owner = ctx.owner();
-    let sender = ctx.sender();
-    let state = host.state();
-    // Ensuring sender is the contract owner
-    ensure!(sender.matches_account(&owner), ContractError::Unauthorized);
+    logger: &mut impl HasLogger,
+) -> ContractResult<AuctionId> {
+    let seller = match ctx.sender() {
+        Address::Contract(_) => bail!(CustomContractError::OnlyAccountAddress.into()),
+        Address::Account(account_address) => account_address,
+    };
+    let parameter: CreateAuctionParams = ctx.parameter_cursor().get()?;
+    create_auction_of(ctx, host, logger, seller, parameter)
+}
+
+/// Create an auction of an item on behalf of the seller, e.g. by a listing contract moving an unsold listing to the
+/// auction. The sender must be an operator of the seller in the NFT contract, and the auction is then created the
+/// same way as with `createAuction`. Returns the identifier of the new auction.
+///
+/// Rejects if the sender is not an operator of the seller, if the seller doesn't own the item, or if the item is
+/// already sold in another auction that is not over yet.
+#[receive(
+    contract = "BictoryAuction",
+    name = "createAuctionFor",
+    parameter = "CreateAuctionForParams",
+    return_value = "AuctionId",
+    mutable,
+    enable_logger
+)]
+fn create_auction_for<S: HasStateApi>(
+    ctx: &impl HasReceiveContext,
+    host: &mut impl HasHost<State<S>, StateApiType = S>,
+    logger: &mut impl HasLogger,
+) -> ContractResult<AuctionId> {
+    let parameter: CreateAuctionForParams = ctx.parameter_cursor().get()?;
+    ensure!(
+        query_operator(
+            host,
+            &parameter.auction.item,
+            parameter.seller,
+            ctx.sender()
+        )?,
+        CustomContractError::Unauthorized.into()
+    );
+    create_auction_of(ctx, host, logger, parameter.seller, parameter.auction)
+}
 
-    // Ensuring state is not already authorised
+/// Create an auction of an item owned by the seller, shared by `createAuction` and `createAuctionFor`.
+fn create_auction_of<S: HasStateApi>(
+    ctx: &impl HasReceiveContext,
+    host: &mut impl HasHost<State<S>, StateApiType = S>,
+    logger: &mut impl HasLogger,
+    seller: AccountAddress,
+    mut parameter: CreateAuctionParams,
+) -> ContractResult<AuctionId> {
+    ensure!(
+        parameter.bid_granularity > Amount::zero(),
+        CustomContractError::InvalidFields.into()
+    );
+    // Penalty is a share of the highest bid
     ensure!(
-        !state.viewable_state.is_authorised,
-        CustomContractError::AlreadyAuthorized.into()
+        parameter.cancellation_penalty <= Percentage::HUNDRED,
+        CustomContractError::InvalidFields.into()
     );
+    // Seller can only attach CCD to `cancel`, so penalties can't be paid in the payment token
+    ensure!(
+        parameter.payment_token.is_none() || parameter.cancellation_penalty == Percentage::ZERO,
+        CustomContractError::InvalidFields.into()
+    );
+    // Reserve and increment must be reachable with granular bids
+    let granularity = parameter.bid_granularity.micro_ccd;
+    ensure!(
+        parameter.reserve_price.micro_ccd % granularity == 0
+            && parameter.min_increment.micro_ccd % granularity == 0,
+        CustomContractError::InvalidFields.into()
+    );
+    // Auction must accept bids at some point
+    ensure!(
+        parameter
+            .start
+            .map_or(true, |start| start < parameter.expiry),
+        CustomContractError::InvalidFields.into()
+    );
+    if let AuctionKind::Dutch(dutch) = &parameter.kind {
+        // Dutch auctions are bought with attached CCD and the price must not rise
+        ensure!(
+            parameter.payment_token.is_none()
+                && dutch.start_price >= dutch.end_price
+                && dutch.decay != PriceDecay::Stepwise(Duration::from_millis(0)),
+            CustomContractError::InvalidFields.into()
+        );
+        // Price starts falling right away
+        let slot_time = ctx.metadata().slot_time();
+        ensure!(
+            slot_time < parameter.expiry,
+            CustomContractError::InvalidFields.into()
+        );
+        parameter.start.get_or_insert(slot_time);
+    }
 
-    host.state_mut().viewable_state.is_authorised = true;
+    let item = parameter.item.clone();
+    host.state().ensure_not_auctioned(&item)?;
+    ensure!(
+        query_balance(host, &item, seller)? > ContractTokenAmount::from(0),
+        CustomContractError::OnlyOwner.into()
+    );
 
     // Adding this contract as operator to the receiving contract
     let update_operator: UpdateOperatorParams = UpdateOperatorParams(vec![UpdateOperator {
@@ -41,19 +145,134 @@ fn authorize<S: HasStateApi>(
     }]);
     let entrypoint_name = EntrypointName::new_unchecked("updateOperator");
     host.invoke_contract(
-        &host.state().viewable_state.item.contract.clone(),
+        &item.contract,
         &update_operator,
         entrypoint_name,
         Amount::zero(),
     )?;
 
-    Ok(())
+    let metadata_url = query_metadata_url(host, &item);
+    let royalties = query_royalties(host, &item, seller);
+
+    // Royalties must leave a share for the seller, so that settlement can't fail
+    let bictory_royalty = host.state().bictory_royalty(&parameter);
+    let total = royalty_total(&royalties)
+        .and_then(|total| total.checked_add(bictory_royalty))
+        .ok_or(CustomContractError::Overflow)?;
+    ensure!(
+        total <= Percentage::HUNDRED,
+        CustomContractError::InvalidRoyalty.into()
+    );
+
+    let (state, state_builder) = host.state_and_builder();
+    let auction_id = state.create_auction(parameter, seller, state_builder);
+    let mut auction = state.auction_mut(auction_id)?;
+    auction.metadata_url = metadata_url;
+    auction.royalties = royalties;
+
+    // Event for AuctionCreated.
+    logger.log(&CustomEvent::AuctionCreated(
+        auction.lot_details(auction_id),
+    ))?;
+
+    Ok(auction_id)
+}
+
+/// Query the balance of the item held by the account with CIS-2 `balanceOf`.
+fn query_balance<S: HasStateApi>(
+    host: &impl HasHost<State<S>, StateApiType = S>,
+    item: &Token,
+    account: AccountAddress,
+) -> ContractResult<ContractTokenAmount> {
+    let params = ContractBalanceOfQueryParams {
+        queries: vec![BalanceOfQuery {
+            token_id: item.id.clone(),
+            address: Address::Account(account),
+        }],
+    };
+    let entrypoint_name = EntrypointName::new_unchecked("balanceOf");
+    let mut response = host
+        .invoke_contract_read_only(&item.contract, &params, entrypoint_name, Amount::zero())?
+        .ok_or(CustomContractError::InvokeContractError)?;
+    let BalanceOfQueryResponse(balances): ContractBalanceOfQueryResponse = response.get()?;
+    balances
+        .into_iter()
+        .next()
+        .ok_or_else(|| CustomContractError::InvokeContractError.into())
+}
+
+/// Query whether the address is an operator of the account in the NFT contract of the item with CIS-2 `operatorOf`.
+fn query_operator<S: HasStateApi>(
+    host: &impl HasHost<State<S>, StateApiType = S>,
+    item: &Token,
+    owner: AccountAddress,
+    address: Address,
+) -> ContractResult<bool> {
+    let params = OperatorOfQueryParams {
+        queries: vec![OperatorOfQuery {
+            owner: Address::Account(owner),
+            address,
+        }],
+    };
+    let entrypoint_name = EntrypointName::new_unchecked("operatorOf");
+    let mut response = host
+        .invoke_contract_read_only(&item.contract, &params, entrypoint_name, Amount::zero())?
+        .ok_or(CustomContractError::InvokeContractError)?;
+    let OperatorOfQueryResponse(operators) = response.get()?;
+    operators
+        .into_iter()
+        .next()
+        .ok_or_else(|| CustomContractError::InvokeContractError.into())
 }
 
-/// Receive function in which accounts can bid before the auction end time
+/// Query the metadata URL of the item with CIS-2 `tokenMetadata`. NFT contracts that don't implement it report no URL,
+/// so that their items can still be auctioned. URLs longer than `MAX_METADATA_URL_LENGTH` bytes are not cached, since a
+/// truncated URL would be useless.
+fn query_metadata_url<S: HasStateApi>(
+    host: &impl HasHost<State<S>, StateApiType = S>,
+    item: &Token,
+) -> Option<String> {
+    let params = TokenMetadataQueryParams {
+        queries: vec![item.id.clone()],
+    };
+    let entrypoint_name = EntrypointName::new_unchecked("tokenMetadata");
+    let mut response = host
+        .invoke_contract_read_only(&item.contract, &params, entrypoint_name, Amount::zero())
+        .ok()??;
+    let TokenMetadataQueryResponse(urls) = response.get().ok()?;
+    urls.into_iter()
+        .next()
+        .map(|metadata| metadata.url)
+        .filter(|url| url.len() <= MAX_METADATA_URL_LENGTH)
+}
+
+/// Query the royalties recorded in a Bictory NFT with `viewToken`: the creator, minter and additional recipients.
+/// Royalties can't change after minting, so they're read once when the auction is created. Other NFT contracts report
+/// no royalties. Shares of creators and minters that are contracts are left to the seller, since they can't receive
+/// CCD transfers.
+fn query_royalties<S: HasStateApi>(
+    host: &impl HasHost<State<S>, StateApiType = S>,
+    item: &Token,
+    seller: AccountAddress,
+) -> Vec<Royalty> {
+    let params = ViewTokenParams {
+        owner: Address::Account(seller),
+        token_id: item.id.clone(),
+    };
+    let entrypoint_name = EntrypointName::new_unchecked("viewToken");
+    let nft_data: Option<NFTData> = host
+        .invoke_contract_read_only(&item.contract, &params, entrypoint_name, Amount::zero())
+        .ok()
+        .flatten()
+        .and_then(|mut response| response.get().ok());
+    nft_data.map_or_else(Vec::new, |nft_data| nft_data.royalty_recipients())
+}
+
+/// Receive function in which accounts can bid in the auction before its end time
 #[receive(
     contract = "BictoryAuction",
     name = "bid",
+    parameter = "AuctionId",
     mutable,
     enable_logger,
     payable
@@ -64,294 +283,1038 @@ fn bid<S: HasStateApi>(
     amount: Amount,
     logger: &mut impl HasLogger,
 ) -> ContractResult<()> {
-    let state = host.state_mut();
-    match state.viewable_state.auction_state {
-        AuctionState::NotSoldYet => {
-            let slot_time = ctx.metadata().slot_time();
-            ensure!(
-                slot_time <= state.viewable_state.expiry,
-                CustomContractError::AuctionFinished.into()
-            );
+    let sender_address = match ctx.sender() {
+        Address::Contract(_) => bail!(CustomContractError::OnlyAccountAddress.into()),
+        Address::Account(account_address) => account_address,
+    };
+    let auction_id: AuctionId = ctx.parameter_cursor().get()?;
+
+    let slot_time = ctx.metadata().slot_time();
+    let mut auction = host.state_mut().auction_mut(auction_id)?;
+    auction.ensure_ccd_payment()?;
+    let first_bid = auction.place_bid(sender_address, amount, slot_time)?;
+
+    if first_bid {
+        logger.log(&CustomEvent::Participation(ParticipationEvent {
+            item: auction.viewable_state.item.clone(),
+            bidder: sender_address,
+            timestamp: slot_time,
+        }))?;
+    }
 
-            let sender_address = match ctx.sender() {
-                Address::Contract(_) => bail!(CustomContractError::OnlyAccountAddress.into()),
-                Address::Account(account_address) => account_address,
-            };
-            let mut bid_to_update = state
-                .bids
-                .entry(sender_address)
-                .or_insert_with(Amount::zero);
+    // Event for Biding.
+    logger.log(&CustomEvent::Biding(BidingEvent {
+        account: auction.viewable_state.item.clone(),
+        bid: amount,
+    }))?;
 
-            *bid_to_update += amount;
+    extend_auction(&mut auction, slot_time, logger)?;
 
-            // Ensure that the new bid exceeds the highest bid so far
-            ensure!(
-                *bid_to_update > state.viewable_state.highest_bid,
-                CustomContractError::BidTooLow.into()
-            );
-            state.viewable_state.highest_bid = *bid_to_update;
+    Ok(())
+}
 
-            // Event for Biding.
-            logger.log(&CustomEvent::Biding(BidingEvent {
-                account: state.viewable_state.item.clone(),
-                bid: amount,
-            }))?;
-        }
-        AuctionState::Sold(_) => bail!(CustomContractError::AuctionFinalized.into()),
-        AuctionState::Canceled => bail!(CustomContractError::AuctionCanceled.into()),
+/// Receive function in which accounts place proxy bids in the auction before its end time. Attached amount is added
+/// to the maximum the sender is willing to pay, while their bid is only raised as far as needed to beat other bidders.
+/// Any unused part of the maximum can be withdrawn after finalization.
+#[receive(
+    contract = "BictoryAuction",
+    name = "proxyBid",
+    parameter = "AuctionId",
+    mutable,
+    enable_logger,
+    payable
+)]
+fn proxy_bid<S: HasStateApi>(
+    ctx: &impl HasReceiveContext,
+    host: &mut impl HasHost<State<S>, StateApiType = S>,
+    amount: Amount,
+    logger: &mut impl HasLogger,
+) -> ContractResult<()> {
+    let sender_address = match ctx.sender() {
+        Address::Contract(_) => bail!(CustomContractError::OnlyAccountAddress.into()),
+        Address::Account(account_address) => account_address,
+    };
+    let auction_id: AuctionId = ctx.parameter_cursor().get()?;
+
+    let slot_time = ctx.metadata().slot_time();
+    let mut auction = host.state_mut().auction_mut(auction_id)?;
+    auction.ensure_ccd_payment()?;
+    let first_bid = auction.place_proxy_bid(sender_address, amount, slot_time)?;
+
+    if first_bid {
+        logger.log(&CustomEvent::Participation(ParticipationEvent {
+            item: auction.viewable_state.item.clone(),
+            bidder: sender_address,
+            timestamp: slot_time,
+        }))?;
     }
 
+    // Event for Biding. Only the resulting highest bid is logged, the maximum stays hidden.
+    logger.log(&CustomEvent::Biding(BidingEvent {
+        account: auction.viewable_state.item.clone(),
+        bid: auction.viewable_state.highest_bid,
+    }))?;
+
+    extend_auction(&mut auction, slot_time, logger)?;
+
     Ok(())
 }
 
-/// Receive function used to finalize the auction, returning all bids to their
-/// senders, except for the winning bid
-#[receive(contract = "BictoryAuction", name = "finalize", mutable, enable_logger)]
-fn finalize<S: HasStateApi>(
+/// Receive function in which accounts buy the item of a Dutch auction at its current price. The attached amount must
+/// cover the price, any surplus can be withdrawn after the sale. The auction is settled right away.
+#[receive(
+    contract = "BictoryAuction",
+    name = "buy",
+    parameter = "AuctionId",
+    mutable,
+    enable_logger,
+    payable
+)]
+fn buy<S: HasStateApi>(
     ctx: &impl HasReceiveContext,
     host: &mut impl HasHost<State<S>, StateApiType = S>,
+    amount: Amount,
     logger: &mut impl HasLogger,
 ) -> ContractResult<()> {
-    let state = host.state();
-    let contract = state.viewable_state.item.contract;
+    let buyer = match ctx.sender() {
+        Address::Contract(_) => bail!(CustomContractError::OnlyAccountAddress.into()),
+        Address::Account(account_address) => account_address,
+    };
+    let auction_id: AuctionId = ctx.parameter_cursor().get()?;
+
+    let slot_time = ctx.metadata().slot_time();
+    let mut auction = host.state_mut().auction_mut(auction_id)?;
+    let price = auction.buy(buyer, amount, slot_time)?;
+
+    logger.log(&CustomEvent::Participation(ParticipationEvent {
+        item: auction.viewable_state.item.clone(),
+        bidder: buyer,
+        timestamp: slot_time,
+    }))?;
+
+    // Event for Biding.
+    logger.log(&CustomEvent::Biding(BidingEvent {
+        account: auction.viewable_state.item.clone(),
+        bid: price,
+    }))?;
+
+    // Event for Finalize.
+    logger.log(&CustomEvent::Finalize(auction.viewable_state.item.clone()))?;
+    drop(auction);
+
+    settle(ctx, host, logger, auction_id, buyer, slot_time)
+}
 
-    // Ensuring contract is authorised to perform transaction on NFT contract
+/// Receive function in which accounts bid with the payment token of an auction. Bidders transfer the tokens to the
+/// auction contract with `TokenBidData` as the transfer data, the transferred amount is then bid in the selected
+/// auction the same way as with `bid` or `proxyBid`. Only transfers of the payment token of the auction are accepted.
+#[receive(
+    contract = "BictoryAuction",
+    name = "onReceivingCIS2",
+    parameter = "OnReceivingCis2Params<ContractTokenId, ContractTokenAmount>",
+    mutable,
+    enable_logger
+)]
+fn on_receiving_cis2<S: HasStateApi>(
+    ctx: &impl HasReceiveContext,
+    host: &mut impl HasHost<State<S>, StateApiType = S>,
+    logger: &mut impl HasLogger,
+) -> ContractResult<()> {
+    let params: OnReceivingCis2Params<ContractTokenId, ContractTokenAmount> =
+        ctx.parameter_cursor().get()?;
+    let data: TokenBidData = from_bytes(params.data.as_ref())?;
+
+    let slot_time = ctx.metadata().slot_time();
+    let mut auction = host.state_mut().auction_mut(data.auction_id)?;
+    let payment_token = auction
+        .viewable_state
+        .payment_token
+        .clone()
+        .ok_or(CustomContractError::OperationNotPermitted)?;
+
+    // Ensuring the tokens were transferred by the payment token contract
     ensure!(
-        state.viewable_state.is_authorised,
+        ctx.sender() == Address::Contract(payment_token.contract),
         ContractError::Unauthorized
     );
+    ensure!(
+        params.token_id == payment_token.id,
+        ContractError::InvalidTokenId
+    );
+
+    let bidder = match params.from {
+        Address::Contract(_) => bail!(CustomContractError::OnlyAccountAddress.into()),
+        Address::Account(account_address) => account_address,
+    };
+
+    let amount = Amount::from_micro_ccd(params.amount.0);
+    let first_bid = match data.kind {
+        TokenBid::Bid => auction.place_bid(bidder, amount, slot_time)?,
+        TokenBid::ProxyBid => auction.place_proxy_bid(bidder, amount, slot_time)?,
+    };
+
+    if first_bid {
+        logger.log(&CustomEvent::Participation(ParticipationEvent {
+            item: auction.viewable_state.item.clone(),
+            bidder,
+            timestamp: slot_time,
+        }))?;
+    }
+
+    // Event for Biding. Proxy bids only log the resulting highest bid.
+    logger.log(&CustomEvent::Biding(BidingEvent {
+        account: auction.viewable_state.item.clone(),
+        bid: match data.kind {
+            TokenBid::Bid => amount,
+            TokenBid::ProxyBid => auction.viewable_state.highest_bid,
+        },
+    }))?;
+
+    extend_auction(&mut auction, slot_time, logger)?;
+
+    Ok(())
+}
+
+/// Register Ed25519 public key of the sender account, that is used to verify bids relayed on behalf of the account.
+/// Registering a new key replaces the previous one.
+#[receive(
+    contract = "BictoryAuction",
+    name = "registerBidKey",
+    parameter = "[u8; 32]",
+    mutable
+)]
+fn register_bid_key<S: HasStateApi>(
+    ctx: &impl HasReceiveContext,
+    host: &mut impl HasHost<State<S>, StateApiType = S>,
+) -> ContractResult<()> {
+    let sender_address = match ctx.sender() {
+        Address::Contract(_) => bail!(CustomContractError::OnlyAccountAddress.into()),
+        Address::Account(account_address) => account_address,
+    };
+    let public_key: [u8; 32] = ctx.parameter_cursor().get()?;
+
+    host.state_mut().bid_keys.insert(sender_address, public_key);
+
+    Ok(())
+}
+
+/// Receive function in which a relayer submits bids signed by bidders. Attached amount must cover the sum of all bid
+/// amounts, surplus is returned to the invoker. Each bid is credited to the bidder in the auction selected by the
+/// bidder the same way as with `bid`.
+#[receive(
+    contract = "BictoryAuction",
+    name = "relayBids",
+    parameter = "RelayBidsParams",
+    mutable,
+    enable_logger,
+    payable,
+    crypto_primitives
+)]
+fn relay_bids<S: HasStateApi>(
+    ctx: &impl HasReceiveContext,
+    host: &mut impl HasHost<State<S>, StateApiType = S>,
+    amount: Amount,
+    logger: &mut impl HasLogger,
+    crypto_primitives: &impl HasCryptoPrimitives,
+) -> ContractResult<()> {
+    let params: RelayBidsParams = ctx.parameter_cursor().get()?;
+    let slot_time = ctx.metadata().slot_time();
+
+    let total = params.bids.iter().fold(Amount::zero(), |acc, signed_bid| {
+        acc + signed_bid.intent.amount
+    });
+    ensure!(
+        amount >= total,
+        CustomContractError::InsufficientFunds.into()
+    );
+
+    for signed_bid in params.bids {
+        let intent = signed_bid.intent;
+        let state = host.state_mut();
+
+        let public_key = *state
+            .bid_keys
+            .get(&intent.bidder)
+            .ok_or(CustomContractError::InvalidSignature)?;
+
+        // Signature is bound to this auction contract to prevent replaying it on other instances
+        let message = to_bytes(&(ctx.self_address(), intent));
+        ensure!(
+            crypto_primitives.verify_ed25519_signature(
+                PublicKeyEd25519(public_key),
+                SignatureEd25519(signed_bid.signature),
+                &message
+            ),
+            CustomContractError::InvalidSignature.into()
+        );
+
+        state.use_nonce(intent.bidder, intent.nonce)?;
+        let mut auction = state.auction_mut(intent.auction_id)?;
+        auction.ensure_ccd_payment()?;
+        let first_bid = auction.place_bid(intent.bidder, intent.amount, slot_time)?;
+
+        if first_bid {
+            logger.log(&CustomEvent::Participation(ParticipationEvent {
+                item: auction.viewable_state.item.clone(),
+                bidder: intent.bidder,
+                timestamp: slot_time,
+            }))?;
+        }
+
+        // Event for Biding.
+        logger.log(&CustomEvent::Biding(BidingEvent {
+            account: auction.viewable_state.item.clone(),
+            bid: intent.amount,
+        }))?;
+
+        extend_auction(&mut auction, slot_time, logger)?;
+    }
+
+    // Return surplus to the relayer
+    if amount > total {
+        host.invoke_transfer(&ctx.invoker(), amount - total)?;
+    }
+
+    Ok(())
+}
+
+/// Extend the auction if the bid was placed close to its end.
+fn extend_auction<S: HasStateApi>(
+    auction: &mut AuctionData<S>,
+    slot_time: Timestamp,
+    logger: &mut impl HasLogger,
+) -> ContractResult<()> {
+    if let Some(expiry) = auction.extend_expiry(slot_time) {
+        logger.log(&CustomEvent::AuctionExtended(AuctionExtendedEvent {
+            item: auction.viewable_state.item.clone(),
+            expiry,
+        }))?;
+    }
+    Ok(())
+}
+
+/// Receive function used to finalize an auction, delivering the item to the
+/// winner and paying the winning bid to the seller. Other bids are withdrawn by
+/// their senders with `withdraw`. If no bids were placed or the reserve price
+/// was not met, the auction is canceled, leaving the item with the seller.
+#[receive(
+    contract = "BictoryAuction",
+    name = "finalize",
+    parameter = "AuctionId",
+    mutable,
+    enable_logger
+)]
+fn finalize<S: HasStateApi>(
+    ctx: &impl HasReceiveContext,
+    host: &mut impl HasHost<State<S>, StateApiType = S>,
+    logger: &mut impl HasLogger,
+) -> ContractResult<()> {
+    let auction_id: AuctionId = ctx.parameter_cursor().get()?;
+    let slot_time = ctx.metadata().slot_time();
+    let mut auction = host.state_mut().auction_mut(auction_id)?;
 
-    match state.viewable_state.auction_state {
+    match auction.viewable_state.auction_state {
         AuctionState::NotSoldYet => {
-            let slot_time = ctx.metadata().slot_time();
             ensure!(
-                slot_time > state.viewable_state.expiry,
+                slot_time > auction.viewable_state.expiry,
                 CustomContractError::AuctionStillActive.into()
             );
 
-            let owner = ctx.owner();
-            let balance = host.self_balance();
-
             // Event for Finalize.
-            logger.log(&CustomEvent::Finalize(state.viewable_state.item.clone()))?;
-
-            if balance == Amount::zero() {
-                Ok(())
-            } else {
-                host.invoke_transfer(&owner, state.viewable_state.highest_bid)?;
-                let mut remaining_bid = None;
-
-                // Return bids that are smaller than highest
-                for (addr, amnt) in state.bids.iter() {
-                    if *amnt < state.viewable_state.highest_bid {
-                        host.invoke_transfer(&addr, *amnt)?;
-                    } else {
-                        ensure!(
-                            remaining_bid.is_none(),
-                            CustomContractError::BidMapError.into()
-                        );
-                        remaining_bid = Some((addr, amnt));
-                    }
-                }
+            logger.log(&CustomEvent::Finalize(auction.viewable_state.item.clone()))?;
 
-                // Ensure that the only bidder left in the map is the one with the highest bid
-                match remaining_bid {
-                    Some((addr, amount)) => {
-                        ensure!(
-                            amount.eq(&state.viewable_state.highest_bid),
-                            CustomContractError::BidMapError.into()
-                        );
-
-                        // Transfer token to highest bidder
-                        let transfer = Transfer {
-                            token_id: state.viewable_state.item.id.clone(),
-                            amount: ContractTokenAmount::from(1),
-                            from: Address::Account(owner),
-                            to: Receiver::Account(*addr),
-                            data: AdditionalData::empty(),
-                        };
-                        let parameter = TransferParams(vec![transfer]);
-                        let mut entrypoint_name = EntrypointName::new_unchecked("transfer");
-
-                        host.state_mut().viewable_state.auction_state = AuctionState::Sold(*addr);
-
-                        host.invoke_contract(
-                            &contract,
-                            &parameter,
-                            entrypoint_name,
-                            Amount::zero(),
-                        )?;
-
-                        // Removing this contract as operator to the receiving contract
-                        let update_operator: UpdateOperatorParams =
-                            UpdateOperatorParams(vec![UpdateOperator {
-                                update: OperatorUpdate::Remove,
-                                operator: Address::Contract(ctx.self_address()),
-                            }]);
-                        entrypoint_name = EntrypointName::new_unchecked("updateOperator");
-                        host.invoke_contract(
-                            &contract,
-                            &update_operator,
-                            entrypoint_name,
-                            Amount::zero(),
-                        )?;
-
-                        Ok(())
-                    }
-                    None => bail!(CustomContractError::BidMapError.into()),
+            let winner = match auction.highest_bidder {
+                Some(winner) => winner,
+                None => {
+                    auction.viewable_state.auction_state = AuctionState::Canceled;
+                    return Ok(());
                 }
+            };
+
+            if !auction.reserve_met() {
+                // Item is never transferred to the auction, so the seller keeps it
+                logger.log(&CustomEvent::Cancel(auction.viewable_state.item.clone()))?;
+                auction.viewable_state.auction_state = AuctionState::Canceled;
+                return Ok(());
             }
+            drop(auction);
+
+            settle(ctx, host, logger, auction_id, winner, slot_time)
+        }
+        AuctionState::Sold(_) | AuctionState::AwaitingDelivery(_) => {
+            bail!(CustomContractError::AuctionFinalized.into())
         }
-        AuctionState::Sold(_) => bail!(CustomContractError::AuctionFinalized.into()),
         AuctionState::Canceled => bail!(CustomContractError::AuctionCanceled.into()),
     }
 }
 
-/// Receive function used to cancel the auction, returning all bids to their
-/// senders
-#[receive(contract = "BictoryAuction", name = "cancel", mutable, enable_logger)]
+/// Receive function used by the seller to cancel an auction, after which
+/// bidders withdraw their deposits with `withdraw`. If bids were placed, the
+/// seller has to attach the cancellation penalty, which is paid to the highest
+/// bidder. Any surplus is returned.
+#[receive(
+    contract = "BictoryAuction",
+    name = "cancel",
+    parameter = "AuctionId",
+    mutable,
+    enable_logger,
+    payable
+)]
 fn cancel<S: HasStateApi>(
     ctx: &impl HasReceiveContext,
     host: &mut impl HasHost<State<S>, StateApiType = S>,
+    amount: Amount,
     logger: &mut impl HasLogger,
 ) -> ContractResult<()> {
-    let owner = ctx.owner();
-    let sender = ctx.sender();
-    let state = host.state();
-
-    // Ensuring sender is the contract owner
-    ensure!(sender.matches_account(&owner), ContractError::Unauthorized);
+    let auction_id: AuctionId = ctx.parameter_cursor().get()?;
+    let mut auction = host.state_mut().auction_mut(auction_id)?;
+    let seller = auction.viewable_state.seller;
 
-    // Ensuring contract is authorised to perform transaction on NFT contract
+    // Ensuring sender is the seller
     ensure!(
-        state.viewable_state.is_authorised,
+        ctx.sender().matches_account(&seller),
         ContractError::Unauthorized
     );
 
-    match state.viewable_state.auction_state {
+    match auction.viewable_state.auction_state {
         AuctionState::NotSoldYet => {
-            let balance = host.self_balance();
+            let highest_bid = auction.viewable_state.highest_bid;
+
+            // Penalty is zero if no bids were placed
+            let penalty = auction.viewable_state.cancellation_penalty * highest_bid;
+            ensure!(
+                amount >= penalty,
+                CustomContractError::InsufficientFunds.into()
+            );
 
             // Event for Cancel.
-            logger.log(&CustomEvent::Cancel(state.viewable_state.item.clone()))?;
-
-            if balance == Amount::zero() {
-                Ok(())
-            } else {
-                // Return bids
-                for (addr, amnt) in state.bids.iter() {
-                    host.invoke_transfer(&addr, *amnt)?;
-                }
+            logger.log(&CustomEvent::Cancel(auction.viewable_state.item.clone()))?;
 
-                // Removing this contract as operator to the receiving contract
-                let update_operator: UpdateOperatorParams =
-                    UpdateOperatorParams(vec![UpdateOperator {
-                        update: OperatorUpdate::Remove,
-                        operator: Address::Contract(ctx.self_address()),
-                    }]);
-                let entrypoint_name = EntrypointName::new_unchecked("updateOperator");
-
-                // Update auction state
-                host.state_mut().viewable_state.auction_state = AuctionState::Canceled;
-
-                host.invoke_contract(
-                    &host.state().viewable_state.item.contract.clone(),
-                    &update_operator,
-                    entrypoint_name,
-                    Amount::zero(),
-                )?;
-
-                Ok(())
+            // Deposits are withdrawn by the bidders, the highest bidder additionally gets the penalty
+            let penalty_receiver = auction.highest_bidder;
+            let item = auction.viewable_state.item.clone();
+            auction.viewable_state.auction_state = AuctionState::Canceled;
+            drop(auction);
+
+            // Return surplus to the seller
+            if amount > penalty {
+                host.invoke_transfer(&seller, amount - penalty)?;
+            }
+
+            if let Some(bidder) = penalty_receiver {
+                if penalty > Amount::zero() {
+                    host.invoke_transfer(&bidder, penalty)?;
+
+                    // Event for Cancellation Penalty.
+                    logger.log(&CustomEvent::CancellationPenalty(
+                        CancellationPenaltyEvent {
+                            item,
+                            bidder,
+                            penalty,
+                        },
+                    ))?;
+                }
             }
+
+            Ok(())
+        }
+        AuctionState::Sold(_) | AuctionState::AwaitingDelivery(_) => {
+            bail!(CustomContractError::AuctionFinalized.into())
         }
-        AuctionState::Sold(_) => bail!(CustomContractError::AuctionFinalized.into()),
         AuctionState::Canceled => bail!(CustomContractError::AuctionCanceled.into()),
     }
 }
 
-/// View function that returns the contents of the state except the map of
-/// individual bids.
+/// Receive function used to retry the delivery of the item after the NFT contract rejected the transfer on
+/// finalization, e.g. because the seller revoked the operator rights of the auction contract. Once the item is
+/// delivered, the winning bid is transferred to the seller. Can be called by anyone.
 #[receive(
     contract = "BictoryAuction",
-    name = "view",
-    return_value = "ViewableState"
+    name = "redeliver",
+    parameter = "AuctionId",
+    mutable,
+    enable_logger
 )]
-fn view<S: HasStateApi>(
-    _ctx: &impl HasReceiveContext,
-    host: &impl HasHost<State<S>, StateApiType = S>,
-) -> ReceiveResult<ViewableState> {
-    Ok(host.state().viewable_state.clone())
+fn redeliver<S: HasStateApi>(
+    ctx: &impl HasReceiveContext,
+    host: &mut impl HasHost<State<S>, StateApiType = S>,
+    logger: &mut impl HasLogger,
+) -> ContractResult<()> {
+    let auction_id: AuctionId = ctx.parameter_cursor().get()?;
+    let auction = host.state().auction(auction_id)?;
+    let winner = match auction.viewable_state.auction_state {
+        AuctionState::AwaitingDelivery(winner) => winner,
+        _ => bail!(CustomContractError::OperationNotPermitted.into()),
+    };
+    let item = auction.viewable_state.item.clone();
+    let seller = auction.viewable_state.seller;
+    drop(auction);
+
+    deliver_item(host, &item, seller, winner)?;
+    complete_sale(
+        ctx,
+        host,
+        logger,
+        auction_id,
+        winner,
+        ctx.metadata().slot_time(),
+    )
 }
 
-#[concordium_cfg_test]
-mod tests {
-    use super::*;
-    use test_infrastructure::*;
+/// Receive function used by the winner to take back the deposit if the item could not be delivered. The auction is
+/// canceled afterwards.
+#[receive(
+    contract = "BictoryAuction",
+    name = "claimBid",
+    parameter = "AuctionId",
+    mutable,
+    enable_logger
+)]
+fn claim_bid<S: HasStateApi>(
+    ctx: &impl HasReceiveContext,
+    host: &mut impl HasHost<State<S>, StateApiType = S>,
+    logger: &mut impl HasLogger,
+) -> ContractResult<()> {
+    let auction_id: AuctionId = ctx.parameter_cursor().get()?;
+    let mut auction = host.state_mut().auction_mut(auction_id)?;
+    let winner = match auction.viewable_state.auction_state {
+        AuctionState::AwaitingDelivery(winner) => winner,
+        _ => bail!(CustomContractError::OperationNotPermitted.into()),
+    };
+
+    // Ensuring sender is the winner
+    ensure!(
+        ctx.sender().matches_account(&winner),
+        ContractError::Unauthorized
+    );
 
-    // A counter for generating new account addresses
-    const AUCTION_END: u64 = 1;
-    const ACCOUNT_0: AccountAddress = AccountAddress([0u8; 32]);
-    const ACCOUNT_1: AccountAddress = AccountAddress([1u8; 32]);
-    const ACCOUNT_2: AccountAddress = AccountAddress([2u8; 32]);
+    // Event for Cancel.
+    logger.log(&CustomEvent::Cancel(auction.viewable_state.item.clone()))?;
 
-    fn token_0() -> ContractTokenId {
-        concordium_cis2::TokenIdVec(vec![0, 1])
-    }
+    let deposit = auction.deposit(&winner);
+    let payment_token = auction.viewable_state.payment_token.clone();
+    auction.bids.remove(&winner);
+    auction.proxy_max.remove(&winner);
+    auction.viewable_state.auction_state = AuctionState::Canceled;
+    drop(auction);
 
-    fn dummy_fresh_state<S: HasStateApi>(state_builder: &mut StateBuilder<S>) -> State<S> {
-        dummy_active_state(Amount::zero(), state_builder)
-    }
+    pay(ctx, host, &payment_token, &winner, deposit)
+}
 
-    fn dummy_token() -> Token {
-        Token {
-            contract: ContractAddress {
-                index: 1,
-                subindex: 0,
-            },
-            id: token_0(),
-        }
-    }
+/// Receive function used by bidders to take back their deposits once an auction is finalized or canceled. Bidders
+/// withdraw individually, so that a large number of bidders or a failing transfer can't block the settlement.
+#[receive(
+    contract = "BictoryAuction",
+    name = "withdraw",
+    parameter = "AuctionId",
+    mutable
+)]
+fn withdraw<S: HasStateApi>(
+    ctx: &impl HasReceiveContext,
+    host: &mut impl HasHost<State<S>, StateApiType = S>,
+) -> ContractResult<()> {
+    let sender_address = match ctx.sender() {
+        Address::Contract(_) => bail!(CustomContractError::OnlyAccountAddress.into()),
+        Address::Account(account_address) => account_address,
+    };
+    let auction_id: AuctionId = ctx.parameter_cursor().get()?;
+
+    let mut auction = host.state_mut().auction_mut(auction_id)?;
+    let refund = auction.withdraw(sender_address)?;
+    let payment_token = auction.viewable_state.payment_token.clone();
+    drop(auction);
+
+    pay(ctx, host, &payment_token, &sender_address, refund)
+}
 
-    fn dummy_active_state<S: HasStateApi>(
-        highest: Amount,
-        state_builder: &mut StateBuilder<S>,
-    ) -> State<S> {
-        State {
-            viewable_state: ViewableState {
-                auction_state: AuctionState::NotSoldYet,
-                highest_bid: highest,
-                item: dummy_token(),
-                expiry: Timestamp::from_timestamp_millis(AUCTION_END),
-                is_authorised: false,
-            },
-            bids: state_builder.new_map(),
-        }
+/// Transfer the item from the seller to the winner.
+/// Deliver the item to the winner and complete the sale. The winning bid stays in the contract until the item is
+/// delivered. A rejected transfer must not block the withdrawals of other bidders, so the auction waits for
+/// redelivery instead.
+fn settle<S: HasStateApi>(
+    ctx: &impl HasReceiveContext,
+    host: &mut impl HasHost<State<S>, StateApiType = S>,
+    logger: &mut impl HasLogger,
+    auction_id: AuctionId,
+    winner: AccountAddress,
+    slot_time: Timestamp,
+) -> ContractResult<()> {
+    let auction = host.state().auction(auction_id)?;
+    let item = auction.viewable_state.item.clone();
+    let seller = auction.viewable_state.seller;
+    drop(auction);
+
+    if deliver_item(host, &item, seller, winner).is_ok() {
+        complete_sale(ctx, host, logger, auction_id, winner, slot_time)
+    } else {
+        host.state_mut()
+            .auction_mut(auction_id)?
+            .viewable_state
+            .auction_state = AuctionState::AwaitingDelivery(winner);
+        Ok(())
     }
+}
 
-    fn expect_error<E, T>(expr: Result<T, E>, err: E, msg: &str)
-    where
-        E: Eq + Debug,
-        T: Debug,
-    {
-        let actual = expr.expect_err(msg);
-        assert_eq!(actual, err);
-    }
+fn deliver_item<S: HasStateApi>(
+    host: &mut impl HasHost<State<S>, StateApiType = S>,
+    item: &Token,
+    seller: AccountAddress,
+    winner: AccountAddress,
+) -> ContractResult<()> {
+    let transfer = Transfer {
+        token_id: item.id.clone(),
+        amount: ContractTokenAmount::from(1),
+        from: Address::Account(seller),
+        to: Receiver::Account(winner),
+        data: AdditionalData::empty(),
+    };
+    let parameter = TransferParams(vec![transfer]);
+    let entrypoint_name = EntrypointName::new_unchecked("transfer");
+
+    host.invoke_contract(&item.contract, &parameter, entrypoint_name, Amount::zero())?;
 
-    fn item_expiry_parameter() -> InitParameter {
-        InitParameter {
-            item: dummy_token(),
-            expiry: Timestamp::from_timestamp_millis(AUCTION_END),
+    Ok(())
+}
+
+/// Split the winning bid between the seller, royalty receivers and Bictory after the item was delivered and publish
+/// the final price.
+fn complete_sale<S: HasStateApi>(
+    ctx: &impl HasReceiveContext,
+    host: &mut impl HasHost<State<S>, StateApiType = S>,
+    logger: &mut impl HasLogger,
+    auction_id: AuctionId,
+    winner: AccountAddress,
+    slot_time: Timestamp,
+) -> ContractResult<()> {
+    let mut auction = host.state_mut().auction_mut(auction_id)?;
+    auction.viewable_state.auction_state = AuctionState::Sold(winner);
+    let viewable_state = auction.viewable_state.clone();
+    let royalties = auction.royalties.clone();
+
+    // Event for AuctionSettled.
+    logger.log(&CustomEvent::AuctionSettled(AuctionSettledEvent {
+        lot: auction.lot_details(auction_id),
+        winner,
+        price: viewable_state.highest_bid,
+    }))?;
+    drop(auction);
+
+    // Calculating shares
+    let shares = settle_shares(
+        viewable_state.highest_bid,
+        &royalties,
+        viewable_state.bictory_royalty,
+    )?;
+
+    let payment_token = &viewable_state.payment_token;
+    pay(
+        ctx,
+        host,
+        payment_token,
+        &viewable_state.seller,
+        shares.owner,
+    )?;
+    // Royalties can be `0` thereby avoiding unnecessary gas fees.
+    for (beneficiary, share) in shares.royalties.iter() {
+        if *share > Amount::zero() {
+            pay(ctx, host, payment_token, beneficiary, *share)?;
         }
     }
+    if shares.bictory > Amount::zero() {
+        // Hardcoding address of config smart contract for security reasons.
+        // In a rare case of Bictory's wallet address change this contract address must
+        // also be required to be updated.
+        let bictory_config_contract_address = ContractAddress {
+            index: 571,
+            subindex: 0,
+        };
+        let entrypoint_name = EntrypointName::new_unchecked("sendCCD");
+        host.invoke_contract(
+            &bictory_config_contract_address,
+            &"",
+            entrypoint_name,
+            shares.bictory,
+        )?;
+    }
 
-    fn create_parameter_bytes(parameter: &InitParameter) -> Vec<u8> {
-        to_bytes(parameter)
+    if let Some(registry) = host.state().config.price_registry.clone() {
+        let sale = SalePrice {
+            price: viewable_state.highest_bid,
+            buyer: winner,
+            sold_at: slot_time,
+            auction: ctx.self_address(),
+            auction_id,
+        };
+        // Latest sale of the token replaces the previous one. Publication is best-effort, so that a failing registry
+        // can't revert a completed sale.
+        let _ = host.storage_upsert(
+            &registry.contract,
+            registry.prefix.as_ref(),
+            &viewable_state.item,
+            &sale,
+        );
     }
 
-    fn parametrized_init_ctx<'a>(parameter_bytes: &'a Vec<u8>) -> TestInitContext<'a> {
-        let mut ctx = TestInitContext::empty();
-        ctx.set_parameter(parameter_bytes);
-        ctx
+    Ok(())
+}
+
+/// Pay the amount from the auction contract to the account in the currency of the auction: CCD, or the payment token
+/// with a CIS-2 transfer.
+fn pay<S: HasStateApi>(
+    ctx: &impl HasReceiveContext,
+    host: &mut impl HasHost<State<S>, StateApiType = S>,
+    payment_token: &Option<Token>,
+    to: &AccountAddress,
+    amount: Amount,
+) -> ContractResult<()> {
+    match payment_token {
+        None => host.invoke_transfer(to, amount)?,
+        Some(token) => {
+            let transfer = Transfer {
+                token_id: token.id.clone(),
+                amount: ContractTokenAmount::from(amount.micro_ccd),
+                from: Address::Contract(ctx.self_address()),
+                to: Receiver::Account(*to),
+                data: AdditionalData::empty(),
+            };
+            let parameter = TransferParams(vec![transfer]);
+            let entrypoint_name = EntrypointName::new_unchecked("transfer");
+            host.invoke_contract(&token.contract, &parameter, entrypoint_name, Amount::zero())?;
+        }
+    }
+    Ok(())
+}
+
+/// View function that returns the contents of an auction except the map of
+/// individual bids, together with the phase of the auction at the current time.
+#[receive(
+    contract = "BictoryAuction",
+    name = "view",
+    parameter = "AuctionId",
+    return_value = "AuctionView"
+)]
+fn view<S: HasStateApi>(
+    ctx: &impl HasReceiveContext,
+    host: &impl HasHost<State<S>, StateApiType = S>,
+) -> ContractResult<AuctionView> {
+    let auction_id: AuctionId = ctx.parameter_cursor().get()?;
+    let auction = host.state().auction(auction_id)?;
+    Ok(AuctionView {
+        auction_id,
+        phase: auction.phase(ctx.metadata().slot_time()),
+        state: auction.viewable_state.clone(),
+    })
+}
+
+/// View auctions in the order of creation. Since return value size is limited, `skip` and `show` select the part of
+/// the auctions to return.
+#[receive(
+    contract = "BictoryAuction",
+    name = "viewAuctions",
+    parameter = "ViewAuctionsParams",
+    return_value = "Vec<AuctionView>"
+)]
+fn view_auctions<S: HasStateApi>(
+    ctx: &impl HasReceiveContext,
+    host: &impl HasHost<State<S>, StateApiType = S>,
+) -> ContractResult<Vec<AuctionView>> {
+    let params: ViewAuctionsParams = ctx.parameter_cursor().get()?;
+    let slot_time = ctx.metadata().slot_time();
+
+    Ok(host
+        .state()
+        .auctions
+        .iter()
+        .skip(params.skip as usize)
+        .take(params.show as usize)
+        .map(|(auction_id, auction)| AuctionView {
+            auction_id: *auction_id,
+            phase: auction.phase(slot_time),
+            state: auction.viewable_state.clone(),
+        })
+        .collect())
+}
+
+/// Set the storage the final price of every sale is published to, `None` to stop publishing prices. Applies to sales of
+/// existing auctions too.
+///
+/// Rejects if:
+/// - Sender doesn't have admin rights.
+/// - It fails to parse the parameter.
+#[receive(
+    contract = "BictoryAuction",
+    name = "setPriceRegistry",
+    parameter = "Option<PriceRegistry>",
+    mutable
+)]
+fn set_price_registry<S: HasStateApi>(
+    ctx: &impl HasReceiveContext,
+    host: &mut impl HasHost<State<S>, StateApiType = S>,
+) -> ContractResult<()> {
+    let price_registry: Option<PriceRegistry> = ctx.parameter_cursor().get()?;
+    let state = host.state_mut();
+
+    ensure!(
+        state.authority.has_admin_rights(&ctx.sender()),
+        ContractError::Unauthorized
+    );
+
+    state.config.price_registry = price_registry;
+    Ok(())
+}
+
+/// Set the share of the winning bid paid to Bictory in auctions created afterwards. Existing auctions keep the share
+/// they were created with.
+///
+/// Rejects if:
+/// - Sender doesn't have admin rights.
+/// - It fails to parse the parameter.
+/// - The share exceeds 100%.
+#[receive(
+    contract = "BictoryAuction",
+    name = "setBictoryRoyalty",
+    parameter = "Percentage",
+    mutable
+)]
+fn set_bictory_royalty<S: HasStateApi>(
+    ctx: &impl HasReceiveContext,
+    host: &mut impl HasHost<State<S>, StateApiType = S>,
+) -> ContractResult<()> {
+    let bictory_royalty: Percentage = ctx.parameter_cursor().get()?;
+    let state = host.state_mut();
+
+    ensure!(
+        state.authority.has_admin_rights(&ctx.sender()),
+        ContractError::Unauthorized
+    );
+    ensure!(
+        bictory_royalty <= Percentage::HUNDRED,
+        CustomContractError::InvalidFields.into()
+    );
+
+    state.config.bictory_royalty = bictory_royalty;
+    Ok(())
+}
+
+/// View the configuration of the auction contract.
+#[receive(
+    contract = "BictoryAuction",
+    name = "viewConfig",
+    return_value = "AuctionConfig"
+)]
+fn view_config<S: HasStateApi>(
+    _ctx: &impl HasReceiveContext,
+    host: &impl HasHost<State<S>, StateApiType = S>,
+) -> ContractResult<AuctionConfig> {
+    Ok(host.state().config.clone())
+}
+
+/// Function to manage addresses that are allowed to maintain and modify the state of the contract.
+///
+///  It rejects if:
+///  - Fails to parse `AuthorityUpdateParams` parameters.
+///  - If sender is neither one of the admins nor one of the maintainers.
+#[receive(
+    mutable,
+    contract = "BictoryAuction",
+    name = "updateAuthority",
+    parameter = "AuthorityUpdateParams"
+)]
+fn update_authority<S: HasStateApi>(
+    ctx: &impl HasReceiveContext,
+    host: &mut impl HasHost<State<S>, StateApiType = S>,
+) -> ReceiveResult<()> {
+    let state = host.state_mut();
+    let params = AuthorityUpdateParams::deserial(&mut ctx.parameter_cursor())?;
+    let sender = ctx.sender();
+    state.authority.handle_update(sender, params)
+}
+
+/// Function to view addresses that are allowed to maintain and modify the state of the contract.
+#[receive(
+    contract = "BictoryAuction",
+    name = "viewAuthority",
+    parameter = "AuthorityViewParams",
+    return_value = "Vec<Address>"
+)]
+fn view_authority<S: HasStateApi>(
+    ctx: &impl HasReceiveContext,
+    host: &impl HasHost<State<S>, StateApiType = S>,
+) -> ReceiveResult<Vec<Address>> {
+    let params = AuthorityViewParams::deserial(&mut ctx.parameter_cursor())?;
+    Ok(host.state().authority.handle_view(params))
+}
+
+/// Check which standards the contract supports (CIS-0).
+///
+/// It rejects if:
+/// - It fails to parse the parameter.
+#[receive(
+    contract = "BictoryAuction",
+    name = "supports",
+    parameter = "SupportsQueryParams",
+    return_value = "SupportsQueryResponse"
+)]
+fn supports<S: HasStateApi>(
+    ctx: &impl HasReceiveContext,
+    _host: &impl HasHost<State<S>, StateApiType = S>,
+) -> ContractResult<SupportsQueryResponse> {
+    let params: SupportsQueryParams = ctx.parameter_cursor().get()?;
+    Ok(supports_standards(&SUPPORTED_STANDARDS, params))
+}
+
+#[concordium_cfg_test]
+mod tests {
+    use super::*;
+    use commons::test::*;
+    use core::convert::TryInto;
+    use test_infrastructure::*;
+
+    // A counter for generating new account addresses
+    const AUCTION_END: u64 = 1;
+    const AUCTION_ID: AuctionId = 0;
+    const AUCTION_ID_BYTES: [u8; 8] = AUCTION_ID.to_le_bytes();
+    const ACCOUNT_0: AccountAddress = AccountAddress([0u8; 32]);
+    const ACCOUNT_1: AccountAddress = AccountAddress([1u8; 32]);
+    const ACCOUNT_2: AccountAddress = AccountAddress([2u8; 32]);
+    const CREATOR: AccountAddress = AccountAddress([5u8; 32]);
+    const MINTER: AccountAddress = AccountAddress([6u8; 32]);
+    const COLLABORATOR: AccountAddress = AccountAddress([7u8; 32]);
+    const ADMIN: AccountAddress = AccountAddress([9u8; 32]);
+
+    fn token_0() -> ContractTokenId {
+        concordium_cis2::TokenIdVec(vec![0, 1])
+    }
+
+    fn dummy_token() -> Token {
+        Token {
+            contract: ContractAddress {
+                index: 1,
+                subindex: 0,
+            },
+            id: token_0(),
+        }
+    }
+
+    /// Contract with a single auction of ACCOUNT_0, created without invoking the NFT contract.
+    fn new_host(parameter: CreateAuctionParams) -> TestHost<State<TestStateApi>> {
+        let mut state_builder = TestStateBuilder::new();
+        let mut state = State::empty(&mut state_builder, ADMIN);
+        state.create_auction(parameter, ACCOUNT_0, &mut state_builder);
+        TestHost::new(state, state_builder)
+    }
+
+    /// Contract without auctions, with the NFT contract accepting the auction contract as operator and reporting
+    /// ACCOUNT_0 as the owner of the item.
+    fn empty_host() -> TestHost<State<TestStateApi>> {
+        let mut state_builder = TestStateBuilder::new();
+        let state = State::empty(&mut state_builder, ADMIN);
+        let mut host = TestHost::new(state, state_builder);
+        setup_balance_mock(&mut host, 1);
+        host.setup_mock_entrypoint(
+            dummy_token().contract,
+            OwnedEntrypointName::new_unchecked(String::from("updateOperator")),
+            MockFn::returning_ok(()),
+        );
+        host.setup_mock_entrypoint(
+            dummy_token().contract,
+            OwnedEntrypointName::new_unchecked(String::from("tokenMetadata")),
+            MockFn::returning_ok(TokenMetadataQueryResponse(Vec::new())),
+        );
+        setup_view_token_mock(&mut host, 0, 0, Vec::new());
+        host
+    }
+
+    /// NFT contract reporting the balance of the item held by the seller.
+    fn setup_balance_mock(host: &mut TestHost<State<TestStateApi>>, balance: u64) {
+        host.setup_mock_entrypoint(
+            dummy_token().contract,
+            OwnedEntrypointName::new_unchecked(String::from("balanceOf")),
+            MockFn::new(move |parameter, _amount, _balance, _state| {
+                ContractBalanceOfQueryParams::deserial(&mut Cursor::new(parameter))
+                    .map_err(|_| CallContractError::Trap)?;
+                let response: ContractBalanceOfQueryResponse =
+                    BalanceOfQueryResponse(vec![ContractTokenAmount::from(balance)]);
+                Ok((false, Some(response)))
+            }),
+        );
+    }
+
+    /// NFT contract recording the royalties of CREATOR, MINTER and additional recipients for the item.
+    fn setup_view_token_mock(
+        host: &mut TestHost<State<TestStateApi>>,
+        creator_royalty: u32,
+        minter_royalty: u32,
+        royalties: Vec<Royalty>,
+    ) {
+        host.setup_mock_entrypoint(
+            dummy_token().contract,
+            OwnedEntrypointName::new_unchecked(String::from("viewToken")),
+            MockFn::returning_ok(NFTData {
+                creator: Address::Account(CREATOR),
+                creator_royalty,
+                minter: Address::Account(MINTER),
+                minter_royalty,
+                price: Amount::zero(),
+                cid: Vec::new(),
+                quantity: ContractTokenAmount::from(1),
+                royalties: royalties
+                    .try_into()
+                    .expect_report("Too many royalty recipients"),
+            }),
+        );
+    }
+
+    /// Create an auction with ACCOUNT_0 as the seller.
+    fn create_as_seller(
+        host: &mut TestHost<State<TestStateApi>>,
+        parameter: &CreateAuctionParams,
+        logger: &mut TestLogger,
+    ) -> ContractResult<AuctionId> {
+        let parameter_bytes = to_bytes(parameter);
+        let mut ctx = new_ctx(ACCOUNT_0, ACCOUNT_0, 0);
+        ctx.set_parameter(&parameter_bytes);
+        create_auction(&ctx, host, logger)
+    }
+
+    fn viewable(host: &TestHost<State<TestStateApi>>) -> ViewableState {
+        host.state()
+            .auction(AUCTION_ID)
+            .expect_report("Auction should exist")
+            .viewable_state
+            .clone()
+    }
+
+    fn highest_bidder(host: &TestHost<State<TestStateApi>>) -> Option<AccountAddress> {
+        host.state()
+            .auction(AUCTION_ID)
+            .expect_report("Auction should exist")
+            .highest_bidder
+    }
+
+    fn expect_error<E, T>(expr: Result<T, E>, err: E, msg: &str)
+    where
+        E: Eq + Debug,
+        T: Debug,
+    {
+        let actual = expr.expect_err(msg);
+        assert_eq!(actual, err);
+    }
+
+    fn item_expiry_parameter() -> CreateAuctionParams {
+        CreateAuctionParams {
+            item: dummy_token(),
+            expiry: Timestamp::from_timestamp_millis(AUCTION_END),
+            cancellation_penalty: Percentage::ZERO,
+            bid_granularity: Amount::from_micro_ccd(1),
+            payment_token: None,
+            reserve_price: Amount::zero(),
+            min_increment: Amount::zero(),
+            extension_window: Duration::from_millis(0),
+            extension_duration: Duration::from_millis(0),
+            start: None,
+            kind: AuctionKind::English,
+        }
     }
 
     fn new_account_ctx<'a>() -> (AccountAddress, TestReceiveContext<'a>) {
@@ -360,6 +1323,7 @@ mod tests {
         (account, ctx)
     }
 
+    /// Context selecting the auction with `AUCTION_ID` as the parameter.
     fn new_ctx<'a>(
         owner: AccountAddress,
         sender: AccountAddress,
@@ -369,23 +1333,86 @@ mod tests {
         ctx.set_sender(Address::Account(sender));
         ctx.set_owner(owner);
         ctx.set_metadata_slot_time(Timestamp::from_timestamp_millis(slot_time));
+        ctx.set_parameter(&AUCTION_ID_BYTES);
         ctx
     }
 
     #[concordium_test]
-    /// Test that the smart-contract initialization sets the state correctly
-    /// (no bids, active state, indicated auction-end time and item name).
+    /// Test that the smart-contract initialization creates no auctions and that
+    /// auctions are created with an active state and the seller as the sender.
     fn test_init() {
-        let parameter_bytes = create_parameter_bytes(&item_expiry_parameter());
-        let ctx = parametrized_init_ctx(&parameter_bytes);
+        let mut ctx = TestInitContext::empty();
+        ctx.set_init_origin(ADMIN);
         let mut state_builder = TestStateBuilder::new();
 
         let state_result = init(&ctx, &mut state_builder);
         let state = state_result.expect("Contract initialization results in error");
+        claim!(state.auctions.iter().next().is_none());
+        claim!(state.authority.has_admin_rights(&Address::Account(ADMIN)));
+
+        let mut host = empty_host();
+        let mut logger = TestLogger::init();
+        let auction_id = create_as_seller(&mut host, &item_expiry_parameter(), &mut logger)
+            .expect("Creating auction should pass");
+        claim_eq!(auction_id, AUCTION_ID);
         claim_eq!(
-            state.viewable_state.auction_state,
+            viewable(&host).auction_state,
             AuctionState::NotSoldYet,
-            "Auction state should be new after initialization"
+            "Auction state should be new after creation"
+        );
+        claim_eq!(viewable(&host).seller, ACCOUNT_0);
+    }
+
+    #[concordium_test]
+    /// Test that the auction parameter keeps the encoding used by deployed auctions and SDKs.
+    fn test_golden_init_parameter() {
+        let mut parameter = item_expiry_parameter();
+        parameter.expiry = Timestamp::from_timestamp_millis(1000);
+        parameter.cancellation_penalty = Percentage::from_percent(10);
+        parameter.bid_granularity = Amount::from_ccd(1);
+        parameter.reserve_price = Amount::from_ccd(2);
+        parameter.min_increment = Amount::from_ccd(1);
+        parameter.extension_window = Duration::from_minutes(10);
+        parameter.extension_duration = Duration::from_minutes(5);
+        parameter.start = Some(Timestamp::from_timestamp_millis(500));
+        let reserve_increment: &[u8] = &[
+            0x80, 0x84, 0x1e, 0, 0, 0, 0, 0, 0x40, 0x42, 0x0f, 0, 0, 0, 0, 0,
+        ];
+        let extension: &[u8] = &[
+            0xc0, 0x27, 0x09, 0, 0, 0, 0, 0, 0xe0, 0x93, 0x04, 0, 0, 0, 0, 0,
+        ];
+        let start: &[u8] = &[1, 0xf4, 0x01, 0, 0, 0, 0, 0, 0];
+        let kind: &[u8] = &[0];
+        let golden: Vec<u8> = [
+            &[1, 0, 0, 0, 0, 0, 0, 0][..],
+            &[0; 8],
+            &[2, 0, 1],
+            &[0xe8, 0x03, 0, 0, 0, 0, 0, 0],
+            &[0x80, 0x96, 0x98, 0, 0, 0, 0, 0],
+            &[0x40, 0x42, 0x0f, 0, 0, 0, 0, 0],
+            &[0],
+            reserve_increment,
+            extension,
+            start,
+            kind,
+        ]
+        .concat();
+        claim_eq!(to_bytes(&parameter), golden);
+
+        // Payment token follows the bid granularity
+        parameter.payment_token = Some(dummy_token());
+        claim_eq!(
+            to_bytes(&parameter),
+            [
+                &golden[..43],
+                &[1],
+                &golden[..19],
+                reserve_increment,
+                extension,
+                start,
+                kind
+            ]
+            .concat()
         );
     }
 
@@ -401,9 +1428,6 @@ mod tests {
     /// Carol (the owner of the contract) collects the highest bid amount.
     /// 6. Attempts to subsequently bid or finalize fail.
     fn test_auction_bid_and_finalize() {
-        let parameter_bytes = create_parameter_bytes(&item_expiry_parameter());
-        let ctx0 = parametrized_init_ctx(&parameter_bytes);
-
         let amount = Amount::from_micro_ccd(100);
         let winning_amount = Amount::from_micro_ccd(300);
         let big_amount = Amount::from_micro_ccd(500);
@@ -411,9 +1435,13 @@ mod tests {
         let mut state_builder = TestStateBuilder::new();
         let mut bid_map = state_builder.new_map();
 
-        // initializing auction
-        let mut state = init(&ctx0, &mut state_builder).expect("Initialization should pass");
-        let mut host = TestHost::new(state, state_builder);
+        // creating auction
+        let mut host = new_host(item_expiry_parameter());
+        host.setup_mock_entrypoint(
+            dummy_token().contract,
+            OwnedEntrypointName::new_unchecked(String::from("transfer")),
+            MockFn::returning_ok(()),
+        );
 
         // 1st bid: account1 bids amount1
         let (alice, alice_ctx) = new_account_ctx();
@@ -443,7 +1471,8 @@ mod tests {
         );
 
         // 3rd bid: second account
-        let (bob, bob_ctx) = new_account_ctx();
+        let bob = ACCOUNT_1;
+        let bob_ctx = new_ctx(ACCOUNT_0, bob, AUCTION_END);
         verify_bid(
             &mut host,
             bob,
@@ -457,8 +1486,7 @@ mod tests {
         // trying to finalize auction that is still active
         // (specifically, the bid is submitted at the last moment, at the AUCTION_END
         // time)
-        let mut ctx4 = TestReceiveContext::empty();
-        ctx4.set_metadata_slot_time(Timestamp::from_timestamp_millis(AUCTION_END));
+        let ctx4 = new_ctx(ACCOUNT_0, ACCOUNT_0, AUCTION_END);
         let finres: ContractResult<()> = finalize(&ctx4, &mut host, &mut logger);
         expect_error(
             finres,
@@ -470,7 +1498,7 @@ mod tests {
         let carol = ACCOUNT_1;
         let dave = ACCOUNT_2;
         let mut ctx5 = new_ctx(carol, dave, AUCTION_END + 1);
-        host.set_self_balance(winning_amount);
+        host.set_self_balance(amount + amount + winning_amount);
         let finres2: ContractResult<()> = finalize(&ctx5, &mut host, &mut logger);
         let _ = finres2.expect("Finalizing auction should work");
 
@@ -491,6 +1519,15 @@ mod tests {
         );
     }
 
+    /// Withdraw the deposit of the account after the auction is over.
+    fn withdraw_as(
+        host: &mut TestHost<State<TestStateApi>>,
+        account: AccountAddress,
+    ) -> ContractResult<()> {
+        let ctx = new_ctx(ACCOUNT_0, account, AUCTION_END + 1);
+        withdraw(&ctx, host)
+    }
+
     fn verify_bid<S: HasStateApi>(
         mut host: &mut TestHost<State<TestStateApi>>,
         _account: AccountAddress,
@@ -509,17 +1546,13 @@ mod tests {
     fn test_auction_bid_repeated_bid() {
         let (account1, ctx1) = new_account_ctx();
 
-        let parameter_bytes = create_parameter_bytes(&item_expiry_parameter());
-        let ctx0 = parametrized_init_ctx(&parameter_bytes);
-
         let amount = Amount::from_micro_ccd(100);
 
         let mut state_builder = TestStateBuilder::new();
         let mut bid_map = state_builder.new_map();
 
-        // initializing auction
-        let mut state = init(&ctx0, &mut state_builder).expect("Init results in error");
-        let mut host = TestHost::new(state, state_builder);
+        // creating auction
+        let mut host = new_host(item_expiry_parameter());
 
         let mut logger = TestLogger::init();
 
@@ -539,12 +1572,7 @@ mod tests {
     /// Bids for 0 CCD should be rejected.
     fn test_auction_bid_zero() {
         let ctx1 = new_account_ctx().1;
-        let parameter_bytes = create_parameter_bytes(&item_expiry_parameter());
-        let ctx = parametrized_init_ctx(&parameter_bytes);
-        let mut state_builder = TestStateBuilder::new();
-
-        let mut state = init(&ctx, &mut state_builder).expect("Init results in error");
-        let mut host = TestHost::new(state, state_builder);
+        let mut host = new_host(item_expiry_parameter());
         let mut logger = TestLogger::init();
 
         let res: ContractResult<()> = bid(&ctx1, &mut host, Amount::zero(), &mut logger);
@@ -556,19 +1584,122 @@ mod tests {
     }
 
     #[concordium_test]
-    fn test_auction_cancel() {
-        let parameter_bytes = create_parameter_bytes(&item_expiry_parameter());
-        let ctx0 = parametrized_init_ctx(&parameter_bytes);
+    /// Bids that are not multiples of the bid granularity should be rejected,
+    /// and proxy bids should be raised by the granularity.
+    fn test_auction_bid_granularity() {
+        let mut parameter = item_expiry_parameter();
+        parameter.bid_granularity = Amount::zero();
+        let mut host = empty_host();
+        let mut logger = TestLogger::init();
+        claim!(
+            create_as_seller(&mut host, &parameter, &mut logger).is_err(),
+            "Zero granularity should be rejected"
+        );
+
+        parameter.bid_granularity = Amount::from_ccd(1);
+        create_as_seller(&mut host, &parameter, &mut logger).expect("Creating auction should pass");
+
+        let alice_ctx = new_ctx(ACCOUNT_0, ACCOUNT_1, AUCTION_END);
+        let bob_ctx = new_ctx(ACCOUNT_0, ACCOUNT_2, AUCTION_END);
+
+        let res = bid(
+            &alice_ctx,
+            &mut host,
+            Amount::from_micro_ccd(1_500_000),
+            &mut logger,
+        );
+        expect_error(
+            res,
+            CustomContractError::InvalidAmountGranularity.into(),
+            "Fractional bid should fail",
+        );
+        let res = proxy_bid(
+            &alice_ctx,
+            &mut host,
+            Amount::from_micro_ccd(1),
+            &mut logger,
+        );
+        expect_error(
+            res,
+            CustomContractError::InvalidAmountGranularity.into(),
+            "Fractional proxy bid should fail",
+        );
+
+        proxy_bid(&alice_ctx, &mut host, Amount::from_ccd(10), &mut logger)
+            .expect("Proxy bidding should pass");
+        bid(&bob_ctx, &mut host, Amount::from_ccd(3), &mut logger).expect("Bidding should pass");
+        claim_eq!(viewable(&host).highest_bid, Amount::from_ccd(4));
+        claim_eq!(highest_bidder(&host), Some(ACCOUNT_1));
+        claim_eq!(viewable(&host).bid_granularity, Amount::from_ccd(1));
+    }
+
+    #[concordium_test]
+    /// Only the first bid of each account should produce a participation event.
+    fn test_auction_participation() {
+        let mut host = new_host(item_expiry_parameter());
+        let mut logger = TestLogger::init();
+
+        let alice_ctx = new_ctx(ACCOUNT_0, ACCOUNT_1, AUCTION_END - 1);
+        let bob_ctx = new_ctx(ACCOUNT_0, ACCOUNT_2, AUCTION_END);
+
+        let res: ContractResult<()> = bid(
+            &alice_ctx,
+            &mut host,
+            Amount::from_micro_ccd(100),
+            &mut logger,
+        );
+        claim!(res.is_ok());
+        let res: ContractResult<()> = bid(
+            &alice_ctx,
+            &mut host,
+            Amount::from_micro_ccd(100),
+            &mut logger,
+        );
+        claim!(res.is_ok());
+        let res: ContractResult<()> = bid(
+            &bob_ctx,
+            &mut host,
+            Amount::from_micro_ccd(300),
+            &mut logger,
+        );
+        claim!(res.is_ok());
+
+        claim_eq!(viewable(&host).participants, 2);
 
+        let participation_events: Vec<_> = logger
+            .logs
+            .iter()
+            .filter(|log| log[0] == PARTICIPATION_TAG)
+            .collect();
+        claim_eq!(participation_events.len(), 2);
+        claim_eq!(
+            participation_events[0],
+            &to_bytes(&CustomEvent::Participation(ParticipationEvent {
+                item: dummy_token(),
+                bidder: ACCOUNT_1,
+                timestamp: Timestamp::from_timestamp_millis(AUCTION_END - 1),
+            }))
+        );
+        claim_eq!(
+            participation_events[1],
+            &to_bytes(&CustomEvent::Participation(ParticipationEvent {
+                item: dummy_token(),
+                bidder: ACCOUNT_2,
+                timestamp: Timestamp::from_timestamp_millis(AUCTION_END),
+            }))
+        );
+    }
+
+    #[concordium_test]
+    fn test_auction_cancel() {
         let amount = Amount::from_micro_ccd(100);
         let winning_amount = Amount::from_micro_ccd(300);
 
         let mut state_builder = TestStateBuilder::new();
         let mut bid_map = state_builder.new_map();
 
-        // initializing auction
-        let mut state = init(&ctx0, &mut state_builder).expect("Initialization should pass");
-        let mut host = TestHost::new(state, state_builder);
+        // creating auction
+        let mut host = new_host(item_expiry_parameter());
 
         // 1st bid: account1 bids amount1
         let (alice, mut alice_ctx) = new_account_ctx();
@@ -585,6 +1716,1194 @@ mod tests {
             &mut logger,
         );
 
-        let _: ContractResult<()> = cancel(&alice_ctx, &mut host, &mut logger);
+        let _: ContractResult<()> = cancel(&alice_ctx, &mut host, Amount::zero(), &mut logger);
+    }
+
+    #[concordium_test]
+    /// Proxy bids should only rise as far as needed to beat other bidders, and
+    /// unused maximums should be returned on finalization.
+    fn test_auction_proxy_bidding() {
+        const ACCOUNT_3: AccountAddress = AccountAddress([3u8; 32]);
+
+        let mut host = new_host(item_expiry_parameter());
+        host.setup_mock_entrypoint(
+            dummy_token().contract,
+            OwnedEntrypointName::new_unchecked(String::from("transfer")),
+            MockFn::returning_ok(()),
+        );
+        let mut logger = TestLogger::init();
+
+        let alice_ctx = new_ctx(ACCOUNT_0, ACCOUNT_1, AUCTION_END);
+        let bob_ctx = new_ctx(ACCOUNT_0, ACCOUNT_2, AUCTION_END);
+        let carol_ctx = new_ctx(ACCOUNT_0, ACCOUNT_3, AUCTION_END);
+
+        // Alice's proxy bid starts at the smallest possible bid
+        proxy_bid(
+            &alice_ctx,
+            &mut host,
+            Amount::from_micro_ccd(500),
+            &mut logger,
+        )
+        .expect("Proxy bidding should pass");
+        claim_eq!(viewable(&host).highest_bid, Amount::from_micro_ccd(1));
+
+        // Direct bid below Alice's maximum is outbid automatically
+        bid(
+            &bob_ctx,
+            &mut host,
+            Amount::from_micro_ccd(300),
+            &mut logger,
+        )
+        .expect("Bidding should pass");
+        claim_eq!(viewable(&host).highest_bid, Amount::from_micro_ccd(301));
+        claim_eq!(highest_bidder(&host), Some(ACCOUNT_1));
+
+        // Proxy bid below Alice's maximum is outbid automatically
+        proxy_bid(
+            &carol_ctx,
+            &mut host,
+            Amount::from_micro_ccd(400),
+            &mut logger,
+        )
+        .expect("Proxy bidding should pass");
+        claim_eq!(viewable(&host).highest_bid, Amount::from_micro_ccd(401));
+        claim_eq!(highest_bidder(&host), Some(ACCOUNT_1));
+
+        // Bob raises his maximum above Alice's and takes the lead
+        proxy_bid(
+            &bob_ctx,
+            &mut host,
+            Amount::from_micro_ccd(300),
+            &mut logger,
+        )
+        .expect("Proxy bidding should pass");
+        claim_eq!(viewable(&host).highest_bid, Amount::from_micro_ccd(501));
+        claim_eq!(highest_bidder(&host), Some(ACCOUNT_2));
+        claim_eq!(viewable(&host).participants, 3);
+
+        // Winner pays the highest bid and gets the unused maximum back
+        host.set_self_balance(Amount::from_micro_ccd(1500));
+        let ctx = new_ctx(ACCOUNT_0, ACCOUNT_0, AUCTION_END + 1);
+        finalize(&ctx, &mut host, &mut logger).expect("Finalizing auction should work");
+
+        claim!(host.transfer_occurred(&ACCOUNT_0, Amount::from_micro_ccd(501)));
+        claim_eq!(viewable(&host).auction_state, AuctionState::Sold(ACCOUNT_2));
+
+        // Bidders withdraw their deposits, the winner only the unused maximum
+        for account in [ACCOUNT_1, ACCOUNT_2, ACCOUNT_3] {
+            withdraw_as(&mut host, account).expect("Withdrawing should pass");
+        }
+        claim!(host.transfer_occurred(&ACCOUNT_1, Amount::from_micro_ccd(500)));
+        claim!(host.transfer_occurred(&ACCOUNT_2, Amount::from_micro_ccd(99)));
+        claim!(host.transfer_occurred(&ACCOUNT_3, Amount::from_micro_ccd(400)));
+        expect_error(
+            withdraw_as(&mut host, ACCOUNT_2),
+            CustomContractError::NotFound.into(),
+            "Withdrawing twice should fail",
+        );
+    }
+
+    #[concordium_test]
+    /// Test that bids must beat the highest bid by the minimum increment and that the item is only sold if the reserve
+    /// price is met.
+    fn test_auction_reserve_and_increment() {
+        const ACCOUNT_3: AccountAddress = AccountAddress([3u8; 32]);
+        let micro_ccd = Amount::from_micro_ccd;
+
+        let mut parameter = item_expiry_parameter();
+        parameter.bid_granularity = Amount::from_ccd(1);
+        parameter.min_increment = micro_ccd(1_500_000);
+        let mut logger = TestLogger::init();
+        claim!(
+            create_as_seller(&mut empty_host(), &parameter, &mut logger).is_err(),
+            "Increment that is not a multiple of the granularity should be rejected"
+        );
+
+        let reserve_host = || {
+            let mut parameter = item_expiry_parameter();
+            parameter.reserve_price = micro_ccd(100);
+            parameter.min_increment = micro_ccd(10);
+            let mut host = new_host(parameter);
+            host.setup_mock_entrypoint(
+                dummy_token().contract,
+                OwnedEntrypointName::new_unchecked(String::from("transfer")),
+                MockFn::returning_ok(()),
+            );
+            host
+        };
+        let alice_ctx = new_ctx(ACCOUNT_0, ACCOUNT_1, AUCTION_END);
+        let bob_ctx = new_ctx(ACCOUNT_0, ACCOUNT_2, AUCTION_END);
+        let carol_ctx = new_ctx(ACCOUNT_0, ACCOUNT_3, AUCTION_END);
+        let end_ctx = new_ctx(ACCOUNT_0, ACCOUNT_0, AUCTION_END + 1);
+
+        // Bids below the reserve are accepted, but every new highest bid needs the increment
+        let mut host = reserve_host();
+        bid(&alice_ctx, &mut host, micro_ccd(50), &mut logger).expect("Bidding should pass");
+        let res = bid(&bob_ctx, &mut host, micro_ccd(55), &mut logger);
+        expect_error(
+            res,
+            CustomContractError::BidTooLow.into(),
+            "Bid below the increment should fail",
+        );
+        bid(&bob_ctx, &mut host, micro_ccd(60), &mut logger).expect("Bidding should pass");
+
+        // Reserve was not met, so all bids are returned and the item is not delivered
+        host.set_self_balance(micro_ccd(110));
+        finalize(&end_ctx, &mut host, &mut logger).expect("Finalizing auction should work");
+        claim!(!host.transfer_occurred(&ACCOUNT_0, micro_ccd(60)));
+        claim_eq!(viewable(&host).auction_state, AuctionState::Canceled);
+        withdraw_as(&mut host, ACCOUNT_1).expect("Withdrawing should pass");
+        withdraw_as(&mut host, ACCOUNT_2).expect("Withdrawing should pass");
+        claim!(host.transfer_occurred(&ACCOUNT_1, micro_ccd(50)));
+        claim!(host.transfer_occurred(&ACCOUNT_2, micro_ccd(60)));
+
+        // Proxy bids start at the reserve and are raised by the increment
+        let mut host = reserve_host();
+        proxy_bid(&carol_ctx, &mut host, micro_ccd(150), &mut logger)
+            .expect("Proxy bidding should pass");
+        claim_eq!(viewable(&host).highest_bid, micro_ccd(100));
+        let res = bid(&alice_ctx, &mut host, micro_ccd(105), &mut logger);
+        expect_error(
+            res,
+            CustomContractError::BidTooLow.into(),
+            "Bid below the increment should fail",
+        );
+        bid(&alice_ctx, &mut host, micro_ccd(120), &mut logger).expect("Bidding should pass");
+        claim_eq!(viewable(&host).highest_bid, micro_ccd(130));
+        claim_eq!(highest_bidder(&host), Some(ACCOUNT_3));
+
+        host.set_self_balance(micro_ccd(270));
+        finalize(&end_ctx, &mut host, &mut logger).expect("Finalizing auction should work");
+        withdraw_as(&mut host, ACCOUNT_1).expect("Withdrawing should pass");
+        withdraw_as(&mut host, ACCOUNT_3).expect("Withdrawing should pass");
+        claim!(host.transfer_occurred(&ACCOUNT_0, micro_ccd(130)));
+        claim!(host.transfer_occurred(&ACCOUNT_1, micro_ccd(120)));
+        claim!(host.transfer_occurred(&ACCOUNT_3, micro_ccd(20)));
+        claim_eq!(viewable(&host).auction_state, AuctionState::Sold(ACCOUNT_3));
+    }
+
+    #[concordium_test]
+    /// Test that bids close to the auction end push the end forward.
+    fn test_auction_extension() {
+        const EXPIRY: u64 = 100_000;
+
+        let mut parameter = item_expiry_parameter();
+        parameter.expiry = Timestamp::from_timestamp_millis(EXPIRY);
+        parameter.extension_window = Duration::from_millis(10_000);
+        parameter.extension_duration = Duration::from_millis(20_000);
+        let mut host = new_host(parameter);
+        let mut logger = TestLogger::init();
+
+        // Bids before the window don't extend the auction
+        let ctx = new_ctx(ACCOUNT_0, ACCOUNT_1, 50_000);
+        bid(&ctx, &mut host, Amount::from_micro_ccd(1), &mut logger).expect("Bidding should pass");
+        claim_eq!(
+            viewable(&host).expiry,
+            Timestamp::from_timestamp_millis(EXPIRY)
+        );
+
+        // Late bid gives other bidders the extension duration to respond
+        let ctx = new_ctx(ACCOUNT_0, ACCOUNT_2, 95_000);
+        bid(&ctx, &mut host, Amount::from_micro_ccd(2), &mut logger).expect("Bidding should pass");
+        let expiry = Timestamp::from_timestamp_millis(115_000);
+        claim_eq!(viewable(&host).expiry, expiry);
+        claim!(logger
+            .logs
+            .contains(&to_bytes(&CustomEvent::AuctionExtended(
+                AuctionExtendedEvent {
+                    item: dummy_token(),
+                    expiry,
+                }
+            ))));
+
+        // Bids after the original end are accepted until the extended end
+        let ctx = new_ctx(ACCOUNT_0, ACCOUNT_1, 110_000);
+        proxy_bid(&ctx, &mut host, Amount::from_micro_ccd(5), &mut logger)
+            .expect("Proxy bidding should pass");
+        claim_eq!(
+            viewable(&host).expiry,
+            Timestamp::from_timestamp_millis(130_000)
+        );
+
+        let ctx = new_ctx(ACCOUNT_0, ACCOUNT_0, 120_000);
+        let res = finalize(&ctx, &mut host, &mut logger);
+        expect_error(
+            res,
+            CustomContractError::AuctionStillActive.into(),
+            "Finalizing extended auction should fail",
+        );
+    }
+
+    #[concordium_test]
+    /// Test that the final price is published to the price registry on finalization.
+    fn test_auction_price_publication() {
+        const REGISTRY: ContractAddress = ContractAddress {
+            index: 2,
+            subindex: 0,
+        };
+        const AUCTION: ContractAddress = ContractAddress {
+            index: 3,
+            subindex: 0,
+        };
+
+        let mut host = new_host(item_expiry_parameter());
+        host.state_mut().config.price_registry = Some(PriceRegistry {
+            contract: REGISTRY,
+            prefix: Bytes(b"auction_sales".to_vec()),
+        });
+        host.setup_mock_entrypoint(
+            dummy_token().contract,
+            OwnedEntrypointName::new_unchecked(String::from("transfer")),
+            MockFn::returning_ok(()),
+        );
+        host.setup_mock_entrypoint(
+            REGISTRY,
+            OwnedEntrypointName::new_unchecked(String::from("upsert")),
+            parse_and_check_mock::<StorageEntries, _>(
+                |entries| {
+                    let sale = SalePrice {
+                        price: Amount::from_micro_ccd(300),
+                        buyer: ACCOUNT_1,
+                        sold_at: Timestamp::from_timestamp_millis(AUCTION_END + 1),
+                        auction: AUCTION,
+                        auction_id: AUCTION_ID,
+                    };
+                    entries.prefix.0 == b"auction_sales"
+                        && entries.entries == vec![StorageEntry::new(&dummy_token(), &sale)]
+                },
+                (),
+            ),
+        );
+        let mut logger = TestLogger::init();
+
+        let alice_ctx = new_ctx(ACCOUNT_0, ACCOUNT_1, AUCTION_END);
+        bid(
+            &alice_ctx,
+            &mut host,
+            Amount::from_micro_ccd(300),
+            &mut logger,
+        )
+        .expect("Bidding should pass");
+
+        host.set_self_balance(Amount::from_micro_ccd(300));
+        let mut ctx = new_ctx(ACCOUNT_0, ACCOUNT_0, AUCTION_END + 1);
+        ctx.set_self_address(AUCTION);
+        finalize(&ctx, &mut host, &mut logger).expect("Finalizing auction should work");
+
+        claim!(host.transfer_occurred(&ACCOUNT_0, Amount::from_micro_ccd(300)));
+        claim_eq!(viewable(&host).auction_state, AuctionState::Sold(ACCOUNT_1));
+    }
+
+    #[concordium_test]
+    /// Test that a rejecting price registry doesn't block the settlement of the sale.
+    fn test_auction_price_publication_rejected() {
+        const REGISTRY: ContractAddress = ContractAddress {
+            index: 2,
+            subindex: 0,
+        };
+
+        let mut host = new_host(item_expiry_parameter());
+        host.state_mut().config.price_registry = Some(PriceRegistry {
+            contract: REGISTRY,
+            prefix: Bytes(b"auction_sales".to_vec()),
+        });
+        setup_transfer_mock(&mut host, MockFn::returning_ok(()));
+        host.setup_mock_entrypoint(
+            REGISTRY,
+            OwnedEntrypointName::new_unchecked(String::from("upsert")),
+            parse_and_check_mock::<StorageEntries, _>(|_| false, ()),
+        );
+        let mut logger = TestLogger::init();
+        place_test_bids(&mut host, &mut logger);
+
+        let ctx = new_ctx(ACCOUNT_0, ACCOUNT_0, AUCTION_END + 1);
+        finalize(&ctx, &mut host, &mut logger).expect("Finalizing auction should work");
+        withdraw_as(&mut host, ACCOUNT_1).expect("Withdrawing should pass");
+
+        claim!(host.transfer_occurred(&ACCOUNT_0, Amount::from_micro_ccd(300)));
+        claim!(host.transfer_occurred(&ACCOUNT_1, Amount::from_micro_ccd(200)));
+        claim_eq!(viewable(&host).auction_state, AuctionState::Sold(ACCOUNT_2));
+    }
+
+    #[concordium_test]
+    /// Test that canceling an auction with bids pays the penalty to the
+    /// highest bidder and returns the surplus to the seller.
+    fn test_auction_cancel_penalty() {
+        let mut parameter = item_expiry_parameter();
+        parameter.cancellation_penalty = Percentage::from_percent(10);
+
+        let bid_amount = Amount::from_micro_ccd(300);
+        let penalty = Amount::from_micro_ccd(30);
+        let surplus = Amount::from_micro_ccd(5);
+
+        let mut host = new_host(parameter);
+        let mut logger = TestLogger::init();
+
+        let bob_ctx = new_ctx(ACCOUNT_0, ACCOUNT_1, AUCTION_END);
+        bid(&bob_ctx, &mut host, bid_amount, &mut logger).expect("Bidding should pass");
+
+        // Only the seller can cancel
+        expect_error(
+            cancel(&bob_ctx, &mut host, Amount::zero(), &mut logger),
+            ContractError::Unauthorized,
+            "Canceling by another account should fail",
+        );
+
+        // Seller cancels without covering the penalty
+        let owner_ctx = new_ctx(ACCOUNT_0, ACCOUNT_0, AUCTION_END);
+        host.set_self_balance(bid_amount);
+        let res = cancel(&owner_ctx, &mut host, Amount::zero(), &mut logger);
+        expect_error(
+            res,
+            CustomContractError::InsufficientFunds.into(),
+            "Canceling without penalty should fail",
+        );
+
+        // Seller cancels with the penalty and some surplus attached
+        host.set_self_balance(bid_amount + penalty + surplus);
+        cancel(&owner_ctx, &mut host, penalty + surplus, &mut logger)
+            .expect("Canceling should pass");
+
+        claim!(host.transfer_occurred(&ACCOUNT_1, penalty));
+        claim!(host.transfer_occurred(&ACCOUNT_0, surplus));
+        claim_eq!(viewable(&host).auction_state, AuctionState::Canceled);
+
+        // Highest bidder withdraws the whole deposit of a canceled auction
+        withdraw_as(&mut host, ACCOUNT_1).expect("Withdrawing should pass");
+        claim!(host.transfer_occurred(&ACCOUNT_1, bid_amount));
+    }
+
+    #[concordium_test]
+    /// Test that auctions with a cancellation penalty over 100% are rejected on creation.
+    fn test_auction_cancel_penalty_limit() {
+        let mut host = empty_host();
+        let mut logger = TestLogger::init();
+
+        let mut parameter = item_expiry_parameter();
+        parameter.cancellation_penalty = Percentage::from_micro_percent(100_000_001);
+        expect_error(
+            create_as_seller(&mut host, &parameter, &mut logger),
+            CustomContractError::InvalidFields.into(),
+            "Creating auction with a penalty over 100% should fail",
+        );
+
+        parameter.cancellation_penalty = Percentage::HUNDRED;
+        create_as_seller(&mut host, &parameter, &mut logger).expect("Creating auction should pass");
+    }
+
+    #[concordium_test]
+    /// Test that relayed bids are credited to bidders, replayed nonces are
+    /// rejected and the surplus is returned to the relayer.
+    fn test_relay_bids() {
+        let mut host = new_host(item_expiry_parameter());
+        let mut logger = TestLogger::init();
+
+        // Bidder registers a signing key
+        let key_bytes = to_bytes(&[7u8; 32]);
+        let mut bidder_ctx = new_ctx(ACCOUNT_0, ACCOUNT_1, AUCTION_END);
+        bidder_ctx.set_parameter(&key_bytes);
+        register_bid_key(&bidder_ctx, &mut host).expect("Registering key should pass");
+
+        let mut crypto_primitives = TestCryptoPrimitives::new();
+        crypto_primitives.setup_verify_ed25519_signature_mock(|public_key, _, _| {
+            public_key == PublicKeyEd25519([7u8; 32])
+        });
+
+        let bid_amount = Amount::from_micro_ccd(300);
+        let params = RelayBidsParams {
+            bids: vec![SignedBid {
+                intent: BidIntent {
+                    auction_id: AUCTION_ID,
+                    bidder: ACCOUNT_1,
+                    amount: bid_amount,
+                    nonce: 0,
+                },
+                signature: [0u8; 64],
+            }],
+        };
+        let parameter_bytes = to_bytes(&params);
+        let mut relayer_ctx = new_ctx(ACCOUNT_0, ACCOUNT_2, AUCTION_END);
+        relayer_ctx.set_invoker(ACCOUNT_2);
+        relayer_ctx.set_parameter(&parameter_bytes);
+
+        // Relayer attaches less than the bid sum
+        let res = relay_bids(
+            &relayer_ctx,
+            &mut host,
+            Amount::from_micro_ccd(100),
+            &mut logger,
+            &crypto_primitives,
+        );
+        expect_error(
+            res,
+            CustomContractError::InsufficientFunds.into(),
+            "Relaying should fail without enough CCD",
+        );
+
+        let surplus = Amount::from_micro_ccd(5);
+        host.set_self_balance(bid_amount + surplus);
+        relay_bids(
+            &relayer_ctx,
+            &mut host,
+            bid_amount + surplus,
+            &mut logger,
+            &crypto_primitives,
+        )
+        .expect("Relaying should pass");
+        claim_eq!(viewable(&host).highest_bid, bid_amount);
+        claim!(host.transfer_occurred(&ACCOUNT_2, surplus));
+
+        // Replaying the same intent fails
+        let res = relay_bids(
+            &relayer_ctx,
+            &mut host,
+            bid_amount,
+            &mut logger,
+            &crypto_primitives,
+        );
+        expect_error(
+            res,
+            CustomContractError::InvalidNonce.into(),
+            "Replayed bid should fail",
+        );
+    }
+
+    fn setup_transfer_mock(
+        host: &mut TestHost<State<TestStateApi>>,
+        transfer: MockFn<State<TestStateApi>>,
+    ) {
+        host.setup_mock_entrypoint(
+            dummy_token().contract,
+            OwnedEntrypointName::new_unchecked(String::from("transfer")),
+            transfer,
+        );
+    }
+
+    /// NFT contract rejecting every transfer, e.g. after the owner revoked operator rights of the auction.
+    fn rejecting_transfer_mock() -> MockFn<State<TestStateApi>> {
+        parse_and_check_mock::<TransferParameter, _>(|_| false, ())
+    }
+
+    /// Bids of 200 by ACCOUNT_1 and winning 300 by ACCOUNT_2, with the auction balance holding both deposits.
+    fn place_test_bids(host: &mut TestHost<State<TestStateApi>>, logger: &mut TestLogger) {
+        for (bidder, amount) in [(ACCOUNT_1, 200), (ACCOUNT_2, 300)] {
+            let ctx = new_ctx(ACCOUNT_0, bidder, AUCTION_END);
+            bid(&ctx, host, Amount::from_micro_ccd(amount), logger).expect("Bidding should pass");
+        }
+        host.set_self_balance(Amount::from_micro_ccd(500));
+    }
+
+    #[concordium_test]
+    /// Test that revoking operator rights of the auction mid-auction doesn't lock bids: the losing bid can be withdrawn
+    /// after finalization and the winning bid is held until the item is redelivered.
+    fn test_operator_revoked_mid_auction() {
+        let mut host = new_host(item_expiry_parameter());
+        let mut logger = TestLogger::init();
+
+        let ctx = new_ctx(ACCOUNT_0, ACCOUNT_1, AUCTION_END);
+        bid(&ctx, &mut host, Amount::from_micro_ccd(200), &mut logger)
+            .expect("Bidding should pass");
+
+        // Seller revokes operator rights of the auction while it's still accepting bids
+        setup_transfer_mock(&mut host, rejecting_transfer_mock());
+        let ctx = new_ctx(ACCOUNT_0, ACCOUNT_2, AUCTION_END);
+        bid(&ctx, &mut host, Amount::from_micro_ccd(300), &mut logger)
+            .expect("Bidding should pass");
+        host.set_self_balance(Amount::from_micro_ccd(500));
+
+        let ctx = new_ctx(ACCOUNT_0, ACCOUNT_1, AUCTION_END + 1);
+        finalize(&ctx, &mut host, &mut logger).expect("Finalizing auction should work");
+        withdraw_as(&mut host, ACCOUNT_1).expect("Withdrawing should pass");
+
+        claim!(host.transfer_occurred(&ACCOUNT_1, Amount::from_micro_ccd(200)));
+        claim!(!host.transfer_occurred(&ACCOUNT_0, Amount::from_micro_ccd(300)));
+        claim_eq!(
+            viewable(&host).auction_state,
+            AuctionState::AwaitingDelivery(ACCOUNT_2)
+        );
+        expect_error(
+            withdraw_as(&mut host, ACCOUNT_2),
+            CustomContractError::NotFound.into(),
+            "Withdrawing the winning bid should fail",
+        );
+        expect_error(
+            finalize(&ctx, &mut host, &mut logger),
+            CustomContractError::AuctionFinalized.into(),
+            "Finalizing auction a second time should fail",
+        );
+
+        // Redelivery fails while the NFT contract keeps rejecting the transfer
+        claim!(redeliver(&ctx, &mut host, &mut logger).is_err());
+
+        // Seller grants operator rights again
+        setup_transfer_mock(
+            &mut host,
+            parse_and_check_mock::<TransferParameter, _>(
+                |TransferParams(transfers)| {
+                    transfers.len() == 1
+                        && transfers[0].from == Address::Account(ACCOUNT_0)
+                        && matches!(transfers[0].to, Receiver::Account(to) if to == ACCOUNT_2)
+                },
+                (),
+            ),
+        );
+        redeliver(&ctx, &mut host, &mut logger).expect("Redelivery should pass");
+
+        claim!(host.transfer_occurred(&ACCOUNT_0, Amount::from_micro_ccd(300)));
+        claim_eq!(viewable(&host).auction_state, AuctionState::Sold(ACCOUNT_2));
+        expect_error(
+            redeliver(&ctx, &mut host, &mut logger),
+            CustomContractError::OperationNotPermitted.into(),
+            "Redelivering a sold item should fail",
+        );
+    }
+
+    #[concordium_test]
+    /// Test that the winner can claim back the winning bid if the NFT contract rejects the transfer at finalization.
+    fn test_transfer_rejected_at_finalize() {
+        let mut host = new_host(item_expiry_parameter());
+        let mut logger = TestLogger::init();
+
+        setup_transfer_mock(&mut host, rejecting_transfer_mock());
+        place_test_bids(&mut host, &mut logger);
+
+        let ctx = new_ctx(ACCOUNT_0, ACCOUNT_1, AUCTION_END + 1);
+        finalize(&ctx, &mut host, &mut logger).expect("Finalizing auction should work");
+
+        // Only the winner can claim the bid
+        expect_error(
+            claim_bid(&ctx, &mut host, &mut logger),
+            ContractError::Unauthorized,
+            "Claiming the bid by another account should fail",
+        );
+
+        let winner_ctx = new_ctx(ACCOUNT_0, ACCOUNT_2, AUCTION_END + 1);
+        claim_bid(&winner_ctx, &mut host, &mut logger).expect("Claiming the bid should pass");
+        withdraw_as(&mut host, ACCOUNT_1).expect("Withdrawing should pass");
+
+        claim!(host.transfer_occurred(&ACCOUNT_1, Amount::from_micro_ccd(200)));
+        claim!(host.transfer_occurred(&ACCOUNT_2, Amount::from_micro_ccd(300)));
+        claim!(!host.transfer_occurred(&ACCOUNT_0, Amount::from_micro_ccd(300)));
+        claim_eq!(viewable(&host).auction_state, AuctionState::Canceled);
+        expect_error(
+            claim_bid(&winner_ctx, &mut host, &mut logger),
+            CustomContractError::OperationNotPermitted.into(),
+            "Claiming the bid twice should fail",
+        );
+        expect_error(
+            withdraw_as(&mut host, ACCOUNT_2),
+            CustomContractError::NotFound.into(),
+            "Withdrawing the claimed bid should fail",
+        );
+    }
+
+    #[concordium_test]
+    /// Test that malformed responses of the NFT contract don't prevent settlement and cancellation.
+    fn test_malformed_nft_responses() {
+        let mut host = new_host(item_expiry_parameter());
+        let mut logger = TestLogger::init();
+
+        setup_transfer_mock(&mut host, MockFn::returning_ok(vec![0xffu8; 7]));
+        place_test_bids(&mut host, &mut logger);
+
+        let ctx = new_ctx(ACCOUNT_0, ACCOUNT_1, AUCTION_END + 1);
+        finalize(&ctx, &mut host, &mut logger).expect("Finalizing auction should work");
+
+        claim!(host.transfer_occurred(&ACCOUNT_0, Amount::from_micro_ccd(300)));
+        claim_eq!(viewable(&host).auction_state, AuctionState::Sold(ACCOUNT_2));
+
+        // Cancellation releases all bids without invoking the NFT contract
+        let mut host = new_host(item_expiry_parameter());
+        place_test_bids(&mut host, &mut logger);
+
+        let owner_ctx = new_ctx(ACCOUNT_0, ACCOUNT_0, AUCTION_END);
+        cancel(&owner_ctx, &mut host, Amount::zero(), &mut logger).expect("Canceling should pass");
+        withdraw_as(&mut host, ACCOUNT_1).expect("Withdrawing should pass");
+        withdraw_as(&mut host, ACCOUNT_2).expect("Withdrawing should pass");
+
+        claim!(host.transfer_occurred(&ACCOUNT_1, Amount::from_micro_ccd(200)));
+        claim!(host.transfer_occurred(&ACCOUNT_2, Amount::from_micro_ccd(300)));
+        claim_eq!(viewable(&host).auction_state, AuctionState::Canceled);
+    }
+
+    #[concordium_test]
+    /// Test that lifecycle events carry the lot details, with the metadata URL cached at creation.
+    fn test_lifecycle_events() {
+        let mut host = empty_host();
+        let mut logger = TestLogger::init();
+        let url = String::from("https://bictory.io/metadata/0");
+
+        host.setup_mock_entrypoint(
+            dummy_token().contract,
+            OwnedEntrypointName::new_unchecked(String::from("tokenMetadata")),
+            MockFn::returning_ok(TokenMetadataQueryResponse(vec![MetadataUrl {
+                url: url.clone(),
+                hash: None,
+            }])),
+        );
+        setup_transfer_mock(&mut host, MockFn::returning_ok(()));
+
+        create_as_seller(&mut host, &item_expiry_parameter(), &mut logger)
+            .expect("Creating auction should pass");
+        claim_eq!(
+            host.state()
+                .auction(AUCTION_ID)
+                .map(|auction| auction.metadata_url.clone()),
+            Ok(Some(url.clone()))
+        );
+
+        let lot = || LotDetails {
+            auction_id: AUCTION_ID,
+            item: dummy_token(),
+            seller: ACCOUNT_0,
+            metadata_url: Some(url.clone()),
+            expiry: Timestamp::from_timestamp_millis(AUCTION_END),
+            cancellation_penalty: Percentage::ZERO,
+            bid_granularity: Amount::from_micro_ccd(1),
+            payment_token: None,
+        };
+        claim!(logger
+            .logs
+            .contains(&to_bytes(&CustomEvent::AuctionCreated(lot()))));
+
+        place_test_bids(&mut host, &mut logger);
+        let ctx = new_ctx(ACCOUNT_0, ACCOUNT_1, AUCTION_END + 1);
+        finalize(&ctx, &mut host, &mut logger).expect("Finalizing auction should work");
+
+        let settled = CustomEvent::AuctionSettled(AuctionSettledEvent {
+            lot: lot(),
+            winner: ACCOUNT_2,
+            price: Amount::from_micro_ccd(300),
+        });
+        claim!(logger.logs.contains(&to_bytes(&settled)));
+    }
+
+    #[concordium_test]
+    /// Test that overlong metadata URLs are left out of the lot details, so that lifecycle events can be logged.
+    fn test_lifecycle_events_long_url() {
+        let mut host = empty_host();
+        let mut logger = TestLogger::init();
+
+        host.setup_mock_entrypoint(
+            dummy_token().contract,
+            OwnedEntrypointName::new_unchecked(String::from("tokenMetadata")),
+            MockFn::returning_ok(TokenMetadataQueryResponse(vec![MetadataUrl {
+                url: "a".repeat(MAX_METADATA_URL_LENGTH + 1),
+                hash: None,
+            }])),
+        );
+
+        create_as_seller(&mut host, &item_expiry_parameter(), &mut logger)
+            .expect("Creating auction should pass");
+        claim_eq!(
+            host.state()
+                .auction(AUCTION_ID)
+                .map(|auction| auction.metadata_url.clone()),
+            Ok(None)
+        );
+        claim!(logger.logs.iter().all(|log| log.len() <= 512));
+    }
+
+    #[concordium_test]
+    /// Test that auctions with a payment token accept bids only as transfers of that token and settle with CIS-2
+    /// transfers from the auction.
+    fn test_payment_token_bids() {
+        const PAYMENT: ContractAddress = ContractAddress {
+            index: 3,
+            subindex: 0,
+        };
+        const AUCTION: ContractAddress = ContractAddress {
+            index: 4,
+            subindex: 0,
+        };
+        let payment_token = Token {
+            contract: PAYMENT,
+            id: concordium_cis2::TokenIdVec(vec![7]),
+        };
+
+        let mut parameter = item_expiry_parameter();
+        parameter.payment_token = Some(payment_token.clone());
+        let mut host = new_host(parameter);
+        setup_transfer_mock(&mut host, MockFn::returning_ok(()));
+        // Refunds and the winning bid are paid in the payment token by the auction
+        host.setup_mock_entrypoint(
+            PAYMENT,
+            OwnedEntrypointName::new_unchecked(String::from("transfer")),
+            parse_and_check_mock::<TransferParameter, _>(
+                |params| params.0[0].from == Address::Contract(AUCTION),
+                (),
+            ),
+        );
+        let mut logger = TestLogger::init();
+
+        // CCD bids are not accepted
+        let ctx = new_ctx(ACCOUNT_0, ACCOUNT_1, AUCTION_END);
+        expect_error(
+            bid(&ctx, &mut host, Amount::from_micro_ccd(200), &mut logger),
+            CustomContractError::OperationNotPermitted.into(),
+            "Bidding with CCD should fail",
+        );
+
+        let token_bid = |from: AccountAddress, amount: u64, kind: TokenBid| {
+            let data = TokenBidData {
+                auction_id: AUCTION_ID,
+                kind,
+            };
+            to_bytes(&OnReceivingCis2Params {
+                token_id: payment_token.id.clone(),
+                amount: ContractTokenAmount::from(amount),
+                from: Address::Account(from),
+                data: AdditionalData::from(to_bytes(&data)),
+            })
+        };
+
+        // Other tokens can't be used for bidding
+        let parameter_bytes = token_bid(ACCOUNT_1, 200, TokenBid::Bid);
+        let mut ctx = new_ctx(ACCOUNT_0, ACCOUNT_1, AUCTION_END);
+        ctx.set_sender(Address::Contract(dummy_token().contract));
+        ctx.set_parameter(&parameter_bytes);
+        expect_error(
+            on_receiving_cis2(&ctx, &mut host, &mut logger),
+            ContractError::Unauthorized,
+            "Bidding with another token should fail",
+        );
+
+        ctx.set_sender(Address::Contract(PAYMENT));
+        on_receiving_cis2(&ctx, &mut host, &mut logger).expect("Bidding should pass");
+
+        let parameter_bytes = token_bid(ACCOUNT_2, 500, TokenBid::ProxyBid);
+        ctx.set_parameter(&parameter_bytes);
+        on_receiving_cis2(&ctx, &mut host, &mut logger).expect("Proxy bidding should pass");
+        claim_eq!(highest_bidder(&host), Some(ACCOUNT_2));
+        claim_eq!(viewable(&host).highest_bid, Amount::from_micro_ccd(201));
+
+        let mut ctx = new_ctx(ACCOUNT_0, ACCOUNT_1, AUCTION_END + 1);
+        ctx.set_self_address(AUCTION);
+        finalize(&ctx, &mut host, &mut logger).expect("Finalizing auction should work");
+        claim_eq!(viewable(&host).auction_state, AuctionState::Sold(ACCOUNT_2));
+
+        // Deposits are withdrawn in the payment token as well
+        for account in [ACCOUNT_1, ACCOUNT_2] {
+            let mut ctx = new_ctx(ACCOUNT_0, account, AUCTION_END + 1);
+            ctx.set_self_address(AUCTION);
+            withdraw(&ctx, &mut host).expect("Withdrawing should pass");
+        }
+
+        // No CCD is paid out
+        claim!(!host.transfer_occurred(&ACCOUNT_0, Amount::from_micro_ccd(201)));
+        claim!(!host.transfer_occurred(&ACCOUNT_1, Amount::from_micro_ccd(200)));
+        claim!(!host.transfer_occurred(&ACCOUNT_2, Amount::from_micro_ccd(299)));
+    }
+
+    #[concordium_test]
+    /// Test that auctions of one contract are independent and can be listed page by page.
+    fn test_multiple_auctions() {
+        let mut host = empty_host();
+        let mut logger = TestLogger::init();
+
+        let first = item_expiry_parameter();
+        let mut second = item_expiry_parameter();
+        second.item.id = concordium_cis2::TokenIdVec(vec![2]);
+        second.expiry = Timestamp::from_timestamp_millis(AUCTION_END + 10);
+        claim_eq!(create_as_seller(&mut host, &first, &mut logger), Ok(0));
+        claim_eq!(create_as_seller(&mut host, &second, &mut logger), Ok(1));
+
+        // Bids are placed in the selected auction only
+        let second_id = to_bytes(&1u64);
+        let mut ctx = new_ctx(ACCOUNT_0, ACCOUNT_1, AUCTION_END);
+        ctx.set_parameter(&second_id);
+        bid(&ctx, &mut host, Amount::from_micro_ccd(300), &mut logger)
+            .expect("Bidding should pass");
+        claim_eq!(viewable(&host).highest_bid, Amount::zero());
+        claim_eq!(
+            host.state()
+                .auction(1)
+                .map(|auction| auction.viewable_state.highest_bid),
+            Ok(Amount::from_micro_ccd(300))
+        );
+
+        // First auction ends without bids while the second is still active
+        let ctx = new_ctx(ACCOUNT_0, ACCOUNT_1, AUCTION_END + 1);
+        finalize(&ctx, &mut host, &mut logger).expect("Finalizing auction should work");
+        claim_eq!(viewable(&host).auction_state, AuctionState::Canceled);
+        let mut ctx = new_ctx(ACCOUNT_0, ACCOUNT_1, AUCTION_END + 1);
+        ctx.set_parameter(&second_id);
+        expect_error(
+            finalize(&ctx, &mut host, &mut logger),
+            CustomContractError::AuctionStillActive.into(),
+            "Finalizing active auction should fail",
+        );
+
+        let unknown_id = to_bytes(&2u64);
+        ctx.set_parameter(&unknown_id);
+        expect_error(
+            bid(&ctx, &mut host, Amount::from_micro_ccd(300), &mut logger),
+            CustomContractError::NotFound.into(),
+            "Bidding in unknown auction should fail",
+        );
+
+        let parameter_bytes = to_bytes(&ViewAuctionsParams { skip: 1, show: 10 });
+        ctx.set_parameter(&parameter_bytes);
+        let auctions = view_auctions(&ctx, &host).expect("Viewing auctions should pass");
+        claim_eq!(auctions.len(), 1);
+        claim_eq!(auctions[0].auction_id, 1);
+        claim_eq!(auctions[0].phase, AuctionPhase::Active);
+        claim_eq!(auctions[0].state.item, second.item);
+    }
+
+    #[concordium_test]
+    /// Test that only the owner of an item can auction it, and only in one live auction at a time.
+    fn test_create_auction_item_checks() {
+        let mut host = empty_host();
+        let mut logger = TestLogger::init();
+        let parameter = item_expiry_parameter();
+
+        setup_balance_mock(&mut host, 0);
+        expect_error(
+            create_as_seller(&mut host, &parameter, &mut logger),
+            CustomContractError::OnlyOwner.into(),
+            "Auctioning an item of someone else should fail",
+        );
+
+        setup_balance_mock(&mut host, 1);
+        claim_eq!(create_as_seller(&mut host, &parameter, &mut logger), Ok(0));
+        expect_error(
+            create_as_seller(&mut host, &parameter, &mut logger),
+            CustomContractError::AlreadyExists.into(),
+            "Auctioning an item twice should fail",
+        );
+
+        // Item can be auctioned again once the auction is over
+        let ctx = new_ctx(ACCOUNT_0, ACCOUNT_1, AUCTION_END + 1);
+        finalize(&ctx, &mut host, &mut logger).expect("Finalizing auction should work");
+        claim_eq!(create_as_seller(&mut host, &parameter, &mut logger), Ok(1));
+    }
+
+    #[concordium_test]
+    /// Test that a listing contract creates auctions on behalf of the sellers it's an operator of, while
+    /// `createAuction` rejects it.
+    fn test_create_auction_for() {
+        const LISTING: ContractAddress = ContractAddress {
+            index: 5,
+            subindex: 0,
+        };
+        let mut host = empty_host();
+        let mut logger = TestLogger::init();
+        host.setup_mock_entrypoint(
+            dummy_token().contract,
+            OwnedEntrypointName::new_unchecked(String::from("operatorOf")),
+            MockFn::new(|parameter, _amount, _balance, _state| {
+                let params = OperatorOfQueryParams::deserial(&mut Cursor::new(parameter))
+                    .map_err(|_| CallContractError::Trap)?;
+                let response = OperatorOfQueryResponse(
+                    params
+                        .queries
+                        .iter()
+                        .map(|query| {
+                            query.owner == Address::Account(ACCOUNT_0)
+                                && query.address == Address::Contract(LISTING)
+                        })
+                        .collect(),
+                );
+                Ok((false, Some(response)))
+            }),
+        );
+
+        // Parameter sent by the listing contract's `sweepToAuction`
+        let parameter = CreateAuctionParams {
+            bid_granularity: Amount::from_micro_ccd(1),
+            reserve_price: Amount::from_ccd(50),
+            ..item_expiry_parameter()
+        };
+        let parameter_bytes = to_bytes(&parameter);
+        let mut ctx = new_ctx(ACCOUNT_0, ACCOUNT_0, 0);
+        ctx.set_sender(Address::Contract(LISTING));
+        ctx.set_parameter(&parameter_bytes);
+        expect_error(
+            create_auction(&ctx, &mut host, &mut logger),
+            CustomContractError::OnlyAccountAddress.into(),
+            "Contracts can't create auctions as sellers",
+        );
+
+        let create_for = |host: &mut TestHost<State<TestStateApi>>,
+                          sender: ContractAddress,
+                          seller: AccountAddress,
+                          logger: &mut TestLogger| {
+            let parameter_bytes = to_bytes(&CreateAuctionForParams {
+                seller,
+                auction: CreateAuctionParams {
+                    bid_granularity: Amount::from_micro_ccd(1),
+                    reserve_price: Amount::from_ccd(50),
+                    ..item_expiry_parameter()
+                },
+            });
+            let mut ctx = new_ctx(ACCOUNT_0, ACCOUNT_0, 0);
+            ctx.set_sender(Address::Contract(sender));
+            ctx.set_parameter(&parameter_bytes);
+            create_auction_for(&ctx, host, logger)
+        };
+
+        expect_error(
+            create_for(&mut host, dummy_token().contract, ACCOUNT_0, &mut logger),
+            CustomContractError::Unauthorized.into(),
+            "Only operators of the seller can create auctions on its behalf",
+        );
+        expect_error(
+            create_for(&mut host, LISTING, ACCOUNT_1, &mut logger),
+            CustomContractError::Unauthorized.into(),
+            "Listing is not an operator of other accounts",
+        );
+
+        setup_balance_mock(&mut host, 0);
+        expect_error(
+            create_for(&mut host, LISTING, ACCOUNT_0, &mut logger),
+            CustomContractError::OnlyOwner.into(),
+            "Seller must own the item",
+        );
+
+        setup_balance_mock(&mut host, 1);
+        claim_eq!(
+            create_for(&mut host, LISTING, ACCOUNT_0, &mut logger),
+            Ok(AUCTION_ID)
+        );
+        let state = viewable(&host);
+        claim_eq!(state.seller, ACCOUNT_0);
+        claim_eq!(state.reserve_price, Amount::from_ccd(50));
+    }
+
+    #[concordium_test]
+    /// Test that only admins can set the price registry.
+    fn test_set_price_registry() {
+        let mut host = empty_host();
+        let registry = Some(PriceRegistry {
+            contract: ContractAddress {
+                index: 2,
+                subindex: 0,
+            },
+            prefix: Bytes(b"auction_sales".to_vec()),
+        });
+        let parameter_bytes = to_bytes(&registry);
+
+        let mut ctx = new_ctx(ACCOUNT_0, ACCOUNT_0, AUCTION_END);
+        ctx.set_parameter(&parameter_bytes);
+        expect_error(
+            set_price_registry(&ctx, &mut host),
+            ContractError::Unauthorized,
+            "Setting the price registry without admin rights should fail",
+        );
+
+        let mut ctx = new_ctx(ACCOUNT_0, ADMIN, AUCTION_END);
+        ctx.set_parameter(&parameter_bytes);
+        claim_eq!(set_price_registry(&ctx, &mut host), Ok(()));
+        let config = view_config(&ctx, &host).expect("Viewing config should pass");
+        claim_eq!(
+            config.price_registry.map(|registry| registry.prefix),
+            Some(Bytes(b"auction_sales".to_vec()))
+        );
+    }
+
+    #[concordium_test]
+    /// Test that bids are only accepted from the start time and that the view reports the phase of the auction.
+    fn test_scheduled_start() {
+        const START: u64 = 10;
+        let mut host = empty_host();
+        let mut logger = TestLogger::init();
+
+        let mut parameter = item_expiry_parameter();
+        parameter.start = Some(Timestamp::from_timestamp_millis(START));
+        expect_error(
+            create_as_seller(&mut host, &parameter, &mut logger),
+            CustomContractError::InvalidFields.into(),
+            "Creating auction starting after the expiry should fail",
+        );
+        parameter.expiry = Timestamp::from_timestamp_millis(START + 10);
+        create_as_seller(&mut host, &parameter, &mut logger).expect("Creating auction should pass");
+
+        let phase = |host: &TestHost<State<TestStateApi>>, slot_time: u64| {
+            let ctx = new_ctx(ACCOUNT_0, ACCOUNT_1, slot_time);
+            view(&ctx, host)
+                .expect_report("Viewing auction should pass")
+                .phase
+        };
+        claim_eq!(phase(&host, START - 1), AuctionPhase::Pending);
+
+        let ctx = new_ctx(ACCOUNT_0, ACCOUNT_1, START - 1);
+        expect_error(
+            bid(&ctx, &mut host, Amount::from_micro_ccd(100), &mut logger),
+            CustomContractError::AuctionNotStarted.into(),
+            "Bidding before the start should fail",
+        );
+        expect_error(
+            proxy_bid(&ctx, &mut host, Amount::from_micro_ccd(100), &mut logger),
+            CustomContractError::AuctionNotStarted.into(),
+            "Proxy bidding before the start should fail",
+        );
+
+        let ctx = new_ctx(ACCOUNT_0, ACCOUNT_1, START);
+        bid(&ctx, &mut host, Amount::from_micro_ccd(100), &mut logger)
+            .expect("Bidding at the start should pass");
+        claim_eq!(phase(&host, START), AuctionPhase::Active);
+        claim_eq!(phase(&host, START + 11), AuctionPhase::Ended);
+
+        setup_transfer_mock(&mut host, MockFn::returning_ok(()));
+        host.set_self_balance(Amount::from_micro_ccd(100));
+        let ctx = new_ctx(ACCOUNT_0, ACCOUNT_1, START + 11);
+        finalize(&ctx, &mut host, &mut logger).expect("Finalizing auction should pass");
+        claim_eq!(phase(&host, START + 11), AuctionPhase::Finalized);
+    }
+
+    #[concordium_test]
+    /// Test that Dutch auctions are bought at the falling price and settled right away, and don't accept bids.
+    fn test_dutch_auction() {
+        let mut host = empty_host();
+        let mut logger = TestLogger::init();
+
+        let mut parameter = item_expiry_parameter();
+        parameter.expiry = Timestamp::from_timestamp_millis(100);
+        parameter.kind = AuctionKind::Dutch(DutchAuction {
+            start_price: Amount::from_micro_ccd(1000),
+            end_price: Amount::from_micro_ccd(2000),
+            decay: PriceDecay::Linear,
+        });
+        expect_error(
+            create_as_seller(&mut host, &parameter, &mut logger),
+            CustomContractError::InvalidFields.into(),
+            "Creating Dutch auction with rising price should fail",
+        );
+        parameter.kind = AuctionKind::Dutch(DutchAuction {
+            start_price: Amount::from_micro_ccd(1000),
+            end_price: Amount::from_micro_ccd(200),
+            decay: PriceDecay::Linear,
+        });
+        create_as_seller(&mut host, &parameter, &mut logger).expect("Creating auction should pass");
+        claim_eq!(
+            viewable(&host).start,
+            Some(Timestamp::from_timestamp_millis(0)),
+            "Price should start falling at creation"
+        );
+
+        let price = |host: &TestHost<State<TestStateApi>>, slot_time: u64| {
+            host.state()
+                .auction(AUCTION_ID)
+                .expect_report("Auction should exist")
+                .dutch_price(Timestamp::from_timestamp_millis(slot_time))
+        };
+        claim_eq!(price(&host, 0), Some(Amount::from_micro_ccd(1000)));
+        claim_eq!(price(&host, 25), Some(Amount::from_micro_ccd(800)));
+        claim_eq!(price(&host, 200), Some(Amount::from_micro_ccd(200)));
+
+        let ctx = new_ctx(ACCOUNT_0, ACCOUNT_1, 50);
+        expect_error(
+            bid(&ctx, &mut host, Amount::from_micro_ccd(1000), &mut logger),
+            CustomContractError::OperationNotPermitted.into(),
+            "Bidding in Dutch auction should fail",
+        );
+        expect_error(
+            buy(&ctx, &mut host, Amount::from_micro_ccd(599), &mut logger),
+            CustomContractError::InsufficientFunds.into(),
+            "Buying below the current price should fail",
+        );
+
+        setup_transfer_mock(&mut host, MockFn::returning_ok(()));
+        host.set_self_balance(Amount::from_micro_ccd(700));
+        buy(&ctx, &mut host, Amount::from_micro_ccd(700), &mut logger)
+            .expect("Buying at the current price should pass");
+        claim_eq!(viewable(&host).auction_state, AuctionState::Sold(ACCOUNT_1));
+        claim_eq!(viewable(&host).highest_bid, Amount::from_micro_ccd(600));
+        claim!(host.transfer_occurred(&ACCOUNT_0, Amount::from_micro_ccd(600)));
+        expect_error(
+            buy(&ctx, &mut host, Amount::from_micro_ccd(700), &mut logger),
+            CustomContractError::AuctionFinalized.into(),
+            "Buying sold item should fail",
+        );
+
+        // Surplus over the price is returned to the buyer
+        withdraw_as(&mut host, ACCOUNT_1).expect("Withdrawing surplus should pass");
+        claim!(host.transfer_occurred(&ACCOUNT_1, Amount::from_micro_ccd(100)));
+
+        // Stepwise decay keeps the price within a step
+        let mut parameter = item_expiry_parameter();
+        parameter.expiry = Timestamp::from_timestamp_millis(100);
+        parameter.kind = AuctionKind::Dutch(DutchAuction {
+            start_price: Amount::from_micro_ccd(1000),
+            end_price: Amount::zero(),
+            decay: PriceDecay::Stepwise(Duration::from_millis(30)),
+        });
+        let mut host = new_host(parameter);
+        host.state_mut()
+            .auction_mut(AUCTION_ID)
+            .expect_report("Auction should exist")
+            .viewable_state
+            .start = Some(Timestamp::from_timestamp_millis(0));
+        claim_eq!(price(&host, 29), Some(Amount::from_micro_ccd(1000)));
+        claim_eq!(price(&host, 30), Some(Amount::from_micro_ccd(700)));
+        claim_eq!(price(&host, 99), Some(Amount::from_micro_ccd(100)));
+        claim_eq!(price(&host, 100), Some(Amount::zero()));
+    }
+
+    #[concordium_test]
+    /// Test that the winning bid is split between the seller, the royalty receivers recorded in the NFT and Bictory.
+    fn test_royalty_settlement() {
+        const BICTORY_CONFIG: ContractAddress = ContractAddress {
+            index: 571,
+            subindex: 0,
+        };
+        let ccd = Amount::from_ccd;
+
+        let mut host = empty_host();
+        let mut logger = TestLogger::init();
+        let parameter = item_expiry_parameter();
+
+        // Only admins set the Bictory share
+        let royalty_bytes = to_bytes(&Percentage::from_percent(2));
+        let mut ctx = new_ctx(ACCOUNT_0, ACCOUNT_0, AUCTION_END);
+        ctx.set_parameter(&royalty_bytes);
+        expect_error(
+            set_bictory_royalty(&ctx, &mut host),
+            ContractError::Unauthorized,
+            "Setting the Bictory share without admin rights should fail",
+        );
+        let mut ctx = new_ctx(ACCOUNT_0, ADMIN, AUCTION_END);
+        ctx.set_parameter(&royalty_bytes);
+        claim_eq!(set_bictory_royalty(&ctx, &mut host), Ok(()));
+
+        // Royalties exceeding the winning bid are rejected on creation
+        setup_view_token_mock(&mut host, 60_000_000, 40_000_000, Vec::new());
+        expect_error(
+            create_as_seller(&mut host, &parameter, &mut logger),
+            CustomContractError::InvalidRoyalty.into(),
+            "Creating auction with royalties over 100% should fail",
+        );
+
+        let collaborator = |percent: u64| Royalty {
+            beneficiary: COLLABORATOR,
+            percentage: Percentage::from_percent(percent),
+        };
+        setup_view_token_mock(&mut host, 60_000_000, 30_000_000, vec![collaborator(10)]);
+        expect_error(
+            create_as_seller(&mut host, &parameter, &mut logger),
+            CustomContractError::InvalidRoyalty.into(),
+            "Creating auction with additional royalties over 100% should fail",
+        );
+
+        setup_view_token_mock(&mut host, 5_000_000, 2_000_000, vec![collaborator(1)]);
+        create_as_seller(&mut host, &parameter, &mut logger).expect("Creating auction should pass");
+        setup_transfer_mock(&mut host, MockFn::returning_ok(()));
+        host.setup_mock_entrypoint(
+            BICTORY_CONFIG,
+            OwnedEntrypointName::new_unchecked(String::from("sendCCD")),
+            MockFn::returning_ok(()),
+        );
+
+        let ctx = new_ctx(ACCOUNT_0, ACCOUNT_1, AUCTION_END);
+        bid(&ctx, &mut host, ccd(100), &mut logger).expect("Bidding should pass");
+        host.set_self_balance(ccd(100));
+        let ctx = new_ctx(ACCOUNT_0, ACCOUNT_1, AUCTION_END + 1);
+        finalize(&ctx, &mut host, &mut logger).expect("Finalizing auction should work");
+
+        claim!(host.transfer_occurred(&ACCOUNT_0, ccd(90)));
+        claim!(host.transfer_occurred(&CREATOR, ccd(5)));
+        claim!(host.transfer_occurred(&MINTER, ccd(2)));
+        claim!(host.transfer_occurred(&COLLABORATOR, ccd(1)));
     }
 }
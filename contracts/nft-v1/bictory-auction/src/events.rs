@@ -9,6 +9,68 @@ pub struct BidingEvent {
     pub bid: Amount,
 }
 
+/// An untagged event of cancellation penalty payout.
+#[derive(Debug, Serialize, SchemaType)]
+pub struct CancellationPenaltyEvent {
+    /// Auctioned item.
+    pub item: Token,
+    /// Highest bidder receiving the penalty.
+    pub bidder: AccountAddress,
+    /// Penalty amount.
+    pub penalty: Amount,
+}
+
+/// An untagged event of the first bid placed by an account.
+#[derive(Debug, Serialize, SchemaType)]
+pub struct ParticipationEvent {
+    /// Auctioned item.
+    pub item: Token,
+    /// Account joining the auction.
+    pub bidder: AccountAddress,
+    /// Time of the first bid.
+    pub timestamp: Timestamp,
+}
+
+/// An untagged event of the auction end pushed forward by a late bid.
+#[derive(Debug, Serialize, SchemaType)]
+pub struct AuctionExtendedEvent {
+    /// Auctioned item.
+    pub item: Token,
+    /// New time of the auction end.
+    pub expiry: Timestamp,
+}
+
+/// Details of the auctioned lot, included in lifecycle events so that notifications can be built from the event alone.
+#[derive(Debug, Serialize, SchemaType)]
+pub struct LotDetails {
+    /// Auction within the auction contract.
+    pub auction_id: AuctionId,
+    /// Auctioned item.
+    pub item: Token,
+    /// Account that created the auction and sells the item.
+    pub seller: AccountAddress,
+    /// Metadata URL of the item cached on creation, `None` if the NFT contract doesn't report one.
+    pub metadata_url: Option<String>,
+    /// Time of the auction end.
+    pub expiry: Timestamp,
+    /// Share of the highest bid paid to the highest bidder on cancellation.
+    pub cancellation_penalty: Percentage,
+    /// Bid amounts must be multiples of this amount.
+    pub bid_granularity: Amount,
+    /// CIS-2 token bids are paid in, `None` for CCD auctions.
+    pub payment_token: Option<Token>,
+}
+
+/// An untagged event of the item delivered to the winner and the winning bid paid to the seller.
+#[derive(Debug, Serialize, SchemaType)]
+pub struct AuctionSettledEvent {
+    pub lot: LotDetails,
+    /// Account the item was sold to.
+    pub winner: AccountAddress,
+    /// Winning bid.
+    pub price: Amount,
+}
+
 /// Tagged Custom event to be serialized for the event log.
 #[derive(Debug)]
 pub enum CustomEvent {
@@ -18,6 +80,16 @@ pub enum CustomEvent {
     Finalize(Token),
     /// Cancel Auction
     Cancel(Token),
+    /// Cancellation penalty paid to the highest bidder
+    CancellationPenalty(CancellationPenaltyEvent),
+    /// First bid of an account
+    Participation(ParticipationEvent),
+    /// Auction was created and accepts bids
+    AuctionCreated(LotDetails),
+    /// Item was sold
+    AuctionSettled(AuctionSettledEvent),
+    /// Auction end was extended by a late bid
+    AuctionExtended(AuctionExtendedEvent),
 }
 
 impl Serial for CustomEvent {
@@ -35,6 +107,26 @@ impl Serial for CustomEvent {
                 out.write_u8(CANCEL_TAG)?;
                 event.serial(out)
             }
+            CustomEvent::CancellationPenalty(event) => {
+                out.write_u8(CANCELLATION_PENALTY_TAG)?;
+                event.serial(out)
+            }
+            CustomEvent::Participation(event) => {
+                out.write_u8(PARTICIPATION_TAG)?;
+                event.serial(out)
+            }
+            CustomEvent::AuctionCreated(event) => {
+                out.write_u8(AUCTION_CREATED_TAG)?;
+                event.serial(out)
+            }
+            CustomEvent::AuctionSettled(event) => {
+                out.write_u8(AUCTION_SETTLED_TAG)?;
+                event.serial(out)
+            }
+            CustomEvent::AuctionExtended(event) => {
+                out.write_u8(AUCTION_EXTENDED_TAG)?;
+                event.serial(out)
+            }
         }
     }
 }
@@ -46,6 +138,19 @@ impl Deserial for CustomEvent {
             BIDING_TAG => BidingEvent::deserial(source).map(CustomEvent::Biding),
             FINALIZE_TAG => Token::deserial(source).map(CustomEvent::Finalize),
             CANCEL_TAG => Token::deserial(source).map(CustomEvent::Cancel),
+            CANCELLATION_PENALTY_TAG => {
+                CancellationPenaltyEvent::deserial(source).map(CustomEvent::CancellationPenalty)
+            }
+            PARTICIPATION_TAG => {
+                ParticipationEvent::deserial(source).map(CustomEvent::Participation)
+            }
+            AUCTION_CREATED_TAG => LotDetails::deserial(source).map(CustomEvent::AuctionCreated),
+            AUCTION_SETTLED_TAG => {
+                AuctionSettledEvent::deserial(source).map(CustomEvent::AuctionSettled)
+            }
+            AUCTION_EXTENDED_TAG => {
+                AuctionExtendedEvent::deserial(source).map(CustomEvent::AuctionExtended)
+            }
             _ => Err(ParseError::default()),
         }
     }
@@ -2,17 +2,442 @@ use super::*;
 
 // Functions for creating, updating and querying the contract state.
 impl<S: HasStateApi> State<S> {
-    /// Creates a empty state with no tokens.
-    pub fn empty(itm: Token, exp: Timestamp, state_builder: &mut StateBuilder<S>) -> Self {
+    /// Creates a empty state with no auctions and the given admin.
+    pub fn empty(state_builder: &mut StateBuilder<S>, admin: AccountAddress) -> Self {
+        Self {
+            auctions: state_builder.new_map(),
+            next_auction_id: 0,
+            item_auctions: state_builder.new_map(),
+            authority: Authority::new(state_builder, Address::Account(admin)),
+            config: AuctionConfig {
+                price_registry: None,
+                bictory_royalty: Percentage::ZERO,
+            },
+            bid_keys: state_builder.new_map(),
+            nonces: state_builder.new_map(),
+        }
+    }
+
+    /// Add a new auction of the seller and return its identifier.
+    pub fn create_auction(
+        &mut self,
+        parameter: CreateAuctionParams,
+        seller: AccountAddress,
+        state_builder: &mut StateBuilder<S>,
+    ) -> AuctionId {
+        let auction_id = self.next_auction_id;
+        self.next_auction_id += 1;
+        self.item_auctions
+            .insert(parameter.item.clone(), auction_id);
+        let bictory_royalty = self.bictory_royalty(&parameter);
+        self.auctions.insert(
+            auction_id,
+            AuctionData::new(parameter, seller, bictory_royalty, state_builder),
+        );
+        auction_id
+    }
+
+    /// Bictory share of an auction created with the parameter.
+    pub fn bictory_royalty(&self, parameter: &CreateAuctionParams) -> Percentage {
+        match parameter.payment_token {
+            None => self.config.bictory_royalty,
+            Some(_) => Percentage::ZERO,
+        }
+    }
+
+    /// Ensure the item is not sold in an auction that is still accepting bids, waiting for finalization or waiting
+    /// for the delivery of the item.
+    pub fn ensure_not_auctioned(&self, item: &Token) -> ContractResult<()> {
+        let live = self
+            .item_auctions
+            .get(item)
+            .and_then(|auction_id| self.auctions.get(&*auction_id))
+            .map_or(false, |auction| {
+                matches!(
+                    auction.viewable_state.auction_state,
+                    AuctionState::NotSoldYet | AuctionState::AwaitingDelivery(_)
+                )
+            });
+        ensure!(!live, CustomContractError::AlreadyExists.into());
+        Ok(())
+    }
+
+    pub fn auction(&self, auction_id: AuctionId) -> ContractResult<StateRef<AuctionData<S>>> {
+        self.auctions
+            .get(&auction_id)
+            .ok_or_else(|| CustomContractError::NotFound.into())
+    }
+
+    pub fn auction_mut(
+        &mut self,
+        auction_id: AuctionId,
+    ) -> ContractResult<StateRefMut<AuctionData<S>, S>> {
+        self.auctions
+            .get_mut(&auction_id)
+            .ok_or_else(|| CustomContractError::NotFound.into())
+    }
+
+    /// Check the expected nonce of the bidder and increment it.
+    pub fn use_nonce(&mut self, bidder: AccountAddress, nonce: u64) -> ContractResult<()> {
+        let mut expected = self.nonces.entry(bidder).or_insert_with(|| 0);
+        ensure_eq!(*expected, nonce, CustomContractError::InvalidNonce.into());
+        *expected += 1;
+        Ok(())
+    }
+}
+
+// Functions for bidding and settling a single auction.
+impl<S: HasStateApi> AuctionData<S> {
+    fn new(
+        parameter: CreateAuctionParams,
+        seller: AccountAddress,
+        bictory_royalty: Percentage,
+        state_builder: &mut StateBuilder<S>,
+    ) -> Self {
         Self {
             viewable_state: ViewableState {
                 auction_state: AuctionState::NotSoldYet,
                 highest_bid: Amount::zero(),
-                item: itm,
-                expiry: exp,
-                is_authorised: false,
+                item: parameter.item,
+                seller,
+                expiry: parameter.expiry,
+                cancellation_penalty: parameter.cancellation_penalty,
+                participants: 0,
+                bid_granularity: parameter.bid_granularity,
+                payment_token: parameter.payment_token,
+                reserve_price: parameter.reserve_price,
+                min_increment: parameter.min_increment,
+                extension_window: parameter.extension_window,
+                extension_duration: parameter.extension_duration,
+                bictory_royalty,
+                start: parameter.start,
+                kind: parameter.kind,
             },
             bids: state_builder.new_map(),
+            proxy_max: state_builder.new_map(),
+            highest_bidder: None,
+            metadata_url: None,
+            royalties: Vec::new(),
+        }
+    }
+
+    /// Details of the auctioned lot for lifecycle events.
+    pub fn lot_details(&self, auction_id: AuctionId) -> LotDetails {
+        LotDetails {
+            auction_id,
+            item: self.viewable_state.item.clone(),
+            seller: self.viewable_state.seller,
+            metadata_url: self.metadata_url.clone(),
+            expiry: self.viewable_state.expiry,
+            cancellation_penalty: self.viewable_state.cancellation_penalty,
+            bid_granularity: self.viewable_state.bid_granularity,
+            payment_token: self.viewable_state.payment_token.clone(),
+        }
+    }
+
+    /// Ensure bids are paid in CCD, i.e. the auction has no payment token.
+    pub fn ensure_ccd_payment(&self) -> ContractResult<()> {
+        ensure!(
+            self.viewable_state.payment_token.is_none(),
+            CustomContractError::OperationNotPermitted.into()
+        );
+        Ok(())
+    }
+
+    /// Ensure the auction is still accepting bids.
+    fn ensure_accepting_bids(&self, slot_time: Timestamp) -> ContractResult<()> {
+        match self.viewable_state.auction_state {
+            AuctionState::NotSoldYet => {
+                ensure!(
+                    self.viewable_state
+                        .start
+                        .map_or(true, |start| slot_time >= start),
+                    CustomContractError::AuctionNotStarted.into()
+                );
+                ensure!(
+                    slot_time <= self.viewable_state.expiry,
+                    CustomContractError::AuctionFinished.into()
+                );
+                Ok(())
+            }
+            AuctionState::Sold(_) | AuctionState::AwaitingDelivery(_) => {
+                bail!(CustomContractError::AuctionFinalized.into())
+            }
+            AuctionState::Canceled => bail!(CustomContractError::AuctionCanceled.into()),
+        }
+    }
+
+    /// Ensure the auction is an English auction, which accepts bids.
+    fn ensure_english(&self) -> ContractResult<()> {
+        ensure_eq!(
+            self.viewable_state.kind,
+            AuctionKind::English,
+            CustomContractError::OperationNotPermitted.into()
+        );
+        Ok(())
+    }
+
+    /// Price of a Dutch auction at the given time, `None` for English auctions. The price falls from the start price
+    /// at the auction start to the end price at the auction end.
+    pub fn dutch_price(&self, slot_time: Timestamp) -> Option<Amount> {
+        let dutch = match &self.viewable_state.kind {
+            AuctionKind::English => return None,
+            AuctionKind::Dutch(dutch) => dutch,
+        };
+        let start = self
+            .viewable_state
+            .start
+            .unwrap_or(self.viewable_state.expiry);
+        let length = self
+            .viewable_state
+            .expiry
+            .duration_since(start)
+            .map_or(0, |length| length.millis());
+        if length == 0 {
+            return Some(dutch.end_price);
+        }
+
+        let mut elapsed = slot_time
+            .duration_since(start)
+            .map_or(0, |elapsed| elapsed.millis())
+            .min(length);
+        // Price reaches the end price at the auction end even if it's not at a step boundary
+        if let (PriceDecay::Stepwise(step), true) = (dutch.decay, elapsed < length) {
+            elapsed -= elapsed % step.millis().max(1);
+        }
+
+        let range = dutch
+            .start_price
+            .micro_ccd
+            .saturating_sub(dutch.end_price.micro_ccd);
+        let decrease = range as u128 * elapsed as u128 / length as u128;
+        Some(Amount::from_micro_ccd(
+            dutch.start_price.micro_ccd - decrease as u64,
+        ))
+    }
+
+    /// Phase of the auction at the given time.
+    pub fn phase(&self, slot_time: Timestamp) -> AuctionPhase {
+        match self.viewable_state.auction_state {
+            AuctionState::NotSoldYet => match self.viewable_state.start {
+                Some(start) if slot_time < start => AuctionPhase::Pending,
+                _ if slot_time > self.viewable_state.expiry => AuctionPhase::Ended,
+                _ => AuctionPhase::Active,
+            },
+            AuctionState::Sold(_) | AuctionState::AwaitingDelivery(_) => AuctionPhase::Finalized,
+            AuctionState::Canceled => AuctionPhase::Canceled,
+        }
+    }
+
+    /// Push the auction end forward if a bid is placed within the extension window before it, so that other bidders
+    /// have time to respond to last moment bids. Returns the new auction end if it was extended.
+    pub fn extend_expiry(&mut self, slot_time: Timestamp) -> Option<Timestamp> {
+        let viewable_state = &mut self.viewable_state;
+        if viewable_state.extension_window.millis() == 0 {
+            return None;
+        }
+
+        let window_start = viewable_state
+            .expiry
+            .checked_sub(viewable_state.extension_window)
+            .unwrap_or_else(|| Timestamp::from_timestamp_millis(0));
+        if slot_time < window_start {
+            return None;
+        }
+
+        let expiry = slot_time.checked_add(viewable_state.extension_duration)?;
+        if expiry <= viewable_state.expiry {
+            return None;
+        }
+        viewable_state.expiry = expiry;
+        Some(expiry)
+    }
+
+    /// Ensure the bid amount is a multiple of the bid granularity. Coarse bids prevent micro-increment bidding wars.
+    fn ensure_granular(&self, amount: Amount) -> ContractResult<()> {
+        ensure_eq!(
+            amount.micro_ccd % self.viewable_state.bid_granularity.micro_ccd,
+            0,
+            CustomContractError::InvalidAmountGranularity.into()
+        );
+        Ok(())
+    }
+
+    /// Ensure the amount exceeds the highest bid, and by at least the minimum increment once a bid was placed.
+    fn ensure_outbids(&self, amount: Amount) -> ContractResult<()> {
+        let highest_bid = self.viewable_state.highest_bid;
+        let min_bid = match self.highest_bidder {
+            Some(_) => highest_bid
+                .micro_ccd
+                .saturating_add(self.viewable_state.min_increment.micro_ccd),
+            None => 0,
+        };
+        ensure!(
+            amount > highest_bid && amount.micro_ccd >= min_bid,
+            CustomContractError::BidTooLow.into()
+        );
+        Ok(())
+    }
+
+    /// Check if the highest bid meets the reserve price, so that the item can be sold.
+    pub fn reserve_met(&self) -> bool {
+        self.viewable_state.highest_bid >= self.viewable_state.reserve_price
+    }
+
+    /// Total amount the bidder has sent to the contract. For proxy bidders this is their maximum, for others it's
+    /// equal to their bid.
+    pub fn deposit(&self, bidder: &AccountAddress) -> Amount {
+        match self.proxy_max.get(bidder) {
+            Some(max) => *max,
+            None => self.bids.get(bidder).map_or(Amount::zero(), |bid| *bid),
+        }
+    }
+
+    /// Add amount to the total bid of the bidder. Total bid must exceed the highest bid.
+    /// Returns `true` if this is the first bid of the bidder.
+    pub fn place_bid(
+        &mut self,
+        bidder: AccountAddress,
+        amount: Amount,
+        slot_time: Timestamp,
+    ) -> ContractResult<bool> {
+        self.ensure_english()?;
+        self.ensure_accepting_bids(slot_time)?;
+        self.ensure_granular(amount)?;
+
+        let first_bid = self.bids.get(&bidder).is_none();
+        // Direct bid reveals the whole deposit, even if the bidder placed proxy bids before
+        let total = self.deposit(&bidder) + amount;
+
+        // Ensure that the new bid exceeds the highest bid so far
+        self.ensure_outbids(total)?;
+        self.proxy_max.remove(&bidder);
+        self.bids.insert(bidder, total);
+        self.challenge(bidder, total);
+
+        if first_bid {
+            self.viewable_state.participants += 1;
+        }
+
+        Ok(first_bid)
+    }
+
+    /// Add amount to the maximum the bidder is willing to pay. The bid of the bidder is only raised as far as needed
+    /// to beat the other bidders. Maximum must exceed the highest bid.
+    /// Returns `true` if this is the first bid of the bidder.
+    pub fn place_proxy_bid(
+        &mut self,
+        bidder: AccountAddress,
+        amount: Amount,
+        slot_time: Timestamp,
+    ) -> ContractResult<bool> {
+        self.ensure_english()?;
+        self.ensure_accepting_bids(slot_time)?;
+        self.ensure_granular(amount)?;
+
+        let first_bid = self.bids.get(&bidder).is_none();
+        let max = self.deposit(&bidder) + amount;
+        self.ensure_outbids(max)?;
+        self.proxy_max.insert(bidder, max);
+
+        if self.highest_bidder == Some(bidder) {
+            // Leader only raises the maximum, the current bid is still enough unless it's below the reserve
+            let bid = max.min(self.viewable_state.reserve_price);
+            if bid > self.viewable_state.highest_bid {
+                self.bids.insert(bidder, bid);
+                self.viewable_state.highest_bid = bid;
+            }
+            return Ok(first_bid);
+        }
+
+        self.challenge(bidder, max);
+        if first_bid {
+            self.viewable_state.participants += 1;
+        }
+
+        Ok(first_bid)
+    }
+
+    /// Buy the item of a Dutch auction at the current price, which the amount must cover. The buyer becomes the highest
+    /// bidder with the price as the bid, and the rest of the amount can be withdrawn after settlement. Returns the price.
+    pub fn buy(
+        &mut self,
+        buyer: AccountAddress,
+        amount: Amount,
+        slot_time: Timestamp,
+    ) -> ContractResult<Amount> {
+        self.ensure_accepting_bids(slot_time)?;
+        let price = self
+            .dutch_price(slot_time)
+            .ok_or(CustomContractError::OperationNotPermitted)?;
+        ensure!(
+            amount >= price,
+            CustomContractError::InsufficientFunds.into()
+        );
+
+        self.bids.insert(buyer, amount);
+        self.highest_bidder = Some(buyer);
+        self.viewable_state.highest_bid = price;
+        self.viewable_state.participants += 1;
+        Ok(price)
+    }
+
+    /// Resolve a challenge of the highest bidder by a bidder with the given maximum, which must exceed the highest bid.
+    /// Proxy bidders bid only one step more than needed to beat the other side, up to their maximum, and at least the
+    /// reserve price if their maximum allows. The step is the minimum increment, or the bid granularity if it's
+    /// larger. If both maximums are equal, the earlier bidder keeps the lead.
+    fn challenge(&mut self, challenger: AccountAddress, challenger_max: Amount) {
+        let challenger_is_proxy = self.proxy_max.get(&challenger).is_some();
+        let leader = self
+            .highest_bidder
+            .filter(|leader| *leader != challenger)
+            .map(|leader| (leader, self.deposit(&leader)));
+
+        let outcome = resolve_challenge(
+            leader.map(|(_, leader_max)| leader_max),
+            self.viewable_state.highest_bid,
+            challenger_max,
+            challenger_is_proxy,
+            self.viewable_state
+                .bid_granularity
+                .max(self.viewable_state.min_increment),
+            self.viewable_state.reserve_price,
+        );
+
+        self.bids.insert(challenger, outcome.challenger_bid);
+        if let (Some((leader, _)), Some(bid)) = (leader, outcome.leader_bid) {
+            self.bids.insert(leader, bid);
+        }
+        if outcome.challenger_leads {
+            self.highest_bidder = Some(challenger);
+        }
+        self.viewable_state.highest_bid = outcome.highest_bid;
+    }
+
+    /// Record the refund of the bidder as withdrawn once the auction is over and return its amount. Bidders get back
+    /// their whole deposit, except for the winner of a sale who gets back the unused part of it. The winning bid stays
+    /// as the deposit of the winner until it's paid to the owner or claimed back.
+    pub fn withdraw(&mut self, bidder: AccountAddress) -> ContractResult<Amount> {
+        let winner = match self.viewable_state.auction_state {
+            AuctionState::NotSoldYet => bail!(CustomContractError::AuctionStillActive.into()),
+            AuctionState::Sold(winner) | AuctionState::AwaitingDelivery(winner) => Some(winner),
+            AuctionState::Canceled => None,
+        };
+        let is_winner = winner == Some(bidder);
+        let highest_bid = self.viewable_state.highest_bid;
+
+        let refund = settlement_refund(self.deposit(&bidder), highest_bid, is_winner)?;
+        ensure!(
+            refund > Amount::zero(),
+            CustomContractError::NotFound.into()
+        );
+
+        self.proxy_max.remove(&bidder);
+        if is_winner {
+            self.bids.insert(bidder, highest_bid);
+        } else {
+            self.bids.remove(&bidder);
         }
+        Ok(refund)
     }
 }
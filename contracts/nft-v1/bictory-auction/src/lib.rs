@@ -1,5 +1,10 @@
 //! # Implementation of an auction smart contract
 //!
+//! A single contract instance hosts any number of auctions. Sellers create
+//! auctions with `createAuction`, and every other function selects the
+//! auction by the returned identifier. Operators of the seller, e.g. a listing
+//! contract, create auctions on the seller's behalf with `createAuctionFor`.
+//!
 //! To bid, participants send CCD using the bid function.
 //! The participant with the highest bid wins the auction.
 //! Bids are to be placed before the auction end. After that, bids are refused.
@@ -14,10 +19,31 @@
 //! After the auction end, any account can finalize the auction.
 //! The auction can be finalized only once.
 //! When the auction is finalized, every participant except the
-//! winner gets their money back.
+//! winner can withdraw their money with `withdraw`.
+//!
+//! Bidders can also place proxy bids with `proxyBid`: the attached amount is
+//! the maximum they are willing to pay, and their bid is only raised as far as
+//! needed to stay ahead of other bidders. Unused part of the maximum is
+//! returned on finalization.
+//!
+//! A minimum increment can be required between successive highest bids, and a
+//! reserve price below which the item is not sold. If the reserve is not met,
+//! all bids are returned on finalization and the seller keeps the item.
+//!
+//! To prevent sniping, bids placed within the extension window before the
+//! auction end push the end forward, giving other bidders time to respond.
+//!
+//! Auctions created with a CIS-2 payment token accept bids in that token
+//! instead of CCD. Bidders transfer the tokens to the auction, which receives
+//! them in `onReceivingCIS2`, and refunds and the winning bid are paid out
+//! with CIS-2 transfers.
+//!
+//! Auctions are English auctions by default. Dutch auctions don't accept
+//! bids; instead, the price falls over time and the first account to `buy`
+//! at the current price wins the item right away.
 #![cfg_attr(not(feature = "std"), no_std)]
 use crate::{events::*, structs::*};
-use commons::*;
+use commons::{bictory_nft::view::*, *};
 use concordium_cis2::*;
 use concordium_std::*;
 use core::fmt::Debug;
@@ -12,18 +12,96 @@ pub enum AuctionState {
     Sold(AccountAddress), // winning account's address
     /// The auction is cancelled
     Canceled,
+    /// The auction is over and bids are settled, but the NFT contract rejected the transfer of the item to the
+    /// indicated address. The winning bid is held until the item is redelivered or claimed back by the winner.
+    AwaitingDelivery(AccountAddress),
 }
 
+/// How the price of an auction is found.
+#[derive(Debug, Serialize, SchemaType, Eq, PartialEq, Clone)]
+pub enum AuctionKind {
+    /// Bidders outbid each other until the auction end and the highest bid wins
+    English,
+    /// The price falls from the start to the end of the auction and the first buyer accepting it wins
+    Dutch(DutchAuction),
+}
+
+/// Price schedule of a Dutch auction.
+#[derive(Debug, Serialize, SchemaType, Eq, PartialEq, Clone)]
+pub struct DutchAuction {
+    /// Price at the auction start
+    pub start_price: Amount,
+    /// Price at the auction end, must not exceed the start price
+    pub end_price: Amount,
+    pub decay: PriceDecay,
+}
+
+/// How the price of a Dutch auction falls between the auction start and end.
+#[derive(Debug, Serialize, SchemaType, Eq, PartialEq, Clone, Copy)]
+pub enum PriceDecay {
+    /// Price falls continuously
+    Linear,
+    /// Price falls in steps of the given duration, staying the same within a step
+    Stepwise(Duration),
+}
+
+/// Phase of an auction at the time it is viewed.
+#[derive(Debug, Serialize, SchemaType, Eq, PartialEq, Clone, Copy)]
+pub enum AuctionPhase {
+    /// The auction has a start time in the future and doesn't accept bids yet
+    Pending,
+    /// The auction is accepting bids
+    Active,
+    /// The auction end has passed, but nobody has finalized the auction yet
+    Ended,
+    /// The auction was finalized and the item was sold
+    Finalized,
+    /// The auction was canceled or finalized without a sale
+    Canceled,
+}
+
+/// Identifier of an auction within the contract instance, assigned in the order auctions are created.
+pub type AuctionId = u64;
+
 /// The state of the smart contract.
 /// This is the state that will be shown when the contract is queried using
 /// `concordium-client contract show`.
 #[derive(Serial, DeserialWithState)]
 #[concordium(state_parameter = "S")]
 pub struct State<S: HasStateApi> {
+    /// Auctions created in this instance
+    pub auctions: StateMap<AuctionId, AuctionData<S>, S>,
+    /// Identifier assigned to the next created auction
+    pub next_auction_id: AuctionId,
+    /// Latest auction created for each item, used to reject a second auction while the first one is live
+    pub item_auctions: StateMap<Token, AuctionId, S>,
+    pub authority: Authority<S>,
+    /// Configuration set by admins that applies to all auctions
+    pub config: AuctionConfig,
+    /// Ed25519 public keys used by accounts to sign relayed bids
+    pub bid_keys: StateMap<AccountAddress, [u8; 32], S>,
+    /// Next expected nonce of relayed bids for each account
+    pub nonces: StateMap<AccountAddress, u64, S>,
+}
+
+/// The state of a single auction.
+#[derive(Serial, DeserialWithState)]
+#[concordium(state_parameter = "S")]
+pub struct AuctionData<S: HasStateApi> {
     /// The part of the state that can be viewed
     pub viewable_state: ViewableState,
     /// Keeping track of which account bid how much money
     pub bids: StateMap<AccountAddress, Amount, S>,
+    /// Maximum amounts of proxy bidders. Proxy bidders deposit their maximum, while their bid in `bids` is only
+    /// raised as far as needed to beat other bidders.
+    pub proxy_max: StateMap<AccountAddress, Amount, S>,
+    /// Account currently holding the highest bid
+    pub highest_bidder: Option<AccountAddress>,
+    /// Metadata URL of the item queried from the NFT contract on creation
+    pub metadata_url: Option<String>,
+    /// Royalties recorded in the item, queried from the NFT contract on creation and paid from the winning bid on
+    /// settlement
+    pub royalties: Vec<Royalty>,
 }
 
 /// The part of the state to be viewed using `concordium-client contract invoke`
@@ -37,19 +115,163 @@ pub struct ViewableState {
     /// The sold item (to be displayed to the auction participants), encoded in
     /// ASCII
     pub item: Token,
+    /// Account that created the auction and receives the winning bid
+    pub seller: AccountAddress,
     /// Expiration time of the auction at which bids will be closed (to be
     /// displayed to the auction
     pub expiry: Timestamp,
-    /// Flag to check wheather auction contract is authorized to perform transaction
-    /// on NFT contract or not
-    pub is_authorised: bool,
+    /// Share of the highest bid paid by the seller to the highest bidder if the
+    /// auction is canceled after bids were placed
+    pub cancellation_penalty: Percentage,
+    /// Number of unique accounts that have placed a bid
+    pub participants: u32,
+    /// Bid amounts must be multiples of this amount. Also the step by which proxy bids are raised
+    pub bid_granularity: Amount,
+    /// CIS-2 token bids are paid in. `None` for CCD auctions
+    pub payment_token: Option<Token>,
+    /// Lowest winning bid. The item is not sold if the highest bid is below the reserve
+    pub reserve_price: Amount,
+    /// Amount every new highest bid must exceed the previous one by
+    pub min_increment: Amount,
+    /// Bids placed this close to the auction end extend the auction
+    pub extension_window: Duration,
+    /// Time the auction lasts after an extending bid
+    pub extension_duration: Duration,
+    /// Share of the winning bid paid to Bictory on settlement, taken from the contract configuration on creation
+    pub bictory_royalty: Percentage,
+    /// Time bids are accepted from. `None` if bids were accepted since creation
+    pub start: Option<Timestamp>,
+    /// English or Dutch auction
+    pub kind: AuctionKind,
+}
+
+/// Configuration of the auction contract, set by admins.
+#[derive(Debug, Serialize, SchemaType, Clone)]
+pub struct AuctionConfig {
+    /// Storage the final price of every sale is published to
+    pub price_registry: Option<PriceRegistry>,
+    /// Share of the winning bid paid to Bictory on settlement of auctions created afterwards. Auctions with a payment
+    /// token pay no Bictory share, since the share is sent to the Bictory config contract in CCD
+    pub bictory_royalty: Percentage,
+}
+
+/// BictoryStorage prefix final prices are published to, so that other contracts can read recent sale prices without
+/// indexing logs.
+#[derive(Debug, Serialize, SchemaType, Clone)]
+pub struct PriceRegistry {
+    /// BictoryStorage contract. The auction has to be a writer allowed to modify the prefix
+    pub contract: ContractAddress,
+    /// Prefix sale prices are written under, keyed by the sold token
+    pub prefix: Bytes,
+}
+
+/// Sale price published to the price registry.
+#[derive(Debug, Serialize, SchemaType, PartialEq, Eq)]
+pub struct SalePrice {
+    /// Winning bid
+    pub price: Amount,
+    pub buyer: AccountAddress,
+    /// Time the auction was finalized
+    pub sold_at: Timestamp,
+    /// Auction contract the token was sold in
+    pub auction: ContractAddress,
+    /// Auction the token was sold in within the auction contract
+    pub auction_id: AuctionId,
 }
 
-/// Type of the parameter to the `init` function.
+/// Bid intent signed by the bidder.
+#[derive(Debug, Serialize, SchemaType, Clone, Copy)]
+pub struct BidIntent {
+    /// Auction the bid is placed in
+    pub auction_id: AuctionId,
+    /// Account the bid is placed for
+    pub bidder: AccountAddress,
+    /// Amount to add to the bidder's total bid
+    pub amount: Amount,
+    /// Bidder's relayed bid counter, prevents replaying the intent
+    pub nonce: u64,
+}
+
+/// Bid intent together with the Ed25519 signature of the bidder.
+/// Signed message is the serialized auction contract address followed by the serialized intent.
+#[derive(Serialize, SchemaType)]
+pub struct SignedBid {
+    pub intent: BidIntent,
+    pub signature: [u8; 64],
+}
+
+/// Type of the parameter to the `relayBids` function.
 #[derive(Serialize, SchemaType)]
-pub struct InitParameter {
+pub struct RelayBidsParams {
+    pub bids: Vec<SignedBid>,
+}
+
+/// Type of the parameter to the `createAuction` function.
+#[derive(Serialize, SchemaType)]
+pub struct CreateAuctionParams {
     /// The item to be sold.
     pub item: Token,
     /// Time of the auction end in the RFC 3339 format (https://tools.ietf.org/html/rfc3339)
     pub expiry: Timestamp,
+    /// Share of the highest bid to be paid to the highest bidder on cancellation
+    pub cancellation_penalty: Percentage,
+    /// Bid amounts must be multiples of this amount, e.g. 1 CCD to only accept whole CCD bids. Must not be zero
+    pub bid_granularity: Amount,
+    /// CIS-2 token bids are paid in instead of CCD. Amounts of the auction are then counted in the smallest unit of
+    /// the token. Cancellation penalty must be zero, since the owner can't attach tokens to `cancel`
+    pub payment_token: Option<Token>,
+    /// Lowest winning bid, zero for no reserve. Must be a multiple of the bid granularity
+    pub reserve_price: Amount,
+    /// Amount every new highest bid must exceed the previous one by, zero for any higher bid. Must be a multiple of
+    /// the bid granularity
+    pub min_increment: Amount,
+    /// Bids placed this close to the auction end extend the auction, zero to never extend it
+    pub extension_window: Duration,
+    /// Time the auction lasts after an extending bid
+    pub extension_duration: Duration,
+    /// Time bids are accepted from in the RFC 3339 format, `None` to accept bids right away. Must be before the expiry
+    pub start: Option<Timestamp>,
+    /// English or Dutch auction. Dutch auctions must not have a payment token, and start falling in price at the
+    /// creation time if no start is given
+    pub kind: AuctionKind,
+}
+
+/// Type of the parameter to the `createAuctionFor` function.
+#[derive(Serialize, SchemaType)]
+pub struct CreateAuctionForParams {
+    /// Account owning the item, which becomes the seller
+    pub seller: AccountAddress,
+    pub auction: CreateAuctionParams,
+}
+
+/// Kind of bid placed by transferring payment tokens to the auction.
+#[derive(Debug, Serialize, SchemaType, Clone, Copy, PartialEq, Eq)]
+pub enum TokenBid {
+    Bid,
+    ProxyBid,
+}
+
+/// Bid placed by transferring payment tokens to the auction contract, passed as `data` of the CIS-2 transfer.
+#[derive(Debug, Serialize, SchemaType, Clone, Copy, PartialEq, Eq)]
+pub struct TokenBidData {
+    /// Auction the bid is placed in
+    pub auction_id: AuctionId,
+    pub kind: TokenBid,
+}
+
+/// Type of the parameter to the `viewAuctions` function. Since return value size is limited, `skip` and `show` select
+/// the part of the auctions to return.
+#[derive(Serialize, SchemaType)]
+pub struct ViewAuctionsParams {
+    pub skip: u32,
+    pub show: u32,
+}
+
+/// Auction returned by `view` and `viewAuctions`.
+#[derive(Debug, Serialize, SchemaType, Clone)]
+pub struct AuctionView {
+    pub auction_id: AuctionId,
+    /// Phase of the auction at the time of the query
+    pub phase: AuctionPhase,
+    pub state: ViewableState,
 }
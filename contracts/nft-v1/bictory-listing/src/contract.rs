@@ -1,25 +1,34 @@
 use super::*;
 
+/// Standards supported by this contract.
+const SUPPORTED_STANDARDS: [StandardIdentifier<'static>; 2] = [
+    CIS0_STANDARD_IDENTIFIER,
+    BICTORY_AUTHORITY_STANDARD_IDENTIFIER,
+];
+
 /// Initialize the listing contract with an empty list of listings.
 #[init(contract = "BictoryListing")]
 fn init<S: HasStateApi>(
-    _ctx: &impl HasInitContext,
+    ctx: &impl HasInitContext,
     state_builder: &mut StateBuilder<S>,
 ) -> InitResult<State<S>> {
-    Ok(State::empty(state_builder))
+    Ok(State::empty(state_builder, ctx.init_origin()))
 }
 
 /// List or update the price of a list of NFTs.
 ///
 /// During this operation, the contract address of this contract will be
-/// added as operator.
+/// added as operator. With curation on, the listing can be bought only after
+/// a maintainer approves it. Listings priced in a CIS-2 token are bought by
+/// transferring the token to this contract, see `onReceivingCIS2`.
 ///  
-/// Will reject if not send by the NFT owner or if it fails to parse the
-/// parameter.
+/// Will reject if not send by the NFT owner, if it fails to parse the
+/// parameter, if the jurisdiction is not in the tax table or if the contract
+/// is paused.
 #[receive(
     contract = "BictoryListing",
     name = "list",
-    parameter = "ListParams",
+    parameter = "ListingParams",
     mutable,
     enable_logger
 )]
@@ -28,13 +37,54 @@ fn list<S: HasStateApi, V: Read>(
     host: &mut impl HasHost<State<S>, StateApiType = S, ReturnValueType = V>,
     logger: &mut impl HasLogger,
 ) -> ContractResult<()> {
+    let params: ListingParams = ctx.parameter_cursor().get()?;
+    list_token(ctx, host, logger, params)
+}
+
+/// List several NFTs of the sender in one transaction. Each token is listed the same way as with `list`, and the whole
+/// batch is rejected if any of the tokens can't be listed.
+#[receive(
+    contract = "BictoryListing",
+    name = "listBatch",
+    parameter = "Vec<ListingParams>",
+    mutable,
+    enable_logger
+)]
+fn list_batch<S: HasStateApi, V: Read>(
+    ctx: &impl HasReceiveContext,
+    host: &mut impl HasHost<State<S>, StateApiType = S, ReturnValueType = V>,
+    logger: &mut impl HasLogger,
+) -> ContractResult<()> {
+    let listings: Vec<ListingParams> = ctx.parameter_cursor().get()?;
+    for params in listings {
+        list_token(ctx, host, logger, params)?;
+    }
+
+    Ok(())
+}
+
+fn list_token<S: HasStateApi, V: Read>(
+    ctx: &impl HasReceiveContext,
+    host: &mut impl HasHost<State<S>, StateApiType = S, ReturnValueType = V>,
+    logger: &mut impl HasLogger,
+    params: ListingParams,
+) -> ContractResult<()> {
+    host.state().ensure_not_paused()?;
+
     let sender = ctx.sender();
     let owner = get_account_address(sender)?;
-    let params: ListParams = ctx.parameter_cursor().get()?;
+
+    // Ensuring tax can be calculated for the jurisdiction
+    if let Some(jurisdiction) = &params.jurisdiction {
+        ensure!(
+            host.state().taxes.get(jurisdiction).is_some(),
+            CustomContractError::NotFound.into()
+        );
+    }
 
     // Ensuring token is not already listed for sale
     ensure!(
-        host.state().listings.contains(&params.token),
+        !host.state().is_listed(&params.token),
         CustomContractError::TokenAlreadyListedForSale.into()
     );
 
@@ -65,7 +115,16 @@ fn list<S: HasStateApi, V: Read>(
         // Update price
         get_update_price_action(host, &params.token.clone(), nft_data.price)?;
 
-        host.state_mut().list(&params.token, owner, nft_data)?;
+        let (state, state_builder) = host.state_and_builder();
+        state.list(
+            &params.token,
+            owner,
+            nft_data,
+            params.jurisdiction.clone(),
+            None,
+            params.payment.clone(),
+            state_builder,
+        )?;
 
         // Event for listing NFT.
         logger.log(&CustomEvent::Listing(params))?;
@@ -76,7 +135,143 @@ fn list<S: HasStateApi, V: Read>(
     Ok(())
 }
 
-/// Remove NFTs from the listing.
+/// Register Ed25519 public key of the sender account, that is used to verify consignment agreements signed by the
+/// account. Registering a new key replaces the previous one.
+#[receive(
+    contract = "BictoryListing",
+    name = "registerConsignorKey",
+    parameter = "[u8; 32]",
+    mutable
+)]
+fn register_consignor_key<S: HasStateApi>(
+    ctx: &impl HasReceiveContext,
+    host: &mut impl HasHost<State<S>, StateApiType = S>,
+) -> ContractResult<()> {
+    let sender = get_account_address(ctx.sender())?;
+    let public_key: [u8; 32] = ctx.parameter_cursor().get()?;
+
+    host.state_mut().consignor_keys.insert(sender, public_key);
+
+    Ok(())
+}
+
+/// List an NFT held by a custodian on behalf of the owner. The owner signs a consignment agreement with the listing
+/// price and the custodian's commission. At buy the commission is deducted from the owner's share and paid to the
+/// custodian.
+///
+/// The custodian has to add this contract as an operator of the token beforehand.
+///
+/// Rejects if:
+/// - The contract is paused.
+/// - Not send by the custodian of the agreement.
+/// - It fails to parse the parameter.
+/// - The owner's signature or nonce is invalid.
+/// - The commission exceeds 100%.
+/// - The jurisdiction is not in the tax table.
+/// - The token is already listed or not held by the custodian.
+#[receive(
+    contract = "BictoryListing",
+    name = "listConsigned",
+    parameter = "ListConsignedParams",
+    mutable,
+    enable_logger,
+    crypto_primitives
+)]
+fn list_consigned<S: HasStateApi, V: Read>(
+    ctx: &impl HasReceiveContext,
+    host: &mut impl HasHost<State<S>, StateApiType = S, ReturnValueType = V>,
+    logger: &mut impl HasLogger,
+    crypto_primitives: &impl HasCryptoPrimitives,
+) -> ContractResult<()> {
+    host.state().ensure_not_paused()?;
+
+    let sender = ctx.sender();
+    let params: ListConsignedParams = ctx.parameter_cursor().get()?;
+    let agreement = params.agreement;
+
+    ensure!(
+        sender == agreement.consignment.custodian,
+        ContractError::Unauthorized
+    );
+    ensure!(
+        agreement.consignment.commission <= Percentage::HUNDRED,
+        CustomContractError::InvalidFields.into()
+    );
+
+    let state = host.state_mut();
+    let public_key = *state
+        .consignor_keys
+        .get(&agreement.owner)
+        .ok_or(CustomContractError::InvalidSignature)?;
+
+    // Signature is bound to this listing instance to prevent replaying it on other listing contracts
+    let message = to_bytes(&(ctx.self_address(), &agreement));
+    ensure!(
+        crypto_primitives.verify_ed25519_signature(
+            PublicKeyEd25519(public_key),
+            SignatureEd25519(params.signature),
+            &message
+        ),
+        CustomContractError::InvalidSignature.into()
+    );
+    state.use_nonce(agreement.owner, agreement.nonce)?;
+
+    // Ensuring tax can be calculated for the jurisdiction
+    if let Some(jurisdiction) = &params.jurisdiction {
+        ensure!(
+            state.taxes.get(jurisdiction).is_some(),
+            CustomContractError::NotFound.into()
+        );
+    }
+
+    // Ensuring token is not already listed for sale
+    ensure!(
+        !state.is_listed(&agreement.token),
+        CustomContractError::TokenAlreadyListedForSale.into()
+    );
+
+    // Getting token info of the custodian from NFT contract
+    let parameter = ViewTokenParams {
+        owner: sender,
+        token_id: agreement.token.id.clone(),
+    };
+    let entrypoint_name = EntrypointName::new_unchecked("viewToken");
+    let (_, value) = host.invoke_contract(
+        &agreement.token.contract,
+        &parameter,
+        entrypoint_name,
+        Amount::zero(),
+    )?;
+    let mut owned_data = value.ok_or(ContractError::InvalidTokenId)?;
+    let mut nft_data = NFTData::deserial(&mut owned_data)?;
+    nft_data.price = agreement.price;
+
+    // Update price
+    get_update_price_action(host, &agreement.token, agreement.price)?;
+
+    let (state, state_builder) = host.state_and_builder();
+    state.list(
+        &agreement.token,
+        agreement.owner,
+        nft_data,
+        params.jurisdiction.clone(),
+        Some(agreement.consignment),
+        None,
+        state_builder,
+    )?;
+
+    // Event for listing NFT.
+    logger.log(&CustomEvent::Listing(ListingParams {
+        token: agreement.token,
+        owner: agreement.owner,
+        jurisdiction: params.jurisdiction,
+        payment: None,
+    }))?;
+
+    Ok(())
+}
+
+/// Remove NFTs from the listing. Pending listings are withdrawn as a whole.
 ///
 /// Remember that operator will not be removed during this
 /// operation as their might be another listing operations that
@@ -84,7 +279,7 @@ fn list<S: HasStateApi, V: Read>(
 /// remove the operator.
 ///
 /// Rejects if
-/// - Not send by the NFT owner.
+/// - Not send by the NFT owner or, for consigned listings, the custodian.
 /// - It fails to parse the parameter.
 /// - Any of the tokens are not listed.
 #[receive(
@@ -102,13 +297,27 @@ fn unlist<S: HasStateApi>(
     let sender = ctx.sender();
     let unlisting: ListParams = ctx.parameter_cursor().get()?;
 
-    // Ensuring only owner of NFT can unlist the tokens
+    // Ensuring only owner of NFT or the custodian of a consigned NFT can unlist the tokens
+    let is_custodian = host
+        .state()
+        .listing(&unlisting.token)
+        .and_then(|listing| listing.consignment)
+        .map_or(false, |consignment| consignment.custodian == sender);
     ensure!(
-        sender.matches_account(&unlisting.owner),
+        is_custodian || sender.matches_account(&unlisting.owner),
         CustomContractError::OnlyOwner.into()
     );
 
-    host.state_mut().unlist(&unlisting.token)?;
+    // Pending listings can't be opted into auto-auction, so there is no bounty to return
+    if host
+        .state_mut()
+        .pending
+        .remove_and_get(&unlisting.token)
+        .is_none()
+    {
+        let nft_details = host.state_mut().unlist(&unlisting.token)?;
+        refund_bounty(host, &nft_details)?;
+    }
 
     // Event for unlisting NFT.
     logger.log(&CustomEvent::Unlisting(unlisting))?;
@@ -116,13 +325,17 @@ fn unlist<S: HasStateApi>(
     Ok(())
 }
 
-/// Buy one of the listed NFTs.
+/// Buy one of the listed NFTs. Sales tax of the listing's jurisdiction is added on top of the listed price and remitted
+/// to the tax collector. For consigned listings the token is transferred from the custodian, which receives its
+/// commission from the owner's share.
 ///
 /// Rejects if:
+/// - The contract is paused.
 /// - Sender is a contract address.
 /// - It fails to parse the parameter.
 /// - The token is not listed
-/// - The amount is less then the listed price.
+/// - The listing is paid in a CIS-2 token.
+/// - The amount is less then the listed price with tax.
 /// - The NFT contract transfer rejects.
 #[receive(
     contract = "BictoryListing",
@@ -140,162 +353,2731 @@ fn buy<S: HasStateApi>(
 ) -> ContractResult<()> {
     let sender = get_account_address(ctx.sender())?;
     let params: BuyParams = ctx.parameter_cursor().get()?;
-    let nft_details = host.state_mut().unlist(&params.token)?;
+    buy_token(
+        ctx,
+        host,
+        logger,
+        sender,
+        params.token,
+        Funds {
+            currency: None,
+            amount: price,
+        },
+    )?;
+
+    Ok(())
+}
+
+/// Buy several listed NFTs in one transaction. Each token is bought the same way as with `buy`, with its own tax,
+/// royalty split and `BuyEvent`. The attached amount must cover the prices with tax of all tokens.
+///
+/// Rejects if:
+/// - The contract is paused.
+/// - Sender is a contract address.
+/// - It fails to parse the parameter.
+/// - Any of the tokens is not listed.
+/// - The amount is less then the total of the listed prices with tax.
+/// - Any NFT contract transfer rejects.
+#[receive(
+    contract = "BictoryListing",
+    name = "buyBatch",
+    parameter = "BuyBatchParams",
+    mutable,
+    enable_logger,
+    payable
+)]
+fn buy_batch<S: HasStateApi>(
+    ctx: &impl HasReceiveContext,
+    host: &mut impl HasHost<State<S>, StateApiType = S>,
+    amount: Amount,
+    logger: &mut impl HasLogger,
+) -> ContractResult<()> {
+    let sender = get_account_address(ctx.sender())?;
+    let params: BuyBatchParams = ctx.parameter_cursor().get()?;
+
+    let mut remaining = amount;
+    for token in params.tokens {
+        let cost = buy_token(
+            ctx,
+            host,
+            logger,
+            sender,
+            token,
+            Funds {
+                currency: None,
+                amount: remaining,
+            },
+        )?;
+        remaining -= cost;
+    }
+
+    Ok(())
+}
+
+/// Buy a listed NFT with the funds, which must be in the currency of the listing, and return the price with tax paid
+/// for it. Shares are paid out in the same currency, or held in escrow if the escrow window is set.
+fn buy_token<S: HasStateApi>(
+    ctx: &impl HasReceiveContext,
+    host: &mut impl HasHost<State<S>, StateApiType = S>,
+    logger: &mut impl HasLogger,
+    sender: AccountAddress,
+    token: Token,
+    funds: Funds,
+) -> ContractResult<Amount> {
+    host.state().ensure_not_paused()?;
+
+    let nft_details = host.state_mut().unlist(&token)?;
+    ensure!(
+        nft_details.payment_token == funds.currency,
+        CustomContractError::OperationNotPermitted.into()
+    );
 
-    // Ensuring price of NFT is lesser or equal to the amount passed
-    ensure!(nft_details.price <= price, ContractError::InsufficientFunds);
+    let tax_rule = host.state().tax_rule(&nft_details);
+    let tax = tax_rule.map_or(Amount::zero(), |rule| rule.rate * nft_details.price);
+
+    // Ensuring price of NFT with tax is lesser or equal to the amount passed
+    ensure!(
+        nft_details.price + tax <= funds.amount,
+        ContractError::InsufficientFunds
+    );
 
     // Ensuring anyone can buy except owner
     ensure!(sender.ne(&nft_details.owner), ContractError::Unauthorized);
 
-    // Transfer action
-    let transfer = Transfer {
-        token_id: params.token.id.clone(),
-        amount: ContractTokenAmount::from(1),
-        from: Address::Account(nft_details.owner),
-        to: Receiver::Account(sender),
-        data: AdditionalData::empty(),
+    let from = nft_details
+        .consignment
+        .map_or(Address::Account(nft_details.owner), |consignment| {
+            consignment.custodian
+        });
+    refund_bounty(host, &nft_details)?;
+    let cost = nft_details.price + tax;
+    let sale = Sale {
+        token,
+        listing: nft_details,
+        buyer: sender,
+        tax,
+        tax_rule,
     };
-    let parameter = TransferParams(vec![transfer]);
-    let mut entrypoint_name = EntrypointName::new_unchecked("transfer");
-    host.invoke_contract(
-        &params.token.contract,
-        &parameter,
-        entrypoint_name,
-        Amount::zero(),
-    )?;
 
-    // Calculating shares
-    let mut shares = calc_shares(
+    match host.state().escrow_window {
+        None => {
+            transfer_token(host, &sale.token, from, Receiver::Account(sender))?;
+            settle_sale(host, logger, ctx.self_address(), sale)?;
+        }
+        Some(window) => {
+            let dispute_until = ctx
+                .metadata()
+                .slot_time()
+                .checked_add(window)
+                .ok_or(CustomContractError::Overflow)?;
+            let event = EscrowEvent {
+                token: sale.token.clone(),
+                seller: sale.listing.owner,
+                buyer: sender,
+            };
+            ensure!(
+                host.state().escrows.get(&event.token).is_none(),
+                CustomContractError::OperationNotPermitted.into()
+            );
+            // Escrow is stored first, so that the token is accepted into custody by `onReceivingCIS2`
+            host.state_mut().escrows.insert(
+                event.token.clone(),
+                Escrow {
+                    sale,
+                    dispute_until,
+                    disputed: false,
+                },
+            );
+            transfer_token(
+                host,
+                &event.token,
+                from,
+                Receiver::Contract(
+                    ctx.self_address(),
+                    OwnedEntrypointName::new_unchecked(String::from("onReceivingCIS2")),
+                ),
+            )?;
+            logger.log(&CustomEvent::EscrowOpened(event))?;
+        }
+    }
+
+    Ok(cost)
+}
+
+/// Royalty of the creator, who is the first royalty recipient of a sale.
+fn creator_share(shares: &Shares) -> Amount {
+    shares
+        .royalties
+        .first()
+        .map_or(Amount::zero(), |(_, share)| *share)
+}
+
+/// Pay out the shares of a sale, whose token was already delivered to the buyer, from the payment held by this
+/// contract.
+fn settle_sale<S: HasStateApi>(
+    host: &mut impl HasHost<State<S>, StateApiType = S>,
+    logger: &mut impl HasLogger,
+    self_address: ContractAddress,
+    sale: Sale,
+) -> ContractResult<()> {
+    let nft_details = sale.listing;
+    let currency = &nft_details.payment_token;
+
+    // Calculating shares. Bictory is paid the configured marketplace fee instead of a share of the price.
+    let shares = settle_shares(
         nft_details.price,
-        nft_details.creator_royalty as u64,
-        nft_details.minter_royalty as u64,
-        params.bictory_royalty as u64,
-    );
-    shares.adjust_owner_share();
+        &nft_details.royalty_recipients(),
+        Percentage::ZERO,
+    )?;
 
     // Balance Transfer
-    entrypoint_name = EntrypointName::new_unchecked("sendCCD");
-
-    // Hardcoding address of config smart contract for security reasons.
-    // In a rare case of Bictory's wallet address change this contract address must
-    // also be required to be updated.
-    let bictory_config_contract_address = ContractAddress {
-        index: 571,
-        subindex: 0,
-    };
-    host.invoke_contract(
-        &bictory_config_contract_address,
-        &"",
-        entrypoint_name,
-        shares.bictory,
+    let fee = pay_marketplace_fee(
+        host,
+        self_address,
+        currency,
+        nft_details.price,
+        shares.owner,
     )?;
+    let owner_share = shares.owner - fee;
 
-    host.invoke_transfer(&nft_details.owner, shares.owner)?;
+    // Custodian's commission is taken from the owner's share
+    let commission = nft_details
+        .consignment
+        .map_or(Amount::zero(), |consignment| {
+            consignment.commission * owner_share
+        });
+    pay(
+        host,
+        self_address,
+        currency,
+        &nft_details.owner,
+        owner_share - commission,
+    )?;
+    if let Some(consignment) = nft_details.consignment {
+        if commission > Amount::zero() {
+            pay(
+                host,
+                self_address,
+                currency,
+                &consignment.commission_account,
+                commission,
+            )?;
+        }
+    }
 
-    // Creator Royalty can be `0` thereby avoiding unnecessary gas fees.
-    if nft_details.creator_royalty != 0 {
-        host.invoke_transfer(&nft_details.creator, shares.creator)?;
+    // Royalties can be `0` thereby avoiding unnecessary gas fees.
+    for (beneficiary, share) in shares.royalties.iter() {
+        if *share > Amount::zero() {
+            pay(host, self_address, currency, beneficiary, *share)?;
+        }
     }
-    // Minter Royalty can be `0` thereby avoiding unnecessary gas fees.
-    if nft_details.minter_royalty != 0 {
-        host.invoke_transfer(&nft_details.minter, shares.minter)?;
+
+    if let Some(rule) = sale.tax_rule {
+        if sale.tax > Amount::zero() {
+            pay(host, self_address, currency, &rule.collector, sale.tax)?;
+        }
     }
 
     // Event for buying NFT.
     logger.log(&CustomEvent::Buy(BuyEvent {
-        token: params.token,
+        token: sale.token,
         seller: nft_details.owner,
-        buyer: sender,
-        owner_share: shares.owner,
-        creator_share: shares.creator,
+        buyer: sale.buyer,
+        owner_share: owner_share - commission,
+        creator_share: creator_share(&shares),
+        price: nft_details.price,
+        tax: sale.tax,
+        jurisdiction: nft_details.jurisdiction,
+        commission,
+        fee,
+        payment_token: nft_details.payment_token,
     }))?;
 
     Ok(())
 }
 
-/// Update the price of the listed NFT.
-///  
+/// Receive function in which accounts buy listings priced in a CIS-2 token. Buyers transfer the payment token to the
+/// listing contract with the listed `Token` as the transfer data. The transferred amount must cover the price with tax,
+/// the rest is returned to the buyer. The token is then bought the same way as with `buy`, with shares paid out in the
+/// payment token.
+///
+/// Bought NFTs transferred to this contract for an escrowed purchase are accepted into custody without transfer data.
+///
 /// Rejects if:
-/// - Sender is not NFT owner.
-/// - It fails to parse the parameter.
+/// - The contract is paused.
+/// - It fails to parse the parameter or the transfer data.
+/// - Tokens are sent from a contract address.
 /// - The token is not listed.
+/// - The transferred token is not the payment token of the listing.
+/// - The transferred amount is less then the listed price with tax.
+/// - The NFT contract transfer rejects.
 #[receive(
     contract = "BictoryListing",
-    name = "updatePrice",
-    parameter = "UpdateListingPrice",
-    mutable
+    name = "onReceivingCIS2",
+    parameter = "OnReceivingCis2Params<ContractTokenId, ContractTokenAmount>",
+    mutable,
+    enable_logger
 )]
-fn update_price<S: HasStateApi>(
+fn on_receiving_cis2<S: HasStateApi>(
     ctx: &impl HasReceiveContext,
     host: &mut impl HasHost<State<S>, StateApiType = S>,
+    logger: &mut impl HasLogger,
 ) -> ContractResult<()> {
-    let sender = ctx.sender();
-    let params: UpdateListingPrice = ctx.parameter_cursor().get()?;
+    let params: OnReceivingCis2Params<ContractTokenId, ContractTokenAmount> =
+        ctx.parameter_cursor().get()?;
 
-    get_update_price_action(host, &params.token, params.price)?;
+    // Transferred token is identified by the contract that invoked the hook
+    let received = match ctx.sender() {
+        Address::Contract(contract) => Token {
+            contract,
+            id: params.token_id,
+        },
+        Address::Account(_) => bail!(ContractError::Unauthorized),
+    };
+    if host.state().escrows.get(&received).is_some() {
+        return Ok(());
+    }
 
-    if let Some(mut listing) = host.state().listings.get_mut(&params.token) {
-        // Ensuring only owner of NFT can list and alter with parameters
-        // during listing.
-        ensure!(
-            sender.matches_account(&listing.owner),
-            CustomContractError::OnlyOwner.into()
-        );
+    let token: Token = from_bytes(params.data.as_ref())?;
+    let buyer = get_account_address(params.from)?;
+    let currency = received;
+    let amount = Amount::from_micro_ccd(params.amount.0);
+    let cost = buy_token(
+        ctx,
+        host,
+        logger,
+        buyer,
+        token,
+        Funds {
+            currency: Some(currency.clone()),
+            amount,
+        },
+    )?;
 
-        // Update price
-        listing.price = params.price;
-    } else {
-        return Err(CustomContractError::TokenNotListedForSale.into());
+    if amount > cost {
+        pay(
+            host,
+            ctx.self_address(),
+            &Some(currency),
+            &buyer,
+            amount - cost,
+        )?;
     }
 
     Ok(())
 }
 
-/// View function that returns the contents of the NFTDetails
-/// that is listed by given token_id
+/// Dispute an escrowed purchase within the dispute window. Disputed purchases can only be resolved by a maintainer with
+/// `resolveEscrow`.
+///
+/// Rejects if:
+/// - It fails to parse the parameter.
+/// - There is no escrow for the token.
+/// - Sender is not the buyer.
+/// - The dispute window has ended or the purchase is already disputed.
 #[receive(
     contract = "BictoryListing",
-    name = "view",
+    name = "disputeEscrow",
     parameter = "Token",
-    return_value = "NFTDetails"
+    mutable,
+    enable_logger
 )]
-fn view<S: HasStateApi>(
+fn dispute_escrow<S: HasStateApi>(
     ctx: &impl HasReceiveContext,
-    host: &impl HasHost<State<S>, StateApiType = S>,
-) -> ReceiveResult<NFTDetails> {
+    host: &mut impl HasHost<State<S>, StateApiType = S>,
+    logger: &mut impl HasLogger,
+) -> ContractResult<()> {
     let token: Token = ctx.parameter_cursor().get()?;
-    let state = host.state();
+    let mut escrow = host
+        .state_mut()
+        .escrows
+        .get_mut(&token)
+        .ok_or(CustomContractError::NotFound)?;
 
-    Ok(*state
-        .listings
-        .get(&token)
-        .ok_or(CustomContractError::UnknownToken)?)
-}
+    ensure!(
+        ctx.sender().matches_account(&escrow.sale.buyer),
+        ContractError::Unauthorized
+    );
+    ensure!(
+        !escrow.disputed && ctx.metadata().slot_time() <= escrow.dispute_until,
+        CustomContractError::OperationNotPermitted.into()
+    );
+    escrow.disputed = true;
 
-#[concordium_cfg_test]
-mod tests {
-    use super::*;
-    use test_infrastructure::*;
+    logger.log(&CustomEvent::EscrowDisputed(EscrowEvent {
+        token,
+        seller: escrow.sale.listing.owner,
+        buyer: escrow.sale.buyer,
+    }))?;
 
-    /// Test initialization succeeds.
-    #[concordium_test]
-    fn test_init() {
-        // Setup the context
-        let ctx = TestInitContext::empty();
-        let mut builder = TestStateBuilder::new();
+    Ok(())
+}
 
-        // Call the contract function.
-        let result = init(&ctx, &mut builder);
+/// Complete an undisputed escrowed purchase, delivering the token to the buyer and paying out the shares. The buyer can
+/// finalize at any time, the seller only after the dispute window.
+///
+/// Rejects if:
+/// - The contract is paused.
+/// - It fails to parse the parameter.
+/// - There is no escrow for the token.
+/// - Sender is neither the buyer nor the seller.
+/// - The purchase is disputed, or the seller finalizes within the dispute window.
+#[receive(
+    contract = "BictoryListing",
+    name = "finalizeEscrow",
+    parameter = "Token",
+    mutable,
+    enable_logger
+)]
+fn finalize_escrow<S: HasStateApi>(
+    ctx: &impl HasReceiveContext,
+    host: &mut impl HasHost<State<S>, StateApiType = S>,
+    logger: &mut impl HasLogger,
+) -> ContractResult<()> {
+    host.state().ensure_not_paused()?;
 
-        // Check the result
-        let state = result.expect_report("Contract initialization failed");
+    let token: Token = ctx.parameter_cursor().get()?;
+    let escrow = host
+        .state()
+        .escrows
+        .get(&token)
+        .ok_or(CustomContractError::NotFound)?
+        .clone();
 
-        // Check the state
-        claim_eq!(
+    ensure!(
+        !escrow.disputed,
+        CustomContractError::OperationNotPermitted.into()
+    );
+    let sender = ctx.sender();
+    if sender.matches_account(&escrow.sale.listing.owner) {
+        ensure!(
+            ctx.metadata().slot_time() > escrow.dispute_until,
+            CustomContractError::OperationNotPermitted.into()
+        );
+    } else {
+        ensure!(
+            sender.matches_account(&escrow.sale.buyer),
+            ContractError::Unauthorized
+        );
+    }
+
+    host.state_mut().escrows.remove(&token);
+    release_escrow(ctx, host, logger, escrow)
+}
+
+/// Resolve a disputed escrowed purchase. Requires maintainer rights. Released purchases are completed as with
+/// `finalizeEscrow`, otherwise the token is returned to the seller and the buyer is refunded the price with tax.
+///
+/// Rejects if:
+/// - Sender doesn't have maintainer rights.
+/// - It fails to parse the parameter.
+/// - There is no escrow for the token, or it's not disputed.
+#[receive(
+    contract = "BictoryListing",
+    name = "resolveEscrow",
+    parameter = "ResolveEscrowParams",
+    mutable,
+    enable_logger
+)]
+fn resolve_escrow<S: HasStateApi>(
+    ctx: &impl HasReceiveContext,
+    host: &mut impl HasHost<State<S>, StateApiType = S>,
+    logger: &mut impl HasLogger,
+) -> ContractResult<()> {
+    let params: ResolveEscrowParams = ctx.parameter_cursor().get()?;
+    let state = host.state_mut();
+
+    ensure!(
+        state.authority.has_maintainer_rights(&ctx.sender()),
+        ContractError::Unauthorized
+    );
+
+    let escrow = state
+        .escrows
+        .get(&params.token)
+        .ok_or(CustomContractError::NotFound)?
+        .clone();
+    ensure!(
+        escrow.disputed,
+        CustomContractError::OperationNotPermitted.into()
+    );
+    state.escrows.remove(&params.token);
+
+    if params.release {
+        return release_escrow(ctx, host, logger, escrow);
+    }
+
+    let sale = escrow.sale;
+    transfer_token(
+        host,
+        &sale.token,
+        Address::Contract(ctx.self_address()),
+        Receiver::Account(sale.listing.owner),
+    )?;
+    pay(
+        host,
+        ctx.self_address(),
+        &sale.listing.payment_token,
+        &sale.buyer,
+        sale.listing.price + sale.tax,
+    )?;
+
+    logger.log(&CustomEvent::EscrowRefunded(EscrowEvent {
+        token: sale.token,
+        seller: sale.listing.owner,
+        buyer: sale.buyer,
+    }))?;
+
+    Ok(())
+}
+
+/// Deliver the token of a removed escrow to the buyer and settle the sale.
+fn release_escrow<S: HasStateApi>(
+    ctx: &impl HasReceiveContext,
+    host: &mut impl HasHost<State<S>, StateApiType = S>,
+    logger: &mut impl HasLogger,
+    escrow: Escrow,
+) -> ContractResult<()> {
+    let sale = escrow.sale;
+    transfer_token(
+        host,
+        &sale.token,
+        Address::Contract(ctx.self_address()),
+        Receiver::Account(sale.buyer),
+    )?;
+    settle_sale(host, logger, ctx.self_address(), sale)
+}
+
+/// Set the dispute window of escrowed purchases. With a window set, bought tokens are held by this contract and the
+/// payment is held in escrow until the purchase is finalized. `None` turns escrow off. Applies to new purchases.
+///
+/// Rejects if:
+/// - Sender doesn't have maintainer rights.
+/// - It fails to parse the parameter.
+#[receive(
+    contract = "BictoryListing",
+    name = "setEscrowWindow",
+    parameter = "Option<Duration>",
+    mutable
+)]
+fn set_escrow_window<S: HasStateApi>(
+    ctx: &impl HasReceiveContext,
+    host: &mut impl HasHost<State<S>, StateApiType = S>,
+) -> ContractResult<()> {
+    let window: Option<Duration> = ctx.parameter_cursor().get()?;
+    let state = host.state_mut();
+
+    ensure!(
+        state.authority.has_maintainer_rights(&ctx.sender()),
+        ContractError::Unauthorized
+    );
+
+    state.escrow_window = window;
+    Ok(())
+}
+
+/// View the escrowed purchase of a token.
+#[receive(
+    contract = "BictoryListing",
+    name = "viewEscrow",
+    parameter = "Token",
+    return_value = "Option<Escrow>"
+)]
+fn view_escrow<S: HasStateApi>(
+    ctx: &impl HasReceiveContext,
+    host: &impl HasHost<State<S>, StateApiType = S>,
+) -> ContractResult<Option<Escrow>> {
+    let token: Token = ctx.parameter_cursor().get()?;
+    Ok(host
+        .state()
+        .escrows
+        .get(&token)
+        .map(|escrow| escrow.clone()))
+}
+
+/// Offer to buy a token, whether it's listed or not. The attached amount is escrowed until the token owner accepts the
+/// offer or the sender cancels it. Making another offer for the same token raises the escrowed amount.
+///
+/// Rejects if:
+/// - The contract is paused.
+/// - Sender is a contract address.
+/// - It fails to parse the parameter.
+/// - No amount is attached.
+#[receive(
+    contract = "BictoryListing",
+    name = "makeOffer",
+    parameter = "Token",
+    mutable,
+    enable_logger,
+    payable
+)]
+fn make_offer<S: HasStateApi>(
+    ctx: &impl HasReceiveContext,
+    host: &mut impl HasHost<State<S>, StateApiType = S>,
+    amount: Amount,
+    logger: &mut impl HasLogger,
+) -> ContractResult<()> {
+    host.state().ensure_not_paused()?;
+
+    let buyer = get_account_address(ctx.sender())?;
+    let token: Token = ctx.parameter_cursor().get()?;
+
+    ensure!(
+        amount > Amount::zero(),
+        CustomContractError::InvalidFields.into()
+    );
+
+    let mut offer = host
+        .state_mut()
+        .offers
+        .entry((token.clone(), buyer))
+        .or_insert_with(Amount::zero);
+    *offer = offer
+        .micro_ccd
+        .checked_add(amount.micro_ccd)
+        .map(Amount::from_micro_ccd)
+        .ok_or(CustomContractError::Overflow)?;
+    let total = *offer;
+    drop(offer);
+
+    logger.log(&CustomEvent::OfferMade(OfferEvent {
+        token,
+        buyer,
+        amount: total,
+    }))?;
+
+    Ok(())
+}
+
+/// Cancel the offer of the sender for a token and refund the escrowed amount.
+///
+/// Rejects if:
+/// - Sender is a contract address.
+/// - It fails to parse the parameter.
+/// - The sender has no offer for the token.
+#[receive(
+    contract = "BictoryListing",
+    name = "cancelOffer",
+    parameter = "Token",
+    mutable,
+    enable_logger
+)]
+fn cancel_offer<S: HasStateApi>(
+    ctx: &impl HasReceiveContext,
+    host: &mut impl HasHost<State<S>, StateApiType = S>,
+    logger: &mut impl HasLogger,
+) -> ContractResult<()> {
+    let buyer = get_account_address(ctx.sender())?;
+    let token: Token = ctx.parameter_cursor().get()?;
+
+    let amount = host
+        .state_mut()
+        .offers
+        .remove_and_get(&(token.clone(), buyer))
+        .ok_or(CustomContractError::NotFound)?;
+    host.invoke_transfer(&buyer, amount)?;
+
+    logger.log(&CustomEvent::OfferCanceled(OfferEvent {
+        token,
+        buyer,
+        amount,
+    }))?;
+
+    Ok(())
+}
+
+/// Accept an offer for a token owned by the sender. The token is transferred to the buyer with this contract as the
+/// operator, and the escrowed amount is split between the owner and royalty receivers, with the marketplace fee
+/// deducted from the owner's share the same way as with `buy`. A listing of the token by the sender is removed.
+///
+/// The sender has to add this contract as an operator of the token beforehand, e.g. by listing it.
+///
+/// Rejects if:
+/// - The contract is paused.
+/// - Sender is a contract address or the buyer.
+/// - It fails to parse the parameter.
+/// - The buyer has no offer for the token.
+/// - The offer doesn't match the expected amount.
+/// - The token is not owned by the sender.
+/// - The NFT contract transfer rejects.
+#[receive(
+    contract = "BictoryListing",
+    name = "acceptOffer",
+    parameter = "AcceptOfferParams",
+    mutable,
+    enable_logger
+)]
+fn accept_offer<S: HasStateApi, V: Read>(
+    ctx: &impl HasReceiveContext,
+    host: &mut impl HasHost<State<S>, StateApiType = S, ReturnValueType = V>,
+    logger: &mut impl HasLogger,
+) -> ContractResult<()> {
+    host.state().ensure_not_paused()?;
+
+    let seller = get_account_address(ctx.sender())?;
+    let params: AcceptOfferParams = ctx.parameter_cursor().get()?;
+    ensure!(params.buyer != seller, ContractError::Unauthorized);
+
+    let offer_key = (params.token.clone(), params.buyer);
+    let price = host
+        .state()
+        .offers
+        .get(&offer_key)
+        .map(|amount| *amount)
+        .ok_or(CustomContractError::NotFound)?;
+    // Buyer could have raised the offer in between, so the accepted amount must be stated explicitly
+    ensure!(
+        price == params.expected_amount,
+        CustomContractError::InvalidFields.into()
+    );
+    host.state_mut().offers.remove(&offer_key);
+
+    // Getting token info of the seller from NFT contract, which fails if the seller doesn't own the token
+    let parameter = ViewTokenParams {
+        owner: Address::Account(seller),
+        token_id: params.token.id.clone(),
+    };
+    let entrypoint_name = EntrypointName::new_unchecked("viewToken");
+    let (_, value) = host.invoke_contract(
+        &params.token.contract,
+        &parameter,
+        entrypoint_name,
+        Amount::zero(),
+    )?;
+    let mut owned_data = value.ok_or(ContractError::InvalidTokenId)?;
+    let nft_data = NFTData::deserial(&mut owned_data)?;
+    // Royalties are only paid to accounts
+    get_account_address(nft_data.creator)?;
+    get_account_address(nft_data.minter)?;
+
+    // Transfer action
+    let transfer = Transfer {
+        token_id: params.token.id.clone(),
+        amount: ContractTokenAmount::from(1),
+        from: Address::Account(seller),
+        to: Receiver::Account(params.buyer),
+        data: AdditionalData::empty(),
+    };
+    let parameter = TransferParams(vec![transfer]);
+    let entrypoint_name = EntrypointName::new_unchecked("transfer");
+    host.invoke_contract(
+        &params.token.contract,
+        &parameter,
+        entrypoint_name,
+        Amount::zero(),
+    )?;
+
+    // Sold item is no longer for sale
+    let listed_by_seller = host
+        .state()
+        .listings
+        .get(&params.token)
+        .map_or(false, |listing| listing.owner == seller);
+    if listed_by_seller {
+        let nft_details = host.state_mut().unlist(&params.token)?;
+        refund_bounty(host, &nft_details)?;
+    }
+
+    // Calculating shares. Marketplace fee replaces the Bictory share the same way as in `settle_sale`.
+    let shares = settle_shares(price, &nft_data.royalty_recipients(), Percentage::ZERO)?;
+
+    // Balance Transfer
+    let fee = pay_marketplace_fee(host, ctx.self_address(), &None, price, shares.owner)?;
+    host.invoke_transfer(&seller, shares.owner - fee)?;
+
+    // Royalties can be `0` thereby avoiding unnecessary gas fees.
+    for (beneficiary, share) in shares.royalties.iter() {
+        if *share > Amount::zero() {
+            host.invoke_transfer(beneficiary, *share)?;
+        }
+    }
+
+    // Event for buying NFT.
+    logger.log(&CustomEvent::Buy(BuyEvent {
+        token: params.token,
+        seller,
+        buyer: params.buyer,
+        owner_share: shares.owner - fee,
+        creator_share: creator_share(&shares),
+        price,
+        tax: Amount::zero(),
+        jurisdiction: None,
+        commission: Amount::zero(),
+        fee,
+        payment_token: None,
+    }))?;
+
+    Ok(())
+}
+
+/// View the amount escrowed by an account offering to buy a token.
+#[receive(
+    contract = "BictoryListing",
+    name = "viewOffer",
+    parameter = "OfferParams",
+    return_value = "Option<Amount>"
+)]
+fn view_offer<S: HasStateApi>(
+    ctx: &impl HasReceiveContext,
+    host: &impl HasHost<State<S>, StateApiType = S>,
+) -> ContractResult<Option<Amount>> {
+    let params: OfferParams = ctx.parameter_cursor().get()?;
+    Ok(host
+        .state()
+        .offers
+        .get(&(params.token, params.buyer))
+        .map(|amount| *amount))
+}
+
+/// Update the price of the listed NFT.
+///  
+/// Rejects if:
+/// - The contract is paused.
+/// - Sender is not NFT owner.
+/// - It fails to parse the parameter.
+/// - The token is not listed.
+#[receive(
+    contract = "BictoryListing",
+    name = "updatePrice",
+    parameter = "UpdateListingPrice",
+    mutable,
+    enable_logger
+)]
+fn update_price<S: HasStateApi>(
+    ctx: &impl HasReceiveContext,
+    host: &mut impl HasHost<State<S>, StateApiType = S>,
+    logger: &mut impl HasLogger,
+) -> ContractResult<()> {
+    host.state().ensure_not_paused()?;
+
+    let sender = ctx.sender();
+    let params: UpdateListingPrice = ctx.parameter_cursor().get()?;
+
+    get_update_price_action(host, &params.token, params.price)?;
+
+    let event = if let Some(mut listing) = host.state().listings.get_mut(&params.token) {
+        // Ensuring only owner of NFT can list and alter with parameters
+        // during listing.
+        ensure!(
+            sender.matches_account(&listing.owner),
+            CustomContractError::OnlyOwner.into()
+        );
+
+        // Update price
+        let old_price = listing.price;
+        listing.price = params.price;
+        PriceChangedEvent {
+            token: params.token,
+            owner: listing.owner,
+            old_price,
+            new_price: params.price,
+        }
+    } else {
+        return Err(CustomContractError::TokenNotListedForSale.into());
+    };
+
+    // Event for changing the price.
+    logger.log(&CustomEvent::PriceChanged(event))?;
+
+    Ok(())
+}
+
+/// Opt the listing into auto-auction, so that it's moved to an auction with preset terms if it stays unsold for the
+/// given time. The attached amount is deposited as a bounty for the account calling `sweepToAuction`, and is returned
+/// to the owner when the listing is opted out, bought or unlisted.
+///
+/// The auction contract has to be able to transfer the token on behalf of the owner, e.g. by being its operator. This
+/// contract must stay an operator of the owner, so that it can create the auction on the owner's behalf.
+///
+/// Rejects if:
+/// - The contract is paused.
+/// - Sender is not NFT owner.
+/// - It fails to parse the parameter.
+/// - The token is not listed.
+/// - The listing is consigned or paid in a CIS-2 token.
+/// - An amount is attached when opting out.
+#[receive(
+    contract = "BictoryListing",
+    name = "setAutoAuction",
+    parameter = "SetAutoAuctionParams",
+    mutable,
+    payable
+)]
+fn set_auto_auction<S: HasStateApi>(
+    ctx: &impl HasReceiveContext,
+    host: &mut impl HasHost<State<S>, StateApiType = S>,
+    amount: Amount,
+) -> ContractResult<()> {
+    host.state().ensure_not_paused()?;
+
+    let sender = ctx.sender();
+    let params: SetAutoAuctionParams = ctx.parameter_cursor().get()?;
+
+    let auto_auction = match params.terms {
+        Some(terms) => {
+            let sweep_after = ctx
+                .metadata()
+                .slot_time()
+                .checked_add(terms.unsold_for)
+                .ok_or(CustomContractError::Overflow)?;
+            Some(AutoAuction {
+                terms,
+                sweep_after,
+                bounty: amount,
+            })
+        }
+        None => {
+            ensure!(
+                amount == Amount::zero(),
+                CustomContractError::InvalidFields.into()
+            );
+            None
+        }
+    };
+
+    let (owner, previous) = {
+        let mut listing = host
+            .state()
+            .listings
+            .get_mut(&params.token)
+            .ok_or(CustomContractError::TokenNotListedForSale)?;
+        ensure!(
+            sender.matches_account(&listing.owner),
+            CustomContractError::OnlyOwner.into()
+        );
+        // Consigned tokens are held by the custodian, which the auction can't transfer them from. Auctions started by
+        // the sweep are paid in CCD.
+        ensure!(
+            listing.consignment.is_none() && listing.payment_token.is_none(),
+            CustomContractError::OperationNotPermitted.into()
+        );
+        let previous = core::mem::replace(&mut listing.auto_auction, auto_auction);
+        (listing.owner, previous)
+    };
+
+    // Bounty of the previous opt-in is returned
+    if let Some(previous) = previous {
+        if previous.bounty > Amount::zero() {
+            host.invoke_transfer(&owner, previous.bounty)?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Move an unsold listing opted into auto-auction to the auction. Can be called by anyone once the listing has stayed
+/// unsold for the time set by the owner. Removes the listing, creates an English auction of the token lasting for the
+/// duration of the terms with `createAuctionFor` of the auction contract, with the listing owner as the seller, and
+/// pays the bounty to the invoker.
+///
+/// Rejects if:
+/// - The contract is paused.
+/// - It fails to parse the parameter.
+/// - The token is not listed.
+/// - The listing is not opted into auto-auction, or can't be swept yet.
+/// - The auction end overflows.
+/// - The auction contract rejects.
+#[receive(
+    contract = "BictoryListing",
+    name = "sweepToAuction",
+    parameter = "Token",
+    mutable,
+    enable_logger
+)]
+fn sweep_to_auction<S: HasStateApi>(
+    ctx: &impl HasReceiveContext,
+    host: &mut impl HasHost<State<S>, StateApiType = S>,
+    logger: &mut impl HasLogger,
+) -> ContractResult<()> {
+    host.state().ensure_not_paused()?;
+
+    let token: Token = ctx.parameter_cursor().get()?;
+
+    let listing = host
+        .state()
+        .listings
+        .get(&token)
+        .ok_or(CustomContractError::TokenNotListedForSale)?
+        .clone();
+    let auto_auction = listing.auto_auction.ok_or(CustomContractError::NotFound)?;
+    let slot_time = ctx.metadata().slot_time();
+    ensure!(
+        slot_time >= auto_auction.sweep_after,
+        CustomContractError::OperationNotPermitted.into()
+    );
+
+    host.state_mut().remove_listing(&token);
+
+    let terms = auto_auction.terms;
+    let expiry = slot_time
+        .checked_add(terms.duration)
+        .ok_or(CustomContractError::Overflow)?;
+    // Any bid above the reserve price is accepted
+    let parameter = CreateAuctionForParams {
+        seller: listing.owner,
+        auction: CreateAuctionParams {
+            item: token.clone(),
+            expiry,
+            cancellation_penalty: Percentage::ZERO,
+            bid_granularity: Amount::from_micro_ccd(1),
+            payment_token: listing.payment_token.clone(),
+            reserve_price: terms.reserve_price,
+            min_increment: Amount::zero(),
+            extension_window: Duration::from_millis(0),
+            extension_duration: Duration::from_millis(0),
+            start: None,
+            kind: AuctionKind::English,
+        },
+    };
+    host.invoke_contract(
+        &terms.auction,
+        &parameter,
+        EntrypointName::new_unchecked("createAuctionFor"),
+        Amount::zero(),
+    )?;
+
+    if auto_auction.bounty > Amount::zero() {
+        host.invoke_transfer(&ctx.invoker(), auto_auction.bounty)?;
+    }
+
+    // Event for unlisting NFT.
+    logger.log(&CustomEvent::Unlisting(ListParams {
+        token,
+        owner: listing.owner,
+    }))?;
+
+    Ok(())
+}
+
+/// View function that returns the contents of the NFTDetails
+/// that is listed by given token_id
+#[receive(
+    contract = "BictoryListing",
+    name = "view",
+    parameter = "Token",
+    return_value = "NFTDetails"
+)]
+fn view<S: HasStateApi>(
+    ctx: &impl HasReceiveContext,
+    host: &impl HasHost<State<S>, StateApiType = S>,
+) -> ReceiveResult<NFTDetails> {
+    let token: Token = ctx.parameter_cursor().get()?;
+    let state = host.state();
+
+    Ok(state
+        .listings
+        .get(&token)
+        .ok_or(CustomContractError::UnknownToken)?
+        .clone())
+}
+
+/// Turn curation of new listings on or off. Requires maintainer rights. Listings that are already pending stay in the
+/// queue until approved or rejected.
+///
+/// Rejects if:
+/// - Sender doesn't have maintainer rights.
+/// - It fails to parse the parameter.
+#[receive(
+    contract = "BictoryListing",
+    name = "setCuration",
+    parameter = "bool",
+    mutable
+)]
+fn set_curation<S: HasStateApi>(
+    ctx: &impl HasReceiveContext,
+    host: &mut impl HasHost<State<S>, StateApiType = S>,
+) -> ContractResult<()> {
+    let curation: bool = ctx.parameter_cursor().get()?;
+    let state = host.state_mut();
+
+    ensure!(
+        state.authority.has_maintainer_rights(&ctx.sender()),
+        ContractError::Unauthorized
+    );
+
+    state.curation = curation;
+
+    Ok(())
+}
+
+/// Approve a pending listing, so that it can be bought. Requires maintainer rights.
+///
+/// Rejects if:
+/// - Sender doesn't have maintainer rights.
+/// - It fails to parse the parameter.
+/// - The token is not pending approval.
+#[receive(
+    contract = "BictoryListing",
+    name = "approveListing",
+    parameter = "Token",
+    mutable,
+    enable_logger
+)]
+fn approve_listing<S: HasStateApi>(
+    ctx: &impl HasReceiveContext,
+    host: &mut impl HasHost<State<S>, StateApiType = S>,
+    logger: &mut impl HasLogger,
+) -> ContractResult<()> {
+    let token: Token = ctx.parameter_cursor().get()?;
+    let (state, state_builder) = host.state_and_builder();
+
+    ensure!(
+        state.authority.has_maintainer_rights(&ctx.sender()),
+        ContractError::Unauthorized
+    );
+
+    let nft_details = state.approve(&token, state_builder)?;
+
+    logger.log(&CustomEvent::ListingApproved(ListParams {
+        token,
+        owner: nft_details.owner,
+    }))?;
+
+    Ok(())
+}
+
+/// Reject a pending listing, removing it from the queue. Requires maintainer rights.
+///
+/// Rejects if:
+/// - Sender doesn't have maintainer rights.
+/// - It fails to parse the parameter.
+/// - The token is not pending approval.
+#[receive(
+    contract = "BictoryListing",
+    name = "rejectListing",
+    parameter = "Token",
+    mutable,
+    enable_logger
+)]
+fn reject_listing<S: HasStateApi>(
+    ctx: &impl HasReceiveContext,
+    host: &mut impl HasHost<State<S>, StateApiType = S>,
+    logger: &mut impl HasLogger,
+) -> ContractResult<()> {
+    let token: Token = ctx.parameter_cursor().get()?;
+    let state = host.state_mut();
+
+    ensure!(
+        state.authority.has_maintainer_rights(&ctx.sender()),
+        ContractError::Unauthorized
+    );
+
+    let nft_details = state
+        .pending
+        .remove_and_get(&token)
+        .ok_or(CustomContractError::TokenNotListedForSale)?;
+
+    logger.log(&CustomEvent::ListingRejected(ListParams {
+        token,
+        owner: nft_details.owner,
+    }))?;
+
+    Ok(())
+}
+
+/// View listings waiting for approval. Since return value size is limited, `skip` and `show` select the part of the
+/// queue to return.
+#[receive(
+    contract = "BictoryListing",
+    name = "viewPending",
+    parameter = "ViewPendingParams",
+    return_value = "Vec<ListingEntry>"
+)]
+fn view_pending<S: HasStateApi>(
+    ctx: &impl HasReceiveContext,
+    host: &impl HasHost<State<S>, StateApiType = S>,
+) -> ContractResult<Vec<ListingEntry>> {
+    let params: ViewPendingParams = ctx.parameter_cursor().get()?;
+
+    Ok(host
+        .state()
+        .pending
+        .iter()
+        .skip(params.skip as usize)
+        .take(params.show as usize)
+        .map(|(token, details)| ListingEntry {
+            token: token.clone(),
+            details: details.clone(),
+        })
+        .collect())
+}
+
+/// View active listings of an owner, e.g. to render the owner's shop page. Since return value size is limited, `skip`
+/// and `show` select the part of the listings to return. Contract addresses have no listings.
+#[receive(
+    contract = "BictoryListing",
+    name = "viewListingsByOwner",
+    parameter = "ViewListingsByOwnerParams",
+    return_value = "Vec<ListingEntry>"
+)]
+fn view_listings_by_owner<S: HasStateApi>(
+    ctx: &impl HasReceiveContext,
+    host: &impl HasHost<State<S>, StateApiType = S>,
+) -> ContractResult<Vec<ListingEntry>> {
+    let params: ViewListingsByOwnerParams = ctx.parameter_cursor().get()?;
+    let state = host.state();
+
+    let tokens = match params.owner {
+        Address::Account(owner) => state.owner_listings.get(&owner),
+        Address::Contract(_) => None,
+    };
+    Ok(tokens.map_or_else(Vec::new, |tokens| {
+        state.listing_entries(
+            tokens.iter().map(|token| token.clone()),
+            params.skip,
+            params.show,
+        )
+    }))
+}
+
+/// View active listings of tokens of an NFT contract, e.g. to render a collection page. Since return value size is
+/// limited, `skip` and `show` select the part of the listings to return.
+#[receive(
+    contract = "BictoryListing",
+    name = "viewListingsByContract",
+    parameter = "ViewListingsByContractParams",
+    return_value = "Vec<ListingEntry>"
+)]
+fn view_listings_by_contract<S: HasStateApi>(
+    ctx: &impl HasReceiveContext,
+    host: &impl HasHost<State<S>, StateApiType = S>,
+) -> ContractResult<Vec<ListingEntry>> {
+    let params: ViewListingsByContractParams = ctx.parameter_cursor().get()?;
+    let state = host.state();
+
+    Ok(state
+        .contract_listings
+        .get(&params.contract)
+        .map_or_else(Vec::new, |tokens| {
+            state.listing_entries(
+                tokens.iter().map(|token| token.clone()),
+                params.skip,
+                params.show,
+            )
+        }))
+}
+
+/// Add, change or remove the sales tax rule of a jurisdiction. Requires maintainer rights. Changes apply to existing
+/// listings of the jurisdiction.
+///
+/// Rejects if:
+/// - Sender doesn't have maintainer rights.
+/// - It fails to parse the parameter.
+/// - The jurisdiction to remove is not in the tax table.
+#[receive(
+    contract = "BictoryListing",
+    name = "updateTax",
+    parameter = "UpdateTaxParams",
+    mutable
+)]
+fn update_tax<S: HasStateApi>(
+    ctx: &impl HasReceiveContext,
+    host: &mut impl HasHost<State<S>, StateApiType = S>,
+) -> ContractResult<()> {
+    let params: UpdateTaxParams = ctx.parameter_cursor().get()?;
+    let state = host.state_mut();
+
+    ensure!(
+        state.authority.has_maintainer_rights(&ctx.sender()),
+        ContractError::Unauthorized
+    );
+
+    match params.rule {
+        Some(rule) => {
+            state.taxes.insert(params.jurisdiction, rule);
+        }
+        None => {
+            state
+                .taxes
+                .remove_and_get(&params.jurisdiction)
+                .ok_or(CustomContractError::NotFound)?;
+        }
+    }
+
+    Ok(())
+}
+
+/// View the sales tax rule of a jurisdiction.
+#[receive(
+    contract = "BictoryListing",
+    name = "viewTax",
+    parameter = "String",
+    return_value = "Option<TaxRule>"
+)]
+fn view_tax<S: HasStateApi>(
+    ctx: &impl HasReceiveContext,
+    host: &impl HasHost<State<S>, StateApiType = S>,
+) -> ContractResult<Option<TaxRule>> {
+    let jurisdiction: String = ctx.parameter_cursor().get()?;
+    Ok(host.state().taxes.get(&jurisdiction).map(|rule| *rule))
+}
+
+/// Set the marketplace fee deducted from the seller's share of each sale. Applies to existing listings.
+///
+/// Rejects if:
+/// - Sender doesn't have maintainer rights.
+/// - It fails to parse the parameter.
+/// - The fee exceeds 100%.
+#[receive(
+    contract = "BictoryListing",
+    name = "setMarketplaceFee",
+    parameter = "Percentage",
+    mutable
+)]
+fn set_marketplace_fee<S: HasStateApi>(
+    ctx: &impl HasReceiveContext,
+    host: &mut impl HasHost<State<S>, StateApiType = S>,
+) -> ContractResult<()> {
+    let fee: Percentage = ctx.parameter_cursor().get()?;
+    let state = host.state_mut();
+
+    ensure!(
+        state.authority.has_maintainer_rights(&ctx.sender()),
+        ContractError::Unauthorized
+    );
+    ensure!(
+        fee <= Percentage::HUNDRED,
+        CustomContractError::InvalidFields.into()
+    );
+
+    state.fees.fee = fee;
+    Ok(())
+}
+
+/// Set the account marketplace fees are paid to.
+///
+/// Rejects if:
+/// - Sender doesn't have maintainer rights.
+/// - It fails to parse the parameter.
+#[receive(
+    contract = "BictoryListing",
+    name = "setFeeBeneficiary",
+    parameter = "AccountAddress",
+    mutable
+)]
+fn set_fee_beneficiary<S: HasStateApi>(
+    ctx: &impl HasReceiveContext,
+    host: &mut impl HasHost<State<S>, StateApiType = S>,
+) -> ContractResult<()> {
+    let beneficiary: AccountAddress = ctx.parameter_cursor().get()?;
+    let state = host.state_mut();
+
+    ensure!(
+        state.authority.has_maintainer_rights(&ctx.sender()),
+        ContractError::Unauthorized
+    );
+
+    state.fees.beneficiary = beneficiary;
+    Ok(())
+}
+
+/// View the marketplace fee and its beneficiary.
+#[receive(
+    contract = "BictoryListing",
+    name = "viewFees",
+    return_value = "MarketplaceFees"
+)]
+fn view_fees<S: HasStateApi>(
+    _ctx: &impl HasReceiveContext,
+    host: &impl HasHost<State<S>, StateApiType = S>,
+) -> ContractResult<MarketplaceFees> {
+    Ok(host.state().fees)
+}
+
+/// Pause or resume trading. While paused, listing, buying, offers, price and auto auction updates and escrow
+/// finalization are rejected. Unlisting, cancelling offers and disputing or resolving escrows stay available, so that
+/// owners and buyers can withdraw.
+///
+/// Rejects if:
+/// - Sender doesn't have maintainer rights.
+/// - It fails to parse the parameter.
+#[receive(
+    contract = "BictoryListing",
+    name = "setPaused",
+    parameter = "bool",
+    mutable
+)]
+fn set_paused<S: HasStateApi>(
+    ctx: &impl HasReceiveContext,
+    host: &mut impl HasHost<State<S>, StateApiType = S>,
+) -> ContractResult<()> {
+    let paused: bool = ctx.parameter_cursor().get()?;
+    let state = host.state_mut();
+
+    ensure!(
+        state.authority.has_maintainer_rights(&ctx.sender()),
+        ContractError::Unauthorized
+    );
+
+    state.paused = paused;
+    Ok(())
+}
+
+/// Function to manage addresses that are allowed to maintain and modify the state of the contract.
+///
+///  It rejects if:
+///  - Fails to parse `AuthorityUpdateParams` parameters.
+///  - If sender is neither one of the admins nor one of the maintainers.
+#[receive(
+    mutable,
+    contract = "BictoryListing",
+    name = "updateAuthority",
+    parameter = "AuthorityUpdateParams"
+)]
+fn update_authority<S: HasStateApi>(
+    ctx: &impl HasReceiveContext,
+    host: &mut impl HasHost<State<S>, StateApiType = S>,
+) -> ReceiveResult<()> {
+    let state = host.state_mut();
+    let params = AuthorityUpdateParams::deserial(&mut ctx.parameter_cursor())?;
+    let sender = ctx.sender();
+    state.authority.handle_update(sender, params)
+}
+
+/// Function to view addresses that are allowed to maintain and modify the state of the contract.
+#[receive(
+    contract = "BictoryListing",
+    name = "viewAuthority",
+    parameter = "AuthorityViewParams",
+    return_value = "Vec<Address>"
+)]
+fn view_authority<S: HasStateApi>(
+    ctx: &impl HasReceiveContext,
+    host: &impl HasHost<State<S>, StateApiType = S>,
+) -> ReceiveResult<Vec<Address>> {
+    let params = AuthorityViewParams::deserial(&mut ctx.parameter_cursor())?;
+    Ok(host.state().authority.handle_view(params))
+}
+
+/// Check which standards the contract supports (CIS-0).
+///
+/// It rejects if:
+/// - It fails to parse the parameter.
+#[receive(
+    contract = "BictoryListing",
+    name = "supports",
+    parameter = "SupportsQueryParams",
+    return_value = "SupportsQueryResponse"
+)]
+fn supports<S: HasStateApi>(
+    ctx: &impl HasReceiveContext,
+    _host: &impl HasHost<State<S>, StateApiType = S>,
+) -> ReceiveResult<SupportsQueryResponse> {
+    let params = SupportsQueryParams::deserial(&mut ctx.parameter_cursor())?;
+    Ok(supports_standards(&SUPPORTED_STANDARDS, params))
+}
+
+#[concordium_cfg_test]
+mod tests {
+    use super::*;
+    use commons::test::*;
+    use core::convert::TryInto;
+    use test_infrastructure::*;
+
+    const ADMIN: AccountAddress = AccountAddress([0u8; 32]);
+    const SELLER: AccountAddress = AccountAddress([1u8; 32]);
+    const BUYER: AccountAddress = AccountAddress([2u8; 32]);
+    const COLLECTOR: AccountAddress = AccountAddress([3u8; 32]);
+    const CUSTODIAN_ACCOUNT: AccountAddress = AccountAddress([4u8; 32]);
+    const CUSTODIAN: ContractAddress = ContractAddress {
+        index: 9,
+        subindex: 0,
+    };
+
+    fn dummy_token() -> Token {
+        Token {
+            contract: ContractAddress {
+                index: 1,
+                subindex: 0,
+            },
+            id: TokenIdVec(vec![0, 1]),
+        }
+    }
+
+    fn dummy_listing(jurisdiction: Option<String>) -> NFTDetails {
+        NFTDetails {
+            owner: SELLER,
+            creator: SELLER,
+            creator_royalty: 0,
+            minter: SELLER,
+            minter_royalty: 0,
+            royalties: RoyaltyRecipients::new(),
+            price: Amount::from_ccd(100),
+            quantity: ContractTokenAmount::from(1),
+            jurisdiction,
+            consignment: None,
+            auto_auction: None,
+            payment_token: None,
+        }
+    }
+
+    /// Test initialization succeeds.
+    #[concordium_test]
+    fn test_init() {
+        // Setup the context
+        let mut ctx = TestInitContext::empty();
+        ctx.set_init_origin(ADMIN);
+        let mut builder = TestStateBuilder::new();
+
+        // Call the contract function.
+        let result = init(&ctx, &mut builder);
+
+        // Check the result
+        let state = result.expect_report("Contract initialization failed");
+
+        // Check the state
+        claim_eq!(
             state.listings.iter().count(),
             0,
             "No listings should be initialized"
         );
     }
+
+    /// Test buying adds the jurisdiction's sales tax and remits it to the collector.
+    #[concordium_test]
+    fn test_buy_with_tax() {
+        let mut init_ctx = TestInitContext::empty();
+        init_ctx.set_init_origin(ADMIN);
+        let mut builder = TestStateBuilder::new();
+        let state = init(&init_ctx, &mut builder).expect_report("Contract initialization failed");
+        let mut host = TestHost::new(state, builder);
+        host.setup_mock_entrypoint(
+            dummy_token().contract,
+            OwnedEntrypointName::new_unchecked(String::from("transfer")),
+            MockFn::returning_ok(()),
+        );
+        let mut logger = TestLogger::init();
+
+        // Only maintainers can update the tax table
+        let parameter_bytes = to_bytes(&UpdateTaxParams {
+            jurisdiction: String::from("EE"),
+            rule: Some(TaxRule {
+                rate: Percentage::from_percent(20),
+                collector: COLLECTOR,
+            }),
+        });
+        let mut ctx = TestReceiveContext::empty();
+        ctx.set_sender(Address::Account(SELLER));
+        ctx.set_parameter(&parameter_bytes);
+        claim_eq!(
+            update_tax(&ctx, &mut host),
+            Err(ContractError::Unauthorized)
+        );
+
+        ctx.set_sender(Address::Account(ADMIN));
+        claim_eq!(update_tax(&ctx, &mut host), Ok(()));
+
+        host.state_mut()
+            .listings
+            .insert(dummy_token(), dummy_listing(Some(String::from("EE"))));
+
+        // Paying only the listing price is not enough
+        let parameter_bytes = to_bytes(&BuyParams {
+            token: dummy_token(),
+        });
+        let mut ctx = TestReceiveContext::empty();
+        ctx.set_sender(Address::Account(BUYER));
+        ctx.set_parameter(&parameter_bytes);
+        let result = buy(&ctx, &mut host, Amount::from_ccd(100), &mut logger);
+        claim_eq!(result, Err(ContractError::InsufficientFunds));
+
+        host.state_mut()
+            .listings
+            .insert(dummy_token(), dummy_listing(Some(String::from("EE"))));
+        host.set_self_balance(Amount::from_ccd(120));
+        let result = buy(&ctx, &mut host, Amount::from_ccd(120), &mut logger);
+        claim_eq!(result, Ok(()));
+
+        claim!(host.transfer_occurred(&SELLER, Amount::from_ccd(100)));
+        claim!(host.transfer_occurred(&COLLECTOR, Amount::from_ccd(20)));
+        claim_eq!(
+            logger.logs.last(),
+            Some(&to_bytes(&CustomEvent::Buy(BuyEvent {
+                token: dummy_token(),
+                seller: SELLER,
+                buyer: BUYER,
+                owner_share: Amount::from_ccd(100),
+                creator_share: Amount::zero(),
+                price: Amount::from_ccd(100),
+                tax: Amount::from_ccd(20),
+                jurisdiction: Some(String::from("EE")),
+                commission: Amount::zero(),
+                fee: Amount::zero(),
+                payment_token: None,
+            })))
+        );
+    }
+
+    /// Test the marketplace fee is deducted from the seller's share and paid to the beneficiary.
+    #[concordium_test]
+    fn test_marketplace_fee() {
+        let mut init_ctx = TestInitContext::empty();
+        init_ctx.set_init_origin(ADMIN);
+        let mut builder = TestStateBuilder::new();
+        let state = init(&init_ctx, &mut builder).expect_report("Contract initialization failed");
+        let mut host = TestHost::new(state, builder);
+        host.setup_mock_entrypoint(
+            dummy_token().contract,
+            OwnedEntrypointName::new_unchecked(String::from("transfer")),
+            MockFn::returning_ok(()),
+        );
+        let mut logger = TestLogger::init();
+
+        // No fee is charged by default
+        let ctx = TestReceiveContext::empty();
+        claim_eq!(
+            view_fees(&ctx, &host),
+            Ok(MarketplaceFees {
+                fee: Percentage::ZERO,
+                beneficiary: ADMIN,
+            })
+        );
+
+        // Only maintainers can configure fees
+        let fee_bytes = to_bytes(&Percentage::from_percent(5));
+        let mut ctx = TestReceiveContext::empty();
+        ctx.set_sender(Address::Account(SELLER));
+        ctx.set_parameter(&fee_bytes);
+        claim_eq!(
+            set_marketplace_fee(&ctx, &mut host),
+            Err(ContractError::Unauthorized)
+        );
+
+        ctx.set_sender(Address::Account(ADMIN));
+        claim_eq!(set_marketplace_fee(&ctx, &mut host), Ok(()));
+
+        let fee_bytes = to_bytes(&Percentage::from_percent(101));
+        ctx.set_parameter(&fee_bytes);
+        claim_eq!(
+            set_marketplace_fee(&ctx, &mut host),
+            Err(CustomContractError::InvalidFields.into())
+        );
+
+        let beneficiary_bytes = to_bytes(&COLLECTOR);
+        ctx.set_parameter(&beneficiary_bytes);
+        claim_eq!(set_fee_beneficiary(&ctx, &mut host), Ok(()));
+        claim_eq!(
+            view_fees(&ctx, &host),
+            Ok(MarketplaceFees {
+                fee: Percentage::from_percent(5),
+                beneficiary: COLLECTOR,
+            })
+        );
+
+        host.state_mut()
+            .listings
+            .insert(dummy_token(), dummy_listing(None));
+        host.set_self_balance(Amount::from_ccd(100));
+
+        let parameter_bytes = to_bytes(&BuyParams {
+            token: dummy_token(),
+        });
+        let mut ctx = TestReceiveContext::empty();
+        ctx.set_sender(Address::Account(BUYER));
+        ctx.set_parameter(&parameter_bytes);
+        let result = buy(&ctx, &mut host, Amount::from_ccd(100), &mut logger);
+        claim_eq!(result, Ok(()));
+
+        claim!(host.transfer_occurred(&SELLER, Amount::from_ccd(95)));
+        claim!(host.transfer_occurred(&COLLECTOR, Amount::from_ccd(5)));
+        claim_eq!(
+            logger.logs.last(),
+            Some(&to_bytes(&CustomEvent::Buy(BuyEvent {
+                token: dummy_token(),
+                seller: SELLER,
+                buyer: BUYER,
+                owner_share: Amount::from_ccd(95),
+                creator_share: Amount::zero(),
+                price: Amount::from_ccd(100),
+                tax: Amount::zero(),
+                jurisdiction: None,
+                commission: Amount::zero(),
+                fee: Amount::from_ccd(5),
+                payment_token: None,
+            })))
+        );
+    }
+
+    /// Test the sale price is split between the creator, minter and additional royalty recipients.
+    #[concordium_test]
+    fn test_buy_royalty_recipients() {
+        let mut init_ctx = TestInitContext::empty();
+        init_ctx.set_init_origin(ADMIN);
+        let mut builder = TestStateBuilder::new();
+        let state = init(&init_ctx, &mut builder).expect_report("Contract initialization failed");
+        let mut host = TestHost::new(state, builder);
+        host.setup_mock_entrypoint(
+            dummy_token().contract,
+            OwnedEntrypointName::new_unchecked(String::from("transfer")),
+            MockFn::returning_ok(()),
+        );
+        let mut logger = TestLogger::init();
+
+        let listing = NFTDetails {
+            creator: ADMIN,
+            creator_royalty: 5_000_000,
+            minter: CUSTODIAN_ACCOUNT,
+            minter_royalty: 2_000_000,
+            royalties: vec![Royalty {
+                beneficiary: COLLECTOR,
+                percentage: Percentage::from_percent(3),
+            }]
+            .try_into()
+            .expect_report("Too many royalty recipients"),
+            ..dummy_listing(None)
+        };
+        host.state_mut().listings.insert(dummy_token(), listing);
+        host.set_self_balance(Amount::from_ccd(100));
+
+        let parameter_bytes = to_bytes(&BuyParams {
+            token: dummy_token(),
+        });
+        let mut ctx = TestReceiveContext::empty();
+        ctx.set_sender(Address::Account(BUYER));
+        ctx.set_parameter(&parameter_bytes);
+        let result = buy(&ctx, &mut host, Amount::from_ccd(100), &mut logger);
+        claim_eq!(result, Ok(()));
+
+        claim!(host.transfer_occurred(&SELLER, Amount::from_ccd(90)));
+        claim!(host.transfer_occurred(&ADMIN, Amount::from_ccd(5)));
+        claim!(host.transfer_occurred(&CUSTODIAN_ACCOUNT, Amount::from_ccd(2)));
+        claim!(host.transfer_occurred(&COLLECTOR, Amount::from_ccd(3)));
+        claim_eq!(
+            logger.logs.last(),
+            Some(&to_bytes(&CustomEvent::Buy(BuyEvent {
+                token: dummy_token(),
+                seller: SELLER,
+                buyer: BUYER,
+                owner_share: Amount::from_ccd(90),
+                creator_share: Amount::from_ccd(5),
+                price: Amount::from_ccd(100),
+                tax: Amount::zero(),
+                jurisdiction: None,
+                commission: Amount::zero(),
+                fee: Amount::zero(),
+                payment_token: None,
+            })))
+        );
+    }
+
+    /// Test listings priced in a CIS-2 token are bought by transferring the token, with shares and change paid out in
+    /// the token by the listing contract.
+    #[concordium_test]
+    fn test_payment_token_purchase() {
+        const LISTING: ContractAddress = ContractAddress {
+            index: 5,
+            subindex: 0,
+        };
+        const PAYMENT: ContractAddress = ContractAddress {
+            index: 6,
+            subindex: 0,
+        };
+        let payment_token = Token {
+            contract: PAYMENT,
+            id: TokenIdVec(vec![7]),
+        };
+        let token_listing = || NFTDetails {
+            price: Amount::from_micro_ccd(100),
+            payment_token: Some(payment_token.clone()),
+            ..dummy_listing(None)
+        };
+
+        let mut init_ctx = TestInitContext::empty();
+        init_ctx.set_init_origin(ADMIN);
+        let mut builder = TestStateBuilder::new();
+        let state = init(&init_ctx, &mut builder).expect_report("Contract initialization failed");
+        let mut host = TestHost::new(state, builder);
+        host.setup_mock_entrypoint(
+            dummy_token().contract,
+            OwnedEntrypointName::new_unchecked(String::from("transfer")),
+            MockFn::returning_ok(()),
+        );
+        // Shares and change are paid in the payment token by the listing contract
+        host.setup_mock_entrypoint(
+            PAYMENT,
+            OwnedEntrypointName::new_unchecked(String::from("transfer")),
+            parse_and_check_mock::<TransferParameter, _>(
+                |params| params.0[0].from == Address::Contract(LISTING),
+                (),
+            ),
+        );
+        let mut logger = TestLogger::init();
+        host.state_mut()
+            .listings
+            .insert(dummy_token(), token_listing());
+
+        // CCD is not accepted
+        let parameter_bytes = to_bytes(&BuyParams {
+            token: dummy_token(),
+        });
+        let mut ctx = TestReceiveContext::empty();
+        ctx.set_sender(Address::Account(BUYER));
+        ctx.set_self_address(LISTING);
+        ctx.set_parameter(&parameter_bytes);
+        claim_eq!(
+            buy(&ctx, &mut host, Amount::from_ccd(100), &mut logger),
+            Err(CustomContractError::OperationNotPermitted.into())
+        );
+
+        let token_payment = |amount: u64| {
+            to_bytes(&OnReceivingCis2Params {
+                token_id: payment_token.id.clone(),
+                amount: ContractTokenAmount::from(amount),
+                from: Address::Account(BUYER),
+                data: AdditionalData::from(to_bytes(&dummy_token())),
+            })
+        };
+
+        // Other tokens can't be used for buying. State is not rolled back by the test host.
+        host.state_mut()
+            .listings
+            .insert(dummy_token(), token_listing());
+        let parameter_bytes = token_payment(100);
+        ctx.set_sender(Address::Contract(dummy_token().contract));
+        ctx.set_parameter(&parameter_bytes);
+        claim_eq!(
+            on_receiving_cis2(&ctx, &mut host, &mut logger),
+            Err(CustomContractError::OperationNotPermitted.into())
+        );
+
+        host.state_mut()
+            .listings
+            .insert(dummy_token(), token_listing());
+        ctx.set_sender(Address::Contract(PAYMENT));
+        let parameter_bytes = token_payment(99);
+        ctx.set_parameter(&parameter_bytes);
+        claim_eq!(
+            on_receiving_cis2(&ctx, &mut host, &mut logger),
+            Err(ContractError::InsufficientFunds)
+        );
+
+        host.state_mut()
+            .listings
+            .insert(dummy_token(), token_listing());
+        let parameter_bytes = token_payment(120);
+        ctx.set_parameter(&parameter_bytes);
+        claim_eq!(on_receiving_cis2(&ctx, &mut host, &mut logger), Ok(()));
+        claim!(!host.state().is_listed(&dummy_token()));
+
+        // No CCD is paid out
+        claim!(!host.transfer_occurred(&SELLER, Amount::from_micro_ccd(100)));
+        claim_eq!(
+            logger.logs.last(),
+            Some(&to_bytes(&CustomEvent::Buy(BuyEvent {
+                token: dummy_token(),
+                seller: SELLER,
+                buyer: BUYER,
+                owner_share: Amount::from_micro_ccd(100),
+                creator_share: Amount::zero(),
+                price: Amount::from_micro_ccd(100),
+                tax: Amount::zero(),
+                jurisdiction: None,
+                commission: Amount::zero(),
+                fee: Amount::zero(),
+                payment_token: Some(payment_token.clone()),
+            })))
+        );
+    }
+
+    /// Test consigned listing verifies the owner's agreement, transfers the token from the custodian and pays the
+    /// custodian's commission from the owner's share.
+    #[concordium_test]
+    fn test_list_consigned() {
+        let mut init_ctx = TestInitContext::empty();
+        init_ctx.set_init_origin(ADMIN);
+        let mut builder = TestStateBuilder::new();
+        let state = init(&init_ctx, &mut builder).expect_report("Contract initialization failed");
+        let mut host = TestHost::new(state, builder);
+        host.setup_mock_entrypoint(
+            dummy_token().contract,
+            OwnedEntrypointName::new_unchecked(String::from("viewToken")),
+            parse_and_map_mock::<ViewTokenParams, _, _>(|params| {
+                // Token is held by the custodian
+                (params.owner == Address::Contract(CUSTODIAN)).then(|| NFTData {
+                    creator: Address::Account(SELLER),
+                    creator_royalty: 0,
+                    minter: Address::Account(SELLER),
+                    minter_royalty: 0,
+                    price: Amount::from_ccd(50),
+                    cid: Vec::new(),
+                    quantity: ContractTokenAmount::from(1),
+                    royalties: RoyaltyRecipients::new(),
+                })
+            }),
+        );
+        host.setup_mock_entrypoint(
+            dummy_token().contract,
+            OwnedEntrypointName::new_unchecked(String::from("updatePrice")),
+            MockFn::returning_ok(()),
+        );
+        host.setup_mock_entrypoint(
+            dummy_token().contract,
+            OwnedEntrypointName::new_unchecked(String::from("transfer")),
+            parse_and_check_mock::<TransferParameter, _>(
+                |params| params.0[0].from == Address::Contract(CUSTODIAN),
+                (),
+            ),
+        );
+        let mut logger = TestLogger::init();
+
+        // Owner registers a signing key
+        let key_bytes = to_bytes(&[7u8; 32]);
+        let mut ctx = TestReceiveContext::empty();
+        ctx.set_sender(Address::Account(SELLER));
+        ctx.set_parameter(&key_bytes);
+        claim_eq!(register_consignor_key(&ctx, &mut host), Ok(()));
+
+        let mut crypto_primitives = TestCryptoPrimitives::new();
+        crypto_primitives.setup_verify_ed25519_signature_mock(|public_key, _, _| {
+            public_key == PublicKeyEd25519([7u8; 32])
+        });
+
+        let parameter_bytes = to_bytes(&ListConsignedParams {
+            agreement: ConsignmentAgreement {
+                token: dummy_token(),
+                owner: SELLER,
+                consignment: Consignment {
+                    custodian: Address::Contract(CUSTODIAN),
+                    commission_account: CUSTODIAN_ACCOUNT,
+                    commission: Percentage::from_percent(10),
+                },
+                price: Amount::from_ccd(100),
+                nonce: 0,
+            },
+            signature: [0u8; 64],
+            jurisdiction: None,
+        });
+        let mut ctx = TestReceiveContext::empty();
+        ctx.set_self_address(ContractAddress {
+            index: 2,
+            subindex: 0,
+        });
+        ctx.set_parameter(&parameter_bytes);
+
+        // Only the custodian can list the consigned token
+        ctx.set_sender(Address::Account(SELLER));
+        let result = list_consigned(&ctx, &mut host, &mut logger, &crypto_primitives);
+        claim_eq!(result, Err(ContractError::Unauthorized));
+
+        ctx.set_sender(Address::Contract(CUSTODIAN));
+        let result = list_consigned(&ctx, &mut host, &mut logger, &crypto_primitives);
+        claim_eq!(result, Ok(()));
+        claim_eq!(
+            host.state()
+                .listings
+                .get(&dummy_token())
+                .map(|listing| listing.price),
+            Some(Amount::from_ccd(100))
+        );
+
+        // Replaying the agreement fails
+        let result = list_consigned(&ctx, &mut host, &mut logger, &crypto_primitives);
+        claim_eq!(result, Err(CustomContractError::InvalidNonce.into()));
+
+        let parameter_bytes = to_bytes(&BuyParams {
+            token: dummy_token(),
+        });
+        let mut ctx = TestReceiveContext::empty();
+        ctx.set_sender(Address::Account(BUYER));
+        ctx.set_parameter(&parameter_bytes);
+        host.set_self_balance(Amount::from_ccd(100));
+        let result = buy(&ctx, &mut host, Amount::from_ccd(100), &mut logger);
+        claim_eq!(result, Ok(()));
+
+        claim!(host.transfer_occurred(&SELLER, Amount::from_ccd(90)));
+        claim!(host.transfer_occurred(&CUSTODIAN_ACCOUNT, Amount::from_ccd(10)));
+        claim_eq!(
+            logger.logs.last(),
+            Some(&to_bytes(&CustomEvent::Buy(BuyEvent {
+                token: dummy_token(),
+                seller: SELLER,
+                buyer: BUYER,
+                owner_share: Amount::from_ccd(90),
+                creator_share: Amount::zero(),
+                price: Amount::from_ccd(100),
+                tax: Amount::zero(),
+                jurisdiction: None,
+                commission: Amount::from_ccd(10),
+                fee: Amount::zero(),
+                payment_token: None,
+            })))
+        );
+    }
+
+    /// Test an unsold listing is moved to the auction once the opt-in period passes and the bounty is paid out.
+    #[concordium_test]
+    fn test_sweep_to_auction() {
+        const AUCTION: ContractAddress = ContractAddress {
+            index: 5,
+            subindex: 0,
+        };
+        let terms = AutoAuctionTerms {
+            auction: AUCTION,
+            unsold_for: Duration::from_days(30),
+            duration: Duration::from_days(7),
+            reserve_price: Amount::from_ccd(50),
+        };
+
+        let mut init_ctx = TestInitContext::empty();
+        init_ctx.set_init_origin(ADMIN);
+        let mut builder = TestStateBuilder::new();
+        let state = init(&init_ctx, &mut builder).expect_report("Contract initialization failed");
+        let mut host = TestHost::new(state, builder);
+        host.state_mut()
+            .listings
+            .insert(dummy_token(), dummy_listing(None));
+        let mut logger = TestLogger::init();
+
+        // Only the owner can opt the listing in
+        let parameter_bytes = to_bytes(&SetAutoAuctionParams {
+            token: dummy_token(),
+            terms: Some(terms.clone()),
+        });
+        let mut ctx = TestReceiveContext::empty();
+        ctx.set_sender(Address::Account(BUYER));
+        ctx.set_parameter(&parameter_bytes);
+        ctx.set_metadata_slot_time(Timestamp::from_timestamp_millis(0));
+        let result = set_auto_auction(&ctx, &mut host, Amount::from_ccd(1));
+        claim_eq!(result, Err(CustomContractError::OnlyOwner.into()));
+
+        ctx.set_sender(Address::Account(SELLER));
+        let result = set_auto_auction(&ctx, &mut host, Amount::from_ccd(1));
+        claim_eq!(result, Ok(()));
+        host.set_self_balance(Amount::from_ccd(1));
+
+        // Listing can't be swept before the opt-in period passes
+        let parameter_bytes = to_bytes(&dummy_token());
+        let mut ctx = TestReceiveContext::empty();
+        ctx.set_sender(Address::Account(BUYER));
+        ctx.set_invoker(BUYER);
+        ctx.set_parameter(&parameter_bytes);
+        ctx.set_metadata_slot_time(Timestamp::from_timestamp_millis(1_000));
+        let result = sweep_to_auction(&ctx, &mut host, &mut logger);
+        claim_eq!(
+            result,
+            Err(CustomContractError::OperationNotPermitted.into())
+        );
+
+        // Auction of the token ends after the duration of the terms
+        let expected = CreateAuctionParams {
+            item: dummy_token(),
+            expiry: Timestamp::from_timestamp_millis(Duration::from_days(37).millis()),
+            cancellation_penalty: Percentage::ZERO,
+            bid_granularity: Amount::from_micro_ccd(1),
+            payment_token: None,
+            reserve_price: Amount::from_ccd(50),
+            min_increment: Amount::zero(),
+            extension_window: Duration::from_millis(0),
+            extension_duration: Duration::from_millis(0),
+            start: None,
+            kind: AuctionKind::English,
+        };
+        // Auction contract rejects contract senders in `createAuction`, and only accepts `createAuctionFor` from
+        // operators of the seller. The NFT contract reports this contract as an operator of the listing owner only.
+        host.setup_mock_entrypoint(
+            AUCTION,
+            OwnedEntrypointName::new_unchecked(String::from("createAuction")),
+            parse_and_check_mock::<CreateAuctionParams, _>(|_| false, 0u64),
+        );
+        host.setup_mock_entrypoint(
+            AUCTION,
+            OwnedEntrypointName::new_unchecked(String::from("createAuctionFor")),
+            parse_and_check_mock::<CreateAuctionForParams, _>(
+                move |params| {
+                    let auction = &params.auction;
+                    let granularity = auction.bid_granularity.micro_ccd;
+                    params.seller == SELLER
+                        && granularity > 0
+                        && auction.reserve_price.micro_ccd % granularity == 0
+                        && auction.min_increment.micro_ccd % granularity == 0
+                        && *auction == expected
+                },
+                0u64,
+            ),
+        );
+        ctx.set_metadata_slot_time(Timestamp::from_timestamp_millis(
+            Duration::from_days(30).millis(),
+        ));
+        let result = sweep_to_auction(&ctx, &mut host, &mut logger);
+        claim_eq!(result, Ok(()));
+
+        claim!(host.transfer_occurred(&BUYER, Amount::from_ccd(1)));
+        claim!(host.state().listings.get(&dummy_token()).is_none());
+        claim_eq!(
+            logger.logs,
+            vec![to_bytes(&CustomEvent::Unlisting(ListParams {
+                token: dummy_token(),
+                owner: SELLER,
+            }))]
+        );
+    }
+
+    /// Test a collection is listed and bought in one transaction each, with the attached amount covering all prices.
+    #[concordium_test]
+    fn test_batch_list_and_buy() {
+        let other_token = Token {
+            id: TokenIdVec(vec![2]),
+            ..dummy_token()
+        };
+
+        let mut init_ctx = TestInitContext::empty();
+        init_ctx.set_init_origin(ADMIN);
+        let mut builder = TestStateBuilder::new();
+        let state = init(&init_ctx, &mut builder).expect_report("Contract initialization failed");
+        let mut host = TestHost::new(state, builder);
+        host.setup_mock_entrypoint(
+            dummy_token().contract,
+            OwnedEntrypointName::new_unchecked(String::from("viewToken")),
+            parse_and_map_mock::<ViewTokenParams, _, _>(|_| {
+                Some(NFTData {
+                    creator: Address::Account(SELLER),
+                    creator_royalty: 0,
+                    minter: Address::Account(SELLER),
+                    minter_royalty: 0,
+                    price: Amount::from_ccd(10),
+                    cid: Vec::new(),
+                    quantity: ContractTokenAmount::from(1),
+                    royalties: RoyaltyRecipients::new(),
+                })
+            }),
+        );
+        for entrypoint in ["updateOperator", "updatePrice", "transfer"] {
+            host.setup_mock_entrypoint(
+                dummy_token().contract,
+                OwnedEntrypointName::new_unchecked(String::from(entrypoint)),
+                MockFn::returning_ok(()),
+            );
+        }
+        let mut logger = TestLogger::init();
+
+        let parameter_bytes = to_bytes(&vec![
+            ListingParams {
+                token: dummy_token(),
+                owner: SELLER,
+                jurisdiction: None,
+                payment: None,
+            },
+            ListingParams {
+                token: other_token.clone(),
+                owner: SELLER,
+                jurisdiction: None,
+                payment: None,
+            },
+        ]);
+        let mut ctx = TestReceiveContext::empty();
+        ctx.set_sender(Address::Account(SELLER));
+        ctx.set_parameter(&parameter_bytes);
+        claim_eq!(list_batch(&ctx, &mut host, &mut logger), Ok(()));
+        claim_eq!(host.state().listings.iter().count(), 2);
+        claim_eq!(logger.logs.len(), 2);
+
+        // Listed tokens can't be listed again
+        claim_eq!(
+            list_batch(&ctx, &mut host, &mut logger),
+            Err(CustomContractError::TokenAlreadyListedForSale.into())
+        );
+        claim_eq!(logger.logs.len(), 2);
+
+        // Attached amount must cover all listings
+        let parameter_bytes = to_bytes(&BuyBatchParams {
+            tokens: vec![dummy_token(), other_token.clone()],
+        });
+        let mut ctx = TestReceiveContext::empty();
+        ctx.set_sender(Address::Account(BUYER));
+        ctx.set_parameter(&parameter_bytes);
+        host.set_self_balance(Amount::from_ccd(15));
+        let result = buy_batch(&ctx, &mut host, Amount::from_ccd(15), &mut logger);
+        claim_eq!(result, Err(ContractError::InsufficientFunds));
+
+        // Test host doesn't roll back failed transactions, so the listings are restored
+        for token in [dummy_token(), other_token.clone()] {
+            let mut listing = dummy_listing(None);
+            listing.price = Amount::from_ccd(10);
+            host.state_mut().listings.insert(token, listing);
+        }
+        logger.logs.clear();
+
+        host.set_self_balance(Amount::from_ccd(20));
+        let result = buy_batch(&ctx, &mut host, Amount::from_ccd(20), &mut logger);
+        claim_eq!(result, Ok(()));
+        claim!(host.transfer_occurred(&SELLER, Amount::from_ccd(10)));
+        claim_eq!(host.state().listings.iter().count(), 0);
+        claim_eq!(
+            logger.logs.len(),
+            2,
+            "Each bought token should produce a BuyEvent"
+        );
+    }
+
+    /// Test offers are escrowed for unlisted tokens, refunded on cancellation and paid to the owner on acceptance.
+    #[concordium_test]
+    fn test_offers() {
+        let mut init_ctx = TestInitContext::empty();
+        init_ctx.set_init_origin(ADMIN);
+        let mut builder = TestStateBuilder::new();
+        let state = init(&init_ctx, &mut builder).expect_report("Contract initialization failed");
+        let mut host = TestHost::new(state, builder);
+        host.setup_mock_entrypoint(
+            dummy_token().contract,
+            OwnedEntrypointName::new_unchecked(String::from("viewToken")),
+            parse_and_map_mock::<ViewTokenParams, _, _>(|params| {
+                // Token is owned by the seller
+                (params.owner == Address::Account(SELLER)).then(|| NFTData {
+                    creator: Address::Account(SELLER),
+                    creator_royalty: 0,
+                    minter: Address::Account(SELLER),
+                    minter_royalty: 0,
+                    price: Amount::zero(),
+                    cid: Vec::new(),
+                    quantity: ContractTokenAmount::from(1),
+                    royalties: RoyaltyRecipients::new(),
+                })
+            }),
+        );
+        host.setup_mock_entrypoint(
+            dummy_token().contract,
+            OwnedEntrypointName::new_unchecked(String::from("transfer")),
+            parse_and_check_mock::<TransferParameter, _>(
+                |params| {
+                    params.0[0].from == Address::Account(SELLER)
+                        && matches!(params.0[0].to, Receiver::Account(to) if to == BUYER)
+                },
+                (),
+            ),
+        );
+        let mut logger = TestLogger::init();
+
+        // Offers need an escrowed amount
+        let token_bytes = to_bytes(&dummy_token());
+        let mut ctx = TestReceiveContext::empty();
+        ctx.set_sender(Address::Account(BUYER));
+        ctx.set_parameter(&token_bytes);
+        claim_eq!(
+            make_offer(&ctx, &mut host, Amount::zero(), &mut logger),
+            Err(CustomContractError::InvalidFields.into())
+        );
+
+        // Repeated offers raise the escrowed amount
+        claim_eq!(
+            make_offer(&ctx, &mut host, Amount::from_ccd(60), &mut logger),
+            Ok(())
+        );
+        claim_eq!(
+            make_offer(&ctx, &mut host, Amount::from_ccd(40), &mut logger),
+            Ok(())
+        );
+        claim_eq!(
+            logger.logs.last(),
+            Some(&to_bytes(&CustomEvent::OfferMade(OfferEvent {
+                token: dummy_token(),
+                buyer: BUYER,
+                amount: Amount::from_ccd(100),
+            })))
+        );
+
+        // Other offer is canceled and refunded
+        ctx.set_sender(Address::Account(COLLECTOR));
+        claim_eq!(
+            make_offer(&ctx, &mut host, Amount::from_ccd(50), &mut logger),
+            Ok(())
+        );
+        host.set_self_balance(Amount::from_ccd(150));
+        claim_eq!(cancel_offer(&ctx, &mut host, &mut logger), Ok(()));
+        claim!(host.transfer_occurred(&COLLECTOR, Amount::from_ccd(50)));
+        claim_eq!(
+            cancel_offer(&ctx, &mut host, &mut logger),
+            Err(CustomContractError::NotFound.into())
+        );
+
+        let parameter_bytes = to_bytes(&OfferParams {
+            token: dummy_token(),
+            buyer: BUYER,
+        });
+        ctx.set_parameter(&parameter_bytes);
+        claim_eq!(view_offer(&ctx, &host), Ok(Some(Amount::from_ccd(100))));
+
+        // Owner accepts the offer without listing the token
+        host.state_mut().fees = MarketplaceFees {
+            fee: Percentage::from_percent(5),
+            beneficiary: COLLECTOR,
+        };
+        let parameter_bytes = to_bytes(&AcceptOfferParams {
+            token: dummy_token(),
+            buyer: BUYER,
+            expected_amount: Amount::from_ccd(100),
+        });
+        let mut ctx = TestReceiveContext::empty();
+        ctx.set_sender(Address::Account(BUYER));
+        ctx.set_parameter(&parameter_bytes);
+        claim_eq!(
+            accept_offer(&ctx, &mut host, &mut logger),
+            Err(ContractError::Unauthorized)
+        );
+
+        // Offer must match the amount the owner expects to accept
+        let mismatched_bytes = to_bytes(&AcceptOfferParams {
+            token: dummy_token(),
+            buyer: BUYER,
+            expected_amount: Amount::from_ccd(60),
+        });
+        ctx.set_sender(Address::Account(SELLER));
+        ctx.set_parameter(&mismatched_bytes);
+        claim_eq!(
+            accept_offer(&ctx, &mut host, &mut logger),
+            Err(CustomContractError::InvalidFields.into())
+        );
+
+        ctx.set_parameter(&parameter_bytes);
+        claim_eq!(accept_offer(&ctx, &mut host, &mut logger), Ok(()));
+        claim!(host.transfer_occurred(&SELLER, Amount::from_ccd(95)));
+        claim!(host.transfer_occurred(&COLLECTOR, Amount::from_ccd(5)));
+        claim!(host.state().offers.get(&(dummy_token(), BUYER)).is_none());
+        claim_eq!(
+            logger.logs.last(),
+            Some(&to_bytes(&CustomEvent::Buy(BuyEvent {
+                token: dummy_token(),
+                seller: SELLER,
+                buyer: BUYER,
+                owner_share: Amount::from_ccd(95),
+                creator_share: Amount::zero(),
+                price: Amount::from_ccd(100),
+                tax: Amount::zero(),
+                jurisdiction: None,
+                commission: Amount::zero(),
+                fee: Amount::from_ccd(5),
+                payment_token: None,
+            })))
+        );
+    }
+
+    /// Test listings wait for approval with curation on and can be bought only after a maintainer approves them.
+    #[concordium_test]
+    fn test_curation() {
+        let mut init_ctx = TestInitContext::empty();
+        init_ctx.set_init_origin(ADMIN);
+        let mut builder = TestStateBuilder::new();
+        let state = init(&init_ctx, &mut builder).expect_report("Contract initialization failed");
+        let mut host = TestHost::new(state, builder);
+        host.setup_mock_entrypoint(
+            dummy_token().contract,
+            OwnedEntrypointName::new_unchecked(String::from("viewToken")),
+            parse_and_map_mock::<ViewTokenParams, _, _>(|_| {
+                Some(NFTData {
+                    creator: Address::Account(SELLER),
+                    creator_royalty: 0,
+                    minter: Address::Account(SELLER),
+                    minter_royalty: 0,
+                    price: Amount::from_ccd(100),
+                    cid: Vec::new(),
+                    quantity: ContractTokenAmount::from(1),
+                    royalties: RoyaltyRecipients::new(),
+                })
+            }),
+        );
+        host.setup_mock_entrypoint(
+            dummy_token().contract,
+            OwnedEntrypointName::new_unchecked(String::from("updateOperator")),
+            MockFn::returning_ok(()),
+        );
+        host.setup_mock_entrypoint(
+            dummy_token().contract,
+            OwnedEntrypointName::new_unchecked(String::from("updatePrice")),
+            MockFn::returning_ok(()),
+        );
+        let mut logger = TestLogger::init();
+
+        // Only maintainers can turn curation on
+        let parameter_bytes = to_bytes(&true);
+        let mut ctx = TestReceiveContext::empty();
+        ctx.set_sender(Address::Account(SELLER));
+        ctx.set_parameter(&parameter_bytes);
+        claim_eq!(
+            set_curation(&ctx, &mut host),
+            Err(ContractError::Unauthorized)
+        );
+        ctx.set_sender(Address::Account(ADMIN));
+        claim_eq!(set_curation(&ctx, &mut host), Ok(()));
+
+        let parameter_bytes = to_bytes(&ListingParams {
+            token: dummy_token(),
+            owner: SELLER,
+            jurisdiction: None,
+            payment: None,
+        });
+        let mut ctx = TestReceiveContext::empty();
+        ctx.set_sender(Address::Account(SELLER));
+        ctx.set_parameter(&parameter_bytes);
+        claim_eq!(list(&ctx, &mut host, &mut logger), Ok(()));
+
+        // Pending listing can't be bought
+        let parameter_bytes = to_bytes(&BuyParams {
+            token: dummy_token(),
+        });
+        let mut ctx = TestReceiveContext::empty();
+        ctx.set_sender(Address::Account(BUYER));
+        ctx.set_parameter(&parameter_bytes);
+        let result = buy(&ctx, &mut host, Amount::from_ccd(100), &mut logger);
+        claim_eq!(result, Err(CustomContractError::UnknownToken.into()));
+
+        let parameter_bytes = to_bytes(&ViewPendingParams { skip: 0, show: 10 });
+        let mut ctx = TestReceiveContext::empty();
+        ctx.set_parameter(&parameter_bytes);
+        let pending = view_pending(&ctx, &host).expect_report("Failed to view pending listings");
+        claim_eq!(pending.len(), 1);
+        claim_eq!(pending[0].token, dummy_token());
+
+        // Only maintainers can approve listings
+        let parameter_bytes = to_bytes(&dummy_token());
+        let mut ctx = TestReceiveContext::empty();
+        ctx.set_sender(Address::Account(SELLER));
+        ctx.set_parameter(&parameter_bytes);
+        claim_eq!(
+            approve_listing(&ctx, &mut host, &mut logger),
+            Err(ContractError::Unauthorized)
+        );
+
+        ctx.set_sender(Address::Account(ADMIN));
+        claim_eq!(approve_listing(&ctx, &mut host, &mut logger), Ok(()));
+        claim!(host.state().listings.get(&dummy_token()).is_some());
+        claim!(host.state().pending.get(&dummy_token()).is_none());
+        claim_eq!(
+            logger.logs.last(),
+            Some(&to_bytes(&CustomEvent::ListingApproved(ListParams {
+                token: dummy_token(),
+                owner: SELLER,
+            })))
+        );
+
+        // Approved listing is no longer pending
+        claim_eq!(
+            reject_listing(&ctx, &mut host, &mut logger),
+            Err(CustomContractError::TokenNotListedForSale.into())
+        );
+    }
+
+    /// Test only the owner can change the price of a listing, which logs the old and new price.
+    #[concordium_test]
+    fn test_update_price() {
+        let mut init_ctx = TestInitContext::empty();
+        init_ctx.set_init_origin(ADMIN);
+        let mut builder = TestStateBuilder::new();
+        let state = init(&init_ctx, &mut builder).expect_report("Contract initialization failed");
+        let mut host = TestHost::new(state, builder);
+        host.setup_mock_entrypoint(
+            dummy_token().contract,
+            OwnedEntrypointName::new_unchecked(String::from("updatePrice")),
+            MockFn::returning_ok(()),
+        );
+        let mut logger = TestLogger::init();
+
+        let parameter_bytes = to_bytes(&UpdateListingPrice {
+            token: dummy_token(),
+            price: Amount::from_ccd(80),
+        });
+        let mut ctx = TestReceiveContext::empty();
+        ctx.set_sender(Address::Account(SELLER));
+        ctx.set_parameter(&parameter_bytes);
+        claim_eq!(
+            update_price(&ctx, &mut host, &mut logger),
+            Err(CustomContractError::TokenNotListedForSale.into())
+        );
+
+        host.state_mut()
+            .listings
+            .insert(dummy_token(), dummy_listing(None));
+        ctx.set_sender(Address::Account(BUYER));
+        claim_eq!(
+            update_price(&ctx, &mut host, &mut logger),
+            Err(CustomContractError::OnlyOwner.into())
+        );
+        claim!(logger.logs.is_empty());
+
+        ctx.set_sender(Address::Account(SELLER));
+        claim_eq!(update_price(&ctx, &mut host, &mut logger), Ok(()));
+        claim_eq!(
+            host.state()
+                .listings
+                .get(&dummy_token())
+                .map(|listing| listing.price),
+            Some(Amount::from_ccd(80))
+        );
+        claim_eq!(
+            logger.logs,
+            vec![to_bytes(&CustomEvent::PriceChanged(PriceChangedEvent {
+                token: dummy_token(),
+                owner: SELLER,
+                old_price: Amount::from_ccd(100),
+                new_price: Amount::from_ccd(80),
+            }))]
+        );
+    }
+
+    /// Test listings can be viewed by owner and by NFT contract, and the views follow listing changes.
+    #[concordium_test]
+    fn test_listing_views() {
+        let mut init_ctx = TestInitContext::empty();
+        init_ctx.set_init_origin(ADMIN);
+        let mut builder = TestStateBuilder::new();
+        let state = init(&init_ctx, &mut builder).expect_report("Contract initialization failed");
+        let mut host = TestHost::new(state, builder);
+
+        let other_token = Token {
+            id: TokenIdVec(vec![0, 2]),
+            ..dummy_token()
+        };
+        let other_contract_token = Token {
+            contract: CUSTODIAN,
+            ..dummy_token()
+        };
+        let (state, state_builder) = host.state_and_builder();
+        state.insert_listing(&dummy_token(), dummy_listing(None), state_builder);
+        state.insert_listing(&other_token, dummy_listing(None), state_builder);
+        state.insert_listing(
+            &other_contract_token,
+            NFTDetails {
+                owner: BUYER,
+                ..dummy_listing(None)
+            },
+            state_builder,
+        );
+
+        let by_owner = |host: &TestHost<State<TestStateApi>>, owner: Address, skip: u32| {
+            let parameter_bytes = to_bytes(&ViewListingsByOwnerParams {
+                owner,
+                skip,
+                show: 10,
+            });
+            let mut ctx = TestReceiveContext::empty();
+            ctx.set_parameter(&parameter_bytes);
+            view_listings_by_owner(&ctx, host)
+                .expect_report("Viewing listings failed")
+                .into_iter()
+                .map(|entry| entry.token)
+                .collect::<Vec<_>>()
+        };
+        let by_contract = |host: &TestHost<State<TestStateApi>>, contract: ContractAddress| {
+            let parameter_bytes = to_bytes(&ViewListingsByContractParams {
+                contract,
+                skip: 0,
+                show: 10,
+            });
+            let mut ctx = TestReceiveContext::empty();
+            ctx.set_parameter(&parameter_bytes);
+            view_listings_by_contract(&ctx, host)
+                .expect_report("Viewing listings failed")
+                .into_iter()
+                .map(|entry| entry.token)
+                .collect::<Vec<_>>()
+        };
+
+        claim_eq!(by_owner(&host, Address::Account(SELLER), 0).len(), 2);
+        claim_eq!(by_owner(&host, Address::Account(SELLER), 1).len(), 1);
+        claim_eq!(
+            by_owner(&host, Address::Account(BUYER), 0),
+            vec![other_contract_token.clone()]
+        );
+        claim!(by_owner(&host, Address::Contract(CUSTODIAN), 0).is_empty());
+        claim_eq!(by_contract(&host, dummy_token().contract).len(), 2);
+        claim_eq!(
+            by_contract(&host, CUSTODIAN),
+            vec![other_contract_token.clone()]
+        );
+
+        // Removed listings leave the views
+        host.state_mut().remove_listing(&other_token);
+        claim_eq!(
+            by_owner(&host, Address::Account(SELLER), 0),
+            vec![dummy_token()]
+        );
+        claim_eq!(
+            by_contract(&host, dummy_token().contract),
+            vec![dummy_token()]
+        );
+    }
+
+    /// Test escrowed purchases hold the token and the payment until the buyer or, after the dispute window, the seller
+    /// finalizes, and disputes are resolved by maintainers.
+    #[concordium_test]
+    fn test_escrow() {
+        const LISTING: ContractAddress = ContractAddress {
+            index: 5,
+            subindex: 0,
+        };
+        let mut init_ctx = TestInitContext::empty();
+        init_ctx.set_init_origin(ADMIN);
+        let mut builder = TestStateBuilder::new();
+        let state = init(&init_ctx, &mut builder).expect_report("Contract initialization failed");
+        let mut host = TestHost::new(state, builder);
+        host.setup_mock_entrypoint(
+            dummy_token().contract,
+            OwnedEntrypointName::new_unchecked(String::from("transfer")),
+            MockFn::returning_ok(()),
+        );
+        let mut logger = TestLogger::init();
+        let escrow_event = || EscrowEvent {
+            token: dummy_token(),
+            seller: SELLER,
+            buyer: BUYER,
+        };
+
+        // Only maintainers can turn escrow on
+        let parameter_bytes = to_bytes(&Some(Duration::from_millis(1_000)));
+        let mut ctx = TestReceiveContext::empty();
+        ctx.set_sender(Address::Account(SELLER));
+        ctx.set_parameter(&parameter_bytes);
+        claim_eq!(
+            set_escrow_window(&ctx, &mut host),
+            Err(ContractError::Unauthorized)
+        );
+        ctx.set_sender(Address::Account(ADMIN));
+        claim_eq!(set_escrow_window(&ctx, &mut host), Ok(()));
+
+        let buy_bytes = to_bytes(&BuyParams {
+            token: dummy_token(),
+        });
+        let token_bytes = to_bytes(&dummy_token());
+        fn ctx_at(sender: AccountAddress, millis: u64, parameter: &[u8]) -> TestReceiveContext {
+            let mut ctx = TestReceiveContext::empty();
+            ctx.set_sender(Address::Account(sender));
+            ctx.set_self_address(LISTING);
+            ctx.set_metadata_slot_time(Timestamp::from_timestamp_millis(millis));
+            ctx.set_parameter(parameter);
+            ctx
+        }
+
+        // Payment and token are held in escrow
+        host.state_mut()
+            .listings
+            .insert(dummy_token(), dummy_listing(None));
+        let ctx = ctx_at(BUYER, 0, &buy_bytes);
+        claim_eq!(
+            buy(&ctx, &mut host, Amount::from_ccd(100), &mut logger),
+            Ok(())
+        );
+        claim!(host.state().escrows.get(&dummy_token()).is_some());
+        claim!(!host.transfer_occurred(&SELLER, Amount::from_ccd(100)));
+        claim_eq!(
+            logger.logs.last(),
+            Some(&to_bytes(&CustomEvent::EscrowOpened(escrow_event())))
+        );
+
+        // Escrowed token is accepted into custody
+        let parameter_bytes = to_bytes(&OnReceivingCis2Params {
+            token_id: dummy_token().id,
+            amount: ContractTokenAmount::from(1),
+            from: Address::Account(SELLER),
+            data: AdditionalData::empty(),
+        });
+        let mut ctx = ctx_at(BUYER, 0, &parameter_bytes);
+        ctx.set_sender(Address::Contract(dummy_token().contract));
+        claim_eq!(on_receiving_cis2(&ctx, &mut host, &mut logger), Ok(()));
+
+        // Seller can't finalize within the dispute window, which only the buyer can dispute
+        let ctx = ctx_at(SELLER, 500, &token_bytes);
+        claim_eq!(
+            finalize_escrow(&ctx, &mut host, &mut logger),
+            Err(CustomContractError::OperationNotPermitted.into())
+        );
+        let ctx = ctx_at(COLLECTOR, 500, &token_bytes);
+        claim_eq!(
+            dispute_escrow(&ctx, &mut host, &mut logger),
+            Err(ContractError::Unauthorized)
+        );
+        let ctx = ctx_at(BUYER, 500, &token_bytes);
+        claim_eq!(dispute_escrow(&ctx, &mut host, &mut logger), Ok(()));
+        claim_eq!(
+            logger.logs.last(),
+            Some(&to_bytes(&CustomEvent::EscrowDisputed(escrow_event())))
+        );
+        claim_eq!(
+            finalize_escrow(&ctx, &mut host, &mut logger),
+            Err(CustomContractError::OperationNotPermitted.into())
+        );
+
+        // Maintainer refunds the disputed purchase
+        let parameter_bytes = to_bytes(&ResolveEscrowParams {
+            token: dummy_token(),
+            release: false,
+        });
+        let ctx = ctx_at(SELLER, 500, &parameter_bytes);
+        claim_eq!(
+            resolve_escrow(&ctx, &mut host, &mut logger),
+            Err(ContractError::Unauthorized)
+        );
+        host.set_self_balance(Amount::from_ccd(100));
+        let ctx = ctx_at(ADMIN, 500, &parameter_bytes);
+        claim_eq!(resolve_escrow(&ctx, &mut host, &mut logger), Ok(()));
+        claim!(host.state().escrows.get(&dummy_token()).is_none());
+        claim!(host.transfer_occurred(&BUYER, Amount::from_ccd(100)));
+        claim_eq!(
+            logger.logs.last(),
+            Some(&to_bytes(&CustomEvent::EscrowRefunded(escrow_event())))
+        );
+
+        // Seller finalizes an undisputed purchase after the dispute window
+        host.state_mut()
+            .listings
+            .insert(dummy_token(), dummy_listing(None));
+        let ctx = ctx_at(BUYER, 0, &buy_bytes);
+        claim_eq!(
+            buy(&ctx, &mut host, Amount::from_ccd(100), &mut logger),
+            Ok(())
+        );
+        host.set_self_balance(Amount::from_ccd(100));
+        let ctx = ctx_at(SELLER, 1_001, &token_bytes);
+        claim_eq!(finalize_escrow(&ctx, &mut host, &mut logger), Ok(()));
+        claim!(host.state().escrows.get(&dummy_token()).is_none());
+        claim!(host.transfer_occurred(&SELLER, Amount::from_ccd(100)));
+    }
+
+    /// Test trading is rejected while the contract is paused.
+    #[concordium_test]
+    fn test_pause() {
+        let mut init_ctx = TestInitContext::empty();
+        init_ctx.set_init_origin(ADMIN);
+        let mut builder = TestStateBuilder::new();
+        let state = init(&init_ctx, &mut builder).expect_report("Contract initialization failed");
+        let mut host = TestHost::new(state, builder);
+        host.setup_mock_entrypoint(
+            dummy_token().contract,
+            OwnedEntrypointName::new_unchecked(String::from("transfer")),
+            MockFn::returning_ok(()),
+        );
+        let mut logger = TestLogger::init();
+        host.state_mut()
+            .listings
+            .insert(dummy_token(), dummy_listing(None));
+
+        // Only maintainers can pause trading
+        let paused_bytes = to_bytes(&true);
+        let mut ctx = TestReceiveContext::empty();
+        ctx.set_sender(Address::Account(SELLER));
+        ctx.set_parameter(&paused_bytes);
+        claim_eq!(
+            set_paused(&ctx, &mut host),
+            Err(ContractError::Unauthorized)
+        );
+
+        ctx.set_sender(Address::Account(ADMIN));
+        claim_eq!(set_paused(&ctx, &mut host), Ok(()));
+
+        let parameter_bytes = to_bytes(&BuyParams {
+            token: dummy_token(),
+        });
+        let mut buy_ctx = TestReceiveContext::empty();
+        buy_ctx.set_sender(Address::Account(BUYER));
+        buy_ctx.set_parameter(&parameter_bytes);
+        host.set_self_balance(Amount::from_ccd(100));
+        let result = buy(&buy_ctx, &mut host, Amount::from_ccd(100), &mut logger);
+        claim_eq!(result, Err(CustomContractError::ContractPaused.into()));
+        claim!(host.state().is_listed(&dummy_token()));
+
+        // Trading resumes once unpaused
+        let paused_bytes = to_bytes(&false);
+        ctx.set_parameter(&paused_bytes);
+        claim_eq!(set_paused(&ctx, &mut host), Ok(()));
+        let result = buy(&buy_ctx, &mut host, Amount::from_ccd(100), &mut logger);
+        claim_eq!(result, Ok(()));
+        claim!(host.transfer_occurred(&SELLER, Amount::from_ccd(100)));
+    }
 }
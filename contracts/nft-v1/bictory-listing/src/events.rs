@@ -13,6 +13,52 @@ pub struct BuyEvent {
     pub owner_share: Amount,
     /// Creayers's share.
     pub creator_share: Amount,
+    /// Listing price the shares are calculated from.
+    pub price: Amount,
+    /// Sales tax paid on top of the price.
+    pub tax: Amount,
+    /// Jurisdiction the tax was collected for.
+    pub jurisdiction: Option<String>,
+    /// Custodian's commission deducted from the seller's share of consigned listings.
+    pub commission: Amount,
+    /// Marketplace fee deducted from the seller's share.
+    pub fee: Amount,
+    /// CIS-2 token the purchase was paid with, with amounts in its smallest units. `None` for CCD.
+    pub payment_token: Option<Token>,
+}
+
+/// An untagged event of an offer made or canceled.
+#[derive(Debug, Serialize, SchemaType)]
+pub struct OfferEvent {
+    /// Token the offer is made for.
+    pub token: Token,
+    /// Account making the offer.
+    pub buyer: AccountAddress,
+    /// Total escrowed amount of the offer, or the refunded amount on cancellation.
+    pub amount: Amount,
+}
+
+/// An untagged event of a change of an escrowed purchase.
+#[derive(Debug, Serialize, SchemaType)]
+pub struct EscrowEvent {
+    /// Token held in escrow.
+    pub token: Token,
+    /// The address owning the token before buying.
+    pub seller: AccountAddress,
+    /// The address buying the token.
+    pub buyer: AccountAddress,
+}
+
+/// An untagged event of the price of a listed token being changed.
+#[derive(Debug, Serialize, SchemaType)]
+pub struct PriceChangedEvent {
+    pub token: Token,
+    /// Owner that listed the token.
+    pub owner: AccountAddress,
+    /// Price before the change.
+    pub old_price: Amount,
+    /// Price after the change.
+    pub new_price: Amount,
 }
 
 /// Tagged Custom event to be serialized for the event log.
@@ -23,7 +69,23 @@ pub enum CustomEvent {
     /// Buying NFT
     Buy(BuyEvent),
     /// Listing NFT
-    Listing(ListParams),
+    Listing(ListingParams),
+    /// Pending listing was approved by a maintainer and can be bought
+    ListingApproved(ListParams),
+    /// Pending listing was rejected by a maintainer
+    ListingRejected(ListParams),
+    /// Offer was made or raised
+    OfferMade(OfferEvent),
+    /// Offer was canceled and refunded
+    OfferCanceled(OfferEvent),
+    /// Purchase was paid and the token taken into escrow
+    EscrowOpened(EscrowEvent),
+    /// Buyer disputed an escrowed purchase
+    EscrowDisputed(EscrowEvent),
+    /// Escrowed purchase was canceled, returning the token to the seller and the payment to the buyer
+    EscrowRefunded(EscrowEvent),
+    /// Price of a listed NFT was changed
+    PriceChanged(PriceChangedEvent),
 }
 
 impl Serial for CustomEvent {
@@ -34,13 +96,45 @@ impl Serial for CustomEvent {
                 event.serial(out)
             }
             CustomEvent::Buy(event) => {
-                out.write_u8(BUY_TAG)?;
+                out.write_u8(SALE_TAG)?;
                 event.serial(out)
             }
             CustomEvent::Listing(event) => {
                 out.write_u8(LISTING_TAG)?;
                 event.serial(out)
             }
+            CustomEvent::ListingApproved(event) => {
+                out.write_u8(LISTING_APPROVED_TAG)?;
+                event.serial(out)
+            }
+            CustomEvent::ListingRejected(event) => {
+                out.write_u8(LISTING_REJECTED_TAG)?;
+                event.serial(out)
+            }
+            CustomEvent::OfferMade(event) => {
+                out.write_u8(OFFER_MADE_TAG)?;
+                event.serial(out)
+            }
+            CustomEvent::OfferCanceled(event) => {
+                out.write_u8(OFFER_CANCELED_TAG)?;
+                event.serial(out)
+            }
+            CustomEvent::EscrowOpened(event) => {
+                out.write_u8(ESCROW_OPENED_TAG)?;
+                event.serial(out)
+            }
+            CustomEvent::EscrowDisputed(event) => {
+                out.write_u8(ESCROW_DISPUTED_TAG)?;
+                event.serial(out)
+            }
+            CustomEvent::EscrowRefunded(event) => {
+                out.write_u8(ESCROW_REFUNDED_TAG)?;
+                event.serial(out)
+            }
+            CustomEvent::PriceChanged(event) => {
+                out.write_u8(PRICE_CHANGED_TAG)?;
+                event.serial(out)
+            }
         }
     }
 }
@@ -50,8 +144,16 @@ impl Deserial for CustomEvent {
         let tag = source.read_u8()?;
         match tag {
             UNLISTING_TAG => ListParams::deserial(source).map(CustomEvent::Unlisting),
-            BUY_TAG => BuyEvent::deserial(source).map(CustomEvent::Buy),
-            LISTING_TAG => ListParams::deserial(source).map(CustomEvent::Listing),
+            SALE_TAG => BuyEvent::deserial(source).map(CustomEvent::Buy),
+            LISTING_TAG => ListingParams::deserial(source).map(CustomEvent::Listing),
+            LISTING_APPROVED_TAG => ListParams::deserial(source).map(CustomEvent::ListingApproved),
+            LISTING_REJECTED_TAG => ListParams::deserial(source).map(CustomEvent::ListingRejected),
+            OFFER_MADE_TAG => OfferEvent::deserial(source).map(CustomEvent::OfferMade),
+            OFFER_CANCELED_TAG => OfferEvent::deserial(source).map(CustomEvent::OfferCanceled),
+            ESCROW_OPENED_TAG => EscrowEvent::deserial(source).map(CustomEvent::EscrowOpened),
+            ESCROW_DISPUTED_TAG => EscrowEvent::deserial(source).map(CustomEvent::EscrowDisputed),
+            ESCROW_REFUNDED_TAG => EscrowEvent::deserial(source).map(CustomEvent::EscrowRefunded),
+            PRICE_CHANGED_TAG => PriceChangedEvent::deserial(source).map(CustomEvent::PriceChanged),
             _ => Err(ParseError::default()),
         }
     }
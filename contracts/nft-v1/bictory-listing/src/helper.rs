@@ -43,6 +43,90 @@ pub fn get_update_price_action<S: HasStateApi>(
     Ok(())
 }
 
+/// Transfer a single NFT on behalf of `from`, which is either this contract or an owner it is an operator of.
+pub fn transfer_token<S: HasStateApi>(
+    host: &mut impl HasHost<State<S>, StateApiType = S>,
+    token: &Token,
+    from: Address,
+    to: Receiver,
+) -> ContractResult<()> {
+    let transfer = Transfer {
+        token_id: token.id.clone(),
+        amount: ContractTokenAmount::from(1),
+        from,
+        to,
+        data: AdditionalData::empty(),
+    };
+    let parameter = TransferParams(vec![transfer]);
+    let entrypoint_name = EntrypointName::new_unchecked("transfer");
+    host.invoke_contract(&token.contract, &parameter, entrypoint_name, Amount::zero())?;
+    Ok(())
+}
+
+/// Pay the amount from the listing contract to the account in the currency of a listing: CCD, or the payment token
+/// with a CIS-2 transfer.
+pub fn pay<S: HasStateApi>(
+    host: &mut impl HasHost<State<S>, StateApiType = S>,
+    self_address: ContractAddress,
+    currency: &Option<Token>,
+    to: &AccountAddress,
+    amount: Amount,
+) -> ContractResult<()> {
+    match currency {
+        None => host.invoke_transfer(to, amount)?,
+        Some(token) => {
+            let transfer = Transfer {
+                token_id: token.id.clone(),
+                amount: ContractTokenAmount::from(amount.micro_ccd),
+                from: Address::Contract(self_address),
+                to: Receiver::Account(*to),
+                data: AdditionalData::empty(),
+            };
+            let parameter = TransferParams(vec![transfer]);
+            let entrypoint_name = EntrypointName::new_unchecked("transfer");
+            host.invoke_contract(&token.contract, &parameter, entrypoint_name, Amount::zero())?;
+        }
+    }
+    Ok(())
+}
+
+/// Pay the marketplace fee of a sale to the fee beneficiary and return it. The fee is taken from the owner's share,
+/// so it can't exceed it.
+pub fn pay_marketplace_fee<S: HasStateApi>(
+    host: &mut impl HasHost<State<S>, StateApiType = S>,
+    self_address: ContractAddress,
+    currency: &Option<Token>,
+    price: Amount,
+    owner_share: Amount,
+) -> ContractResult<Amount> {
+    let fees = host.state().fees;
+    let fee = fees.fee * price;
+    ensure!(
+        fee <= owner_share,
+        CustomContractError::InvalidRoyalty.into()
+    );
+    if fee > Amount::zero() {
+        pay(host, self_address, currency, &fees.beneficiary, fee)?;
+    }
+    Ok(fee)
+}
+
+/// Return the auto-auction bounty to the owner once the last item of the listing is removed.
+pub fn refund_bounty<S: HasStateApi>(
+    host: &mut impl HasHost<State<S>, StateApiType = S>,
+    nft_details: &NFTDetails,
+) -> ContractResult<()> {
+    if nft_details.quantity != 0.into() {
+        return Ok(());
+    }
+    if let Some(auto_auction) = &nft_details.auto_auction {
+        if auto_auction.bounty > Amount::zero() {
+            host.invoke_transfer(&nft_details.owner, auto_auction.bounty)?;
+        }
+    }
+    Ok(())
+}
+
 pub fn get_account_address(address: Address) -> ContractResult<AccountAddress> {
     match address {
         Address::Account(addr) => Ok(addr),
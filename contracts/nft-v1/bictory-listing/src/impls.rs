@@ -1,50 +1,205 @@
 use super::*;
 
+use core::ops::DerefMut;
+
 // Functions for creating and updating the contract state.
 impl<S: HasStateApi> State<S> {
     /// Creates a new state with no listings.
-    pub fn empty(state_builder: &mut StateBuilder<S>) -> Self {
+    pub fn empty(state_builder: &mut StateBuilder<S>, admin: AccountAddress) -> Self {
         Self {
             listings: state_builder.new_map(),
+            authority: Authority::new(state_builder, Address::Account(admin)),
+            taxes: state_builder.new_map(),
+            consignor_keys: state_builder.new_map(),
+            nonces: state_builder.new_map(),
+            curation: false,
+            pending: state_builder.new_map(),
+            offers: state_builder.new_map(),
+            fees: MarketplaceFees {
+                fee: Percentage::ZERO,
+                beneficiary: admin,
+            },
+            owner_listings: state_builder.new_map(),
+            contract_listings: state_builder.new_map(),
+            escrow_window: None,
+            escrows: state_builder.new_map(),
+            paused: false,
         }
     }
 
-    /// Add/update the state with the new listing price.
+    /// Add/update the state with the new listing price. With curation on, the listing waits for approval in the pending
+    /// queue.
     pub fn list(
         &mut self,
         token: &Token,
         owner: AccountAddress,
         nft_data: NFTData,
+        jurisdiction: Option<String>,
+        consignment: Option<Consignment>,
+        payment: Option<TokenPrice>,
+        state_builder: &mut StateBuilder<S>,
     ) -> ContractResult<()> {
-        self.listings.insert(
-            token.clone(),
-            NFTDetails {
-                owner,
-                creator: get_account_address(nft_data.creator)?,
-                creator_royalty: nft_data.creator_royalty,
-                minter: get_account_address(nft_data.minter)?,
-                minter_royalty: nft_data.minter_royalty,
-                price: nft_data.price,
-                quantity: nft_data.quantity,
-            },
-        );
+        let nft_details = NFTDetails {
+            owner,
+            creator: get_account_address(nft_data.creator)?,
+            creator_royalty: nft_data.creator_royalty,
+            minter: get_account_address(nft_data.minter)?,
+            minter_royalty: nft_data.minter_royalty,
+            royalties: nft_data.royalties,
+            price: payment.as_ref().map_or(nft_data.price, |payment| {
+                Amount::from_micro_ccd(payment.amount.0)
+            }),
+            quantity: nft_data.quantity,
+            jurisdiction,
+            consignment,
+            auto_auction: None,
+            payment_token: payment.map(|payment| payment.token),
+        };
+        if self.curation {
+            self.pending.insert(token.clone(), nft_details);
+        } else {
+            self.insert_listing(token, nft_details, state_builder);
+        }
 
         Ok(())
     }
 
+    /// Add or replace an active listing and its index entries.
+    pub fn insert_listing(
+        &mut self,
+        token: &Token,
+        nft_details: NFTDetails,
+        state_builder: &mut StateBuilder<S>,
+    ) {
+        if let Some(previous) = self.listings.insert(token.clone(), nft_details.clone()) {
+            self.unindex(token, &previous);
+        }
+        self.owner_listings
+            .entry(nft_details.owner)
+            .or_insert_with(|| state_builder.new_set())
+            .deref_mut()
+            .insert(token.clone());
+        self.contract_listings
+            .entry(token.contract)
+            .or_insert_with(|| state_builder.new_set())
+            .deref_mut()
+            .insert(token.clone());
+    }
+
+    /// Remove an active listing together with its index entries.
+    pub fn remove_listing(&mut self, token: &Token) -> Option<NFTDetails> {
+        let nft_details = self.listings.remove_and_get(token)?;
+        self.unindex(token, &nft_details);
+        Some(nft_details)
+    }
+
+    fn unindex(&mut self, token: &Token, nft_details: &NFTDetails) {
+        self.owner_listings
+            .get_mut(&nft_details.owner)
+            .map(|mut tokens| tokens.remove(token));
+        self.contract_listings
+            .get_mut(&token.contract)
+            .map(|mut tokens| tokens.remove(token));
+    }
+
     /// Remove a listing and fails with UnknownToken, if token is not listed.
     /// Returns the listing price and owner if successful.
     pub fn unlist(&mut self, token: &Token) -> ContractResult<NFTDetails> {
-        let nft_details = *self
+        let nft_details = self
             .listings
             .entry(token.clone())
             .and_modify(|details| details.quantity -= 1.into())
-            .occupied_or(ContractError::Custom(CustomContractError::UnknownToken))?;
+            .occupied_or(ContractError::Custom(CustomContractError::UnknownToken))?
+            .clone();
 
         if nft_details.quantity == 0.into() {
-            self.listings.remove(token)
+            self.remove_listing(token);
         }
 
         Ok(nft_details)
     }
+
+    /// Check if the token is listed or waiting for approval.
+    pub fn is_listed(&self, token: &Token) -> bool {
+        self.listings.get(token).is_some() || self.pending.get(token).is_some()
+    }
+
+    /// Active or pending listing of the token.
+    pub fn listing(&self, token: &Token) -> Option<NFTDetails> {
+        self.listings
+            .get(token)
+            .or_else(|| self.pending.get(token))
+            .map(|details| details.clone())
+    }
+
+    /// Active listings of the tokens, paginated with `skip` and `show`.
+    pub fn listing_entries(
+        &self,
+        tokens: impl Iterator<Item = Token>,
+        skip: u32,
+        show: u32,
+    ) -> Vec<ListingEntry> {
+        tokens
+            .skip(skip as usize)
+            .take(show as usize)
+            .filter_map(|token| {
+                let details = self.listings.get(&token)?.clone();
+                Some(ListingEntry { token, details })
+            })
+            .collect()
+    }
+
+    /// Move a pending listing to the active listings. Fails with TokenNotListedForSale, if the token is not pending.
+    pub fn approve(
+        &mut self,
+        token: &Token,
+        state_builder: &mut StateBuilder<S>,
+    ) -> ContractResult<NFTDetails> {
+        let nft_details = self
+            .pending
+            .remove_and_get(token)
+            .ok_or(CustomContractError::TokenNotListedForSale)?;
+        self.insert_listing(token, nft_details.clone(), state_builder);
+        Ok(nft_details)
+    }
+
+    /// Results in an error if trading is paused.
+    pub fn ensure_not_paused(&self) -> ContractResult<()> {
+        ensure!(!self.paused, CustomContractError::ContractPaused.into());
+        Ok(())
+    }
+
+    /// Check the expected nonce of the owner and increment it.
+    pub fn use_nonce(&mut self, owner: AccountAddress, nonce: u64) -> ContractResult<()> {
+        let mut expected = self.nonces.entry(owner).or_insert_with(|| 0);
+        ensure_eq!(*expected, nonce, CustomContractError::InvalidNonce.into());
+        *expected += 1;
+        Ok(())
+    }
+
+    /// Tax rule applying to the listing, if its jurisdiction is in the tax table.
+    pub fn tax_rule(&self, nft_details: &NFTDetails) -> Option<TaxRule> {
+        nft_details
+            .jurisdiction
+            .as_ref()
+            .and_then(|jurisdiction| self.taxes.get(jurisdiction).map(|rule| *rule))
+    }
+}
+
+impl NFTDetails {
+    /// Royalties of the creator and minter followed by the additional recipients.
+    pub fn royalty_recipients(&self) -> Vec<Royalty> {
+        let mut royalties = vec![
+            Royalty {
+                beneficiary: self.creator,
+                percentage: Percentage::from_micro_percent(self.creator_royalty as u64),
+            },
+            Royalty {
+                beneficiary: self.minter,
+                percentage: Percentage::from_micro_percent(self.minter_royalty as u64),
+            },
+        ];
+        royalties.extend(self.royalties.iter().cloned());
+        royalties
+    }
 }
@@ -5,17 +5,111 @@ use super::*;
 #[concordium(state_parameter = "S")]
 pub struct State<S: HasStateApi> {
     pub listings: StateMap<Token, NFTDetails, S>,
+    pub authority: Authority<S>,
+    /// Sales tax rules by jurisdiction tag
+    pub taxes: StateMap<String, TaxRule, S>,
+    /// Ed25519 public keys used by owners to sign consignment agreements
+    pub consignor_keys: StateMap<AccountAddress, [u8; 32], S>,
+    /// Next expected nonce of consignment agreements for each owner
+    pub nonces: StateMap<AccountAddress, u64, S>,
+    /// New listings require maintainer approval before they can be bought
+    pub curation: bool,
+    /// Listings waiting for maintainer approval
+    pub pending: StateMap<Token, NFTDetails, S>,
+    /// CCD escrowed by accounts offering to buy a token, which doesn't have to be listed
+    pub offers: StateMap<(Token, AccountAddress), Amount, S>,
+    /// Marketplace fee charged on sales
+    pub fees: MarketplaceFees,
+    /// Index of active listings by owner
+    pub owner_listings: StateMap<AccountAddress, StateSet<Token, S>, S>,
+    /// Index of active listings by NFT contract
+    pub contract_listings: StateMap<ContractAddress, StateSet<Token, S>, S>,
+    /// Dispute window of escrowed purchases. `None` settles purchases instantly.
+    pub escrow_window: Option<Duration>,
+    /// Purchases held in escrow, with the token in custody of this contract
+    pub escrows: StateMap<Token, Escrow, S>,
+    /// Set by maintainers to stop trading, e.g. during incident response
+    pub paused: bool,
 }
 
-#[derive(Serialize, SchemaType, Clone, Copy)]
+#[derive(Serialize, SchemaType, Clone)]
 pub struct NFTDetails {
     pub owner: AccountAddress,
     pub creator: AccountAddress,
     pub creator_royalty: u32,
     pub minter: AccountAddress,
     pub minter_royalty: u32,
+    /// Additional royalty recipients, paid after the creator and minter
+    pub royalties: RoyaltyRecipients,
     pub price: Amount,
     pub quantity: ContractTokenAmount,
+    /// Jurisdiction whose sales tax is added on top of the price
+    pub jurisdiction: Option<String>,
+    /// Custodian holding the token on behalf of the owner. `None` for tokens listed by the owner.
+    pub consignment: Option<Consignment>,
+    /// Auction the listing is moved to if it stays unsold
+    pub auto_auction: Option<AutoAuction>,
+    /// CIS-2 token the listing is paid with, with the price in its smallest units. `None` for listings paid in CCD.
+    pub payment_token: Option<Token>,
+}
+
+/// Preset parameters of the auction an unsold listing is moved to.
+#[derive(Debug, Serialize, SchemaType, Clone, PartialEq, Eq)]
+pub struct AutoAuctionTerms {
+    /// Auction contract, whose `createAuction` entrypoint is invoked with `CreateAuctionParams`
+    pub auction: ContractAddress,
+    /// Time after opting in, after which the unsold listing can be swept to the auction
+    pub unsold_for: Duration,
+    /// Duration of the auction
+    pub duration: Duration,
+    /// Lowest bid accepted by the auction
+    pub reserve_price: Amount,
+}
+
+/// Auto-auction opt-in of a listing.
+#[derive(Debug, Serialize, SchemaType, Clone, PartialEq, Eq)]
+pub struct AutoAuction {
+    pub terms: AutoAuctionTerms,
+    /// Listing can be swept to the auction from this time
+    pub sweep_after: Timestamp,
+    /// Deposited by the owner and paid to the account sweeping the listing
+    pub bounty: Amount,
+}
+
+/// Terms of a consigned listing.
+#[derive(Debug, Serialize, SchemaType, Clone, Copy, PartialEq, Eq)]
+pub struct Consignment {
+    /// Address holding the token and listing it on behalf of the owner
+    pub custodian: Address,
+    /// Account receiving the custodian's commission
+    pub commission_account: AccountAddress,
+    /// Part of the owner's share paid to the custodian
+    pub commission: Percentage,
+}
+
+/// Consignment agreement signed by the token owner.
+#[derive(Debug, Serialize, SchemaType, Clone)]
+pub struct ConsignmentAgreement {
+    pub token: Token,
+    /// Account receiving the proceeds of the sale
+    pub owner: AccountAddress,
+    /// Terms of the consignment
+    pub consignment: Consignment,
+    /// Listing price agreed by the owner
+    pub price: Amount,
+    /// Owner's consignment counter, prevents replaying the agreement
+    pub nonce: u64,
+}
+
+/// Type of the parameter to the `listConsigned` function.
+/// Signed message is the serialized listing contract address followed by the serialized agreement.
+#[derive(Debug, Serialize, SchemaType)]
+pub struct ListConsignedParams {
+    pub agreement: ConsignmentAgreement,
+    /// Ed25519 signature of the owner
+    pub signature: [u8; 64],
+    /// Jurisdiction tag from the tax table. Listings without it are not taxed.
+    pub jurisdiction: Option<String>,
 }
 
 #[derive(Debug, SchemaType, Serialize)]
@@ -24,10 +118,184 @@ pub struct ListParams {
     pub owner: AccountAddress,
 }
 
+#[derive(Debug, SchemaType, Serialize)]
+pub struct ListingParams {
+    pub token: Token,
+    pub owner: AccountAddress,
+    /// Jurisdiction tag from the tax table. Listings without it are not taxed.
+    pub jurisdiction: Option<String>,
+    /// Price in a CIS-2 token. Listings without it are paid in CCD at the price from the NFT contract.
+    pub payment: Option<TokenPrice>,
+}
+
+/// Price of a listing paid in a CIS-2 token.
+#[derive(Debug, Serialize, SchemaType, Clone, PartialEq, Eq)]
+pub struct TokenPrice {
+    pub token: Token,
+    pub amount: ContractTokenAmount,
+}
+
+/// Funds a buyer pays with.
+pub struct Funds {
+    /// CIS-2 token received by the listing contract, `None` for CCD
+    pub currency: Option<Token>,
+    pub amount: Amount,
+}
+
+/// Sales tax charged on top of the listing price.
+#[derive(Debug, Serialize, SchemaType, Clone, Copy, PartialEq, Eq)]
+pub struct TaxRule {
+    /// Tax rate applied to the listing price
+    pub rate: Percentage,
+    /// Account the collected tax is remitted to
+    pub collector: AccountAddress,
+}
+
+/// Purchase of a listing, settled instantly or after the dispute window of an escrow.
+#[derive(Serialize, SchemaType, Clone)]
+pub struct Sale {
+    pub token: Token,
+    /// Listing the token was bought from
+    pub listing: NFTDetails,
+    pub buyer: AccountAddress,
+    /// Sales tax paid on top of the price
+    pub tax: Amount,
+    /// Tax rule the tax was calculated with
+    pub tax_rule: Option<TaxRule>,
+}
+
+/// Purchase held in escrow until it's finalized or a dispute is resolved.
+#[derive(Serialize, SchemaType, Clone)]
+pub struct Escrow {
+    pub sale: Sale,
+    /// Buyer can dispute the purchase until this time, after which the seller can finalize it
+    pub dispute_until: Timestamp,
+    /// Disputed purchases can only be resolved by a maintainer
+    pub disputed: bool,
+}
+
+/// Type of the parameter to the `resolveEscrow` function.
+#[derive(Debug, Serialize, SchemaType)]
+pub struct ResolveEscrowParams {
+    pub token: Token,
+    /// Complete the purchase if `true`, otherwise return the token to the seller and refund the buyer
+    pub release: bool,
+}
+
+/// Marketplace fee deducted from the seller's share of each sale.
+#[derive(Debug, Serialize, SchemaType, Clone, Copy, PartialEq, Eq)]
+pub struct MarketplaceFees {
+    /// Fee rate applied to the sale price
+    pub fee: Percentage,
+    /// Account the collected fees are paid to
+    pub beneficiary: AccountAddress,
+}
+
+/// Update Tax Params.
+#[derive(Debug, Serialize, SchemaType)]
+pub struct UpdateTaxParams {
+    /// Jurisdiction tag
+    pub jurisdiction: String,
+    /// New tax rule. `None` removes the jurisdiction from the tax table.
+    pub rule: Option<TaxRule>,
+}
+
+/// Type of the parameter to the `setAutoAuction` function.
+#[derive(Debug, Serialize, SchemaType)]
+pub struct SetAutoAuctionParams {
+    pub token: Token,
+    /// Auction terms. `None` opts the listing out and returns the bounty.
+    pub terms: Option<AutoAuctionTerms>,
+}
+
+/// Kind of the auction created by `sweepToAuction`. Matches the layout of the auction contract's `AuctionKind`, of
+/// which only English auctions are created.
+#[derive(Debug, Serialize, SchemaType, Clone, Copy, PartialEq, Eq)]
+pub enum AuctionKind {
+    English,
+}
+
+/// Auction terms of the auction contract's `createAuction` entrypoint.
+#[derive(Debug, Serialize, SchemaType, PartialEq, Eq)]
+pub struct CreateAuctionParams {
+    pub item: Token,
+    pub expiry: Timestamp,
+    pub cancellation_penalty: Percentage,
+    pub bid_granularity: Amount,
+    pub payment_token: Option<Token>,
+    pub reserve_price: Amount,
+    pub min_increment: Amount,
+    pub extension_window: Duration,
+    pub extension_duration: Duration,
+    pub start: Option<Timestamp>,
+    pub kind: AuctionKind,
+}
+
+/// Parameter of the auction contract's `createAuctionFor` entrypoint, invoked by `sweepToAuction`.
+#[derive(Debug, Serialize, SchemaType, PartialEq, Eq)]
+pub struct CreateAuctionForParams {
+    /// Listing owner, who becomes the seller
+    pub seller: AccountAddress,
+    pub auction: CreateAuctionParams,
+}
+
 #[derive(SchemaType, Serialize)]
 pub struct BuyParams {
     pub token: Token,
-    pub bictory_royalty: u32,
+}
+
+/// Offer of an account to buy a token. Parameter to the `viewOffer` function.
+#[derive(Debug, Serialize, SchemaType)]
+pub struct OfferParams {
+    pub token: Token,
+    /// Account that made the offer
+    pub buyer: AccountAddress,
+}
+
+/// Type of the parameter to the `acceptOffer` function.
+#[derive(Debug, Serialize, SchemaType)]
+pub struct AcceptOfferParams {
+    pub token: Token,
+    /// Account whose offer is accepted
+    pub buyer: AccountAddress,
+    /// Escrowed amount of the offer, which must match the stored offer
+    pub expected_amount: Amount,
+}
+
+/// Type of the parameter to the `buyBatch` function.
+#[derive(SchemaType, Serialize)]
+pub struct BuyBatchParams {
+    pub tokens: Vec<Token>,
+}
+
+/// Type of the parameter to the `viewPending` function.
+#[derive(Debug, Serialize, SchemaType)]
+pub struct ViewPendingParams {
+    pub skip: u32,
+    pub show: u32,
+}
+
+/// Listing together with its token.
+#[derive(Serialize, SchemaType, Clone)]
+pub struct ListingEntry {
+    pub token: Token,
+    pub details: NFTDetails,
+}
+
+/// Type of the parameter to the `viewListingsByOwner` function.
+#[derive(Debug, Serialize, SchemaType)]
+pub struct ViewListingsByOwnerParams {
+    pub owner: Address,
+    pub skip: u32,
+    pub show: u32,
+}
+
+/// Type of the parameter to the `viewListingsByContract` function.
+#[derive(Debug, Serialize, SchemaType)]
+pub struct ViewListingsByContractParams {
+    pub contract: ContractAddress,
+    pub skip: u32,
+    pub show: u32,
 }
 
 /// Update Price Params.
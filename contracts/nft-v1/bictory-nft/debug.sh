@@ -10,6 +10,7 @@ cargo concordium run init \
 --module debug/mod.wasm \
 --contract "BictoryNFT" \
 --context debug/init_context.json \
+--parameter-json parameters/init.json \
 --out-bin debug/state.bin
 
 # mint
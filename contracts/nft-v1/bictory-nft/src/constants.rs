@@ -2,6 +2,21 @@
 /// Note: To add the ERC721 metadata extension, change this to a Map from
 /// TokenId to some Metadata struct.
 
-/// The baseurl for the token metadata, gets appended with the token ID as hex
-/// encoding before emitted in the TokenMetadata event.
+/// The initial baseurl for the token metadata, gets appended with the token ID as hex
+/// encoding before emitted in the TokenMetadata event. Can be changed with `setMetadataBaseUrl`.
 pub const TOKEN_METADATA_BASE_URL: &str = "https://nft.bictory.io/metadata/";
+
+/// Maximum number of items in a single `authorityAirdrop`, so that one event per item fits in the event limit of a
+/// transaction.
+pub const MAX_AIRDROP_ITEMS: usize = 64;
+
+/// Maximum number of tokens transferred by a single `claimInheritance`, so that one event per token and the closing
+/// `InheritanceClaimed` event fit in the event limit of a transaction.
+pub const MAX_INHERITANCE_TOKENS: usize = 62;
+
+/// Version byte of a binary CIDv1
+pub const CID_V1: u8 = 0x01;
+/// Multicodec of a CID addressing raw bytes, whose multihash is the hash of the content itself
+pub const CID_CODEC_RAW: u8 = 0x55;
+/// Multihash code of `sha2-256`
+pub const MULTIHASH_SHA2_256: u8 = 0x12;
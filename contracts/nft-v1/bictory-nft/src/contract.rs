@@ -1,27 +1,56 @@
 use super::*;
 
-/// Initialize contract instance with no token types initially.
-#[init(contract = "BictoryNFT")]
+/// Standards supported by this contract.
+const SUPPORTED_STANDARDS: [StandardIdentifier<'static>; 3] = [
+    CIS0_STANDARD_IDENTIFIER,
+    CIS2_STANDARD_IDENTIFIER,
+    BICTORY_AUTHORITY_STANDARD_IDENTIFIER,
+];
+
+/// Initialize contract instance with no token types initially. The royalty and the contract receiving it can be
+/// changed later with `updateInternalValue`. Only instances initialized in migration mode accept `importState`, and
+/// they reject all other state-changing functions until `sealState` is called.
+///
+/// It rejects if:
+/// - Fails to parse parameter.
+/// - The royalty exceeds 100%.
+#[init(contract = "BictoryNFT", parameter = "InitParams")]
 fn init<S: HasStateApi>(
-    _ctx: &impl HasInitContext,
+    ctx: &impl HasInitContext,
     state_builder: &mut StateBuilder<S>,
 ) -> InitResult<State<S>> {
+    let params: InitParams = ctx.parameter_cursor().get()?;
+    ensure!(
+        params.royalty <= Percentage::HUNDRED,
+        CustomContractError::InvalidFields.into()
+    );
+
     // Construct the initial contract state.
-    let state = State::empty(state_builder);
+    let state = State::empty(state_builder, ctx.init_origin(), params);
     Ok(state)
 }
 
 /// Mint new tokens with a given address as the owner of these tokens.
 /// Logs a `Mint` and a `TokenMetadata` event for each token.
 /// The url for the token metadata is the token ID encoded in hex, appended on
-/// the `TOKEN_METADATA_BASE_URL`.
+/// the metadata base URL set with `setMetadataBaseUrl`.
 /// Ow&mut &mut ner and Minter will be same during minting.
 ///
 /// It rejects if:
+/// - The contract is paused.
 /// - The sender is not the contract instance owner.
 /// - Fails to parse parameter.
+/// - The token ID is reserved for another minter, or the sender has reserved ranges and the token ID is outside of
+///   them.
+/// - The sender is not a maintainer and mint phases are configured, but
+///     - no phase has started yet, or the active phase is closed
+///     - the sender is not on the allowlist of the active phase, or the allowlist is a Merkle tree
+///     - the amount is lower than the phase price of all copies
+///     - the token is not a drop token defined with `updateDropTokens`, or differs from it in anything but the owner
+///     - the sender would exceed the cap of the phase
 /// - Any of the tokens fails to be minted, which could be if:
 ///     - The minted token ID already exists.
+///     - The creator, minter and additional royalties sum up to more than 100%.
 ///     - Fails to log Mint event
 ///     - Fails to log TokenMetadata event
 ///
@@ -30,7 +59,7 @@ fn init<S: HasStateApi>(
 #[receive(
     contract = "BictoryNFT",
     name = "mint",
-    parameter = "MintParams",
+    parameter = "MintTokenParams",
     mutable,
     enable_logger,
     payable
@@ -41,40 +70,99 @@ fn mint<S: HasStateApi>(
     price: Amount,
     logger: &mut impl HasLogger,
 ) -> ContractResult<()> {
+    host.state().ensure_sealed()?;
+
+    // Parse the parameter.
+    let params: MintTokenParams = ctx.parameter_cursor().get()?;
+
+    mint_token(
+        ctx,
+        host,
+        params.mint,
+        params.transferable.unwrap_or(true),
+        price,
+        None,
+        logger,
+    )
+}
+
+/// Mint a token during a mint phase with a Merkle allowlist, proving the sender is on it. The leaf of the sender is the
+/// SHA-256 hash of the serialized sender address. Otherwise same as `mint`.
+///
+/// It rejects if:
+/// - Fails to parse parameter.
+/// - The proof does not lead to the root of the allowlist of the active phase.
+/// - The token fails to be minted, same as in `mint`.
+#[receive(
+    contract = "BictoryNFT",
+    name = "mintWithProof",
+    parameter = "MintWithProofParams",
+    mutable,
+    enable_logger,
+    payable,
+    crypto_primitives
+)]
+fn mint_with_proof<S: HasStateApi>(
+    ctx: &impl HasReceiveContext,
+    host: &mut impl HasHost<State<S>, StateApiType = S>,
+    price: Amount,
+    logger: &mut impl HasLogger,
+    crypto_primitives: &impl HasCryptoPrimitives,
+) -> ContractResult<()> {
+    host.state().ensure_sealed()?;
+
     // Parse the parameter.
-    let mint_data: MintParams = ctx.parameter_cursor().get()?;
+    let params: MintWithProofParams = ctx.parameter_cursor().get()?;
+
+    let leaf = crypto_primitives.hash_sha2_256(&to_bytes(&ctx.sender())).0;
+    let root = merkle_root(leaf, &params.proof, crypto_primitives);
+
+    mint_token(ctx, host, params.mint, true, price, Some(root), logger)
+}
+
+/// Mint the token, pay royalties and log the `Mint` and `TokenMetadata` events. `proven_root` is the root of the Merkle
+/// allowlist the sender proved membership in, if any. The price of tokens minted during a mint phase goes to the drop
+/// beneficiary.
+fn mint_token<S: HasStateApi>(
+    ctx: &impl HasReceiveContext,
+    host: &mut impl HasHost<State<S>, StateApiType = S>,
+    mint_data: MintParams,
+    transferable: bool,
+    price: Amount,
+    proven_root: Option<[u8; 32]>,
+    logger: &mut impl HasLogger,
+) -> ContractResult<()> {
+    host.state().ensure_not_paused()?;
 
     let (state, state_builder) = host.state_and_builder();
     let token_id = mint_data.token_id.clone();
 
+    // Token IDs reserved for other minters can't be taken.
+    state.check_reservation(&token_id, &ctx.sender())?;
+    // Only the active mint phase's minters can mint its drop tokens.
+    let phase_mint = state.use_mint_phase(ctx, &mint_data, price, proven_root)?;
+
     // Mint the token in the state.
-    state.mint(mint_data.clone(), price, state_builder)?;
+    state.mint(mint_data.clone(), price, transferable, state_builder)?;
+    state.record_activity(&ctx.sender(), ctx);
 
-    if mint_data.owner.ne(&mint_data.creator) {
-        // Royalty is set to 20% when creator and minter are different
-        let bictory_royalty = 20_000_000;
-        let royalty_to_creator: u64 = 100_000_000 - bictory_royalty;
-        let shares = calc_shares(price, royalty_to_creator, 0, bictory_royalty);
+    if phase_mint {
+        let drop_beneficiary = state.drop_beneficiary;
+        if price > Amount::zero() {
+            host.invoke_transfer(&drop_beneficiary, price)?;
+        }
+    } else if mint_data.owner.ne(&mint_data.creator) {
+        // Bictory receives the configured royalty when creator and minter are different
+        let bictory_share = state.royalty * price;
+        let creator_share = price - bictory_share;
+        let fee_receiver = state.fee_receiver;
 
         if let Address::Account(creator) = mint_data.creator {
-            host.invoke_transfer(&creator, shares.creator)?;
+            host.invoke_transfer(&creator, creator_share)?;
         }
 
         let entrypoint_name = EntrypointName::new_unchecked("sendCCD");
-
-        // Hardcoding address of config smart contract for security reasons.
-        // In a rare case of Bictory's wallet address change this contract address must
-        // also be required to be updated.
-        let bictory_config_contract_address = ContractAddress {
-            index: 571,
-            subindex: 0,
-        };
-        host.invoke_contract(
-            &bictory_config_contract_address,
-            &"",
-            entrypoint_name,
-            shares.bictory,
-        )?;
+        host.invoke_contract(&fee_receiver, &"", entrypoint_name, bictory_share)?;
     }
 
     // Event for minted NFT.
@@ -85,7 +173,7 @@ fn mint<S: HasStateApi>(
     }))?;
 
     // Metadata URL for the NFT.
-    logger.log(&token_metadata_event(token_id))?;
+    logger.log(&token_metadata_event(host.state(), token_id))?;
 
     Ok(())
 }
@@ -97,6 +185,7 @@ fn mint<S: HasStateApi>(
 /// receiver of a transfer.
 ///
 /// It rejects if:
+/// - The contract is paused.
 /// - It fails to parse the parameter.
 /// - Any of the transfers fail to be executed, which could be if:
 ///     - The `token_id` does not exist.
@@ -118,10 +207,25 @@ fn transfer<S: HasStateApi>(
     host: &mut impl HasHost<State<S>, StateApiType = S>,
     logger: &mut impl HasLogger,
 ) -> ContractResult<()> {
+    host.state().ensure_sealed()?;
+
     // Parse the parameter.
     let TransferParams(transfers): TransferParameter = ctx.parameter_cursor().get()?;
     // Get the sender who invoked this contract function.
-    let sender = ctx.sender();
+    transfer_tokens(ctx, host, logger, ctx.sender(), transfers)
+}
+
+/// Execute token transfers on behalf of the sender, logging a `Transfer` event and notifying contract receivers.
+fn transfer_tokens<S: HasStateApi>(
+    ctx: &impl HasReceiveContext,
+    host: &mut impl HasHost<State<S>, StateApiType = S>,
+    logger: &mut impl HasLogger,
+    sender: Address,
+    transfers: Vec<Transfer<ContractTokenId, ContractTokenAmount>>,
+) -> ContractResult<()> {
+    host.state().ensure_not_paused()?;
+
+    host.state_mut().record_activity(&sender, ctx);
 
     for transfer in transfers {
         let (state, state_builder) = host.state_and_builder();
@@ -164,6 +268,197 @@ fn transfer<S: HasStateApi>(
     Ok(())
 }
 
+/// Send tokens owned by the sender to many receivers at once.
+/// Balance of each distinct token is checked once for the total amount sent,
+/// before any transfer is made.
+///
+/// Logs a `Transfer` event for each item and an `Airdrop` summary event.
+/// Produces an action which sends a message to each contract which is a
+/// receiver.
+///
+/// It rejects if:
+/// - The contract is paused.
+/// - It fails to parse the parameter.
+/// - Any of the tokens does not exist.
+/// - The sender does not own enough copies of any of the tokens.
+/// - Fails to log event.
+/// - Any of the messages sent to contracts receiving tokens choose to reject.
+#[receive(
+    contract = "BictoryNFT",
+    name = "airdrop",
+    parameter = "Vec<AirdropItem>",
+    mutable,
+    enable_logger
+)]
+fn airdrop<S: HasStateApi>(
+    ctx: &impl HasReceiveContext,
+    host: &mut impl HasHost<State<S>, StateApiType = S>,
+    logger: &mut impl HasLogger,
+) -> ContractResult<()> {
+    host.state().ensure_sealed()?;
+    host.state().ensure_not_paused()?;
+
+    // Parse the parameter.
+    let items: Vec<AirdropItem> = ctx.parameter_cursor().get()?;
+    // Tokens are always sent from the sender.
+    let sender = ctx.sender();
+
+    // Sum up amounts per token to check ownership once per token.
+    let mut totals: Vec<(ContractTokenId, ContractTokenAmount)> = Vec::new();
+    for item in items.iter() {
+        match totals
+            .iter_mut()
+            .find(|(token_id, _)| *token_id == item.token_id)
+        {
+            Some((_, total)) => *total += item.amount,
+            None => totals.push((item.token_id.clone(), item.amount)),
+        }
+    }
+
+    let state = host.state();
+    for (token_id, total) in totals.iter() {
+        ensure!(
+            state.balance(token_id, &sender)? >= *total,
+            ContractError::InsufficientFunds
+        );
+    }
+
+    host.state_mut().record_activity(&sender, ctx);
+
+    let transfers = items.len() as u32;
+    let amount = totals
+        .iter()
+        .fold(ContractTokenAmount::from(0), |acc, (_, total)| acc + *total);
+
+    for item in items {
+        send_airdrop_item(host, logger, sender, item)?;
+    }
+
+    logger.log(&CustomEvent::<ContractTokenId>::Airdrop(AirdropEvent {
+        owner: sender,
+        transfers,
+        amount,
+    }))?;
+
+    Ok(())
+}
+
+/// Mint or send tokens to many receivers at once on behalf of the authority, e.g. for marketing campaigns. Tokens are
+/// minted without payment and are transferable. Sent tokens must be owned by the sender.
+///
+/// Logs a `Mint` event for each minted item and a `Transfer` event for each sent item. Metadata of minted tokens is
+/// available through `tokenMetadata`.
+/// Produces an action which sends a message to each contract which is a
+/// receiver of sent tokens.
+///
+/// It rejects if:
+/// - The contract is paused.
+/// - Sender is not a maintainer.
+/// - It fails to parse the parameter.
+/// - There are more than `MAX_AIRDROP_ITEMS` items.
+/// - Any of the minted token IDs already exists.
+/// - Any of the sent tokens does not exist or is not owned by the sender.
+/// - Fails to log event.
+/// - Any of the messages sent to contracts receiving tokens choose to reject.
+#[receive(
+    contract = "BictoryNFT",
+    name = "authorityAirdrop",
+    parameter = "Vec<AuthorityAirdropItem>",
+    mutable,
+    enable_logger
+)]
+fn authority_airdrop<S: HasStateApi>(
+    ctx: &impl HasReceiveContext,
+    host: &mut impl HasHost<State<S>, StateApiType = S>,
+    logger: &mut impl HasLogger,
+) -> ContractResult<()> {
+    host.state().ensure_sealed()?;
+    host.state().ensure_not_paused()?;
+
+    let sender = ctx.sender();
+    ensure!(
+        host.state().authority.has_maintainer_rights(&sender),
+        ContractError::Unauthorized
+    );
+
+    // Parse the parameter.
+    let items: Vec<AuthorityAirdropItem> = ctx.parameter_cursor().get()?;
+    ensure!(
+        items.len() <= MAX_AIRDROP_ITEMS,
+        CustomContractError::CapacityExceeded.into()
+    );
+
+    for item in items {
+        match item {
+            AuthorityAirdropItem::Mint(mint_data) => {
+                let (state, state_builder) = host.state_and_builder();
+                let event = MintEvent {
+                    token_id: mint_data.token_id.clone(),
+                    amount: mint_data.quantity,
+                    owner: mint_data.owner,
+                };
+                state.mint(mint_data, Amount::zero(), true, state_builder)?;
+
+                // Event for minted NFT.
+                logger.log(&Cis2Event::Mint(event))?;
+            }
+            AuthorityAirdropItem::Transfer(item) => {
+                send_airdrop_item(host, logger, sender, item)?;
+            }
+        }
+    }
+
+    host.state_mut().record_activity(&sender, ctx);
+
+    Ok(())
+}
+
+/// Send tokens of the airdrop item from the sender and log the `Transfer` event.
+fn send_airdrop_item<S: HasStateApi>(
+    host: &mut impl HasHost<State<S>, StateApiType = S>,
+    logger: &mut impl HasLogger,
+    sender: Address,
+    item: AirdropItem,
+) -> ContractResult<()> {
+    let (state, state_builder) = host.state_and_builder();
+    let transfer = Transfer {
+        token_id: item.token_id,
+        amount: item.amount,
+        from: sender,
+        to: item.to,
+        data: AdditionalData::empty(),
+    };
+
+    // Update the contract state
+    state.transfer(&transfer, state_builder)?;
+
+    // Log transfer event
+    logger.log(&Cis2Event::Transfer(TransferEvent {
+        token_id: transfer.token_id.clone(),
+        amount: transfer.amount,
+        from: sender,
+        to: transfer.to.address(),
+    }))?;
+
+    if let Receiver::Contract(address, entrypoint_name) = transfer.to {
+        let parameter = OnReceivingCis2Params {
+            token_id: transfer.token_id,
+            amount: transfer.amount,
+            from: sender,
+            data: transfer.data,
+        };
+
+        host.invoke_contract(
+            &address,
+            &parameter,
+            entrypoint_name.as_entrypoint_name(),
+            Amount::zero(),
+        )?;
+    }
+
+    Ok(())
+}
+
 /// Enable or disable addresses as operators of the sender address.
 /// Logs an `UpdateOperator` event.
 ///
@@ -183,12 +478,25 @@ fn update_operator<S: HasStateApi>(
     host: &mut impl HasHost<State<S>, StateApiType = S>,
     logger: &mut impl HasLogger,
 ) -> ContractResult<()> {
+    host.state().ensure_sealed()?;
+
     // Parse the parameter.
     let UpdateOperatorParams(params) = ctx.parameter_cursor().get()?;
     // Get the sender who invoked this contract function.
     let sender = Address::Account(ctx.invoker());
+    update_operators(ctx, host, logger, sender, params)
+}
 
+/// Add or remove operators of the sender, logging an `UpdateOperator` event for each update.
+fn update_operators<S: HasStateApi>(
+    ctx: &impl HasReceiveContext,
+    host: &mut impl HasHost<State<S>, StateApiType = S>,
+    logger: &mut impl HasLogger,
+    sender: Address,
+    params: Vec<UpdateOperator>,
+) -> ContractResult<()> {
     let (state, state_builder) = host.state_and_builder();
+    state.record_activity(&sender, ctx);
     for param in params {
         // Update the operator in the state.
         match param.update {
@@ -211,6 +519,117 @@ fn update_operator<S: HasStateApi>(
     Ok(())
 }
 
+/// Register Ed25519 public key of the sender account, that is used to verify messages signed by the account for
+/// `permit`. Registering a new key replaces the previous one.
+///
+/// It rejects if:
+/// - The sender is a contract.
+/// - Fails to parse parameter.
+#[receive(
+    contract = "BictoryNFT",
+    name = "registerPermitKey",
+    parameter = "[u8; 32]",
+    mutable
+)]
+fn register_permit_key<S: HasStateApi>(
+    ctx: &impl HasReceiveContext,
+    host: &mut impl HasHost<State<S>, StateApiType = S>,
+) -> ContractResult<()> {
+    host.state().ensure_sealed()?;
+
+    let sender = match ctx.sender() {
+        Address::Account(account) => account,
+        Address::Contract(_) => return Err(ContractError::Unauthorized),
+    };
+    let public_key: [u8; 32] = ctx.parameter_cursor().get()?;
+
+    host.state_mut().permit_keys.insert(sender, public_key);
+
+    Ok(())
+}
+
+/// Perform a transfer or an operator update signed by an account, so that a relayer can send the transaction and pay
+/// for it on behalf of the token owner. The action is executed as if it was sent by the signer, logging the same
+/// events.
+///
+/// It rejects if:
+/// - The contract is paused.
+/// - Fails to parse parameter.
+/// - The message has expired.
+/// - The signer has no registered key, or the signature or nonce is invalid.
+/// - The action fails, same as `transfer` or `updateOperator`.
+#[receive(
+    contract = "BictoryNFT",
+    name = "permit",
+    parameter = "PermitParams",
+    mutable,
+    enable_logger,
+    crypto_primitives
+)]
+fn permit<S: HasStateApi>(
+    ctx: &impl HasReceiveContext,
+    host: &mut impl HasHost<State<S>, StateApiType = S>,
+    logger: &mut impl HasLogger,
+    crypto_primitives: &impl HasCryptoPrimitives,
+) -> ContractResult<()> {
+    host.state().ensure_sealed()?;
+    // Checked for every action, including operator updates, which are otherwise allowed while paused
+    host.state().ensure_not_paused()?;
+
+    // Parse the parameter.
+    let params: PermitParams = ctx.parameter_cursor().get()?;
+    let message = params.message;
+
+    ensure!(
+        ctx.metadata().slot_time() <= message.expiry,
+        CustomContractError::InvalidDuration.into()
+    );
+
+    let state = host.state_mut();
+    let public_key = *state
+        .permit_keys
+        .get(&message.signer)
+        .ok_or(CustomContractError::InvalidSignature)?;
+
+    // Signature is bound to this contract instance to prevent replaying it on other instances
+    let signed_message = to_bytes(&(ctx.self_address(), &message));
+    ensure!(
+        crypto_primitives.verify_ed25519_signature(
+            PublicKeyEd25519(public_key),
+            SignatureEd25519(params.signature),
+            &signed_message
+        ),
+        CustomContractError::InvalidSignature.into()
+    );
+    state.use_nonce(message.signer, message.nonce)?;
+
+    let signer = Address::Account(message.signer);
+    match message.action {
+        PermitAction::Transfer(TransferParams(transfers)) => {
+            transfer_tokens(ctx, host, logger, signer, transfers)
+        }
+        PermitAction::UpdateOperator(UpdateOperatorParams(updates)) => {
+            update_operators(ctx, host, logger, signer, updates)
+        }
+    }
+}
+
+/// View the nonce expected in the next message signed by the account for `permit`.
+#[receive(
+    contract = "BictoryNFT",
+    name = "viewPermitNonce",
+    parameter = "AccountAddress",
+    return_value = "u64"
+)]
+fn view_permit_nonce<S: HasStateApi>(
+    ctx: &impl HasReceiveContext,
+    host: &impl HasHost<State<S>, StateApiType = S>,
+) -> ContractResult<u64> {
+    // Parse the parameter.
+    let account: AccountAddress = ctx.parameter_cursor().get()?;
+    Ok(host.state().nonces.get(&account).map_or(0, |nonce| *nonce))
+}
+
 /// Takes a list of queries. Each query is an owner address and some address to
 /// check as an operator of the owner address. It takes a contract address plus
 /// contract function to invoke with the result.
@@ -274,16 +693,18 @@ fn balance_of<S: HasStateApi>(
 }
 
 /// NFT Burn Functionality.
-/// Can only be called by token owner.
+/// Can only be called by token owner or its operator.
 /// Logs a `Burn` and a `TokenMetadata` event for each token.
 /// The url for the token metadata is the token ID encoded in hex, appended on
-/// the `TOKEN_METADATA_BASE_URL`.
+/// the metadata base URL set with `setMetadataBaseUrl`.
 ///
 /// It rejects if:
-/// - The sender is not the token owner.
+/// - The contract is paused.
+/// - The sender is neither the token owner nor its operator.
 /// - Fails to parse parameter.
 /// - Tokens fails to be upated, which could be if:
 ///     - The minted token ID does not exist.
+///     - The owner holds less copies than the quantity.
 ///     - Fails to log Burn event
 ///     - Fails to log TokenMetadata event
 #[receive(
@@ -298,26 +719,69 @@ fn burn<S: HasStateApi>(
     host: &mut impl HasHost<State<S>>,
     logger: &mut impl HasLogger,
 ) -> ContractResult<()> {
+    host.state().ensure_sealed()?;
+    host.state().ensure_not_paused()?;
+
     // Parse the parameter.
     let params: BurnParams = ctx.parameter_cursor().get()?;
 
+    burn_tokens(ctx, host.state_mut(), logger, vec![params])
+}
+
+/// Burn a list of tokens, in the order of the list, same as `burn`.
+///
+/// It rejects if:
+/// - The contract is paused.
+/// - Fails to parse parameter.
+/// - Any of the burns fails, same as in `burn`.
+#[receive(
+    contract = "BictoryNFT",
+    name = "burnMany",
+    parameter = "Vec<BurnParams>",
+    mutable,
+    enable_logger
+)]
+fn burn_many<S: HasStateApi>(
+    ctx: &impl HasReceiveContext,
+    host: &mut impl HasHost<State<S>>,
+    logger: &mut impl HasLogger,
+) -> ContractResult<()> {
+    host.state().ensure_sealed()?;
+    host.state().ensure_not_paused()?;
+
+    // Parse the parameter.
+    let burns: Vec<BurnParams> = ctx.parameter_cursor().get()?;
+
+    burn_tokens(ctx, host.state_mut(), logger, burns)
+}
+
+/// Burn the tokens on behalf of the sender and log the `Burn` and `TokenMetadata` events.
+fn burn_tokens<S: HasStateApi>(
+    ctx: &impl HasReceiveContext,
+    state: &mut State<S>,
+    logger: &mut impl HasLogger,
+    burns: Vec<BurnParams>,
+) -> ContractResult<()> {
     let sender = ctx.sender();
-    let state = host.state_mut();
 
-    // Authenticate the sender for this burn
-    ensure!(
-        params.owner == sender || state.is_operator(&sender, &params.owner),
-        ContractError::Unauthorized
-    );
+    for params in burns {
+        // Authenticate the sender for this burn
+        ensure!(
+            params.owner == sender || state.is_operator(&sender, &params.owner),
+            ContractError::Unauthorized
+        );
 
-    // Burning NFT
-    let event = state.burn(&params.owner, params.clone())?;
+        // Burning NFT
+        let token_id = params.token_id.clone();
+        let event = state.burn(&params.owner, params)?;
 
-    // Event for burning NFT.
-    logger.log(&Cis2Event::Burn(event))?;
+        // Event for burning NFT.
+        logger.log(&Cis2Event::Burn(event))?;
 
-    // Metadata URL for the NFT.
-    logger.log(&token_metadata_event(params.token_id))?;
+        // Metadata URL for the NFT.
+        logger.log(&token_metadata_event(state, token_id))?;
+    }
+    state.record_activity(&sender, ctx);
 
     Ok(())
 }
@@ -326,9 +790,10 @@ fn burn<S: HasStateApi>(
 /// Can only be called by token owner.
 /// Logs a `UpdatePrice` and a `TokenMetadata` event for each token.
 /// The url for the token metadata is the token ID encoded in hex, appended on
-/// the `TOKEN_METADATA_BASE_URL`.
+/// the metadata base URL set with `setMetadataBaseUrl`.
 ///
 /// It rejects if:
+/// - The contract is paused.
 /// - The sender is not the token owner.
 /// - Fails to parse parameter.
 /// - Tokens fails to be upated, which could be if:
@@ -347,6 +812,9 @@ fn update_price<S: HasStateApi>(
     host: &mut impl HasHost<State<S>>,
     logger: &mut impl HasLogger,
 ) -> ContractResult<()> {
+    host.state().ensure_sealed()?;
+    host.state().ensure_not_paused()?;
+
     // Parse the parameter.
     let params: UpdatePriceParameter = ctx.parameter_cursor().get()?;
 
@@ -354,47 +822,173 @@ fn update_price<S: HasStateApi>(
     let state = host.state_mut();
 
     // Updating Price
-    let event = state.update_price(&Address::Account(ctx.invoker()), params)?;
+    let owner = Address::Account(ctx.invoker());
+    let event = state.update_price(&owner, params)?;
+    state.record_activity(&owner, ctx);
 
     // Event for updating price of NFT.
     logger.log(&CustomEvent::UpdatePrice(event))?;
 
     // Metadata URL for the NFT.
-    logger.log(&token_metadata_event(token_id))?;
+    logger.log(&token_metadata_event(state, token_id))?;
 
     Ok(())
 }
 
-fn token_metadata_event(
-    token_id: ContractTokenId,
-) -> Cis2Event<ContractTokenId, ContractTokenAmount> {
-    let token_metadata_url = build_token_metadata_url(&token_id);
-    Cis2Event::TokenMetadata(TokenMetadataEvent {
-        token_id,
-        metadata_url: MetadataUrl {
-            url: token_metadata_url,
-            hash: None,
-        },
-    })
-}
-
-/// View tokens owned by particular address.
+/// Designate an heir who can claim all tokens of the sender after the sender has been inactive for the given period, or
+/// cancel the plan if no heir is provided. Any later state-changing action of the sender restarts the inactivity
+/// period.
+/// Logs an `InheritanceUpdated` event.
+///
+/// It rejects if:
+/// - It fails to parse the parameter.
+/// - The inactivity period is zero.
+/// - Fails to log event.
 #[receive(
     contract = "BictoryNFT",
-    name = "viewAddressStateByOwner",
-    parameter = "Address",
-    return_value = "ViewAddressState"
+    name = "setHeir",
+    parameter = "Option<InheritancePlan>",
+    mutable,
+    enable_logger
 )]
-fn view_address_state_by_owner<S: HasStateApi>(
+fn set_heir<S: HasStateApi>(
     ctx: &impl HasReceiveContext,
-    host: &impl HasHost<State<S>, StateApiType = S>,
-) -> ReceiveResult<ViewAddressState> {
+    host: &mut impl HasHost<State<S>, StateApiType = S>,
+    logger: &mut impl HasLogger,
+) -> ContractResult<()> {
+    host.state().ensure_sealed()?;
+
     // Parse the parameter.
-    let owner: Address = ctx.parameter_cursor().get()?;
-    let mut view_addr_state = ViewAddressState::new();
+    let plan: Option<InheritancePlan> = ctx.parameter_cursor().get()?;
+    let owner = ctx.sender();
 
-    if let Some(addr_state) = host.state().state.get(&owner) {
-        let operators = addr_state.operators.iter().map(|x| *x).collect();
+    host.state_mut()
+        .set_inheritance(owner, plan, ctx.metadata().slot_time())?;
+
+    logger.log(&CustomEvent::<ContractTokenId>::InheritanceUpdated(
+        InheritanceUpdatedEvent { owner, plan },
+    ))?;
+
+    Ok(())
+}
+
+/// Claim the tokens of an owner who has been inactive for longer than the inactivity period of its inheritance plan. At
+/// most `MAX_INHERITANCE_TOKENS` tokens are transferred per call, and the plan is removed once no transferable tokens
+/// are left, so owners with more tokens are claimed over several calls. Operators of the owner are kept.
+/// Logs a `Transfer` event for each token, followed by an `InheritanceClaimed` event.
+///
+/// It rejects if:
+/// - The contract is paused.
+/// - It fails to parse the parameter.
+/// - The owner has no inheritance plan.
+/// - The sender is not the heir of the owner.
+/// - The owner was active within the inactivity period.
+/// - Fails to log event.
+#[receive(
+    contract = "BictoryNFT",
+    name = "claimInheritance",
+    parameter = "Address",
+    mutable,
+    enable_logger
+)]
+fn claim_inheritance<S: HasStateApi>(
+    ctx: &impl HasReceiveContext,
+    host: &mut impl HasHost<State<S>, StateApiType = S>,
+    logger: &mut impl HasLogger,
+) -> ContractResult<()> {
+    host.state().ensure_sealed()?;
+    host.state().ensure_not_paused()?;
+
+    // Parse the parameter.
+    let owner: Address = ctx.parameter_cursor().get()?;
+
+    let (state, state_builder) = host.state_and_builder();
+    let plan = state.claimable_inheritance(&owner, &ctx.sender(), ctx.metadata().slot_time())?;
+
+    let mut tokens = state.transferable_tokens(&owner);
+    if tokens.len() > MAX_INHERITANCE_TOKENS {
+        tokens.truncate(MAX_INHERITANCE_TOKENS);
+    } else {
+        state.inheritance.remove(&owner);
+    }
+    for (token_id, amount) in tokens.iter() {
+        let transfer = Transfer {
+            token_id: token_id.clone(),
+            amount: *amount,
+            from: owner,
+            to: Receiver::from_account(plan.heir),
+            data: AdditionalData::empty(),
+        };
+        state.transfer(&transfer, state_builder)?;
+
+        logger.log(&Cis2Event::Transfer(TransferEvent {
+            token_id: transfer.token_id,
+            amount: transfer.amount,
+            from: owner,
+            to: Address::Account(plan.heir),
+        }))?;
+    }
+
+    logger.log(&CustomEvent::<ContractTokenId>::InheritanceClaimed(
+        InheritanceClaimedEvent {
+            owner,
+            heir: plan.heir,
+            tokens: tokens.len() as u32,
+        },
+    ))?;
+
+    Ok(())
+}
+
+/// View the inheritance plan of the owner together with its last activity.
+#[receive(
+    contract = "BictoryNFT",
+    name = "viewInheritance",
+    parameter = "Address",
+    return_value = "Option<Inheritance>"
+)]
+fn view_inheritance<S: HasStateApi>(
+    ctx: &impl HasReceiveContext,
+    host: &impl HasHost<State<S>, StateApiType = S>,
+) -> ContractResult<Option<Inheritance>> {
+    // Parse the parameter.
+    let owner: Address = ctx.parameter_cursor().get()?;
+
+    Ok(host
+        .state()
+        .inheritance
+        .get(&owner)
+        .map(|inheritance| *inheritance))
+}
+
+fn token_metadata_event<S: HasStateApi>(
+    state: &State<S>,
+    token_id: ContractTokenId,
+) -> Cis2Event<ContractTokenId, ContractTokenAmount> {
+    let metadata_url = state.metadata_url(&token_id);
+    Cis2Event::TokenMetadata(TokenMetadataEvent {
+        token_id,
+        metadata_url,
+    })
+}
+
+/// View tokens owned by particular address.
+#[receive(
+    contract = "BictoryNFT",
+    name = "viewAddressStateByOwner",
+    parameter = "Address",
+    return_value = "ViewAddressState"
+)]
+fn view_address_state_by_owner<S: HasStateApi>(
+    ctx: &impl HasReceiveContext,
+    host: &impl HasHost<State<S>, StateApiType = S>,
+) -> ReceiveResult<ViewAddressState> {
+    // Parse the parameter.
+    let owner: Address = ctx.parameter_cursor().get()?;
+    let mut view_addr_state = ViewAddressState::new();
+
+    if let Some(addr_state) = host.state().state.get(&owner) {
+        let operators = addr_state.operators.iter().map(|x| *x).collect();
         view_addr_state.operators = operators;
 
         for (token_id, owned_data) in addr_state.owned_tokens.iter() {
@@ -412,12 +1006,12 @@ fn view_address_state_by_owner<S: HasStateApi>(
     contract = "BictoryNFT",
     name = "viewToken",
     parameter = "ViewTokenParams",
-    return_value = "NFTData"
+    return_value = "TokenView"
 )]
 fn view_token<S: HasStateApi>(
     ctx: &impl HasReceiveContext,
     host: &impl HasHost<State<S>, StateApiType = S>,
-) -> ReceiveResult<NFTData> {
+) -> ReceiveResult<TokenView> {
     // Parse the parameter.
     let params: ViewTokenParams = ctx.parameter_cursor().get()?;
 
@@ -431,112 +1025,1486 @@ fn view_token<S: HasStateApi>(
         .get(&params.token_id)
         .ok_or(ContractError::InvalidTokenId)?;
 
-    Ok(owned_data.as_nft_data())
+    Ok(TokenView {
+        data: owned_data.as_nft_data(),
+        transferable: owned_data.transferable,
+    })
 }
 
-#[concordium_cfg_test]
-mod tests {
-    use super::*;
-    use test_infrastructure::*;
+/// View the holder of a numbered copy of a token together with the metadata URL of the edition.
+///
+/// It rejects if:
+/// - Fails to parse parameter.
+/// - The token ID does not exist.
+/// - The edition was burned or never minted.
+#[receive(
+    contract = "BictoryNFT",
+    name = "viewEdition",
+    parameter = "ViewEditionParams",
+    return_value = "EditionView"
+)]
+fn view_edition<S: HasStateApi>(
+    ctx: &impl HasReceiveContext,
+    host: &impl HasHost<State<S>, StateApiType = S>,
+) -> ContractResult<EditionView> {
+    // Parse the parameter.
+    let params: ViewEditionParams = ctx.parameter_cursor().get()?;
 
-    const ACCOUNT_0: AccountAddress = AccountAddress([0u8; 32]);
-    const ADDRESS_0: Address = Address::Account(ACCOUNT_0);
-    const ACCOUNT_1: AccountAddress = AccountAddress([1u8; 32]);
-    const ADDRESS_1: Address = Address::Account(ACCOUNT_1);
+    let owner = host
+        .state()
+        .edition_owner(&params.token_id, params.edition)?;
+
+    Ok(EditionView {
+        edition: params.edition,
+        owner,
+        metadata_url: build_edition_metadata_url(
+            &host.state().metadata_base_url,
+            &params.token_id,
+            params.edition,
+        ),
+    })
+}
 
-    fn token_0() -> ContractTokenId {
-        TokenIdVec(vec![0, 1])
-    }
+/// View royalties of a token in the same format as the CNS NFT contract, so that marketplaces can settle sales of
+/// both the same way. Lists the creator and minter royalties followed by Bictory's share of sales.
+///
+/// It rejects if:
+/// - Fails to parse parameter.
+/// - The token ID does not exist.
+/// - All copies of the token were burned.
+#[receive(
+    contract = "BictoryNFT",
+    name = "getRoyalties",
+    parameter = "GetRoyaltiesParams",
+    return_value = "GetRoyaltiesResponse"
+)]
+fn get_royalties<S: HasStateApi>(
+    ctx: &impl HasReceiveContext,
+    host: &impl HasHost<State<S>, StateApiType = S>,
+) -> ContractResult<GetRoyaltiesResponse> {
+    let params: GetRoyaltiesParams = ctx.parameter_cursor().get()?;
+    host.state().royalties(&params.token_id)
+}
 
-    fn token_1() -> ContractTokenId {
-        TokenIdVec(vec![42, 84, 168])
-    }
+/// View current holders of all tokens with their balances, so that airdrop and governance tooling can enumerate
+/// holders without reconstructing them from events. Holders are returned page by page starting from the `cursor` until
+/// the returned `next` is `None`. Can only be called by admins and maintainers.
+///
+/// It rejects if:
+/// - The sender is neither one of the admins nor one of the maintainers.
+/// - Fails to parse parameter.
+/// - The limit is zero.
+#[receive(
+    contract = "BictoryNFT",
+    name = "snapshotHolders",
+    parameter = "SnapshotHoldersParams",
+    return_value = "SnapshotHoldersResult"
+)]
+fn snapshot_holders<S: HasStateApi>(
+    ctx: &impl HasReceiveContext,
+    host: &impl HasHost<State<S>, StateApiType = S>,
+) -> ContractResult<SnapshotHoldersResult> {
+    ensure!(
+        host.state().authority.has_maintainer_rights(&ctx.sender()),
+        ContractError::Unauthorized
+    );
 
-    fn get_mint_data(
-        owner: Address,
-        creator: Address,
-        token_id: ContractTokenId,
-        quantity: ContractTokenAmount,
-    ) -> MintData<ContractTokenId> {
-        MintData {
-            token_id,
-            cid: Vec::new(),
-            creator_royalty: 0,
-            minter_royalty: 0,
-            creator,
-            quantity,
-            owner,
-        }
-    }
+    // Parse the parameter.
+    let params: SnapshotHoldersParams = ctx.parameter_cursor().get()?;
+    ensure!(params.limit > 0, CustomContractError::InvalidFields.into());
 
-    fn new_mint_params(
-        owner: Address,
-        creator: Address,
-        token_id: ContractTokenId,
-        quantity: ContractTokenAmount,
-    ) -> MintParams {
-        get_mint_data(owner, creator, token_id, quantity)
-    }
+    host.state().snapshot_holders(params.cursor, params.limit)
+}
 
-    /// Test helper function which creates a contract state with two tokens with
-    /// id `token_0` and id `token_1` owned by `ADDRESS_0`
-    fn initial_state<S: HasStateApi>(
-        state_builder: &mut StateBuilder<S>,
-        quantity: ContractTokenAmount,
-    ) -> State<S> {
-        let mut state = State::empty(state_builder);
-        let price = Amount::zero();
+/// View the number of token IDs minted in this contract, including tokens with all copies burned.
+#[receive(contract = "BictoryNFT", name = "totalSupply", return_value = "u64")]
+fn total_supply<S: HasStateApi>(
+    _ctx: &impl HasReceiveContext,
+    host: &impl HasHost<State<S>, StateApiType = S>,
+) -> ContractResult<u64> {
+    Ok(host.state().total_supply())
+}
 
-        // parameter
-        let mut mint_data = get_mint_data(ADDRESS_0, ADDRESS_0, token_0(), quantity);
+/// View a page of all token IDs minted in this contract, so the collection can be enumerated without reconstructing it
+/// from events.
+///
+/// It rejects if:
+/// - Fails to parse parameter.
+#[receive(
+    contract = "BictoryNFT",
+    name = "viewTokens",
+    parameter = "ViewTokensParams",
+    return_value = "Vec<ContractTokenId>"
+)]
+fn view_tokens<S: HasStateApi>(
+    ctx: &impl HasReceiveContext,
+    host: &impl HasHost<State<S>, StateApiType = S>,
+) -> ContractResult<Vec<ContractTokenId>> {
+    // Parse the parameter.
+    let params: ViewTokensParams = ctx.parameter_cursor().get()?;
 
-        state
-            .mint(mint_data, price, state_builder)
-            .expect_report("Failed to mint token_0");
+    Ok(host.state().tokens(params.skip, params.show))
+}
 
-        // parameter
-        mint_data = get_mint_data(ADDRESS_1, ADDRESS_1, token_1(), quantity);
+/// View a page of current holders of a token.
+///
+/// It rejects if:
+/// - Fails to parse parameter.
+/// - The token ID does not exist.
+#[receive(
+    contract = "BictoryNFT",
+    name = "viewOwners",
+    parameter = "ViewOwnersParams",
+    return_value = "Vec<Address>"
+)]
+fn view_owners<S: HasStateApi>(
+    ctx: &impl HasReceiveContext,
+    host: &impl HasHost<State<S>, StateApiType = S>,
+) -> ContractResult<Vec<Address>> {
+    // Parse the parameter.
+    let params: ViewOwnersParams = ctx.parameter_cursor().get()?;
+
+    host.state()
+        .owners(&params.token_id, params.skip, params.show)
+}
+
+/// Reserve a token ID prefix for a minter, so that tokens with IDs starting with the prefix can only be minted by that
+/// minter. Can only be called by admins and maintainers.
+///
+/// It rejects if:
+/// - The sender is neither one of the admins nor one of the maintainers.
+/// - Fails to parse parameter.
+/// - The prefix is empty.
+/// - The prefix overlaps with a prefix reserved for another minter.
+/// - The reservation to release does not exist.
+#[receive(
+    contract = "BictoryNFT",
+    name = "reserveTokenIds",
+    parameter = "ReserveTokenIdsParams",
+    mutable
+)]
+fn reserve_token_ids<S: HasStateApi>(
+    ctx: &impl HasReceiveContext,
+    host: &mut impl HasHost<State<S>, StateApiType = S>,
+) -> ContractResult<()> {
+    host.state().ensure_sealed()?;
+
+    ensure!(
+        host.state().authority.has_maintainer_rights(&ctx.sender()),
+        ContractError::Unauthorized
+    );
+
+    // Parse the parameter.
+    let params: ReserveTokenIdsParams = ctx.parameter_cursor().get()?;
+
+    host.state_mut().reserve_token_ids(params)
+}
+
+/// View all token ID prefixes reserved for minters.
+#[receive(
+    contract = "BictoryNFT",
+    name = "viewReservations",
+    return_value = "Vec<TokenIdReservation>"
+)]
+fn view_reservations<S: HasStateApi>(
+    _ctx: &impl HasReceiveContext,
+    host: &impl HasHost<State<S>, StateApiType = S>,
+) -> ContractResult<Vec<TokenIdReservation>> {
+    Ok(host.state().reservations())
+}
+
+/// Mint a new token and invoke the configured mint hook with its ID and the data provided by the caller in the same
+/// transaction, so partner contracts can register the token without a separate call. Minting is rolled back if the
+/// hook rejects.
+///
+/// It rejects if:
+/// - The contract is paused.
+/// - Fails to parse parameter.
+/// - The mint hook is not configured.
+/// - The token fails to be minted, same as in `mint`.
+/// - The mint hook rejects.
+#[receive(
+    contract = "BictoryNFT",
+    name = "mintAndRegister",
+    parameter = "MintAndRegisterParams",
+    mutable,
+    enable_logger,
+    payable
+)]
+fn mint_and_register<S: HasStateApi>(
+    ctx: &impl HasReceiveContext,
+    host: &mut impl HasHost<State<S>, StateApiType = S>,
+    price: Amount,
+    logger: &mut impl HasLogger,
+) -> ContractResult<()> {
+    host.state().ensure_sealed()?;
+
+    // Parse the parameter.
+    let params: MintAndRegisterParams = ctx.parameter_cursor().get()?;
+    let hook = host
+        .state()
+        .mint_hook
+        .clone()
+        .ok_or(CustomContractError::NotFound)?;
+
+    let hook_params = MintHookParams {
+        token_id: params.mint.token_id.clone(),
+        owner: params.mint.owner,
+        minter: ctx.sender(),
+        data: params.data,
+    };
+    mint_token(ctx, host, params.mint, true, price, None, logger)?;
+
+    host.invoke_contract(
+        &hook.contract,
+        &hook_params,
+        hook.entrypoint.as_entrypoint_name(),
+        Amount::zero(),
+    )?;
+
+    Ok(())
+}
+
+/// Set the contract entrypoint invoked by `mintAndRegister`. `None` disables `mintAndRegister`. Can only be called by
+/// admins and maintainers.
+///
+/// It rejects if:
+/// - The sender is neither one of the admins nor one of the maintainers.
+/// - The migrated state is not sealed yet.
+/// - Fails to parse parameter.
+#[receive(
+    contract = "BictoryNFT",
+    name = "setMintHook",
+    parameter = "Option<MintHook>",
+    mutable
+)]
+fn set_mint_hook<S: HasStateApi>(
+    ctx: &impl HasReceiveContext,
+    host: &mut impl HasHost<State<S>, StateApiType = S>,
+) -> ContractResult<()> {
+    host.state().ensure_sealed()?;
+
+    ensure!(
+        host.state().authority.has_maintainer_rights(&ctx.sender()),
+        ContractError::Unauthorized
+    );
+
+    // Parse the parameter.
+    let hook: Option<MintHook> = ctx.parameter_cursor().get()?;
+
+    host.state_mut().mint_hook = hook;
+    Ok(())
+}
+
+/// View the contract entrypoint invoked by `mintAndRegister`.
+#[receive(
+    contract = "BictoryNFT",
+    name = "viewMintHook",
+    return_value = "Option<MintHook>"
+)]
+fn view_mint_hook<S: HasStateApi>(
+    _ctx: &impl HasReceiveContext,
+    host: &impl HasHost<State<S>, StateApiType = S>,
+) -> ContractResult<Option<MintHook>> {
+    Ok(host.state().mint_hook.clone())
+}
+
+/// Export a chunk of the contract state as canonical records, so that it can be imported into an instance of the
+/// upgraded contract with `importState`. Chunks are exported starting from the `cursor` until the returned `next` is
+/// `None`. Can only be called by admins and maintainers.
+///
+/// It rejects if:
+/// - The sender is neither one of the admins nor one of the maintainers.
+/// - Fails to parse parameter.
+/// - The limit is zero.
+#[receive(
+    contract = "BictoryNFT",
+    name = "exportState",
+    parameter = "ExportStateParams",
+    return_value = "ExportStateResult"
+)]
+fn export_state<S: HasStateApi>(
+    ctx: &impl HasReceiveContext,
+    host: &impl HasHost<State<S>, StateApiType = S>,
+) -> ContractResult<ExportStateResult> {
+    ensure!(
+        host.state().authority.has_maintainer_rights(&ctx.sender()),
+        ContractError::Unauthorized
+    );
+
+    // Parse the parameter.
+    let params: ExportStateParams = ctx.parameter_cursor().get()?;
+    ensure!(params.limit > 0, CustomContractError::InvalidFields.into());
+
+    Ok(host.state().export(params.cursor, params.limit))
+}
+
+/// Import records exported with `exportState` from the previous instance of the contract. Imported records don't log
+/// events. Can only be called by admins and maintainers before the state is sealed.
+///
+/// It rejects if:
+/// - The sender is neither one of the admins nor one of the maintainers.
+/// - Fails to parse parameter.
+/// - The state is sealed.
+/// - The owner already holds the token of an imported balance.
+#[receive(
+    contract = "BictoryNFT",
+    name = "importState",
+    parameter = "Vec<StateRecord>",
+    mutable
+)]
+fn import_state<S: HasStateApi>(
+    ctx: &impl HasReceiveContext,
+    host: &mut impl HasHost<State<S>, StateApiType = S>,
+) -> ContractResult<()> {
+    ensure!(
+        host.state().authority.has_maintainer_rights(&ctx.sender()),
+        ContractError::Unauthorized
+    );
+    ensure!(
+        !host.state().sealed,
+        CustomContractError::OperationNotPermitted.into()
+    );
+
+    // Parse the parameter.
+    let records: Vec<StateRecord> = ctx.parameter_cursor().get()?;
+
+    let (state, state_builder) = host.state_and_builder();
+    for record in records {
+        state.import(record, state_builder)?;
+    }
+    Ok(())
+}
+
+/// Seal the imported state, activating the contract. Rejects all subsequent `importState` calls, while all other
+/// state-changing functions are rejected until then. Can only be called by admins and maintainers.
+///
+/// It rejects if:
+/// - The sender is neither one of the admins nor one of the maintainers.
+#[receive(contract = "BictoryNFT", name = "sealState", mutable)]
+fn seal_state<S: HasStateApi>(
+    ctx: &impl HasReceiveContext,
+    host: &mut impl HasHost<State<S>, StateApiType = S>,
+) -> ContractResult<()> {
+    ensure!(
+        host.state().authority.has_maintainer_rights(&ctx.sender()),
+        ContractError::Unauthorized
+    );
+
+    host.state_mut().sealed = true;
+    Ok(())
+}
+
+/// Pause or resume the contract. While paused, minting, transfers, airdrops, burning, price updates and inheritance
+/// claims are rejected, including transfers with `permit`.
+///
+///  It rejects if:
+///  - Fails to parse parameter.
+///  - If sender is neither one of the admins nor one of the maintainers.
+#[receive(
+    mutable,
+    contract = "BictoryNFT",
+    name = "setPaused",
+    parameter = "bool"
+)]
+fn set_paused<S: HasStateApi>(
+    ctx: &impl HasReceiveContext,
+    host: &mut impl HasHost<State<S>, StateApiType = S>,
+) -> ContractResult<()> {
+    host.state().ensure_sealed()?;
+
+    let paused: bool = ctx.parameter_cursor().get()?;
+    let state = host.state_mut();
+
+    ensure!(
+        state.authority.has_maintainer_rights(&ctx.sender()),
+        ContractError::Unauthorized
+    );
+
+    state.paused = paused;
+    Ok(())
+}
+
+/// Replace the mint phases, which must be ordered by strictly increasing start time. Each phase lasts until the next one
+/// starts. Allowlists and minted copies are tracked per phase start, so they are kept if a phase is reconfigured with
+/// the same start.
+///
+///  It rejects if:
+///  - Fails to parse parameter.
+///  - If sender is neither one of the admins nor one of the maintainers.
+///  - The phases are not ordered by start time.
+#[receive(
+    mutable,
+    contract = "BictoryNFT",
+    name = "setMintPhases",
+    parameter = "Vec<MintPhase>"
+)]
+fn set_mint_phases<S: HasStateApi>(
+    ctx: &impl HasReceiveContext,
+    host: &mut impl HasHost<State<S>, StateApiType = S>,
+) -> ContractResult<()> {
+    host.state().ensure_sealed()?;
+
+    let phases: Vec<MintPhase> = ctx.parameter_cursor().get()?;
+    let state = host.state_mut();
+
+    ensure!(
+        state.authority.has_maintainer_rights(&ctx.sender()),
+        ContractError::Unauthorized
+    );
+
+    state.set_mint_phases(phases)
+}
+
+/// Add and remove addresses from the allowlist of the phase starting at the given time.
+///
+///  It rejects if:
+///  - Fails to parse parameter.
+///  - If sender is neither one of the admins nor one of the maintainers.
+#[receive(
+    mutable,
+    contract = "BictoryNFT",
+    name = "updateAllowlist",
+    parameter = "UpdateAllowlistParams"
+)]
+fn update_allowlist<S: HasStateApi>(
+    ctx: &impl HasReceiveContext,
+    host: &mut impl HasHost<State<S>, StateApiType = S>,
+) -> ContractResult<()> {
+    host.state().ensure_sealed()?;
+
+    let params: UpdateAllowlistParams = ctx.parameter_cursor().get()?;
+    let state = host.state_mut();
+
+    ensure!(
+        state.authority.has_maintainer_rights(&ctx.sender()),
+        ContractError::Unauthorized
+    );
+
+    for address in params.add {
+        state.allowlist.insert((params.phase_start, address));
+    }
+    for address in params.remove {
+        state.allowlist.remove(&(params.phase_start, address));
+    }
+    Ok(())
+}
+
+/// Add and remove tokens that can be minted during mint phases. Each drop token is minted once, with the owner chosen
+/// by the minter.
+///
+///  It rejects if:
+///  - Fails to parse parameter.
+///  - If sender is neither one of the admins nor one of the maintainers.
+#[receive(
+    mutable,
+    contract = "BictoryNFT",
+    name = "updateDropTokens",
+    parameter = "UpdateDropTokensParams"
+)]
+fn update_drop_tokens<S: HasStateApi>(
+    ctx: &impl HasReceiveContext,
+    host: &mut impl HasHost<State<S>, StateApiType = S>,
+) -> ContractResult<()> {
+    host.state().ensure_sealed()?;
+
+    let params: UpdateDropTokensParams = ctx.parameter_cursor().get()?;
+    let state = host.state_mut();
+
+    ensure!(
+        state.authority.has_maintainer_rights(&ctx.sender()),
+        ContractError::Unauthorized
+    );
+
+    for drop_token in params.add {
+        state
+            .drop_tokens
+            .insert(drop_token.token_id.clone(), drop_token);
+    }
+    for token_id in params.remove {
+        state.drop_tokens.remove(&token_id);
+    }
+    Ok(())
+}
+
+/// View the configured mint phases.
+#[receive(
+    contract = "BictoryNFT",
+    name = "viewMintPhases",
+    return_value = "Vec<MintPhase>"
+)]
+fn view_mint_phases<S: HasStateApi>(
+    _ctx: &impl HasReceiveContext,
+    host: &impl HasHost<State<S>, StateApiType = S>,
+) -> ContractResult<Vec<MintPhase>> {
+    Ok(host.state().mint_phases.clone())
+}
+
+/// Get the token metadata URLs given a list of token IDs. The URL of a token is the token ID encoded in hex appended on
+/// the metadata base URL, together with the SHA-256 hash of the metadata if it is known from the mint data.
+///
+/// It rejects if:
+/// - It fails to parse the parameter.
+/// - Any of the queried `token_id` does not exist.
+#[receive(
+    contract = "BictoryNFT",
+    name = "tokenMetadata",
+    parameter = "TokenMetadataQueryParams<ContractTokenId>",
+    return_value = "TokenMetadataQueryResponse"
+)]
+fn token_metadata<S: HasStateApi>(
+    ctx: &impl HasReceiveContext,
+    host: &impl HasHost<State<S>, StateApiType = S>,
+) -> ContractResult<TokenMetadataQueryResponse> {
+    // Parse the parameter.
+    let params: TokenMetadataQueryParams<ContractTokenId> = ctx.parameter_cursor().get()?;
+    // Build the response.
+    let mut response = Vec::with_capacity(params.queries.len());
+    let state = host.state();
+    for token_id in params.queries {
+        // Check the token exists.
+        ensure!(
+            state.all_tokens.contains(&token_id),
+            ContractError::InvalidTokenId
+        );
+        response.push(state.metadata_url(&token_id));
+    }
+    Ok(TokenMetadataQueryResponse::from(response))
+}
+
+/// Change the base of the token metadata URLs, so the metadata can be hosted elsewhere without redeploying. Only affects
+/// `tokenMetadata` responses and events logged afterwards.
+///
+///  It rejects if:
+///  - Fails to parse parameter.
+///  - If sender is neither one of the admins nor one of the maintainers.
+#[receive(
+    mutable,
+    contract = "BictoryNFT",
+    name = "setMetadataBaseUrl",
+    parameter = "String"
+)]
+fn set_metadata_base_url<S: HasStateApi>(
+    ctx: &impl HasReceiveContext,
+    host: &mut impl HasHost<State<S>, StateApiType = S>,
+) -> ContractResult<()> {
+    host.state().ensure_sealed()?;
+
+    let base_url: String = ctx.parameter_cursor().get()?;
+    let state = host.state_mut();
+
+    ensure!(
+        state.authority.has_maintainer_rights(&ctx.sender()),
+        ContractError::Unauthorized
+    );
+
+    state.metadata_base_url = base_url;
+    Ok(())
+}
+
+/// Function to manage addresses that are allowed to maintain and modify the state of the contract.
+///
+///  It rejects if:
+///  - Fails to parse `AuthorityUpdateParams` parameters.
+///  - If sender is neither one of the admins nor one of the maintainers.
+#[receive(
+    mutable,
+    contract = "BictoryNFT",
+    name = "updateAuthority",
+    parameter = "AuthorityUpdateParams"
+)]
+fn update_authority<S: HasStateApi>(
+    ctx: &impl HasReceiveContext,
+    host: &mut impl HasHost<State<S>, StateApiType = S>,
+) -> ReceiveResult<()> {
+    let state = host.state_mut();
+    let params = AuthorityUpdateParams::deserial(&mut ctx.parameter_cursor())?;
+    let sender = ctx.sender();
+    state.authority.handle_update(sender, params)
+}
+
+/// Function to view addresses that are allowed to maintain and modify the state of the contract.
+#[receive(
+    contract = "BictoryNFT",
+    name = "viewAuthority",
+    parameter = "AuthorityViewParams",
+    return_value = "Vec<Address>"
+)]
+fn view_authority<S: HasStateApi>(
+    ctx: &impl HasReceiveContext,
+    host: &impl HasHost<State<S>, StateApiType = S>,
+) -> ReceiveResult<Vec<Address>> {
+    let params = AuthorityViewParams::deserial(&mut ctx.parameter_cursor())?;
+    Ok(host.state().authority.handle_view(params))
+}
+
+/// Check which standards the contract supports (CIS-0).
+///
+/// It rejects if:
+/// - It fails to parse the parameter.
+#[receive(
+    contract = "BictoryNFT",
+    name = "supports",
+    parameter = "SupportsQueryParams",
+    return_value = "SupportsQueryResponse"
+)]
+fn supports<S: HasStateApi>(
+    ctx: &impl HasReceiveContext,
+    _host: &impl HasHost<State<S>, StateApiType = S>,
+) -> ReceiveResult<SupportsQueryResponse> {
+    let params = SupportsQueryParams::deserial(&mut ctx.parameter_cursor())?;
+    Ok(supports_standards(&SUPPORTED_STANDARDS, params))
+}
+
+/// Function to update values required for internal contract functionality. This includes:
+/// - Royalty. Bictory's share of the price when a token is minted for another creator.
+/// - FeeReceiver. Contract receiving the royalty with its `sendCCD` entrypoint.
+/// - SaleRoyalty. Bictory's share of sales reported by `getRoyalties`.
+/// - Beneficiary. Account receiving Bictory's share of sales.
+///
+///  It rejects if:
+///  - Fails to parse `InternalValue` parameters.
+///  - If sender is neither one of the admins nor one of the maintainers.
+///  - The royalty exceeds 100%.
+#[receive(
+    mutable,
+    contract = "BictoryNFT",
+    name = "updateInternalValue",
+    parameter = "InternalValue"
+)]
+fn update_internal_value<S: HasStateApi>(
+    ctx: &impl HasReceiveContext,
+    host: &mut impl HasHost<State<S>, StateApiType = S>,
+) -> ContractResult<()> {
+    host.state().ensure_sealed()?;
+
+    let params: InternalValue = ctx.parameter_cursor().get()?;
+    let state = host.state_mut();
+
+    ensure!(
+        state.authority.has_maintainer_rights(&ctx.sender()),
+        ContractError::Unauthorized
+    );
+
+    match params {
+        InternalValue::Royalty(royalty) => {
+            ensure!(
+                royalty <= Percentage::HUNDRED,
+                CustomContractError::InvalidFields.into()
+            );
+            state.royalty = royalty;
+        }
+        InternalValue::FeeReceiver(contract) => state.fee_receiver = contract,
+        InternalValue::SaleRoyalty(royalty) => {
+            ensure!(
+                royalty <= Percentage::HUNDRED,
+                CustomContractError::InvalidFields.into()
+            );
+            state.sale_royalty = royalty;
+        }
+        InternalValue::Beneficiary(account) => state.beneficiary = account,
+        InternalValue::DropBeneficiary(account) => state.drop_beneficiary = account,
+    }
+
+    Ok(())
+}
+
+/// Function to view values required for internal contract functionality, see `updateInternalValue`.
+///
+///  It rejects if:
+///  - Fails to parse `ViewInternalValueParams` parameters.
+#[receive(
+    contract = "BictoryNFT",
+    name = "viewInternalValue",
+    parameter = "ViewInternalValueParams",
+    return_value = "InternalValue"
+)]
+fn view_internal_value<S: HasStateApi>(
+    ctx: &impl HasReceiveContext,
+    host: &impl HasHost<State<S>, StateApiType = S>,
+) -> ContractResult<InternalValue> {
+    let params: ViewInternalValueParams = ctx.parameter_cursor().get()?;
+    let state = host.state();
+
+    let value = match params {
+        ViewInternalValueParams::Royalty => InternalValue::Royalty(state.royalty),
+        ViewInternalValueParams::FeeReceiver => InternalValue::FeeReceiver(state.fee_receiver),
+        ViewInternalValueParams::SaleRoyalty => InternalValue::SaleRoyalty(state.sale_royalty),
+        ViewInternalValueParams::Beneficiary => InternalValue::Beneficiary(state.beneficiary),
+        ViewInternalValueParams::DropBeneficiary => {
+            InternalValue::DropBeneficiary(state.drop_beneficiary)
+        }
+    };
+
+    Ok(value)
+}
+
+#[concordium_cfg_test]
+mod tests {
+    use super::*;
+    use commons::test::*;
+    use core::convert::TryInto;
+    use test_infrastructure::*;
+
+    const ACCOUNT_0: AccountAddress = AccountAddress([0u8; 32]);
+    const ADDRESS_0: Address = Address::Account(ACCOUNT_0);
+    const ACCOUNT_1: AccountAddress = AccountAddress([1u8; 32]);
+    const ADDRESS_1: Address = Address::Account(ACCOUNT_1);
+    const ACCOUNT_2: AccountAddress = AccountAddress([2u8; 32]);
+
+    fn token_0() -> ContractTokenId {
+        TokenIdVec(vec![0, 1])
+    }
+
+    fn token_1() -> ContractTokenId {
+        TokenIdVec(vec![42, 84, 168])
+    }
+
+    const FEE_RECEIVER: ContractAddress = ContractAddress {
+        index: 7,
+        subindex: 1,
+    };
+
+    fn init_params() -> InitParams {
+        InitParams {
+            royalty: Percentage::from_percent(20),
+            fee_receiver: FEE_RECEIVER,
+            migration: false,
+        }
+    }
+
+    /// Test helper function which creates an empty contract state administered by `ACCOUNT_0`
+    fn empty_state<S: HasStateApi>(state_builder: &mut StateBuilder<S>) -> State<S> {
+        State::empty(state_builder, ACCOUNT_0, init_params())
+    }
+
+    fn get_mint_data(
+        owner: Address,
+        creator: Address,
+        token_id: ContractTokenId,
+        quantity: ContractTokenAmount,
+    ) -> MintData<ContractTokenId> {
+        MintData {
+            token_id,
+            cid: Vec::new(),
+            creator_royalty: 0,
+            minter_royalty: 0,
+            creator,
+            quantity,
+            owner,
+            royalties: RoyaltyRecipients::new(),
+        }
+    }
+
+    fn new_mint_params(
+        owner: Address,
+        creator: Address,
+        token_id: ContractTokenId,
+        quantity: ContractTokenAmount,
+    ) -> MintParams {
+        get_mint_data(owner, creator, token_id, quantity)
+    }
+
+    /// Test helper function which creates a contract state with two tokens with
+    /// id `token_0` and id `token_1` owned by `ADDRESS_0`
+    fn initial_state<S: HasStateApi>(
+        state_builder: &mut StateBuilder<S>,
+        quantity: ContractTokenAmount,
+    ) -> State<S> {
+        let mut state = empty_state(state_builder);
+        let price = Amount::zero();
+
+        // parameter
+        let mut mint_data = get_mint_data(ADDRESS_0, ADDRESS_0, token_0(), quantity);
+
+        state
+            .mint(mint_data, price, true, state_builder)
+            .expect_report("Failed to mint token_0");
+
+        // parameter
+        mint_data = get_mint_data(ADDRESS_1, ADDRESS_1, token_1(), quantity);
+
+        state
+            .mint(mint_data, price, true, state_builder)
+            .expect_report("Failed to mint token_1");
+
+        state
+    }
+
+    /// Test initialization succeeds.
+    #[concordium_test]
+    fn test_init() {
+        // Setup the context
+        let mut ctx = TestInitContext::empty();
+        ctx.set_init_origin(ACCOUNT_0);
+        let parameter_bytes = to_bytes(&init_params());
+        ctx.set_parameter(&parameter_bytes);
+        let mut builder = TestStateBuilder::new();
+
+        // Call the contract function.
+        let result = init(&ctx, &mut builder);
+
+        // Check the result
+        let state = result.expect_report("Contract initialization failed");
+
+        // Check the state
+        claim_eq!(
+            state.all_tokens.iter().count(),
+            0,
+            "No token should be initialized"
+        );
+        claim_eq!(state.royalty, Percentage::from_percent(20));
+        claim_eq!(state.fee_receiver, FEE_RECEIVER);
+        claim!(state.sealed, "State should be sealed outside of migration");
+    }
+
+    /// Test minting for another creator pays the configured royalty to the fee receiver.
+    #[concordium_test]
+    fn test_mint_royalty() {
+        let price = Amount::from_ccd(100);
+
+        let mut ctx = TestReceiveContext::empty();
+        ctx.set_sender(ADDRESS_1);
+        let mint_data = new_mint_params(
+            ADDRESS_1,
+            ADDRESS_0,
+            token_0(),
+            ContractTokenAmount::from(1),
+        );
+        let parameter_bytes = to_bytes(&mint_data);
+        ctx.set_parameter(&parameter_bytes);
+
+        let mut logger = TestLogger::init();
+        let mut state_builder = TestStateBuilder::new();
+        let state = empty_state(&mut state_builder);
+        let mut host = TestHost::new(state, state_builder);
+        host.set_self_balance(price);
+        host.setup_mock_entrypoint(
+            FEE_RECEIVER,
+            OwnedEntrypointName::new_unchecked("sendCCD".into()),
+            MockFn::new_v1(|_, amount, _, _| {
+                if amount != Amount::from_ccd(10) {
+                    return Err(CallContractError::Trap);
+                }
+                Ok((false, ()))
+            }),
+        );
+        host.state_mut().royalty = Percentage::from_percent(10);
+
+        let result: ContractResult<()> = mint(&ctx, &mut host, price, &mut logger);
+
+        claim_eq!(result, Ok(()));
+        claim!(host.transfer_occurred(&ACCOUNT_0, Amount::from_ccd(90)));
+    }
+
+    /// Test internal values can only be updated by the authority.
+    #[concordium_test]
+    fn test_internal_values() {
+        let mut state_builder = TestStateBuilder::new();
+        let state = empty_state(&mut state_builder);
+        let mut host = TestHost::new(state, state_builder);
+
+        let receiver = ContractAddress {
+            index: 8,
+            subindex: 0,
+        };
+        let mut ctx = TestReceiveContext::empty();
+        let parameter_bytes = to_bytes(&InternalValue::FeeReceiver(receiver));
+        ctx.set_parameter(&parameter_bytes);
+
+        ctx.set_sender(ADDRESS_1);
+        claim_eq!(
+            update_internal_value(&ctx, &mut host),
+            Err(ContractError::Unauthorized)
+        );
+
+        ctx.set_sender(ADDRESS_0);
+        claim_eq!(update_internal_value(&ctx, &mut host), Ok(()));
+
+        let parameter_bytes = to_bytes(&InternalValue::Royalty(Percentage::from_percent(101)));
+        ctx.set_parameter(&parameter_bytes);
+        claim_eq!(
+            update_internal_value(&ctx, &mut host),
+            Err(CustomContractError::InvalidFields.into())
+        );
+
+        let parameter_bytes = to_bytes(&ViewInternalValueParams::FeeReceiver);
+        ctx.set_parameter(&parameter_bytes);
+        claim_eq!(
+            view_internal_value(&ctx, &host),
+            Ok(InternalValue::FeeReceiver(receiver))
+        );
+        let parameter_bytes = to_bytes(&ViewInternalValueParams::Royalty);
+        ctx.set_parameter(&parameter_bytes);
+        claim_eq!(
+            view_internal_value(&ctx, &host),
+            Ok(InternalValue::Royalty(Percentage::from_percent(20)))
+        );
+    }
+
+    /// Test minting, ensuring the new tokens are owned by the given address and
+    /// the appropriate events are logged.
+    #[concordium_test]
+    fn test_mint() {
+        let quantity_1 = ContractTokenAmount::from(1);
+
+        // Setup the context
+        let mut ctx = TestReceiveContext::empty();
+        ctx.set_sender(ADDRESS_0);
+        ctx.set_owner(ACCOUNT_0);
+        ctx.set_invoker(ACCOUNT_0);
+
+        let mint_data = new_mint_params(ADDRESS_0, ADDRESS_0, token_0(), quantity_1);
+
+        let parameter_bytes = to_bytes(&mint_data);
+        ctx.set_parameter(&parameter_bytes);
+
+        let mut logger = TestLogger::init();
+        let mut state_builder = TestStateBuilder::new();
+        let state = empty_state(&mut state_builder);
+        let mut host = TestHost::new(state, state_builder);
+
+        // Call the contract function.
+        let result: ContractResult<()> = mint(&ctx, &mut host, Amount::zero(), &mut logger);
+
+        // Check the result
+        claim!(result.is_ok(), "Results in rejection");
+
+        // Check the state
+        claim_eq!(
+            host.state().all_tokens.iter().count(),
+            1,
+            "Expected three tokens in the state."
+        );
+
+        let balance0 = host
+            .state()
+            .balance(&token_0(), &ADDRESS_0)
+            .expect_report("Token is expected to exist");
+        claim_eq!(
+            balance0,
+            quantity_1,
+            "Tokens should be owned by the given address 0"
+        );
+
+        // Check the logs
+        claim!(
+            logger.logs.contains(&to_bytes(&Cis2Event::Mint(MintEvent {
+                owner: ADDRESS_0,
+                token_id: token_0(),
+                amount: quantity_1,
+            }))),
+            "Expected an event for minting token_0"
+        );
+    }
+
+    /// Test transfer succeeds, when `from` is the sender.
+    #[concordium_test]
+    fn test_transfer_account() {
+        let quantity_1 = ContractTokenAmount::from(1);
+
+        // Setup the context
+        let mut ctx = TestReceiveContext::empty();
+        ctx.set_sender(ADDRESS_0);
+
+        // and parameter.
+        let transfer = Transfer {
+            token_id: token_0(),
+            from: ADDRESS_0,
+            to: Receiver::from_account(ACCOUNT_1),
+            amount: quantity_1,
+            data: AdditionalData::empty(),
+        };
+        let parameter = TransferParams(vec![transfer]);
+        let parameter_bytes = to_bytes(&parameter);
+        ctx.set_parameter(&parameter_bytes);
+
+        let mut logger = TestLogger::init();
+        let mut state_builder = TestStateBuilder::new();
+        let state = initial_state(&mut state_builder, quantity_1);
+        let mut host = TestHost::new(state, state_builder);
+
+        // Call the contract function.
+        let result: ContractResult<()> = self::transfer(&ctx, &mut host, &mut logger);
+        // Check the result.
+        claim!(result.is_ok(), "Results in rejection");
+
+        // Check the logs.
+        claim_eq!(logger.logs.len(), 1, "Only one event should be logged");
+        claim_eq!(
+            logger.logs[0],
+            to_bytes(&Cis2Event::Transfer(TransferEvent {
+                from: ADDRESS_0,
+                to: ADDRESS_1,
+                token_id: token_0(),
+                amount: quantity_1,
+            })),
+            "Incorrect event emitted"
+        )
+    }
+
+    /// Test airdrop sends tokens to every receiver and logs a summary.
+    #[concordium_test]
+    fn test_airdrop() {
+        let quantity = ContractTokenAmount::from(3);
+
+        // Setup the context
+        let mut ctx = TestReceiveContext::empty();
+        ctx.set_sender(ADDRESS_0);
+
+        let account_2 = AccountAddress([2u8; 32]);
+        let items = vec![
+            AirdropItem {
+                to: Receiver::from_account(ACCOUNT_1),
+                token_id: token_0(),
+                amount: ContractTokenAmount::from(1),
+            },
+            AirdropItem {
+                to: Receiver::from_account(account_2),
+                token_id: token_0(),
+                amount: ContractTokenAmount::from(2),
+            },
+        ];
+        let parameter_bytes = to_bytes(&items);
+        ctx.set_parameter(&parameter_bytes);
+
+        let mut logger = TestLogger::init();
+        let mut state_builder = TestStateBuilder::new();
+        let state = initial_state(&mut state_builder, quantity);
+        let mut host = TestHost::new(state, state_builder);
+
+        let result: ContractResult<()> = airdrop(&ctx, &mut host, &mut logger);
+        claim!(result.is_ok(), "Results in rejection");
+
+        let state = host.state();
+        claim_eq!(
+            state.balance(&token_0(), &ADDRESS_0),
+            Ok(ContractTokenAmount::from(0))
+        );
+        claim_eq!(
+            state.balance(&token_0(), &ADDRESS_1),
+            Ok(ContractTokenAmount::from(1))
+        );
+        claim_eq!(
+            state.balance(&token_0(), &Address::Account(account_2)),
+            Ok(ContractTokenAmount::from(2))
+        );
+
+        claim_eq!(logger.logs.len(), 3, "Three events should be logged");
+        claim_eq!(
+            logger.logs[2],
+            to_bytes(&CustomEvent::<ContractTokenId>::Airdrop(AirdropEvent {
+                owner: ADDRESS_0,
+                transfers: 2,
+                amount: quantity,
+            })),
+            "Incorrect event emitted"
+        );
+
+        // Sending more than owned in total fails before any transfer is made
+        let mut logger = TestLogger::init();
+        let result: ContractResult<()> = airdrop(&ctx, &mut host, &mut logger);
+        claim_eq!(result, Err(ContractError::InsufficientFunds));
+        claim!(logger.logs.is_empty());
+    }
+
+    /// Test authority airdrop mints and sends tokens with one event per item.
+    #[concordium_test]
+    fn test_authority_airdrop() {
+        let token_2 = TokenIdVec(vec![2]);
+        let items = vec![
+            AuthorityAirdropItem::Mint(new_mint_params(
+                ADDRESS_1,
+                ADDRESS_0,
+                token_2.clone(),
+                ContractTokenAmount::from(1),
+            )),
+            AuthorityAirdropItem::Transfer(AirdropItem {
+                to: Receiver::from_account(ACCOUNT_1),
+                token_id: token_0(),
+                amount: ContractTokenAmount::from(1),
+            }),
+        ];
+        let parameter_bytes = to_bytes(&items);
+        let mut ctx = TestReceiveContext::empty();
+        ctx.set_sender(ADDRESS_1);
+        ctx.set_parameter(&parameter_bytes);
+
+        let mut logger = TestLogger::init();
+        let mut state_builder = TestStateBuilder::new();
+        let state = initial_state(&mut state_builder, ContractTokenAmount::from(1));
+        let mut host = TestHost::new(state, state_builder);
+
+        // Only the authority can airdrop
+        claim_eq!(
+            authority_airdrop(&ctx, &mut host, &mut logger),
+            Err(ContractError::Unauthorized)
+        );
+
+        ctx.set_sender(ADDRESS_0);
+        claim_eq!(authority_airdrop(&ctx, &mut host, &mut logger), Ok(()));
+        claim_eq!(
+            host.state().balance(&token_2, &ADDRESS_1),
+            Ok(ContractTokenAmount::from(1))
+        );
+        claim_eq!(
+            host.state().balance(&token_0(), &ADDRESS_1),
+            Ok(ContractTokenAmount::from(1))
+        );
+        claim_eq!(logger.logs.len(), 2, "One event per item should be logged");
+        claim_eq!(
+            logger.logs[0],
+            to_bytes(&Cis2Event::Mint(MintEvent {
+                token_id: token_2,
+                amount: ContractTokenAmount::from(1),
+                owner: ADDRESS_1,
+            }))
+        );
+
+        // Batches over the limit are rejected
+        let items: Vec<AuthorityAirdropItem> = (0..=MAX_AIRDROP_ITEMS)
+            .map(|_| {
+                AuthorityAirdropItem::Transfer(AirdropItem {
+                    to: Receiver::from_account(ACCOUNT_1),
+                    token_id: token_1(),
+                    amount: ContractTokenAmount::from(0),
+                })
+            })
+            .collect();
+        let parameter_bytes = to_bytes(&items);
+        ctx.set_parameter(&parameter_bytes);
+        claim_eq!(
+            authority_airdrop(&ctx, &mut host, &mut logger),
+            Err(CustomContractError::CapacityExceeded.into())
+        );
+    }
+
+    /// Test transfer token fails, when sender is neither the owner or an
+    /// operator of the owner.
+    #[concordium_test]
+    fn test_transfer_not_authorized() {
+        let quantity_1 = ContractTokenAmount::from(1);
+        
+        // Setup the context
+        let mut ctx = TestReceiveContext::empty();
+        ctx.set_sender(ADDRESS_1);
+
+        // and parameter.
+        let transfer = Transfer {
+            token_id: token_0(),
+            from: ADDRESS_0,
+            amount: quantity_1,
+            to: Receiver::from_account(ACCOUNT_1),
+            data: AdditionalData::empty(),
+        };
+        let parameter = TransferParams(vec![transfer]);
+        let parameter_bytes = to_bytes(&parameter);
+        ctx.set_parameter(&parameter_bytes);
+
+        let mut logger = TestLogger::init();
+        let mut state_builder = TestStateBuilder::new();
+        let state = empty_state(&mut state_builder);
+        let mut host = TestHost::new(state, state_builder);
+
+        // Call the contract function.
+        let result: ContractResult<()> = self::transfer(&ctx, &mut host, &mut logger);
+        // Check the result.
+        let err = result.expect_err_report("Expected to fail");
+        claim_eq!(
+            err,
+            ContractError::Unauthorized,
+            "Error is expected to be Unauthorized"
+        )
+    }
+
+    /// Test transfer succeeds when sender is not the owner, but is an operator
+    /// of the owner.
+    #[concordium_test]
+    fn test_operator_transfer() {
+        let quantity_1 = ContractTokenAmount::from(1);
+        let quantity_2 = ContractTokenAmount::from(2);
+        let quantity_3 = ContractTokenAmount::from(3);
+
+        // Setup the context
+        let mut ctx = TestReceiveContext::empty();
+        ctx.set_sender(ADDRESS_1);
+
+        // and parameter.
+        let transfer = Transfer {
+            from: ADDRESS_0,
+            to: Receiver::from_account(ACCOUNT_1),
+            token_id: token_0(),
+            amount: quantity_2,
+            data: AdditionalData::empty(),
+        };
+        let parameter = TransferParams::from(vec![transfer]);
+        let parameter_bytes = to_bytes(&parameter);
+        ctx.set_parameter(&parameter_bytes);
+
+        let mut logger = TestLogger::init();
+        let mut state_builder = TestStateBuilder::new();
+        let mut state = initial_state(&mut state_builder, quantity_3);
+        state.add_operator(&ADDRESS_0, &ADDRESS_1, &mut state_builder);
+        let mut host = TestHost::new(state, state_builder);
+
+        // Call the contract function.
+        let result: ContractResult<()> = self::transfer(&ctx, &mut host, &mut logger);
+
+        // Check the result.
+        claim!(result.is_ok(), "Results in rejection");
+
+        // Check the state.
+        let balance0 = host
+            .state()
+            .balance(&token_0(), &ADDRESS_0)
+            .expect_report("Token is expected to exist");
+        let balance1 = host
+            .state()
+            .balance(&token_0(), &ADDRESS_1)
+            .expect_report("Token is expected to exist");
+        claim_eq!(
+            balance0,
+            quantity_1,
+            "Token owner balance should be decreased by the transferred amount after first transfer."
+        );
+        claim_eq!(
+            balance1,
+            quantity_2,
+            "Token receiver balance should be increased by the transferred amount after first transfer."
+        );
+
+        // Check the logs.
+        claim_eq!(logger.logs.len(), 1, "Only one event should be logged");
+        claim_eq!(
+            logger.logs[0],
+            to_bytes(&Cis2Event::Transfer(TransferEvent {
+                from: ADDRESS_0,
+                to: ADDRESS_1,
+                token_id: token_0(),
+                amount: quantity_2,
+            })),
+            "Incorrect event emitted"
+        );
+
+        // and parameter.
+        let transfer = Transfer {
+            from: ADDRESS_0,
+            to: Receiver::from_account(ACCOUNT_1),
+            token_id: token_0(),
+            amount: quantity_1,
+            data: AdditionalData::empty(),
+        };
+        let parameter = TransferParams::from(vec![transfer]);
+        let parameter_bytes = to_bytes(&parameter);
+        ctx.set_parameter(&parameter_bytes);
+
+        // Call the contract function.
+        let _: ContractResult<()> = self::transfer(&ctx, &mut host, &mut logger);
+
+        // Check the state.
+        let balance0 = host
+            .state()
+            .balance(&token_0(), &ADDRESS_0)
+            .expect_report("Token is expected to exist");
+        let balance1 = host
+            .state()
+            .balance(&token_0(), &ADDRESS_1)
+            .expect_report("Token is expected to exist");
+        claim_eq!(
+            balance0,
+            ContractTokenAmount::from(0),
+            "Token owner balance should be decreased by the transferred amount after second transfer."
+        );
+        claim_eq!(
+            balance1,
+            quantity_3,
+            "Token receiver balance should be increased by the transferred amount after second transfer."
+        );
+    }
+
+    /// Test adding an operator succeeds and the appropriate event is logged.
+    #[concordium_test]
+    fn test_add_operator() {
+        // Setup the context
+        let mut ctx = TestReceiveContext::empty();
+        ctx.set_sender(ADDRESS_0);
+        ctx.set_invoker(ACCOUNT_0);
+        ctx.set_invoker(ACCOUNT_0);
+
+        // and parameter.
+        let update = UpdateOperator {
+            update: OperatorUpdate::Add,
+            operator: ADDRESS_1,
+        };
+        let parameter = UpdateOperatorParams(vec![update]);
+        let parameter_bytes = to_bytes(&parameter);
+        ctx.set_parameter(&parameter_bytes);
+
+        let mut logger = TestLogger::init();
+        let mut state_builder = TestStateBuilder::new();
+        let state = empty_state(&mut state_builder);
+        let mut host = TestHost::new(state, state_builder);
+
+        // Call the contract function.
+        let result: ContractResult<()> = update_operator(&ctx, &mut host, &mut logger);
+
+        // Check the result.
+        claim!(result.is_ok(), "Results in rejection");
+
+        // Check the state.
+        let is_operator = host.state().is_operator(&ADDRESS_0, &ADDRESS_1);
+        claim!(is_operator, "Account should be an operator");
+
+        // Check the logs.
+        claim_eq!(logger.logs.len(), 1, "One event should be logged");
+        claim_eq!(
+            logger.logs[0],
+            to_bytes(
+                &Cis2Event::<ContractTokenId, ContractTokenAmount>::UpdateOperator(
+                    UpdateOperatorEvent {
+                        owner: ADDRESS_0,
+                        operator: ADDRESS_1,
+                        update: OperatorUpdate::Add,
+                    }
+                )
+            ),
+            "Incorrect event emitted"
+        )
+    }
+
+    // Testing burn functionality
+    #[concordium_test]
+    fn test_burn() {
+        let quantity_1 = ContractTokenAmount::from(1);
+        let quantity_2 = ContractTokenAmount::from(2);
+        
+        // Setup the context
+        let mut ctx = TestReceiveContext::empty();
+        ctx.set_sender(ADDRESS_0);
+        ctx.set_owner(ACCOUNT_0);
+        ctx.set_invoker(ACCOUNT_0);
+
+        // and parameter.
+        let mint_data = new_mint_params(ADDRESS_0, ADDRESS_0, token_0(), quantity_2);
+
+        let parameter_bytes = to_bytes(&mint_data);
+        ctx.set_parameter(&parameter_bytes);
+
+        let mut logger = TestLogger::init();
+        let mut state_builder = TestStateBuilder::new();
+        let state = empty_state(&mut state_builder);
+        let mut host = TestHost::new(state, state_builder);
+
+        // Call the contract function.
+        let _: ContractResult<()> = mint(&ctx, &mut host, Amount::zero(), &mut logger);
+
+        let parameter_bytes = to_bytes(&BurnParams {
+            token_id: token_0(),
+            quantity: quantity_1,
+            owner: ADDRESS_0,
+        });
+        ctx.set_parameter(&parameter_bytes);
+
+        // Call the contract function.
+        let result: ContractResult<()> = burn(&ctx, &mut host, &mut logger);
+
+        // Check the result
+        claim!(result.is_ok(), "Results in rejection");
+
+        // Check the logs
+        claim!(
+            logger.logs.contains(&to_bytes(&Cis2Event::Burn(BurnEvent {
+                token_id: token_0(),
+                amount: quantity_1,
+                owner: ADDRESS_0
+            }))),
+            "Expected an event for buning by address ADDRESS_0"
+        );
+
+        // Check the state.
+        let balance0 = host
+            .state()
+            .balance(&token_0(), &ADDRESS_0)
+            .expect_report("Token is expected to exist");
+        claim_eq!(
+            balance0,
+            quantity_1,
+            "Token balance should be decreased by the burn quantity after first burn."
+        );
 
-        state
-            .mint(mint_data, price, state_builder)
-            .expect_report("Failed to mint token_1");
+        // Call the contract function.
+        let _: ContractResult<()> = burn(&ctx, &mut host, &mut logger);
 
-        state
+        // Check the state.
+        claim!(
+            host.state().balance(&token_0(), &ADDRESS_0).is_err(),
+            "Token should be burn completely."
+        );
     }
 
-    /// Test initialization succeeds.
+    // Testing update_price functionality
     #[concordium_test]
-    fn test_init() {
+    fn test_update_price() {
+        let quantity_1 = ContractTokenAmount::from(1);
+
         // Setup the context
-        let ctx = TestInitContext::empty();
-        let mut builder = TestStateBuilder::new();
+        let mut ctx = TestReceiveContext::empty();
+        ctx.set_sender(ADDRESS_0);
+        ctx.set_owner(ACCOUNT_0);
+        ctx.set_invoker(ACCOUNT_0);
+
+        // and parameter.
+        let mint_data = new_mint_params(ADDRESS_0, ADDRESS_0, token_0(), quantity_1);
+
+        let parameter_bytes = to_bytes(&mint_data);
+        ctx.set_parameter(&parameter_bytes);
+
+        let mut logger = TestLogger::init();
+        let mut state_builder = TestStateBuilder::new();
+        let state = empty_state(&mut state_builder);
+        let mut host = TestHost::new(state, state_builder);
 
         // Call the contract function.
-        let result = init(&ctx, &mut builder);
+        let _: ContractResult<()> = mint(&ctx, &mut host, Amount::zero(), &mut logger);
+
+        let price = Amount::from_ccd(100);
+        let update_price_params = UpdatePriceParameter {
+            token_id: token_0(),
+            price,
+        };
+        let parameter_bytes = to_bytes(&update_price_params);
+        ctx.set_parameter(&parameter_bytes);
+
+        // Call the contract function.
+        let result: ContractResult<()> = update_price(&ctx, &mut host, &mut logger);
 
         // Check the result
-        let state = result.expect_report("Contract initialization failed");
+        claim!(result.is_ok(), "Results in rejection");
 
-        // Check the state
-        claim_eq!(
-            state.all_tokens.iter().count(),
-            0,
-            "No token should be initialized"
+        // Check the logs
+        claim!(
+            logger
+                .logs
+                .contains(&to_bytes(&CustomEvent::UpdatePrice(UpdatePriceEvent {
+                    token_id: token_0(),
+                    owner: ADDRESS_0,
+                    from: Amount::zero(),
+                    to: price
+                }))),
+            "Expected an event for buning by address ADDRESS_0"
         );
     }
 
-    /// Test minting, ensuring the new tokens are owned by the given address and
-    /// the appropriate events are logged.
+    // Testing view_address_state_by_owner functionality
     #[concordium_test]
-    fn test_mint() {
+    fn test_view_address_state_by_owner() {
         let quantity_1 = ContractTokenAmount::from(1);
 
         // Setup the context
         let mut ctx = TestReceiveContext::empty();
-        ctx.set_sender(ADDRESS_0);
-        ctx.set_owner(ACCOUNT_0);
         ctx.set_invoker(ACCOUNT_0);
 
+        // and parameter.
         let mint_data = new_mint_params(ADDRESS_0, ADDRESS_0, token_0(), quantity_1);
 
         let parameter_bytes = to_bytes(&mint_data);
@@ -544,62 +2512,35 @@ mod tests {
 
         let mut logger = TestLogger::init();
         let mut state_builder = TestStateBuilder::new();
-        let state = State::empty(&mut state_builder);
+        let state = initial_state(&mut state_builder, quantity_1);
         let mut host = TestHost::new(state, state_builder);
 
         // Call the contract function.
-        let result: ContractResult<()> = mint(&ctx, &mut host, Amount::zero(), &mut logger);
-
-        // Check the result
-        claim!(result.is_ok(), "Results in rejection");
+        let _: ContractResult<()> = mint(&ctx, &mut host, Amount::zero(), &mut logger);
 
-        // Check the state
-        claim_eq!(
-            host.state().all_tokens.iter().count(),
-            1,
-            "Expected three tokens in the state."
-        );
+        let parameter_bytes = to_bytes(&ADDRESS_0);
+        ctx.set_parameter(&parameter_bytes);
 
-        let balance0 = host
-            .state()
-            .balance(&token_0(), &ADDRESS_0)
-            .expect_report("Token is expected to exist");
-        claim_eq!(
-            balance0,
-            quantity_1,
-            "Tokens should be owned by the given address 0"
-        );
+        // Call the contract function.
+        let result: ReceiveResult<ViewAddressState> = view_address_state_by_owner(&ctx, &mut host);
 
-        // Check the logs
-        claim!(
-            logger.logs.contains(&to_bytes(&Cis2Event::Mint(MintEvent {
-                owner: ADDRESS_0,
-                token_id: token_0(),
-                amount: quantity_1,
-            }))),
-            "Expected an event for minting token_0"
-        );
+        // Check the result
+        claim!(result.is_ok(), "Results in rejection");
     }
 
-    /// Test transfer succeeds, when `from` is the sender.
+    // Testing view_token functionality
     #[concordium_test]
-    fn test_transfer_account() {
+    fn test_view_token() {
         let quantity_1 = ContractTokenAmount::from(1);
 
         // Setup the context
         let mut ctx = TestReceiveContext::empty();
-        ctx.set_sender(ADDRESS_0);
+        ctx.set_invoker(ACCOUNT_0);
 
         // and parameter.
-        let transfer = Transfer {
-            token_id: token_0(),
-            from: ADDRESS_0,
-            to: Receiver::from_account(ACCOUNT_1),
-            amount: quantity_1,
-            data: AdditionalData::empty(),
-        };
-        let parameter = TransferParams(vec![transfer]);
-        let parameter_bytes = to_bytes(&parameter);
+        let mint_data = new_mint_params(ADDRESS_0, ADDRESS_0, token_0(), quantity_1);
+
+        let parameter_bytes = to_bytes(&mint_data);
         ctx.set_parameter(&parameter_bytes);
 
         let mut logger = TestLogger::init();
@@ -608,407 +2549,1420 @@ mod tests {
         let mut host = TestHost::new(state, state_builder);
 
         // Call the contract function.
-        let result: ContractResult<()> = self::transfer(&ctx, &mut host, &mut logger);
-        // Check the result.
+        let _: ContractResult<()> = mint(&ctx, &mut host, Amount::zero(), &mut logger);
+
+        let params = ViewTokenParams {
+            token_id: token_0(),
+            owner: ADDRESS_0,
+        };
+        let parameter_bytes = to_bytes(&params);
+        ctx.set_parameter(&parameter_bytes);
+
+        // Call the contract function.
+        let result: ReceiveResult<TokenView> = view_token(&ctx, &mut host);
+
+        // Check the result
         claim!(result.is_ok(), "Results in rejection");
+    }
 
-        // Check the logs.
-        claim_eq!(logger.logs.len(), 1, "Only one event should be logged");
+    // Testing pause functionality
+    #[concordium_test]
+    fn test_pause() {
+        let mut state_builder = TestStateBuilder::new();
+        let state = initial_state(&mut state_builder, ContractTokenAmount::from(1));
+        let mut host = TestHost::new(state, state_builder);
+        let mut logger = TestLogger::init();
+
+        // Only the authority can pause the contract
+        let paused_bytes = to_bytes(&true);
+        let mut ctx = TestReceiveContext::empty();
+        ctx.set_sender(ADDRESS_1);
+        ctx.set_parameter(&paused_bytes);
         claim_eq!(
-            logger.logs[0],
-            to_bytes(&Cis2Event::Transfer(TransferEvent {
+            set_paused(&ctx, &mut host),
+            Err(ContractError::Unauthorized)
+        );
+
+        ctx.set_sender(ADDRESS_0);
+        claim_eq!(set_paused(&ctx, &mut host), Ok(()));
+
+        let transfer_bytes = to_bytes(&TransferParams(vec![Transfer {
+            token_id: token_0(),
+            from: ADDRESS_0,
+            to: Receiver::from_account(ACCOUNT_1),
+            amount: ContractTokenAmount::from(1),
+            data: AdditionalData::empty(),
+        }]));
+        let mut transfer_ctx = TestReceiveContext::empty();
+        transfer_ctx.set_sender(ADDRESS_0);
+        transfer_ctx.set_parameter(&transfer_bytes);
+        let result: ContractResult<()> = transfer(&transfer_ctx, &mut host, &mut logger);
+        claim_eq!(result, Err(CustomContractError::ContractPaused.into()));
+
+        let burn_bytes = to_bytes(&BurnParams {
+            token_id: token_0(),
+            quantity: ContractTokenAmount::from(1),
+            owner: ADDRESS_0,
+        });
+        let mut burn_ctx = TestReceiveContext::empty();
+        burn_ctx.set_sender(ADDRESS_0);
+        burn_ctx.set_parameter(&burn_bytes);
+        let result: ContractResult<()> = burn(&burn_ctx, &mut host, &mut logger);
+        claim_eq!(result, Err(CustomContractError::ContractPaused.into()));
+        claim!(logger.logs.is_empty());
+
+        // Tokens can be moved again once unpaused
+        let paused_bytes = to_bytes(&false);
+        ctx.set_parameter(&paused_bytes);
+        claim_eq!(set_paused(&ctx, &mut host), Ok(()));
+        let result: ContractResult<()> = transfer(&transfer_ctx, &mut host, &mut logger);
+        claim_eq!(result, Ok(()));
+    }
+
+    // Testing permit functionality
+    #[concordium_test]
+    fn test_permit() {
+        let mut state_builder = TestStateBuilder::new();
+        let state = initial_state(&mut state_builder, ContractTokenAmount::from(1));
+        let mut host = TestHost::new(state, state_builder);
+        let mut logger = TestLogger::init();
+
+        // Owner registers a signing key
+        let key_bytes = to_bytes(&[7u8; 32]);
+        let mut ctx = TestReceiveContext::empty();
+        ctx.set_sender(ADDRESS_0);
+        ctx.set_parameter(&key_bytes);
+        claim_eq!(register_permit_key(&ctx, &mut host), Ok(()));
+
+        let mut crypto_primitives = TestCryptoPrimitives::new();
+        crypto_primitives.setup_verify_ed25519_signature_mock(|public_key, signature, _| {
+            public_key == PublicKeyEd25519([7u8; 32]) && signature == SignatureEd25519([1u8; 64])
+        });
+
+        let permit_params = |expiry: u64, signature: [u8; 64]| PermitParams {
+            message: PermitMessage {
+                signer: ACCOUNT_0,
+                nonce: 0,
+                expiry: Timestamp::from_timestamp_millis(expiry),
+                action: PermitAction::Transfer(TransferParams(vec![Transfer {
+                    token_id: token_0(),
+                    from: ADDRESS_0,
+                    to: Receiver::from_account(ACCOUNT_1),
+                    amount: ContractTokenAmount::from(1),
+                    data: AdditionalData::empty(),
+                }])),
+            },
+            signature,
+        };
+
+        // Relayer sends the transfer signed by the owner
+        let mut ctx = TestReceiveContext::empty();
+        ctx.set_sender(ADDRESS_1);
+        ctx.set_invoker(ACCOUNT_1);
+        ctx.set_metadata_slot_time(Timestamp::from_timestamp_millis(10));
+
+        let parameter_bytes = to_bytes(&permit_params(5, [1u8; 64]));
+        ctx.set_parameter(&parameter_bytes);
+        let result = permit(&ctx, &mut host, &mut logger, &crypto_primitives);
+        claim_eq!(result, Err(CustomContractError::InvalidDuration.into()));
+
+        let parameter_bytes = to_bytes(&permit_params(20, [2u8; 64]));
+        ctx.set_parameter(&parameter_bytes);
+        let result = permit(&ctx, &mut host, &mut logger, &crypto_primitives);
+        claim_eq!(result, Err(CustomContractError::InvalidSignature.into()));
+
+        let parameter_bytes = to_bytes(&permit_params(20, [1u8; 64]));
+        ctx.set_parameter(&parameter_bytes);
+        let result = permit(&ctx, &mut host, &mut logger, &crypto_primitives);
+        claim_eq!(result, Ok(()));
+        claim_eq!(
+            host.state().balance(&token_0(), &ADDRESS_1),
+            Ok(ContractTokenAmount::from(1))
+        );
+        claim_eq!(
+            logger.logs,
+            vec![to_bytes(&Cis2Event::Transfer(TransferEvent {
+                token_id: token_0(),
+                amount: ContractTokenAmount::from(1),
                 from: ADDRESS_0,
                 to: ADDRESS_1,
-                token_id: token_0(),
-                amount: quantity_1,
-            })),
-            "Incorrect event emitted"
-        )
+            }))]
+        );
+
+        // Replaying the message fails
+        let result = permit(&ctx, &mut host, &mut logger, &crypto_primitives);
+        claim_eq!(result, Err(CustomContractError::InvalidNonce.into()));
+
+        let parameter_bytes = to_bytes(&ACCOUNT_0);
+        ctx.set_parameter(&parameter_bytes);
+        claim_eq!(view_permit_nonce(&ctx, &host), Ok(1));
+
+        // Operator updates with permit are rejected while paused, without using the nonce
+        let parameter_bytes = to_bytes(&PermitParams {
+            message: PermitMessage {
+                signer: ACCOUNT_0,
+                nonce: 1,
+                expiry: Timestamp::from_timestamp_millis(20),
+                action: PermitAction::UpdateOperator(UpdateOperatorParams(vec![UpdateOperator {
+                    update: OperatorUpdate::Add,
+                    operator: ADDRESS_1,
+                }])),
+            },
+            signature: [1u8; 64],
+        });
+        ctx.set_parameter(&parameter_bytes);
+        host.state_mut().paused = true;
+        let result = permit(&ctx, &mut host, &mut logger, &crypto_primitives);
+        claim_eq!(result, Err(CustomContractError::ContractPaused.into()));
+        claim!(!host.state().is_operator(&ADDRESS_0, &ADDRESS_1));
+
+        host.state_mut().paused = false;
+        let result = permit(&ctx, &mut host, &mut logger, &crypto_primitives);
+        claim_eq!(result, Ok(()));
+        claim!(host.state().is_operator(&ADDRESS_0, &ADDRESS_1));
     }
 
-    /// Test transfer token fails, when sender is neither the owner or an
-    /// operator of the owner.
+    // Testing getRoyalties functionality
     #[concordium_test]
-    fn test_transfer_not_authorized() {
-        let quantity_1 = ContractTokenAmount::from(1);
-        
-        // Setup the context
+    fn test_get_royalties() {
+        let mut state_builder = TestStateBuilder::new();
+        let mut state = empty_state(&mut state_builder);
+        let mut mint_data = get_mint_data(
+            ADDRESS_0,
+            ADDRESS_1,
+            token_0(),
+            ContractTokenAmount::from(1),
+        );
+        mint_data.creator_royalty = 5_000_000;
+        mint_data.minter_royalty = 2_000_000;
+        mint_data.royalties = vec![Royalty {
+            beneficiary: ACCOUNT_2,
+            percentage: Percentage::from_percent(3),
+        }]
+        .try_into()
+        .expect_report("Too many royalty recipients");
+        state
+            .mint(mint_data, Amount::zero(), true, &mut state_builder)
+            .expect_report("Failed to mint token_0");
+        state.sale_royalty = Percentage::from_percent(1);
+        let host = TestHost::new(state, state_builder);
+
         let mut ctx = TestReceiveContext::empty();
-        ctx.set_sender(ADDRESS_1);
+        let parameter_bytes = to_bytes(&GetRoyaltiesParams {
+            token_id: token_0(),
+        });
+        ctx.set_parameter(&parameter_bytes);
+
+        let royalties = vec![
+            Royalty {
+                beneficiary: ACCOUNT_1,
+                percentage: Percentage::from_percent(5),
+            },
+            Royalty {
+                beneficiary: ACCOUNT_0,
+                percentage: Percentage::from_percent(2),
+            },
+            Royalty {
+                beneficiary: ACCOUNT_2,
+                percentage: Percentage::from_percent(3),
+            },
+            Royalty {
+                beneficiary: ACCOUNT_0,
+                percentage: Percentage::from_percent(1),
+            },
+        ];
+        claim_eq!(
+            get_royalties(&ctx, &host),
+            Ok(GetRoyaltiesResponse {
+                royalties: royalties.try_into().expect_report("Too many royalties"),
+            })
+        );
+
+        let parameter_bytes = to_bytes(&GetRoyaltiesParams {
+            token_id: token_1(),
+        });
+        ctx.set_parameter(&parameter_bytes);
+        claim_eq!(
+            get_royalties(&ctx, &host),
+            Err(ContractError::InvalidTokenId)
+        );
+    }
+
+    /// Test minting fails if the royalties of all recipients exceed 100%.
+    #[concordium_test]
+    fn test_mint_royalties_over_hundred_percent() {
+        let mut state_builder = TestStateBuilder::new();
+        let mut state = empty_state(&mut state_builder);
+        let mut mint_data = get_mint_data(
+            ADDRESS_0,
+            ADDRESS_1,
+            token_0(),
+            ContractTokenAmount::from(1),
+        );
+        mint_data.creator_royalty = 50_000_000;
+        mint_data.minter_royalty = 30_000_000;
+        mint_data.royalties = vec![Royalty {
+            beneficiary: ACCOUNT_2,
+            percentage: Percentage::from_percent(21),
+        }]
+        .try_into()
+        .expect_report("Too many royalty recipients");
+
+        claim_eq!(
+            state.mint(mint_data.clone(), Amount::zero(), true, &mut state_builder),
+            Err(CustomContractError::InvalidRoyalty.into())
+        );
+
+        // Failed mint doesn't take the token ID
+        mint_data.royalties = vec![Royalty {
+            beneficiary: ACCOUNT_2,
+            percentage: Percentage::from_percent(20),
+        }]
+        .try_into()
+        .expect_report("Too many royalty recipients");
+        claim!(state
+            .mint(mint_data, Amount::zero(), true, &mut state_builder)
+            .is_ok());
+    }
+
+    // Testing editions functionality
+    #[concordium_test]
+    fn test_editions() {
+        let mut state_builder = TestStateBuilder::new();
+        let state = initial_state(&mut state_builder, ContractTokenAmount::from(5));
+        let mut host = TestHost::new(state, state_builder);
+
+        let send = |from: Address, to: AccountAddress, amount: u64| Transfer {
+            token_id: token_0(),
+            from,
+            to: Receiver::from_account(to),
+            amount: ContractTokenAmount::from(amount),
+            data: AdditionalData::empty(),
+        };
+        let (state, state_builder) = host.state_and_builder();
+        // Lowest editions are sent first: ADDRESS_1 holds 1 and 2, ADDRESS_0 holds 3 to 5
+        claim!(state
+            .transfer(&send(ADDRESS_0, ACCOUNT_1, 2), state_builder)
+            .is_ok());
+        // Edition 1 returns to ADDRESS_0 and is burned
+        claim!(state
+            .transfer(&send(ADDRESS_1, ACCOUNT_0, 1), state_builder)
+            .is_ok());
+        let burn_params = BurnParams {
+            token_id: token_0(),
+            quantity: ContractTokenAmount::from(1),
+            owner: ADDRESS_0,
+        };
+        claim!(state.burn(&ADDRESS_0, burn_params).is_ok());
+
+        let view = |host: &TestHost<State<TestStateApi>>, edition: u64| {
+            let parameter_bytes = to_bytes(&ViewEditionParams {
+                token_id: token_0(),
+                edition,
+            });
+            let mut ctx = TestReceiveContext::empty();
+            ctx.set_parameter(&parameter_bytes);
+            view_edition(&ctx, host)
+        };
+
+        claim_eq!(view(&host, 1), Err(CustomContractError::NotFound.into()));
+        claim_eq!(view(&host, 6), Err(CustomContractError::NotFound.into()));
+        claim_eq!(view(&host, 2).map(|view| view.owner), Ok(ADDRESS_1));
+        claim_eq!(
+            view(&host, 3),
+            Ok(EditionView {
+                edition: 3,
+                owner: ADDRESS_0,
+                metadata_url: String::from(TOKEN_METADATA_BASE_URL) + "0001/3",
+            })
+        );
+
+        // Received editions are merged with the held ones
+        let (state, state_builder) = host.state_and_builder();
+        claim!(state
+            .transfer(&send(ADDRESS_1, ACCOUNT_0, 1), state_builder)
+            .is_ok());
+        let editions = host
+            .state()
+            .state
+            .get(&ADDRESS_0)
+            .and_then(|address_state| {
+                address_state
+                    .owned_tokens
+                    .get(&token_0())
+                    .map(|data| data.editions.clone())
+            });
+        claim_eq!(editions, Some(vec![EditionRange { first: 2, last: 5 }]));
+    }
 
-        // and parameter.
+    // Testing snapshot_holders functionality
+    #[concordium_test]
+    fn test_snapshot_holders() {
+        let quantity_2 = ContractTokenAmount::from(2);
+        let mut state_builder = TestStateBuilder::new();
+        let mut state = initial_state(&mut state_builder, quantity_2);
+
+        // Transfer a single copy of token_0 from ADDRESS_0 to ADDRESS_1
         let transfer = Transfer {
             token_id: token_0(),
+            amount: ContractTokenAmount::from(1),
             from: ADDRESS_0,
-            amount: quantity_1,
-            to: Receiver::from_account(ACCOUNT_1),
+            to: Receiver::Account(ACCOUNT_1),
             data: AdditionalData::empty(),
         };
-        let parameter = TransferParams(vec![transfer]);
-        let parameter_bytes = to_bytes(&parameter);
+        state
+            .transfer(&transfer, &mut state_builder)
+            .expect_report("Transfer failed");
+        let host = TestHost::new(state, state_builder);
+
+        let parameter_bytes = to_bytes(&SnapshotHoldersParams {
+            cursor: 0,
+            limit: 2,
+        });
+        let mut ctx = TestReceiveContext::empty();
+        ctx.set_sender(ADDRESS_1);
+        ctx.set_parameter(&parameter_bytes);
+
+        // Only admins and maintainers are allowed to take snapshots
+        let result = snapshot_holders(&ctx, &host);
+        claim_eq!(result, Err(ContractError::Unauthorized));
+
+        // Snapshot all tokens in pages of two holders
+        ctx.set_sender(ADDRESS_0);
+        let mut holders = Vec::new();
+        let mut cursor = Some(0);
+        while let Some(next) = cursor {
+            let parameter_bytes = to_bytes(&SnapshotHoldersParams {
+                cursor: next,
+                limit: 2,
+            });
+            ctx.set_parameter(&parameter_bytes);
+            let page = snapshot_holders(&ctx, &host).expect_report("Snapshot failed");
+            claim!(page.holders.len() <= 2);
+            holders.extend(page.holders);
+            cursor = page.next;
+        }
+        claim_eq!(holders.len(), 3);
+        claim!(holders.contains(&HolderBalance {
+            token_id: token_0(),
+            owner: ADDRESS_0,
+            balance: ContractTokenAmount::from(1),
+        }));
+        claim!(holders.contains(&HolderBalance {
+            token_id: token_0(),
+            owner: ADDRESS_1,
+            balance: ContractTokenAmount::from(1),
+        }));
+        claim!(holders.contains(&HolderBalance {
+            token_id: token_1(),
+            owner: ADDRESS_1,
+            balance: ContractTokenAmount::from(2),
+        }));
+
+        // Empty pages are rejected
+        let parameter_bytes = to_bytes(&SnapshotHoldersParams {
+            cursor: 0,
+            limit: 0,
+        });
         ctx.set_parameter(&parameter_bytes);
+        claim_eq!(
+            snapshot_holders(&ctx, &host),
+            Err(CustomContractError::InvalidFields.into())
+        );
+    }
 
+    // Testing token ID reservations
+    #[concordium_test]
+    fn test_reserve_token_ids() {
         let mut logger = TestLogger::init();
         let mut state_builder = TestStateBuilder::new();
-        let state = State::empty(&mut state_builder);
+        let state = empty_state(&mut state_builder);
         let mut host = TestHost::new(state, state_builder);
 
-        // Call the contract function.
-        let result: ContractResult<()> = self::transfer(&ctx, &mut host, &mut logger);
-        // Check the result.
-        let err = result.expect_err_report("Expected to fail");
+        // Only admins and maintainers can reserve token IDs
+        let parameter_bytes = to_bytes(&ReserveTokenIdsParams {
+            prefix: vec![0],
+            minter: Some(ADDRESS_1),
+        });
+        let mut ctx = TestReceiveContext::empty();
+        ctx.set_sender(ADDRESS_1);
+        ctx.set_parameter(&parameter_bytes);
+        let result = reserve_token_ids(&ctx, &mut host);
+        claim_eq!(result, Err(ContractError::Unauthorized));
+
+        ctx.set_sender(ADDRESS_0);
+        let result = reserve_token_ids(&ctx, &mut host);
+        claim_eq!(result, Ok(()));
+
+        // Overlapping ranges can't be reserved for another minter
+        let parameter_bytes = to_bytes(&ReserveTokenIdsParams {
+            prefix: vec![0, 1],
+            minter: Some(ADDRESS_0),
+        });
+        ctx.set_parameter(&parameter_bytes);
+        let result = reserve_token_ids(&ctx, &mut host);
         claim_eq!(
-            err,
-            ContractError::Unauthorized,
-            "Error is expected to be Unauthorized"
-        )
-    }
+            result,
+            Err(ContractError::Custom(CustomContractError::AlreadyExists))
+        );
 
-    /// Test transfer succeeds when sender is not the owner, but is an operator
-    /// of the owner.
-    #[concordium_test]
-    fn test_operator_transfer() {
-        let quantity_1 = ContractTokenAmount::from(1);
-        let quantity_2 = ContractTokenAmount::from(2);
-        let quantity_3 = ContractTokenAmount::from(3);
+        claim_eq!(
+            view_reservations(&ctx, &host),
+            Ok(vec![TokenIdReservation {
+                prefix: vec![0],
+                minter: ADDRESS_1,
+            }])
+        );
 
-        // Setup the context
+        // Token ID reserved for another minter
+        let parameter_bytes = to_bytes(&new_mint_params(
+            ADDRESS_0,
+            ADDRESS_0,
+            token_0(),
+            ContractTokenAmount::from(1),
+        ));
+        let mut ctx = TestReceiveContext::empty();
+        ctx.set_sender(ADDRESS_0);
+        ctx.set_owner(ACCOUNT_0);
+        ctx.set_parameter(&parameter_bytes);
+        let result = mint(&ctx, &mut host, Amount::zero(), &mut logger);
+        claim_eq!(result, Err(ContractError::Unauthorized));
+
+        // Token ID outside of the minter's reserved range
+        let parameter_bytes = to_bytes(&new_mint_params(
+            ADDRESS_1,
+            ADDRESS_1,
+            token_1(),
+            ContractTokenAmount::from(1),
+        ));
         let mut ctx = TestReceiveContext::empty();
         ctx.set_sender(ADDRESS_1);
+        ctx.set_owner(ACCOUNT_0);
+        ctx.set_parameter(&parameter_bytes);
+        let result = mint(&ctx, &mut host, Amount::zero(), &mut logger);
+        claim_eq!(result, Err(ContractError::Unauthorized));
+
+        // Token ID inside of the minter's reserved range
+        let parameter_bytes = to_bytes(&new_mint_params(
+            ADDRESS_1,
+            ADDRESS_1,
+            token_0(),
+            ContractTokenAmount::from(1),
+        ));
+        ctx.set_parameter(&parameter_bytes);
+        let result = mint(&ctx, &mut host, Amount::zero(), &mut logger);
+        claim_eq!(result, Ok(()));
 
-        // and parameter.
-        let transfer = Transfer {
-            from: ADDRESS_0,
-            to: Receiver::from_account(ACCOUNT_1),
-            token_id: token_0(),
-            amount: quantity_2,
-            data: AdditionalData::empty(),
-        };
-        let parameter = TransferParams::from(vec![transfer]);
-        let parameter_bytes = to_bytes(&parameter);
+        // Release the reservation
+        let parameter_bytes = to_bytes(&ReserveTokenIdsParams {
+            prefix: vec![0],
+            minter: None,
+        });
+        let mut ctx = TestReceiveContext::empty();
+        ctx.set_sender(ADDRESS_0);
+        ctx.set_owner(ACCOUNT_0);
         ctx.set_parameter(&parameter_bytes);
+        let result = reserve_token_ids(&ctx, &mut host);
+        claim_eq!(result, Ok(()));
+        claim_eq!(view_reservations(&ctx, &host), Ok(Vec::new()));
+    }
+
+    // Testing inheritance of tokens by an heir
+    #[concordium_test]
+    fn test_inheritance() {
+        let quantity_2 = ContractTokenAmount::from(2);
 
         let mut logger = TestLogger::init();
         let mut state_builder = TestStateBuilder::new();
-        let mut state = initial_state(&mut state_builder, quantity_3);
-        state.add_operator(&ADDRESS_0, &ADDRESS_1, &mut state_builder);
+        let state = initial_state(&mut state_builder, quantity_2);
         let mut host = TestHost::new(state, state_builder);
 
-        // Call the contract function.
-        let result: ContractResult<()> = self::transfer(&ctx, &mut host, &mut logger);
-
-        // Check the result.
-        claim!(result.is_ok(), "Results in rejection");
+        // Owner designates an heir
+        let plan = InheritancePlan {
+            heir: ACCOUNT_1,
+            inactivity: Duration::from_millis(100),
+        };
+        let parameter_bytes = to_bytes(&Some(plan));
+        let mut ctx = TestReceiveContext::empty();
+        ctx.set_sender(ADDRESS_0);
+        ctx.set_invoker(ACCOUNT_0);
+        ctx.set_metadata_slot_time(Timestamp::from_timestamp_millis(0));
+        ctx.set_parameter(&parameter_bytes);
+        let result = set_heir(&ctx, &mut host, &mut logger);
+        claim_eq!(result, Ok(()));
+        claim!(logger.logs.contains(&to_bytes(
+            &CustomEvent::<ContractTokenId>::InheritanceUpdated(InheritanceUpdatedEvent {
+                owner: ADDRESS_0,
+                plan: Some(plan),
+            })
+        )));
 
-        // Check the state.
-        let balance0 = host
-            .state()
-            .balance(&token_0(), &ADDRESS_0)
-            .expect_report("Token is expected to exist");
-        let balance1 = host
-            .state()
-            .balance(&token_0(), &ADDRESS_1)
-            .expect_report("Token is expected to exist");
-        claim_eq!(
-            balance0,
-            quantity_1,
-            "Token owner balance should be decreased by the transferred amount after first transfer."
-        );
+        // Heir can't claim before the inactivity period passes
+        let parameter_bytes = to_bytes(&ADDRESS_0);
+        let mut claim_ctx = TestReceiveContext::empty();
+        claim_ctx.set_sender(ADDRESS_1);
+        claim_ctx.set_metadata_slot_time(Timestamp::from_timestamp_millis(50));
+        claim_ctx.set_parameter(&parameter_bytes);
+        let result = claim_inheritance(&claim_ctx, &mut host, &mut logger);
         claim_eq!(
-            balance1,
-            quantity_2,
-            "Token receiver balance should be increased by the transferred amount after first transfer."
+            result,
+            Err(ContractError::Custom(
+                CustomContractError::OperationNotPermitted
+            ))
         );
 
-        // Check the logs.
-        claim_eq!(logger.logs.len(), 1, "Only one event should be logged");
+        // Owner activity restarts the inactivity period
+        let parameter_bytes = to_bytes(&UpdateOperatorParams(vec![UpdateOperator {
+            update: OperatorUpdate::Add,
+            operator: ADDRESS_1,
+        }]));
+        ctx.set_metadata_slot_time(Timestamp::from_timestamp_millis(80));
+        ctx.set_parameter(&parameter_bytes);
+        let result = update_operator(&ctx, &mut host, &mut logger);
+        claim_eq!(result, Ok(()));
+
+        claim_ctx.set_metadata_slot_time(Timestamp::from_timestamp_millis(150));
+        let result = claim_inheritance(&claim_ctx, &mut host, &mut logger);
         claim_eq!(
-            logger.logs[0],
-            to_bytes(&Cis2Event::Transfer(TransferEvent {
-                from: ADDRESS_0,
-                to: ADDRESS_1,
-                token_id: token_0(),
-                amount: quantity_2,
-            })),
-            "Incorrect event emitted"
+            result,
+            Err(ContractError::Custom(
+                CustomContractError::OperationNotPermitted
+            ))
         );
 
-        // and parameter.
-        let transfer = Transfer {
-            from: ADDRESS_0,
-            to: Receiver::from_account(ACCOUNT_1),
-            token_id: token_0(),
-            amount: quantity_1,
-            data: AdditionalData::empty(),
-        };
-        let parameter = TransferParams::from(vec![transfer]);
-        let parameter_bytes = to_bytes(&parameter);
-        ctx.set_parameter(&parameter_bytes);
+        // Only the heir can claim
+        claim_ctx.set_metadata_slot_time(Timestamp::from_timestamp_millis(180));
+        claim_ctx.set_sender(ADDRESS_0);
+        let result = claim_inheritance(&claim_ctx, &mut host, &mut logger);
+        claim_eq!(result, Err(ContractError::Unauthorized));
 
-        // Call the contract function.
-        let _: ContractResult<()> = self::transfer(&ctx, &mut host, &mut logger);
+        claim_ctx.set_sender(ADDRESS_1);
+        let result = claim_inheritance(&claim_ctx, &mut host, &mut logger);
+        claim_eq!(result, Ok(()));
 
-        // Check the state.
-        let balance0 = host
-            .state()
-            .balance(&token_0(), &ADDRESS_0)
-            .expect_report("Token is expected to exist");
-        let balance1 = host
-            .state()
-            .balance(&token_0(), &ADDRESS_1)
-            .expect_report("Token is expected to exist");
+        // Check the state
         claim_eq!(
-            balance0,
-            ContractTokenAmount::from(0),
-            "Token owner balance should be decreased by the transferred amount after second transfer."
+            host.state().balance(&token_0(), &ADDRESS_0),
+            Ok(ContractTokenAmount::from(0))
         );
+        claim_eq!(host.state().balance(&token_0(), &ADDRESS_1), Ok(quantity_2));
+
+        // Check the logs
+        claim!(logger
+            .logs
+            .contains(&to_bytes(&Cis2Event::Transfer(TransferEvent {
+                token_id: token_0(),
+                amount: quantity_2,
+                from: ADDRESS_0,
+                to: ADDRESS_1,
+            }))));
+        claim!(logger.logs.contains(&to_bytes(
+            &CustomEvent::<ContractTokenId>::InheritanceClaimed(InheritanceClaimedEvent {
+                owner: ADDRESS_0,
+                heir: ACCOUNT_1,
+                tokens: 1,
+            })
+        )));
+
+        // Inheritance can only be claimed once
+        let result = claim_inheritance(&claim_ctx, &mut host, &mut logger);
         claim_eq!(
-            balance1,
-            quantity_3,
-            "Token receiver balance should be increased by the transferred amount after second transfer."
+            result,
+            Err(ContractError::Custom(CustomContractError::NotFound))
         );
+        claim_eq!(view_inheritance(&claim_ctx, &host), Ok(None));
     }
 
-    /// Test adding an operator succeeds and the appropriate event is logged.
+    /// Test claiming more tokens than fit in the event limit, which takes several calls.
     #[concordium_test]
-    fn test_add_operator() {
-        // Setup the context
-        let mut ctx = TestReceiveContext::empty();
-        ctx.set_sender(ADDRESS_0);
-        ctx.set_invoker(ACCOUNT_0);
-        ctx.set_invoker(ACCOUNT_0);
-
-        // and parameter.
-        let update = UpdateOperator {
-            update: OperatorUpdate::Add,
-            operator: ADDRESS_1,
+    fn test_inheritance_batches() {
+        let mut state_builder = TestStateBuilder::new();
+        let mut state = empty_state(&mut state_builder);
+        for token in 0..=MAX_INHERITANCE_TOKENS as u8 {
+            let mint_data = get_mint_data(
+                ADDRESS_0,
+                ADDRESS_0,
+                TokenIdVec(vec![token]),
+                ContractTokenAmount::from(1),
+            );
+            state
+                .mint(mint_data, Amount::zero(), true, &mut state_builder)
+                .expect_report("Failed to mint token");
+        }
+        let plan = InheritancePlan {
+            heir: ACCOUNT_1,
+            inactivity: Duration::from_millis(100),
         };
-        let parameter = UpdateOperatorParams(vec![update]);
-        let parameter_bytes = to_bytes(&parameter);
+        state
+            .set_inheritance(ADDRESS_0, Some(plan), Timestamp::from_timestamp_millis(0))
+            .expect_report("Failed to set heir");
+        let mut host = TestHost::new(state, state_builder);
+
+        let parameter_bytes = to_bytes(&ADDRESS_0);
+        let mut ctx = TestReceiveContext::empty();
+        ctx.set_sender(ADDRESS_1);
+        ctx.set_metadata_slot_time(Timestamp::from_timestamp_millis(100));
         ctx.set_parameter(&parameter_bytes);
 
+        // First call claims as many tokens as fit in the event limit and keeps the plan
+        let mut logger = TestLogger::init();
+        claim_eq!(claim_inheritance(&ctx, &mut host, &mut logger), Ok(()));
+        claim_eq!(logger.logs.len(), MAX_INHERITANCE_TOKENS + 1);
+        claim_eq!(host.state().transferable_tokens(&ADDRESS_0).len(), 1);
+        claim!(view_inheritance(&ctx, &host)
+            .expect_report("View failed")
+            .is_some());
+
+        // Last call claims the rest and removes the plan
+        let mut logger = TestLogger::init();
+        claim_eq!(claim_inheritance(&ctx, &mut host, &mut logger), Ok(()));
+        claim_eq!(logger.logs.len(), 2);
+        claim!(host.state().transferable_tokens(&ADDRESS_0).is_empty());
+        claim_eq!(view_inheritance(&ctx, &host), Ok(None));
+    }
+
+    /// Test minting with the mint hook, ensuring the hook receives the new token and its rejection fails the mint.
+    #[concordium_test]
+    fn test_mint_and_register() {
+        let hook = MintHook {
+            contract: ContractAddress {
+                index: 7,
+                subindex: 0,
+            },
+            entrypoint: OwnedEntrypointName::new_unchecked(String::from("register")),
+        };
         let mut logger = TestLogger::init();
         let mut state_builder = TestStateBuilder::new();
-        let state = State::empty(&mut state_builder);
+        let state = empty_state(&mut state_builder);
         let mut host = TestHost::new(state, state_builder);
 
-        // Call the contract function.
-        let result: ContractResult<()> = update_operator(&ctx, &mut host, &mut logger);
+        let parameter_bytes = to_bytes(&MintAndRegisterParams {
+            mint: new_mint_params(
+                ADDRESS_0,
+                ADDRESS_0,
+                token_0(),
+                ContractTokenAmount::from(1),
+            ),
+            data: vec![1, 2, 3],
+        });
+        let mut ctx = TestReceiveContext::empty();
+        ctx.set_sender(ADDRESS_0);
+        ctx.set_owner(ACCOUNT_0);
+        ctx.set_parameter(&parameter_bytes);
 
-        // Check the result.
-        claim!(result.is_ok(), "Results in rejection");
+        // Hook has to be configured first
+        let result = mint_and_register(&ctx, &mut host, Amount::zero(), &mut logger);
+        claim_eq!(
+            result,
+            Err(ContractError::Custom(CustomContractError::NotFound))
+        );
 
-        // Check the state.
-        let is_operator = host.state().is_operator(&ADDRESS_1, &ADDRESS_0);
-        claim!(is_operator, "Account should be an operator");
+        // Only the contract owner can configure the hook
+        let hook_bytes = to_bytes(&Some(hook.clone()));
+        let mut owner_ctx = TestReceiveContext::empty();
+        owner_ctx.set_sender(ADDRESS_1);
+        owner_ctx.set_owner(ACCOUNT_0);
+        owner_ctx.set_parameter(&hook_bytes);
+        let result = set_mint_hook(&owner_ctx, &mut host);
+        claim_eq!(result, Err(ContractError::Unauthorized));
+
+        owner_ctx.set_sender(ADDRESS_0);
+        let result = set_mint_hook(&owner_ctx, &mut host);
+        claim_eq!(result, Ok(()));
+        claim_eq!(view_mint_hook(&owner_ctx, &host), Ok(Some(hook.clone())));
+
+        // Rejection of the hook fails the mint
+        host.setup_mock_entrypoint(
+            hook.contract,
+            hook.entrypoint.clone(),
+            parse_and_check_mock::<MintHookParams, _>(|_| false, ()),
+        );
+        let result = mint_and_register(&ctx, &mut host, Amount::zero(), &mut logger);
+        claim!(result.is_err(), "Hook rejection should fail the mint");
 
-        // Check the logs.
-        claim_eq!(logger.logs.len(), 1, "One event should be logged");
-        claim_eq!(
-            logger.logs[0],
-            to_bytes(
-                &Cis2Event::<ContractTokenId, ContractTokenAmount>::UpdateOperator(
-                    UpdateOperatorEvent {
-                        owner: ADDRESS_0,
-                        operator: ADDRESS_1,
-                        update: OperatorUpdate::Add,
-                    }
-                )
+        let expected = MintHookParams {
+            token_id: token_1(),
+            owner: ADDRESS_0,
+            minter: ADDRESS_0,
+            data: vec![1, 2, 3],
+        };
+        host.setup_mock_entrypoint(
+            hook.contract,
+            hook.entrypoint.clone(),
+            parse_and_check_mock::<MintHookParams, _>(move |params| *params == expected, ()),
+        );
+        let parameter_bytes = to_bytes(&MintAndRegisterParams {
+            mint: new_mint_params(
+                ADDRESS_0,
+                ADDRESS_0,
+                token_1(),
+                ContractTokenAmount::from(1),
             ),
-            "Incorrect event emitted"
-        )
+            data: vec![1, 2, 3],
+        });
+        ctx.set_parameter(&parameter_bytes);
+        let result = mint_and_register(&ctx, &mut host, Amount::zero(), &mut logger);
+        claim_eq!(result, Ok(()));
+        claim!(
+            host.state().all_tokens.contains(&token_1()),
+            "Token should be minted"
+        );
     }
 
-    // Testing burn functionality
+    // Testing state export and import for contract migration
     #[concordium_test]
-    fn test_burn() {
-        let quantity_1 = ContractTokenAmount::from(1);
-        let quantity_2 = ContractTokenAmount::from(2);
-        
-        // Setup the context
+    fn test_export_import_state() {
+        let mut state_builder = TestStateBuilder::new();
+        let mut state = initial_state(&mut state_builder, ContractTokenAmount::from(2));
+        state.add_operator(&ADDRESS_0, &ADDRESS_1, &mut state_builder);
+        state
+            .reserve_token_ids(ReserveTokenIdsParams {
+                prefix: vec![42],
+                minter: Some(ADDRESS_1),
+            })
+            .expect_report("Reservation failed");
+        state.mint_hook = Some(MintHook {
+            contract: ContractAddress {
+                index: 7,
+                subindex: 0,
+            },
+            entrypoint: OwnedEntrypointName::new_unchecked(String::from("register")),
+        });
+        let drop_token_id = TokenIdVec(vec![2]);
+        state.drop_tokens.insert(
+            drop_token_id.clone(),
+            new_mint_params(
+                ADDRESS_0,
+                ADDRESS_0,
+                drop_token_id,
+                ContractTokenAmount::from(1),
+            ),
+        );
+        let phase = MintPhase {
+            start: Timestamp::from_timestamp_millis(10),
+            access: MintAccess::AllowlistSet,
+            price: Amount::from_micro_ccd(5),
+            wallet_cap: Some(2),
+        };
+        state
+            .set_mint_phases(vec![phase])
+            .expect_report("Setting mint phases failed");
+        state.allowlist.insert((phase.start, ADDRESS_1));
+        state.phase_mints.insert((phase.start, ADDRESS_1), 1);
+        state.nonces.insert(ACCOUNT_1, 3);
+        state.permit_keys.insert(ACCOUNT_1, [5; 32]);
+        state.metadata_base_url = String::from("https://example.com/");
+        state.sale_royalty = Percentage::from_percent(5);
+        state.beneficiary = ACCOUNT_2;
+        state.drop_beneficiary = ACCOUNT_1;
+        state.paused = true;
+        let host = TestHost::new(state, state_builder);
+
         let mut ctx = TestReceiveContext::empty();
-        ctx.set_sender(ADDRESS_0);
-        ctx.set_owner(ACCOUNT_0);
-        ctx.set_invoker(ACCOUNT_0);
+        ctx.set_sender(ADDRESS_1);
 
-        // and parameter.
-        let mint_data = new_mint_params(ADDRESS_0, ADDRESS_0, token_0(), quantity_2);
+        // Only admins and maintainers can export the state
+        let parameter_bytes = to_bytes(&ExportStateParams {
+            cursor: 0,
+            limit: 2,
+        });
+        ctx.set_parameter(&parameter_bytes);
+        claim_eq!(export_state(&ctx, &host), Err(ContractError::Unauthorized));
 
-        let parameter_bytes = to_bytes(&mint_data);
+        // Export the state in chunks of two records
+        ctx.set_sender(ADDRESS_0);
+        let mut records = Vec::new();
+        let mut cursor = Some(0);
+        while let Some(next) = cursor {
+            let parameter_bytes = to_bytes(&ExportStateParams {
+                cursor: next,
+                limit: 2,
+            });
+            ctx.set_parameter(&parameter_bytes);
+            let chunk = export_state(&ctx, &host).expect_report("Export failed");
+            claim!(chunk.records.len() <= 2);
+            records.extend(chunk.records);
+            cursor = chunk.next;
+        }
+        // Two token IDs, two balances, an operator, a reservation, the mint hook, a drop token, a mint phase, an
+        // allowlisted address, minted copies of the phase, a nonce, a permit key and the configuration
+        claim_eq!(records.len(), 14);
+
+        // Normally initialized instances don't accept imports
+        let mut state_builder = TestStateBuilder::new();
+        let state = empty_state(&mut state_builder);
+        let mut new_host = TestHost::new(state, state_builder);
+        let parameter_bytes = to_bytes(&records);
         ctx.set_parameter(&parameter_bytes);
+        claim_eq!(
+            import_state(&ctx, &mut new_host),
+            Err(ContractError::Custom(
+                CustomContractError::OperationNotPermitted
+            ))
+        );
 
-        let mut logger = TestLogger::init();
+        // Import the records into a fresh instance in migration mode
         let mut state_builder = TestStateBuilder::new();
-        let state = State::empty(&mut state_builder);
-        let mut host = TestHost::new(state, state_builder);
-
-        // Call the contract function.
-        let _: ContractResult<()> = mint(&ctx, &mut host, Amount::zero(), &mut logger);
+        let params = InitParams {
+            migration: true,
+            ..init_params()
+        };
+        let state = State::empty(&mut state_builder, ACCOUNT_0, params);
+        let mut new_host = TestHost::new(state, state_builder);
+        claim_eq!(import_state(&ctx, &mut new_host), Ok(()));
 
-        let parameter_bytes = to_bytes(&BurnParams {
-            token_id: token_0(),
-            quantity: quantity_1,
-            owner: ADDRESS_0,
+        let parameter_bytes = to_bytes(&ExportStateParams {
+            cursor: 0,
+            limit: 100,
         });
         ctx.set_parameter(&parameter_bytes);
+        let export = export_state(&ctx, &new_host).expect_report("Export failed");
+        claim_eq!(export.next, None);
+        claim_eq!(export.records, records);
+        claim_eq!(
+            new_host.state().balance(&token_0(), &ADDRESS_0),
+            Ok(ContractTokenAmount::from(2))
+        );
+        claim!(new_host.state().is_operator(&ADDRESS_0, &ADDRESS_1));
+        claim_eq!(new_host.state().total_supply(), host.state().total_supply());
+        claim_eq!(new_host.state().mint_phases, vec![phase]);
+        claim_eq!(
+            new_host.state().nonces.get(&ACCOUNT_1).map(|nonce| *nonce),
+            Some(3)
+        );
+        claim_eq!(new_host.state().metadata_base_url, "https://example.com/");
+        claim_eq!(new_host.state().drop_beneficiary, ACCOUNT_1);
+        claim!(new_host.state().paused);
 
-        // Call the contract function.
-        let result: ContractResult<()> = burn(&ctx, &mut host, &mut logger);
-
-        // Check the result
-        claim!(result.is_ok(), "Results in rejection");
+        // Nothing else can be changed before the state is sealed
+        let parameter_bytes = to_bytes(&false);
+        ctx.set_parameter(&parameter_bytes);
+        claim_eq!(
+            set_paused(&ctx, &mut new_host),
+            Err(ContractError::Custom(
+                CustomContractError::OperationNotPermitted
+            ))
+        );
 
-        // Check the logs
-        claim!(
-            logger.logs.contains(&to_bytes(&Cis2Event::Burn(BurnEvent {
-                token_id: token_0(),
-                amount: quantity_1,
-                owner: ADDRESS_0
-            }))),
-            "Expected an event for buning by address ADDRESS_0"
+        // Importing a balance twice fails
+        let parameter_bytes = to_bytes(&records);
+        ctx.set_parameter(&parameter_bytes);
+        claim_eq!(
+            import_state(&ctx, &mut new_host),
+            Err(ContractError::Custom(CustomContractError::AlreadyExists))
         );
 
-        // Check the state.
-        let balance0 = host
-            .state()
-            .balance(&token_0(), &ADDRESS_0)
-            .expect_report("Token is expected to exist");
+        // No imports after the state is sealed
+        claim_eq!(seal_state(&ctx, &mut new_host), Ok(()));
+        let parameter_bytes = to_bytes(&false);
+        ctx.set_parameter(&parameter_bytes);
+        claim_eq!(set_paused(&ctx, &mut new_host), Ok(()));
+        let parameter_bytes = to_bytes(&Vec::<StateRecord>::new());
+        ctx.set_parameter(&parameter_bytes);
         claim_eq!(
-            balance0,
-            quantity_1,
-            "Token balance should be decreased by the burn quantity after first burn."
+            import_state(&ctx, &mut new_host),
+            Err(ContractError::Custom(
+                CustomContractError::OperationNotPermitted
+            ))
         );
+    }
 
-        // Call the contract function.
-        let _: ContractResult<()> = burn(&ctx, &mut host, &mut logger);
+    #[concordium_test]
+    fn test_token_metadata() {
+        let mut state_builder = TestStateBuilder::new();
+        let mut state = empty_state(&mut state_builder);
+        let digest = [7u8; 32];
+        let mut mint_data = get_mint_data(
+            ADDRESS_0,
+            ADDRESS_0,
+            token_0(),
+            ContractTokenAmount::from(1),
+        );
+        mint_data.cid = [&[0x01, 0x55, 0x12, 0x20][..], &digest[..]].concat();
+        state
+            .mint(mint_data, Amount::zero(), true, &mut state_builder)
+            .expect_report("Failed to mint token_0");
+        let mint_data = get_mint_data(
+            ADDRESS_0,
+            ADDRESS_0,
+            token_1(),
+            ContractTokenAmount::from(1),
+        );
+        state
+            .mint(mint_data, Amount::zero(), true, &mut state_builder)
+            .expect_report("Failed to mint token_1");
+        let mut host = TestHost::new(state, state_builder);
 
-        // Check the state.
-        claim!(
-            host.state().balance(&token_0(), &ADDRESS_0).is_err(),
-            "Token should be burn completely."
+        let query = |host: &TestHost<State<TestStateApi>>, queries: Vec<ContractTokenId>| {
+            let parameter_bytes = to_bytes(&TokenMetadataQueryParams { queries });
+            let mut ctx = TestReceiveContext::empty();
+            ctx.set_parameter(&parameter_bytes);
+            token_metadata(&ctx, host)
+        };
+
+        // Hash is only known for the token minted with a SHA-256 content identifier
+        let result = query(&host, vec![token_0(), token_1()]).expect_report("Query failed");
+        let expected = TokenMetadataQueryResponse::from(vec![
+            MetadataUrl {
+                url: String::from(TOKEN_METADATA_BASE_URL) + "0001/",
+                hash: Some(digest),
+            },
+            MetadataUrl {
+                url: String::from(TOKEN_METADATA_BASE_URL) + "2A54A8/",
+                hash: None,
+            },
+        ]);
+        claim_eq!(to_bytes(&result), to_bytes(&expected));
+        claim_eq!(
+            query(&host, vec![TokenIdVec(vec![3])]).err(),
+            Some(ContractError::InvalidTokenId)
+        );
+
+        // Only the authority can move the metadata
+        let base_url_bytes = to_bytes(&String::from("https://example.com/"));
+        let mut ctx = TestReceiveContext::empty();
+        ctx.set_sender(ADDRESS_1);
+        ctx.set_parameter(&base_url_bytes);
+        claim_eq!(
+            set_metadata_base_url(&ctx, &mut host),
+            Err(ContractError::Unauthorized)
         );
+        ctx.set_sender(ADDRESS_0);
+        claim_eq!(set_metadata_base_url(&ctx, &mut host), Ok(()));
+
+        let result = query(&host, vec![token_0()]).expect_report("Query failed");
+        let expected = TokenMetadataQueryResponse::from(vec![MetadataUrl {
+            url: String::from("https://example.com/0001/"),
+            hash: Some(digest),
+        }]);
+        claim_eq!(to_bytes(&result), to_bytes(&expected));
     }
 
-    // Testing update_price functionality
     #[concordium_test]
-    fn test_update_price() {
-        let quantity_1 = ContractTokenAmount::from(1);
+    fn test_mint_phases() {
+        let mut state_builder = TestStateBuilder::new();
+        let state = empty_state(&mut state_builder);
+        let mut host = TestHost::new(state, state_builder);
 
-        // Setup the context
+        // Hashing is mocked with a simple mix of the bytes
+        let mut crypto_primitives = TestCryptoPrimitives::new();
+        crypto_primitives.setup_hash_sha2_256_mock(|data| {
+            let mut hash = [0u8; 32];
+            for (i, byte) in data.iter().enumerate() {
+                hash[i % 32] = hash[i % 32].wrapping_mul(31).wrapping_add(*byte);
+            }
+            HashSha2256(hash)
+        });
+        let leaf = |address: Address| crypto_primitives.hash_sha2_256(&to_bytes(&address)).0;
+        let sibling = leaf(Address::Account(AccountAddress([2u8; 32])));
+        let root = merkle_root(leaf(ADDRESS_1), &[sibling], &crypto_primitives);
+
+        let phase =
+            |start: u64, access: MintAccess, price: u64, wallet_cap: Option<u64>| MintPhase {
+                start: Timestamp::from_timestamp_millis(start),
+                access,
+                price: Amount::from_ccd(price),
+                wallet_cap,
+            };
+        let phases = vec![
+            phase(10, MintAccess::AllowlistSet, 1, Some(2)),
+            phase(20, MintAccess::AllowlistMerkle(root), 0, None),
+            phase(30, MintAccess::Public, 2, None),
+            phase(40, MintAccess::Closed, 0, None),
+        ];
+
+        // Only the authority can configure phases, in order of start time
         let mut ctx = TestReceiveContext::empty();
+        ctx.set_sender(ADDRESS_1);
+        let parameter_bytes = to_bytes(&phases);
+        ctx.set_parameter(&parameter_bytes);
+        claim_eq!(
+            set_mint_phases(&ctx, &mut host),
+            Err(ContractError::Unauthorized)
+        );
         ctx.set_sender(ADDRESS_0);
-        ctx.set_owner(ACCOUNT_0);
-        ctx.set_invoker(ACCOUNT_0);
+        let unordered_bytes = to_bytes(&vec![phases[1], phases[0]]);
+        ctx.set_parameter(&unordered_bytes);
+        claim_eq!(
+            set_mint_phases(&ctx, &mut host),
+            Err(CustomContractError::InvalidFields.into())
+        );
+        ctx.set_parameter(&parameter_bytes);
+        claim_eq!(set_mint_phases(&ctx, &mut host), Ok(()));
+
+        // Only drop tokens defined by the authority can be minted during phases
+        let drop_token = |token: u8, quantity: u64| {
+            new_mint_params(
+                ADDRESS_0,
+                ADDRESS_0,
+                TokenIdVec(vec![token]),
+                ContractTokenAmount::from(quantity),
+            )
+        };
+        let parameter_bytes = to_bytes(&UpdateDropTokensParams {
+            add: vec![
+                drop_token(1, 2),
+                drop_token(2, 1),
+                drop_token(3, 1),
+                drop_token(4, 1),
+                drop_token(5, 1),
+            ],
+            remove: Vec::new(),
+        });
+        ctx.set_parameter(&parameter_bytes);
+        ctx.set_sender(ADDRESS_1);
+        claim_eq!(
+            update_drop_tokens(&ctx, &mut host),
+            Err(ContractError::Unauthorized)
+        );
+        ctx.set_sender(ADDRESS_0);
+        claim_eq!(update_drop_tokens(&ctx, &mut host), Ok(()));
+        let parameter_bytes = to_bytes(&UpdateDropTokensParams {
+            add: Vec::new(),
+            remove: vec![TokenIdVec(vec![5])],
+        });
+        ctx.set_parameter(&parameter_bytes);
+        claim_eq!(update_drop_tokens(&ctx, &mut host), Ok(()));
+
+        /// Mint copies of a drop token for the sender, with a proof if given.
+        fn mint_at(
+            host: &mut TestHost<State<TestStateApi>>,
+            slot_time: u64,
+            sender: Address,
+            token: u8,
+            quantity: u64,
+            price: u64,
+            proof: Option<(Vec<[u8; 32]>, &TestCryptoPrimitives)>,
+        ) -> ContractResult<()> {
+            let mint_params = new_mint_params(
+                sender,
+                ADDRESS_0,
+                TokenIdVec(vec![token]),
+                ContractTokenAmount::from(quantity),
+            );
+            let mut ctx = TestReceiveContext::empty();
+            ctx.set_sender(sender);
+            ctx.set_metadata_slot_time(Timestamp::from_timestamp_millis(slot_time));
+            let mut logger = TestLogger::init();
+            let price = Amount::from_ccd(price);
+            host.set_self_balance(price);
+            match proof {
+                Some((proof, crypto_primitives)) => {
+                    let parameter_bytes = to_bytes(&MintWithProofParams {
+                        mint: mint_params,
+                        proof,
+                    });
+                    ctx.set_parameter(&parameter_bytes);
+                    mint_with_proof(&ctx, host, price, &mut logger, crypto_primitives)
+                }
+                None => {
+                    let parameter_bytes = to_bytes(&mint_params);
+                    ctx.set_parameter(&parameter_bytes);
+                    mint(&ctx, host, price, &mut logger)
+                }
+            }
+        }
 
-        // and parameter.
-        let mint_data = new_mint_params(ADDRESS_0, ADDRESS_0, token_0(), quantity_1);
+        // Nobody but the authority can mint before the first phase
+        claim_eq!(
+            mint_at(&mut host, 5, ADDRESS_1, 0, 1, 1, None),
+            Err(ContractError::Unauthorized)
+        );
+        claim_eq!(mint_at(&mut host, 5, ADDRESS_0, 0, 1, 0, None), Ok(()));
 
-        let parameter_bytes = to_bytes(&mint_data);
+        // Allowlist phase requires the sender on the list, the price of each copy and respects the cap
+        claim_eq!(
+            mint_at(&mut host, 10, ADDRESS_1, 1, 2, 2, None),
+            Err(ContractError::Unauthorized)
+        );
+        let parameter_bytes = to_bytes(&UpdateAllowlistParams {
+            phase_start: Timestamp::from_timestamp_millis(10),
+            add: vec![ADDRESS_1],
+            remove: Vec::new(),
+        });
         ctx.set_parameter(&parameter_bytes);
+        claim_eq!(update_allowlist(&ctx, &mut host), Ok(()));
+        claim_eq!(
+            mint_at(&mut host, 10, ADDRESS_1, 1, 2, 1, None),
+            Err(CustomContractError::InsufficientFunds.into())
+        );
+        claim_eq!(
+            mint_at(&mut host, 15, ADDRESS_1, 1, 1, 2, None),
+            Err(ContractError::Unauthorized)
+        );
+        claim_eq!(mint_at(&mut host, 15, ADDRESS_1, 1, 2, 2, None), Ok(()));
+        claim!(host.transfer_occurred(&ACCOUNT_0, Amount::from_ccd(2)));
+        claim_eq!(
+            mint_at(&mut host, 15, ADDRESS_1, 2, 1, 1, None),
+            Err(CustomContractError::CapacityExceeded.into())
+        );
 
-        let mut logger = TestLogger::init();
+        // Merkle allowlist phase requires a proof leading to the root
+        claim_eq!(
+            mint_at(&mut host, 20, ADDRESS_1, 2, 1, 0, None),
+            Err(ContractError::Unauthorized)
+        );
+        claim_eq!(
+            mint_at(
+                &mut host,
+                20,
+                ADDRESS_1,
+                2,
+                1,
+                0,
+                Some((vec![[0u8; 32]], &crypto_primitives))
+            ),
+            Err(ContractError::Unauthorized)
+        );
+        claim_eq!(
+            mint_at(
+                &mut host,
+                20,
+                ADDRESS_1,
+                2,
+                1,
+                0,
+                Some((vec![sibling], &crypto_primitives))
+            ),
+            Ok(())
+        );
+
+        // Public phase is open to anyone paying the price, until the closed phase. Each drop token is minted once and
+        // the price goes to the drop beneficiary.
+        let address_2 = Address::Account(AccountAddress([2u8; 32]));
+        claim_eq!(
+            mint_at(&mut host, 30, address_2, 2, 1, 2, None),
+            Err(ContractError::Unauthorized)
+        );
+        claim_eq!(
+            mint_at(&mut host, 30, address_2, 5, 1, 2, None),
+            Err(ContractError::Unauthorized)
+        );
+        let parameter_bytes = to_bytes(&InternalValue::DropBeneficiary(ACCOUNT_1));
+        ctx.set_parameter(&parameter_bytes);
+        claim_eq!(update_internal_value(&ctx, &mut host), Ok(()));
+        claim_eq!(mint_at(&mut host, 30, address_2, 3, 1, 2, None), Ok(()));
+        claim!(host.transfer_occurred(&ACCOUNT_1, Amount::from_ccd(2)));
+        claim_eq!(
+            mint_at(&mut host, 40, address_2, 4, 1, 2, None),
+            Err(ContractError::Unauthorized)
+        );
+
+        claim_eq!(
+            view_mint_phases(&TestReceiveContext::empty(), &host),
+            Ok(phases)
+        );
+    }
+
+    #[concordium_test]
+    fn test_token_enumeration() {
         let mut state_builder = TestStateBuilder::new();
-        let state = State::empty(&mut state_builder);
+        let state = initial_state(&mut state_builder, ContractTokenAmount::from(2));
         let mut host = TestHost::new(state, state_builder);
 
-        // Call the contract function.
-        let _: ContractResult<()> = mint(&ctx, &mut host, Amount::zero(), &mut logger);
-
-        let price = Amount::from_ccd(100);
-        let update_price_params = UpdatePriceParameter {
+        let (state, state_builder) = host.state_and_builder();
+        let transfer = Transfer {
             token_id: token_0(),
-            price,
+            from: ADDRESS_0,
+            to: Receiver::from_account(ACCOUNT_1),
+            amount: ContractTokenAmount::from(1),
+            data: AdditionalData::empty(),
         };
-        let parameter_bytes = to_bytes(&update_price_params);
-        ctx.set_parameter(&parameter_bytes);
-
-        // Call the contract function.
-        let result: ContractResult<()> = update_price(&ctx, &mut host, &mut logger);
+        claim!(state.transfer(&transfer, state_builder).is_ok());
 
-        // Check the result
-        claim!(result.is_ok(), "Results in rejection");
+        let ctx = TestReceiveContext::empty();
+        claim_eq!(total_supply(&ctx, &host), Ok(2));
 
-        // Check the logs
-        claim!(
-            logger
-                .logs
-                .contains(&to_bytes(&CustomEvent::UpdatePrice(UpdatePriceEvent {
-                    token_id: token_0(),
-                    owner: ADDRESS_0,
-                    from: Amount::zero(),
-                    to: price
-                }))),
-            "Expected an event for buning by address ADDRESS_0"
+        let view = |skip: u32, show: u32| {
+            let parameter_bytes = to_bytes(&ViewTokensParams { skip, show });
+            let mut ctx = TestReceiveContext::empty();
+            ctx.set_parameter(&parameter_bytes);
+            view_tokens(&ctx, &host)
+        };
+        claim_eq!(view(0, 10), Ok(vec![token_0(), token_1()]));
+        claim_eq!(view(1, 10), Ok(vec![token_1()]));
+        claim_eq!(view(0, 1), Ok(vec![token_0()]));
+
+        let owners = |token_id: ContractTokenId, skip: u32, show: u32| {
+            let parameter_bytes = to_bytes(&ViewOwnersParams {
+                token_id,
+                skip,
+                show,
+            });
+            let mut ctx = TestReceiveContext::empty();
+            ctx.set_parameter(&parameter_bytes);
+            view_owners(&ctx, &host)
+        };
+        claim_eq!(owners(token_0(), 0, 10), Ok(vec![ADDRESS_0, ADDRESS_1]));
+        claim_eq!(owners(token_0(), 1, 10), Ok(vec![ADDRESS_1]));
+        claim_eq!(owners(token_0(), 0, 1), Ok(vec![ADDRESS_0]));
+        claim_eq!(owners(token_1(), 0, 10), Ok(vec![ADDRESS_1]));
+        claim_eq!(
+            owners(TokenIdVec(vec![3]), 0, 10),
+            Err(ContractError::InvalidTokenId)
         );
     }
 
-    // Testing view_address_state_by_owner functionality
     #[concordium_test]
-    fn test_view_address_state_by_owner() {
-        let quantity_1 = ContractTokenAmount::from(1);
+    fn test_soulbound() {
+        let mint_data = new_mint_params(
+            ADDRESS_0,
+            ADDRESS_0,
+            token_0(),
+            ContractTokenAmount::from(2),
+        );
+        // Parameters without the flag mint transferable tokens, and the encoding matches the schema
+        let decoded: MintTokenParams =
+            from_bytes(&to_bytes(&mint_data)).expect_report("Failed to parse parameter");
+        claim_eq!(decoded.transferable, None);
+        for transferable in [None, Some(false), Some(true)] {
+            let params = MintTokenParams {
+                mint: mint_data.clone(),
+                transferable,
+            };
+            let decoded: MintTokenParams =
+                from_bytes(&to_bytes(&params)).expect_report("Failed to parse parameter");
+            claim_eq!(decoded.transferable, transferable);
+        }
 
-        // Setup the context
         let mut ctx = TestReceiveContext::empty();
-        ctx.set_invoker(ACCOUNT_0);
-
-        // and parameter.
-        let mint_data = new_mint_params(ADDRESS_0, ADDRESS_0, token_0(), quantity_1);
-
-        let parameter_bytes = to_bytes(&mint_data);
+        ctx.set_sender(ADDRESS_0);
+        let parameter_bytes = to_bytes(&MintTokenParams {
+            mint: mint_data,
+            transferable: Some(false),
+        });
         ctx.set_parameter(&parameter_bytes);
 
         let mut logger = TestLogger::init();
         let mut state_builder = TestStateBuilder::new();
-        let state = initial_state(&mut state_builder, quantity_1);
+        let state = empty_state(&mut state_builder);
         let mut host = TestHost::new(state, state_builder);
+        claim_eq!(mint(&ctx, &mut host, Amount::zero(), &mut logger), Ok(()));
 
-        // Call the contract function.
-        let _: ContractResult<()> = mint(&ctx, &mut host, Amount::zero(), &mut logger);
-
-        let parameter_bytes = to_bytes(&ADDRESS_0);
+        let parameter_bytes = to_bytes(&ViewTokenParams {
+            token_id: token_0(),
+            owner: ADDRESS_0,
+        });
         ctx.set_parameter(&parameter_bytes);
+        claim_eq!(
+            view_token(&ctx, &host).map(|view| view.transferable),
+            Ok(false)
+        );
 
-        // Call the contract function.
-        let result: ReceiveResult<ViewAddressState> = view_address_state_by_owner(&ctx, &mut host);
+        // Soulbound tokens can't be transferred, not even by the owner
+        let transfer = Transfer {
+            token_id: token_0(),
+            from: ADDRESS_0,
+            to: Receiver::from_account(ACCOUNT_1),
+            amount: ContractTokenAmount::from(1),
+            data: AdditionalData::empty(),
+        };
+        let parameter_bytes = to_bytes(&TransferParams(vec![transfer]));
+        ctx.set_parameter(&parameter_bytes);
+        claim_eq!(
+            self::transfer(&ctx, &mut host, &mut logger),
+            Err(CustomContractError::Soulbound.into())
+        );
 
-        // Check the result
-        claim!(result.is_ok(), "Results in rejection");
+        // But they can be burned by the owner
+        let parameter_bytes = to_bytes(&BurnParams {
+            token_id: token_0(),
+            quantity: ContractTokenAmount::from(1),
+            owner: ADDRESS_0,
+        });
+        ctx.set_parameter(&parameter_bytes);
+        claim_eq!(burn(&ctx, &mut host, &mut logger), Ok(()));
+        claim_eq!(
+            host.state().balance(&token_0(), &ADDRESS_0),
+            Ok(ContractTokenAmount::from(1))
+        );
     }
 
-    // Testing view_token functionality
     #[concordium_test]
-    fn test_view_token() {
-        let quantity_1 = ContractTokenAmount::from(1);
-
-        // Setup the context
+    fn test_burn_many() {
         let mut ctx = TestReceiveContext::empty();
-        ctx.set_invoker(ACCOUNT_0);
-
-        // and parameter.
-        let mint_data = new_mint_params(ADDRESS_0, ADDRESS_0, token_0(), quantity_1);
-
+        ctx.set_sender(ADDRESS_0);
+        let mint_data = new_mint_params(
+            ADDRESS_0,
+            ADDRESS_0,
+            token_0(),
+            ContractTokenAmount::from(3),
+        );
         let parameter_bytes = to_bytes(&mint_data);
         ctx.set_parameter(&parameter_bytes);
 
         let mut logger = TestLogger::init();
         let mut state_builder = TestStateBuilder::new();
-        let state = initial_state(&mut state_builder, quantity_1);
+        let state = empty_state(&mut state_builder);
         let mut host = TestHost::new(state, state_builder);
+        claim_eq!(mint(&ctx, &mut host, Amount::zero(), &mut logger), Ok(()));
 
-        // Call the contract function.
-        let _: ContractResult<()> = mint(&ctx, &mut host, Amount::zero(), &mut logger);
+        let burns = vec![
+            BurnParams {
+                owner: ADDRESS_0,
+                token_id: token_0(),
+                quantity: ContractTokenAmount::from(1),
+            },
+            BurnParams {
+                owner: ADDRESS_0,
+                token_id: token_0(),
+                quantity: ContractTokenAmount::from(1),
+            },
+        ];
+        let parameter_bytes = to_bytes(&burns);
+        ctx.set_parameter(&parameter_bytes);
 
-        let params = ViewTokenParams {
-            token_id: token_0(),
-            owner: ADDRESS_0,
+        // Only the owner or its operator can burn
+        ctx.set_sender(ADDRESS_1);
+        claim_eq!(
+            burn_many(&ctx, &mut host, &mut logger),
+            Err(ContractError::Unauthorized)
+        );
+
+        let (state, state_builder) = host.state_and_builder();
+        state.add_operator(&ADDRESS_0, &ADDRESS_1, state_builder);
+        logger = TestLogger::init();
+        claim_eq!(burn_many(&ctx, &mut host, &mut logger), Ok(()));
+        claim_eq!(
+            host.state().balance(&token_0(), &ADDRESS_0),
+            Ok(ContractTokenAmount::from(1))
+        );
+        claim_eq!(
+            logger.logs[0],
+            to_bytes(&Cis2Event::Burn(BurnEvent {
+                token_id: token_0(),
+                amount: ContractTokenAmount::from(1),
+                owner: ADDRESS_0,
+            }))
+        );
+        claim_eq!(logger.logs.len(), 4);
+    }
+
+    #[concordium_test]
+    fn test_supports() {
+        let mut state_builder = TestStateBuilder::new();
+        let state = empty_state(&mut state_builder);
+        let host = TestHost::new(state, state_builder);
+
+        let params = SupportsQueryParams {
+            queries: vec![
+                StandardIdentifierOwned::new_unchecked("CIS-0".into()),
+                StandardIdentifierOwned::new_unchecked("CIS-1".into()),
+                StandardIdentifierOwned::new_unchecked("CIS-2".into()),
+            ],
         };
         let parameter_bytes = to_bytes(&params);
+        let mut ctx = TestReceiveContext::empty();
         ctx.set_parameter(&parameter_bytes);
 
-        // Call the contract function.
-        let result: ReceiveResult<NFTData> = view_token(&ctx, &mut host);
-
-        // Check the result
-        claim!(result.is_ok(), "Results in rejection");
+        let response = supports(&ctx, &host).expect_report("Query should succeed");
+        claim_eq!(
+            to_bytes(&response),
+            to_bytes(&SupportsQueryResponse::from(vec![
+                SupportResult::Support,
+                SupportResult::NoSupport,
+                SupportResult::Support,
+            ]))
+        );
     }
 }
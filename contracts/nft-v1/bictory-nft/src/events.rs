@@ -13,11 +13,48 @@ pub struct UpdatePriceEvent<T: IsTokenId> {
     pub to: Amount,
 }
 
+/// An untagged event summarizing an airdrop.
+#[derive(Debug, Serialize, SchemaType)]
+pub struct AirdropEvent {
+    /// The address tokens were sent from.
+    pub owner: Address,
+    /// Number of transfers made.
+    pub transfers: u32,
+    /// Total copies of NFT sent over all transfers.
+    pub amount: ContractTokenAmount,
+}
+
+/// An untagged event of an inheritance plan being set or removed.
+#[derive(Debug, Serialize, SchemaType)]
+pub struct InheritanceUpdatedEvent {
+    /// The owner whose tokens are inherited.
+    pub owner: Address,
+    /// New inheritance plan, `None` if it was removed.
+    pub plan: Option<InheritancePlan>,
+}
+
+/// An untagged event of an heir claiming the tokens of an inactive owner.
+#[derive(Debug, Serialize, SchemaType)]
+pub struct InheritanceClaimedEvent {
+    /// The previous owner of the tokens.
+    pub owner: Address,
+    /// The heir receiving the tokens.
+    pub heir: AccountAddress,
+    /// Number of distinct tokens transferred.
+    pub tokens: u32,
+}
+
 /// Tagged Custom event to be serialized for the event log.
 #[derive(Debug)]
 pub enum CustomEvent<T: IsTokenId> {
     /// Updating price of NFT
     UpdatePrice(UpdatePriceEvent<T>),
+    /// Summary of an airdrop
+    Airdrop(AirdropEvent),
+    /// Inheritance plan was set or removed
+    InheritanceUpdated(InheritanceUpdatedEvent),
+    /// Heir claimed the tokens of an inactive owner
+    InheritanceClaimed(InheritanceClaimedEvent),
 }
 
 impl<T: IsTokenId> Serial for CustomEvent<T> {
@@ -27,6 +64,18 @@ impl<T: IsTokenId> Serial for CustomEvent<T> {
                 out.write_u8(UPDATE_PRICE_TAG)?;
                 event.serial(out)
             }
+            CustomEvent::Airdrop(event) => {
+                out.write_u8(AIRDROP_TAG)?;
+                event.serial(out)
+            }
+            CustomEvent::InheritanceUpdated(event) => {
+                out.write_u8(INHERITANCE_UPDATED_TAG)?;
+                event.serial(out)
+            }
+            CustomEvent::InheritanceClaimed(event) => {
+                out.write_u8(INHERITANCE_CLAIMED_TAG)?;
+                event.serial(out)
+            }
         }
     }
 }
@@ -38,6 +87,13 @@ impl<T: IsTokenId> Deserial for CustomEvent<T> {
             UPDATE_PRICE_TAG => {
                 UpdatePriceEvent::<T>::deserial(source).map(CustomEvent::UpdatePrice)
             }
+            AIRDROP_TAG => AirdropEvent::deserial(source).map(CustomEvent::Airdrop),
+            INHERITANCE_UPDATED_TAG => {
+                InheritanceUpdatedEvent::deserial(source).map(CustomEvent::InheritanceUpdated)
+            }
+            INHERITANCE_CLAIMED_TAG => {
+                InheritanceClaimedEvent::deserial(source).map(CustomEvent::InheritanceClaimed)
+            }
             _ => Err(ParseError::default()),
         }
     }
@@ -9,7 +9,13 @@ impl<S: HasStateApi> AddressState<S> {
     }
 
     #[inline(always)]
-    fn insert(&mut self, owner: Address, mint_data: MintData<ContractTokenId>, price: Amount) {
+    fn insert(
+        &mut self,
+        owner: Address,
+        mint_data: MintData<ContractTokenId>,
+        price: Amount,
+        transferable: bool,
+    ) {
         self.owned_tokens.insert(
             mint_data.token_id,
             OwnedData {
@@ -19,7 +25,16 @@ impl<S: HasStateApi> AddressState<S> {
                 creator_royalty: mint_data.creator_royalty,
                 minter: owner,
                 minter_royalty: mint_data.minter_royalty,
+                royalties: mint_data.royalties,
                 quantity: mint_data.quantity,
+                editions: match mint_data.quantity.0 {
+                    0 => Vec::new(),
+                    quantity => vec![EditionRange {
+                        first: 1,
+                        last: quantity,
+                    }],
+                },
+                transferable,
                 phantom_data: PhantomData,
             },
         );
@@ -29,34 +44,513 @@ impl<S: HasStateApi> AddressState<S> {
 // Functions for creating, updating and querying the contract state.
 impl<S: HasStateApi> State<S> {
     /// Creates a empty state with no tokens.
-    pub fn empty(state_builder: &mut StateBuilder<S>) -> Self {
+    pub fn empty(
+        state_builder: &mut StateBuilder<S>,
+        admin: AccountAddress,
+        params: InitParams,
+    ) -> Self {
         State {
             state: state_builder.new_map(),
             all_tokens: state_builder.new_set(),
+            token_count: 0,
+            holders: state_builder.new_map(),
+            reservations: state_builder.new_map(),
+            inheritance: state_builder.new_map(),
+            mint_hook: None,
+            sealed: !params.migration,
+            authority: Authority::new(state_builder, Address::Account(admin)),
+            royalty: params.royalty,
+            fee_receiver: params.fee_receiver,
+            sale_royalty: Percentage::ZERO,
+            beneficiary: admin,
+            permit_keys: state_builder.new_map(),
+            nonces: state_builder.new_map(),
+            paused: false,
+            metadata_base_url: String::from(TOKEN_METADATA_BASE_URL),
+            mint_phases: Vec::new(),
+            allowlist: state_builder.new_set(),
+            phase_mints: state_builder.new_map(),
+            drop_tokens: state_builder.new_map(),
+            drop_beneficiary: admin,
         }
     }
 
+    /// Metadata URL of the token. The hash is the SHA-256 digest from the IPFS content identifier given when the
+    /// token was minted, if there are holders left and the identifier is a `raw` CIDv1 with a `sha2-256` multihash.
+    pub fn metadata_url(&self, token_id: &ContractTokenId) -> MetadataUrl {
+        // Content identifier is the same for all copies, so any holder's data can be used
+        let hash = self
+            .holders
+            .get(token_id)
+            .and_then(|holders| holders.iter().next().map(|holder| *holder))
+            .and_then(|holder| self.state.get(&holder))
+            .and_then(|address_state| {
+                address_state
+                    .owned_tokens
+                    .get(token_id)
+                    .and_then(|data| cid_sha256_digest(&data.cid))
+            });
+
+        MetadataUrl {
+            url: build_token_metadata_url(&self.metadata_base_url, token_id),
+            hash,
+        }
+    }
+
+    /// Results in an error if the contract is paused.
+    pub fn ensure_not_paused(&self) -> ContractResult<()> {
+        ensure!(!self.paused, CustomContractError::ContractPaused.into());
+        Ok(())
+    }
+
+    /// Results in an error if the migrated state is still being imported.
+    pub fn ensure_sealed(&self) -> ContractResult<()> {
+        ensure!(
+            self.sealed,
+            CustomContractError::OperationNotPermitted.into()
+        );
+        Ok(())
+    }
+
+    /// Check the expected `permit` nonce of the account and increment it.
+    pub fn use_nonce(&mut self, account: AccountAddress, nonce: u64) -> ContractResult<()> {
+        let mut expected = self.nonces.entry(account).or_insert_with(|| 0);
+        ensure_eq!(*expected, nonce, CustomContractError::InvalidNonce.into());
+        *expected += 1;
+        Ok(())
+    }
+
     /// Mint a new token with a given address as the owner and creator
     pub fn mint(
         &mut self,
         params: MintData<ContractTokenId>,
         price: Amount,
+        transferable: bool,
         state_builder: &mut StateBuilder<S>,
     ) -> ContractResult<()> {
+        let creator_royalty = Percentage::from_micro_percent(params.creator_royalty as u64);
+        let minter_royalty = Percentage::from_micro_percent(params.minter_royalty as u64);
+        let royalties = royalty_total(&params.royalties)
+            .and_then(|total| total.checked_add(creator_royalty))
+            .and_then(|total| total.checked_add(minter_royalty))
+            .ok_or(CustomContractError::Overflow)?;
+        ensure!(
+            royalties <= Percentage::HUNDRED,
+            CustomContractError::InvalidRoyalty.into()
+        );
         ensure!(
             self.all_tokens.insert(params.token_id.clone()),
             CustomContractError::TokenIdAlreadyExists.into()
         );
+        self.token_count += 1;
+
+        self.add_holder(&params.token_id, params.owner, state_builder);
 
         let mut owner_address = self
             .state
             .entry(params.owner)
             .or_insert_with(|| AddressState::empty(state_builder));
 
-        owner_address.insert(params.owner, params, price);
+        owner_address.insert(params.owner, params, price, transferable);
         Ok(())
     }
 
+    /// Reserve token ID prefix for the minter, or release it if `minter` is `None`.
+    /// Results in an error if the prefix is empty or overlaps with a prefix reserved for another minter.
+    pub fn reserve_token_ids(&mut self, params: ReserveTokenIdsParams) -> ContractResult<()> {
+        ensure!(
+            !params.prefix.is_empty(),
+            CustomContractError::InvalidFields.into()
+        );
+
+        match params.minter {
+            Some(minter) => {
+                let overlaps = self.reservations.iter().any(|(prefix, owner)| {
+                    *owner != minter
+                        && (prefix.starts_with(&params.prefix)
+                            || params.prefix.starts_with(&prefix))
+                });
+                ensure!(!overlaps, CustomContractError::AlreadyExists.into());
+                self.reservations.insert(params.prefix, minter);
+            }
+            None => {
+                self.reservations
+                    .remove_and_get(&params.prefix)
+                    .ok_or(CustomContractError::NotFound)?;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Check that the minter is allowed to mint the token ID. Token IDs in a reserved range can only be minted by the
+    /// minter it's reserved for, and minters with reservations can only mint inside their own ranges.
+    pub fn check_reservation(
+        &self,
+        token_id: &ContractTokenId,
+        minter: &Address,
+    ) -> ContractResult<()> {
+        let mut has_reservations = false;
+        for (prefix, owner) in self.reservations.iter() {
+            let in_range = token_id.0.starts_with(&prefix);
+            if *owner == *minter {
+                if in_range {
+                    return Ok(());
+                }
+                has_reservations = true;
+            } else {
+                ensure!(!in_range, ContractError::Unauthorized);
+            }
+        }
+
+        ensure!(!has_reservations, ContractError::Unauthorized);
+        Ok(())
+    }
+
+    /// List all token ID reservations.
+    pub fn reservations(&self) -> Vec<TokenIdReservation> {
+        self.reservations
+            .iter()
+            .map(|(prefix, minter)| TokenIdReservation {
+                prefix: prefix.clone(),
+                minter: *minter,
+            })
+            .collect()
+    }
+
+    /// Set the inheritance plan of the owner, or remove it if `plan` is `None`.
+    /// Results in an error if the inactivity period is zero.
+    pub fn set_inheritance(
+        &mut self,
+        owner: Address,
+        plan: Option<InheritancePlan>,
+        now: Timestamp,
+    ) -> ContractResult<()> {
+        match plan {
+            Some(plan) => {
+                ensure!(
+                    plan.inactivity.millis() > 0,
+                    CustomContractError::InvalidDuration.into()
+                );
+                self.inheritance.insert(
+                    owner,
+                    Inheritance {
+                        plan,
+                        last_activity: now,
+                    },
+                );
+            }
+            None => {
+                self.inheritance.remove(&owner);
+            }
+        }
+        Ok(())
+    }
+
+    /// Replace the mint phases. Results in an error if the phases are not ordered by strictly increasing start time.
+    pub fn set_mint_phases(&mut self, phases: Vec<MintPhase>) -> ContractResult<()> {
+        ensure!(
+            phases.windows(2).all(|pair| pair[0].start < pair[1].start),
+            CustomContractError::InvalidFields.into()
+        );
+        self.mint_phases = phases;
+        Ok(())
+    }
+
+    /// Check the sender can mint the drop token for `price` in the phase active at the slot time, count the copies
+    /// towards the sender's cap and remove the drop token. `proven_root` is the Merkle root the sender proved
+    /// membership in, if any. Maintainers are not restricted, and neither is anyone if there are no phases, so the slot
+    /// time is only read otherwise. Returns whether the token is minted as part of a phase.
+    /// Results in an error if
+    /// - no phase has started yet, or the active phase is closed
+    /// - the sender is not on the allowlist of the active phase
+    /// - the price is lower than the phase price of all copies
+    /// - the token is not a drop token, or differs from it in anything but the owner
+    /// - the sender would exceed the cap of the phase
+    pub fn use_mint_phase(
+        &mut self,
+        ctx: &impl HasReceiveContext,
+        mint_data: &MintParams,
+        price: Amount,
+        proven_root: Option<[u8; 32]>,
+    ) -> ContractResult<bool> {
+        let minter = ctx.sender();
+        if self.mint_phases.is_empty() || self.authority.has_maintainer_rights(&minter) {
+            return Ok(false);
+        }
+
+        let now = ctx.metadata().slot_time();
+        let phase = self
+            .mint_phases
+            .iter()
+            .rev()
+            .find(|phase| phase.start <= now)
+            .copied()
+            .ok_or(ContractError::Unauthorized)?;
+
+        let allowed = match phase.access {
+            MintAccess::Closed => false,
+            MintAccess::AllowlistSet => self.allowlist.contains(&(phase.start, minter)),
+            MintAccess::AllowlistMerkle(root) => proven_root == Some(root),
+            MintAccess::Public => true,
+        };
+        ensure!(allowed, ContractError::Unauthorized);
+
+        let cost = phase
+            .price
+            .micro_ccd
+            .checked_mul(mint_data.quantity.0)
+            .ok_or(CustomContractError::Overflow)?;
+        ensure!(
+            price.micro_ccd >= cost,
+            CustomContractError::InsufficientFunds.into()
+        );
+
+        let drop_token = self
+            .drop_tokens
+            .get(&mint_data.token_id)
+            .map(|drop_token| drop_token.clone())
+            .ok_or(ContractError::Unauthorized)?;
+        ensure!(
+            MintParams {
+                owner: mint_data.owner,
+                ..drop_token
+            } == *mint_data,
+            ContractError::Unauthorized
+        );
+
+        if let Some(cap) = phase.wallet_cap {
+            let mut minted = self
+                .phase_mints
+                .entry((phase.start, minter))
+                .or_insert_with(|| 0);
+            let total = minted
+                .checked_add(mint_data.quantity.0)
+                .ok_or(CustomContractError::Overflow)?;
+            ensure!(total <= cap, CustomContractError::CapacityExceeded.into());
+            *minted = total;
+        }
+
+        self.drop_tokens.remove(&mint_data.token_id);
+        Ok(true)
+    }
+
+    /// Record a state-changing action of the owner, restarting the inactivity period of its inheritance plan.
+    /// Activity is only tracked for owners with an inheritance plan, so the slot time is only read for them.
+    pub fn record_activity(&mut self, owner: &Address, ctx: &impl HasReceiveContext) {
+        if let Some(mut inheritance) = self.inheritance.get_mut(owner) {
+            inheritance.last_activity = ctx.metadata().slot_time();
+        }
+    }
+
+    /// Get the inheritance plan of the owner for the heir to claim.
+    /// Results in an error if
+    /// - the owner has no inheritance plan
+    /// - the claimer is not the heir
+    /// - the owner was active within the inactivity period
+    pub fn claimable_inheritance(
+        &self,
+        owner: &Address,
+        claimer: &Address,
+        now: Timestamp,
+    ) -> ContractResult<InheritancePlan> {
+        let inheritance = *self
+            .inheritance
+            .get(owner)
+            .ok_or(CustomContractError::NotFound)?;
+
+        ensure!(
+            claimer.matches_account(&inheritance.plan.heir),
+            ContractError::Unauthorized
+        );
+
+        let claimable_at = inheritance
+            .last_activity
+            .checked_add(inheritance.plan.inactivity)
+            .ok_or(CustomContractError::Overflow)?;
+        ensure!(
+            now >= claimable_at,
+            CustomContractError::OperationNotPermitted.into()
+        );
+
+        Ok(inheritance.plan)
+    }
+
+    /// All transferable tokens held by the address together with their balances.
+    pub fn transferable_tokens(
+        &self,
+        owner: &Address,
+    ) -> Vec<(ContractTokenId, ContractTokenAmount)> {
+        self.state
+            .get(owner)
+            .map_or_else(Vec::new, |address_state| {
+                address_state
+                    .owned_tokens
+                    .iter()
+                    .filter(|(_, owned_data)| owned_data.transferable)
+                    .map(|(token_id, owned_data)| (token_id.clone(), owned_data.quantity))
+                    .collect()
+            })
+    }
+
+    /// Add address to the holders of the token.
+    fn add_holder(
+        &mut self,
+        token_id: &ContractTokenId,
+        holder: Address,
+        state_builder: &mut StateBuilder<S>,
+    ) {
+        self.holders
+            .entry(token_id.clone())
+            .or_insert_with(|| state_builder.new_set())
+            .insert(holder);
+    }
+
+    /// Remove address from the holders of the token.
+    fn remove_holder(&mut self, token_id: &ContractTokenId, holder: &Address) {
+        if let Some(mut holders) = self.holders.get_mut(token_id) {
+            holders.remove(holder);
+        }
+    }
+
+    /// Get up to `limit` holders of all tokens together with their balances, starting from the holder with index
+    /// `cursor`. Holders are always listed in the same order, grouped by token ID.
+    pub fn snapshot_holders(
+        &self,
+        cursor: u32,
+        limit: u32,
+    ) -> ContractResult<SnapshotHoldersResult> {
+        let mut holders = Vec::new();
+        let mut index = 0u32;
+
+        for (token_id, token_holders) in self.holders.iter() {
+            for owner in token_holders.iter() {
+                if holders.len() as u32 >= limit {
+                    return Ok(SnapshotHoldersResult {
+                        holders,
+                        next: Some(index),
+                    });
+                }
+                if index >= cursor {
+                    holders.push(HolderBalance {
+                        token_id: token_id.clone(),
+                        owner: *owner,
+                        balance: self.balance(&token_id, &owner)?,
+                    });
+                }
+                index += 1;
+            }
+        }
+
+        Ok(SnapshotHoldersResult {
+            holders,
+            next: None,
+        })
+    }
+
+    /// Number of token IDs minted in this contract, including tokens with all copies burned.
+    pub fn total_supply(&self) -> u64 {
+        self.token_count
+    }
+
+    /// List token IDs in the order of their serialization.
+    pub fn tokens(&self, skip: u32, show: u32) -> Vec<ContractTokenId> {
+        self.all_tokens
+            .iter()
+            .skip(skip as usize)
+            .take(show as usize)
+            .map(|token_id| token_id.clone())
+            .collect()
+    }
+
+    /// List a page of current holders of the token.
+    /// Results in an error if the token ID does not exist.
+    pub fn owners(
+        &self,
+        token_id: &ContractTokenId,
+        skip: u32,
+        show: u32,
+    ) -> ContractResult<Vec<Address>> {
+        ensure!(self.contains_token(token_id), ContractError::InvalidTokenId);
+
+        Ok(self
+            .holders
+            .get(token_id)
+            .map(|holders| {
+                holders
+                    .iter()
+                    .skip(skip as usize)
+                    .take(show as usize)
+                    .map(|owner| *owner)
+                    .collect()
+            })
+            .unwrap_or_default())
+    }
+
+    /// Royalties of the token creator, minter and additional recipients followed by Bictory's share of sales. Shares of
+    /// contract addresses and zero shares are not included.
+    /// Results in an error if the token ID does not exist or all of its copies were burned.
+    pub fn royalties(&self, token_id: &ContractTokenId) -> ContractResult<GetRoyaltiesResponse> {
+        ensure!(self.contains_token(token_id), ContractError::InvalidTokenId);
+
+        // Royalties are the same for all copies, so any holder's data can be used
+        let data = self
+            .holders
+            .get(token_id)
+            .and_then(|holders| holders.iter().next().map(|holder| *holder))
+            .and_then(|holder| self.state.get(&holder))
+            .and_then(|address_state| {
+                address_state
+                    .owned_tokens
+                    .get(token_id)
+                    .map(|data| data.as_nft_data())
+            })
+            .ok_or(CustomContractError::NotFound)?;
+
+        let mut shares = data.royalty_recipients();
+        shares.push(Royalty {
+            beneficiary: self.beneficiary,
+            percentage: self.sale_royalty,
+        });
+        let mut royalties = BoundedVec::new();
+        for royalty in shares {
+            if royalty.percentage > Percentage::ZERO {
+                royalties.push(royalty)?;
+            }
+        }
+
+        Ok(GetRoyaltiesResponse { royalties })
+    }
+
+    /// Find the current holder of the token copy with the edition number.
+    /// Results in an error if the token ID does not exist or the edition was burned or never minted.
+    pub fn edition_owner(
+        &self,
+        token_id: &ContractTokenId,
+        edition: u64,
+    ) -> ContractResult<Address> {
+        ensure!(self.contains_token(token_id), ContractError::InvalidTokenId);
+
+        let holders = self
+            .holders
+            .get(token_id)
+            .ok_or(CustomContractError::NotFound)?;
+        let owner = holders.iter().find(|owner| {
+            self.state
+                .get(owner)
+                .and_then(|address_state| {
+                    address_state
+                        .owned_tokens
+                        .get(token_id)
+                        .map(|data| data.has_edition(edition))
+                })
+                .unwrap_or(false)
+        });
+        owner
+            .map(|owner| *owner)
+            .ok_or_else(|| CustomContractError::NotFound.into())
+    }
+
     /// Check that the token ID currently exists in this contract.
     #[inline(always)]
     fn contains_token(&self, token_id: &ContractTokenId) -> bool {
@@ -106,7 +600,7 @@ impl<S: HasStateApi> State<S> {
             return Ok(());
         }
 
-        let mut owned_data = {
+        let (mut owned_data, sent_all) = {
             let balance = Self::balance(self, &transfer.token_id, &transfer.from)?;
 
             let mut from_address_state = self
@@ -114,29 +608,38 @@ impl<S: HasStateApi> State<S> {
                 .entry(transfer.from)
                 .occupied_or(CustomContractError::AddressNotFound)?;
 
-            match balance.cmp(&transfer.amount) {
-                core::cmp::Ordering::Equal => {
-                    let mut owned_data = from_address_state
+            let transferable = from_address_state
+                .owned_tokens
+                .get(&transfer.token_id)
+                .map_or(true, |data| data.transferable);
+            ensure!(transferable, CustomContractError::Soulbound.into());
+
+            match debit_balance(balance, transfer.amount)? {
+                Debit::All => {
+                    let owned_data = from_address_state
                         .owned_tokens
                         .remove_and_get(&transfer.token_id)
                         .ok_or(ContractError::InvalidTokenId)?;
-                    owned_data.quantity -= transfer.amount;
-                    owned_data
+                    (owned_data, true)
                 }
-                core::cmp::Ordering::Greater => {
+                Debit::Partial(remaining) => {
                     let mut owned_data = from_address_state
                         .owned_tokens
                         .entry(transfer.token_id.clone())
                         .occupied_or(ContractError::InvalidTokenId)?;
-                    owned_data.quantity -= transfer.amount;
-                    owned_data.copy()
-                }
-                core::cmp::Ordering::Less => {
-                    return Err(ContractError::InsufficientFunds);
+                    owned_data.quantity = remaining;
+                    let mut sent_data = owned_data.copy();
+                    sent_data.editions = owned_data.take_editions(transfer.amount.0);
+                    (sent_data, false)
                 }
             }
         };
 
+        if sent_all {
+            self.remove_holder(&transfer.token_id, &transfer.from);
+        }
+        self.add_holder(&transfer.token_id, transfer.to.address(), state_builder);
+
         let mut to_address_state = self
             .state
             .entry(transfer.to.address())
@@ -145,7 +648,10 @@ impl<S: HasStateApi> State<S> {
         to_address_state
             .owned_tokens
             .entry(transfer.token_id.clone())
-            .and_modify(|data| data.quantity += transfer.amount)
+            .and_modify(|data| {
+                data.quantity += transfer.amount;
+                data.add_editions(core::mem::take(&mut owned_data.editions));
+            })
             .or_insert_with(|| {
                 owned_data.quantity = transfer.amount;
                 owned_data
@@ -195,18 +701,20 @@ impl<S: HasStateApi> State<S> {
 
         let balance = Self::balance(self, &params.token_id, owner)?;
 
-        match balance.cmp(&params.quantity) {
-            core::cmp::Ordering::Equal => {
+        match debit_balance(balance, params.quantity)? {
+            Debit::All => {
                 addr_state.owned_tokens.remove(&params.token_id);
+                drop(addr_state);
+                self.remove_holder(&params.token_id, owner);
             }
-            core::cmp::Ordering::Greater => addr_state
+            Debit::Partial(remaining) => addr_state
                 .owned_tokens
                 .get_mut(&params.token_id)
-                .map(|mut owned_data| owned_data.quantity -= params.quantity)
+                .map(|mut owned_data| {
+                    owned_data.quantity = remaining;
+                    owned_data.take_editions(params.quantity.0);
+                })
                 .ok_or(ContractError::InvalidTokenId)?,
-            core::cmp::Ordering::Less => {
-                return Err(ContractError::InsufficientFunds);
-            }
         }
 
         Ok(BurnEvent {
@@ -216,6 +724,214 @@ impl<S: HasStateApi> State<S> {
         })
     }
 
+    /// Export up to `limit` records of the state, starting from the record with index `cursor`. Records are always
+    /// listed in the same order: token IDs, balances and operators of each address, reservations, inheritance plans,
+    /// the mint hook, drop tokens, mint phases with their allowlists and minted copies, `permit` nonces and keys, and
+    /// the contract configuration.
+    pub fn export(&self, cursor: u32, limit: u32) -> ExportStateResult {
+        let mut export = StateExport::new(cursor, limit);
+
+        for token_id in self.all_tokens.iter() {
+            if !export.push(|| StateRecord::TokenId(token_id.clone())) {
+                return export.finish(false);
+            }
+        }
+
+        for (owner, address_state) in self.state.iter() {
+            for (token_id, data) in address_state.owned_tokens.iter() {
+                let record = || {
+                    StateRecord::Balance(TokenRecord {
+                        owner: *owner,
+                        token_id: token_id.clone(),
+                        creator: data.creator,
+                        creator_royalty: data.creator_royalty,
+                        minter: data.minter,
+                        minter_royalty: data.minter_royalty,
+                        royalties: data.royalties.clone(),
+                        price: data.price,
+                        cid: data.cid.clone(),
+                        quantity: data.quantity,
+                        editions: data.editions.clone(),
+                        transferable: data.transferable,
+                    })
+                };
+                if !export.push(record) {
+                    return export.finish(false);
+                }
+            }
+            for operator in address_state.operators.iter() {
+                if !export.push(|| StateRecord::Operator(*owner, *operator)) {
+                    return export.finish(false);
+                }
+            }
+        }
+
+        for (prefix, minter) in self.reservations.iter() {
+            let record = || {
+                StateRecord::Reservation(TokenIdReservation {
+                    prefix: prefix.clone(),
+                    minter: *minter,
+                })
+            };
+            if !export.push(record) {
+                return export.finish(false);
+            }
+        }
+
+        for (owner, inheritance) in self.inheritance.iter() {
+            if !export.push(|| StateRecord::Inheritance(*owner, *inheritance)) {
+                return export.finish(false);
+            }
+        }
+
+        if let Some(hook) = &self.mint_hook {
+            if !export.push(|| StateRecord::MintHook(hook.clone())) {
+                return export.finish(false);
+            }
+        }
+
+        for (_, drop_token) in self.drop_tokens.iter() {
+            if !export.push(|| StateRecord::DropToken(drop_token.clone())) {
+                return export.finish(false);
+            }
+        }
+
+        for phase in self.mint_phases.iter() {
+            if !export.push(|| StateRecord::MintPhase(*phase)) {
+                return export.finish(false);
+            }
+        }
+
+        for entry in self.allowlist.iter() {
+            let (phase_start, address) = *entry;
+            if !export.push(|| StateRecord::Allowlisted(phase_start, address)) {
+                return export.finish(false);
+            }
+        }
+
+        for (key, minted) in self.phase_mints.iter() {
+            let (phase_start, minter) = *key;
+            if !export.push(|| StateRecord::PhaseMints(phase_start, minter, *minted)) {
+                return export.finish(false);
+            }
+        }
+
+        for (account, nonce) in self.nonces.iter() {
+            if !export.push(|| StateRecord::Nonce(*account, *nonce)) {
+                return export.finish(false);
+            }
+        }
+
+        for (account, key) in self.permit_keys.iter() {
+            if !export.push(|| StateRecord::PermitKey(*account, *key)) {
+                return export.finish(false);
+            }
+        }
+
+        let record = || {
+            StateRecord::Config(ConfigRecord {
+                royalty: self.royalty,
+                fee_receiver: self.fee_receiver,
+                sale_royalty: self.sale_royalty,
+                beneficiary: self.beneficiary,
+                drop_beneficiary: self.drop_beneficiary,
+                metadata_base_url: self.metadata_base_url.clone(),
+                paused: self.paused,
+            })
+        };
+        if !export.push(record) {
+            return export.finish(false);
+        }
+
+        export.finish(true)
+    }
+
+    /// Import a record exported with `export`.
+    /// Results in an error if the owner already holds the token of an imported balance.
+    pub fn import(
+        &mut self,
+        record: StateRecord,
+        state_builder: &mut StateBuilder<S>,
+    ) -> ContractResult<()> {
+        match record {
+            StateRecord::TokenId(token_id) => {
+                if self.all_tokens.insert(token_id) {
+                    self.token_count += 1;
+                }
+            }
+            StateRecord::Balance(record) => {
+                if self.all_tokens.insert(record.token_id.clone()) {
+                    self.token_count += 1;
+                }
+                self.add_holder(&record.token_id, record.owner, state_builder);
+
+                let mut address_state = self
+                    .state
+                    .entry(record.owner)
+                    .or_insert_with(|| AddressState::empty(state_builder));
+                ensure!(
+                    address_state.owned_tokens.get(&record.token_id).is_none(),
+                    CustomContractError::AlreadyExists.into()
+                );
+                address_state.owned_tokens.insert(
+                    record.token_id,
+                    OwnedData {
+                        creator: record.creator,
+                        creator_royalty: record.creator_royalty,
+                        minter: record.minter,
+                        minter_royalty: record.minter_royalty,
+                        royalties: record.royalties,
+                        price: record.price,
+                        cid: record.cid,
+                        quantity: record.quantity,
+                        editions: record.editions,
+                        transferable: record.transferable,
+                        phantom_data: PhantomData,
+                    },
+                );
+            }
+            StateRecord::Operator(owner, operator) => {
+                self.add_operator(&owner, &operator, state_builder)
+            }
+            StateRecord::Reservation(reservation) => {
+                self.reservations
+                    .insert(reservation.prefix, reservation.minter);
+            }
+            StateRecord::Inheritance(owner, inheritance) => {
+                self.inheritance.insert(owner, inheritance);
+            }
+            StateRecord::MintHook(hook) => self.mint_hook = Some(hook),
+            StateRecord::DropToken(drop_token) => {
+                self.drop_tokens
+                    .insert(drop_token.token_id.clone(), drop_token);
+            }
+            // Phases are exported in order of their start time
+            StateRecord::MintPhase(phase) => self.mint_phases.push(phase),
+            StateRecord::Allowlisted(phase_start, address) => {
+                self.allowlist.insert((phase_start, address));
+            }
+            StateRecord::PhaseMints(phase_start, minter, minted) => {
+                self.phase_mints.insert((phase_start, minter), minted);
+            }
+            StateRecord::Nonce(account, nonce) => {
+                self.nonces.insert(account, nonce);
+            }
+            StateRecord::PermitKey(account, key) => {
+                self.permit_keys.insert(account, key);
+            }
+            StateRecord::Config(config) => {
+                self.royalty = config.royalty;
+                self.fee_receiver = config.fee_receiver;
+                self.sale_royalty = config.sale_royalty;
+                self.beneficiary = config.beneficiary;
+                self.drop_beneficiary = config.drop_beneficiary;
+                self.metadata_base_url = config.metadata_base_url;
+                self.paused = config.paused;
+            }
+        }
+        Ok(())
+    }
+
     /// Updating price of NFT.
     /// Results in an error if the
     /// - token ID does not exist in the state
@@ -260,6 +976,7 @@ impl<S: HasStateApi> OwnedData<S> {
             price: self.price,
             cid: self.cid.clone(),
             quantity: self.quantity,
+            royalties: self.royalties.clone(),
         }
     }
 
@@ -269,10 +986,95 @@ impl<S: HasStateApi> OwnedData<S> {
             creator_royalty: self.creator_royalty,
             minter: self.minter,
             minter_royalty: self.minter_royalty,
+            royalties: self.royalties.clone(),
             price: self.price,
             cid: self.cid.clone(),
             quantity: self.quantity,
+            editions: self.editions.clone(),
+            transferable: self.transferable,
             phantom_data: PhantomData,
         }
     }
+
+    /// Check that the owner holds the copy with the edition number.
+    pub fn has_edition(&self, edition: u64) -> bool {
+        self.editions
+            .iter()
+            .any(|range| range.first <= edition && edition <= range.last)
+    }
+
+    /// Remove up to `count` of the lowest editions held, when copies are transferred or burned.
+    pub fn take_editions(&mut self, mut count: u64) -> Vec<EditionRange> {
+        let mut taken = Vec::new();
+        while count > 0 && !self.editions.is_empty() {
+            let range = &mut self.editions[0];
+            let len = range.last - range.first + 1;
+            if len <= count {
+                count -= len;
+                taken.push(self.editions.remove(0));
+            } else {
+                taken.push(EditionRange {
+                    first: range.first,
+                    last: range.first + count - 1,
+                });
+                range.first += count;
+                count = 0;
+            }
+        }
+        taken
+    }
+
+    /// Add received editions, merging adjacent ranges.
+    pub fn add_editions(&mut self, editions: Vec<EditionRange>) {
+        self.editions.extend(editions);
+        self.editions.sort_unstable_by_key(|range| range.first);
+
+        let mut merged: Vec<EditionRange> = Vec::with_capacity(self.editions.len());
+        for range in self.editions.drain(..) {
+            match merged.last_mut() {
+                Some(last) if last.last + 1 == range.first => last.last = range.last,
+                _ => merged.push(range),
+            }
+        }
+        self.editions = merged;
+    }
+}
+
+/// Single chunk of the exported state. Records before the cursor are only counted, without being built.
+struct StateExport {
+    index: u32,
+    cursor: u32,
+    limit: u32,
+    records: Vec<StateRecord>,
+}
+
+impl StateExport {
+    fn new(cursor: u32, limit: u32) -> Self {
+        Self {
+            index: 0,
+            cursor,
+            limit,
+            records: Vec::new(),
+        }
+    }
+
+    /// Visit the next record of the state. Returns `false` if the chunk is already full.
+    fn push(&mut self, record: impl FnOnce() -> StateRecord) -> bool {
+        if self.records.len() as u32 >= self.limit {
+            return false;
+        }
+        if self.index >= self.cursor {
+            self.records.push(record());
+        }
+        self.index += 1;
+        true
+    }
+
+    /// Finish the chunk. If not all records were visited, the next chunk starts with the first record left out.
+    fn finish(self, visited_all: bool) -> ExportStateResult {
+        ExportStateResult {
+            next: (!visited_all).then(|| self.index),
+            records: self.records,
+        }
+    }
 }
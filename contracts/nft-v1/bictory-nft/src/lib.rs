@@ -24,6 +24,7 @@ use crate::{constants::*, events::*, structs::*};
 use commons::{bictory_nft::view::*, *};
 use concordium_cis2::*;
 use concordium_std::*;
+use core::convert::TryInto;
 use marker::PhantomData;
 
 mod constants;
@@ -32,15 +33,49 @@ mod events;
 mod impls;
 mod structs;
 
-/// Build a string from TOKEN_METADATA_BASE_URL appended with the token ID
+/// Build a string from the metadata base URL appended with the token ID
 /// encoded as hex.
-fn build_token_metadata_url(token_id: &ContractTokenId) -> String {
-    let mut token_metadata_url = String::from(TOKEN_METADATA_BASE_URL);
+fn build_token_metadata_url(base_url: &str, token_id: &ContractTokenId) -> String {
+    let mut token_metadata_url = String::from(base_url);
     push_token_id(&mut token_metadata_url, token_id);
     token_metadata_url.push('/');
     token_metadata_url
 }
 
+/// Build the metadata URL of a single edition, the token metadata URL appended with the edition number.
+fn build_edition_metadata_url(base_url: &str, token_id: &ContractTokenId, edition: u64) -> String {
+    let mut edition_metadata_url = build_token_metadata_url(base_url, token_id);
+    edition_metadata_url.push_str(&edition.to_string());
+    edition_metadata_url
+}
+
+/// Extract the SHA-256 digest of the content from an IPFS content identifier, if the CID is a binary CIDv1 with the
+/// `raw` codec and a `sha2-256` multihash. Other codecs, like the `dag-pb` of CIDv0, hash the encoded IPFS blocks
+/// rather than the metadata itself.
+fn cid_sha256_digest(cid: &[u8]) -> Option<[u8; 32]> {
+    match cid {
+        [CID_V1, CID_CODEC_RAW, MULTIHASH_SHA2_256, 0x20, digest @ ..] => digest.try_into().ok(),
+        _ => None,
+    }
+}
+
+/// Compute the root of a Merkle tree from the leaf and the sibling hashes on its path. Each pair is hashed in sorted
+/// order, so the proof doesn't need to encode on which side the siblings are.
+fn merkle_root(
+    leaf: [u8; 32],
+    proof: &[[u8; 32]],
+    crypto_primitives: &impl HasCryptoPrimitives,
+) -> [u8; 32] {
+    proof.iter().fold(leaf, |node, sibling| {
+        let pair = if node <= *sibling {
+            [node, *sibling]
+        } else {
+            [*sibling, node]
+        };
+        crypto_primitives.hash_sha2_256(&pair.concat()).0
+    })
+}
+
 fn push_token_id(string: &mut String, token_id: &ContractTokenId) {
     for byte in &token_id.0 {
         string.push(bits_to_hex_char(byte >> 4));
@@ -79,4 +114,20 @@ mod tests {
             claim_eq!(token_id_string, token_id.to_string());
         }
     }
+
+    #[concordium_test]
+    fn cid_digest_extraction() {
+        let digest = [0xABu8; 32];
+        let raw = [&[0x01, 0x55, 0x12, 0x20][..], &digest[..]].concat();
+        claim_eq!(cid_sha256_digest(&raw), Some(digest));
+        // Digests of encoded blocks, other hash functions and truncated digests are not SHA-256 hashes of the metadata
+        let cid_v0 = [&[0x12, 0x20][..], &digest[..]].concat();
+        let dag_pb = [&[0x01, 0x70, 0x12, 0x20][..], &digest[..]].concat();
+        let blake3 = [&[0x01, 0x55, 0x1E, 0x20][..], &digest[..]].concat();
+        claim_eq!(cid_sha256_digest(&cid_v0), None);
+        claim_eq!(cid_sha256_digest(&dag_pb), None);
+        claim_eq!(cid_sha256_digest(&blake3), None);
+        claim_eq!(cid_sha256_digest(&raw[..20]), None);
+        claim_eq!(cid_sha256_digest(&[]), None);
+    }
 }
@@ -12,16 +12,30 @@ pub struct OwnedData<S: HasStateApi> {
     pub minter: Address,
     /// Royalty percentage for minter
     pub minter_royalty: u32,
+    /// Additional royalty recipients
+    pub royalties: RoyaltyRecipients,
     /// Cost of NFT
     pub price: Amount,
     /// IPFS content identifier
     pub cid: Vec<u8>,
     /// Copies of NFT
     pub quantity: ContractTokenAmount,
+    /// Edition numbers of the held copies
+    pub editions: Vec<EditionRange>,
+    /// Soulbound tokens can't be transferred, only burned
+    pub transferable: bool,
     /// Unused variable
     pub phantom_data: PhantomData<S>,
 }
 
+/// Consecutive edition numbers of a token, from `first` to `last` inclusive. Minting `quantity` copies creates
+/// editions 1 to `quantity`.
+#[derive(Debug, Serialize, SchemaType, Clone, Copy, PartialEq, Eq)]
+pub struct EditionRange {
+    pub first: u64,
+    pub last: u64,
+}
+
 /// The state for each address.
 #[derive(Serial, DeserialWithState, Deletable)]
 #[concordium(state_parameter = "S")]
@@ -42,4 +56,391 @@ pub struct State<S: HasStateApi> {
     pub state: StateMap<Address, AddressState<S>, S>,
     /// All of the token IDs
     pub all_tokens: StateSet<ContractTokenId, S>,
+    /// Number of token IDs in `all_tokens`, kept so that `totalSupply` doesn't iterate them
+    pub token_count: u64,
+    /// Current holders of each token ID
+    pub holders: StateMap<ContractTokenId, StateSet<Address, S>, S>,
+    /// Token ID prefixes reserved for specific minters
+    pub reservations: StateMap<Vec<u8>, Address, S>,
+    /// Inheritance plans of owners who designated an heir
+    pub inheritance: StateMap<Address, Inheritance, S>,
+    /// Contract notified about tokens minted with `mintAndRegister`
+    pub mint_hook: Option<MintHook>,
+    /// Set once the migrated state is activated, after which `importState` is rejected
+    pub sealed: bool,
+    /// Authority module for administrative rights management
+    pub authority: Authority<S>,
+    /// Bictory's share of the price when a token is minted for another creator
+    pub royalty: Percentage,
+    /// Contract receiving Bictory's share with its `sendCCD` entrypoint
+    pub fee_receiver: ContractAddress,
+    /// Bictory's share of sales reported by `getRoyalties`
+    pub sale_royalty: Percentage,
+    /// Account receiving Bictory's share of sales
+    pub beneficiary: AccountAddress,
+    /// Ed25519 public keys verifying messages signed for `permit`
+    pub permit_keys: StateMap<AccountAddress, [u8; 32], S>,
+    /// Nonce expected in the next `permit` message of each account
+    pub nonces: StateMap<AccountAddress, u64, S>,
+    /// Set by maintainers to stop minting and moving tokens, e.g. during incident response
+    pub paused: bool,
+    /// Base of the token metadata URLs, followed by the token ID encoded as hex
+    pub metadata_base_url: String,
+    /// Mint phases ordered by start time. Minting is not restricted by phases if there are none.
+    pub mint_phases: Vec<MintPhase>,
+    /// Addresses allowed to mint during the phase starting at the given time
+    pub allowlist: StateSet<(Timestamp, Address), S>,
+    /// Copies minted by each address during the phase starting at the given time
+    pub phase_mints: StateMap<(Timestamp, Address), u64, S>,
+    /// Tokens defined by maintainers that can be minted during mint phases, by token ID
+    pub drop_tokens: StateMap<ContractTokenId, MintParams, S>,
+    /// Account receiving the price paid for tokens minted during mint phases
+    pub drop_beneficiary: AccountAddress,
+}
+
+/// Parameter type for the contract initialization.
+#[derive(Debug, Serialize, SchemaType, Clone)]
+pub struct InitParams {
+    /// Bictory's share of the price when a token is minted for another creator
+    pub royalty: Percentage,
+    /// Contract receiving Bictory's share with its `sendCCD` entrypoint
+    pub fee_receiver: ContractAddress,
+    /// Start in migration mode, accepting `importState` until `sealState` is called. Otherwise the state starts sealed
+    pub migration: bool,
+}
+
+/// Value updated with `updateInternalValue`.
+#[derive(Debug, Serialize, SchemaType, Clone, PartialEq, Eq)]
+pub enum InternalValue {
+    Royalty(Percentage),
+    FeeReceiver(ContractAddress),
+    SaleRoyalty(Percentage),
+    Beneficiary(AccountAddress),
+    DropBeneficiary(AccountAddress),
+}
+
+/// Parameter type for the `viewInternalValue` function.
+#[derive(Debug, Serialize, SchemaType, Clone)]
+pub enum ViewInternalValueParams {
+    Royalty,
+    FeeReceiver,
+    SaleRoyalty,
+    Beneficiary,
+    DropBeneficiary,
+}
+
+/// Parameter type for the `snapshotHolders` function.
+#[derive(Serialize, SchemaType)]
+pub struct SnapshotHoldersParams {
+    /// Index of the first holder to return, `next` of the previous page
+    pub cursor: u32,
+    /// Maximum number of holders to return
+    pub limit: u32,
+}
+
+/// Return type of the `snapshotHolders` function.
+#[derive(Debug, Serialize, SchemaType, PartialEq, Eq)]
+pub struct SnapshotHoldersResult {
+    pub holders: Vec<HolderBalance>,
+    /// Cursor of the next page. `None` if all holders were returned
+    pub next: Option<u32>,
+}
+
+/// Parameter type for the `viewTokens` function.
+#[derive(Serialize, SchemaType)]
+pub struct ViewTokensParams {
+    /// Number of tokens to skip
+    pub skip: u32,
+    /// Maximum number of tokens to return
+    pub show: u32,
+}
+
+/// Parameter type for the `viewOwners` function.
+#[derive(Serialize, SchemaType)]
+pub struct ViewOwnersParams {
+    /// Token ID to list holders of
+    pub token_id: ContractTokenId,
+    /// Number of holders to skip
+    pub skip: u32,
+    /// Maximum number of holders to return
+    pub show: u32,
+}
+
+/// Token holder with its current balance.
+#[derive(Debug, Serialize, SchemaType, PartialEq, Eq)]
+pub struct HolderBalance {
+    pub token_id: ContractTokenId,
+    /// Address of the holder
+    pub owner: Address,
+    /// Copies of NFT held
+    pub balance: ContractTokenAmount,
+}
+
+/// Action performed by `permit` on behalf of the signer.
+#[derive(Serialize, SchemaType)]
+pub enum PermitAction {
+    Transfer(TransferParameter),
+    UpdateOperator(UpdateOperatorParams),
+}
+
+/// Message signed by the account performing the action with `permit`.
+#[derive(Serialize, SchemaType)]
+pub struct PermitMessage {
+    /// Account the action is performed for
+    pub signer: AccountAddress,
+    /// Signer's permit counter, prevents replaying the message
+    pub nonce: u64,
+    /// Message can't be used after this time
+    pub expiry: Timestamp,
+    pub action: PermitAction,
+}
+
+/// Parameter type for the `permit` function.
+/// Signed message is the serialized contract address followed by the serialized permit message.
+#[derive(Serialize, SchemaType)]
+pub struct PermitParams {
+    pub message: PermitMessage,
+    /// Ed25519 signature of the signer
+    pub signature: [u8; 64],
+}
+
+/// Parameter type for the `viewEdition` function.
+#[derive(Debug, Serialize, SchemaType)]
+pub struct ViewEditionParams {
+    pub token_id: ContractTokenId,
+    /// Edition number, starting from 1
+    pub edition: u64,
+}
+
+/// Return type of the `viewEdition` function.
+#[derive(Debug, Serialize, SchemaType, PartialEq, Eq)]
+pub struct EditionView {
+    pub edition: u64,
+    /// Current holder of the edition
+    pub owner: Address,
+    /// Metadata URL of the edition, the token metadata URL followed by the edition number
+    pub metadata_url: String,
+}
+
+/// Single transfer of the `airdrop` function. Tokens are always sent from the sender.
+#[derive(Debug, Serialize, SchemaType)]
+pub struct AirdropItem {
+    /// Receiver of the tokens
+    pub to: Receiver,
+    /// Token ID to send
+    pub token_id: ContractTokenId,
+    /// Copies of NFT to send
+    pub amount: ContractTokenAmount,
+}
+
+/// Single item of the `authorityAirdrop` function.
+#[derive(Serialize, SchemaType)]
+pub enum AuthorityAirdropItem {
+    /// Mint a new token to the owner in the minting data
+    Mint(MintParams),
+    /// Send tokens owned by the sender
+    Transfer(AirdropItem),
+}
+
+/// Parameter type for the `reserveTokenIds` function.
+#[derive(Debug, Serialize, SchemaType)]
+pub struct ReserveTokenIdsParams {
+    /// Token IDs starting with these bytes are reserved
+    pub prefix: Vec<u8>,
+    /// Minter the prefix is reserved for. `None` releases the reservation.
+    pub minter: Option<Address>,
+}
+
+/// Token ID prefix reserved for a minter.
+#[derive(Debug, Serialize, SchemaType, PartialEq, Eq)]
+pub struct TokenIdReservation {
+    /// Token IDs starting with these bytes are reserved
+    pub prefix: Vec<u8>,
+    /// Only address allowed to mint tokens in the reserved range
+    pub minter: Address,
+}
+
+/// Contract entrypoint invoked with every token minted with `mintAndRegister`, e.g. a listing or a registry.
+#[derive(Debug, Serialize, SchemaType, Clone, PartialEq, Eq)]
+pub struct MintHook {
+    pub contract: ContractAddress,
+    pub entrypoint: OwnedEntrypointName,
+}
+
+/// Parameter type for the `mintAndRegister` function.
+#[derive(Serialize, SchemaType)]
+pub struct MintAndRegisterParams {
+    /// Token to mint, same as in `mint`
+    pub mint: MintParams,
+    /// Data passed to the mint hook as is
+    pub data: Vec<u8>,
+}
+
+/// Parameter sent to the mint hook contract.
+#[derive(Debug, Serialize, SchemaType, PartialEq, Eq)]
+pub struct MintHookParams {
+    /// Newly minted token
+    pub token_id: ContractTokenId,
+    /// Owner of the minted token
+    pub owner: Address,
+    /// Address that minted the token
+    pub minter: Address,
+    /// Data provided by the minter
+    pub data: Vec<u8>,
+}
+
+/// Addresses allowed to mint during a mint phase.
+#[derive(Debug, Serialize, SchemaType, Clone, Copy, PartialEq, Eq)]
+pub enum MintAccess {
+    /// Only the authority can mint
+    Closed,
+    /// Addresses added to the allowlist of the phase with `updateAllowlist`
+    AllowlistSet,
+    /// Addresses proving membership in the Merkle tree with this root with `mintWithProof`
+    AllowlistMerkle([u8; 32]),
+    /// Anyone can mint
+    Public,
+}
+
+/// Minting rules from the start time until the next phase starts.
+#[derive(Debug, Serialize, SchemaType, Clone, Copy, PartialEq, Eq)]
+pub struct MintPhase {
+    pub start: Timestamp,
+    pub access: MintAccess,
+    /// Minimum amount paid for each minted copy
+    pub price: Amount,
+    /// Maximum number of copies each address can mint during the phase
+    pub wallet_cap: Option<u64>,
+}
+
+/// Parameter type for the `updateAllowlist` function.
+#[derive(Debug, Serialize, SchemaType)]
+pub struct UpdateAllowlistParams {
+    /// Start time of the phase, which identifies its allowlist
+    pub phase_start: Timestamp,
+    pub add: Vec<Address>,
+    pub remove: Vec<Address>,
+}
+
+/// Parameter type for the `updateDropTokens` function.
+#[derive(Debug, Serialize, SchemaType)]
+pub struct UpdateDropTokensParams {
+    /// Tokens that can be minted during mint phases. The owner is replaced by the one chosen by the minter
+    pub add: Vec<MintParams>,
+    pub remove: Vec<ContractTokenId>,
+}
+
+/// Token data of an owner returned by `viewToken`. Starts with the encoding of `NFTData`, so callers decoding `NFTData`
+/// keep working.
+#[derive(Serialize, SchemaType)]
+pub struct TokenView {
+    pub data: NFTData,
+    pub transferable: bool,
+}
+
+/// Parameter type for the `mintWithProof` function.
+#[derive(Serialize, SchemaType)]
+pub struct MintWithProofParams {
+    /// Token to mint, same as in `mint`
+    pub mint: MintParams,
+    /// Sibling hashes on the path from the sender's leaf to the root of the allowlist
+    pub proof: Vec<[u8; 32]>,
+}
+
+/// Parameter type for the `setHeir` function.
+#[derive(Debug, Serialize, SchemaType, Clone, Copy, PartialEq, Eq)]
+pub struct InheritancePlan {
+    /// Account allowed to claim all tokens of the owner
+    pub heir: AccountAddress,
+    /// Time without owner activity after which the heir can claim the tokens
+    pub inactivity: Duration,
+}
+
+/// Inheritance plan of an owner together with the owner's last activity.
+#[derive(Debug, Serialize, SchemaType, Clone, Copy, PartialEq, Eq)]
+pub struct Inheritance {
+    pub plan: InheritancePlan,
+    /// Time of the last state-changing action of the owner
+    pub last_activity: Timestamp,
+}
+
+/// Parameter type for the `exportState` function.
+#[derive(Debug, Serialize, SchemaType)]
+pub struct ExportStateParams {
+    /// Index of the first record to export, `next` of the previous chunk
+    pub cursor: u32,
+    /// Maximum number of records to export
+    pub limit: u32,
+}
+
+/// Balance of a token held by an owner together with the token data.
+#[derive(Debug, Serialize, SchemaType, PartialEq, Eq)]
+pub struct TokenRecord {
+    pub owner: Address,
+    pub token_id: ContractTokenId,
+    /// Address of the creator
+    pub creator: Address,
+    /// Royalty percentage for creator
+    pub creator_royalty: u32,
+    /// Address of the minter
+    pub minter: Address,
+    /// Royalty percentage for minter
+    pub minter_royalty: u32,
+    /// Additional royalty recipients
+    pub royalties: RoyaltyRecipients,
+    /// Cost of NFT
+    pub price: Amount,
+    /// IPFS content identifier
+    pub cid: Vec<u8>,
+    /// Copies of NFT held by the owner
+    pub quantity: ContractTokenAmount,
+    /// Edition numbers of the copies held by the owner
+    pub editions: Vec<EditionRange>,
+    pub transferable: bool,
+}
+
+/// Single entry of the exported contract state.
+#[derive(Debug, Serialize, SchemaType, PartialEq, Eq)]
+pub enum StateRecord {
+    /// Minted token ID, including tokens that were burned completely
+    TokenId(ContractTokenId),
+    Balance(TokenRecord),
+    /// Owner followed by its operator
+    Operator(Address, Address),
+    Reservation(TokenIdReservation),
+    /// Owner followed by its inheritance plan
+    Inheritance(Address, Inheritance),
+    MintHook(MintHook),
+    /// Token that can be minted during mint phases
+    DropToken(MintParams),
+    MintPhase(MintPhase),
+    /// Start time of the phase followed by an address on its allowlist
+    Allowlisted(Timestamp, Address),
+    /// Start time of the phase, the minter and the copies it minted during the phase
+    PhaseMints(Timestamp, Address, u64),
+    /// Account followed by the nonce expected in its next `permit` message
+    Nonce(AccountAddress, u64),
+    /// Account followed by its `permit` public key
+    PermitKey(AccountAddress, [u8; 32]),
+    Config(ConfigRecord),
+}
+
+/// Contract configuration that is not changed by token operations.
+#[derive(Debug, Serialize, SchemaType, PartialEq, Eq)]
+pub struct ConfigRecord {
+    pub royalty: Percentage,
+    pub fee_receiver: ContractAddress,
+    pub sale_royalty: Percentage,
+    pub beneficiary: AccountAddress,
+    pub drop_beneficiary: AccountAddress,
+    pub metadata_base_url: String,
+    pub paused: bool,
+}
+
+/// Return type of the `exportState` function.
+#[derive(Debug, Serialize, SchemaType, PartialEq, Eq)]
+pub struct ExportStateResult {
+    pub records: Vec<StateRecord>,
+    /// Cursor of the next chunk. `None` if all records were exported
+    pub next: Option<u32>,
 }
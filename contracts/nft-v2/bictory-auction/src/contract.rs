@@ -386,6 +386,24 @@ mod tests {
         );
     }
 
+    #[concordium_test]
+    /// Test that the init parameter keeps the encoding used by deployed auctions and SDKs.
+    fn test_golden_init_parameter() {
+        let parameter = InitParameter {
+            storage_address: STORAGE,
+            token_id: token_0(),
+            expiry: Timestamp::from_timestamp_millis(1000),
+        };
+        let golden: Vec<u8> = [
+            &[1, 0, 0, 0, 0, 0, 0, 0][..],
+            &[1, 0, 0, 0, 0, 0, 0, 0],
+            &[2, 0, 1],
+            &[0xe8, 0x03, 0, 0, 0, 0, 0, 0],
+        ]
+        .concat();
+        claim_eq!(create_parameter_bytes(&parameter), golden);
+    }
+
     #[concordium_test]
     /// Test a sequence of bids and finalizations:
     /// 0. Auction is initialized.
@@ -1,14 +1,45 @@
 use super::*;
 
-/// Initialize the listing contract with an empty list of listings.
+/// Initialize the listing contract with an empty list of listings. The account initializing the contract becomes its
+/// owner.
 #[init(contract = "BictoryListing", parameter = "ContractAddress")]
 fn contract_init(ctx: &impl HasInitContext) -> InitResult<State> {
     let storage_address: ContractAddress = ctx.parameter_cursor().get()?;
     // Construct the initial contract state.
-    let state = State::new(storage_address);
+    let state = State::new(storage_address, ctx.init_origin());
     Ok(state)
 }
 
+/// Set Bictory's share of accepted offers.
+///
+/// Rejects if:
+/// - Sender is not the contract owner.
+/// - It fails to parse the parameter.
+/// - The share is over 100%.
+#[receive(
+    contract = "BictoryListing",
+    name = "setBictoryRoyalty",
+    parameter = "Percentage"
+)]
+fn contract_set_bictory_royalty<A: HasActions>(
+    ctx: &impl HasReceiveContext,
+    state: &mut State,
+) -> ContractResult<A> {
+    ensure!(
+        ctx.sender().matches_account(&state.owner),
+        ContractError::Unauthorized
+    );
+
+    let bictory_royalty: Percentage = ctx.parameter_cursor().get()?;
+    ensure!(
+        bictory_royalty <= Percentage::HUNDRED,
+        CustomContractError::InvalidRoyalty.into()
+    );
+    state.bictory_royalty = bictory_royalty;
+
+    Ok(A::accept())
+}
+
 /// List or update the price of a list of NFTs.
 ///
 /// During this operation, the contract address of this contract will be
@@ -175,13 +206,18 @@ fn contract_buy<A: HasActions>(
         ));
 
         // Calculating shares
+        let royalties = royalty_recipients(
+            nft_details.creator,
+            nft_details.creator_royalty,
+            nft_details.minter,
+            nft_details.minter_royalty,
+        );
         let mut shares = calc_shares(
             nft_details.price,
-            nft_details.creator_royalty as u64,
-            nft_details.minter_royalty as u64,
-            bictory_royalty as u64,
+            &royalties,
+            Percentage::from_micro_percent(bictory_royalty as u64),
         );
-        shares.adjust_owner_share();
+        shares.adjust_owner_share()?;
 
         // Balance Transfer
         let receive_name = ReceiveName::new_unchecked("BictoryConfig.sendCCD");
@@ -207,13 +243,11 @@ fn contract_buy<A: HasActions>(
         actions = actions.and_then(send_bictory_share);
         actions = actions.and_then(A::simple_transfer(&nft_details.owner, shares.owner));
 
-        // Creator Royalty can be `0` thereby avoiding unnecessary gas fees.
-        if nft_details.creator_royalty != 0 {
-            actions = actions.and_then(A::simple_transfer(&nft_details.creator, shares.creator));
-        }
-        // Minter Royalty can be `0` thereby avoiding unnecessary gas fees.
-        if nft_details.minter_royalty != 0 {
-            actions = actions.and_then(A::simple_transfer(&nft_details.minter, shares.minter));
+        // Royalties can be `0` thereby avoiding unnecessary gas fees.
+        for (beneficiary, share) in shares.royalties.iter() {
+            if *share > Amount::zero() {
+                actions = actions.and_then(A::simple_transfer(beneficiary, *share));
+            }
         }
 
         // Remove Operator
@@ -238,7 +272,7 @@ fn contract_buy<A: HasActions>(
             seller: nft_details.owner,
             buyer: sender,
             owner_share: shares.owner,
-            creator_share: shares.creator,
+            creator_share: shares.royalties[0].1,
         }))?;
     } else {
         return Err(CustomContractError::OperationDoesNotExist.into());
@@ -247,6 +281,194 @@ fn contract_buy<A: HasActions>(
     Ok(actions)
 }
 
+/// Change the price of a listed NFT without unlisting it. The owner is checked with the token details from storage,
+/// after which the new price is stored and a `PriceChanged` event is logged.
+///
+/// Rejects if:
+/// - Another call is in progress.
+/// - It fails to parse the parameter.
+/// - The invoker doesn't own the token.
+/// - The token is not listed.
+#[receive(
+    contract = "BictoryListing",
+    name = "updateListingPrice",
+    parameter = "UpdateListingPriceParams"
+)]
+fn contract_update_listing_price<A: HasActions>(
+    ctx: &impl HasReceiveContext,
+    state: &mut State,
+) -> ContractResult<A> {
+    // Ensure that no other call is in progress
+    ensure!(
+        state.function_states.is_none(),
+        CustomContractError::RequestInProgress.into()
+    );
+
+    let params: UpdateListingPriceParams = ctx.parameter_cursor().get()?;
+    let storage = StorageContract::new(&state.storage_address);
+    let keys = vec![OWNER.as_ref(), PRICE.as_ref(), FOR_SALE.as_ref()];
+
+    let action = storage.send_get(
+        &ctx.self_address(),
+        "BictoryListing.functionContinue",
+        <&ByteSlice>::from(params.token_id.0.as_slice()),
+        &keys,
+    );
+
+    state.function_states = Some(FunctionStates::UpdatePrice(params));
+
+    Ok(action)
+}
+
+/// Offer to buy a token, whether it's listed or not. The attached amount is escrowed until the token owner accepts the
+/// offer or the sender cancels it. Making another offer for the same token raises the escrowed amount.
+///
+/// Rejects if:
+/// - Sender is a contract address.
+/// - It fails to parse the parameter.
+/// - No amount is attached.
+#[receive(
+    contract = "BictoryListing",
+    name = "makeOffer",
+    parameter = "ContractTokenId",
+    enable_logger,
+    payable
+)]
+fn contract_make_offer<A: HasActions>(
+    ctx: &impl HasReceiveContext,
+    amount: Amount,
+    logger: &mut impl HasLogger,
+    state: &mut State,
+) -> ContractResult<A> {
+    let buyer = match ctx.sender() {
+        Address::Account(addr) => addr,
+        Address::Contract(_) => bail!(CustomContractError::OnlyAccountAddress.into()),
+    };
+    let token_id: ContractTokenId = ctx.parameter_cursor().get()?;
+
+    ensure!(
+        amount > Amount::zero(),
+        CustomContractError::InvalidFields.into()
+    );
+
+    let offer = state
+        .offers
+        .entry((token_id.clone(), buyer))
+        .or_insert_with(Amount::zero);
+    *offer = offer
+        .micro_ccd
+        .checked_add(amount.micro_ccd)
+        .map(Amount::from_micro_ccd)
+        .ok_or(CustomContractError::Overflow)?;
+
+    logger.log(&CustomEvent::OfferMade(OfferEvent {
+        token_id,
+        buyer,
+        amount: *offer,
+    }))?;
+
+    Ok(A::accept())
+}
+
+/// Cancel the offer of the sender for a token and refund the escrowed amount.
+///
+/// Rejects if:
+/// - Sender is a contract address.
+/// - It fails to parse the parameter.
+/// - The sender has no offer for the token.
+#[receive(
+    contract = "BictoryListing",
+    name = "cancelOffer",
+    parameter = "ContractTokenId",
+    enable_logger
+)]
+fn contract_cancel_offer<A: HasActions>(
+    ctx: &impl HasReceiveContext,
+    logger: &mut impl HasLogger,
+    state: &mut State,
+) -> ContractResult<A> {
+    let buyer = match ctx.sender() {
+        Address::Account(addr) => addr,
+        Address::Contract(_) => bail!(CustomContractError::OnlyAccountAddress.into()),
+    };
+    let token_id: ContractTokenId = ctx.parameter_cursor().get()?;
+
+    let amount = state
+        .offers
+        .remove(&(token_id.clone(), buyer))
+        .ok_or(CustomContractError::NotFound)?;
+
+    logger.log(&CustomEvent::OfferCanceled(OfferEvent {
+        token_id,
+        buyer,
+        amount,
+    }))?;
+
+    Ok(A::simple_transfer(&buyer, amount))
+}
+
+/// Accept an offer for a token owned by the invoker. The owner is checked with the token details from storage, after
+/// which the token is given to the buyer and the escrowed amount is split between the owner, royalty receivers and
+/// Bictory the same way as with `buy`.
+///
+/// Rejects if:
+/// - Another call is in progress.
+/// - It fails to parse the parameter.
+/// - The buyer has no offer for the token.
+/// - The offer doesn't match the expected amount.
+/// - The invoker doesn't own the token.
+#[receive(
+    contract = "BictoryListing",
+    name = "acceptOffer",
+    parameter = "AcceptOfferParams"
+)]
+fn contract_accept_offer<A: HasActions>(
+    ctx: &impl HasReceiveContext,
+    state: &mut State,
+) -> ContractResult<A> {
+    // Ensure that no other call is in progress
+    ensure!(
+        state.function_states.is_none(),
+        CustomContractError::RequestInProgress.into()
+    );
+
+    let params: AcceptOfferParams = ctx.parameter_cursor().get()?;
+    let storage = StorageContract::new(&state.storage_address);
+
+    let price = state
+        .offers
+        .remove(&(params.token_id.clone(), params.buyer))
+        .ok_or(CustomContractError::NotFound)?;
+    // Buyer could have raised the offer in between, so the accepted amount must be stated explicitly
+    ensure!(
+        price == params.expected_amount,
+        CustomContractError::InvalidFields.into()
+    );
+
+    let keys = vec![
+        OWNER.as_ref(),
+        CREATOR.as_ref(),
+        CREATOR_ROYALTY.as_ref(),
+        MINTER.as_ref(),
+        MINTER_ROYALTY.as_ref(),
+    ];
+
+    let action = storage.send_get(
+        &ctx.self_address(),
+        "BictoryListing.functionContinue",
+        <&ByteSlice>::from(params.token_id.0.as_slice()),
+        &keys,
+    );
+
+    state.function_states = Some(FunctionStates::AcceptOffer(AcceptedOffer {
+        token_id: params.token_id,
+        buyer: params.buyer,
+        price,
+    }));
+
+    Ok(action)
+}
+
 #[receive(
     contract = "BictoryListing",
     name = "functionContinue",
@@ -343,9 +565,119 @@ fn contract_continue<A: HasActions>(
 
             Ok(action)
         }
+        FunctionStates::UpdatePrice(update) => {
+            let owner: AccountAddress = params.get(OWNER)?;
+            let old_price: Amount = params.get(PRICE)?;
+            let for_sale: bool = params.get(FOR_SALE).unwrap_or(false);
+
+            // Ensuring only owner of NFT can change the price.
+            ensure!(
+                ctx.invoker().eq(&owner),
+                CustomContractError::OnlyOwner.into()
+            );
+
+            // Ensuring Token is listed for sale
+            ensure!(for_sale, CustomContractError::TokenNotListedForSale.into());
+
+            let action = storage.send_set(
+                <&ByteSlice>::from(update.token_id.0.as_slice()),
+                &[StorageEntryRef::new(PRICE, &update.price)],
+            );
+
+            // Event for changing the price.
+            logger.log(&CustomEvent::PriceChanged(PriceChangedEvent {
+                token_id: update.token_id,
+                owner,
+                old_price,
+                new_price: update.price,
+            }))?;
+
+            Ok(action)
+        }
+        FunctionStates::AcceptOffer(offer) => {
+            let owner: AccountAddress = params.get(OWNER)?;
+            let creator: AccountAddress = params.get(CREATOR)?;
+            let creator_royalty: u32 = params.get(CREATOR_ROYALTY)?;
+            let minter: AccountAddress = params.get(MINTER)?;
+            let minter_royalty: u32 = params.get(MINTER_ROYALTY)?;
+
+            // Ensuring only owner of NFT can accept offers.
+            ensure!(
+                ctx.invoker().eq(&owner),
+                CustomContractError::OnlyOwner.into()
+            );
+            ensure!(offer.buyer.ne(&owner), ContractError::Unauthorized);
+
+            // Sold token is no longer for sale
+            let for_sale = false;
+            let mut actions = storage.send_set(
+                <&ByteSlice>::from(offer.token_id.0.as_slice()),
+                &[
+                    StorageEntryRef::new(FOR_SALE, &for_sale),
+                    StorageEntryRef::new(OWNER, &offer.buyer),
+                ],
+            );
+
+            // Calculating shares
+            let royalties = royalty_recipients(creator, creator_royalty, minter, minter_royalty);
+            let mut shares = calc_shares(offer.price, &royalties, state.bictory_royalty);
+            shares.adjust_owner_share()?;
+
+            // Balance Transfer. Same config contract as in `buy`.
+            let receive_name = ReceiveName::new_unchecked("BictoryConfig.sendCCD");
+            let bictory_config_contract_address = ContractAddress {
+                index: 1902,
+                subindex: 0,
+            };
+            actions = actions.and_then(send(
+                &bictory_config_contract_address,
+                receive_name,
+                shares.bictory,
+                &state.bictory_royalty,
+            ));
+            actions = actions.and_then(A::simple_transfer(&owner, shares.owner));
+
+            // Royalties can be `0` thereby avoiding unnecessary gas fees.
+            for (beneficiary, share) in shares.royalties.iter() {
+                if *share > Amount::zero() {
+                    actions = actions.and_then(A::simple_transfer(beneficiary, *share));
+                }
+            }
+
+            // Event for buying NFT.
+            logger.log(&CustomEvent::Buy(BuyEvent {
+                for_sale,
+                token_id: offer.token_id,
+                seller: owner,
+                buyer: offer.buyer,
+                owner_share: shares.owner,
+                creator_share: shares.royalties[0].1,
+            }))?;
+
+            Ok(actions)
+        }
     }
 }
 
+/// Royalties of the creator and minter recorded in the storage contract, with the creator first.
+fn royalty_recipients(
+    creator: AccountAddress,
+    creator_royalty: u32,
+    minter: AccountAddress,
+    minter_royalty: u32,
+) -> [Royalty; 2] {
+    [
+        Royalty {
+            beneficiary: creator,
+            percentage: Percentage::from_micro_percent(creator_royalty as u64),
+        },
+        Royalty {
+            beneficiary: minter,
+            percentage: Percentage::from_micro_percent(minter_royalty as u64),
+        },
+    ]
+}
+
 #[receive(
     contract = "BictoryListing",
     name = "updateOperator",
@@ -32,6 +32,29 @@ pub struct ListingEvent {
     pub nft_details: NFTDetails,
 }
 
+/// An untagged event of an offer made or canceled.
+#[derive(Debug, Serialize, SchemaType)]
+pub struct OfferEvent {
+    /// Token the offer is made for.
+    pub token_id: ContractTokenId,
+    /// Account making the offer.
+    pub buyer: AccountAddress,
+    /// Total escrowed amount of the offer, or the refunded amount on cancellation.
+    pub amount: Amount,
+}
+
+/// An untagged event of the price of a listed token being changed.
+#[derive(Debug, Serialize, SchemaType)]
+pub struct PriceChangedEvent {
+    pub token_id: ContractTokenId,
+    /// Owner that listed the token.
+    pub owner: AccountAddress,
+    /// Price before the change.
+    pub old_price: Amount,
+    /// Price after the change.
+    pub new_price: Amount,
+}
+
 /// Tagged Custom event to be serialized for the event log.
 #[derive(Debug)]
 pub enum CustomEvent {
@@ -41,6 +64,12 @@ pub enum CustomEvent {
     Buy(BuyEvent),
     /// Listing NFT
     Listing(ListingEvent),
+    /// Offer was made or raised
+    OfferMade(OfferEvent),
+    /// Offer was canceled and refunded
+    OfferCanceled(OfferEvent),
+    /// Price of a listed NFT was changed
+    PriceChanged(PriceChangedEvent),
 }
 
 impl Serial for CustomEvent {
@@ -58,6 +87,18 @@ impl Serial for CustomEvent {
                 out.write_u8(LISTING_TAG)?;
                 event.serial(out)
             }
+            CustomEvent::OfferMade(event) => {
+                out.write_u8(OFFER_MADE_TAG)?;
+                event.serial(out)
+            }
+            CustomEvent::OfferCanceled(event) => {
+                out.write_u8(OFFER_CANCELED_TAG)?;
+                event.serial(out)
+            }
+            CustomEvent::PriceChanged(event) => {
+                out.write_u8(PRICE_CHANGED_TAG)?;
+                event.serial(out)
+            }
         }
     }
 }
@@ -69,6 +110,9 @@ impl Deserial for CustomEvent {
             UNLISTING_TAG => UnlistingEvent::deserial(source).map(CustomEvent::Unlisting),
             BUY_TAG => BuyEvent::deserial(source).map(CustomEvent::Buy),
             LISTING_TAG => ListingEvent::deserial(source).map(CustomEvent::Listing),
+            OFFER_MADE_TAG => OfferEvent::deserial(source).map(CustomEvent::OfferMade),
+            OFFER_CANCELED_TAG => OfferEvent::deserial(source).map(CustomEvent::OfferCanceled),
+            PRICE_CHANGED_TAG => PriceChangedEvent::deserial(source).map(CustomEvent::PriceChanged),
             _ => Err(ParseError::default()),
         }
     }
@@ -3,21 +3,18 @@ use super::*;
 // Functions for creating and updating the contract state.
 impl State {
     /// Creates a new state with no listings.
-    pub fn new(storage_address: ContractAddress) -> Self {
+    pub fn new(storage_address: ContractAddress, owner: AccountAddress) -> Self {
         State {
             storage_address,
             function_states: None,
             leaf_contract_address: None,
+            offers: BTreeMap::new(),
+            owner,
+            bictory_royalty: Percentage::ZERO,
         }
     }
 }
 
-impl Shares {
-    pub fn adjust_owner_share(&mut self) {
-        self.owner -= self.creator + self.minter + self.bictory
-    }
-}
-
 impl Default for NFTDetails {
     fn default() -> Self {
         let default_acc_addr = AccountAddress([0; 32]);
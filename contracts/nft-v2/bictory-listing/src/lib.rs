@@ -1,12 +1,11 @@
 //! It exposes a function for listing NFTs and a function for buying
 //! one of the listed NFTs.
 #![cfg_attr(not(feature = "std"), no_std)]
-use crate::{calculations::*, events::*, structs::*};
+use crate::{events::*, structs::*};
 use commons::*;
 use concordium_cis1::*;
-use concordium_std::*;
+use concordium_std::{collections::BTreeMap, *};
 
-mod calculations;
 mod contract;
 mod events;
 mod impls;
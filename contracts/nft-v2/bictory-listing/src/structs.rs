@@ -10,12 +10,48 @@ pub struct State {
     pub function_states: Option<FunctionStates>,
     /// The contract address of listing token
     pub leaf_contract_address: Option<ContractAddress>,
+    /// CCD escrowed by accounts offering to buy a token, which doesn't have to be listed
+    #[concordium(size_length = 2)]
+    pub offers: BTreeMap<(ContractTokenId, AccountAddress), Amount>,
+    /// Account that initialized the contract and can configure it
+    pub owner: AccountAddress,
+    /// Bictory's share of accepted offers
+    pub bictory_royalty: Percentage,
 }
 
 #[derive(Serialize, SchemaType, Clone)]
 pub enum FunctionStates {
     List(NFTDetails),
     UnList(ContractTokenId),
+    AcceptOffer(AcceptedOffer),
+    UpdatePrice(UpdateListingPriceParams),
+}
+
+/// Type of the parameter to the `updateListingPrice` function.
+#[derive(Debug, Serialize, SchemaType, Clone)]
+pub struct UpdateListingPriceParams {
+    pub token_id: ContractTokenId,
+    /// New price of the listed token
+    pub price: Amount,
+}
+
+/// Offer accepted by the invoker, waiting for the token details from storage.
+#[derive(Debug, Serialize, SchemaType, Clone)]
+pub struct AcceptedOffer {
+    pub token_id: ContractTokenId,
+    pub buyer: AccountAddress,
+    /// Escrowed amount of the offer
+    pub price: Amount,
+}
+
+/// Type of the parameter to the `acceptOffer` function.
+#[derive(Debug, Serialize, SchemaType)]
+pub struct AcceptOfferParams {
+    pub token_id: ContractTokenId,
+    /// Account whose offer is accepted
+    pub buyer: AccountAddress,
+    /// Escrowed amount of the offer, which must match the stored offer
+    pub expected_amount: Amount,
 }
 
 #[derive(Debug, SchemaType, Serialize, Clone)]
@@ -29,11 +65,3 @@ pub struct NFTDetails {
     pub price: Amount,
     pub for_sale: bool,
 }
-
-#[derive(SchemaType, Serialize, Eq, PartialEq, PartialOrd, Ord, Debug)]
-pub struct Shares {
-    pub creator: Amount,
-    pub minter: Amount,
-    pub owner: Amount,
-    pub bictory: Amount,
-}
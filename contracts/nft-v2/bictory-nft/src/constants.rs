@@ -5,3 +5,6 @@
 /// The baseurl for the token metadata, gets appended with the token ID as hex
 /// encoding before emitted in the TokenMetadata event.
 pub const TOKEN_METADATA_BASE_URL: &str = "https://nft.bictory.io/metadata/";
+
+/// Time after which a pending request can be canceled if storage didn't respond.
+pub const REQUEST_TIMEOUT: Duration = Duration::from_millis(10 * 60 * 1000);
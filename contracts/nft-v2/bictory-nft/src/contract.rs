@@ -30,12 +30,6 @@ fn contract_mint<A: HasActions>(
     ctx: &impl HasReceiveContext,
     state: &mut State,
 ) -> ContractResult<A> {
-    // Ensure that no other call is in progress
-    ensure!(
-        state.function_state.is_none(),
-        CustomContractError::RequestInProgress.into()
-    );
-
     let storage = StorageContract::new(&state.storage_address);
 
     // Parse the parameter.
@@ -72,7 +66,7 @@ fn contract_mint<A: HasActions>(
         mint_state.push(mint_data);
     }
 
-    state.function_state = Some(FunctionState::Mint(mint_state));
+    state.start_request(ctx, FunctionState::Mint(mint_state))?;
 
     Ok(actions)
 }
@@ -89,12 +83,11 @@ fn contract_continue<A: HasActions>(
 
     let storage = StorageContract::new(&state.storage_address);
 
-    let function_state = state
-        .function_state
-        .take()
-        .ok_or(CustomContractError::NoRequestInProgress)?;
+    // Both storage responses start with the prefix of the requested entry
+    let prefix: Bytes = ctx.parameter_cursor().get()?;
+    let (id, request) = state.take_request(&prefix.0, &ctx.sender())?;
 
-    match function_state {
+    match request.function_state {
         FunctionState::Mint(mut mint_state) => {
             let params = StorageFindResponse::deserial(&mut ctx.parameter_cursor())?;
 
@@ -109,11 +102,16 @@ fn contract_continue<A: HasActions>(
             {
                 let token = mint_state.remove(idx);
 
-                // If all mints handled, clear function_state
-                if mint_state.is_empty() {
-                    state.function_state = None;
-                } else {
-                    state.function_state = Some(FunctionState::Mint(mint_state));
+                // Keep the request until all mints are handled
+                if !mint_state.is_empty() {
+                    let function_state = FunctionState::Mint(mint_state);
+                    state.requests.insert(
+                        id,
+                        PendingRequest {
+                            function_state,
+                            ..request
+                        },
+                    );
                 }
 
                 token
@@ -160,11 +158,16 @@ fn contract_continue<A: HasActions>(
                 .position(|transfer| transfer.token_id.0 == params.prefix.0)
             {
                 let transfer = transfer_state.remove(idx);
-                // If all transfers handled, clear function_state
-                if transfer_state.is_empty() {
-                    state.function_state = None;
-                } else {
-                    state.function_state = Some(FunctionState::Transfer(transfer_state));
+                // Keep the request until all transfers are handled
+                if !transfer_state.is_empty() {
+                    let function_state = FunctionState::Transfer(transfer_state);
+                    state.requests.insert(
+                        id,
+                        PendingRequest {
+                            function_state,
+                            ..request
+                        },
+                    );
                 }
                 transfer
             } else {
@@ -205,24 +208,16 @@ fn contract_continue<A: HasActions>(
                 &[StorageEntryRef::new(OWNER, &to_address)],
             ));
 
-            // FIXME: ALL operators must be removed on token transfer. Otherwise malicious contract controlled by
-            // previous owner may be kept as a token operator! This requires storage API change
             // TODO: Leaf contract also requires proper iterface. See `StorageContract`
-            // Update leaf contract rights
+            // Replace leaf contract rights, so no operator of the previous owner can keep modifying the token
             actions = actions.and_then(send(
                 &leaf,
-                ReceiveName::new_unchecked("BictoryStorage.updateOperator"),
+                ReceiveName::new_unchecked("BictoryStorage.replaceOperators"),
                 Amount::zero(),
-                &UpdateOperatorParams(vec![
-                    UpdateOperator {
-                        update: OperatorUpdate::Add,
-                        operator: to_address,
-                    },
-                    UpdateOperator {
-                        update: OperatorUpdate::Remove,
-                        operator: transfer.from,
-                    },
-                ]),
+                &ReplaceOperatorsParams {
+                    prefix: Bytes(transfer.token_id.0.clone()),
+                    operators: vec![to_address],
+                },
             ));
 
             // Log transfer event
@@ -260,12 +255,20 @@ fn contract_continue<A: HasActions>(
                 &[StorageEntryRef::new(PRICE, &price_update.price)],
             ))
         }
-        FunctionState::Burn(step) => {
+        FunctionState::Burn(step, mut burn_state) => {
             match step {
-                BurnStep::Find(token_id) => {
+                BurnStep::Find => {
                     let params = StorageFindResponse::deserial(&mut ctx.parameter_cursor())?;
                     if let Some(leaf) = params.contract {
-                        state.function_state = Some(FunctionState::Burn(BurnStep::GetInfo(leaf)));
+                        let function_state =
+                            FunctionState::Burn(BurnStep::GetInfo(leaf), burn_state);
+                        state.requests.insert(
+                            id,
+                            PendingRequest {
+                                function_state,
+                                ..request
+                            },
+                        );
                         Ok(send(
                             &leaf,
                             ReceiveName::new_unchecked("BictoryStorage.get"),
@@ -275,43 +278,132 @@ fn contract_continue<A: HasActions>(
                                 result_function: OwnedReceiveName::new_unchecked(String::from(
                                     "BictoryNFT.functionContinue",
                                 )),
-                                prefix: Bytes(token_id.0),
-                                keys: vec![
-                                    Bytes(OWNER.as_bytes().to_vec()),
-                                    Bytes(PRICE.as_bytes().to_vec()),
-                                ],
+                                prefix: params.prefix,
+                                keys: vec![Bytes(OWNER.as_bytes().to_vec())],
                             },
                         ))
                     } else {
                         return Err(CustomContractError::UnknownToken.into());
                     }
                 }
-                BurnStep::GetInfo(_) => {
+                BurnStep::GetInfo(leaf) => {
                     let params = StorageGetResponse::deserial(&mut ctx.parameter_cursor())?;
-                    let owner = params.get(OWNER)?;
-                    let price = params.get(PRICE)?;
-
-                    // FIXME: Interact with leaf instead of root
-                    // FIXME: Remove all operators on the leaf to prevent updating NFT data after burn
-                    let actions = storage.send_unset(&params.prefix, &[<&ByteSlice>::from(OWNER)]);
+                    let owner: Address = params.get(OWNER)?;
 
-                    // Log Burn event
-                    logger.log(&Cis1Event::Burn(BurnEvent {
-                        token_id: TokenIdVec(params.prefix.0.clone()),
-                        owner,
-                        amount: price,
-                    }))?;
+                    let burn = if let Some(idx) = burn_state
+                        .iter()
+                        .position(|burn| burn.token_id.0 == params.prefix.0)
+                    {
+                        burn_state.remove(idx)
+                    } else {
+                        return Err(CustomContractError::UnknownToken.into());
+                    };
 
-                    // Metadata URL for the NFT.
-                    logger.log(&token_metadata_event(TokenIdVec(params.prefix.0)))?;
+                    // Token must still be owned by the owner given in the burn
+                    ensure!(owner == burn.owner, ContractError::Unauthorized);
+
+                    if owner == Address::Account(request.invoker) {
+                        // Continue with the remaining burns, if any
+                        if !burn_state.is_empty() {
+                            let function_state = FunctionState::Burn(BurnStep::Find, burn_state);
+                            state.requests.insert(
+                                id,
+                                PendingRequest {
+                                    function_state,
+                                    ..request
+                                },
+                            );
+                        }
+                        return finish_burn(&leaf, burn, None, logger);
+                    }
 
-                    Ok(actions)
+                    // Operators of the owner are only known by the leaf storage
+                    let query = OperatorOfQuery {
+                        owner,
+                        address: Address::Account(request.invoker),
+                    };
+                    burn_state.insert(0, burn);
+                    let function_state =
+                        FunctionState::Burn(BurnStep::CheckOperator(leaf), burn_state);
+                    state.requests.insert(
+                        id,
+                        PendingRequest {
+                            function_state,
+                            ..request
+                        },
+                    );
+                    Ok(send(
+                        &leaf,
+                        ReceiveName::new_unchecked("BictoryStorage.operatorOf"),
+                        Amount::zero(),
+                        &OperatorOfQueryParams {
+                            queries: vec![query],
+                            result_contract: ctx.self_address(),
+                            result_function: OwnedReceiveName::new_unchecked(String::from(
+                                "BictoryNFT.operatorContinue",
+                            )),
+                        },
+                    ))
                 }
+                // Operator responses are handled by `operatorContinue`
+                BurnStep::CheckOperator(_) => Err(CustomContractError::NoRequestInProgress.into()),
             }
         }
+        FunctionState::OwnerOf(step, query) => match step {
+            OwnerOfStep::Find => {
+                let params = StorageFindResponse::deserial(&mut ctx.parameter_cursor())?;
+                match params.contract {
+                    Some(leaf) => {
+                        let prefix = Bytes(query.token_id.0.clone());
+                        let function_state =
+                            FunctionState::OwnerOf(OwnerOfStep::GetInfo(leaf), query);
+                        state.requests.insert(
+                            id,
+                            PendingRequest {
+                                function_state,
+                                ..request
+                            },
+                        );
+                        Ok(send(
+                            &leaf,
+                            ReceiveName::new_unchecked("BictoryStorage.get"),
+                            Amount::zero(),
+                            &StorageGetParams {
+                                result_contract: ctx.self_address(),
+                                result_function: OwnedReceiveName::new_unchecked(String::from(
+                                    "BictoryNFT.functionContinue",
+                                )),
+                                prefix,
+                                keys: vec![Bytes(OWNER.as_bytes().to_vec())],
+                            },
+                        ))
+                    }
+                    // Token was never minted
+                    None => Ok(send_owner_of_response(query, None)),
+                }
+            }
+            OwnerOfStep::GetInfo(_) => {
+                let params = StorageGetResponse::deserial(&mut ctx.parameter_cursor())?;
+                // Owner entry is removed when the token is burnt
+                let owner = params.get(OWNER).ok();
+                Ok(send_owner_of_response(query, owner))
+            }
+        },
     }
 }
 
+fn send_owner_of_response<A: HasActions>(query: OwnerOfParams, owner: Option<Address>) -> A {
+    send(
+        &query.result_contract,
+        query.result_function.as_ref(),
+        Amount::zero(),
+        &OwnerOfResponse {
+            token_id: query.token_id,
+            owner,
+        },
+    )
+}
+
 /// Execute a list of token transfers, in the order of the list.
 ///
 /// Logs a `Transfer` event for each transfer in the list.
@@ -337,11 +429,6 @@ fn contract_transfer<A: HasActions>(
     ctx: &impl HasReceiveContext,
     state: &mut State,
 ) -> ContractResult<A> {
-    ensure!(
-        state.function_state.is_none(),
-        CustomContractError::RequestInProgress.into()
-    );
-
     let storage = StorageContract::new(&state.storage_address);
 
     // Parse the parameter.
@@ -363,7 +450,7 @@ fn contract_transfer<A: HasActions>(
         ));
     }
 
-    state.function_state = Some(FunctionState::Transfer(transfers));
+    state.start_request(ctx, FunctionState::Transfer(transfers))?;
 
     Ok(actions)
 }
@@ -479,46 +566,313 @@ fn contract_transfer<A: HasActions>(
 //     ))
 // }
 
+/// Look up the owner of the token in storage and send it to the given contract function, so that other contracts
+/// don't have to know the storage key layout.
+///
+/// It rejects if:
+/// - It fails to parse the parameter.
+/// - Another request for the token is in progress.
+/// - Message sent back with the result rejects.
+#[receive(contract = "BictoryNFT", name = "ownerOf", parameter = "OwnerOfParams")]
+fn contract_owner_of<A: HasActions>(
+    ctx: &impl HasReceiveContext,
+    state: &mut State,
+) -> ContractResult<A> {
+    let storage = StorageContract::new(&state.storage_address);
+
+    // Parse the parameter.
+    let params: OwnerOfParams = ctx.parameter_cursor().get()?;
+
+    let action = storage.send_find(
+        &ctx.self_address(),
+        "BictoryNFT.functionContinue",
+        <&ByteSlice>::from(params.token_id.0.as_slice()),
+    );
+    state.start_request(ctx, FunctionState::OwnerOf(OwnerOfStep::Find, params))?;
+
+    Ok(action)
+}
+
+/// Drop a request the storage didn't respond to, unlocking its tokens for new requests.
+/// Can only be called by the account that started the request or the contract owner, once `REQUEST_TIMEOUT` has
+/// passed since the request was started.
+///
+/// It rejects if:
+/// - It fails to parse the parameter.
+/// - The request does not exist.
+/// - The sender is neither the request invoker nor the contract owner.
+/// - The timeout has not passed yet.
+#[receive(
+    contract = "BictoryNFT",
+    name = "cancelRequest",
+    parameter = "RequestId"
+)]
+fn contract_cancel_request<A: HasActions>(
+    ctx: &impl HasReceiveContext,
+    state: &mut State,
+) -> ContractResult<A> {
+    // Parse the parameter.
+    let id: RequestId = ctx.parameter_cursor().get()?;
+
+    let request = state
+        .requests
+        .get(&id)
+        .ok_or(CustomContractError::NoRequestInProgress)?;
+
+    let sender = ctx.sender();
+    ensure!(
+        sender.matches_account(&request.invoker) || sender.matches_account(&ctx.owner()),
+        ContractError::Unauthorized
+    );
+
+    let timeout_at = request
+        .created_at
+        .checked_add(REQUEST_TIMEOUT)
+        .ok_or(CustomContractError::Overflow)?;
+    ensure!(
+        ctx.metadata().slot_time() >= timeout_at,
+        CustomContractError::OperationNotPermitted.into()
+    );
+
+    state.requests.remove(&id);
+
+    Ok(A::accept())
+}
+
+/// Send the list of pending requests to the given contract function.
+///
+/// It rejects if:
+/// - It fails to parse the parameter.
+/// - Message sent back with the result rejects.
+#[receive(
+    contract = "BictoryNFT",
+    name = "viewPendingRequests",
+    parameter = "ViewPendingRequestsParams"
+)]
+fn contract_view_pending_requests<A: HasActions>(
+    ctx: &impl HasReceiveContext,
+    state: &mut State,
+) -> ContractResult<A> {
+    // Parse the parameter.
+    let params: ViewPendingRequestsParams = ctx.parameter_cursor().get()?;
+
+    let requests: Vec<PendingRequestInfo> = state
+        .requests
+        .iter()
+        .map(|(id, request)| PendingRequestInfo {
+            id: *id,
+            invoker: request.invoker,
+            created_at: request.created_at,
+            token_ids: request
+                .function_state
+                .token_ids()
+                .into_iter()
+                .cloned()
+                .collect(),
+        })
+        .collect();
+
+    Ok(send(
+        &params.result_contract,
+        params.result_function.as_ref(),
+        Amount::zero(),
+        &requests,
+    ))
+}
+
 /// NFT Burn Functionality.
-/// Can only be called by token owner.
-/// Logs a `Burn` and a `TokenMetadata` event for each token.
+/// Can only be called by token owner or an operator of the owner. Operators are tracked by the leaf storage of the
+/// token, which is asked in `functionContinue` when the invoker is not the owner.
+/// Logs a `Burn` and a `TokenMetadata` event for each token, once the owner is confirmed by the storage.
 /// The url for the token metadata is the token ID encoded in hex, appended on
 /// the `TOKEN_METADATA_BASE_URL`.
 ///
 /// It rejects if:
-/// - The sender is not the token owner.
+/// - Another request for any of the tokens is in progress.
+/// - The invoker is not the token owner or an operator of the owner.
 /// - Fails to parse parameter.
+/// - The quantity is more than 1.
 /// - Tokens fails to be upated, which could be if:
 ///     - The minted token ID does not exist.
 ///     - Fails to log Burn event
 ///     - Fails to log TokenMetadata event
-#[receive(contract = "BictoryNFT", name = "burn", parameter = "ContractTokenId")]
+#[receive(contract = "BictoryNFT", name = "burn", parameter = "BurnParams")]
 fn contract_burn<A: HasActions>(
     ctx: &impl HasReceiveContext,
     state: &mut State,
 ) -> ContractResult<A> {
-    ensure!(
-        state.function_state.is_none(),
-        CustomContractError::RequestInProgress.into()
-    );
+    // Parse the parameter.
+    let params: BurnParams = ctx.parameter_cursor().get()?;
 
+    burn_tokens(ctx, state, vec![params])
+}
+
+/// Burn a list of tokens, in the order of the list, same as `burn`.
+///
+/// It rejects if:
+/// - Fails to parse parameter.
+/// - Any of the burns fails, same as in `burn`.
+#[receive(
+    contract = "BictoryNFT",
+    name = "burnMany",
+    parameter = "Vec<BurnParams>"
+)]
+fn contract_burn_many<A: HasActions>(
+    ctx: &impl HasReceiveContext,
+    state: &mut State,
+) -> ContractResult<A> {
     // Parse the parameter.
-    let token_id: ContractTokenId = ctx.parameter_cursor().get()?;
+    let burns: Vec<BurnParams> = ctx.parameter_cursor().get()?;
 
-    state.function_state = Some(FunctionState::Burn(BurnStep::Find(token_id.clone())));
+    burn_tokens(ctx, state, burns)
+}
 
-    Ok(send(
-        &state.storage_address,
-        ReceiveName::new_unchecked("BictoryStorage.get"),
-        Amount::zero(),
-        &StorageFindParams {
-            result_contract: ctx.self_address(),
-            result_function: OwnedReceiveName::new_unchecked(
-                "BictoryNFT.functionContinue".to_string(),
-            ),
-            prefix: Bytes(token_id.0),
-        },
-    ))
+/// Finish a burn requested by the owner or an operator of the owner, once the leaf storage has confirmed the
+/// operator in `BurnStep::CheckOperator`.
+///
+/// It rejects if:
+/// - The sender is not a contract.
+/// - Fails to parse parameter.
+/// - No burn is waiting for an operator response of the sender.
+/// - The invoker of the burn is not an operator of the owner.
+#[receive(
+    contract = "BictoryNFT",
+    name = "operatorContinue",
+    parameter = "OperatorOfQueryResponse",
+    enable_logger
+)]
+fn contract_operator_continue<A: HasActions>(
+    ctx: &impl HasReceiveContext,
+    logger: &mut impl HasLogger,
+    state: &mut State,
+) -> ContractResult<A> {
+    let leaf = match ctx.sender() {
+        Address::Contract(leaf) => leaf,
+        Address::Account(_) => return Err(CustomContractError::ContractOnly.into()),
+    };
+
+    let OperatorOfQueryResponse(response) = ctx.parameter_cursor().get()?;
+    let (query, is_operator) = response
+        .into_iter()
+        .next()
+        .ok_or(CustomContractError::ParseParams)?;
+
+    let id = state
+        .requests
+        .iter()
+        .find(|(_, request)| match &request.function_state {
+            FunctionState::Burn(BurnStep::CheckOperator(expected), burns) => {
+                *expected == leaf
+                    && query.address == Address::Account(request.invoker)
+                    && burns.first().map(|burn| burn.owner) == Some(query.owner)
+            }
+            _ => false,
+        })
+        .map(|(id, _)| *id)
+        .ok_or(CustomContractError::NoRequestInProgress)?;
+    let request = state
+        .requests
+        .remove(&id)
+        .ok_or(CustomContractError::NoRequestInProgress)?;
+
+    ensure!(is_operator, ContractError::Unauthorized);
+
+    let mut burn_state = match request.function_state {
+        FunctionState::Burn(_, burns) => burns,
+        _ => return Err(CustomContractError::NoRequestInProgress.into()),
+    };
+    let burn = burn_state.remove(0);
+
+    // Continue with the remaining burns, if any
+    if !burn_state.is_empty() {
+        let function_state = FunctionState::Burn(BurnStep::Find, burn_state);
+        state.requests.insert(
+            id,
+            PendingRequest {
+                function_state,
+                ..request
+            },
+        );
+    }
+
+    finish_burn(&leaf, burn, Some(query.address), logger)
+}
+
+/// Look up the burned tokens in storage. Burns are finished in `functionContinue`, or in `operatorContinue` when the
+/// invoker is not the owner.
+fn burn_tokens<A: HasActions>(
+    ctx: &impl HasReceiveContext,
+    state: &mut State,
+    burns: Vec<BurnParams>,
+) -> ContractResult<A> {
+    let storage = StorageContract::new(&state.storage_address);
+
+    let mut actions = A::accept();
+    let self_address = ctx.self_address();
+
+    for burn in &burns {
+        // Since this contract only contains NFTs, no one will have an amount greater than 1.
+        ensure!(burn.quantity.0 <= 1, ContractError::InsufficientFunds);
+
+        actions = actions.and_then(storage.send_find(
+            &self_address,
+            "BictoryNFT.functionContinue",
+            <&ByteSlice>::from(burn.token_id.0.as_slice()),
+        ));
+    }
+
+    state.start_request(ctx, FunctionState::Burn(BurnStep::Find, burns))?;
+
+    Ok(actions)
+}
+
+/// Unset the owner of a burned token on its leaf storage and remove the owner and the burning operator, if any, from
+/// the leaf operators, so they can no longer update the token data. Logs a `Burn` and a `TokenMetadata` event.
+fn finish_burn<A: HasActions>(
+    leaf: &ContractAddress,
+    burn: BurnParams,
+    operator: Option<Address>,
+    logger: &mut impl HasLogger,
+) -> ContractResult<A> {
+    // A zero burn does not modify the state.
+    let actions = if burn.quantity.0 == 0 {
+        A::accept()
+    } else {
+        let mut operators = vec![UpdateOperator {
+            update: OperatorUpdate::Remove,
+            operator: burn.owner,
+        }];
+        if let Some(operator) = operator {
+            operators.push(UpdateOperator {
+                update: OperatorUpdate::Remove,
+                operator,
+            });
+        }
+
+        StorageContract::new(leaf)
+            .send_unset(
+                &Bytes(burn.token_id.0.clone()),
+                &[<&ByteSlice>::from(OWNER)],
+            )
+            .and_then(send(
+                leaf,
+                ReceiveName::new_unchecked("BictoryStorage.updateOperator"),
+                Amount::zero(),
+                &UpdateOperatorParams(operators),
+            ))
+    };
+
+    // Log Burn event
+    logger.log(&Cis1Event::Burn(BurnEvent {
+        token_id: burn.token_id.clone(),
+        owner: burn.owner,
+        amount: burn.quantity.0,
+    }))?;
+
+    // Metadata URL for the NFT.
+    logger.log(&token_metadata_event(burn.token_id))?;
+
+    Ok(actions)
 }
 
 /// NFT Update Price Functionality.
@@ -543,12 +897,12 @@ fn contract_update_price<A: HasActions>(
     ctx: &impl HasReceiveContext,
     state: &mut State,
 ) -> ContractResult<A> {
+    // Parse the parameter.
+    let params: UpdatePriceParameter = ctx.parameter_cursor().get()?;
     ensure!(
-        state.function_state.is_none(),
+        !state.is_pending(&params.token_id.0),
         CustomContractError::RequestInProgress.into()
     );
-    // Parse the parameter.
-    let params: UpdatePriceParameter = ctx.parameter_cursor().get()?;
 
     Ok(send(
         &state.storage_address,
@@ -564,6 +918,399 @@ fn contract_update_price<A: HasActions>(
     ))
 }
 
+#[concordium_cfg_test]
+mod continuation_tests {
+    use super::*;
+    use test_infrastructure::*;
+
+    const ACCOUNT_0: AccountAddress = AccountAddress([0u8; 32]);
+    const ADDRESS_0: Address = Address::Account(ACCOUNT_0);
+    const ACCOUNT_1: AccountAddress = AccountAddress([1u8; 32]);
+    const ADDRESS_1: Address = Address::Account(ACCOUNT_1);
+    const SELF_ADDRESS: ContractAddress = ContractAddress {
+        index: 0,
+        subindex: 0,
+    };
+    const STORAGE_ADDRESS: ContractAddress = ContractAddress {
+        index: 1,
+        subindex: 0,
+    };
+    const LEAF_ADDRESS: ContractAddress = ContractAddress {
+        index: 2,
+        subindex: 0,
+    };
+
+    const RESULT_ADDRESS: ContractAddress = ContractAddress {
+        index: 3,
+        subindex: 0,
+    };
+
+    fn token_0() -> ContractTokenId {
+        TokenIdVec(vec![0, 1])
+    }
+    fn token_1() -> ContractTokenId {
+        TokenIdVec(vec![42, 84, 168])
+    }
+
+    /// Context of the account starting a request at the given slot time.
+    fn invoker_ctx<'a>(invoker: AccountAddress, slot_time: u64) -> ReceiveContextTest<'a> {
+        let mut ctx = ReceiveContextTest::empty();
+        ctx.set_self_address(SELF_ADDRESS);
+        ctx.set_owner(ACCOUNT_0);
+        ctx.set_invoker(invoker);
+        ctx.set_sender(Address::Account(invoker));
+        ctx.set_metadata_slot_time(Timestamp::from_timestamp_millis(slot_time));
+        ctx
+    }
+
+    /// Parameter transferring the token from the owner to `ACCOUNT_1`.
+    fn transfer_bytes(token_id: ContractTokenId, from: Address) -> Vec<u8> {
+        to_bytes(&TransferParams(vec![Transfer {
+            token_id,
+            amount: 1,
+            from,
+            to: Receiver::from_account(ACCOUNT_1),
+            data: AdditionalData::empty(),
+        }]))
+    }
+
+    /// Response of the storage to a `find` request for the token.
+    fn find_response(token_id: &ContractTokenId, leaf: Option<ContractAddress>) -> Vec<u8> {
+        to_bytes(&StorageFindResponse {
+            prefix: Bytes(token_id.0.clone()),
+            contract: leaf,
+        })
+    }
+
+    /// Test the transfer is finished once the storage finds the leaf of the token.
+    #[concordium_test]
+    fn test_transfer_continue() {
+        let mut state = State::new(STORAGE_ADDRESS);
+
+        let mut ctx = invoker_ctx(ACCOUNT_0, 0);
+        let parameter_bytes = transfer_bytes(token_0(), ADDRESS_0);
+        ctx.set_parameter(&parameter_bytes);
+        let result: ContractResult<ActionsTree> = contract_transfer(&ctx, &mut state);
+        claim!(result.is_ok(), "Results in rejection");
+        claim!(state.is_pending(&token_0().0));
+
+        // Storage responds with the leaf of the token
+        let parameter_bytes = find_response(&token_0(), Some(LEAF_ADDRESS));
+        ctx.set_sender(Address::Contract(STORAGE_ADDRESS));
+        ctx.set_parameter(&parameter_bytes);
+        let mut logger = LogRecorder::init();
+        let result: ContractResult<ActionsTree> = contract_continue(&ctx, &mut logger, &mut state);
+
+        // Owner is updated in the storage and the new owner replaces all operators on the leaf, so that operators set
+        // by the previous owner are gone
+        let operators = ReplaceOperatorsParams {
+            prefix: Bytes(token_0().0),
+            operators: vec![ADDRESS_1],
+        };
+        let storage = StorageContract::new(&STORAGE_ADDRESS);
+        let expected = ActionsTree::accept()
+            .and_then(storage.send_set(
+                token_0().0.as_slice(),
+                &[StorageEntryRef::new(OWNER, &ADDRESS_1)],
+            ))
+            .and_then(send(
+                &LEAF_ADDRESS,
+                ReceiveName::new_unchecked("BictoryStorage.replaceOperators"),
+                Amount::zero(),
+                &operators,
+            ));
+        claim_eq!(result, Ok(expected));
+        claim!(!state.is_pending(&token_0().0));
+        claim_eq!(
+            logger.logs,
+            vec![
+                to_bytes(&Cis1Event::Transfer(TransferEvent {
+                    token_id: token_0(),
+                    amount: 1,
+                    from: ADDRESS_0,
+                    to: ADDRESS_1,
+                })),
+                to_bytes(&token_metadata_event(token_0())),
+            ]
+        );
+
+        // The request is finished, so another response is rejected
+        let result: ContractResult<ActionsTree> = contract_continue(&ctx, &mut logger, &mut state);
+        claim_eq!(result, Err(CustomContractError::NoRequestInProgress.into()));
+    }
+
+    /// Test requests for different tokens are pending at the same time and only accept responses of the contract they
+    /// are waiting for.
+    #[concordium_test]
+    fn test_concurrent_requests() {
+        let mut state = State::new(STORAGE_ADDRESS);
+        let mut logger = LogRecorder::init();
+
+        // Transfer of token_0 and owner query of token_1 are pending together
+        let mut ctx = invoker_ctx(ACCOUNT_0, 0);
+        let transfer_parameter = transfer_bytes(token_0(), ADDRESS_0);
+        ctx.set_parameter(&transfer_parameter);
+        let result: ContractResult<ActionsTree> = contract_transfer(&ctx, &mut state);
+        claim!(result.is_ok(), "Results in rejection");
+
+        let mut owner_ctx = invoker_ctx(ACCOUNT_1, 0);
+        let owner_of_parameter = to_bytes(&OwnerOfParams {
+            token_id: token_1(),
+            result_contract: RESULT_ADDRESS,
+            result_function: OwnedReceiveName::new_unchecked(String::from("Result.owner")),
+        });
+        owner_ctx.set_parameter(&owner_of_parameter);
+        let result: ContractResult<ActionsTree> = contract_owner_of(&owner_ctx, &mut state);
+        claim!(result.is_ok(), "Results in rejection");
+        claim_eq!(state.requests.len(), 2);
+
+        // Another request for a pending token is rejected
+        let result: ContractResult<ActionsTree> = contract_transfer(&ctx, &mut state);
+        claim_eq!(result, Err(CustomContractError::RequestInProgress.into()));
+
+        // Storage finds the leaf of token_1 first, after which only the leaf can respond about it
+        let mut response_ctx = invoker_ctx(ACCOUNT_1, 0);
+        let token_1_found = find_response(&token_1(), Some(LEAF_ADDRESS));
+        response_ctx.set_parameter(&token_1_found);
+        let result: ContractResult<ActionsTree> =
+            contract_continue(&response_ctx, &mut logger, &mut state);
+        claim_eq!(result, Err(CustomContractError::ContractOnly.into()));
+
+        response_ctx.set_sender(Address::Contract(LEAF_ADDRESS));
+        let result: ContractResult<ActionsTree> =
+            contract_continue(&response_ctx, &mut logger, &mut state);
+        claim_eq!(result, Err(ContractError::Unauthorized));
+
+        response_ctx.set_sender(Address::Contract(STORAGE_ADDRESS));
+        let result: ContractResult<ActionsTree> =
+            contract_continue(&response_ctx, &mut logger, &mut state);
+        claim!(result.is_ok(), "Results in rejection");
+        claim_eq!(state.requests.len(), 2);
+
+        let result: ContractResult<ActionsTree> =
+            contract_continue(&response_ctx, &mut logger, &mut state);
+        claim_eq!(result, Err(ContractError::Unauthorized));
+
+        // Transfer of token_0 is finished by the storage independently
+        let token_0_found = find_response(&token_0(), Some(LEAF_ADDRESS));
+        response_ctx.set_parameter(&token_0_found);
+        response_ctx.set_sender(Address::Contract(LEAF_ADDRESS));
+        let result: ContractResult<ActionsTree> =
+            contract_continue(&response_ctx, &mut logger, &mut state);
+        claim_eq!(result, Err(ContractError::Unauthorized));
+
+        response_ctx.set_sender(Address::Contract(STORAGE_ADDRESS));
+        let result: ContractResult<ActionsTree> =
+            contract_continue(&response_ctx, &mut logger, &mut state);
+        claim!(result.is_ok(), "Results in rejection");
+        claim!(!state.is_pending(&token_0().0));
+        claim!(state.is_pending(&token_1().0));
+    }
+
+    /// Test an operator burn is finished once the leaf storage confirms the operator, removing the owner entry and the
+    /// operators from the leaf.
+    #[concordium_test]
+    fn test_operator_burn() {
+        let mut state = State::new(STORAGE_ADDRESS);
+        let mut logger = LogRecorder::init();
+
+        // ACCOUNT_1 burns the token of ADDRESS_0
+        let burn = BurnParams {
+            owner: ADDRESS_0,
+            token_id: token_0(),
+            quantity: TokenAmountU64(1),
+        };
+        let mut ctx = invoker_ctx(ACCOUNT_1, 0);
+        let burn_parameter = to_bytes(&burn);
+        ctx.set_parameter(&burn_parameter);
+        let result: ContractResult<ActionsTree> = contract_burn(&ctx, &mut state);
+        claim!(result.is_ok(), "Results in rejection");
+        claim!(state.is_pending(&token_0().0));
+
+        // Leaf confirmed the owner and was asked for the operator
+        state.requests.clear();
+        state
+            .start_request(
+                &ctx,
+                FunctionState::Burn(BurnStep::CheckOperator(LEAF_ADDRESS), vec![burn]),
+            )
+            .expect_report("Failed to start request");
+
+        let query = || OperatorOfQuery {
+            owner: ADDRESS_0,
+            address: ADDRESS_1,
+        };
+        let mut response_ctx = invoker_ctx(ACCOUNT_1, 0);
+
+        // Only the leaf can respond
+        let confirmed = to_bytes(&OperatorOfQueryResponse(vec![(query(), true)]));
+        response_ctx.set_parameter(&confirmed);
+        let result: ContractResult<ActionsTree> =
+            contract_operator_continue(&response_ctx, &mut logger, &mut state);
+        claim_eq!(result, Err(CustomContractError::ContractOnly.into()));
+
+        response_ctx.set_sender(Address::Contract(STORAGE_ADDRESS));
+        let result: ContractResult<ActionsTree> =
+            contract_operator_continue(&response_ctx, &mut logger, &mut state);
+        claim_eq!(result, Err(CustomContractError::NoRequestInProgress.into()));
+
+        // Invoker is not an operator of the owner
+        response_ctx.set_sender(Address::Contract(LEAF_ADDRESS));
+        let rejected = to_bytes(&OperatorOfQueryResponse(vec![(query(), false)]));
+        response_ctx.set_parameter(&rejected);
+        let result: ContractResult<ActionsTree> =
+            contract_operator_continue(&response_ctx, &mut logger, &mut state);
+        claim_eq!(result, Err(ContractError::Unauthorized));
+
+        // Leaf confirms the operator
+        let mut state = State::new(STORAGE_ADDRESS);
+        state
+            .start_request(
+                &ctx,
+                FunctionState::Burn(
+                    BurnStep::CheckOperator(LEAF_ADDRESS),
+                    vec![BurnParams {
+                        owner: ADDRESS_0,
+                        token_id: token_0(),
+                        quantity: TokenAmountU64(1),
+                    }],
+                ),
+            )
+            .expect_report("Failed to start request");
+        response_ctx.set_parameter(&confirmed);
+        let result: ContractResult<ActionsTree> =
+            contract_operator_continue(&response_ctx, &mut logger, &mut state);
+
+        let expected: ActionsTree = StorageContract::new(&LEAF_ADDRESS)
+            .send_unset(&Bytes(token_0().0), &[<&ByteSlice>::from(OWNER)])
+            .and_then(send(
+                &LEAF_ADDRESS,
+                ReceiveName::new_unchecked("BictoryStorage.updateOperator"),
+                Amount::zero(),
+                &UpdateOperatorParams(vec![
+                    UpdateOperator {
+                        update: OperatorUpdate::Remove,
+                        operator: ADDRESS_0,
+                    },
+                    UpdateOperator {
+                        update: OperatorUpdate::Remove,
+                        operator: ADDRESS_1,
+                    },
+                ]),
+            ));
+        claim_eq!(result, Ok(expected));
+        claim!(state.requests.is_empty());
+        claim_eq!(
+            logger.logs,
+            vec![
+                to_bytes(&Cis1Event::Burn(BurnEvent {
+                    token_id: token_0(),
+                    owner: ADDRESS_0,
+                    amount: 1,
+                })),
+                to_bytes(&token_metadata_event(token_0())),
+            ]
+        );
+    }
+
+    /// Test pending requests can only be canceled by their invoker or the owner once the timeout has passed.
+    #[concordium_test]
+    fn test_cancel_request() {
+        let mut state = State::new(STORAGE_ADDRESS);
+        let timeout = REQUEST_TIMEOUT.millis();
+
+        let mut ctx = invoker_ctx(ACCOUNT_1, 0);
+        let transfer_parameter = transfer_bytes(token_0(), ADDRESS_1);
+        ctx.set_parameter(&transfer_parameter);
+        let result: ContractResult<ActionsTree> = contract_transfer(&ctx, &mut state);
+        claim!(result.is_ok(), "Results in rejection");
+
+        let id_bytes = to_bytes(&0u64);
+        let mut cancel_ctx = invoker_ctx(ACCOUNT_1, timeout - 1);
+        cancel_ctx.set_parameter(&id_bytes);
+        let result: ContractResult<ActionsTree> = contract_cancel_request(&cancel_ctx, &mut state);
+        claim_eq!(
+            result,
+            Err(CustomContractError::OperationNotPermitted.into())
+        );
+        claim!(state.is_pending(&token_0().0));
+
+        let other = AccountAddress([2u8; 32]);
+        let mut other_ctx = invoker_ctx(other, timeout);
+        other_ctx.set_parameter(&id_bytes);
+        let result: ContractResult<ActionsTree> = contract_cancel_request(&other_ctx, &mut state);
+        claim_eq!(result, Err(ContractError::Unauthorized));
+
+        cancel_ctx.set_metadata_slot_time(Timestamp::from_timestamp_millis(timeout));
+        let result: ContractResult<ActionsTree> = contract_cancel_request(&cancel_ctx, &mut state);
+        claim!(result.is_ok(), "Results in rejection");
+        claim!(!state.is_pending(&token_0().0));
+
+        let result: ContractResult<ActionsTree> = contract_cancel_request(&cancel_ctx, &mut state);
+        claim_eq!(result, Err(CustomContractError::NoRequestInProgress.into()));
+
+        // The contract owner can cancel requests of other accounts
+        let result: ContractResult<ActionsTree> = contract_transfer(&ctx, &mut state);
+        claim!(result.is_ok(), "Results in rejection");
+        let id_bytes = to_bytes(&1u64);
+        let mut owner_ctx = invoker_ctx(ACCOUNT_0, timeout);
+        owner_ctx.set_parameter(&id_bytes);
+        let result: ContractResult<ActionsTree> = contract_cancel_request(&owner_ctx, &mut state);
+        claim!(result.is_ok(), "Results in rejection");
+        claim!(state.requests.is_empty());
+    }
+
+    /// Test pending requests are sent to the given contract function.
+    #[concordium_test]
+    fn test_view_pending_requests() {
+        let mut state = State::new(STORAGE_ADDRESS);
+
+        let mut ctx = invoker_ctx(ACCOUNT_0, 5);
+        let transfer_parameter = transfer_bytes(token_0(), ADDRESS_0);
+        ctx.set_parameter(&transfer_parameter);
+        let result: ContractResult<ActionsTree> = contract_transfer(&ctx, &mut state);
+        claim!(result.is_ok(), "Results in rejection");
+
+        let mut ctx = invoker_ctx(ACCOUNT_1, 7);
+        let transfer_parameter = transfer_bytes(token_1(), ADDRESS_1);
+        ctx.set_parameter(&transfer_parameter);
+        let result: ContractResult<ActionsTree> = contract_transfer(&ctx, &mut state);
+        claim!(result.is_ok(), "Results in rejection");
+
+        let result_function = OwnedReceiveName::new_unchecked(String::from("Result.requests"));
+        let view_parameter = to_bytes(&ViewPendingRequestsParams {
+            result_contract: RESULT_ADDRESS,
+            result_function: result_function.clone(),
+        });
+        ctx.set_parameter(&view_parameter);
+        let result: ContractResult<ActionsTree> = contract_view_pending_requests(&ctx, &mut state);
+
+        let expected = vec![
+            PendingRequestInfo {
+                id: 0,
+                invoker: ACCOUNT_0,
+                created_at: Timestamp::from_timestamp_millis(5),
+                token_ids: vec![token_0()],
+            },
+            PendingRequestInfo {
+                id: 1,
+                invoker: ACCOUNT_1,
+                created_at: Timestamp::from_timestamp_millis(7),
+                token_ids: vec![token_1()],
+            },
+        ];
+        claim_eq!(
+            result,
+            Ok(send(
+                &RESULT_ADDRESS,
+                result_function.as_ref(),
+                Amount::zero(),
+                &expected
+            ))
+        );
+    }
+}
+
 // #[concordium_cfg_test]
 // mod tests {
 //     use super::*;
@@ -1,15 +1,108 @@
 use super::*;
 
+impl FunctionState {
+    /// Tokens the function is waiting for.
+    pub fn token_ids(&self) -> Vec<&ContractTokenId> {
+        match self {
+            FunctionState::Mint(mints) => mints.iter().map(|mint| &mint.token_id).collect(),
+            FunctionState::Transfer(transfers) => transfers
+                .iter()
+                .map(|transfer| &transfer.token_id)
+                .collect(),
+            FunctionState::UpdatePrice(update) => vec![&update.token_id],
+            FunctionState::Burn(_, burns) => burns.iter().map(|burn| &burn.token_id).collect(),
+            FunctionState::OwnerOf(_, query) => vec![&query.token_id],
+        }
+    }
+
+    pub fn contains_token(&self, token_id: &[u8]) -> bool {
+        self.token_ids().iter().any(|id| id.0 == token_id)
+    }
+
+    /// Contract expected to send the next response: the leaf storage when waiting for token data, otherwise the
+    /// storage.
+    pub fn responder(&self, storage_address: &ContractAddress) -> ContractAddress {
+        match self {
+            FunctionState::Burn(BurnStep::GetInfo(leaf), _)
+            | FunctionState::Burn(BurnStep::CheckOperator(leaf), _)
+            | FunctionState::OwnerOf(OwnerOfStep::GetInfo(leaf), _) => *leaf,
+            _ => *storage_address,
+        }
+    }
+}
+
 // Functions for creating, updating and querying the contract state.
 impl State {
     /// Creates a empty state with no tokens.
     pub fn new(storage_address: ContractAddress) -> Self {
         State {
             storage_address,
-            function_state: None,
+            next_request_id: 0,
+            requests: BTreeMap::new(),
         }
     }
 
+    /// Check whether any pending request is waiting for a response about the token.
+    pub fn is_pending(&self, token_id: &[u8]) -> bool {
+        self.requests
+            .values()
+            .any(|request| request.function_state.contains_token(token_id))
+    }
+
+    /// Store a request waiting for responses of the storage. Requests without tokens are not stored, since no response
+    /// will arrive for them. Results in an error if another request is waiting for any of the tokens.
+    pub fn start_request(
+        &mut self,
+        ctx: &impl HasReceiveContext,
+        function_state: FunctionState,
+    ) -> ContractResult<()> {
+        let token_ids = function_state.token_ids();
+        if token_ids.is_empty() {
+            return Ok(());
+        }
+        ensure!(
+            token_ids
+                .iter()
+                .all(|token_id| !self.is_pending(&token_id.0)),
+            CustomContractError::RequestInProgress.into()
+        );
+
+        let id = self.next_request_id;
+        self.next_request_id += 1;
+        self.requests.insert(
+            id,
+            PendingRequest {
+                invoker: ctx.invoker(),
+                created_at: ctx.metadata().slot_time(),
+                function_state,
+            },
+        );
+        Ok(())
+    }
+
+    /// Remove the request waiting for a response about the token from the state.
+    /// Results in an error if no request is waiting for the token, or the sender is not the contract expected to
+    /// respond to it.
+    pub fn take_request(
+        &mut self,
+        token_id: &[u8],
+        sender: &Address,
+    ) -> ContractResult<(RequestId, PendingRequest)> {
+        let (id, request) = self
+            .requests
+            .iter()
+            .find(|(_, request)| request.function_state.contains_token(token_id))
+            .ok_or(CustomContractError::NoRequestInProgress)?;
+        ensure!(
+            *sender == Address::Contract(request.function_state.responder(&self.storage_address)),
+            ContractError::Unauthorized
+        );
+        let id = *id;
+        self.requests
+            .remove_entry(&id)
+            .ok_or_else(|| CustomContractError::NoRequestInProgress.into())
+    }
+
     // /// Check that the token ID currently exists in this contract.
     // #[inline(always)]
     // pub fn contains_token(&self, token_id: &ContractTokenId) -> bool {
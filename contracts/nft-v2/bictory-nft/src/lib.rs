@@ -23,7 +23,7 @@
 use crate::{constants::*, events::*, helper::*, structs::*};
 use commons::*;
 use concordium_cis1::*;
-use concordium_std::*;
+use concordium_std::{collections::BTreeMap, *};
 
 mod constants;
 mod contract;
@@ -2,7 +2,17 @@ use super::*;
 
 #[derive(Serialize, SchemaType)]
 pub enum BurnStep {
-    Find(ContractTokenId),
+    Find,
+    /// Waiting for token data from the leaf storage
+    GetInfo(ContractAddress),
+    /// Waiting for the leaf storage to confirm the invoker is an operator of the owner
+    CheckOperator(ContractAddress),
+}
+
+#[derive(Serialize, SchemaType)]
+pub enum OwnerOfStep {
+    Find,
+    /// Waiting for token data from the leaf storage
     GetInfo(ContractAddress),
 }
 
@@ -11,7 +21,53 @@ pub enum FunctionState {
     Mint(Vec<MintData>),
     Transfer(Vec<Transfer<ContractTokenId>>),
     UpdatePrice(UpdatePriceParameter),
-    Burn(BurnStep),
+    Burn(BurnStep, Vec<BurnParams>),
+    OwnerOf(OwnerOfStep, OwnerOfParams),
+}
+
+/// Identifier of a pending request.
+pub type RequestId = u64;
+
+/// Request waiting for responses of the storage.
+#[derive(Serialize, SchemaType)]
+pub struct PendingRequest {
+    /// Account that started the request
+    pub invoker: AccountAddress,
+    /// Time the request was started
+    pub created_at: Timestamp,
+    pub function_state: FunctionState,
+}
+
+/// Summary of a pending request.
+#[derive(Serialize, SchemaType)]
+pub struct PendingRequestInfo {
+    pub id: RequestId,
+    pub invoker: AccountAddress,
+    pub created_at: Timestamp,
+    /// Tokens the request is waiting for
+    pub token_ids: Vec<ContractTokenId>,
+}
+
+/// Pending requests query. Result is sent to `result_function` of `result_contract` as `Vec<PendingRequestInfo>`.
+#[derive(Serialize, SchemaType)]
+pub struct ViewPendingRequestsParams {
+    pub result_contract: ContractAddress,
+    pub result_function: OwnedReceiveName,
+}
+
+/// Owner query. Result is sent to `result_function` of `result_contract` as `OwnerOfResponse`.
+#[derive(Serialize, SchemaType)]
+pub struct OwnerOfParams {
+    pub token_id: ContractTokenId,
+    pub result_contract: ContractAddress,
+    pub result_function: OwnedReceiveName,
+}
+
+/// Owner of the token, `None` if the token does not exist or was burnt.
+#[derive(Serialize, SchemaType)]
+pub struct OwnerOfResponse {
+    pub token_id: ContractTokenId,
+    pub owner: Option<Address>,
 }
 
 /// Minting Data.
@@ -52,8 +108,10 @@ pub struct TransferData {
 pub struct State {
     /// Contract Address of storage
     pub storage_address: ContractAddress,
-    /// State of the functions, that require
-    pub function_state: Option<FunctionState>,
+    /// Id of the next request
+    pub next_request_id: RequestId,
+    /// Requests waiting for responses of the storage
+    pub requests: BTreeMap<RequestId, PendingRequest>,
 }
 
 /// Update Price Params.
@@ -0,0 +1,25 @@
+#![no_std]
+
+use generic_proxy::proxy_contract;
+
+#[allow(unused)]
+use commons::{
+    CnsMintParams, ContractTokenId, GetRoyaltiesParams, LendParams, TokenListParams, TokenParams,
+    TransferParameter,
+};
+#[allow(unused)]
+use concordium_cis2::UpdateOperatorParams;
+
+proxy_contract! {
+    contract: "BictoryCnsNft"
+
+    pub contract_mint => "mint"("CnsMintParams");
+    pub contract_lend => "lend"("LendParams");
+    pub contract_transfer => "transfer"("TransferParameter");
+    pub contract_update_operator => "updateOperator"("UpdateOperatorParams");
+    pub contract_burn => "burn"("ContractTokenId");
+    pub contract_get_token_expiry => read "getTokenExpiry"("TokenParams");
+    pub contract_get_token_expiries => read "getTokenExpiries"("TokenListParams");
+    pub contract_get_token_info => read "getTokenInfo"("TokenParams");
+    pub contract_get_royalties => read "getRoyalties"("GetRoyaltiesParams");
+}
@@ -1,21 +1,146 @@
 #![no_std]
 
+/// Generate a proxy contract forwarding the listed functions to the target contract. Functions marked with `read` are
+/// forwarded as read-only calls, functions marked with `payable` pass the received amount on to the target. Return
+/// values of the target are passed back to the caller unchanged. Calls of entrypoints not listed here are forwarded by
+/// the fallback entrypoint as mutable, payable calls, so functions added to the target later are reachable without
+/// redeploying the proxy. Listing a function is still needed to include its parameter in the schema.
+///
+/// The target is changed in two steps: developers propose a new target with `proxyProposeTarget` and can apply it with
+/// `proxyApplyTarget` once the timelock set on initialization has passed. Admins can cancel a pending proposal. Only
+/// admins can change the admins and developers.
+///
+/// ```ignore
+/// proxy_contract! {
+///     contract: "BictoryNFT"
+///
+///     pub contract_mint => payable "mint"("MintTokenParams");
+///     pub contract_transfer => "transfer"("TransferParameter");
+///     pub contract_balance_of => read "balanceOf"("ContractBalanceOfQueryParams");
+/// }
+/// ```
 #[macro_export]
 macro_rules! proxy_contract {
+    (@forward $contract:literal, $vis:vis $rust_func:ident, read $contract_func:literal, $contract_param:literal) => {
+        #[receive(contract = $contract, name = $contract_func, parameter = $contract_param)]
+        $vis fn $rust_func<S: HasStateApi>(
+            ctx: &impl HasReceiveContext,
+            host: &impl HasHost<State, StateApiType = S>,
+        ) -> ReceiveResult<RawReturnValue> {
+            let parameter = read_parameter(&mut ctx.parameter_cursor())?;
+            let (_, return_value) = host
+                .invoke_contract_raw_read_only(
+                    &host.state().target,
+                    Parameter(&parameter),
+                    EntrypointName::new_unchecked($contract_func),
+                    Amount::zero(),
+                )
+                .map_err(forward_error)?;
+            RawReturnValue::read(return_value)
+        }
+    };
+    (@forward $contract:literal, $vis:vis $rust_func:ident, payable $contract_func:literal, $contract_param:literal) => {
+        #[receive(contract = $contract, name = $contract_func, parameter = $contract_param, mutable, payable)]
+        $vis fn $rust_func<S: HasStateApi>(
+            ctx: &impl HasReceiveContext,
+            host: &mut impl HasHost<State, StateApiType = S>,
+            amount: Amount,
+        ) -> ReceiveResult<RawReturnValue> {
+            let parameter = read_parameter(&mut ctx.parameter_cursor())?;
+            let target = host.state().target;
+            let (_, return_value) = host
+                .invoke_contract_raw(
+                    &target,
+                    Parameter(&parameter),
+                    EntrypointName::new_unchecked($contract_func),
+                    amount,
+                )
+                .map_err(forward_error)?;
+            RawReturnValue::read(return_value)
+        }
+    };
+    (@forward $contract:literal, $vis:vis $rust_func:ident, $contract_func:literal, $contract_param:literal) => {
+        #[receive(contract = $contract, name = $contract_func, parameter = $contract_param, mutable)]
+        $vis fn $rust_func<S: HasStateApi>(
+            ctx: &impl HasReceiveContext,
+            host: &mut impl HasHost<State, StateApiType = S>,
+        ) -> ReceiveResult<RawReturnValue> {
+            let parameter = read_parameter(&mut ctx.parameter_cursor())?;
+            let target = host.state().target;
+            let (_, return_value) = host
+                .invoke_contract_raw(
+                    &target,
+                    Parameter(&parameter),
+                    EntrypointName::new_unchecked($contract_func),
+                    Amount::zero(),
+                )
+                .map_err(forward_error)?;
+            RawReturnValue::read(return_value)
+        }
+    };
     (
         contract: $contract:literal
-        $($vis:vis $rust_func:ident => $contract_func:literal($contract_param:literal);)+
+        $($vis:vis $rust_func:ident => $($modifier:ident)? $contract_func:literal($contract_param:literal);)+
     ) => {
-        use ::commons::ContractError;
+        use ::commons::{
+            ContractError, CustomContractError, PROPOSAL_CANCELED_TAG, TARGET_APPLIED_TAG,
+            TARGET_PROPOSED_TAG,
+        };
         use ::concordium_std::*;
 
         type ContractResult<T> = Result<T, ContractError>;
 
+        /// Return value of the target contract, serialized without a length prefix.
+        pub struct RawReturnValue(Vec<u8>);
+
+        impl RawReturnValue {
+            fn read(return_value: Option<impl HasCallResponse>) -> ReceiveResult<Self> {
+                let mut bytes = Vec::new();
+                // V0 contracts don't produce return values
+                if let Some(mut return_value) = return_value {
+                    bytes.resize(return_value.size() as usize, 0);
+                    return_value.read_exact(&mut bytes)?;
+                }
+                Ok(Self(bytes))
+            }
+        }
+
+        impl Serial for RawReturnValue {
+            fn serial<W: Write>(&self, out: &mut W) -> Result<(), W::Err> {
+                out.write_all(&self.0)
+            }
+        }
+
+        fn read_parameter(parameter_cursor: &mut impl HasParameter) -> ReceiveResult<Vec<u8>> {
+            let size = parameter_cursor.size() as usize;
+            let mut parameter = Vec::with_capacity(size);
+            // SAFETY:
+            // * new length is equal to capacity, which is reserved on vector initialization
+            // * all elements are initialized with `read_exact` function, otherwise error is returned and vector is dropped
+            unsafe {
+                parameter.set_len(size);
+                parameter_cursor.read_exact(&mut parameter)?;
+            }
+            Ok(parameter)
+        }
+
+        /// Rejections of the target contract are passed to the caller with the same error code.
+        fn forward_error<R>(error: CallContractError<R>) -> Reject {
+            match error {
+                CallContractError::LogicReject { reason, .. } => {
+                    Reject::new(reason).unwrap_or_default()
+                }
+                e => e.into(),
+            }
+        }
+
         #[derive(Serialize, SchemaType)]
         pub struct InitParameter {
             target: ContractAddress,
             admins: HashSet<Address>,
             developers: HashSet<Address>,
+            /// Minimal time between proposing and applying a new target
+            timelock: Duration,
         }
 
         #[derive(Serialize, SchemaType)]
@@ -34,34 +159,50 @@ macro_rules! proxy_contract {
             target: ContractAddress,
         }
 
+        #[derive(Serialize, SchemaType, Clone, Copy)]
+        pub struct TargetProposal {
+            target: ContractAddress,
+            proposed_at: Timestamp,
+        }
+
         #[derive(Serialize, SchemaType)]
         pub struct State {
             target: ContractAddress,
             admins: HashSet<Address>,
             developers: HashSet<Address>,
+            timelock: Duration,
+            proposal: Option<TargetProposal>,
         }
 
-        impl State {
-            fn forward<A: HasActions>(
-                &self,
-                function_name: &str,
-                parameter_cursor: &mut impl HasParameter,
-            ) -> Result<A, ContractError> {
-                let target_function = ReceiveName::new_unchecked(function_name);
-
-                let size = parameter_cursor.size() as usize;
-                let mut parameter = Vec::with_capacity(size);
-                // SAFETY:
-                // * new length is equal to capacity, which is reserved on vector initialization
-                // * all elements are initialized with `read_exact` function, otherwise error is returned and vector is dropped
-                unsafe {
-                    parameter.set_len(size);
-                    parameter_cursor.read_exact(&mut parameter)?;
-                }
+        pub enum ProxyEvent {
+            /// New target was proposed
+            TargetProposed(TargetProposal),
+            /// Proposed target became the target of the proxy
+            TargetApplied(TargetContract),
+            /// Proposed target was canceled
+            ProposalCanceled(TargetContract),
+        }
 
-                Ok(A::send_raw(&self.target, target_function, Amount::zero(), &parameter))
+        impl Serial for ProxyEvent {
+            fn serial<W: Write>(&self, out: &mut W) -> Result<(), W::Err> {
+                match self {
+                    ProxyEvent::TargetProposed(event) => {
+                        out.write_u8(TARGET_PROPOSED_TAG)?;
+                        event.serial(out)
+                    }
+                    ProxyEvent::TargetApplied(event) => {
+                        out.write_u8(TARGET_APPLIED_TAG)?;
+                        event.serial(out)
+                    }
+                    ProxyEvent::ProposalCanceled(event) => {
+                        out.write_u8(PROPOSAL_CANCELED_TAG)?;
+                        event.serial(out)
+                    }
+                }
             }
+        }
 
+        impl State {
             fn has_admin_rights(&self, addr: &Address) -> bool {
                 self.admins.contains(addr)
             }
@@ -72,8 +213,11 @@ macro_rules! proxy_contract {
         }
 
         /// Initialize proxy contract instance with originator as only admin
-        #[init(contract = "BictoryNFT", parameter = "InitParameter")]
-        pub fn contract_init(ctx: &impl HasInitContext) -> InitResult<State> {
+        #[init(contract = $contract, parameter = "InitParameter")]
+        pub fn contract_init<S: HasStateApi>(
+            ctx: &impl HasInitContext,
+            _state_builder: &mut StateBuilder<S>,
+        ) -> InitResult<State> {
             let mut params: InitParameter = ctx.parameter_cursor().get()?;
             if params.admins.is_empty() {
                 params.admins.insert(Address::Account(ctx.init_origin()));
@@ -82,37 +226,99 @@ macro_rules! proxy_contract {
                 target: params.target,
                 admins: params.admins,
                 developers: params.developers,
+                timelock: params.timelock,
+                proposal: None,
             };
             Ok(state)
         }
 
+        /// Propose a new target contract. Replaces the previous proposal and restarts the timelock.
         #[receive(
-            contract = "BictoryNFT",
-            name = "proxyUpgradeTarget",
-            parameter = "TargetContract"
+            contract = $contract,
+            name = "proxyProposeTarget",
+            parameter = "TargetContract",
+            mutable,
+            enable_logger
         )]
-        pub fn contract_upgrade_contract<A: HasActions>(
+        pub fn contract_propose_target<S: HasStateApi>(
             ctx: &impl HasReceiveContext,
-            state: &mut State,
-        ) -> ContractResult<A> {
-            if state.has_dev_rights(&ctx.sender()) {
-                let params: TargetContract = ctx.parameter_cursor().get()?;
-                state.target = params.target;
-                Ok(A::accept())
-            } else {
-                Err(ContractError::Unauthorized)
-            }
+            host: &mut impl HasHost<State, StateApiType = S>,
+            logger: &mut impl HasLogger,
+        ) -> ContractResult<()> {
+            let state = host.state_mut();
+            ensure!(
+                state.has_dev_rights(&ctx.sender()),
+                ContractError::Unauthorized
+            );
+            let params: TargetContract = ctx.parameter_cursor().get()?;
+            let proposal = TargetProposal {
+                target: params.target,
+                proposed_at: ctx.metadata().slot_time(),
+            };
+            state.proposal = Some(proposal);
+            logger.log(&ProxyEvent::TargetProposed(proposal))?;
+            Ok(())
+        }
+
+        /// Switch to the proposed target contract once the timelock has passed.
+        #[receive(contract = $contract, name = "proxyApplyTarget", mutable, enable_logger)]
+        pub fn contract_apply_target<S: HasStateApi>(
+            ctx: &impl HasReceiveContext,
+            host: &mut impl HasHost<State, StateApiType = S>,
+            logger: &mut impl HasLogger,
+        ) -> ContractResult<()> {
+            let state = host.state_mut();
+            ensure!(
+                state.has_dev_rights(&ctx.sender()),
+                ContractError::Unauthorized
+            );
+            let proposal = state.proposal.ok_or(CustomContractError::NotFound)?;
+            let unlocked_at = proposal
+                .proposed_at
+                .checked_add(state.timelock)
+                .ok_or(CustomContractError::Overflow)?;
+            ensure!(
+                ctx.metadata().slot_time() >= unlocked_at,
+                CustomContractError::TimelockActive.into()
+            );
+            state.target = proposal.target;
+            state.proposal = None;
+            logger.log(&ProxyEvent::TargetApplied(TargetContract {
+                target: proposal.target,
+            }))?;
+            Ok(())
+        }
+
+        /// Drop the pending target proposal.
+        #[receive(contract = $contract, name = "proxyCancelProposal", mutable, enable_logger)]
+        pub fn contract_cancel_proposal<S: HasStateApi>(
+            ctx: &impl HasReceiveContext,
+            host: &mut impl HasHost<State, StateApiType = S>,
+            logger: &mut impl HasLogger,
+        ) -> ContractResult<()> {
+            let state = host.state_mut();
+            ensure!(
+                state.has_admin_rights(&ctx.sender()),
+                ContractError::Unauthorized
+            );
+            let proposal = state.proposal.take().ok_or(CustomContractError::NotFound)?;
+            logger.log(&ProxyEvent::ProposalCanceled(TargetContract {
+                target: proposal.target,
+            }))?;
+            Ok(())
         }
 
         #[receive(
-            contract = "BictoryNFT",
+            contract = $contract,
             name = "proxyUpdateAdmins",
-            parameter = "UserUpdateList"
+            parameter = "UserUpdateList",
+            mutable
         )]
-        pub fn contract_update_admins<A: HasActions>(
+        pub fn contract_update_admins<S: HasStateApi>(
             ctx: &impl HasReceiveContext,
-            state: &mut State,
-        ) -> ContractResult<A> {
+            host: &mut impl HasHost<State, StateApiType = S>,
+        ) -> ContractResult<()> {
+            let state = host.state_mut();
             if state.has_admin_rights(&ctx.sender()) {
                 let params: UserUpdateList = ctx.parameter_cursor().get()?;
                 for (user, update) in params.updates {
@@ -121,22 +327,24 @@ macro_rules! proxy_contract {
                         RightsUpdate::Remove => state.admins.remove(&user),
                     };
                 }
-                Ok(A::accept())
+                Ok(())
             } else {
                 Err(ContractError::Unauthorized)
             }
         }
 
         #[receive(
-            contract = "BictoryNFT",
+            contract = $contract,
             name = "proxyUpdateDevelopers",
-            parameter = "UserUpdateList"
+            parameter = "UserUpdateList",
+            mutable
         )]
-        pub fn contract_update_devs<A: HasActions>(
+        pub fn contract_update_devs<S: HasStateApi>(
             ctx: &impl HasReceiveContext,
-            state: &mut State,
-        ) -> ContractResult<A> {
-            if state.has_dev_rights(&ctx.sender()) {
+            host: &mut impl HasHost<State, StateApiType = S>,
+        ) -> ContractResult<()> {
+            let state = host.state_mut();
+            if state.has_admin_rights(&ctx.sender()) {
                 let params: UserUpdateList = ctx.parameter_cursor().get()?;
                 for (user, update) in params.updates {
                     match update {
@@ -144,26 +352,296 @@ macro_rules! proxy_contract {
                         RightsUpdate::Remove => state.developers.remove(&user),
                     };
                 }
-                Ok(A::accept())
+                Ok(())
             } else {
                 Err(ContractError::Unauthorized)
             }
         }
 
-        $(
-            #[receive(contract = $contract, name = $contract_func, parameter = $contract_param)]
-            pub fn $rust_func<A: HasActions>(
-                ctx: &impl HasReceiveContext,
-                state: &mut State,
-            ) -> ContractResult<A> {
-                state.forward(
-                    concat!($contract, ".", $contract_func),
-                    &mut ctx.parameter_cursor(),
+        /// Forward calls of entrypoints unknown to the proxy to the target contract with the same name.
+        #[receive(contract = $contract, fallback, mutable, payable)]
+        pub fn contract_fallback<S: HasStateApi>(
+            ctx: &impl HasReceiveContext,
+            host: &mut impl HasHost<State, StateApiType = S>,
+            amount: Amount,
+        ) -> ReceiveResult<RawReturnValue> {
+            let entrypoint = ctx.named_entrypoint();
+            let parameter = read_parameter(&mut ctx.parameter_cursor())?;
+            let target = host.state().target;
+            let (_, return_value) = host
+                .invoke_contract_raw(
+                    &target,
+                    Parameter(&parameter),
+                    entrypoint.as_entrypoint_name(),
+                    amount,
                 )
-            }
+                .map_err(forward_error)?;
+            RawReturnValue::read(return_value)
+        }
+
+        $(
+            $crate::proxy_contract!(
+                @forward $contract, $vis $rust_func, $($modifier)? $contract_func, $contract_param
+            );
         )+
     };
     (contract: $contract:literal) => {
         compile_error!("Forwarded functions must be specified for a proxy contract");
     }
 }
+
+#[concordium_std::concordium_cfg_test]
+mod tests {
+    proxy_contract! {
+        contract: "Target"
+
+        pub contract_view => read "view"("u32");
+        pub contract_update => "update"("u32");
+        pub contract_deposit => payable "deposit"("u32");
+    }
+
+    use test_infrastructure::*;
+
+    const ADMIN: Address = Address::Account(AccountAddress([0u8; 32]));
+    const DEVELOPER: Address = Address::Account(AccountAddress([1u8; 32]));
+    const TARGET_0: ContractAddress = ContractAddress {
+        index: 1,
+        subindex: 0,
+    };
+    const TARGET_1: ContractAddress = ContractAddress {
+        index: 2,
+        subindex: 0,
+    };
+
+    fn timelock() -> Duration {
+        Duration::from_days(2)
+    }
+
+    fn slot_time(millis: u64) -> Timestamp {
+        Timestamp::from_timestamp_millis(millis)
+    }
+
+    fn default_host() -> TestHost<State> {
+        let parameter = InitParameter {
+            target: TARGET_0,
+            admins: vec![ADMIN].into_iter().collect(),
+            developers: vec![DEVELOPER].into_iter().collect(),
+            timelock: timelock(),
+        };
+        let bytes = to_bytes(&parameter);
+        let mut ctx = TestInitContext::empty();
+        ctx.set_parameter(&bytes);
+        let mut state_builder = TestStateBuilder::new();
+        let state =
+            contract_init(&ctx, &mut state_builder).expect_report("Contract initialization failed");
+        TestHost::new(state, state_builder)
+    }
+
+    /// Test the target is only switched once the timelock of the proposal has passed and pending proposals can be
+    /// canceled by admins.
+    #[concordium_test]
+    fn test_target_proposal() {
+        let mut host = default_host();
+        let mut logger = TestLogger::init();
+
+        let bytes = to_bytes(&TargetContract { target: TARGET_1 });
+        let mut ctx = TestReceiveContext::empty();
+        ctx.set_sender(DEVELOPER)
+            .set_parameter(&bytes)
+            .set_metadata_slot_time(slot_time(1000));
+        claim_eq!(
+            contract_propose_target(&ctx, &mut host, &mut logger),
+            Ok(())
+        );
+        claim_eq!(host.state().target, TARGET_0);
+
+        // Proposal can't be applied before the timelock has passed
+        let unlocked_at = slot_time(1000).checked_add(timelock()).unwrap();
+        ctx.set_metadata_slot_time(slot_time(unlocked_at.timestamp_millis() - 1));
+        claim_eq!(
+            contract_apply_target(&ctx, &mut host, &mut logger),
+            Err(CustomContractError::TimelockActive.into())
+        );
+        claim_eq!(host.state().target, TARGET_0);
+
+        ctx.set_metadata_slot_time(unlocked_at);
+        claim_eq!(contract_apply_target(&ctx, &mut host, &mut logger), Ok(()));
+        claim_eq!(host.state().target, TARGET_1);
+        claim_eq!(
+            contract_apply_target(&ctx, &mut host, &mut logger),
+            Err(CustomContractError::NotFound.into())
+        );
+
+        // Only admins can cancel proposals
+        let bytes = to_bytes(&TargetContract { target: TARGET_0 });
+        ctx.set_parameter(&bytes);
+        claim_eq!(
+            contract_propose_target(&ctx, &mut host, &mut logger),
+            Ok(())
+        );
+        claim_eq!(
+            contract_cancel_proposal(&ctx, &mut host, &mut logger),
+            Err(ContractError::Unauthorized)
+        );
+
+        ctx.set_sender(ADMIN);
+        claim_eq!(
+            contract_cancel_proposal(&ctx, &mut host, &mut logger),
+            Ok(())
+        );
+        claim!(host.state().proposal.is_none());
+
+        ctx.set_metadata_slot_time(unlocked_at.checked_add(timelock()).unwrap());
+        claim_eq!(
+            contract_apply_target(&ctx, &mut host, &mut logger),
+            Err(CustomContractError::NotFound.into())
+        );
+        claim_eq!(host.state().target, TARGET_1);
+
+        claim_eq!(
+            logger.logs,
+            vec![
+                to_bytes(&ProxyEvent::TargetProposed(TargetProposal {
+                    target: TARGET_1,
+                    proposed_at: slot_time(1000),
+                })),
+                to_bytes(&ProxyEvent::TargetApplied(TargetContract {
+                    target: TARGET_1
+                })),
+                to_bytes(&ProxyEvent::TargetProposed(TargetProposal {
+                    target: TARGET_0,
+                    proposed_at: unlocked_at,
+                })),
+                to_bytes(&ProxyEvent::ProposalCanceled(TargetContract {
+                    target: TARGET_0
+                })),
+            ]
+        );
+    }
+
+    /// Test read-only calls pass the parameter to the target and return its return value unchanged.
+    #[concordium_test]
+    fn test_read_forwarding() {
+        let mut host = default_host();
+        host.setup_mock_entrypoint(
+            TARGET_0,
+            OwnedEntrypointName::new_unchecked("view".into()),
+            MockFn::new_v1(|parameter, _, _, _| {
+                let value = u32::deserial(&mut Cursor::new(parameter.as_ref()))
+                    .map_err(|_| CallContractError::Trap)?;
+                Ok((false, value + 1))
+            }),
+        );
+
+        let bytes = to_bytes(&41u32);
+        let mut ctx = TestReceiveContext::empty();
+        ctx.set_parameter(&bytes);
+        let result = contract_view(&ctx, &host);
+        claim_eq!(result.map(|value| value.0), Ok(to_bytes(&42u32)));
+    }
+
+    /// Mock entrypoint returning the received parameter together with the received amount.
+    fn echo_amount() -> MockFn<State> {
+        MockFn::new_v1(|parameter, amount, _, _| {
+            let value = u32::deserial(&mut Cursor::new(parameter.as_ref()))
+                .map_err(|_| CallContractError::Trap)?;
+            Ok((false, (value, amount)))
+        })
+    }
+
+    /// Test payable calls pass the received amount on to the target.
+    #[concordium_test]
+    fn test_payable_forwarding() {
+        let mut host = default_host();
+        host.setup_mock_entrypoint(
+            TARGET_0,
+            OwnedEntrypointName::new_unchecked("deposit".into()),
+            echo_amount(),
+        );
+        host.set_self_balance(Amount::from_micro_ccd(500));
+
+        let bytes = to_bytes(&7u32);
+        let mut ctx = TestReceiveContext::empty();
+        ctx.set_parameter(&bytes);
+        let result = contract_deposit(&ctx, &mut host, Amount::from_micro_ccd(500));
+        claim_eq!(
+            result.map(|value| value.0),
+            Ok(to_bytes(&(7u32, Amount::from_micro_ccd(500))))
+        );
+    }
+
+    /// Test calls of entrypoints not listed in the proxy reach the target entrypoint with the same name through the
+    /// fallback, together with the parameter and the received amount.
+    #[concordium_test]
+    fn test_fallback_forwarding() {
+        let mut host = default_host();
+        host.setup_mock_entrypoint(
+            TARGET_0,
+            OwnedEntrypointName::new_unchecked("unlisted".into()),
+            echo_amount(),
+        );
+        host.set_self_balance(Amount::from_micro_ccd(300));
+
+        let bytes = to_bytes(&7u32);
+        let mut ctx = TestReceiveContext::empty();
+        ctx.set_parameter(&bytes)
+            .set_named_entrypoint(OwnedEntrypointName::new_unchecked("unlisted".into()));
+        let result = contract_fallback(&ctx, &mut host, Amount::from_micro_ccd(300));
+        claim_eq!(
+            result.map(|value| value.0),
+            Ok(to_bytes(&(7u32, Amount::from_micro_ccd(300))))
+        );
+    }
+
+    /// Test rejections of the target are passed to the caller with the same error code.
+    #[concordium_test]
+    fn test_forward_error() {
+        let mut host = default_host();
+        for entrypoint in ["view", "update"] {
+            host.setup_mock_entrypoint(
+                TARGET_0,
+                OwnedEntrypointName::new_unchecked(entrypoint.into()),
+                MockFn::new_v1(|_, _, _, _| -> Result<(bool, ()), CallContractError<()>> {
+                    Err(CallContractError::LogicReject {
+                        reason: -42,
+                        return_value: (),
+                    })
+                }),
+            );
+        }
+
+        let bytes = to_bytes(&0u32);
+        let mut ctx = TestReceiveContext::empty();
+        ctx.set_parameter(&bytes);
+        claim_eq!(
+            contract_view(&ctx, &host).map(|value| value.0),
+            Err(Reject::new(-42).unwrap())
+        );
+        claim_eq!(
+            contract_update(&ctx, &mut host).map(|value| value.0),
+            Err(Reject::new(-42).unwrap())
+        );
+    }
+
+    /// Test developers can't change the developer list.
+    #[concordium_test]
+    fn test_update_developers() {
+        let mut host = default_host();
+        let other = Address::Account(AccountAddress([2u8; 32]));
+
+        let bytes = to_bytes(&UserUpdateList {
+            updates: vec![(other, RightsUpdate::Add)],
+        });
+        let mut ctx = TestReceiveContext::empty();
+        ctx.set_sender(DEVELOPER).set_parameter(&bytes);
+        claim_eq!(
+            contract_update_devs(&ctx, &mut host),
+            Err(ContractError::Unauthorized)
+        );
+        claim!(!host.state().developers.contains(&other));
+
+        ctx.set_sender(ADMIN);
+        claim_eq!(contract_update_devs(&ctx, &mut host), Ok(()));
+        claim!(host.state().developers.contains(&other));
+    }
+}
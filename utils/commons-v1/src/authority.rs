@@ -8,6 +8,10 @@ pub struct Authority<S: HasStateApi> {
     admins: StateSet<Address, S>,
     /// Semi-trusted addresses that are allowed to maintain the contract and update maintainer list
     maintainers: StateSet<Address, S>,
+    /// Addresses that are allowed to perform only some of the maintainer actions
+    capabilities: StateSet<(Address, Capability), S>,
+    /// Address proposed as a new admin, which becomes an admin once it accepts
+    pending_admin: Option<Address>,
 }
 
 impl<S: HasStateApi> Authority<S> {
@@ -17,6 +21,8 @@ impl<S: HasStateApi> Authority<S> {
         Self {
             admins,
             maintainers: state_builder.new_set(),
+            capabilities: state_builder.new_set(),
+            pending_admin: None,
         }
     }
 
@@ -28,37 +34,133 @@ impl<S: HasStateApi> Authority<S> {
         self.maintainers.contains(address) || self.has_admin_rights(address)
     }
 
+    /// Maintainers and admins have all capabilities, other addresses only the ones granted to them.
+    pub fn has_capability(&self, address: &Address, capability: Capability) -> bool {
+        self.capabilities.contains(&(*address, capability)) || self.has_maintainer_rights(address)
+    }
+
+    /// Grant or revoke a capability. Only admins are allowed to change capabilities.
+    pub fn handle_capability_update(
+        &mut self,
+        sender: Address,
+        update: CapabilityUpdateParams,
+    ) -> Result<(), Reject> {
+        ensure!(
+            self.has_admin_rights(&sender),
+            CustomContractError::Unauthorized.into()
+        );
+
+        match update.kind {
+            AuthorityUpdateKind::Remove => {
+                self.capabilities
+                    .remove(&(update.address, update.capability));
+            }
+            AuthorityUpdateKind::Add => {
+                self.capabilities
+                    .insert((update.address, update.capability));
+            }
+            AuthorityUpdateKind::Accept => {
+                return Err(CustomContractError::OperationNotPermitted.into())
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Capabilities granted to the address, not including the ones it has as a maintainer or admin.
+    pub fn handle_capability_view(&self, address: Address) -> Vec<Capability> {
+        Capability::ALL
+            .iter()
+            .filter(|capability| self.capabilities.contains(&(address, **capability)))
+            .copied()
+            .collect()
+    }
+
+    pub fn pending_admin(&self) -> Option<Address> {
+        self.pending_admin
+    }
+
+    /// Admins are added in two steps, so a mistyped address can't take over the contract. Adding an admin only
+    /// proposes it, replacing any previous proposal, and the proposed address becomes an admin once it accepts.
     pub fn handle_update(
         &mut self,
         sender: Address,
         update: AuthorityUpdateParams,
     ) -> Result<(), Reject> {
-        let address_list = match update.field {
+        match update.field {
             AuthorityField::Maintainer => {
                 ensure!(
                     self.has_maintainer_rights(&sender),
                     CustomContractError::Unauthorized.into()
                 );
-                &mut self.maintainers
+                match update.kind {
+                    AuthorityUpdateKind::Remove => {
+                        self.maintainers.remove(&update.address);
+                    }
+                    AuthorityUpdateKind::Add => {
+                        self.maintainers.insert(update.address);
+                    }
+                    AuthorityUpdateKind::Accept => {
+                        return Err(CustomContractError::OperationNotPermitted.into())
+                    }
+                }
             }
-            AuthorityField::Admin => {
-                ensure!(
-                    self.has_admin_rights(&sender),
-                    CustomContractError::Unauthorized.into()
-                );
-                &mut self.admins
+            AuthorityField::Admin => match update.kind {
+                AuthorityUpdateKind::Remove => {
+                    ensure!(
+                        self.has_admin_rights(&sender),
+                        CustomContractError::Unauthorized.into()
+                    );
+                    // Contract must keep at least one admin
+                    ensure!(
+                        !self.has_admin_rights(&update.address)
+                            || self.admins.iter().nth(1).is_some(),
+                        CustomContractError::OperationNotPermitted.into()
+                    );
+                    self.admins.remove(&update.address);
+                    if self.pending_admin == Some(update.address) {
+                        self.pending_admin = None;
+                    }
+                }
+                AuthorityUpdateKind::Add => self.propose_admin(sender, update.address)?,
+                AuthorityUpdateKind::Accept => {
+                    // Accepting for some other address is not allowed
+                    ensure!(
+                        update.address == sender,
+                        CustomContractError::Unauthorized.into()
+                    );
+                    self.accept_admin(sender)?
+                }
+            },
+            // Pending admin is changed by proposing and accepting admins
+            AuthorityField::PendingAdmin => {
+                return Err(CustomContractError::OperationNotPermitted.into())
             }
-        };
+        }
 
-        match update.kind {
-            AuthorityUpdateKind::Remove => {
-                address_list.remove(&update.address);
-            }
-            AuthorityUpdateKind::Add => {
-                address_list.insert(update.address);
-            }
+        Ok(())
+    }
+
+    /// Propose a new admin. Addresses that already are admins are not proposed again.
+    pub fn propose_admin(&mut self, sender: Address, address: Address) -> Result<(), Reject> {
+        ensure!(
+            self.has_admin_rights(&sender),
+            CustomContractError::Unauthorized.into()
+        );
+        if !self.has_admin_rights(&address) {
+            self.pending_admin = Some(address);
         }
+        Ok(())
+    }
 
+    /// Make the pending admin an admin. Can only be called by the pending admin.
+    pub fn accept_admin(&mut self, sender: Address) -> Result<(), Reject> {
+        ensure!(
+            self.pending_admin == Some(sender),
+            CustomContractError::Unauthorized.into()
+        );
+        self.pending_admin = None;
+        self.admins.insert(sender);
         Ok(())
     }
 
@@ -66,6 +168,15 @@ impl<S: HasStateApi> Authority<S> {
         let address_list = match view.field {
             AuthorityField::Maintainer => &self.maintainers,
             AuthorityField::Admin => &self.admins,
+            AuthorityField::PendingAdmin => {
+                return self
+                    .pending_admin
+                    .iter()
+                    .skip(view.skip as usize)
+                    .take(view.show as usize)
+                    .copied()
+                    .collect()
+            }
         };
 
         let address_vec = address_list
@@ -83,12 +194,44 @@ impl<S: HasStateApi> Authority<S> {
 pub enum AuthorityField {
     Maintainer,
     Admin,
+    /// Address proposed as a new admin. Only viewable, it changes by proposing and accepting admins
+    PendingAdmin,
+}
+
+/// Maintainer actions that can be allowed to an address separately.
+#[derive(Debug, SchemaType, Serialize, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Capability {
+    /// Update contract configuration, e.g. linked contracts and limits
+    CanConfigure,
+    /// Update fees and their beneficiaries, and sweep collected fees
+    CanSetFees,
+    /// Register and extend reserved names and names with limited pricing
+    CanMintReserved,
+    /// Reserve and release names
+    CanReserve,
+    /// Freeze and unfreeze names
+    CanFreeze,
+    /// Update the list of storage writers
+    CanManageWriters,
+}
+
+impl Capability {
+    pub const ALL: [Capability; 6] = [
+        Capability::CanConfigure,
+        Capability::CanSetFees,
+        Capability::CanMintReserved,
+        Capability::CanReserve,
+        Capability::CanFreeze,
+        Capability::CanManageWriters,
+    ];
 }
 
 #[derive(Debug, SchemaType, Serialize)]
 pub enum AuthorityUpdateKind {
     Remove,
     Add,
+    /// Accept the admin proposal, sent by the proposed address
+    Accept,
 }
 
 #[derive(Debug, SchemaType, Serialize)]
@@ -98,6 +241,13 @@ pub struct AuthorityUpdateParams {
     pub address: Address,
 }
 
+#[derive(Debug, SchemaType, Serialize)]
+pub struct CapabilityUpdateParams {
+    pub kind: AuthorityUpdateKind,
+    pub address: Address,
+    pub capability: Capability,
+}
+
 #[derive(Debug, SchemaType, Serialize)]
 pub struct AuthorityViewParams {
     pub field: AuthorityField,
@@ -149,6 +299,20 @@ mod tests {
         authority
     }
 
+    fn accept_admin(
+        authority: &mut Authority<TestStateApi>,
+        address: Address,
+    ) -> Result<(), Reject> {
+        authority.handle_update(
+            address,
+            AuthorityUpdateParams {
+                field: AuthorityField::Admin,
+                kind: AuthorityUpdateKind::Accept,
+                address,
+            },
+        )
+    }
+
     #[concordium_test]
     fn test_update_authority_add_new_admin() {
         let mut authority = default_authority();
@@ -162,6 +326,14 @@ mod tests {
             },
         );
         claim_eq!(result, Ok(()));
+        // Proposed admin has no rights until it accepts
+        claim!(!authority.has_admin_rights(&Address::Account(USER_1)));
+        claim_eq!(authority.pending_admin(), Some(Address::Account(USER_1)));
+        claim_eq!(
+            accept_admin(&mut authority, Address::Account(USER_1)),
+            Ok(())
+        );
+        claim_eq!(authority.pending_admin(), None);
         claim!(authority.has_admin_rights(&Address::Account(USER_1)));
         claim!(authority.has_maintainer_rights(&Address::Account(USER_1)));
 
@@ -174,6 +346,10 @@ mod tests {
             },
         );
         claim_eq!(result, Ok(()));
+        claim_eq!(
+            accept_admin(&mut authority, Address::Contract(CONTRACT_1)),
+            Ok(())
+        );
         claim!(authority.has_admin_rights(&Address::Contract(CONTRACT_1)));
         claim!(authority.has_maintainer_rights(&Address::Contract(CONTRACT_1)));
 
@@ -200,6 +376,81 @@ mod tests {
         claim_eq!(result, Err(CustomContractError::Unauthorized.into()));
         claim!(!authority.has_admin_rights(&Address::Contract(CONTRACT_2)));
         claim!(!authority.has_maintainer_rights(&Address::Contract(CONTRACT_2)));
+        claim_eq!(authority.pending_admin(), None);
+    }
+
+    #[concordium_test]
+    fn test_update_authority_admin_proposal() {
+        let mut authority = default_authority();
+        let propose = |address| AuthorityUpdateParams {
+            field: AuthorityField::Admin,
+            kind: AuthorityUpdateKind::Add,
+            address,
+        };
+
+        let result = authority.handle_update(
+            Address::Account(ADMIN_ACCOUNT),
+            propose(Address::Account(USER_1)),
+        );
+        claim_eq!(result, Ok(()));
+
+        // Only the proposed address can accept, and only for itself
+        claim_eq!(
+            accept_admin(&mut authority, Address::Account(USER_2)),
+            Err(CustomContractError::Unauthorized.into())
+        );
+        let result = authority.handle_update(
+            Address::Account(ADMIN_ACCOUNT),
+            AuthorityUpdateParams {
+                field: AuthorityField::Admin,
+                kind: AuthorityUpdateKind::Accept,
+                address: Address::Account(USER_1),
+            },
+        );
+        claim_eq!(result, Err(CustomContractError::Unauthorized.into()));
+        claim!(!authority.has_admin_rights(&Address::Account(USER_1)));
+
+        // New proposal replaces the previous one
+        let result = authority.handle_update(
+            Address::Account(ADMIN_ACCOUNT),
+            propose(Address::Account(USER_2)),
+        );
+        claim_eq!(result, Ok(()));
+        claim_eq!(
+            accept_admin(&mut authority, Address::Account(USER_1)),
+            Err(CustomContractError::Unauthorized.into())
+        );
+
+        // Removing the proposed address cancels the proposal
+        let result = authority.handle_update(
+            Address::Account(ADMIN_ACCOUNT),
+            AuthorityUpdateParams {
+                field: AuthorityField::Admin,
+                kind: AuthorityUpdateKind::Remove,
+                address: Address::Account(USER_2),
+            },
+        );
+        claim_eq!(result, Ok(()));
+        claim_eq!(authority.pending_admin(), None);
+        claim_eq!(
+            accept_admin(&mut authority, Address::Account(USER_2)),
+            Err(CustomContractError::Unauthorized.into())
+        );
+        claim!(!authority.has_admin_rights(&Address::Account(USER_2)));
+
+        // Maintainers are added immediately, so there is nothing to accept
+        let result = authority.handle_update(
+            Address::Account(MAINTAINER_ACCOUNT),
+            AuthorityUpdateParams {
+                field: AuthorityField::Maintainer,
+                kind: AuthorityUpdateKind::Accept,
+                address: Address::Account(MAINTAINER_ACCOUNT),
+            },
+        );
+        claim_eq!(
+            result,
+            Err(CustomContractError::OperationNotPermitted.into())
+        );
     }
 
     #[concordium_test]
@@ -396,6 +647,39 @@ mod tests {
         claim!(authority.has_maintainer_rights(&Address::Account(ADMIN_ACCOUNT)));
     }
 
+    #[concordium_test]
+    fn test_update_authority_remove_last_admin() {
+        let mut authority = default_authority();
+        let remove = |address| AuthorityUpdateParams {
+            field: AuthorityField::Admin,
+            kind: AuthorityUpdateKind::Remove,
+            address,
+        };
+
+        let result = authority.handle_update(
+            Address::Account(ADMIN_ACCOUNT),
+            remove(Address::Contract(ADMIN_CONTRACT)),
+        );
+        claim_eq!(result, Ok(()));
+
+        let result = authority.handle_update(
+            Address::Account(ADMIN_ACCOUNT),
+            remove(Address::Account(ADMIN_ACCOUNT)),
+        );
+        claim_eq!(
+            result,
+            Err(CustomContractError::OperationNotPermitted.into())
+        );
+        claim!(authority.has_admin_rights(&Address::Account(ADMIN_ACCOUNT)));
+
+        // Removing addresses that are not admins doesn't affect the last admin
+        let result = authority.handle_update(
+            Address::Account(ADMIN_ACCOUNT),
+            remove(Address::Account(USER_1)),
+        );
+        claim_eq!(result, Ok(()));
+    }
+
     #[concordium_test]
     fn test_update_authority_remove_existing_maintainer() {
         let mut authority = default_authority();
@@ -486,6 +770,47 @@ mod tests {
         claim!(!authority.has_maintainer_rights(&Address::Contract(CONTRACT_1)));
     }
 
+    #[concordium_test]
+    fn test_view_authority_pending_admin() {
+        let mut authority = default_authority();
+        let view = |skip| AuthorityViewParams {
+            field: AuthorityField::PendingAdmin,
+            skip,
+            show: 1,
+        };
+        claim_eq!(authority.handle_view(view(0)), Vec::new());
+
+        let result = authority.handle_update(
+            Address::Account(ADMIN_ACCOUNT),
+            AuthorityUpdateParams {
+                field: AuthorityField::Admin,
+                kind: AuthorityUpdateKind::Add,
+                address: Address::Account(USER_1),
+            },
+        );
+        claim_eq!(result, Ok(()));
+        claim_eq!(
+            authority.handle_view(view(0)),
+            vec![Address::Account(USER_1)]
+        );
+        claim_eq!(authority.handle_view(view(1)), Vec::new());
+
+        // Pending admin can't be updated directly
+        let result = authority.handle_update(
+            Address::Account(ADMIN_ACCOUNT),
+            AuthorityUpdateParams {
+                field: AuthorityField::PendingAdmin,
+                kind: AuthorityUpdateKind::Remove,
+                address: Address::Account(USER_1),
+            },
+        );
+        claim_eq!(
+            result,
+            Err(CustomContractError::OperationNotPermitted.into())
+        );
+        claim_eq!(authority.pending_admin(), Some(Address::Account(USER_1)));
+    }
+
     #[concordium_test]
     fn test_view_authority_admins() {
         let mut authority = default_authority();
@@ -536,4 +861,53 @@ mod tests {
         // All addresses must have been removed in the loop
         claim!(admin_set.is_empty());
     }
+
+    #[concordium_test]
+    fn test_capabilities() {
+        let mut authority = default_authority();
+        let user = Address::Account(USER_1);
+
+        // Maintainers and admins have every capability
+        claim!(authority.has_capability(&Address::Account(ADMIN_ACCOUNT), Capability::CanSetFees));
+        claim!(authority.has_capability(
+            &Address::Contract(MAINTAINER_CONTRACT),
+            Capability::CanFreeze
+        ));
+        claim!(!authority.has_capability(&user, Capability::CanSetFees));
+
+        let grant = |kind| CapabilityUpdateParams {
+            kind,
+            address: user,
+            capability: Capability::CanSetFees,
+        };
+
+        // Only admins can grant capabilities
+        let result = authority.handle_capability_update(
+            Address::Account(MAINTAINER_ACCOUNT),
+            grant(AuthorityUpdateKind::Add),
+        );
+        claim_eq!(result, Err(CustomContractError::Unauthorized.into()));
+        claim!(!authority.has_capability(&user, Capability::CanSetFees));
+
+        let result = authority.handle_capability_update(
+            Address::Account(ADMIN_ACCOUNT),
+            grant(AuthorityUpdateKind::Add),
+        );
+        claim_eq!(result, Ok(()));
+        claim!(authority.has_capability(&user, Capability::CanSetFees));
+        claim!(!authority.has_capability(&user, Capability::CanConfigure));
+        claim!(!authority.has_maintainer_rights(&user));
+        claim_eq!(
+            authority.handle_capability_view(user),
+            vec![Capability::CanSetFees]
+        );
+
+        let result = authority.handle_capability_update(
+            Address::Account(ADMIN_ACCOUNT),
+            grant(AuthorityUpdateKind::Remove),
+        );
+        claim_eq!(result, Ok(()));
+        claim!(!authority.has_capability(&user, Capability::CanSetFees));
+        claim!(authority.handle_capability_view(user).is_empty());
+    }
 }
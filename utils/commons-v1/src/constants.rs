@@ -1,3 +1,8 @@
+// Event tags are allocated downwards from `u8::MAX`, so they never collide with each other within a contract log.
+// 255..=251 are reserved for CIS-1/CIS-2 standard events, all other tags are Bictory events. CNS events use
+// 226..=218, 215, 214 and 211, CNS price oracle events use 234, 213 and 212. New tags take the next free value below the
+// lowest one in use.
+
 /// Tag for the Custom For Sale event.
 pub const SET_FOR_SALE_EVENT_TAG: u8 = u8::MAX - 5;
 
@@ -52,6 +57,144 @@ pub const SET_YEARLY_DOMAIN_PRICE_TAG: u8 = u8::MAX - 21;
 /// Tag for the abort event.
 pub const ABORT_TAG: u8 = u8::MAX - 22;
 
+/// Tag for the Custom Cancellation Penalty event.
+pub const CANCELLATION_PENALTY_TAG: u8 = u8::MAX - 23;
+
+/// Tag for the Custom Airdrop event.
+pub const AIRDROP_TAG: u8 = u8::MAX - 24;
+
+/// Tag for the Custom Participation event.
+pub const PARTICIPATION_TAG: u8 = u8::MAX - 25;
+
+/// Tag for the storage Inserted event.
+pub const STORAGE_INSERTED_TAG: u8 = u8::MAX - 26;
+
+/// Tag for the storage Updated event.
+pub const STORAGE_UPDATED_TAG: u8 = u8::MAX - 27;
+
+/// Tag for the storage Removed event.
+pub const STORAGE_REMOVED_TAG: u8 = u8::MAX - 28;
+
+/// Tag for the CNS DomainTransferred event.
+pub const DOMAIN_TRANSFERRED_TAG: u8 = u8::MAX - 29;
+
+/// Tag for the CNS DomainFrozen event.
+pub const DOMAIN_FROZEN_TAG: u8 = u8::MAX - 30;
+
+/// Tag for the CNS DomainUnfrozen event.
+pub const DOMAIN_UNFROZEN_TAG: u8 = u8::MAX - 31;
+
+/// Tag for the CNS AddressChanged event.
+pub const ADDRESS_CHANGED_TAG: u8 = u8::MAX - 32;
+
+/// Tag for the CNS DataChanged event.
+pub const DATA_CHANGED_TAG: u8 = u8::MAX - 33;
+
+/// Tag for the CNS SubdomainCreated event.
+pub const SUBDOMAIN_CREATED_TAG: u8 = u8::MAX - 34;
+
+/// Tag for the CNS SubdomainDeleted event.
+pub const SUBDOMAIN_DELETED_TAG: u8 = u8::MAX - 35;
+
+/// Tag for the CNS Registered event.
+pub const DOMAIN_REGISTERED_TAG: u8 = u8::MAX - 36;
+
+/// Tag for the CNS Extended event.
+pub const DOMAIN_EXTENDED_TAG: u8 = u8::MAX - 37;
+
+/// Tag for the NFT InheritanceUpdated event.
+pub const INHERITANCE_UPDATED_TAG: u8 = u8::MAX - 38;
+
+/// Tag for the NFT InheritanceClaimed event.
+pub const INHERITANCE_CLAIMED_TAG: u8 = u8::MAX - 39;
+
+/// Tag for the CNS ChainAddressChanged event.
+pub const CHAIN_ADDRESS_CHANGED_TAG: u8 = u8::MAX - 40;
+
+/// Tag for the CNS ContentHashChanged event.
+pub const CONTENT_HASH_CHANGED_TAG: u8 = u8::MAX - 41;
+
+/// Tag for the SetPricingSchedule event of CNS price oracle.
+pub const SET_PRICING_SCHEDULE_TAG: u8 = u8::MAX - 42;
+
+/// Tag for the UpdateRate event of CNS price oracle.
+pub const UPDATE_RATE_TAG: u8 = u8::MAX - 43;
+
+/// Tag for the CNS VoucherClaimed event.
+pub const VOUCHER_CLAIMED_TAG: u8 = u8::MAX - 44;
+
+/// Tag for the Custom AuctionCreated event.
+pub const AUCTION_CREATED_TAG: u8 = u8::MAX - 45;
+
+/// Tag for the Custom AuctionSettled event.
+pub const AUCTION_SETTLED_TAG: u8 = u8::MAX - 46;
+
+/// Tag for the CNS NFT UpdateTokenOperator event.
+pub const UPDATE_TOKEN_OPERATOR_TAG: u8 = u8::MAX - 47;
+
+/// Tag for the CNS SubdomainTokenized event.
+pub const SUBDOMAIN_TOKENIZED_TAG: u8 = u8::MAX - 48;
+
+/// Tag for the Custom ListingApproved event.
+pub const LISTING_APPROVED_TAG: u8 = u8::MAX - 49;
+
+/// Tag for the Custom ListingRejected event.
+pub const LISTING_REJECTED_TAG: u8 = u8::MAX - 50;
+
+/// Tag for the Custom AuctionExtended event.
+pub const AUCTION_EXTENDED_TAG: u8 = u8::MAX - 51;
+
+/// Tag for the Custom OfferMade event.
+pub const OFFER_MADE_TAG: u8 = u8::MAX - 52;
+
+/// Tag for the Custom OfferCanceled event.
+pub const OFFER_CANCELED_TAG: u8 = u8::MAX - 53;
+
+/// Tag for the Custom PriceChanged event.
+pub const PRICE_CHANGED_TAG: u8 = u8::MAX - 54;
+
+/// Tag for the Custom EscrowOpened event.
+pub const ESCROW_OPENED_TAG: u8 = u8::MAX - 55;
+
+/// Tag for the Custom EscrowDisputed event.
+pub const ESCROW_DISPUTED_TAG: u8 = u8::MAX - 56;
+
+/// Tag for the Custom EscrowRefunded event.
+pub const ESCROW_REFUNDED_TAG: u8 = u8::MAX - 57;
+
+/// Tag for the Proxy TargetProposed event.
+pub const TARGET_PROPOSED_TAG: u8 = u8::MAX - 58;
+
+/// Tag for the Proxy TargetApplied event.
+pub const TARGET_APPLIED_TAG: u8 = u8::MAX - 59;
+
+/// Tag for the Proxy ProposalCanceled event.
+pub const PROPOSAL_CANCELED_TAG: u8 = u8::MAX - 60;
+
+/// Tag for the Custom Buy event of listings paying the marketplace fee. Replaces `BUY_TAG` for the extended layout.
+pub const SALE_TAG: u8 = u8::MAX - 61;
+
+/// Tag for the SetCurrencyPricing event of CNS price oracle.
+pub const SET_CURRENCY_PRICING_TAG: u8 = u8::MAX - 62;
+
+/// Version of the CNS event layouts. Incremented whenever the layout of an existing CNS event changes, new events
+/// only get a new tag.
+pub const CNS_EVENT_SCHEMA_VERSION: u8 = 2;
+
+/// Maximum number of royalty recipients returned for a single token.
+pub const MAX_ROYALTIES: usize = 10;
+
+/// Maximum number of additional royalty recipients of a token. Leaves room for the creator, minter and Bictory in the
+/// royalties of the token.
+pub const MAX_ROYALTY_RECIPIENTS: usize = MAX_ROYALTIES - 3;
+
+/// Maximum length of a CNS NFT token metadata URL in bytes. URLs are logged in the `TokenMetadata` event on mint, so
+/// this leaves room for the token ID and a hash within the 512 byte event limit.
+pub const MAX_METADATA_URL_LENGTH: usize = 400;
+
+/// Maximum number of entries in the pricing schedule of CNS price oracle.
+pub const MAX_PRICING_SCHEDULE: usize = 16;
+
 pub const OWNER: &str = "owner";
 pub const CREATOR: &str = "creator";
 pub const CREATOR_ROYALTY: &str = "creator_royalty";
@@ -89,8 +89,36 @@ pub enum CustomContractError {
     Unsupported,
     /// Owner is not allowed to perform this action (Error code: -40)
     OwnerForbidden,
-    /// Owner is not allowed to perform this action (Error code: -41)
+    /// Auction does not accept bids yet (Error code: -41)
     AuctionNotStarted,
+    /// Attached amount does not cover the required payment (Error code: -42)
+    InsufficientFunds,
+    /// Bounded collection capacity exceeded (Error code: -43)
+    CapacityExceeded,
+    /// Signature verification failed (Error code: -44)
+    InvalidSignature,
+    /// Nonce does not match the expected one (Error code: -45)
+    InvalidNonce,
+    /// Arithmetic overflow (Error code: -46)
+    Overflow,
+    /// Amount is not a multiple of the required granularity (Error code: -47)
+    InvalidAmountGranularity,
+    /// Entity was frozen by maintainers and can't be modified (Error code: -48)
+    Frozen,
+    /// Name is reserved and can only be taken by maintainers (Error code: -49)
+    Reserved,
+    /// Name label mixes characters of different scripts (Error code: -50)
+    MixedScript,
+    /// Name contains control or whitespace characters (Error code: -51)
+    InvalidCharacter,
+    /// Contract is paused by maintainers (Error code: -52)
+    ContractPaused,
+    /// Token is soulbound and can't be transferred (Error code: -53)
+    Soulbound,
+    /// Timelock of the pending change has not elapsed yet (Error code: -54)
+    TimelockActive,
+    /// Exchange rate is older than the configured maximum age (Error code: -55)
+    StaleExchangeRate,
 }
 
 /// Mapping the logging errors to CustomContractError.
@@ -0,0 +1,40 @@
+//! Golden vectors of mint parameters, which differ between the commons crates. Shared vectors are in `golden.rs`.
+use super::*;
+
+use crate::golden::check_golden;
+
+/// Quantities are CIS-1 token amounts, encoded as little endian `u64`.
+const QUANTITY: TokenAmount = 300;
+const QUANTITY_BYTES: &[u8] = &[0x2c, 0x01, 0, 0, 0, 0, 0, 0];
+
+#[concordium_test]
+fn test_golden_mint_params() {
+    let params = MintParams {
+        token_id: TokenIdVec(vec![0, 1]),
+        owner: Address::Account(AccountAddress([1; 32])),
+        creator: Address::Contract(ContractAddress {
+            index: 5,
+            subindex: 0,
+        }),
+        creator_royalty: 250,
+        minter_royalty: 100,
+        cid: b"cid".to_vec(),
+        quantity: QUANTITY,
+    };
+    check_golden(
+        &params,
+        &[
+            &[2, 0, 1],
+            &[0],
+            &[1; 32],
+            &[1],
+            &[5, 0, 0, 0, 0, 0, 0, 0],
+            &[0; 8],
+            &[250, 0, 0, 0],
+            &[100, 0, 0, 0],
+            &[3, 0, 0, 0],
+            b"cid",
+            QUANTITY_BYTES,
+        ],
+    );
+}
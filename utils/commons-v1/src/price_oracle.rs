@@ -9,6 +9,7 @@ pub trait HostCnsOracleExt<S>: HasHost<S> {
         contract: &ContractAddress,
         domain_kind: DomainKind,
         length: u16,
+        renewal_in_grace: bool,
     ) -> Result<DomainPrice, ContractReadError<Self::ReturnValueType>> {
         let mut result = self
             .invoke_contract_read_only(
@@ -16,6 +17,7 @@ pub trait HostCnsOracleExt<S>: HasHost<S> {
                 &GetDomainPriceParams {
                     domain_kind,
                     length,
+                    renewal_in_grace,
                 },
                 EntrypointName::new_unchecked("getYearlyDomainPrice"),
                 Amount::zero(),
@@ -28,6 +30,31 @@ pub trait HostCnsOracleExt<S>: HasHost<S> {
 
         Ok(result.result)
     }
+
+    /// Get yearly prices of several domains with a single call. Prices are returned in the order of `params`.
+    fn cns_get_yearly_domain_prices(
+        &self,
+        contract: &ContractAddress,
+        params: Vec<GetDomainPriceParams>,
+    ) -> Result<Vec<DomainPrice>, ContractReadError<Self::ReturnValueType>> {
+        let mut result = self
+            .invoke_contract_read_only(
+                contract,
+                &params,
+                EntrypointName::new_unchecked("getYearlyDomainPrices"),
+                Amount::zero(),
+            )
+            .map_err(ContractReadError::Call)?
+            .ok_or(ContractReadError::Compatibility)?;
+
+        let results = <Vec<GetDomainPriceResult>>::deserial(&mut result)
+            .map_err(|_| ContractReadError::Parse)?;
+        if results.len() != params.len() {
+            return Err(ContractReadError::Compatibility);
+        }
+
+        Ok(results.into_iter().map(|result| result.result).collect())
+    }
 }
 
 impl<S, H: HasHost<S>> HostCnsOracleExt<S> for H {}
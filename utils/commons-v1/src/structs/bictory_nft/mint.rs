@@ -1,7 +1,7 @@
 use super::*;
 
 /// Minting Data.
-#[derive(Serialize, SchemaType, Clone, Eq, PartialEq, concordium_std::hash::Hash)]
+#[derive(Debug, Serialize, SchemaType, Clone, Eq, PartialEq, concordium_std::hash::Hash)]
 pub struct MintData<T: IsTokenId> {
     /// TokenId to mint
     pub token_id: T,
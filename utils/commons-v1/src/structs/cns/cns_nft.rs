@@ -27,6 +27,12 @@ pub struct TokenParams {
     pub token_id: ContractTokenId,
 }
 
+#[derive(Serialize, SchemaType)]
+pub struct TokenListParams {
+    /// Token IDs.
+    pub token_ids: Vec<ContractTokenId>,
+}
+
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, SchemaType)]
 pub struct TokenInfo {
     pub domain: String,
@@ -46,6 +52,15 @@ pub enum SubscriptionExpiryStatus {
     Expired,
 }
 
+/// Raw subscription dates of a token, regardless of current time.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, SchemaType)]
+pub struct TokenExpiry {
+    /// Moment the subscription expires and grace period starts.
+    pub expiry: Timestamp,
+    /// Moment the grace period ends and the token can be burnt.
+    pub grace_end: Timestamp,
+}
+
 impl TokenSubscriptionStatus {
     pub fn is_owned_by(&self, address: Address) -> bool {
         self.owner == address && matches!(self.expiry, SubscriptionExpiryStatus::Owned(_))
@@ -73,5 +88,47 @@ pub struct Royalty {
 
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, SchemaType)]
 pub struct GetRoyaltiesResponse {
-    pub royalties: Vec<Royalty>,
+    pub royalties: BoundedVec<Royalty, MAX_ROYALTIES>,
+}
+
+/// Metadata URL of a CNS NFT token: the base URL followed by the domain name. Domain bytes that are not allowed in a
+/// URL path, e.g. non-ASCII characters, are percent-encoded. Metadata is generated from the current token data, e.g.
+/// its expiry, so it has no fixed content and the URL is never given a hash.
+pub fn cns_token_metadata_url(base_url: &str, domain: &str) -> MetadataUrl {
+    const HEX: &[u8; 16] = b"0123456789ABCDEF";
+    let mut url = String::with_capacity(base_url.len() + domain.len());
+    url.push_str(base_url);
+    for byte in domain.bytes() {
+        if byte.is_ascii_alphanumeric() || matches!(byte, b'-' | b'.' | b'_' | b'~') {
+            url.push(byte as char);
+        } else {
+            url.push('%');
+            url.push(HEX[usize::from(byte >> 4)] as char);
+            url.push(HEX[usize::from(byte & 0x0F)] as char);
+        }
+    }
+    MetadataUrl { url, hash: None }
+}
+
+#[concordium_cfg_test]
+mod tests {
+    use super::*;
+
+    #[concordium_test]
+    fn test_metadata_url_encoding() {
+        let base_url = "https://cns.bictory.io/metadata/";
+        claim_eq!(
+            cns_token_metadata_url(base_url, "test-1.ccd").url,
+            "https://cns.bictory.io/metadata/test-1.ccd"
+        );
+        claim_eq!(
+            cns_token_metadata_url(base_url, "\u{442}\u{435}\u{441}\u{442}.ccd").url,
+            "https://cns.bictory.io/metadata/%D1%82%D0%B5%D1%81%D1%82.ccd"
+        );
+        claim_eq!(
+            cns_token_metadata_url(base_url, "a/b?c").url,
+            "https://cns.bictory.io/metadata/a%2Fb%3Fc"
+        );
+        claim_eq!(cns_token_metadata_url(base_url, "test.ccd").hash, None);
+    }
 }
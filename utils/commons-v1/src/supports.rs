@@ -0,0 +1,121 @@
+//! CIS-0 standard support queries. `concordium-cis1` predates CIS-0, so the types mirror the ones of
+//! `concordium-cis2`.
+use concordium_std::*;
+
+/// Identifier of the CIS-0 standard.
+pub const CIS0_STANDARD_IDENTIFIER: StandardIdentifier<'static> =
+    StandardIdentifier::new_unchecked("CIS-0");
+
+/// Identifier of the CIS-1 standard.
+pub const CIS1_STANDARD_IDENTIFIER: StandardIdentifier<'static> =
+    StandardIdentifier::new_unchecked("CIS-1");
+
+/// Identifier of the Bictory authority interface, i.e. `updateAuthority` and `viewAuthority` functions.
+pub const BICTORY_AUTHORITY_STANDARD_IDENTIFIER: StandardIdentifier<'static> =
+    StandardIdentifier::new_unchecked("BICTORY-AUTHORITY");
+
+/// Identifier of a standard. Identifiers are ASCII strings of at most 255 characters.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct StandardIdentifier<'a>(&'a str);
+
+impl<'a> StandardIdentifier<'a> {
+    /// Identifier is not checked to be ASCII and to fit the length limit.
+    pub const fn new_unchecked(id: &'a str) -> Self {
+        Self(id)
+    }
+}
+
+/// Owned identifier of a standard, as received in queries.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, SchemaType)]
+pub struct StandardIdentifierOwned(#[concordium(size_length = 1)] String);
+
+impl StandardIdentifierOwned {
+    /// Identifier is not checked to be ASCII and to fit the length limit.
+    pub fn new_unchecked(id: String) -> Self {
+        Self(id)
+    }
+
+    pub fn as_standard_identifier(&self) -> StandardIdentifier {
+        StandardIdentifier(&self.0)
+    }
+}
+
+/// Parameter of the CIS-0 `supports` function.
+#[derive(Debug, Serialize, SchemaType)]
+pub struct SupportsQueryParams {
+    #[concordium(size_length = 2)]
+    pub queries: Vec<StandardIdentifierOwned>,
+}
+
+/// Support of a single queried standard.
+#[derive(Debug, Serialize, SchemaType)]
+pub enum SupportResult {
+    NoSupport,
+    Support,
+    /// Standard is implemented by other contracts on behalf of this one
+    SupportBy(#[concordium(size_length = 1)] Vec<ContractAddress>),
+}
+
+/// Result of the CIS-0 `supports` function, in the order of the queries.
+#[derive(Debug, Serialize, SchemaType)]
+pub struct SupportsQueryResponse {
+    #[concordium(size_length = 2)]
+    pub results: Vec<SupportResult>,
+}
+
+impl From<Vec<SupportResult>> for SupportsQueryResponse {
+    fn from(results: Vec<SupportResult>) -> Self {
+        SupportsQueryResponse { results }
+    }
+}
+
+/// Answer a CIS-0 `supports` query for a contract implementing `supported` standards itself.
+pub fn supports_standards(
+    supported: &[StandardIdentifier<'static>],
+    params: SupportsQueryParams,
+) -> SupportsQueryResponse {
+    let results = params
+        .queries
+        .iter()
+        .map(|id| {
+            if supported.contains(&id.as_standard_identifier()) {
+                SupportResult::Support
+            } else {
+                SupportResult::NoSupport
+            }
+        })
+        .collect::<Vec<_>>();
+    SupportsQueryResponse::from(results)
+}
+
+#[concordium_cfg_test]
+mod tests {
+    use super::*;
+
+    #[concordium_test]
+    fn test_supports_standards() {
+        let params = SupportsQueryParams {
+            queries: vec![
+                StandardIdentifierOwned::new_unchecked("CIS-0".into()),
+                StandardIdentifierOwned::new_unchecked("CIS-2".into()),
+                StandardIdentifierOwned::new_unchecked("BICTORY-AUTHORITY".into()),
+            ],
+        };
+        let response = supports_standards(
+            &[
+                CIS0_STANDARD_IDENTIFIER,
+                BICTORY_AUTHORITY_STANDARD_IDENTIFIER,
+            ],
+            params,
+        );
+
+        claim_eq!(
+            to_bytes(&response),
+            to_bytes(&SupportsQueryResponse::from(vec![
+                SupportResult::Support,
+                SupportResult::NoSupport,
+                SupportResult::Support,
+            ]))
+        );
+    }
+}
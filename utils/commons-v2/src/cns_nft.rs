@@ -1,8 +1,9 @@
+use concordium_cis2::{AdditionalData, Receiver, TokenAmountU64, Transfer, TransferParams};
 use concordium_std::*;
 
 use crate::{
-    CnsMintParams, ContractReadError, ContractTokenId, LendParams, TokenInfo, TokenParams,
-    TokenSubscriptionStatus,
+    CnsMintParams, ContractReadError, ContractTokenId, LendParams, TokenExpiry, TokenInfo,
+    TokenListParams, TokenParams, TokenSubscriptionStatus,
 };
 
 pub trait HostCnsNftExt<S>: HasHost<S> {
@@ -63,6 +64,30 @@ pub trait HostCnsNftExt<S>: HasHost<S> {
         Ok(())
     }
 
+    /// Transfer the token on behalf of `from`. Calling contract has to be an operator of `from`.
+    fn cns_nft_transfer(
+        &mut self,
+        contract: &ContractAddress,
+        token_id: ContractTokenId,
+        from: Address,
+        to: Receiver,
+    ) -> Result<(), CallContractError<Self::ReturnValueType>> {
+        self.invoke_contract(
+            contract,
+            &TransferParams(vec![Transfer {
+                token_id,
+                amount: TokenAmountU64(1),
+                from,
+                to,
+                data: AdditionalData::empty(),
+            }]),
+            EntrypointName::new_unchecked("transfer"),
+            Amount::zero(),
+        )?;
+
+        Ok(())
+    }
+
     fn cns_nft_get_token_info(
         &self,
         contract: &ContractAddress,
@@ -104,6 +129,27 @@ pub trait HostCnsNftExt<S>: HasHost<S> {
 
         Ok(result)
     }
+
+    fn cns_nft_get_token_expiries(
+        &self,
+        contract: &ContractAddress,
+        token_ids: Vec<ContractTokenId>,
+    ) -> Result<Vec<Option<TokenExpiry>>, ContractReadError<Self::ReturnValueType>> {
+        let mut result = self
+            .invoke_contract_read_only(
+                contract,
+                &TokenListParams { token_ids },
+                EntrypointName::new_unchecked("getTokenExpiries"),
+                Amount::zero(),
+            )
+            .map_err(ContractReadError::Call)?
+            .ok_or(ContractReadError::Compatibility)?;
+
+        let result = <Vec<Option<TokenExpiry>>>::deserial(&mut result)
+            .map_err(|_| ContractReadError::Parse)?;
+
+        Ok(result)
+    }
 }
 
 impl<S, H: HasHost<S>> HostCnsNftExt<S> for H {}
@@ -2,13 +2,19 @@
 #![cfg_attr(not(feature = "std"), no_std)]
 pub use crate::{
     authority::*, calculations::*, cns_nft::*, constants::*, errors::*, price_oracle::*,
-    storage::*, structs::*, types::*,
+    storage::*, structs::*, supports::*, types::*,
 };
 use concordium_cis2::*;
 use concordium_std::*;
 
 pub mod test;
 
+#[concordium_cfg_test]
+#[path = "../../commons/src/golden.rs"]
+mod golden;
+#[concordium_cfg_test]
+mod golden_mint;
+
 mod authority;
 mod calculations;
 mod cns_nft;
@@ -17,4 +23,5 @@ mod errors;
 mod price_oracle;
 mod storage;
 mod structs;
+mod supports;
 mod types;
@@ -1,7 +1,7 @@
 use super::*;
 
 /// Minting Data.
-#[derive(Serialize, SchemaType, Clone, Eq, PartialEq)]
+#[derive(Debug, Serialize, SchemaType, Clone, Eq, PartialEq)]
 pub struct MintData<T: IsTokenId> {
     /// TokenId to mint
     pub token_id: T,
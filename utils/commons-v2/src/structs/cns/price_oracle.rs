@@ -16,6 +16,8 @@ pub enum DomainPrice {
 pub struct GetDomainPriceParams {
     pub domain_kind: DomainKind,
     pub length: u16,
+    /// Price of renewing a domain in its grace period is requested
+    pub renewal_in_grace: bool,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, SchemaType)]
@@ -1,48 +1,150 @@
 use super::*;
 
 use core::convert::TryInto;
-use core::ops::{Add, AddAssign, Mul};
+use core::ops::Mul;
 
+/// Micro percent in a percent.
+const MICRO_PERCENT_PER_PERCENT: u64 = 1_000_000;
+/// Micro percent in a basis point, i.e. in 0.01%.
+const MICRO_PERCENT_PER_BASIS_POINT: u64 = 10_000;
+
+/// Fixed-point percentage with micro percent precision, `100_000_000` is 100%. Values above 100% are allowed, so that
+/// sums of percentages can be compared with `Percentage::HUNDRED`.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, SchemaType)]
 pub struct Percentage(u64);
 
 impl Percentage {
-    pub fn from_micro_percent(micro_percent: u64) -> Self {
+    pub const ZERO: Percentage = Percentage(0);
+    pub const HUNDRED: Percentage = Percentage(100 * MICRO_PERCENT_PER_PERCENT);
+
+    pub const fn from_micro_percent(micro_percent: u64) -> Self {
         Self(micro_percent)
     }
 
-    pub fn from_percent(percent: u64) -> Self {
-        Self(percent * 1_000_000)
+    /// Saturates at the maximum percentage.
+    pub const fn from_percent(percent: u64) -> Self {
+        Self(percent.saturating_mul(MICRO_PERCENT_PER_PERCENT))
+    }
+
+    /// Saturates at the maximum percentage.
+    pub const fn from_basis_points(basis_points: u64) -> Self {
+        Self(basis_points.saturating_mul(MICRO_PERCENT_PER_BASIS_POINT))
     }
 
+    pub const fn micro_percent(self) -> u64 {
+        self.0
+    }
+
+    /// Percentage that `amount` is of `of`. Saturates if `of` is zero or the percentage doesn't fit.
     pub fn of_amount(amount: Amount, of: Amount) -> Percentage {
         Percentage(
-            (amount.micro_ccd as u128 * 100_000_000)
+            (amount.micro_ccd as u128 * Self::HUNDRED.0 as u128)
                 .checked_div(of.micro_ccd as u128)
                 .and_then(|res| res.try_into().ok())
                 .unwrap_or(u64::MAX),
         )
     }
-}
 
-impl Add for Percentage {
-    type Output = Self;
+    pub fn checked_add(self, rhs: Self) -> Option<Self> {
+        self.0.checked_add(rhs.0).map(Percentage)
+    }
+
+    pub fn checked_sub(self, rhs: Self) -> Option<Self> {
+        self.0.checked_sub(rhs.0).map(Percentage)
+    }
 
-    fn add(self, rhs: Self) -> Self::Output {
-        Percentage(self.0 + rhs.0)
+    /// Share of the amount, rounded down. Results in `None` if the share doesn't fit in an amount, which is only
+    /// possible for percentages above 100%.
+    pub fn checked_mul_amount(self, amount: Amount) -> Option<Amount> {
+        (amount.micro_ccd as u128 * self.0 as u128 / Self::HUNDRED.0 as u128)
+            .try_into()
+            .ok()
+            .map(Amount::from_micro_ccd)
+    }
+
+    /// Share of the amount, rounded down. Saturates at the maximum amount.
+    pub fn saturating_mul_amount(self, amount: Amount) -> Amount {
+        self.checked_mul_amount(amount)
+            .unwrap_or(Amount::from_micro_ccd(u64::MAX))
     }
 }
 
-impl AddAssign for Percentage {
-    fn add_assign(&mut self, rhs: Self) {
-        self.0 += rhs.0
+/// Percentage of a percentage, e.g. a share of a royalty. Saturates at the maximum percentage.
+impl Mul for Percentage {
+    type Output = Self;
+
+    fn mul(self, rhs: Self) -> Self::Output {
+        Percentage(
+            (self.0 as u128 * rhs.0 as u128 / Self::HUNDRED.0 as u128)
+                .try_into()
+                .unwrap_or(u64::MAX),
+        )
     }
 }
 
+/// Share of the amount, see `Percentage::saturating_mul_amount`.
 impl Mul<Amount> for Percentage {
     type Output = Amount;
 
     fn mul(self, rhs: Amount) -> Self::Output {
-        Amount::from_micro_ccd((rhs.micro_ccd as u128 * self.0 as u128 / 100_000_000) as u64)
+        self.saturating_mul_amount(rhs)
+    }
+}
+
+#[concordium_cfg_test]
+mod tests {
+    use super::*;
+
+    #[concordium_test]
+    fn test_constructors() {
+        claim_eq!(Percentage::from_percent(100), Percentage::HUNDRED);
+        claim_eq!(Percentage::from_basis_points(10_000), Percentage::HUNDRED);
+        claim_eq!(
+            Percentage::from_basis_points(250),
+            Percentage::from_micro_percent(2_500_000)
+        );
+        claim_eq!(
+            Percentage::from_percent(u64::MAX),
+            Percentage::from_micro_percent(u64::MAX)
+        );
+    }
+
+    #[concordium_test]
+    fn test_amount_share() {
+        let ccd = Amount::from_ccd;
+        claim_eq!(Percentage::from_percent(5) * ccd(100), ccd(5));
+        claim_eq!(
+            Percentage::from_basis_points(1) * Amount::from_micro_ccd(9_999),
+            Amount::zero()
+        );
+
+        let max = Amount::from_micro_ccd(u64::MAX);
+        claim_eq!(Percentage::HUNDRED.checked_mul_amount(max), Some(max));
+        claim_eq!(Percentage::from_percent(200).checked_mul_amount(max), None);
+        claim_eq!(Percentage::from_percent(200) * max, max);
+
+        claim_eq!(
+            Percentage::of_amount(ccd(5), ccd(100)),
+            Percentage::from_percent(5)
+        );
+    }
+
+    #[concordium_test]
+    fn test_checked_ops() {
+        let max = Percentage::from_micro_percent(u64::MAX);
+        claim_eq!(
+            Percentage::from_percent(60).checked_add(Percentage::from_percent(50)),
+            Some(Percentage::from_percent(110))
+        );
+        claim_eq!(max.checked_add(Percentage::from_micro_percent(1)), None);
+        claim_eq!(
+            Percentage::ZERO.checked_sub(Percentage::from_micro_percent(1)),
+            None
+        );
+        claim_eq!(
+            Percentage::from_percent(50) * Percentage::from_percent(10),
+            Percentage::from_percent(5)
+        );
+        claim_eq!(max * max, max);
     }
 }
@@ -218,6 +218,25 @@ pub struct StorageGetEntryResult {
     pub prefix: Bytes,
     #[concordium(size_length = 2)]
     pub entries: Vec<MaybeStorageEntry>,
+    /// Prefix has passed the expiry time set by its writer. Expired data is still returned, it's up to the caller to
+    /// decide whether to use it.
+    pub expired: bool,
+}
+
+#[derive(Debug, Clone, Serialize, SchemaType, PartialEq, Eq)]
+pub struct SetExpiryParams {
+    pub prefix: Bytes,
+    /// Time after which prefix data is considered stale. `None` removes the expiry.
+    pub expiry: Option<Timestamp>,
+}
+
+/// Parameter of the storage `replaceOperators` function.
+#[derive(Debug, Clone, Serialize, SchemaType, PartialEq, Eq)]
+pub struct ReplaceOperatorsParams {
+    pub prefix: Bytes,
+    /// New operators of the prefix. All previous operators are removed, an empty list leaves the prefix without
+    /// operators.
+    pub operators: Vec<Address>,
 }
 
 #[derive(Debug, Clone, Serialize, SchemaType, PartialEq, Eq)]
@@ -225,3 +244,50 @@ pub struct MaybeStorageEntry {
     pub key: Bytes,
     pub value: Option<Bytes>,
 }
+
+/// Writer of a stored value, recorded by the storage contract when the value is written.
+#[derive(Debug, Clone, Copy, Serialize, SchemaType, PartialEq, Eq)]
+pub struct StorageProvenance {
+    pub writer: Address,
+    /// Sequence number of the write that last modified the value. Increases with every write to the storage
+    pub sequence: u64,
+}
+
+#[derive(Debug, Clone, Serialize, SchemaType, PartialEq, Eq)]
+pub struct AttestedStorageEntry {
+    pub key: Bytes,
+    pub value: Option<Bytes>,
+    /// `None` if the key is missing or the value was written before writers were recorded
+    pub provenance: Option<StorageProvenance>,
+}
+
+/// Return value of the storage `getWithProvenance` function.
+#[derive(Debug, Clone, Serialize, SchemaType, PartialEq, Eq)]
+pub struct StorageProvenanceResult {
+    pub prefix: Bytes,
+    #[concordium(size_length = 2)]
+    pub entries: Vec<AttestedStorageEntry>,
+    /// Prefix has passed the expiry time set by its writer
+    pub expired: bool,
+}
+
+/// Parameter of the storage `getPage` function. Entries are returned in ascending byte order of their serialized keys.
+#[derive(Debug, Clone, Serialize, SchemaType, PartialEq, Eq)]
+pub struct StoragePageParams {
+    pub prefix: Bytes,
+    /// Return entries with keys following this key. `None` starts from the first key
+    pub start_after_key: Option<Bytes>,
+    /// Maximum number of entries to return
+    pub limit: u16,
+}
+
+#[derive(Debug, Clone, Serialize, SchemaType, PartialEq, Eq)]
+pub struct StoragePage {
+    pub prefix: Bytes,
+    #[concordium(size_length = 2)]
+    pub entries: Vec<StorageEntry>,
+    /// Cursor for the next page, i.e. the last returned key. `None` if there are no more entries
+    pub next_key: Option<Bytes>,
+    /// Prefix has passed the expiry time set by its writer
+    pub expired: bool,
+}
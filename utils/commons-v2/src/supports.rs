@@ -0,0 +1,58 @@
+//! CIS-0 standard support queries, answered the same way by all contracts.
+use concordium_cis2::*;
+use concordium_std::*;
+
+/// Identifier of the Bictory authority interface, i.e. `updateAuthority` and `viewAuthority` functions.
+pub const BICTORY_AUTHORITY_STANDARD_IDENTIFIER: StandardIdentifier<'static> =
+    StandardIdentifier::new_unchecked("BICTORY-AUTHORITY");
+
+/// Answer a CIS-0 `supports` query for a contract implementing `supported` standards itself.
+pub fn supports_standards(
+    supported: &[StandardIdentifier<'static>],
+    params: SupportsQueryParams,
+) -> SupportsQueryResponse {
+    let results = params
+        .queries
+        .iter()
+        .map(|id| {
+            if supported.contains(&id.as_standard_identifier()) {
+                SupportResult::Support
+            } else {
+                SupportResult::NoSupport
+            }
+        })
+        .collect::<Vec<_>>();
+    SupportsQueryResponse::from(results)
+}
+
+#[concordium_cfg_test]
+mod tests {
+    use super::*;
+
+    #[concordium_test]
+    fn test_supports_standards() {
+        let params = SupportsQueryParams {
+            queries: vec![
+                StandardIdentifierOwned::new_unchecked("CIS-0".into()),
+                StandardIdentifierOwned::new_unchecked("CIS-1".into()),
+                StandardIdentifierOwned::new_unchecked("BICTORY-AUTHORITY".into()),
+            ],
+        };
+        let response = supports_standards(
+            &[
+                CIS0_STANDARD_IDENTIFIER,
+                BICTORY_AUTHORITY_STANDARD_IDENTIFIER,
+            ],
+            params,
+        );
+
+        claim_eq!(
+            to_bytes(&response),
+            to_bytes(&SupportsQueryResponse::from(vec![
+                SupportResult::Support,
+                SupportResult::NoSupport,
+                SupportResult::Support,
+            ]))
+        );
+    }
+}
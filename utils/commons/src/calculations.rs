@@ -1,46 +1,86 @@
 use super::*;
 
-// convert the royalty percentage and amount to pay into a payout
-fn royalty_to_payout(price: Amount, royalty: u64) -> Amount {
-    let ttl_per = Amount::from_ccd(100);
-    Amount::from_micro_ccd(price.micro_ccd * royalty / ttl_per.micro_ccd)
-}
-
-pub fn calc_shares(
-    price: Amount,
-    creator_royalty: u64,
-    minter_royalty: u64,
-    bictory_royalty: u64,
-) -> Shares {
+/// Split the price into royalty payouts. Owner receives the whole price until royalties are subtracted with
+/// `Shares::adjust_owner_share`.
+pub fn calc_shares(price: Amount, royalties: &[Royalty], bictory_royalty: Percentage) -> Shares {
     Shares {
-        creator: royalty_to_payout(price, creator_royalty),
-        minter: royalty_to_payout(price, minter_royalty),
+        royalties: royalties
+            .iter()
+            .map(|royalty| {
+                (
+                    royalty.beneficiary,
+                    royalty.percentage.saturating_mul_amount(price),
+                )
+            })
+            .collect(),
         owner: price,
-        bictory: royalty_to_payout(price, bictory_royalty),
+        bictory: bictory_royalty.saturating_mul_amount(price),
     }
 }
 
+/// Sum of the royalty percentages. Returns `None` if the sum overflows.
+pub fn royalty_total(royalties: &[Royalty]) -> Option<Percentage> {
+    royalties
+        .iter()
+        .try_fold(Percentage::ZERO, |total, royalty| {
+            total.checked_add(royalty.percentage)
+        })
+}
+
 #[concordium_cfg_test]
 mod tests {
     use super::*;
 
+    const CREATOR: AccountAddress = AccountAddress([1; 32]);
+    const MINTER: AccountAddress = AccountAddress([2; 32]);
+    const COLLABORATOR: AccountAddress = AccountAddress([3; 32]);
+
     #[concordium_test]
     fn test_shares() {
         let expected_shares = Shares {
-            creator: Amount::from_ccd(5),
-            minter: Amount::from_ccd(2),
-            owner: Amount::from_ccd(91),
+            royalties: vec![
+                (CREATOR, Amount::from_ccd(5)),
+                (MINTER, Amount::from_ccd(2)),
+                (COLLABORATOR, Amount::from_ccd(1)),
+            ],
+            owner: Amount::from_ccd(90),
             bictory: Amount::from_ccd(2),
         };
 
+        let royalties = [
+            Royalty {
+                beneficiary: CREATOR,
+                percentage: Percentage::from_percent(5),
+            },
+            Royalty {
+                beneficiary: MINTER,
+                percentage: Percentage::from_percent(2),
+            },
+            Royalty {
+                beneficiary: COLLABORATOR,
+                percentage: Percentage::from_basis_points(100),
+            },
+        ];
         let mut actual_shares = calc_shares(
             Amount::from_ccd(100),
-            Amount::from_ccd(5).micro_ccd,
-            Amount::from_ccd(2).micro_ccd,
-            Amount::from_ccd(2).micro_ccd,
+            &royalties,
+            Percentage::from_basis_points(200),
         );
-        actual_shares.adjust_owner_share();
+        claim_eq!(actual_shares.adjust_owner_share(), Ok(()));
 
         claim_eq!(expected_shares, actual_shares);
+        claim_eq!(royalty_total(&royalties), Some(Percentage::from_percent(8)));
+        claim_eq!(royalty_total(&[]), Some(Percentage::ZERO));
+
+        // Royalties above the price can't be paid from the owner share
+        let mut shares = calc_shares(
+            Amount::from_ccd(100),
+            &royalties,
+            Percentage::from_percent(95),
+        );
+        claim_eq!(
+            shares.adjust_owner_share(),
+            Err(CustomContractError::InvalidRoyalty.into())
+        );
     }
 }
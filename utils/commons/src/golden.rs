@@ -0,0 +1,131 @@
+//! Golden vectors of externally visible types. Deployed contracts and SDKs depend on these exact encodings, so a
+//! failing test here means a wire format change, not a test to update.
+//!
+//! This file is the single source of the vectors shared by `commons`, `commons-v1` and `commons-v2`, the other crates
+//! include it by path. Vectors of types that differ between the crates, e.g. token amounts, are in `golden_mint.rs` of
+//! each crate.
+use super::*;
+
+/// Check that the value encodes to exactly the concatenated `fields` and that the encoding decodes back to it.
+pub(crate) fn check_golden<T: Serial + Deserial>(value: &T, fields: &[&[u8]]) {
+    let golden = fields.concat();
+    claim_eq!(to_bytes(value), golden);
+    let decoded: T = from_bytes(&golden).expect_report("Golden vector should decode");
+    claim_eq!(to_bytes(&decoded), golden);
+}
+
+#[concordium_test]
+fn test_golden_token() {
+    let token = Token {
+        contract: ContractAddress {
+            index: 7,
+            subindex: 0,
+        },
+        id: TokenIdVec(vec![0, 1]),
+    };
+    check_golden(&token, &[&[7, 0, 0, 0, 0, 0, 0, 0], &[0; 8], &[2, 0, 1]]);
+}
+
+#[concordium_test]
+fn test_golden_storage() {
+    let entries = StorageEntries {
+        prefix: Bytes(b"pre".to_vec()),
+        entries: vec![StorageEntry {
+            key: Bytes(vec![1]),
+            value: Bytes(vec![2, 3]),
+        }],
+    };
+    check_golden(
+        &entries,
+        &[&[3, 0], b"pre", &[1, 0], &[1, 0, 1], &[2, 0, 2, 3]],
+    );
+
+    let keys = StorageKeys::some(Bytes(b"pre".to_vec()), vec![Bytes(vec![1])]);
+    check_golden(&keys, &[&[3, 0], b"pre", &[1], &[1, 0], &[1, 0, 1]]);
+
+    let keys = StorageKeys::all(Bytes(b"pre".to_vec()));
+    check_golden(&keys, &[&[3, 0], b"pre", &[0]]);
+}
+
+#[concordium_test]
+fn test_golden_authority() {
+    let params = AuthorityUpdateParams {
+        field: AuthorityField::Admin,
+        kind: AuthorityUpdateKind::Add,
+        address: Address::Account(AccountAddress([2; 32])),
+    };
+    check_golden(&params, &[&[1], &[1], &[0], &[2; 32]]);
+
+    let params = AuthorityUpdateParams {
+        field: AuthorityField::Admin,
+        kind: AuthorityUpdateKind::Accept,
+        address: Address::Account(AccountAddress([2; 32])),
+    };
+    check_golden(&params, &[&[1], &[2], &[0], &[2; 32]]);
+
+    let params = AuthorityViewParams {
+        field: AuthorityField::Maintainer,
+        skip: 1,
+        show: 10,
+    };
+    check_golden(&params, &[&[0], &[1, 0, 0, 0], &[10, 0, 0, 0]]);
+
+    let params = AuthorityViewParams {
+        field: AuthorityField::PendingAdmin,
+        skip: 0,
+        show: 1,
+    };
+    check_golden(&params, &[&[2], &[0; 4], &[1, 0, 0, 0]]);
+}
+
+#[concordium_test]
+fn test_golden_domain_price() {
+    let params = GetDomainPriceParams {
+        domain_kind: DomainKind::Subdomain,
+        length: 5,
+        renewal_in_grace: true,
+    };
+    check_golden(&params, &[&[1], &[5, 0], &[1]]);
+
+    let result = GetDomainPriceResult {
+        result: DomainPrice::Amount(Amount::from_micro_ccd(1000)),
+    };
+    check_golden(&result, &[&[1], &[232, 3, 0, 0, 0, 0, 0, 0]]);
+    check_golden(&DomainPrice::Limited, &[&[0]]);
+}
+
+#[concordium_test]
+fn test_golden_percentage() {
+    check_golden(
+        &Percentage::from_percent(5),
+        &[&[0x40, 0x4b, 0x4c, 0, 0, 0, 0, 0]],
+    );
+}
+
+#[concordium_test]
+fn test_golden_supports() {
+    let params = SupportsQueryParams {
+        queries: vec![StandardIdentifierOwned::new_unchecked("CIS-1".into())],
+    };
+    check_golden(&params, &[&[1, 0], &[5], b"CIS-1"]);
+
+    let response = SupportsQueryResponse::from(vec![
+        SupportResult::NoSupport,
+        SupportResult::Support,
+        SupportResult::SupportBy(vec![ContractAddress {
+            index: 5,
+            subindex: 0,
+        }]),
+    ]);
+    check_golden(
+        &response,
+        &[
+            &[3, 0],
+            &[0],
+            &[1],
+            &[2, 1],
+            &[5, 0, 0, 0, 0, 0, 0, 0],
+            &[0; 8],
+        ],
+    );
+}
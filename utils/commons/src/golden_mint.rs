@@ -0,0 +1,90 @@
+//! Golden vectors of mint parameters, which differ between the commons crates. Shared vectors are in `golden.rs`.
+use super::*;
+
+use core::convert::TryInto;
+
+use crate::golden::check_golden;
+
+/// Quantities are CIS-2 token amounts, encoded as LEB128.
+const QUANTITY: ContractTokenAmount = TokenAmountU64(300);
+const QUANTITY_BYTES: &[u8] = &[0xac, 0x02];
+
+/// Check that the concatenated `fields`, an encoding from an older version of the type, still decode to the value.
+fn check_golden_decode<T: Serial + Deserial>(value: &T, fields: &[&[u8]]) {
+    let decoded: T = from_bytes(&fields.concat()).expect_report("Golden vector should decode");
+    claim_eq!(to_bytes(&decoded), to_bytes(value));
+}
+
+#[concordium_test]
+fn test_golden_mint_params() {
+    let params = MintParams {
+        token_id: TokenIdVec(vec![0, 1]),
+        owner: Address::Account(AccountAddress([1; 32])),
+        creator: Address::Contract(ContractAddress {
+            index: 5,
+            subindex: 0,
+        }),
+        creator_royalty: 250,
+        minter_royalty: 100,
+        cid: b"cid".to_vec(),
+        quantity: QUANTITY,
+        royalties: RoyaltyRecipients::new(),
+    };
+    check_golden_decode(
+        &params,
+        &[
+            &[2, 0, 1],
+            &[0],
+            &[1; 32],
+            &[1],
+            &[5, 0, 0, 0, 0, 0, 0, 0],
+            &[0; 8],
+            &[250, 0, 0, 0],
+            &[100, 0, 0, 0],
+            &[3, 0, 0, 0],
+            b"cid",
+            QUANTITY_BYTES,
+        ],
+    );
+}
+
+#[concordium_test]
+fn test_golden_mint_params_royalties() {
+    let params = MintParams {
+        token_id: TokenIdVec(vec![0, 1]),
+        owner: Address::Account(AccountAddress([1; 32])),
+        creator: Address::Contract(ContractAddress {
+            index: 5,
+            subindex: 0,
+        }),
+        creator_royalty: 250,
+        minter_royalty: 100,
+        cid: b"cid".to_vec(),
+        quantity: QUANTITY,
+        royalties: vec![Royalty {
+            beneficiary: AccountAddress([2; 32]),
+            percentage: Percentage::from_percent(1),
+        }]
+        .try_into()
+        .expect_report("Too many royalties"),
+    };
+    check_golden(
+        &params,
+        &[
+            &[2, 0, 1],
+            &[0],
+            &[1; 32],
+            &[1],
+            &[5, 0, 0, 0, 0, 0, 0, 0],
+            &[0; 8],
+            &[250, 0, 0, 0],
+            &[100, 0, 0, 0],
+            &[3, 0, 0, 0],
+            b"cid",
+            QUANTITY_BYTES,
+            &[1, 0, 0, 0],
+            &[2; 32],
+            &[0x40, 0x42, 0x0f, 0, 0, 0, 0, 0],
+        ],
+    );
+}
@@ -1,20 +1,31 @@
 //! It exposes all common structs and types.
 #![cfg_attr(not(feature = "std"), no_std)]
+#[cfg(feature = "std")]
+pub use crate::simulation::*;
 pub use crate::{
-    authority::*, calculations::*, cns_nft::*, constants::*, errors::*, price_oracle::*,
-    storage::*, structs::*, types::*,
+    authority::*, calculations::*, cns_nft::*, constants::*, errors::*, logic::*, price_oracle::*,
+    storage::*, structs::*, supports::*, types::*,
 };
 use concordium_cis2::*;
 use concordium_std::*;
 
 pub mod test;
 
+#[concordium_cfg_test]
+mod golden;
+#[concordium_cfg_test]
+mod golden_mint;
+
 mod authority;
 mod calculations;
 mod cns_nft;
 mod constants;
 mod errors;
+mod logic;
 mod price_oracle;
+#[cfg(feature = "std")]
+mod simulation;
 mod storage;
 mod structs;
+mod supports;
 mod types;
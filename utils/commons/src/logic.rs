@@ -0,0 +1,242 @@
+//! Pure state transitions shared by the marketplace contracts. Functions here don't touch contract state, so they
+//! can be exercised natively, e.g. by the models in `simulation`.
+use super::*;
+
+use core::cmp::Ordering;
+
+/// Outcome of taking tokens from a holder.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Debit {
+    /// Holder keeps the remaining amount
+    Partial(ContractTokenAmount),
+    /// Whole balance of the holder was taken
+    All,
+}
+
+/// Take `amount` of tokens from `balance`. Results in an error if the balance is too low.
+pub fn debit_balance(
+    balance: ContractTokenAmount,
+    amount: ContractTokenAmount,
+) -> ContractResult<Debit> {
+    match balance.cmp(&amount) {
+        Ordering::Equal => Ok(Debit::All),
+        Ordering::Greater => Ok(Debit::Partial(ContractTokenAmount::from(
+            balance.0 - amount.0,
+        ))),
+        Ordering::Less => Err(ContractError::InsufficientFunds),
+    }
+}
+
+/// Add `amount` of tokens to `balance`. Results in an error if the balance overflows.
+pub fn credit_balance(
+    balance: ContractTokenAmount,
+    amount: ContractTokenAmount,
+) -> ContractResult<ContractTokenAmount> {
+    balance
+        .0
+        .checked_add(amount.0)
+        .map(ContractTokenAmount::from)
+        .ok_or_else(|| CustomContractError::Overflow.into())
+}
+
+/// Bids after the highest bidder was challenged.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ChallengeOutcome {
+    /// Challenger took the lead
+    pub challenger_leads: bool,
+    pub challenger_bid: Amount,
+    /// New bid of the previous leader, if there was one
+    pub leader_bid: Option<Amount>,
+    pub highest_bid: Amount,
+}
+
+/// Resolve a challenge of the leader with maximum `leader_max` by a challenger with maximum `challenger_max`, which
+/// must exceed `highest_bid`. Proxy bidders bid only `increment` more than the other side, up to their maximum, but at
+/// least the `reserve` if their maximum allows. The leader is always treated as a proxy bidder, since a direct bid is
+/// equal to its maximum. If both maximums are equal, the leader keeps the lead.
+pub fn resolve_challenge(
+    leader_max: Option<Amount>,
+    highest_bid: Amount,
+    challenger_max: Amount,
+    challenger_is_proxy: bool,
+    increment: Amount,
+    reserve: Amount,
+) -> ChallengeOutcome {
+    let raise = |amount: Amount| {
+        Amount::from_micro_ccd(amount.micro_ccd.saturating_add(increment.micro_ccd)).max(reserve)
+    };
+
+    match leader_max {
+        Some(leader_max) if leader_max >= challenger_max => {
+            let leader_bid = leader_max.min(raise(challenger_max));
+            ChallengeOutcome {
+                challenger_leads: false,
+                challenger_bid: challenger_max,
+                leader_bid: Some(leader_bid),
+                highest_bid: leader_bid,
+            }
+        }
+        _ => {
+            let competing = leader_max.unwrap_or(highest_bid);
+            let challenger_bid = if challenger_is_proxy {
+                challenger_max.min(raise(competing))
+            } else {
+                challenger_max
+            };
+            ChallengeOutcome {
+                challenger_leads: true,
+                challenger_bid,
+                leader_bid: leader_max,
+                highest_bid: challenger_bid,
+            }
+        }
+    }
+}
+
+/// Amount returned to a bidder when the auction is settled: the whole deposit for other bidders and the unused part
+/// of it for the winner.
+pub fn settlement_refund(
+    deposit: Amount,
+    winning_bid: Amount,
+    is_winner: bool,
+) -> ContractResult<Amount> {
+    if !is_winner {
+        return Ok(deposit);
+    }
+    deposit
+        .micro_ccd
+        .checked_sub(winning_bid.micro_ccd)
+        .map(Amount::from_micro_ccd)
+        .ok_or_else(|| CustomContractError::BidMapError.into())
+}
+
+/// Split the sale price into shares, with the owner receiving what is left after royalties. Results in an error if
+/// royalties exceed 100%.
+pub fn settle_shares(
+    price: Amount,
+    royalties: &[Royalty],
+    bictory_royalty: Percentage,
+) -> ContractResult<Shares> {
+    let total = royalty_total(royalties)
+        .and_then(|sum| sum.checked_add(bictory_royalty))
+        .ok_or(CustomContractError::Overflow)?;
+    ensure!(
+        total <= Percentage::HUNDRED,
+        CustomContractError::InvalidRoyalty.into()
+    );
+    // Shares are rounded down, so their sum never exceeds the price
+    let mut shares = calc_shares(price, royalties, bictory_royalty);
+    shares.adjust_owner_share()?;
+    Ok(shares)
+}
+
+#[concordium_cfg_test]
+mod tests {
+    use super::*;
+
+    const INCREMENT: Amount = Amount::from_micro_ccd(1);
+    const NO_RESERVE: Amount = Amount::from_micro_ccd(0);
+
+    #[concordium_test]
+    fn test_debit_credit() {
+        let amount = |value: u64| ContractTokenAmount::from(value);
+        claim_eq!(
+            debit_balance(amount(3), amount(1)),
+            Ok(Debit::Partial(amount(2)))
+        );
+        claim_eq!(debit_balance(amount(3), amount(3)), Ok(Debit::All));
+        claim_eq!(
+            debit_balance(amount(1), amount(3)),
+            Err(ContractError::InsufficientFunds)
+        );
+        claim_eq!(credit_balance(amount(1), amount(2)), Ok(amount(3)));
+        claim_eq!(
+            credit_balance(amount(u64::MAX), amount(1)),
+            Err(CustomContractError::Overflow.into())
+        );
+    }
+
+    #[concordium_test]
+    fn test_resolve_challenge() {
+        let ccd = Amount::from_ccd;
+
+        // Proxy challenger outbids direct leader by the increment
+        let outcome = resolve_challenge(Some(ccd(5)), ccd(5), ccd(10), true, INCREMENT, NO_RESERVE);
+        claim!(outcome.challenger_leads);
+        claim_eq!(outcome.challenger_bid, ccd(5) + INCREMENT);
+        claim_eq!(outcome.highest_bid, ccd(5) + INCREMENT);
+
+        // Proxy leader with higher maximum keeps the lead
+        let outcome =
+            resolve_challenge(Some(ccd(10)), ccd(5), ccd(7), false, INCREMENT, NO_RESERVE);
+        claim!(!outcome.challenger_leads);
+        claim_eq!(outcome.leader_bid, Some(ccd(7) + INCREMENT));
+        claim_eq!(outcome.highest_bid, ccd(7) + INCREMENT);
+
+        // Equal maximums keep the earlier bidder in the lead without exceeding their maximum
+        let outcome = resolve_challenge(Some(ccd(7)), ccd(5), ccd(7), true, INCREMENT, NO_RESERVE);
+        claim!(!outcome.challenger_leads);
+        claim_eq!(outcome.highest_bid, ccd(7));
+
+        // Increment never overflows
+        let max = Amount::from_micro_ccd(u64::MAX);
+        let outcome = resolve_challenge(Some(max), ccd(5), max, true, INCREMENT, NO_RESERVE);
+        claim_eq!(outcome.highest_bid, max);
+
+        // Proxy bids are raised to the reserve as far as the maximum allows
+        let outcome = resolve_challenge(None, ccd(0), ccd(10), true, INCREMENT, ccd(8));
+        claim_eq!(outcome.highest_bid, ccd(8));
+        let outcome = resolve_challenge(Some(ccd(6)), ccd(5), ccd(7), true, INCREMENT, ccd(8));
+        claim_eq!(outcome.highest_bid, ccd(7));
+        let outcome = resolve_challenge(Some(ccd(10)), ccd(5), ccd(7), false, INCREMENT, ccd(9));
+        claim!(!outcome.challenger_leads);
+        claim_eq!(outcome.highest_bid, ccd(9));
+    }
+
+    #[concordium_test]
+    fn test_settlement() {
+        let ccd = Amount::from_ccd;
+        claim_eq!(settlement_refund(ccd(10), ccd(7), true), Ok(ccd(3)));
+        claim_eq!(settlement_refund(ccd(10), ccd(7), false), Ok(ccd(10)));
+        claim_eq!(
+            settlement_refund(ccd(5), ccd(7), true),
+            Err(CustomContractError::BidMapError.into())
+        );
+
+        let royalty = |byte: u8, percent: u64| Royalty {
+            beneficiary: AccountAddress([byte; 32]),
+            percentage: Percentage::from_percent(percent),
+        };
+        claim_eq!(
+            settle_shares(
+                ccd(100),
+                &[royalty(1, 5), royalty(2, 2), royalty(3, 1)],
+                Percentage::from_percent(2)
+            ),
+            Ok(Shares {
+                royalties: vec![
+                    (AccountAddress([1; 32]), ccd(5)),
+                    (AccountAddress([2; 32]), ccd(2)),
+                    (AccountAddress([3; 32]), ccd(1)),
+                ],
+                owner: ccd(90),
+                bictory: ccd(2),
+            })
+        );
+        claim_eq!(
+            settle_shares(
+                ccd(100),
+                &[royalty(1, 60), royalty(2, 30), royalty(3, 10)],
+                Percentage::from_percent(1)
+            ),
+            Err(CustomContractError::InvalidRoyalty.into())
+        );
+        // Large prices don't overflow the royalty calculation
+        let max = Amount::from_micro_ccd(u64::MAX);
+        claim!(settle_shares(max, &[royalty(1, 5)], Percentage::ZERO).is_ok());
+        claim_eq!(
+            settle_shares(max, &[royalty(1, 200)], Percentage::ZERO),
+            Err(CustomContractError::InvalidRoyalty.into())
+        );
+    }
+}
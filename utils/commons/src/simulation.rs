@@ -0,0 +1,331 @@
+//! In-memory models of the marketplace contracts built on the state transitions from `logic`. Only compiled with the
+//! `std` feature, so simulators and fuzzers can run large scenarios natively without the contract test host.
+use super::*;
+
+use std::collections::{BTreeMap, BTreeSet};
+
+/// Token balances of a multi-token contract.
+#[derive(Debug, Clone)]
+pub struct SimLedger<T: Ord + Clone, H: Ord + Clone> {
+    tokens: BTreeSet<T>,
+    balances: BTreeMap<(T, H), ContractTokenAmount>,
+}
+
+impl<T: Ord + Clone, H: Ord + Clone> Default for SimLedger<T, H> {
+    fn default() -> Self {
+        Self {
+            tokens: BTreeSet::new(),
+            balances: BTreeMap::new(),
+        }
+    }
+}
+
+impl<T: Ord + Clone, H: Ord + Clone> SimLedger<T, H> {
+    pub fn balance(&self, token_id: &T, holder: &H) -> ContractTokenAmount {
+        self.balances
+            .get(&(token_id.clone(), holder.clone()))
+            .copied()
+            .unwrap_or_else(|| ContractTokenAmount::from(0))
+    }
+
+    /// Sum of all balances of the token.
+    pub fn supply(&self, token_id: &T) -> u128 {
+        self.balances
+            .iter()
+            .filter(|((id, _), _)| id == token_id)
+            .map(|(_, amount)| amount.0 as u128)
+            .sum()
+    }
+
+    /// Mint a new token. Results in an error if the token ID already exists.
+    pub fn mint(
+        &mut self,
+        token_id: T,
+        owner: H,
+        quantity: ContractTokenAmount,
+    ) -> ContractResult<()> {
+        ensure!(
+            self.tokens.insert(token_id.clone()),
+            CustomContractError::TokenIdAlreadyExists.into()
+        );
+        if quantity.0 > 0 {
+            self.balances.insert((token_id, owner), quantity);
+        }
+        Ok(())
+    }
+
+    pub fn transfer(
+        &mut self,
+        token_id: &T,
+        from: &H,
+        to: &H,
+        amount: ContractTokenAmount,
+    ) -> ContractResult<()> {
+        ensure!(
+            self.tokens.contains(token_id),
+            ContractError::InvalidTokenId
+        );
+        // A zero transfer does not modify the state.
+        if amount.0 == 0 {
+            return Ok(());
+        }
+        self.debit(token_id, from, amount)?;
+        let balance = credit_balance(self.balance(token_id, to), amount)?;
+        self.balances
+            .insert((token_id.clone(), to.clone()), balance);
+        Ok(())
+    }
+
+    pub fn burn(
+        &mut self,
+        token_id: &T,
+        owner: &H,
+        amount: ContractTokenAmount,
+    ) -> ContractResult<()> {
+        ensure!(
+            self.tokens.contains(token_id),
+            ContractError::InvalidTokenId
+        );
+        self.debit(token_id, owner, amount)
+    }
+
+    fn debit(
+        &mut self,
+        token_id: &T,
+        holder: &H,
+        amount: ContractTokenAmount,
+    ) -> ContractResult<()> {
+        let key = (token_id.clone(), holder.clone());
+        match debit_balance(self.balance(token_id, holder), amount)? {
+            Debit::All => self.balances.remove(&key),
+            Debit::Partial(remaining) => self.balances.insert(key, remaining),
+        };
+        Ok(())
+    }
+}
+
+/// Result of settling an auction.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Settlement<B> {
+    /// Winner together with the winning bid
+    pub winner: Option<(B, Amount)>,
+    /// Amounts returned to bidders, excluding zero refunds
+    pub refunds: Vec<(B, Amount)>,
+}
+
+/// Bidding of a single auction, with the same rules as `BictoryAuction`.
+#[derive(Debug, Clone)]
+pub struct SimAuction<B: Ord + Copy> {
+    increment: Amount,
+    reserve: Amount,
+    bids: BTreeMap<B, Amount>,
+    proxy_max: BTreeMap<B, Amount>,
+    highest_bidder: Option<B>,
+    highest_bid: Amount,
+}
+
+impl<B: Ord + Copy> SimAuction<B> {
+    pub fn new(increment: Amount, reserve: Amount) -> Self {
+        Self {
+            increment,
+            reserve,
+            bids: BTreeMap::new(),
+            proxy_max: BTreeMap::new(),
+            highest_bidder: None,
+            highest_bid: Amount::zero(),
+        }
+    }
+
+    pub fn highest_bid(&self) -> Amount {
+        self.highest_bid
+    }
+
+    pub fn highest_bidder(&self) -> Option<B> {
+        self.highest_bidder
+    }
+
+    /// Total amount the bidder has sent to the auction.
+    pub fn deposit(&self, bidder: &B) -> Amount {
+        match self.proxy_max.get(bidder) {
+            Some(max) => *max,
+            None => self.bids.get(bidder).copied().unwrap_or_else(Amount::zero),
+        }
+    }
+
+    /// Sum of deposits of all bidders.
+    pub fn total_deposits(&self) -> u128 {
+        self.bids
+            .keys()
+            .map(|bidder| self.deposit(bidder).micro_ccd as u128)
+            .sum()
+    }
+
+    fn add_deposit(&self, bidder: &B, amount: Amount) -> ContractResult<Amount> {
+        self.deposit(bidder)
+            .micro_ccd
+            .checked_add(amount.micro_ccd)
+            .map(Amount::from_micro_ccd)
+            .ok_or_else(|| CustomContractError::Overflow.into())
+    }
+
+    /// Add amount to the total bid of the bidder.
+    pub fn bid(&mut self, bidder: B, amount: Amount) -> ContractResult<()> {
+        let total = self.add_deposit(&bidder, amount)?;
+        ensure!(
+            total > self.highest_bid,
+            CustomContractError::BidTooLow.into()
+        );
+        self.proxy_max.remove(&bidder);
+        self.bids.insert(bidder, total);
+        self.challenge(bidder, total, false);
+        Ok(())
+    }
+
+    /// Add amount to the maximum the bidder is willing to pay.
+    pub fn proxy_bid(&mut self, bidder: B, amount: Amount) -> ContractResult<()> {
+        let max = self.add_deposit(&bidder, amount)?;
+        ensure!(
+            max > self.highest_bid,
+            CustomContractError::BidTooLow.into()
+        );
+        self.proxy_max.insert(bidder, max);
+        self.bids.entry(bidder).or_insert_with(Amount::zero);
+        if self.highest_bidder != Some(bidder) {
+            self.challenge(bidder, max, true);
+        } else if max.min(self.reserve) > self.highest_bid {
+            // Leader's bid is raised towards the reserve
+            self.highest_bid = max.min(self.reserve);
+            self.bids.insert(bidder, self.highest_bid);
+        }
+        Ok(())
+    }
+
+    fn challenge(&mut self, challenger: B, challenger_max: Amount, challenger_is_proxy: bool) {
+        let leader = self.highest_bidder.filter(|leader| *leader != challenger);
+        let outcome = resolve_challenge(
+            leader.map(|leader| self.deposit(&leader)),
+            self.highest_bid,
+            challenger_max,
+            challenger_is_proxy,
+            self.increment,
+            self.reserve,
+        );
+
+        self.bids.insert(challenger, outcome.challenger_bid);
+        if let (Some(leader), Some(bid)) = (leader, outcome.leader_bid) {
+            self.bids.insert(leader, bid);
+        }
+        if outcome.challenger_leads {
+            self.highest_bidder = Some(challenger);
+        }
+        self.highest_bid = outcome.highest_bid;
+    }
+
+    /// Settle the auction, returning deposits of all bidders except the winning bid. There is no winner if the reserve
+    /// was not met.
+    pub fn settle(&self) -> ContractResult<Settlement<B>> {
+        let winner = self
+            .highest_bidder
+            .filter(|_| self.highest_bid >= self.reserve);
+        let mut refunds = Vec::new();
+        for bidder in self.bids.keys() {
+            let is_winner = winner == Some(*bidder);
+            let refund = settlement_refund(self.deposit(bidder), self.highest_bid, is_winner)?;
+            if refund > Amount::zero() {
+                refunds.push((*bidder, refund));
+            }
+        }
+        Ok(Settlement {
+            winner: winner.map(|winner| (winner, self.highest_bid)),
+            refunds,
+        })
+    }
+}
+
+#[concordium_cfg_test]
+mod tests {
+    use super::*;
+
+    /// Deterministic pseudo-random numbers, so failing scenarios can be reproduced.
+    struct Lcg(u64);
+
+    impl Lcg {
+        fn next(&mut self, bound: u64) -> u64 {
+            self.0 = self
+                .0
+                .wrapping_mul(6364136223846793005)
+                .wrapping_add(1442695040888963407);
+            (self.0 >> 33) % bound
+        }
+    }
+
+    #[concordium_test]
+    fn test_ledger_conserves_supply() {
+        let mut rng = Lcg(42);
+        let mut ledger = SimLedger::<u8, u8>::default();
+        claim_eq!(ledger.mint(0, 0, ContractTokenAmount::from(1_000)), Ok(()));
+        claim_eq!(
+            ledger.mint(0, 1, ContractTokenAmount::from(1)),
+            Err(CustomContractError::TokenIdAlreadyExists.into())
+        );
+
+        let mut burned = 0;
+        for _ in 0..5_000 {
+            let from = rng.next(8) as u8;
+            let to = rng.next(8) as u8;
+            let amount = ContractTokenAmount::from(rng.next(50));
+            let balance = ledger.balance(&0, &from);
+            if rng.next(10) == 0 {
+                let result = ledger.burn(&0, &from, amount);
+                claim_eq!(result.is_ok(), balance >= amount);
+                if result.is_ok() {
+                    burned += amount.0 as u128;
+                }
+            } else {
+                let result = ledger.transfer(&0, &from, &to, amount);
+                claim_eq!(result.is_ok(), balance >= amount);
+            }
+            claim_eq!(ledger.supply(&0) + burned, 1_000);
+        }
+    }
+
+    #[concordium_test]
+    fn test_auction_settlement_balances() {
+        let mut rng = Lcg(7);
+        for _ in 0..100 {
+            let mut auction = SimAuction::<u8>::new(
+                Amount::from_micro_ccd(rng.next(3) + 1),
+                Amount::from_micro_ccd(rng.next(1_500)),
+            );
+            let mut received = 0u128;
+            for _ in 0..50 {
+                let bidder = rng.next(5) as u8;
+                let amount = Amount::from_micro_ccd(rng.next(1_000));
+                let result = if rng.next(2) == 0 {
+                    auction.bid(bidder, amount)
+                } else {
+                    auction.proxy_bid(bidder, amount)
+                };
+                if result.is_ok() {
+                    received += amount.micro_ccd as u128;
+                }
+                claim_eq!(auction.total_deposits(), received);
+                if let Some(leader) = auction.highest_bidder() {
+                    claim!(auction.deposit(&leader) >= auction.highest_bid());
+                }
+            }
+
+            // Everything received is either refunded or paid as the winning bid
+            let settlement = auction.settle().expect_report("Settlement failed");
+            let refunded: u128 = settlement
+                .refunds
+                .iter()
+                .map(|(_, amount)| amount.micro_ccd as u128)
+                .sum();
+            let paid = settlement
+                .winner
+                .map_or(0, |(_, bid)| bid.micro_ccd as u128);
+            claim_eq!(refunded + paid, received);
+        }
+    }
+}
@@ -67,6 +67,39 @@ pub trait HostStorageExt<S>: HasHost<S> {
         Ok(())
     }
 
+    fn storage_upsert<K: Serial, V: Serial>(
+        &mut self,
+        contract: &ContractAddress,
+        prefix: &ByteSlice,
+        key: &K,
+        value: &V,
+    ) -> Result<(), CallContractError<Self::ReturnValueType>> {
+        let params = serial_single_entry(prefix, key, value);
+
+        self.invoke_contract_raw(
+            contract,
+            Parameter(params.as_slice()),
+            EntrypointName::new_unchecked("upsert"),
+            Amount::zero(),
+        )?;
+
+        Ok(())
+    }
+
+    fn storage_upsert_raw(
+        &mut self,
+        contract: &ContractAddress,
+        entries: &StorageEntriesRef,
+    ) -> Result<(), CallContractError<Self::ReturnValueType>> {
+        self.invoke_contract(
+            contract,
+            entries,
+            EntrypointName::new_unchecked("upsert"),
+            Amount::zero(),
+        )?;
+        Ok(())
+    }
+
     fn storage_remove<K: Serial>(
         &mut self,
         contract: &ContractAddress,
@@ -144,6 +177,24 @@ pub trait HostStorageExt<S>: HasHost<S> {
             Err(e) => Err(ContractReadError::Call(e)),
         }
     }
+
+    fn storage_get_page(
+        &self,
+        contract: &ContractAddress,
+        params: &StoragePageParams,
+    ) -> Result<Option<StoragePage>, ContractReadError<Self::ReturnValueType>> {
+        match self.invoke_contract_read_only(
+            contract,
+            params,
+            EntrypointName::new_unchecked("getPage"),
+            Amount::zero(),
+        ) {
+            Ok(Some(mut val)) => <Option<StoragePage>>::deserial(&mut val)
+                .map_err(|_| ContractReadError::Compatibility),
+            Ok(None) => Err(ContractReadError::Compatibility),
+            Err(e) => Err(ContractReadError::Call(e)),
+        }
+    }
 }
 
 pub(crate) fn serial_single_entry<K: Serial, V: Serial>(
@@ -353,6 +404,7 @@ mod tests {
                     key: Bytes::from([4, 5, 6]),
                     value: Some(Bytes(to_bytes(&Bytes::from([7, 8, 9])))),
                 }],
+                expired: false,
             })),
         );
 
@@ -364,4 +416,37 @@ mod tests {
             Err(e) => fail!("Get should not fail: {:?}", e),
         }
     }
+    #[concordium_test]
+    fn test_send_get_page() {
+        let mut host = TestHost::new(TestState(), TestStateBuilder::new());
+
+        let page = StoragePage {
+            prefix: Bytes::from([1, 2, 3]),
+            entries: vec![StorageEntry {
+                key: Bytes::from([4, 5, 6]),
+                value: Bytes::from([7, 8, 9]),
+            }],
+            next_key: Some(Bytes::from([4, 5, 6])),
+            expired: false,
+        };
+        host.setup_mock_entrypoint(
+            STORAGE_ADDR,
+            OwnedEntrypointName::new_unchecked(String::from("getPage")),
+            parse_and_ok_mock::<StoragePageParams, _>(Some(page.clone())),
+        );
+
+        let result = host.storage_get_page(
+            &STORAGE_ADDR,
+            &StoragePageParams {
+                prefix: Bytes::from([1, 2, 3]),
+                start_after_key: None,
+                limit: 1,
+            },
+        );
+
+        match result {
+            Ok(res) => claim_eq!(res, Some(page)),
+            Err(e) => fail!("Get page should not fail: {:?}", e),
+        }
+    }
 }
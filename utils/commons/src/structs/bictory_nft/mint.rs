@@ -1,7 +1,7 @@
 use super::*;
 
 /// Minting Data.
-#[derive(Serialize, SchemaType, Clone, Eq, PartialEq)]
+#[derive(Debug, Serial, SchemaType, Clone, Eq, PartialEq)]
 pub struct MintData<T: IsTokenId> {
     /// TokenId to mint
     pub token_id: T,
@@ -17,4 +17,69 @@ pub struct MintData<T: IsTokenId> {
     pub cid: Vec<u8>,
     /// Copies of NFT
     pub quantity: ContractTokenAmount,
+    /// Additional royalty recipients, e.g. collaborators of the creator. Together with the creator and minter
+    /// royalties they must sum up to at most 100%
+    pub royalties: RoyaltyRecipients,
+}
+
+/// Parameters encoded before royalty recipients were added end after `quantity`, so missing `royalties` decode as no
+/// recipients.
+impl<T: IsTokenId> Deserial for MintData<T> {
+    fn deserial<R: Read>(source: &mut R) -> ParseResult<Self> {
+        let token_id = source.get()?;
+        let owner = source.get()?;
+        let creator = source.get()?;
+        let creator_royalty = source.get()?;
+        let minter_royalty = source.get()?;
+        let cid = source.get()?;
+        let quantity = source.get()?;
+        let royalties = match source.read_u32() {
+            Ok(len) => {
+                ensure!(
+                    len as usize <= MAX_ROYALTY_RECIPIENTS,
+                    ParseError::default()
+                );
+                let mut royalties = RoyaltyRecipients::new();
+                for _ in 0..len {
+                    royalties
+                        .push(source.get()?)
+                        .map_err(|_| ParseError::default())?;
+                }
+                royalties
+            }
+            Err(_) => RoyaltyRecipients::new(),
+        };
+        Ok(Self {
+            token_id,
+            owner,
+            creator,
+            creator_royalty,
+            minter_royalty,
+            cid,
+            quantity,
+            royalties,
+        })
+    }
+}
+
+/// Parameter type for the `mint` function of BictoryNFT, the common `MintParams` followed by the optional transferable
+/// flag. Tokens are transferable if the flag is `None`, which can also be omitted, so callers encoding `MintParams` keep
+/// working.
+#[derive(Serial, SchemaType, Clone)]
+pub struct MintTokenParams {
+    pub mint: MintParams,
+    /// Soulbound tokens can't be transferred, only burned
+    pub transferable: Option<bool>,
+}
+
+impl Deserial for MintTokenParams {
+    fn deserial<R: Read>(source: &mut R) -> ParseResult<Self> {
+        let mint = MintParams::deserial(source)?;
+        let transferable = match source.read_u8() {
+            Ok(0) | Err(_) => None,
+            Ok(1) => Some(bool::deserial(source)?),
+            Ok(_) => return Err(ParseError::default()),
+        };
+        Ok(Self { mint, transferable })
+    }
 }
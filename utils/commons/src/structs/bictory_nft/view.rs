@@ -16,6 +16,30 @@ pub struct NFTData {
     pub cid: Vec<u8>,
     /// Copies of NFT
     pub quantity: ContractTokenAmount,
+    /// Additional royalty recipients
+    pub royalties: RoyaltyRecipients,
+}
+
+impl NFTData {
+    /// Royalties of the creator and minter followed by the additional recipients. Royalties of contract addresses
+    /// are not included, since royalties are paid to accounts.
+    pub fn royalty_recipients(&self) -> Vec<Royalty> {
+        let shares = [
+            (self.creator, self.creator_royalty),
+            (self.minter, self.minter_royalty),
+        ];
+        let mut royalties = Vec::with_capacity(shares.len() + self.royalties.len());
+        for (address, royalty) in shares {
+            if let Address::Account(beneficiary) = address {
+                royalties.push(Royalty {
+                    beneficiary,
+                    percentage: Percentage::from_micro_percent(royalty as u64),
+                });
+            }
+        }
+        royalties.extend(self.royalties.iter().cloned());
+        royalties
+    }
 }
 
 #[derive(Serialize, SchemaType)]
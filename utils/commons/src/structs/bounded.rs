@@ -0,0 +1,110 @@
+use super::*;
+
+use core::convert::TryFrom;
+use core::ops::Deref;
+
+/// List with a fixed maximum capacity of `N` elements. Serialized the same way as `Vec`, but deserialization fails if
+/// the encoded list is longer than `N`, so bounded data can't grow state or parameter size indefinitely.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BoundedVec<T, const N: usize>(Vec<T>);
+
+impl<T, const N: usize> BoundedVec<T, N> {
+    pub fn new() -> Self {
+        Self(Vec::new())
+    }
+
+    /// Maximum number of elements the list can hold.
+    pub fn capacity() -> usize {
+        N
+    }
+
+    pub fn is_full(&self) -> bool {
+        self.0.len() >= N
+    }
+
+    /// Append an element to the list. Fails if the list is full.
+    pub fn push(&mut self, item: T) -> Result<(), CustomContractError> {
+        ensure!(!self.is_full(), CustomContractError::CapacityExceeded);
+        self.0.push(item);
+        Ok(())
+    }
+
+    pub fn into_inner(self) -> Vec<T> {
+        self.0
+    }
+}
+
+impl<T, const N: usize> Default for BoundedVec<T, N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T, const N: usize> Deref for BoundedVec<T, N> {
+    type Target = [T];
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl<T, const N: usize> TryFrom<Vec<T>> for BoundedVec<T, N> {
+    type Error = CustomContractError;
+
+    fn try_from(items: Vec<T>) -> Result<Self, Self::Error> {
+        ensure!(items.len() <= N, CustomContractError::CapacityExceeded);
+        Ok(Self(items))
+    }
+}
+
+impl<T: Serial, const N: usize> Serial for BoundedVec<T, N> {
+    fn serial<W: Write>(&self, out: &mut W) -> Result<(), W::Err> {
+        self.0.serial(out)
+    }
+}
+
+impl<T: Deserial, const N: usize> Deserial for BoundedVec<T, N> {
+    fn deserial<R: Read>(source: &mut R) -> ParseResult<Self> {
+        let len: u32 = source.get()?;
+        // Check length before reading elements to avoid allocating for oversized input
+        ensure!(len as usize <= N, ParseError::default());
+        let mut items = Vec::with_capacity(len as usize);
+        for _ in 0..len {
+            items.push(source.get()?);
+        }
+        Ok(Self(items))
+    }
+}
+
+impl<T: SchemaType, const N: usize> SchemaType for BoundedVec<T, N> {
+    fn get_type() -> schema::Type {
+        <Vec<T>>::get_type()
+    }
+}
+
+#[concordium_cfg_test]
+mod tests {
+    use super::*;
+
+    #[concordium_test]
+    fn test_bounded_vec_capacity() {
+        let mut list = BoundedVec::<u8, 2>::new();
+        claim_eq!(list.push(1), Ok(()));
+        claim_eq!(list.push(2), Ok(()));
+        claim_eq!(list.push(3), Err(CustomContractError::CapacityExceeded));
+        claim_eq!(
+            BoundedVec::<u8, 2>::try_from(vec![1, 2, 3]),
+            Err(CustomContractError::CapacityExceeded)
+        );
+    }
+
+    #[concordium_test]
+    fn test_bounded_vec_deserial() {
+        let bytes = to_bytes(&vec![1u8, 2, 3]);
+        claim_eq!(
+            from_bytes::<BoundedVec<u8, 3>>(&bytes),
+            Ok(BoundedVec::try_from(vec![1u8, 2, 3]).unwrap())
+        );
+        claim!(from_bytes::<BoundedVec<u8, 2>>(&bytes).is_err());
+    }
+}
@@ -1,9 +1,12 @@
 use super::*;
 
 pub mod bictory_nft;
+mod bounded;
 mod cns;
 mod percentage;
 mod royaly_distribution_shares;
 mod storage;
 
-pub use self::{bictory_nft::*, cns::*, percentage::*, royaly_distribution_shares::*, storage::*};
+pub use self::{
+    bictory_nft::*, bounded::*, cns::*, percentage::*, royaly_distribution_shares::*, storage::*,
+};
@@ -1,15 +1,32 @@
 use super::*;
 
+/// Additional royalty recipients of a token, paid after the creator and minter.
+pub type RoyaltyRecipients = BoundedVec<Royalty, MAX_ROYALTY_RECIPIENTS>;
+
 #[derive(SchemaType, Serialize, Eq, PartialEq, PartialOrd, Ord, Debug)]
 pub struct Shares {
-    pub creator: Amount,
-    pub minter: Amount,
+    /// Payouts to the royalty recipients, in the order of the recipients
+    pub royalties: Vec<(AccountAddress, Amount)>,
     pub owner: Amount,
     pub bictory: Amount,
 }
 
 impl Shares {
-    pub fn adjust_owner_share(&mut self) {
-        self.owner -= self.creator + self.minter + self.bictory
+    /// Subtract the royalty payouts from the owner share. Results in an error if the payouts exceed the owner share.
+    pub fn adjust_owner_share(&mut self) -> ContractResult<()> {
+        let royalties = self
+            .royalties
+            .iter()
+            .try_fold(self.bictory.micro_ccd, |total, (_, amount)| {
+                total.checked_add(amount.micro_ccd)
+            })
+            .ok_or(CustomContractError::Overflow)?;
+        self.owner = self
+            .owner
+            .micro_ccd
+            .checked_sub(royalties)
+            .map(Amount::from_micro_ccd)
+            .ok_or(CustomContractError::InvalidRoyalty)?;
+        Ok(())
     }
 }